@@ -293,8 +293,9 @@ def prototype(gs_yaml, rnx_path, plot=True):
 
     import numpy as np
     import pandas as pd
-    from nyx_space.cosmic import Cosm, Orbit, Spacecraft
+    from nyx_space.cosmic import Almanac, Frame, Orbit, Spacecraft
     from nyx_space.mission_design import (
+        ExportCfg,
         TrajectoryLoader,
         SpacecraftDynamics,
         propagate,
@@ -305,16 +306,17 @@ def prototype(gs_yaml, rnx_path, plot=True):
     logging.basicConfig(format=FORMAT)
     logging.getLogger().setLevel(logging.INFO)
 
-    cosm = Cosm.de438()
-    eme2k = cosm.frame("EME2000")
-
     # Base path
     root = Path(__file__).joinpath("../../../../").resolve()
-    config_path = root.joinpath("./data/tests/config/")
     outpath = root.joinpath("output_data/")
 
-    # Load the dynamics
-    dynamics = SpacecraftDynamics.load_named(str(config_path.joinpath("dynamics.yaml")))
+    almanac = Almanac(str(root.joinpath("./data/pck08.pca"))).load(
+        str(root.joinpath("./data/de440s.bsp"))
+    )
+    eme2k = almanac.frame_info(Frame(399, 1))
+
+    # Point masses of the Sun, the Earth, and the Moon
+    dynamics = SpacecraftDynamics([10, 399, 301])
 
     for gs_name, msrs in gs_to_msrs.items():
         if len(msrs) < 100:
@@ -330,14 +332,14 @@ def prototype(gs_yaml, rnx_path, plot=True):
 
         for ta_deg in np.linspace(0, 360, 10):
             orbit = Orbit.from_keplerian(
-                sma_km=7349136.3e-3,
-                ecc=0.00117,
-                inc_deg=99.35,
-                raan_deg=108.48,
-                aop_deg=110.9766,
-                ta_deg=ta_deg,
-                epoch=ref_epoch,
-                frame=eme2k,
+                7349136.3e-3,
+                0.00117,
+                99.35,
+                108.48,
+                110.9766,
+                ta_deg,
+                ref_epoch,
+                eme2k,
             )
 
             # Build a spacecraft
@@ -349,14 +351,14 @@ def prototype(gs_yaml, rnx_path, plot=True):
 
             # Propagate this trajectory for one day
             # An propagate for two periods (we only care about the trajectory)
-            _, traj = propagate(sc, dynamics["hifi"], Unit.Day * 1)
+            _, traj = propagate(sc, dynamics, almanac, Unit.Day * 1)
             print(traj)
 
             # This is a bit of a pain but at the moment, we must serialize the trajectory to a file
             # and reload it into a Dynamic trajectory before passing it to the Python object.
 
             traj_file = str(outpath.joinpath("./od_val_with_arc_truth_ephem.parquet"))
-            traj.to_parquet(traj_file)
+            traj.to_parquet(traj_file, almanac)
             traj = TrajectoryLoader(traj_file)
 
             # Build a tracking arc
@@ -364,8 +366,8 @@ def prototype(gs_yaml, rnx_path, plot=True):
             # Generate the measurements
             path = arc_sim.generate_measurements(
                 str(outpath.joinpath(f"./from_{ref_station.name}-ta_{ta_deg}.parquet")),
-                timestamp=False,
-                metadata={},
+                ExportCfg(timestamp=False, metadata={}),
+                almanac,
             )
             # Load this parquet and print the head
             df = pd.read_parquet(path)
@@ -0,0 +1,99 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use anise::constants::frames::SUN_J2000;
+use anise::prelude::Orbit;
+use snafu::ResultExt;
+
+use super::{AstroAlmanacSnafu, AstroError};
+use crate::linalg::{Matrix3, Vector3};
+use nalgebra::{Rotation3, UnitQuaternion};
+
+/// Pointing modes of a spacecraft. In each mode, the +Z axis of the body frame is aligned with the primary direction, and the +X axis
+/// is in the plane of the primary and secondary directions, towards the secondary direction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointingMode {
+    /// +Z towards the center of the central body, +X towards the velocity (i.e. the LVLH frame for circular orbits)
+    Nadir,
+    /// +Z towards the Sun, +X towards the nadir
+    SunPointing,
+    /// +Z along the velocity, +X towards the nadir
+    VelocityAligned,
+}
+
+/// Computes the attitude of a spacecraft in the provided pointing mode, as the quaternion rotating a vector from the body frame
+/// into the frame of the orbit, e.g. the body +Z axis is rotated onto the nadir direction for nadir pointing.
+///
+/// The almanac is only needed for Sun pointing, to compute the direction of the Sun from the spacecraft.
+pub fn from_mode(
+    orbit: Orbit,
+    almanac: &Almanac,
+    mode: PointingMode,
+) -> Result<UnitQuaternion<f64>, AstroError> {
+    let nadir = -orbit.radius_km;
+    let (primary, secondary) = match mode {
+        PointingMode::Nadir => (nadir, orbit.velocity_km_s),
+        PointingMode::SunPointing => {
+            // Position of the spacecraft with respect to the Sun, in the same orientation as the orbit.
+            let sun_to_sc = almanac
+                .transform_to(
+                    orbit,
+                    SUN_J2000.with_orient(orbit.frame.orientation_id),
+                    None,
+                )
+                .context(AstroAlmanacSnafu)?;
+            (-sun_to_sc.radius_km, nadir)
+        }
+        PointingMode::VelocityAligned => (orbit.velocity_km_s, nadir),
+    };
+
+    body_to_inertial(primary, secondary)
+}
+
+/// Builds the rotation from the body frame to the inertial frame, with the body +Z along the primary direction and the body +X
+/// in the plane of both directions.
+fn body_to_inertial(
+    primary: Vector3<f64>,
+    secondary: Vector3<f64>,
+) -> Result<UnitQuaternion<f64>, AstroError> {
+    let z_axis = primary
+        .try_normalize(f64::EPSILON)
+        .ok_or(AstroError::UndefinedPointing)?;
+
+    // Remove the component of the secondary direction along the primary one. If both are colinear, any perpendicular works.
+    let x_axis = (secondary - secondary.dot(&z_axis) * z_axis)
+        .try_normalize(1e-12 * secondary.norm())
+        .unwrap_or_else(|| {
+            let other = if z_axis.x.abs() < 0.9 {
+                Vector3::x()
+            } else {
+                Vector3::y()
+            };
+            (other - other.dot(&z_axis) * z_axis).normalize()
+        });
+
+    let y_axis = z_axis.cross(&x_axis);
+
+    // The columns of the DCM are the body axes expressed in the inertial frame.
+    let dcm = Matrix3::from_columns(&[x_axis, y_axis, z_axis]);
+
+    Ok(UnitQuaternion::from_rotation_matrix(
+        &Rotation3::from_matrix_unchecked(dcm),
+    ))
+}
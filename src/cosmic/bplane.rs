@@ -186,6 +186,19 @@ impl BPlane {
     }
 }
 
+/// Computes the B-Plane of a hyperbolic orbit, e.g. `orbit.b_plane()?.b_dot_r()`.
+pub trait OrbitBPlane {
+    /// Returns the B-Plane (B·R, B·T and linearized time of flight) of the incoming asymptote of this orbit about the center of its frame.
+    /// Errors with `NotHyperbolic` if the orbit is bound (elliptical) or parabolic.
+    fn b_plane(&self) -> Result<BPlane, AstroError>;
+}
+
+impl OrbitBPlane for Orbit {
+    fn b_plane(&self) -> Result<BPlane, AstroError> {
+        BPlane::new(*self)
+    }
+}
+
 #[cfg_attr(feature = "python", pymethods)]
 impl BPlane {
     pub fn b_dot_t(&self) -> f64 {
@@ -23,9 +23,12 @@ use anise::errors::{AlmanacError, AlmanacResult, EphemerisSnafu};
 use snafu::ResultExt;
 
 pub use super::{Frame, Orbit, Spacecraft};
+use crate::dynamics::SpacecraftDynamics;
 use crate::errors::{EventAlmanacSnafu, EventError};
 use crate::md::EventEvaluator;
-use crate::time::{Duration, Unit};
+use crate::propagators::{ErrorCtrl, PropagationError, Propagator};
+use crate::time::{Duration, Epoch, Unit};
+use crate::State;
 use std::cmp::{Eq, Ord, Ordering, PartialOrd};
 use std::convert::Into;
 use std::fmt;
@@ -152,6 +155,20 @@ impl EclipseLocator {
         }
     }
 
+    /// Creates a new eclipse locator with the Sun as the light source and the provided shadow bodies, whose shapes are fetched from the almanac.
+    ///
+    /// The observer may be centered on any of the shadow bodies. For a spacecraft orbiting a moon, provide both the moon
+    /// and its parent planet (e.g. Europa and Jupiter) such that the eclipses by either body are accounted for.
+    pub fn new(shadow_bodies: Vec<Frame>, almanac: &Almanac) -> AlmanacResult<Self> {
+        Ok(Self {
+            light_source: almanac.frame_from_uid(SUN_J2000)?,
+            shadow_bodies: shadow_bodies
+                .into_iter()
+                .map(|body| almanac.frame_from_uid(body))
+                .collect::<AlmanacResult<Vec<Frame>>>()?,
+        })
+    }
+
     /// Compute the visibility/eclipse between an observer and an observed state
     pub fn compute(&self, observer: Orbit, almanac: Arc<Almanac>) -> AlmanacResult<EclipseState> {
         let mut state = EclipseState::Visibilis;
@@ -164,6 +181,73 @@ impl EclipseLocator {
         Ok(state)
     }
 
+    /// Finds the next change of eclipse state (between visibility, penumbra and umbra) of the provided spacecraft within the search window,
+    /// and returns its epoch (to within a millisecond) and the eclipse state right after that change, or None if the eclipse state
+    /// does not change within the search window.
+    ///
+    /// The spacecraft is propagated forward step by step, and the change is refined by bisection in the step where it occurs.
+    /// This is cheaper than finding all of the eclipses of a trajectory, but an eclipse shorter than a propagator step may be missed,
+    /// so the maximum step of the propagator should be small compared to the duration of the eclipses.
+    pub fn next_transition<'a, E: ErrorCtrl>(
+        &self,
+        state: Spacecraft,
+        prop: &'a Propagator<'a, SpacecraftDynamics, E>,
+        search_window: Duration,
+        almanac: Arc<Almanac>,
+    ) -> Result<Option<(Epoch, EclipseState)>, PropagationError> {
+        let eclipse_of = |sc: &Spacecraft| -> Result<EclipseState, PropagationError> {
+            self.compute(sc.orbit, almanac.clone())
+                .context(EventAlmanacSnafu)
+                .map_err(|source| PropagationError::TrajectoryEventError { source })
+        };
+        let changed = |eclipse: &EclipseState, from: &EclipseState| {
+            std::mem::discriminant(eclipse) != std::mem::discriminant(from)
+        };
+
+        let start_eclipse = eclipse_of(&state)?;
+        let end_epoch = state.epoch() + search_window;
+
+        let mut instance = prop.with(state, almanac.clone()).quiet();
+        let mut prev_state = state;
+        while prev_state.epoch() < end_epoch {
+            instance.single_step()?;
+            let next_state = instance.state;
+            let next_eclipse = eclipse_of(&next_state)?;
+
+            if changed(&next_eclipse, &start_eclipse) {
+                // Bisection between the previous state (before the change) and the duration from it after which the eclipse state changed
+                let mut lo_s = 0.0;
+                let mut hi_s = (next_state.epoch() - prev_state.epoch()).to_seconds();
+                let mut hi = (next_state, next_eclipse);
+                while hi_s - lo_s > 1e-3 {
+                    let mid_s = 0.5 * (lo_s + hi_s);
+                    let mid_state = prop
+                        .with(prev_state, almanac.clone())
+                        .quiet()
+                        .for_duration(mid_s * Unit::Second)?;
+                    let mid_eclipse = eclipse_of(&mid_state)?;
+                    if changed(&mid_eclipse, &start_eclipse) {
+                        hi_s = mid_s;
+                        hi = (mid_state, mid_eclipse);
+                    } else {
+                        lo_s = mid_s;
+                    }
+                }
+
+                let (transition_state, transition_eclipse) = hi;
+                return Ok(if transition_state.epoch() <= end_epoch {
+                    Some((transition_state.epoch(), transition_eclipse))
+                } else {
+                    None
+                });
+            }
+
+            prev_state = next_state;
+        }
+
+        Ok(None)
+    }
+
     /// Creates an umbra event from this eclipse locator.
     /// Evaluation of the event, returns 0.0 for umbra, 1.0 for visibility (no shadow) and some value in between for penumbra
     pub fn to_umbra_event(&self) -> UmbraEvent {
@@ -267,6 +351,86 @@ impl EventEvaluator<Spacecraft> for PenumbraEvent {
     }
 }
 
+/// An event to find the epochs at which the visible fraction of the light source crosses a threshold, e.g. the umbra entries and exits.
+///
+/// The evaluation is the visible fraction (0.0 in umbra, 1.0 in full visibility) minus the threshold, so it is negative when darker than the threshold.
+/// As the threshold is strictly between 0 and 1, the evaluation never vanishes where the fraction is constant (in umbra or in full visibility),
+/// which prevents the event finder from reporting spurious events there.
+#[derive(Clone)]
+pub struct EclipseEvent {
+    e_loc: EclipseLocator,
+    /// Visible fraction of the light source at which this event occurs, strictly between 0 and 1.
+    pub fraction: f64,
+}
+
+impl EclipseEvent {
+    /// Creates an event at which the visible fraction of the light source crosses the provided fraction.
+    ///
+    /// # Panics
+    /// If the fraction is not strictly between 0 and 1.
+    pub fn new(fraction: f64, e_loc: &EclipseLocator) -> Self {
+        assert!(
+            fraction > 0.0 && fraction < 1.0,
+            "eclipse event fraction must be strictly between 0 and 1, got {fraction}"
+        );
+        Self {
+            e_loc: e_loc.clone(),
+            fraction,
+        }
+    }
+
+    /// Creates an event at the umbra entries (falling edges) and exits (rising edges), i.e. more than 98% in shadow.
+    pub fn umbra(e_loc: &EclipseLocator) -> Self {
+        Self::new(0.02, e_loc)
+    }
+
+    /// Creates an event at the penumbra entries (falling edges) and exits (rising edges), i.e. more than 2% in shadow.
+    pub fn penumbra(e_loc: &EclipseLocator) -> Self {
+        Self::new(0.98, e_loc)
+    }
+}
+
+impl fmt::Display for EclipseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "eclipse event at {:.1}% visibility {}",
+            self.fraction * 100.0,
+            self.e_loc
+        )
+    }
+}
+
+impl EventEvaluator<Spacecraft> for EclipseEvent {
+    fn eval(&self, sc: &Spacecraft, almanac: Arc<Almanac>) -> Result<f64, EventError> {
+        let visible: f64 = self
+            .e_loc
+            .compute(sc.orbit, almanac)
+            .context(EventAlmanacSnafu)?
+            .into();
+        Ok(visible - self.fraction)
+    }
+
+    /// Stop searching when the time has converged to less than 0.1 seconds
+    fn epoch_precision(&self) -> Duration {
+        0.1 * Unit::Second
+    }
+
+    /// A tenth of the distance between the threshold and the closest plateau, such that the plateaus are never within the precision
+    fn value_precision(&self) -> f64 {
+        0.1 * self.fraction.min(1.0 - self.fraction)
+    }
+
+    fn eval_string(&self, state: &Spacecraft, almanac: Arc<Almanac>) -> Result<String, EventError> {
+        Ok(format!(
+            "{}",
+            self.e_loc
+                .compute(state.orbit, almanac)
+                .context(EventAlmanacSnafu)?
+        ))
+    }
+}
+
 /// Computes the umbra/visibilis/penumbra state between between two states accounting for eclipsing of the providing geoid.
 pub fn eclipse_state(
     observer: Orbit,
@@ -286,7 +450,7 @@ pub fn eclipse_state(
     if eclipsing_body.mean_equatorial_radius_km().is_err() {
         eclipsing_body =
             almanac
-                .frame_from_uid(light_source)
+                .frame_from_uid(eclipsing_body)
                 .map_err(|e| AlmanacError::GenericError {
                     err: format!("{e} when fetching eclipsing body data ({eclipsing_body})"),
                 })?;
@@ -0,0 +1,171 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::prelude::{Frame, Orbit};
+use nalgebra::Vector3;
+use snafu::{ensure, ResultExt};
+
+use super::{AstroError, AstroPhysicsSnafu, RetrogradeEquatorialSnafu};
+use crate::time::Epoch;
+
+/// Modified equinoctial elements of an orbit, cf. Walker, Ireland and Owens (1985), "A set of modified equinoctial orbit elements".
+///
+/// Contrary to the Keplerian elements, these are not singular for circular or equatorial orbits, only for retrograde equatorial orbits.
+/// With the Keplerian elements, these are:
+/// + f = e cos(ω + Ω) and g = e sin(ω + Ω), the components of the eccentricity vector in the equinoctial frame;
+/// + h = tan(i/2) cos(Ω) and k = tan(i/2) sin(Ω), the components of the node vector in the equinoctial frame;
+/// + L = Ω + ω + ν, the true longitude, in degrees.
+///
+/// The semi-major axis is used as the first element instead of the semi-latus rectum, so parabolic orbits are not supported.
+pub trait Equinoctial: Sized {
+    /// Builds an orbit from its semi-major axis in km, the modified equinoctial elements f, g, h and k, and its true longitude in degrees.
+    #[allow(clippy::too_many_arguments)]
+    fn from_equinoctial(
+        sma_km: f64,
+        f: f64,
+        g: f64,
+        h: f64,
+        k: f64,
+        true_longitude_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> Result<Self, AstroError>;
+
+    /// Returns the equinoctial f element, i.e. the component of the eccentricity vector along the equinoctial f axis.
+    fn equinoctial_f(&self) -> Result<f64, AstroError>;
+
+    /// Returns the equinoctial g element, i.e. the component of the eccentricity vector along the equinoctial g axis.
+    fn equinoctial_g(&self) -> Result<f64, AstroError>;
+
+    /// Returns the equinoctial h element, i.e. tan(i/2) cos(Ω).
+    fn equinoctial_h(&self) -> Result<f64, AstroError>;
+
+    /// Returns the equinoctial k element, i.e. tan(i/2) sin(Ω).
+    fn equinoctial_k(&self) -> Result<f64, AstroError>;
+
+    /// Returns the true longitude in degrees between 0 and 360, i.e. Ω + ω + ν, computed without using the Keplerian elements.
+    fn true_longitude(&self) -> Result<f64, AstroError>;
+}
+
+impl Equinoctial for Orbit {
+    fn from_equinoctial(
+        sma_km: f64,
+        f: f64,
+        g: f64,
+        h: f64,
+        k: f64,
+        true_longitude_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> Result<Self, AstroError> {
+        let mu_km3_s2 = frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+
+        let p_km = sma_km * (1.0 - f.powi(2) - g.powi(2));
+        let (sin_l, cos_l) = true_longitude_deg.to_radians().sin_cos();
+
+        let alpha2 = h.powi(2) - k.powi(2);
+        let s2 = 1.0 + h.powi(2) + k.powi(2);
+        let w = 1.0 + f * cos_l + g * sin_l;
+        let r_km = p_km / w;
+        let sqrt_mu_p = (mu_km3_s2 / p_km).sqrt();
+
+        let radius_km = (r_km / s2)
+            * Vector3::new(
+                cos_l + alpha2 * cos_l + 2.0 * h * k * sin_l,
+                sin_l - alpha2 * sin_l + 2.0 * h * k * cos_l,
+                2.0 * (h * sin_l - k * cos_l),
+            );
+
+        let velocity_km_s = (-sqrt_mu_p / s2)
+            * Vector3::new(
+                sin_l + alpha2 * sin_l - 2.0 * h * k * cos_l + g - 2.0 * f * h * k + alpha2 * g,
+                -cos_l + alpha2 * cos_l + 2.0 * h * k * sin_l - f + 2.0 * g * h * k + alpha2 * f,
+                -2.0 * (h * cos_l + k * sin_l + f * h + g * k),
+            );
+
+        Ok(Orbit::new(
+            radius_km.x,
+            radius_km.y,
+            radius_km.z,
+            velocity_km_s.x,
+            velocity_km_s.y,
+            velocity_km_s.z,
+            epoch,
+            frame,
+        ))
+    }
+
+    fn equinoctial_f(&self) -> Result<f64, AstroError> {
+        let (f_hat, _) = equinoctial_basis(self)?;
+        Ok(eccentricity_vector(self)?.dot(&f_hat))
+    }
+
+    fn equinoctial_g(&self) -> Result<f64, AstroError> {
+        let (_, g_hat) = equinoctial_basis(self)?;
+        Ok(eccentricity_vector(self)?.dot(&g_hat))
+    }
+
+    fn equinoctial_h(&self) -> Result<f64, AstroError> {
+        let h_hat = orbit_normal(self)?;
+        Ok(-h_hat.y / (1.0 + h_hat.z))
+    }
+
+    fn equinoctial_k(&self) -> Result<f64, AstroError> {
+        let h_hat = orbit_normal(self)?;
+        Ok(h_hat.x / (1.0 + h_hat.z))
+    }
+
+    fn true_longitude(&self) -> Result<f64, AstroError> {
+        let (f_hat, g_hat) = equinoctial_basis(self)?;
+        Ok(self
+            .radius_km
+            .dot(&g_hat)
+            .atan2(self.radius_km.dot(&f_hat))
+            .to_degrees()
+            .rem_euclid(360.0))
+    }
+}
+
+/// Unit vector of the orbit angular momentum, which must not be anti-aligned with the Z axis of the frame.
+fn orbit_normal(orbit: &Orbit) -> Result<Vector3<f64>, AstroError> {
+    let h_hat = orbit.radius_km.cross(&orbit.velocity_km_s).normalize();
+    ensure!(1.0 + h_hat.z > f64::EPSILON, RetrogradeEquatorialSnafu);
+    Ok(h_hat)
+}
+
+/// Unit vectors of the f and g axes of the equinoctial frame.
+fn equinoctial_basis(orbit: &Orbit) -> Result<(Vector3<f64>, Vector3<f64>), AstroError> {
+    let h_hat = orbit_normal(orbit)?;
+    let h = -h_hat.y / (1.0 + h_hat.z);
+    let k = h_hat.x / (1.0 + h_hat.z);
+    let s2 = 1.0 + h.powi(2) + k.powi(2);
+
+    let f_hat = Vector3::new(1.0 - k.powi(2) + h.powi(2), 2.0 * h * k, -2.0 * k) / s2;
+    let g_hat = Vector3::new(2.0 * h * k, 1.0 + k.powi(2) - h.powi(2), 2.0 * h) / s2;
+
+    Ok((f_hat, g_hat))
+}
+
+/// Eccentricity vector of the orbit, pointing to the periapsis.
+fn eccentricity_vector(orbit: &Orbit) -> Result<Vector3<f64>, AstroError> {
+    let mu_km3_s2 = orbit.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+    let r = orbit.radius_km;
+    let v = orbit.velocity_km_s;
+
+    Ok(((v.norm_squared() - mu_km3_s2 / r.norm()) * r - r.dot(&v) * v) / mu_km3_s2)
+}
@@ -0,0 +1,95 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use anise::prelude::{Frame, Orbit};
+use snafu::ResultExt;
+
+use super::{AstroAlmanacSnafu, AstroError, AstroPhysicsSnafu};
+
+/// Geodetic coordinates of the sub-satellite point of an orbit, computed in a body fixed frame at the epoch of the orbit.
+///
+/// The orbit may be in any frame known to the Almanac: it is first rotated into the body fixed frame, whose shape is fetched from the Almanac.
+/// The geodetic latitude and height are then iterated from the flattening of that shape.
+pub trait Geodetic {
+    /// Returns the geodetic latitude in degrees, between -90 and 90 degrees.
+    fn geodetic_latitude_deg(
+        &self,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<f64, AstroError>;
+
+    /// Returns the geodetic longitude in degrees, between -180 and 180 degrees.
+    fn geodetic_longitude_deg(
+        &self,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<f64, AstroError>;
+
+    /// Returns the geodetic height above the ellipsoid of the body, in km.
+    fn geodetic_height_km(
+        &self,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<f64, AstroError>;
+}
+
+impl Geodetic for Orbit {
+    fn geodetic_latitude_deg(
+        &self,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<f64, AstroError> {
+        to_body_fixed(self, body_fixed_frame, almanac)?
+            .latitude_deg()
+            .context(AstroPhysicsSnafu)
+    }
+
+    fn geodetic_longitude_deg(
+        &self,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<f64, AstroError> {
+        let longitude_deg = to_body_fixed(self, body_fixed_frame, almanac)?.longitude_deg();
+        Ok((longitude_deg + 180.0).rem_euclid(360.0) - 180.0)
+    }
+
+    fn geodetic_height_km(
+        &self,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<f64, AstroError> {
+        to_body_fixed(self, body_fixed_frame, almanac)?
+            .height_km()
+            .context(AstroPhysicsSnafu)
+    }
+}
+
+/// Rotates the orbit into the body fixed frame loaded from the Almanac, so that its shape is set.
+fn to_body_fixed(
+    orbit: &Orbit,
+    body_fixed_frame: Frame,
+    almanac: &Almanac,
+) -> Result<Orbit, AstroError> {
+    let body_fixed_frame = almanac
+        .frame_from_uid(body_fixed_frame)
+        .context(AstroAlmanacSnafu)?;
+    almanac
+        .transform_to(*orbit, body_fixed_frame, None)
+        .context(AstroAlmanacSnafu)
+}
@@ -0,0 +1,182 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::prelude::{Frame, Orbit};
+
+use super::Spacecraft;
+use crate::time::{Duration, Epoch};
+use std::fmt;
+
+/// A hashable key identifying a state by its frame (central body and orientation) and its epoch.
+///
+/// The position and velocity are _not_ part of the key, so two states of the same frame at the same
+/// epoch share the same key. The epoch is canonicalized to TAI, so the same instant expressed in
+/// different time scales also leads to the same key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StateKey {
+    /// NAIF ID of the central body of the frame
+    pub ephemeris_id: i32,
+    /// NAIF ID of the orientation of the frame
+    pub orientation_id: i32,
+    centuries: i16,
+    nanoseconds: u64,
+}
+
+impl StateKey {
+    /// Builds the key for a state in the provided frame at the provided epoch.
+    pub fn new(frame: Frame, epoch: Epoch) -> Self {
+        let (centuries, nanoseconds) = epoch.to_tai_duration().to_parts();
+        Self {
+            ephemeris_id: frame.ephemeris_id,
+            orientation_id: frame.orientation_id,
+            centuries,
+            nanoseconds,
+        }
+    }
+
+    /// Epoch of this key, in the TAI time scale.
+    pub fn epoch(&self) -> Epoch {
+        Epoch::from_tai_duration(Duration::from_parts(self.centuries, self.nanoseconds))
+    }
+
+    /// Stable identifier of the frame of this key, made of the ephemeris ID in the upper 32 bits and of the orientation ID in the lower 32 bits.
+    pub fn frame_id(&self) -> u64 {
+        frame_id(&self.frame_ref())
+    }
+
+    /// Stable identifier of this key.
+    ///
+    /// Unlike the `Hash` implementation (whose result depends on the hasher), this is a 64-bit FNV-1a digest of
+    /// the frame and epoch, and it is therefore stable across executions and platforms.
+    pub fn id(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        self.ephemeris_id
+            .to_le_bytes()
+            .iter()
+            .chain(self.orientation_id.to_le_bytes().iter())
+            .chain(self.centuries.to_le_bytes().iter())
+            .chain(self.nanoseconds.to_le_bytes().iter())
+            .fold(FNV_OFFSET, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    fn frame_ref(&self) -> Frame {
+        Frame::new(self.ephemeris_id, self.orientation_id)
+    }
+}
+
+impl fmt::Display for StateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} @ {}", self.frame_ref(), self.epoch())
+    }
+}
+
+impl From<Orbit> for StateKey {
+    fn from(orbit: Orbit) -> Self {
+        Self::new(orbit.frame, orbit.epoch)
+    }
+}
+
+impl From<&Orbit> for StateKey {
+    fn from(orbit: &Orbit) -> Self {
+        Self::new(orbit.frame, orbit.epoch)
+    }
+}
+
+impl From<Spacecraft> for StateKey {
+    fn from(sc: Spacecraft) -> Self {
+        Self::from(sc.orbit)
+    }
+}
+
+impl From<&Spacecraft> for StateKey {
+    fn from(sc: &Spacecraft) -> Self {
+        Self::from(sc.orbit)
+    }
+}
+
+/// Stable identifier of a frame, made of the ephemeris ID in the upper 32 bits and of the orientation ID in the lower 32 bits.
+/// The gravitational parameter and shape of the frame are not part of this identifier.
+pub fn frame_id(frame: &Frame) -> u64 {
+    (u64::from(frame.ephemeris_id as u32) << 32) | u64::from(frame.orientation_id as u32)
+}
+
+#[cfg(test)]
+mod ut_key {
+    use super::*;
+    use crate::time::{TimeScale, Unit};
+    use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+    use std::collections::HashMap;
+
+    #[test]
+    fn state_key_map() {
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+        let mut states = HashMap::new();
+        for (i, frame) in [EARTH_J2000, MOON_J2000].iter().enumerate() {
+            for hour in 0..5 {
+                let orbit = Orbit::new(
+                    7000.0 + i as f64,
+                    hour as f64,
+                    0.0,
+                    0.0,
+                    7.5,
+                    0.0,
+                    epoch + hour * Unit::Hour,
+                    *frame,
+                );
+                assert!(states.insert(StateKey::from(orbit), orbit).is_none());
+            }
+        }
+        assert_eq!(states.len(), 10);
+
+        // Retrieve a state from a key built without any state.
+        let key = StateKey::new(MOON_J2000, epoch + 3 * Unit::Hour);
+        let moon_orbit = states[&key];
+        assert_eq!(moon_orbit.frame, MOON_J2000);
+        assert_eq!(moon_orbit.epoch, epoch + 3 * Unit::Hour);
+        assert_eq!(key.epoch(), epoch + 3 * Unit::Hour);
+
+        // The position and velocity are not part of the key.
+        let other = Orbit::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, epoch, EARTH_J2000);
+        assert!(states.contains_key(&StateKey::from(&other)));
+
+        // The same instant in another time scale leads to the same key.
+        let epoch_utc = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+        let epoch_utc_as_gps = epoch_utc.to_time_scale(TimeScale::GPST);
+        assert_eq!(
+            StateKey::new(EARTH_J2000, epoch_utc),
+            StateKey::new(EARTH_J2000, epoch_utc_as_gps)
+        );
+
+        // The IDs are stable and discriminating.
+        assert_eq!(
+            key.id(),
+            StateKey::new(MOON_J2000, epoch + 3 * Unit::Hour).id()
+        );
+        assert_ne!(
+            key.id(),
+            StateKey::new(EARTH_J2000, epoch + 3 * Unit::Hour).id()
+        );
+        assert_eq!(frame_id(&EARTH_J2000), (399 << 32) | 1);
+        assert_eq!(key.frame_id(), frame_id(&MOON_J2000));
+    }
+}
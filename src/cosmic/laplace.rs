@@ -0,0 +1,168 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use anise::prelude::{Frame, Orbit};
+use snafu::ResultExt;
+
+use super::{AstroAlmanacSnafu, AstroError, AstroPhysicsSnafu};
+use crate::linalg::Vector3;
+
+/// Unnormalized J<sub>2</sub> of the Moon, from the GRAIL gravity field.
+pub const MOON_J2: f64 = 2.032_366_1e-4;
+
+/// The Laplace plane of an orbit about an oblate body perturbed by a third body, i.e. the plane about which the orbital plane precesses.
+///
+/// Close to the central body, the J<sub>2</sub> perturbation dominates and the Laplace plane is the equator of the central body. Far from it,
+/// the third body dominates and the Laplace plane tends to the orbital plane of the perturber.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LaplacePlane {
+    /// Unit normal of the Laplace plane, in the inertial frame of the orbit
+    pub normal: Vector3<f64>,
+    /// Angle between the Laplace plane and the equator of the central body, in degrees
+    pub tilt_deg: f64,
+    /// Angle between the equator of the central body and the orbital plane of the perturber, in degrees
+    pub obliquity_deg: f64,
+    /// Laplace radius, in km, at which the J<sub>2</sub> and third body torques on the orbital plane are comparable
+    pub laplace_radius_km: f64,
+}
+
+/// Computes the Laplace radius in km, $r_L^5 = J_2 R^2 a_p^3 (1 - e_p^2)^{3/2} \mu / \mu_p$, where $a_p$ and $e_p$ are the semi-major axis
+/// and eccentricity of the orbit of the perturber about the central body, of gravitational parameters $\mu_p$ and $\mu$ respectively.
+///
+/// The frame of the perturber orbit must be centered on the central body and have its gravitational parameter and shape set.
+pub fn laplace_radius_km(
+    perturber: &Orbit,
+    perturber_mu_km3_s2: f64,
+    j2: f64,
+) -> Result<f64, AstroError> {
+    let mu_km3_s2 = perturber.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+    let eq_radius_km = perturber
+        .frame
+        .mean_equatorial_radius_km()
+        .context(AstroPhysicsSnafu)?;
+    let sma_km = perturber.sma_km().context(AstroPhysicsSnafu)?;
+    let ecc = perturber.ecc().context(AstroPhysicsSnafu)?;
+
+    Ok(
+        (j2 * eq_radius_km.powi(2) * sma_km.powi(3) * (1.0 - ecc.powi(2)).powf(1.5) * mu_km3_s2
+            / perturber_mu_km3_s2)
+            .powf(0.2),
+    )
+}
+
+/// Computes the Laplace plane at the provided semi-major axis about a central body whose pole (i.e. the normal to its equator) is provided
+/// in the inertial frame of the perturber orbit.
+///
+/// The tilt φ of the Laplace plane from the equator satisfies tan 2φ = sin 2ε / (cos 2ε + (r_L / a)^5), where ε is the obliquity of the
+/// perturber orbit with respect to the equator (Tremaine, Touma & Namouni, 2009).
+pub fn laplace_plane(
+    sma_km: f64,
+    pole: Vector3<f64>,
+    perturber: &Orbit,
+    perturber_mu_km3_s2: f64,
+    j2: f64,
+) -> Result<LaplacePlane, AstroError> {
+    let laplace_radius_km = laplace_radius_km(perturber, perturber_mu_km3_s2, j2)?;
+
+    let pole = pole.normalize();
+    let perturber_normal = perturber
+        .radius_km
+        .cross(&perturber.velocity_km_s)
+        .normalize();
+
+    let obliquity_rad = pole.dot(&perturber_normal).clamp(-1.0, 1.0).acos();
+    let tilt_rad = 0.5
+        * (2.0 * obliquity_rad)
+            .sin()
+            .atan2((2.0 * obliquity_rad).cos() + (laplace_radius_km / sma_km).powi(5));
+
+    // Rotate the pole towards the normal of the perturber orbit, in the plane of both vectors.
+    let normal = if obliquity_rad.sin().abs() < f64::EPSILON {
+        pole
+    } else {
+        ((obliquity_rad - tilt_rad).sin() * pole + tilt_rad.sin() * perturber_normal)
+            / obliquity_rad.sin()
+    };
+
+    Ok(LaplacePlane {
+        normal,
+        tilt_deg: tilt_rad.to_degrees(),
+        obliquity_deg: obliquity_rad.to_degrees(),
+        laplace_radius_km,
+    })
+}
+
+/// Computes the Laplace plane of the provided orbit, whose frame must be an inertial frame centered on the central body.
+///
+/// The pole of the central body is the Z axis of the provided body fixed frame, and the perturber is the body with the provided NAIF ID,
+/// both at the epoch of the orbit. The gravitational parameters and the shape of the central body are fetched from the Almanac.
+pub fn body_laplace_plane(
+    almanac: &Almanac,
+    orbit: &Orbit,
+    body_fixed_frame: Frame,
+    j2: f64,
+    perturber_id: i32,
+) -> Result<LaplacePlane, AstroError> {
+    let central_frame = almanac
+        .frame_from_uid(orbit.frame)
+        .context(AstroAlmanacSnafu)?;
+    let perturber_frame = almanac
+        .frame_from_uid(Frame::from_ephem_j2000(perturber_id))
+        .context(AstroAlmanacSnafu)?;
+    let perturber_mu_km3_s2 = perturber_frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+
+    let pole = almanac
+        .transform_to(
+            Orbit::cartesian(0.0, 0.0, 1.0, 0.0, 0.0, 0.0, orbit.epoch, body_fixed_frame),
+            central_frame,
+            None,
+        )
+        .context(AstroAlmanacSnafu)?
+        .radius_km;
+
+    let mut perturber = almanac
+        .transform(perturber_frame, central_frame, orbit.epoch, None)
+        .context(AstroAlmanacSnafu)?;
+    // Ensure that the orbit uses the gravitational data of the central body
+    perturber.frame = central_frame;
+
+    laplace_plane(
+        orbit.sma_km().context(AstroPhysicsSnafu)?,
+        pole,
+        &perturber,
+        perturber_mu_km3_s2,
+        j2,
+    )
+}
+
+/// Returns the circular orbit of the same semi-major axis in the provided Laplace plane, whose orbital plane is frozen under the secular
+/// effects of J<sub>2</sub> and of the perturber. The orbit starts at the projection of the position of the provided orbit onto the Laplace plane.
+pub fn laplace_frozen_orbit(orbit: &Orbit, plane: &LaplacePlane) -> Result<Orbit, AstroError> {
+    let mu_km3_s2 = orbit.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+    let sma_km = orbit.sma_km().context(AstroPhysicsSnafu)?;
+
+    let normal = plane.normal.normalize();
+    let r_hat = (orbit.radius_km - orbit.radius_km.dot(&normal) * normal).normalize();
+
+    let mut frozen = *orbit;
+    frozen.radius_km = sma_km * r_hat;
+    frozen.velocity_km_s = (mu_km3_s2 / sma_km).sqrt() * normal.cross(&r_hat);
+
+    Ok(frozen)
+}
@@ -0,0 +1,538 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::prelude::{Frame, Orbit};
+use snafu::{ensure, ResultExt};
+use std::f64::consts::{PI, TAU};
+use std::fmt;
+
+use super::{AstroError, AstroPhysicsSnafu, CriticalInclinationSnafu, NotEllipticalSnafu};
+use crate::time::Epoch;
+
+/// Maximum number of iterations to invert the periodic terms
+const MAX_ITERATIONS: usize = 25;
+
+/// Smallest value of |1 - 5 cos<sup>2</sup>(i)| for which the long-periodic terms are computed, i.e. about 0.15 degrees around the critical inclination
+const CRITICAL_INCLINATION_TOL: f64 = 1e-2;
+
+/// Kozai-Izsak mean Keplerian elements of a J<sub>2</sub> perturbed orbit.
+///
+/// These are the osculating elements from which the first order short-periodic J<sub>2</sub> terms have been removed.
+/// Contrary to the Brouwer-Lyddane mean elements (cf. [BrouwerMeanElements]), the long-periodic terms are _not_ removed, so these elements are defined at the critical inclination.
+/// Mean elements evolve linearly with time under the first order secular J<sub>2</sub> rates, cf. `at_epoch`.
+///
+/// All angles are in degrees, and the eccentricity must be less than one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KozaiMeanElements {
+    /// Mean semi-major axis in km
+    pub sma_km: f64,
+    /// Mean eccentricity
+    pub ecc: f64,
+    /// Mean inclination in degrees
+    pub inc_deg: f64,
+    /// Mean right ascension of the ascending node in degrees
+    pub raan_deg: f64,
+    /// Mean argument of periapsis in degrees
+    pub aop_deg: f64,
+    /// Mean mean anomaly in degrees
+    pub ma_deg: f64,
+    /// Epoch of these elements
+    pub epoch: Epoch,
+    /// Frame of these elements, which must have its gravitational parameter and shape set
+    pub frame: Frame,
+    /// Unnormalized J<sub>2</sub> used for the short-periodic terms and the secular rates (e.g. [crate::io::gravity::EARTH_J2_JGM3])
+    pub j2: f64,
+}
+
+impl KozaiMeanElements {
+    /// Computes the mean elements of the provided osculating orbit, by iteratively removing the short-periodic J<sub>2</sub> terms.
+    pub fn from_osculating(orbit: &Orbit, j2: f64) -> Result<Self, AstroError> {
+        let osc = osculating_radians(orbit)?;
+
+        let eq_radius_km = orbit
+            .frame
+            .mean_equatorial_radius_km()
+            .context(AstroPhysicsSnafu)?;
+
+        let mean = remove_periodic(osc, j2, eq_radius_km, false);
+
+        Ok(Self::from_radians(mean, orbit.epoch, orbit.frame, j2))
+    }
+
+    /// Returns the osculating orbit of these mean elements, by adding the short-periodic J<sub>2</sub> terms.
+    pub fn to_osculating(&self) -> Result<Orbit, AstroError> {
+        let eq_radius_km = self
+            .frame
+            .mean_equatorial_radius_km()
+            .context(AstroPhysicsSnafu)?;
+
+        let osc = add_periodic(self.to_radians(), self.j2, eq_radius_km, false);
+
+        orbit_from_radians(osc, self.epoch, self.frame)
+    }
+
+    /// Analytically propagates these mean elements to the provided epoch using the first order secular J<sub>2</sub> rates
+    /// of the node, of the argument of periapsis, and of the mean anomaly.
+    pub fn at_epoch(&self, epoch: Epoch) -> Result<Self, AstroError> {
+        let mu_km3_s2 = self.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+        let eq_radius_km = self
+            .frame
+            .mean_equatorial_radius_km()
+            .context(AstroPhysicsSnafu)?;
+
+        let dt_s = (epoch - self.epoch).to_seconds();
+
+        let eta = (1.0 - self.ecc.powi(2)).sqrt();
+        let p_km = self.sma_km * eta.powi(2);
+        let mean_motion_rad_s = (mu_km3_s2 / self.sma_km.powi(3)).sqrt();
+        let factor = mean_motion_rad_s * self.j2 * (eq_radius_km / p_km).powi(2);
+        let cos_inc = self.inc_deg.to_radians().cos();
+
+        let raan_rate_rad_s = -1.5 * factor * cos_inc;
+        let aop_rate_rad_s = 0.75 * factor * (5.0 * cos_inc.powi(2) - 1.0);
+        let ma_rate_rad_s = mean_motion_rad_s + 0.75 * factor * eta * (3.0 * cos_inc.powi(2) - 1.0);
+
+        let mut me = *self;
+        me.epoch = epoch;
+        me.raan_deg = (self.raan_deg + (raan_rate_rad_s * dt_s).to_degrees()).rem_euclid(360.0);
+        me.aop_deg = (self.aop_deg + (aop_rate_rad_s * dt_s).to_degrees()).rem_euclid(360.0);
+        me.ma_deg = (self.ma_deg + (ma_rate_rad_s * dt_s).to_degrees()).rem_euclid(360.0);
+
+        Ok(me)
+    }
+
+    fn from_radians(elements: [f64; 6], epoch: Epoch, frame: Frame, j2: f64) -> Self {
+        Self {
+            sma_km: elements[0],
+            ecc: elements[1],
+            inc_deg: elements[2].to_degrees(),
+            raan_deg: elements[3].rem_euclid(TAU).to_degrees(),
+            aop_deg: elements[4].rem_euclid(TAU).to_degrees(),
+            ma_deg: elements[5].rem_euclid(TAU).to_degrees(),
+            epoch,
+            frame,
+            j2,
+        }
+    }
+
+    fn to_radians(self) -> [f64; 6] {
+        [
+            self.sma_km,
+            self.ecc,
+            self.inc_deg.to_radians(),
+            self.raan_deg.to_radians(),
+            self.aop_deg.to_radians(),
+            self.ma_deg.to_radians(),
+        ]
+    }
+}
+
+impl fmt::Display for KozaiMeanElements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{:x}] {}\tKozai mean elements: sma = {:.6} km\tecc = {:.6}\tinc = {:.6} deg\traan = {:.6} deg\taop = {:.6} deg\tma = {:.6} deg",
+            self.frame,
+            self.epoch,
+            self.sma_km,
+            self.ecc,
+            self.inc_deg,
+            self.raan_deg,
+            self.aop_deg,
+            self.ma_deg
+        )
+    }
+}
+
+/// Brouwer-Lyddane mean Keplerian elements of a J<sub>2</sub> perturbed orbit.
+///
+/// These are the osculating elements from which both the first order short-periodic and long-periodic J<sub>2</sub> terms have been removed,
+/// such that they are directly comparable to the mean elements of analytical theories built on Brouwer's (e.g. those of a two-line element set).
+/// The long-periodic terms are singular at the critical inclination (about 63.43 degrees and its supplement), where these elements are not defined.
+///
+/// All angles are in degrees, and the eccentricity must be less than one. Use [BrouwerLyddane] to convert them to and from osculating orbits.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BrouwerMeanElements {
+    /// Mean semi-major axis in km
+    pub sma_km: f64,
+    /// Mean eccentricity
+    pub ecc: f64,
+    /// Mean inclination in degrees
+    pub inc_deg: f64,
+    /// Mean right ascension of the ascending node in degrees
+    pub raan_deg: f64,
+    /// Mean argument of periapsis in degrees
+    pub aop_deg: f64,
+    /// Mean mean anomaly in degrees
+    pub ma_deg: f64,
+    /// Epoch of these elements
+    pub epoch: Epoch,
+    /// Frame of these elements, which must have its gravitational parameter and shape set
+    pub frame: Frame,
+    /// Unnormalized J<sub>2</sub> used for the periodic terms (e.g. [crate::io::gravity::EARTH_J2_JGM3])
+    pub j2: f64,
+}
+
+impl BrouwerMeanElements {
+    fn to_radians(self) -> [f64; 6] {
+        [
+            self.sma_km,
+            self.ecc,
+            self.inc_deg.to_radians(),
+            self.raan_deg.to_radians(),
+            self.aop_deg.to_radians(),
+            self.ma_deg.to_radians(),
+        ]
+    }
+}
+
+impl fmt::Display for BrouwerMeanElements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{:x}] {}\tBrouwer mean elements: sma = {:.6} km\tecc = {:.6}\tinc = {:.6} deg\traan = {:.6} deg\taop = {:.6} deg\tma = {:.6} deg",
+            self.frame,
+            self.epoch,
+            self.sma_km,
+            self.ecc,
+            self.inc_deg,
+            self.raan_deg,
+            self.aop_deg,
+            self.ma_deg
+        )
+    }
+}
+
+/// Conversion of osculating orbits to and from their first order Brouwer-Lyddane mean elements.
+///
+/// The periodic terms are those of Brouwer's theory, recombined following Lyddane's modification such that they remain defined
+/// for small eccentricities and small inclinations. The central body is that of the frame of the orbit, whose gravitational parameter
+/// and mean equatorial radius must be set.
+///
+/// Sources:
+/// + Brouwer, D., "Solution of the problem of artificial satellite theory without drag", The Astronomical Journal, 1959;
+/// + Lyddane, R. H., "Small eccentricities or inclinations in the Brouwer theory of the artificial satellite", The Astronomical Journal, 1963;
+/// + Schaub, H. and Junkins, J. L., "Analytical Mechanics of Space Systems", Appendix F.
+pub trait BrouwerLyddane: Sized {
+    /// Computes the mean elements of this osculating orbit given the unnormalized J<sub>2</sub> of its central body, by iteratively removing the periodic terms.
+    fn to_mean_elements(&self, j2: f64) -> Result<BrouwerMeanElements, AstroError>;
+
+    /// Returns the osculating orbit of the provided mean elements, by adding the periodic terms.
+    fn from_mean_elements(mean: &BrouwerMeanElements) -> Result<Self, AstroError>;
+}
+
+impl BrouwerLyddane for Orbit {
+    fn to_mean_elements(&self, j2: f64) -> Result<BrouwerMeanElements, AstroError> {
+        let osc = osculating_radians(self)?;
+
+        let eq_radius_km = self
+            .frame
+            .mean_equatorial_radius_km()
+            .context(AstroPhysicsSnafu)?;
+
+        ensure!(!near_critical_inclination(osc[2]), CriticalInclinationSnafu);
+        let mean = remove_periodic(osc, j2, eq_radius_km, true);
+        ensure!(
+            !near_critical_inclination(mean[2]),
+            CriticalInclinationSnafu
+        );
+
+        Ok(BrouwerMeanElements {
+            sma_km: mean[0],
+            ecc: mean[1],
+            inc_deg: mean[2].to_degrees(),
+            raan_deg: mean[3].rem_euclid(TAU).to_degrees(),
+            aop_deg: mean[4].rem_euclid(TAU).to_degrees(),
+            ma_deg: mean[5].rem_euclid(TAU).to_degrees(),
+            epoch: self.epoch,
+            frame: self.frame,
+            j2,
+        })
+    }
+
+    fn from_mean_elements(mean: &BrouwerMeanElements) -> Result<Self, AstroError> {
+        ensure!(mean.ecc < 1.0, NotEllipticalSnafu);
+        ensure!(
+            !near_critical_inclination(mean.inc_deg.to_radians()),
+            CriticalInclinationSnafu
+        );
+
+        let eq_radius_km = mean
+            .frame
+            .mean_equatorial_radius_km()
+            .context(AstroPhysicsSnafu)?;
+
+        let osc = add_periodic(mean.to_radians(), mean.j2, eq_radius_km, true);
+
+        orbit_from_radians(osc, mean.epoch, mean.frame)
+    }
+}
+
+/// Returns the osculating Keplerian elements of the provided orbit (sma, ecc, inc, raan, aop, ma; angles in radians).
+fn osculating_radians(orbit: &Orbit) -> Result<[f64; 6], AstroError> {
+    let osc = [
+        orbit.sma_km().context(AstroPhysicsSnafu)?,
+        orbit.ecc().context(AstroPhysicsSnafu)?,
+        orbit.inc_deg().context(AstroPhysicsSnafu)?.to_radians(),
+        orbit.raan_deg().context(AstroPhysicsSnafu)?.to_radians(),
+        orbit.aop_deg().context(AstroPhysicsSnafu)?.to_radians(),
+        orbit.ma_deg().context(AstroPhysicsSnafu)?.to_radians(),
+    ];
+
+    ensure!(osc[1] < 1.0, NotEllipticalSnafu);
+
+    Ok(osc)
+}
+
+/// Builds the orbit of the provided Keplerian elements (sma, ecc, inc, raan, aop, ma; angles in radians).
+fn orbit_from_radians(elements: [f64; 6], epoch: Epoch, frame: Frame) -> Result<Orbit, AstroError> {
+    Orbit::try_keplerian(
+        elements[0],
+        elements[1],
+        elements[2].to_degrees(),
+        elements[3].rem_euclid(TAU).to_degrees(),
+        elements[4].rem_euclid(TAU).to_degrees(),
+        true_anomaly(elements[1], elements[5])
+            .rem_euclid(TAU)
+            .to_degrees(),
+        epoch,
+        frame,
+    )
+    .context(AstroPhysicsSnafu)
+}
+
+/// Returns whether the long-periodic terms are too large to be meaningful, i.e. whether 1 - 5 cos<sup>2</sup>(i) is close to zero.
+fn near_critical_inclination(inc: f64) -> bool {
+    (1.0 - 5.0 * inc.cos().powi(2)).abs() < CRITICAL_INCLINATION_TOL
+}
+
+/// Removes the periodic terms from the provided osculating elements, and returns the mean elements (same order and units as [add_periodic]).
+///
+/// This is a fixed point iteration: the mean elements are corrected until their osculating elements match the provided ones.
+/// The corrections are computed on nonsingular elements, such that near circular and near equatorial orbits are handled.
+fn remove_periodic(osc: [f64; 6], j2: f64, eq_radius_km: f64, long_periodic: bool) -> [f64; 6] {
+    let target = to_nonsingular(osc);
+    let mut mean = target;
+
+    for _ in 0..MAX_ITERATIONS {
+        let computed = to_nonsingular(add_periodic(
+            from_nonsingular(mean),
+            j2,
+            eq_radius_km,
+            long_periodic,
+        ));
+
+        let mut delta = [0.0; 6];
+        for (i, d) in delta.iter_mut().enumerate() {
+            *d = target[i] - computed[i];
+        }
+        delta[5] = wrap_pm_pi(delta[5]);
+
+        for (m, d) in mean.iter_mut().zip(delta) {
+            *m += d;
+        }
+
+        if delta[0].abs() < 1e-9 && delta[1..].iter().all(|d| d.abs() < 1e-14) {
+            break;
+        }
+    }
+
+    from_nonsingular(mean)
+}
+
+/// Converts Keplerian elements to nonsingular elements: the semi-major axis, the eccentricity vector (e cos(ϖ), e sin(ϖ)) where ϖ = Ω + ω,
+/// the node vector (sin(i/2) cos(Ω), sin(i/2) sin(Ω)), and the mean longitude λ = Ω + ω + M.
+fn to_nonsingular(elements: [f64; 6]) -> [f64; 6] {
+    let [sma_km, ecc, inc, raan, aop, ma] = elements;
+    let lon_peri = raan + aop;
+    let sin_half_inc = (0.5 * inc).sin();
+    [
+        sma_km,
+        ecc * lon_peri.cos(),
+        ecc * lon_peri.sin(),
+        sin_half_inc * raan.cos(),
+        sin_half_inc * raan.sin(),
+        lon_peri + ma,
+    ]
+}
+
+/// Converts the nonsingular elements of [to_nonsingular] back to Keplerian elements.
+fn from_nonsingular(elements: [f64; 6]) -> [f64; 6] {
+    let [sma_km, ex, ey, ix, iy, lambda] = elements;
+    let lon_peri = ey.atan2(ex);
+    let raan = iy.atan2(ix);
+    [
+        sma_km,
+        ex.hypot(ey),
+        2.0 * ix.hypot(iy).min(1.0).asin(),
+        raan,
+        lon_peri - raan,
+        lambda - lon_peri,
+    ]
+}
+
+/// Adds the first order periodic J<sub>2</sub> terms to the provided mean elements (sma, ecc, inc, raan, aop, ma; angles in radians),
+/// and returns the osculating elements in the same order.
+///
+/// The short-periodic terms are always added, and the long-periodic terms only if `long_periodic` is set. The terms are those of Brouwer's
+/// theory as written in Schaub and Junkins, Appendix F, and the eccentricity and mean anomaly, and the inclination and node, are recombined
+/// following Lyddane's modification such that small eccentricities and inclinations do not lead to singularities.
+/// The long-periodic terms are singular at the critical inclination, which must be checked by the caller.
+fn add_periodic(mean: [f64; 6], j2: f64, eq_radius_km: f64, long_periodic: bool) -> [f64; 6] {
+    let [sma_km, ecc, inc, raan, aop, ma] = mean;
+
+    let gamma2 = 0.5 * j2 * (eq_radius_km / sma_km).powi(2);
+    let eta = (1.0 - ecc.powi(2)).sqrt();
+    let gamma2p = gamma2 / eta.powi(4);
+
+    let ta = true_anomaly(ecc, ma);
+    let (sin_ta, cos_ta) = ta.sin_cos();
+    // Ratio of the semi-major axis to the radius
+    let a_r = (1.0 + ecc * cos_ta) / eta.powi(2);
+    let cos_inc = inc.cos();
+    let cos2_inc = cos_inc.powi(2);
+    let sin2_inc = 1.0 - cos2_inc;
+
+    let cos_2w_f = (2.0 * aop + ta).cos();
+    let cos_2w_2f = (2.0 * aop + 2.0 * ta).cos();
+    let cos_2w_3f = (2.0 * aop + 3.0 * ta).cos();
+    let sin_2w_f = (2.0 * aop + ta).sin();
+    let sin_2w_2f = (2.0 * aop + 2.0 * ta).sin();
+    let sin_2w_3f = (2.0 * aop + 3.0 * ta).sin();
+
+    // Equation of the center, plus the eccentricity term
+    let eoc = wrap_pm_pi(ta - ma) + ecc * sin_ta;
+    let periodic = 3.0 * sin_2w_2f + 3.0 * ecc * sin_2w_f + ecc * sin_2w_3f;
+
+    let osc_sma_km = sma_km
+        + sma_km
+            * gamma2
+            * ((3.0 * cos2_inc - 1.0) * (a_r.powi(3) - eta.powi(-3))
+                + 3.0 * sin2_inc * a_r.powi(3) * cos_2w_2f);
+
+    let ecc_terms = 3.0 * cos_ta + 3.0 * ecc * cos_ta.powi(2) + ecc.powi(2) * cos_ta.powi(3);
+    let d_ecc = 0.5
+        * eta.powi(2)
+        * (gamma2
+            * ((3.0 * cos2_inc - 1.0) / eta.powi(6) * (ecc * eta + ecc / (1.0 + eta) + ecc_terms)
+                + 3.0 * sin2_inc / eta.powi(6) * (ecc + ecc_terms) * cos_2w_2f)
+            - gamma2p * sin2_inc * (3.0 * cos_2w_f + cos_2w_3f));
+
+    let d_inc = 0.5
+        * gamma2p
+        * cos_inc
+        * sin2_inc.sqrt()
+        * (3.0 * cos_2w_2f + 3.0 * ecc * cos_2w_f + ecc * cos_2w_3f);
+
+    let d_raan = -0.5 * gamma2p * cos_inc * (6.0 * eoc - periodic);
+
+    // Mean longitude: sum of the mean anomaly, the argument of periapsis, and the node
+    let lambda = ma
+        + aop
+        + raan
+        + 0.25
+            * gamma2p
+            * (-6.0 * (1.0 - 5.0 * cos2_inc) * eoc + (3.0 - 5.0 * cos2_inc) * periodic)
+        + d_raan;
+
+    let a_r_terms = a_r.powi(2) * eta.powi(2) + a_r;
+    let ecc_d_ma = -0.25
+        * gamma2p
+        * eta.powi(3)
+        * (2.0 * (3.0 * cos2_inc - 1.0) * (a_r_terms + 1.0) * sin_ta
+            + 3.0
+                * sin2_inc
+                * ((1.0 - a_r_terms) * sin_2w_f + (a_r_terms + 1.0 / 3.0) * sin_2w_3f));
+
+    // The long-periodic terms only depend on the argument of periapsis. The factor 1 - 11 cos^2(i) - 40 cos^4(i) / (1 - 5 cos^2(i))
+    // is written as sin^2(i) (1 - 15 cos^2(i)) / (1 - 5 cos^2(i)), such that the inclination term, of Brouwer's form -e δe / (η^2 tan(i)),
+    // does not divide by the sine of the inclination.
+    let (d_ecc, d_inc, d_raan, lambda, ecc_d_ma) = if long_periodic {
+        let (sin_2w, cos_2w) = (2.0 * aop).sin_cos();
+        let crit = 1.0 - 5.0 * cos2_inc;
+        let incl_factor = (1.0 - 15.0 * cos2_inc) / crit;
+
+        let d_ecc_lp = 0.125 * gamma2p * ecc * eta.powi(2) * sin2_inc * incl_factor * cos_2w;
+        let d_inc_lp =
+            -0.125 * gamma2p * ecc.powi(2) * sin2_inc.sqrt() * cos_inc * incl_factor * cos_2w;
+        let d_raan_lp = -0.125
+            * gamma2p
+            * ecc.powi(2)
+            * cos_inc
+            * (11.0 + 80.0 * cos2_inc / crit + 200.0 * cos2_inc.powi(2) / crit.powi(2))
+            * sin_2w;
+        let d_lambda_lp = 0.125 * gamma2p * eta.powi(3) * sin2_inc * incl_factor * sin_2w
+            - gamma2p / 16.0
+                * (2.0 + ecc.powi(2)
+                    - 11.0 * (2.0 + 3.0 * ecc.powi(2)) * cos2_inc
+                    - 40.0 * (2.0 + 5.0 * ecc.powi(2)) * cos2_inc.powi(2) / crit
+                    - 400.0 * ecc.powi(2) * cos2_inc.powi(3) / crit.powi(2))
+                * sin_2w
+            + d_raan_lp;
+        let ecc_d_ma_lp = 0.125 * gamma2p * ecc * eta.powi(3) * sin2_inc * incl_factor * sin_2w;
+
+        (
+            d_ecc + d_ecc_lp,
+            d_inc + d_inc_lp,
+            d_raan + d_raan_lp,
+            lambda + d_lambda_lp,
+            ecc_d_ma + ecc_d_ma_lp,
+        )
+    } else {
+        (d_ecc, d_inc, d_raan, lambda, ecc_d_ma)
+    };
+
+    // Recombine the eccentricity and mean anomaly, and the inclination and node, to avoid the singularities at zero.
+    let (sin_ma, cos_ma) = ma.sin_cos();
+    let d1 = (ecc + d_ecc) * sin_ma + ecc_d_ma * cos_ma;
+    let d2 = (ecc + d_ecc) * cos_ma - ecc_d_ma * sin_ma;
+    let osc_ma = d1.atan2(d2);
+    let osc_ecc = d1.hypot(d2);
+
+    let (sin_half_inc, cos_half_inc) = (0.5 * inc).sin_cos();
+    let (sin_raan, cos_raan) = raan.sin_cos();
+    let d3 =
+        (sin_half_inc + 0.5 * cos_half_inc * d_inc) * sin_raan + sin_half_inc * d_raan * cos_raan;
+    let d4 =
+        (sin_half_inc + 0.5 * cos_half_inc * d_inc) * cos_raan - sin_half_inc * d_raan * sin_raan;
+    let osc_raan = d3.atan2(d4);
+    let osc_inc = 2.0 * d3.hypot(d4).min(1.0).asin();
+
+    let osc_aop = lambda - osc_ma - osc_raan;
+
+    [osc_sma_km, osc_ecc, osc_inc, osc_raan, osc_aop, osc_ma]
+}
+
+/// Solves Kepler's equation for an elliptical orbit and returns the true anomaly in radians.
+fn true_anomaly(ecc: f64, ma: f64) -> f64 {
+    let ma = wrap_pm_pi(ma);
+    let mut ea = if ecc < 0.8 { ma } else { PI.copysign(ma) };
+    for _ in 0..50 {
+        let delta = (ea - ecc * ea.sin() - ma) / (1.0 - ecc * ea.cos());
+        ea -= delta;
+        if delta.abs() < 1e-15 {
+            break;
+        }
+    }
+    2.0 * ((1.0 + ecc).sqrt() * (0.5 * ea).sin()).atan2((1.0 - ecc).sqrt() * (0.5 * ea).cos())
+}
+
+/// Wraps an angle in radians into [-pi, pi)
+fn wrap_pm_pi(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
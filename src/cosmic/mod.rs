@@ -134,6 +134,16 @@ pub enum AstroError {
     PartialsUndefined,
     #[snafu(display("Orbit is not hyperbolic so there is no hyperbolic anomaly."))]
     NotHyperbolic,
+    #[snafu(display("Orbit is not elliptical so there are no mean elements."))]
+    NotElliptical,
+    #[snafu(display("equinoctial elements are singular for retrograde equatorial orbits"))]
+    RetrogradeEquatorial,
+    #[snafu(display("Brouwer mean elements are singular at the critical inclination"))]
+    CriticalInclination,
+    #[snafu(display("pointing direction is undefined"))]
+    UndefinedPointing,
+    #[snafu(display("operation requires the {expected} frame"))]
+    UnexpectedFrame { expected: &'static str },
     #[snafu(display("physics error occured during astro computation: {source}"))]
     AstroPhysics { source: PhysicsError },
     #[snafu(display("ANISE Almanac error occured during astro computation: {source}"))]
@@ -155,6 +165,43 @@ pub use self::bplane::*;
 mod spacecraft;
 pub use self::spacecraft::*;
 
+// Re-Export the hashable state key
+mod key;
+pub use self::key::*;
+
+// Re-Export the orbit type classification
+mod orbit_type;
+pub use self::orbit_type::*;
+
+// Re-Export the equinoctial elements
+mod equinoctial;
+pub use self::equinoctial::*;
+
+// Re-Export the geodetic coordinates
+mod geodetic;
+pub use self::geodetic::*;
+
+/// The soi module computes the sphere of influence and Hill sphere radii of a body about its primary.
+pub mod soi;
+
+/// The laplace module computes the Laplace plane of orbits perturbed by both J<sub>2</sub> and a third body, and the orbits frozen in that plane.
+pub mod laplace;
+
+/// The precession module computes the J<sub>2</sub> secular precession rates and periods of the node and of the periapsis.
+pub mod precession;
+
+/// The mean_elements module converts osculating orbits to and from Kozai-Izsak and Brouwer-Lyddane mean elements, and propagates the former analytically under J<sub>2</sub>.
+pub mod mean_elements;
+
+/// The tle module parses NORAD two-line element sets and propagates them with SGP4/SDP4.
+pub mod tle;
+
+/// The attitude module computes the attitude of a spacecraft from its pointing mode (e.g. nadir or Sun pointing).
+pub mod attitude;
+
+/// The true_of_date module rotates Earth J2000 orbits to the true equator and equinox of date (IAU 1976 precession and IAU 1980 nutation).
+pub mod true_of_date;
+
 /// The eclipse module allows finding eclipses and (conversely) visibility between a state and another one (e.g. a planet or the Sun).
 pub mod eclipse;
 
@@ -167,3 +214,6 @@ pub const AU: f64 = 149_597_870.700;
 
 /// From NIST special publication 330, 2008 edition, in meters per second squared
 pub const STD_GRAVITY: f64 = 9.80665;
+
+/// Mean angular velocity of the Moon about its spin axis in degrees per second, i.e. one revolution per sidereal month of 27.321661 days.
+pub const MEAN_MOON_ANGULAR_VELOCITY_DEG_S: f64 = 360.0 / (27.321_661 * 86_400.0);
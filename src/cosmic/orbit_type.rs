@@ -0,0 +1,86 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::astro::orbit::ECC_EPSILON;
+use anise::prelude::Orbit;
+use snafu::ResultExt;
+use std::fmt;
+
+use super::{AstroError, AstroPhysicsSnafu};
+
+/// Orbits whose eccentricity is below this tolerance are circular, as for the singularities of the orbital elements in ANISE.
+pub const CIRCULAR_ECC_TOL: f64 = ECC_EPSILON;
+
+/// Orbits whose eccentricity is within this tolerance of one are parabolic.
+pub const PARABOLIC_ECC_TOL: f64 = 1e-6;
+
+/// Type of a conic section orbit, from its eccentricity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OrbitType {
+    /// Eccentricity below [CIRCULAR_ECC_TOL]
+    Circular,
+    /// Eccentricity between [CIRCULAR_ECC_TOL] and one minus [PARABOLIC_ECC_TOL]
+    Elliptical,
+    /// Eccentricity within [PARABOLIC_ECC_TOL] of one
+    Parabolic,
+    /// Eccentricity above one plus [PARABOLIC_ECC_TOL]
+    Hyperbolic,
+}
+
+impl OrbitType {
+    /// Classifies the orbit of the provided eccentricity.
+    pub fn from_ecc(ecc: f64) -> Self {
+        if ecc < CIRCULAR_ECC_TOL {
+            Self::Circular
+        } else if (ecc - 1.0).abs() <= PARABOLIC_ECC_TOL {
+            Self::Parabolic
+        } else if ecc < 1.0 {
+            Self::Elliptical
+        } else {
+            Self::Hyperbolic
+        }
+    }
+
+    /// Returns whether this orbit is bound (closed), i.e. circular or elliptical, which is when the period and apoapsis are defined.
+    pub fn is_bound(&self) -> bool {
+        matches!(self, Self::Circular | Self::Elliptical)
+    }
+}
+
+impl fmt::Display for OrbitType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Circular => write!(f, "circular"),
+            Self::Elliptical => write!(f, "elliptical"),
+            Self::Parabolic => write!(f, "parabolic"),
+            Self::Hyperbolic => write!(f, "hyperbolic"),
+        }
+    }
+}
+
+/// Allows classifying an orbit by its type.
+pub trait OrbitClassification {
+    /// Returns the type of this orbit from its eccentricity.
+    fn orbit_type(&self) -> Result<OrbitType, AstroError>;
+}
+
+impl OrbitClassification for Orbit {
+    fn orbit_type(&self) -> Result<OrbitType, AstroError> {
+        Ok(OrbitType::from_ecc(self.ecc().context(AstroPhysicsSnafu)?))
+    }
+}
@@ -0,0 +1,94 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::prelude::Orbit;
+use snafu::ResultExt;
+use std::f64::consts::TAU;
+
+use super::{AstroError, AstroPhysicsSnafu};
+use crate::time::{Duration, Unit};
+
+/// Critical inclination (prograde), in degrees, at which the J<sub>2</sub> apsidal precession vanishes, i.e. where 5 cos²(i) = 1.
+pub const CRITICAL_INCLINATION_DEG: f64 = 63.434_948_822_922;
+
+/// Orbits whose inclination is within this many degrees of the critical inclination are considered to have no apsidal precession.
+pub const CRITICAL_INCLINATION_TOL_DEG: f64 = 0.1;
+
+/// Returns the factor n J<sub>2</sub> (R/p)², in radians per second, common to the J<sub>2</sub> secular rates.
+fn j2_secular_factor(orbit: &Orbit, j2: f64) -> Result<f64, AstroError> {
+    let mu_km3_s2 = orbit.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+    let eq_radius_km = orbit
+        .frame
+        .mean_equatorial_radius_km()
+        .context(AstroPhysicsSnafu)?;
+    let sma_km = orbit.sma_km().context(AstroPhysicsSnafu)?;
+    let p_km = orbit.semi_parameter_km().context(AstroPhysicsSnafu)?;
+
+    let mean_motion_rad_s = (mu_km3_s2 / sma_km.powi(3)).sqrt();
+
+    Ok(mean_motion_rad_s * j2 * (eq_radius_km / p_km).powi(2))
+}
+
+/// Returns the secular rate of the right ascension of the ascending node due to J<sub>2</sub>, in radians per second.
+///
+/// The `j2` coefficient is the _unnormalized_ J<sub>2</sub> (e.g. [crate::io::gravity::EARTH_J2_JGM3]). The frame of the orbit must have its gravitational parameter and shape set.
+pub fn nodal_precession_rate_rad_s(orbit: &Orbit, j2: f64) -> Result<f64, AstroError> {
+    let factor = j2_secular_factor(orbit, j2)?;
+    let inc_rad = orbit.inc_deg().context(AstroPhysicsSnafu)?.to_radians();
+
+    Ok(-1.5 * factor * inc_rad.cos())
+}
+
+/// Returns the secular rate of the argument of periapsis due to J<sub>2</sub>, in radians per second.
+///
+/// The `j2` coefficient is the _unnormalized_ J<sub>2</sub> (e.g. [crate::io::gravity::EARTH_J2_JGM3]). The frame of the orbit must have its gravitational parameter and shape set.
+pub fn apsidal_precession_rate_rad_s(orbit: &Orbit, j2: f64) -> Result<f64, AstroError> {
+    let factor = j2_secular_factor(orbit, j2)?;
+    let inc_rad = orbit.inc_deg().context(AstroPhysicsSnafu)?.to_radians();
+
+    Ok(0.75 * factor * (5.0 * inc_rad.cos().powi(2) - 1.0))
+}
+
+/// Returns the time needed for the ascending node to precess by a full revolution due to J<sub>2</sub>,
+/// or None if the orbit is polar (no nodal precession).
+pub fn nodal_precession_period(orbit: &Orbit, j2: f64) -> Result<Option<Duration>, AstroError> {
+    Ok(period_from_rate(nodal_precession_rate_rad_s(orbit, j2)?))
+}
+
+/// Returns the time needed for the argument of periapsis to precess by a full revolution due to J<sub>2</sub>,
+/// or None if the orbit is within [CRITICAL_INCLINATION_TOL_DEG] of the critical inclination (prograde or retrograde),
+/// where the apsidal precession vanishes.
+pub fn apsidal_precession_period(orbit: &Orbit, j2: f64) -> Result<Option<Duration>, AstroError> {
+    let inc_deg = orbit.inc_deg().context(AstroPhysicsSnafu)?;
+    if (inc_deg - CRITICAL_INCLINATION_DEG).abs() < CRITICAL_INCLINATION_TOL_DEG
+        || (inc_deg - (180.0 - CRITICAL_INCLINATION_DEG)).abs() < CRITICAL_INCLINATION_TOL_DEG
+    {
+        return Ok(None);
+    }
+
+    Ok(period_from_rate(apsidal_precession_rate_rad_s(orbit, j2)?))
+}
+
+/// Returns the period of a full revolution at the provided rate, or None if the rate is zero.
+fn period_from_rate(rate_rad_s: f64) -> Option<Duration> {
+    if rate_rad_s.abs() < f64::EPSILON {
+        None
+    } else {
+        Some((TAU / rate_rad_s.abs()) * Unit::Second)
+    }
+}
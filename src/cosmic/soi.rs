@@ -0,0 +1,126 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use anise::prelude::{Frame, Orbit};
+use snafu::ResultExt;
+
+use super::{AstroAlmanacSnafu, AstroError, AstroPhysicsSnafu};
+use crate::time::Epoch;
+
+/// Computes the Laplace sphere of influence radius, in kilometers, of a secondary body of gravitational parameter `secondary_mu_km3_s2`
+/// whose orbit about the primary body is the provided orbit: $r_{SOI} = a (\mu_s / \mu_p)^{2/5}$.
+///
+/// The frame of the orbit must be centered on the primary body and have its gravitational parameter set.
+pub fn sphere_of_influence_km(orbit: &Orbit, secondary_mu_km3_s2: f64) -> Result<f64, AstroError> {
+    let primary_mu_km3_s2 = orbit.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+    let sma_km = orbit.sma_km().context(AstroPhysicsSnafu)?;
+
+    Ok(sma_km * (secondary_mu_km3_s2 / primary_mu_km3_s2).powf(0.4))
+}
+
+/// Computes the Hill sphere radius, in kilometers, of a secondary body of gravitational parameter `secondary_mu_km3_s2`
+/// whose orbit about the primary body is the provided orbit: $r_H = a (1 - e) (\mu_s / (3 \mu_p))^{1/3}$.
+///
+/// The frame of the orbit must be centered on the primary body and have its gravitational parameter set.
+pub fn hill_radius_km(orbit: &Orbit, secondary_mu_km3_s2: f64) -> Result<f64, AstroError> {
+    let primary_mu_km3_s2 = orbit.frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+    let sma_km = orbit.sma_km().context(AstroPhysicsSnafu)?;
+    let ecc = orbit.ecc().context(AstroPhysicsSnafu)?;
+
+    Ok(sma_km * (1.0 - ecc) * (secondary_mu_km3_s2 / (3.0 * primary_mu_km3_s2)).cbrt())
+}
+
+/// Computes the Laplace sphere of influence radius, in kilometers, of the body with the provided NAIF ID with respect to its parent body,
+/// using the osculating orbit of the body about its parent at the provided epoch.
+///
+/// Both gravitational parameters are fetched from the Almanac, which must also include the ephemeris of both bodies.
+pub fn body_sphere_of_influence_km(
+    almanac: &Almanac,
+    body_id: i32,
+    parent_id: i32,
+    epoch: Epoch,
+) -> Result<f64, AstroError> {
+    let body_frame = almanac
+        .frame_from_uid(Frame::from_ephem_j2000(body_id))
+        .context(AstroAlmanacSnafu)?;
+    let parent_frame = almanac
+        .frame_from_uid(Frame::from_ephem_j2000(parent_id))
+        .context(AstroAlmanacSnafu)?;
+
+    let body_mu_km3_s2 = body_frame.mu_km3_s2().context(AstroPhysicsSnafu)?;
+
+    let mut orbit = almanac
+        .transform(body_frame, parent_frame, epoch, None)
+        .context(AstroAlmanacSnafu)?;
+    // Ensure that the orbit uses the gravitational data of the parent
+    orbit.frame = parent_frame;
+
+    sphere_of_influence_km(&orbit, body_mu_km3_s2)
+}
+
+#[cfg(test)]
+mod ut_soi {
+    use super::*;
+    use crate::cosmic::AU;
+    use anise::constants::frames::{EARTH_J2000, SUN_J2000};
+
+    #[test]
+    fn earth_soi_and_hill() {
+        // Earth about the Sun, on a circular orbit of one AU
+        let sun_mu_km3_s2 = 132_712_440_041.939_38;
+        let earth_mu_km3_s2 = 398_600.435_436;
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+        let orbit = Orbit::keplerian(
+            AU,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            epoch,
+            SUN_J2000.with_mu_km3_s2(sun_mu_km3_s2),
+        );
+
+        let soi_km = sphere_of_influence_km(&orbit, earth_mu_km3_s2).unwrap();
+        println!("Earth SOI = {soi_km:.0} km");
+        assert!((soi_km - 925_000.0).abs() < 1_000.0);
+
+        let hill_km = hill_radius_km(&orbit, earth_mu_km3_s2).unwrap();
+        println!("Earth Hill radius = {hill_km:.0} km");
+        assert!((hill_km - 1_496_500.0).abs() < 1_000.0);
+
+        // The Hill sphere shrinks with the periapsis
+        let ecc_orbit = Orbit::keplerian(
+            AU,
+            0.1,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            epoch,
+            SUN_J2000.with_mu_km3_s2(sun_mu_km3_s2),
+        );
+        assert!(
+            (hill_radius_km(&ecc_orbit, earth_mu_km3_s2).unwrap() - 0.9 * hill_km).abs() < 1e-3
+        );
+
+        // The frame must have a gravitational parameter
+        assert!(sphere_of_influence_km(&Orbit::zero(EARTH_J2000), 1.0).is_err());
+    }
+}
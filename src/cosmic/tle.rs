@@ -0,0 +1,287 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use super::true_of_date::{j2000_to_true_of_date_dcm, mean_obliquity_rad, nutation_rad};
+use crate::errors::NyxError;
+use crate::linalg::{Matrix3, Vector3};
+use crate::time::{Epoch, Unit};
+use crate::utils::r3;
+use anise::constants::celestial_objects::EARTH;
+use anise::prelude::{Frame, Orbit};
+use anise::NaifId;
+use std::fmt;
+use std::str::FromStr;
+
+/// Orientation ID of the True Equator Mean Equinox (TEME) frame of SGP4.
+///
+/// This frame is not known to the Almanac: use [teme_to_eme2000] to rotate a TEME orbit into EME2000.
+pub const TEME_ORIENTATION_ID: NaifId = 1_000_001;
+
+/// Gravitational parameter of the Earth in the WGS72 model used by SGP4, in km^3/s^2
+pub const SGP4_WGS72_MU_KM3_S2: f64 = 398_600.8;
+
+/// Earth centered True Equator Mean Equinox (TEME) frame of the SGP4 states.
+pub fn earth_teme() -> Frame {
+    Frame::new(EARTH, TEME_ORIENTATION_ID).with_mu_km3_s2(SGP4_WGS72_MU_KM3_S2)
+}
+
+/// A NORAD two-line element set, propagated with the SGP4 (near Earth) or SDP4 (deep space) model.
+///
+/// The TLE may have a title line (i.e. three lines in total). The checksum of both element lines is validated.
+pub struct Tle {
+    /// Name of the object, from the optional title line
+    pub name: Option<String>,
+    /// NORAD catalog number
+    pub norad_id: u64,
+    /// Epoch of the elements, in UTC
+    pub epoch: Epoch,
+    pub inclination_deg: f64,
+    pub raan_deg: f64,
+    pub eccentricity: f64,
+    pub aop_deg: f64,
+    pub mean_anomaly_deg: f64,
+    /// Kozai mean motion, in revolutions per day
+    pub mean_motion_rev_day: f64,
+    /// Drag term, in inverse Earth radii
+    pub bstar: f64,
+    constants: sgp4::Constants,
+}
+
+impl Tle {
+    /// Propagates the TLE to the provided epoch, returning the orbit in the TEME frame (cf. [earth_teme]).
+    pub fn propagate(&self, epoch: Epoch) -> Result<Orbit, NyxError> {
+        let minutes = (epoch - self.epoch).to_unit(Unit::Minute);
+        let prediction = self
+            .constants
+            .propagate(sgp4::MinutesSinceEpoch(minutes))
+            .map_err(|e| NyxError::Sgp4 {
+                msg: format!("{e} at {epoch}"),
+            })?;
+
+        let [x, y, z] = prediction.position;
+        let [vx, vy, vz] = prediction.velocity;
+
+        Ok(Orbit::cartesian(x, y, z, vx, vy, vz, epoch, earth_teme()))
+    }
+
+    /// Propagates the TLE to the provided epoch, and rotates the orbit into the provided EME2000 frame (e.g. as loaded from the Almanac).
+    pub fn propagate_eme2000(&self, epoch: Epoch, eme2k: Frame) -> Result<Orbit, NyxError> {
+        Ok(teme_to_eme2000(self.propagate(epoch)?, eme2k))
+    }
+}
+
+impl FromStr for Tle {
+    type Err = NyxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s
+            .lines()
+            .map(|line| line.trim_end())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>();
+
+        let (name, line1, line2) = match lines.as_slice() {
+            [line1, line2] => (None, *line1, *line2),
+            [name, line1, line2] => (Some(name.trim().to_string()), *line1, *line2),
+            _ => {
+                return Err(NyxError::TleParsing {
+                    line: 0,
+                    msg: format!("expected two or three lines, got {}", lines.len()),
+                })
+            }
+        };
+
+        validate_line(line1, 1)?;
+        validate_line(line2, 2)?;
+
+        let norad_id = parse_field::<u64>(line1, 1, 2..7, "catalog number")?;
+        if parse_field::<u64>(line2, 2, 2..7, "catalog number")? != norad_id {
+            return Err(NyxError::TleParsing {
+                line: 2,
+                msg: "catalog number differs from line 1".to_string(),
+            });
+        }
+
+        // Epoch as two digit year and fractional day of year
+        let year_yy = parse_field::<i32>(line1, 1, 18..20, "epoch year")?;
+        let day_of_year = parse_field::<f64>(line1, 1, 20..32, "epoch day")?;
+        let year = if year_yy < 57 {
+            2000 + year_yy
+        } else {
+            1900 + year_yy
+        };
+        let epoch =
+            Epoch::from_gregorian_utc_at_midnight(year, 1, 1) + (day_of_year - 1.0) * Unit::Day;
+
+        let bstar = parse_exponent_field(line1, 1, 53..61, "B*")?;
+
+        let inclination_deg = parse_field::<f64>(line2, 2, 8..16, "inclination")?;
+        let raan_deg = parse_field::<f64>(line2, 2, 17..25, "right ascension")?;
+        let eccentricity = parse_field::<f64>(line2, 2, 26..33, "eccentricity")? * 1e-7;
+        let aop_deg = parse_field::<f64>(line2, 2, 34..42, "argument of perigee")?;
+        let mean_anomaly_deg = parse_field::<f64>(line2, 2, 43..51, "mean anomaly")?;
+        let mean_motion_rev_day = parse_field::<f64>(line2, 2, 52..63, "mean motion")?;
+
+        let elements = sgp4::Elements::from_tle(name.clone(), line1.as_bytes(), line2.as_bytes())
+            .map_err(|e| NyxError::TleParsing {
+            line: 0,
+            msg: e.to_string(),
+        })?;
+        let constants = sgp4::Constants::from_elements(&elements)
+            .map_err(|e| NyxError::Sgp4 { msg: e.to_string() })?;
+
+        Ok(Self {
+            name,
+            norad_id,
+            epoch,
+            inclination_deg,
+            raan_deg,
+            eccentricity,
+            aop_deg,
+            mean_anomaly_deg,
+            mean_motion_rev_day,
+            bstar,
+            constants,
+        })
+    }
+}
+
+impl fmt::Display for Tle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TLE {} ({}) @ {}: inc = {} deg\traan = {} deg\tecc = {}\taop = {} deg\tma = {} deg\tn = {} rev/day",
+            self.norad_id,
+            self.name.as_deref().unwrap_or("unnamed"),
+            self.epoch,
+            self.inclination_deg,
+            self.raan_deg,
+            self.eccentricity,
+            self.aop_deg,
+            self.mean_anomaly_deg,
+            self.mean_motion_rev_day
+        )
+    }
+}
+
+/// Checks the length, line number and checksum of a TLE line.
+fn validate_line(line: &str, line_no: usize) -> Result<(), NyxError> {
+    if !line.is_ascii() || line.len() < 69 {
+        return Err(NyxError::TleParsing {
+            line: line_no,
+            msg: format!("expected 69 ASCII characters, got `{line}`"),
+        });
+    }
+
+    if !line.starts_with(&format!("{line_no} ")) {
+        return Err(NyxError::TleParsing {
+            line: line_no,
+            msg: format!("line must start with `{line_no}`"),
+        });
+    }
+
+    // The checksum is the sum of the digits, with minus signs counting as one, modulo 10.
+    let checksum = line[..68]
+        .chars()
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum::<u32>()
+        % 10;
+
+    match line[68..69].parse::<u32>() {
+        Ok(expected) if expected == checksum => Ok(()),
+        _ => Err(NyxError::TleParsing {
+            line: line_no,
+            msg: format!("invalid checksum `{}` (computed {checksum})", &line[68..69]),
+        }),
+    }
+}
+
+fn parse_field<T: FromStr>(
+    line: &str,
+    line_no: usize,
+    cols: std::ops::Range<usize>,
+    name: &str,
+) -> Result<T, NyxError> {
+    let field = line[cols].trim();
+    field.parse::<T>().map_err(|_| NyxError::TleParsing {
+        line: line_no,
+        msg: format!("could not parse {name} from `{field}`"),
+    })
+}
+
+/// Parses a field with an assumed leading decimal point and a power of ten exponent, e.g. ` 12345-3` for 0.12345e-3.
+fn parse_exponent_field(
+    line: &str,
+    line_no: usize,
+    cols: std::ops::Range<usize>,
+    name: &str,
+) -> Result<f64, NyxError> {
+    let field = line[cols].trim();
+    let err = || NyxError::TleParsing {
+        line: line_no,
+        msg: format!("could not parse {name} from `{field}`"),
+    };
+
+    if field.len() < 3 {
+        return Err(err());
+    }
+    let (mantissa, exponent) = field.split_at(field.len() - 2);
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, mantissa.trim_start_matches('+')),
+    };
+    let mantissa = format!("0.{digits}").parse::<f64>().map_err(|_| err())?;
+    let exponent = exponent.parse::<i32>().map_err(|_| err())?;
+
+    Ok(sign * mantissa * 10.0_f64.powi(exponent))
+}
+
+/// Returns the rotation matrix from TEME to EME2000 at the provided epoch.
+///
+/// TEME is rotated into the true of date frame by the equation of the equinoxes, then back into EME2000 by the
+/// nutation and precession of [crate::cosmic::true_of_date]. The truncated nutation series is accurate to a few hundredths
+/// of an arcsecond, which is well within the accuracy of SGP4.
+pub fn teme_to_eme2000_dcm(epoch: Epoch) -> Matrix3<f64> {
+    let (dpsi, _) = nutation_rad(epoch);
+    let eq_equinoxes = dpsi * mean_obliquity_rad(epoch).cos();
+
+    j2000_to_true_of_date_dcm(epoch).transpose() * r3(-eq_equinoxes)
+}
+
+/// Rotates an orbit from TEME (e.g. as returned by [Tle::propagate]) into the provided EME2000 frame.
+///
+/// The velocity is only rotated: the rates of the precession and nutation are negligible.
+pub fn teme_to_eme2000(teme: Orbit, eme2k: Frame) -> Orbit {
+    let dcm = teme_to_eme2000_dcm(teme.epoch);
+    let radius_km: Vector3<f64> = dcm * teme.radius_km;
+    let velocity_km_s: Vector3<f64> = dcm * teme.velocity_km_s;
+
+    Orbit::cartesian(
+        radius_km.x,
+        radius_km.y,
+        radius_km.z,
+        velocity_km_s.x,
+        velocity_km_s.y,
+        velocity_km_s.z,
+        teme.epoch,
+        eme2k,
+    )
+}
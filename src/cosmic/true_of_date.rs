@@ -0,0 +1,144 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::constants::celestial_objects::EARTH;
+use anise::constants::orientations::J2000;
+use anise::prelude::Orbit;
+
+use super::AstroError;
+use crate::linalg::Matrix3;
+use crate::time::{Epoch, TimeScale, Unit};
+use crate::utils::{r1, r2, r3};
+
+/// Orientation ID of the true equator and equinox of date of the Earth.
+///
+/// This ID is specific to Nyx and unknown to the Almanac: orbits in this frame are only meant to compute their osculating
+/// elements (the gravitational parameter and shape of the frame are those of the Earth J2000 frame they were rotated from).
+/// Use [from_true_of_date] to rotate them back to the Earth J2000 frame before any other computation.
+pub const TRUE_OF_DATE: i32 = -3_000;
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// Returns the number of Julian centuries of TT since J2000.
+fn tt_centuries_j2k(epoch: Epoch) -> f64 {
+    (epoch - Epoch::from_gregorian_hms(2000, 1, 1, 12, 0, 0, TimeScale::TT)).to_unit(Unit::Century)
+}
+
+/// Returns the mean obliquity of the ecliptic of date (IAU 1980), in radians.
+pub fn mean_obliquity_rad(epoch: Epoch) -> f64 {
+    let t = tt_centuries_j2k(epoch);
+    (84_381.448 - 46.815_0 * t - 0.000_59 * t.powi(2) + 0.001_813 * t.powi(3)) * ARCSEC_TO_RAD
+}
+
+/// Returns the nutation in longitude and in obliquity, in radians.
+///
+/// Only the six largest terms of the IAU 1980 series are accounted for: the largest neglected term is about 0.05 arcsecond.
+pub fn nutation_rad(epoch: Epoch) -> (f64, f64) {
+    let t = tt_centuries_j2k(epoch);
+    // Fundamental arguments of the nutation (Meeus)
+    let d = (297.850_36 + 445_267.111_480 * t).to_radians();
+    let m_sun = (357.527_72 + 35_999.050_340 * t).to_radians();
+    let m_moon = (134.962_98 + 477_198.867_398 * t).to_radians();
+    let f = (93.271_91 + 483_202.017_538 * t).to_radians();
+    let omega = (125.044_52 - 1_934.136_261 * t).to_radians();
+
+    // Argument and coefficients in longitude and obliquity, in 0.1 milliarcseconds
+    let terms = [
+        (omega, -171_996.0 - 174.2 * t, 92_025.0 + 8.9 * t),
+        (
+            2.0 * (f - d + omega),
+            -13_187.0 - 1.6 * t,
+            5_736.0 - 3.1 * t,
+        ),
+        (2.0 * (f + omega), -2_274.0 - 0.2 * t, 977.0 - 0.5 * t),
+        (2.0 * omega, 2_062.0 + 0.2 * t, -895.0 + 0.5 * t),
+        (m_sun, 1_426.0 - 3.4 * t, 54.0 - 0.1 * t),
+        (m_moon, 712.0 + 0.1 * t, -7.0),
+    ];
+
+    terms
+        .iter()
+        .fold((0.0, 0.0), |(dpsi, deps), (arg, psi_coeff, eps_coeff)| {
+            (
+                dpsi + psi_coeff * arg.sin() * 1e-4 * ARCSEC_TO_RAD,
+                deps + eps_coeff * arg.cos() * 1e-4 * ARCSEC_TO_RAD,
+            )
+        })
+}
+
+/// Returns the IAU 1976 precession matrix, which rotates a vector from the mean equator and equinox of J2000 to that of date.
+pub fn precession_dcm(epoch: Epoch) -> Matrix3<f64> {
+    let t = tt_centuries_j2k(epoch);
+    let zeta = (2_306.218_1 * t + 0.301_88 * t.powi(2) + 0.017_998 * t.powi(3)) * ARCSEC_TO_RAD;
+    let z = (2_306.218_1 * t + 1.094_68 * t.powi(2) + 0.018_203 * t.powi(3)) * ARCSEC_TO_RAD;
+    let theta = (2_004.310_9 * t - 0.426_65 * t.powi(2) - 0.041_833 * t.powi(3)) * ARCSEC_TO_RAD;
+
+    r3(-z) * r2(theta) * r3(-zeta)
+}
+
+/// Returns the nutation matrix, which rotates a vector from the mean equator and equinox of date to the true equator and equinox of date.
+pub fn nutation_dcm(epoch: Epoch) -> Matrix3<f64> {
+    let eps = mean_obliquity_rad(epoch);
+    let (dpsi, deps) = nutation_rad(epoch);
+
+    r1(-eps - deps) * r3(-dpsi) * r1(eps)
+}
+
+/// Returns the matrix which rotates a vector from the Earth J2000 frame to the true equator and equinox of date.
+pub fn j2000_to_true_of_date_dcm(epoch: Epoch) -> Matrix3<f64> {
+    nutation_dcm(epoch) * precession_dcm(epoch)
+}
+
+/// Rotates the provided Earth J2000 orbit to the true equator and equinox of its epoch, e.g. to compute its instantaneous
+/// orbital elements instead of those referenced to the J2000 equator.
+///
+/// The rotation rate of the true of date frame (about 1.5e-11 rad/s) is neglected, as is conventional for this quasi-inertial frame.
+pub fn to_true_of_date(orbit: &Orbit) -> Result<Orbit, AstroError> {
+    if orbit.frame.ephemeris_id != EARTH || orbit.frame.orientation_id != J2000 {
+        return Err(AstroError::UnexpectedFrame {
+            expected: "Earth J2000",
+        });
+    }
+
+    let dcm = j2000_to_true_of_date_dcm(orbit.epoch);
+
+    let mut tod = *orbit;
+    tod.radius_km = dcm * orbit.radius_km;
+    tod.velocity_km_s = dcm * orbit.velocity_km_s;
+    tod.frame = orbit.frame.with_orient(TRUE_OF_DATE);
+
+    Ok(tod)
+}
+
+/// Rotates the provided orbit from the true equator and equinox of date (cf. [to_true_of_date]) back to the Earth J2000 frame.
+pub fn from_true_of_date(orbit: &Orbit) -> Result<Orbit, AstroError> {
+    if orbit.frame.ephemeris_id != EARTH || orbit.frame.orientation_id != TRUE_OF_DATE {
+        return Err(AstroError::UnexpectedFrame {
+            expected: "Earth true of date",
+        });
+    }
+
+    let dcm = j2000_to_true_of_date_dcm(orbit.epoch).transpose();
+
+    let mut j2k = *orbit;
+    j2k.radius_km = dcm * orbit.radius_km;
+    j2k.velocity_km_s = dcm * orbit.velocity_km_s;
+    j2k.frame = orbit.frame.with_orient(J2000);
+
+    Ok(j2k)
+}
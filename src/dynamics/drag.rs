@@ -18,29 +18,345 @@
 
 use anise::almanac::Almanac;
 use anise::constants::frames::IAU_EARTH_FRAME;
+use anise::constants::usual_planetary_constants::MEAN_EARTH_ANGULAR_VELOCITY_DEG_S;
 use snafu::ResultExt;
 
 use super::{
-    DynamicsAlmanacSnafu, DynamicsAstroSnafu, DynamicsError, DynamicsPlanetarySnafu, ForceModel,
+    finite_diff_partials, position_grad, DynamicsAlmanacSnafu, DynamicsAstroSnafu, DynamicsError,
+    DynamicsPlanetarySnafu, ForceModel,
 };
-use crate::cosmic::{AstroError, AstroPhysicsSnafu, Frame, Spacecraft};
-use crate::linalg::{Matrix4x3, Vector3};
+use crate::cosmic::{AstroPhysicsSnafu, Frame, Orbit, Spacecraft};
+use crate::linalg::{Matrix3x6, Matrix4x3, Vector3};
+use crate::time::{Epoch, Unit};
+use crate::State;
+use std::f64::consts::PI;
 use std::fmt;
 use std::sync::Arc;
 
+/// Sidereal rotation rate of the Earth in rad/s, used to co-rotate the atmosphere with the planet.
+pub const EARTH_ROTATION_RATE_RAD_S: f64 = MEAN_EARTH_ANGULAR_VELOCITY_DEG_S * PI / 180.0;
+
 /// Density in kg/m^3 and altitudes in meters, not kilometers!
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum AtmDensity {
     Constant(f64),
-    Exponential { rho0: f64, r0: f64, ref_alt_m: f64 },
-    StdAtm { max_alt_m: f64 },
+    Exponential {
+        rho0: f64,
+        r0: f64,
+        ref_alt_m: f64,
+    },
+    StdAtm {
+        max_alt_m: f64,
+    },
+    /// Density provided by an atmospheric model (e.g. [ReducedMsis]), driven by the provided space weather.
+    Model {
+        model: Arc<dyn Density>,
+        space_weather: SpaceWeather,
+    },
+}
+
+/// Space weather indices which drive the empirical models of the upper atmosphere.
+///
+/// The default values correspond to a moderate solar and geomagnetic activity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpaceWeather {
+    /// Daily solar radio flux at 10.7 cm of the previous day, in solar flux units
+    pub f107: f64,
+    /// 81-day average of the solar radio flux at 10.7 cm, centered on the current day, in solar flux units
+    pub f107a: f64,
+    /// Daily geomagnetic index
+    pub ap: f64,
+}
+
+impl Default for SpaceWeather {
+    fn default() -> Self {
+        Self {
+            f107: 150.0,
+            f107a: 150.0,
+            ap: 4.0,
+        }
+    }
+}
+
+/// Location and conditions at which an atmospheric density model is evaluated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtmosphereState {
+    pub epoch: Epoch,
+    /// Geodetic height above the reference ellipsoid, in km
+    pub height_km: f64,
+    /// Geodetic latitude, in degrees
+    pub latitude_deg: f64,
+    /// Geodetic longitude, in degrees
+    pub longitude_deg: f64,
+    pub space_weather: SpaceWeather,
+}
+
+impl AtmosphereState {
+    /// Returns the local mean solar time in hours, between 0 and 24.
+    pub fn local_solar_time_h(&self) -> f64 {
+        let (_, _, _, hours, minutes, seconds, nanos) = self.epoch.to_gregorian_utc();
+        let ut_h = f64::from(hours)
+            + f64::from(minutes) / 60.0
+            + (f64::from(seconds) + f64::from(nanos) * 1e-9) / 3600.0;
+        (ut_h + self.longitude_deg / 15.0).rem_euclid(24.0)
+    }
+
+    /// Returns the fractional day of the year in UTC, starting at 1.0 at midnight on January 1st.
+    pub fn day_of_year(&self) -> f64 {
+        let (year, _, _, _, _, _, _) = self.epoch.to_gregorian_utc();
+        (self.epoch - Epoch::from_gregorian_utc_at_midnight(year, 1, 1)).to_unit(Unit::Day) + 1.0
+    }
+}
+
+/// An atmospheric density model, which can be used by the [Drag] force model with [AtmDensity::Model].
+pub trait Density: fmt::Debug + Send + Sync {
+    /// Returns the atmospheric density in kg/m^3 at the provided location and conditions.
+    fn density_kg_m3(&self, state: &AtmosphereState) -> f64;
+}
+
+/// The standard atmosphere 1976, as a 6th order polynomial fit of the log of the density (from AVS/Schaub's Basilisk).
+///
+/// This model does not depend on the location nor on the space weather, only on the height.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StdAtm1976 {
+    /// Height above which the density follows a simple exponential, in meters
+    pub max_alt_m: f64,
+}
+
+impl Density for StdAtm1976 {
+    fn density_kg_m3(&self, state: &AtmosphereState) -> f64 {
+        let altitude_km = state.height_km;
+        if altitude_km > self.max_alt_m / 1_000.0 {
+            // Use a constant density
+            10.0_f64.powf((-7e-5) * altitude_km - 14.464)
+        } else {
+            // Calculating the density based on a scaled 6th order polynomial fit to the log of density
+            let scale = (altitude_km - 526.8000) / 292.8563;
+            let logdensity =
+                0.34047 * scale.powi(6) - 0.5889 * scale.powi(5) - 0.5269 * scale.powi(4)
+                    + 1.0036 * scale.powi(3)
+                    + 0.60713 * scale.powi(2)
+                    - 2.3024 * scale
+                    - 12.575;
+
+            /* Calculating density by raising 10 to the log of density */
+            10.0_f64.powf(logdensity)
+        }
+    }
+}
+
+/// Reduced order thermospheric density model, with the structure of the MSIS models but only a handful of coefficients.
+///
+/// This is _not_ NRLMSISE-00 (Picone et al., 2002): it only shares its upper thermosphere formulation. Above 120 km, the temperature follows
+/// the Bates profile from 120 km to the exospheric temperature, and each species (He, O, N<sub>2</sub>, O<sub>2</sub>, Ar, H and N) is in
+/// diffusive equilibrium from its number density at 120 km.
+///
+/// The exospheric temperature follows the solar flux and geomagnetic dependence of Jacchia (1971), scaled with diurnal and seasonal variations.
+/// The densities at 120 km vary with the local solar time, the season, the 81-day average of the solar flux, and semiannually. The scale of the
+/// exospheric temperature, its diurnal and seasonal variations, and the densities at 120 km were fitted to five reference cases of NRLMSISE-00
+/// at 400 km: the cases of the NRLMSISE-00 distribution at day 172 and day 81, at the equator, at 4 h local solar time, and at an average flux of 70 sfu.
+/// The longitudinal and universal time terms of NRLMSISE-00 are not modeled.
+///
+/// Below 120 km, the density is that of [StdAtm1976], scaled to be continuous at 120 km.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReducedMsis;
+
+impl ReducedMsis {
+    /// Height of the lower boundary of the thermosphere, in km
+    const LOWER_BOUNDARY_KM: f64 = 120.0;
+    /// Temperature at the lower boundary, in K
+    const LOWER_BOUNDARY_TEMP_K: f64 = 386.0;
+    /// Temperature gradient at the lower boundary for an average solar flux of 150 sfu, in K/km
+    const LOWER_BOUNDARY_TEMP_GRADIENT_K_KM: f64 = 15.1;
+    /// Effective radius used for the geopotential height, in km
+    const EARTH_RADIUS_KM: f64 = 6356.766;
+    /// Standard gravity, in m/s^2
+    const STD_GRAVITY_M_S2: f64 = 9.80665;
+    /// Boltzmann constant, in J/K
+    const BOLTZMANN_J_K: f64 = 1.380649e-23;
+    /// Atomic mass unit, in kg
+    const AMU_KG: f64 = 1.660_539_07e-27;
+    /// Molar mass in amu, number density at 120 km in m^-3, thermal diffusion coefficient, and seasonal coefficient of each species.
+    const SPECIES: [(f64, f64, f64, f64); 7] = [
+        // He
+        (4.0026, 1.131e13, -0.38, -1.0),
+        // O
+        (15.9994, 5.549e16, 0.0, -0.14),
+        // N2
+        (28.0134, 3.366e17, 0.0, 0.0),
+        // O2
+        (31.9988, 2.285e16, 0.0, 0.0),
+        // Ar
+        (39.948, 2.283e15, 0.0, 0.0),
+        // H
+        (1.00794, 9.947e10, -0.38, 0.0),
+        // N
+        (14.0067, 9.627e14, 0.0, 0.0),
+    ];
+
+    /// Returns the exospheric temperature in K.
+    pub fn exospheric_temp_k(&self, state: &AtmosphereState) -> f64 {
+        let (diurnal, seasonal, _) = Self::variations(state);
+        let sw = state.space_weather;
+
+        // Nighttime minimum exospheric temperature and geomagnetic heating of Jacchia (1971)
+        let solar_temp_k = 379.0 + 3.24 * sw.f107a + 1.3 * (sw.f107 - sw.f107a);
+        let geomagnetic_temp_k = sw.ap + 100.0 * (1.0 - (-0.08 * sw.ap).exp());
+
+        1.226 * solar_temp_k * (1.0 + 0.14 * diurnal + 0.10 * seasonal) + geomagnetic_temp_k
+    }
+
+    /// Returns the temperature in K at the height of the provided state, or at the lower boundary if below it.
+    pub fn temperature_k(&self, state: &AtmosphereState) -> f64 {
+        let exo_temp_k = self.exospheric_temp_k(state);
+        let sigma = Self::shape_factor(state, exo_temp_k);
+        exo_temp_k
+            - (exo_temp_k - Self::LOWER_BOUNDARY_TEMP_K)
+                * (-sigma * Self::geopotential_height_km(state.height_km)).exp()
+    }
+
+    /// Returns the diurnal, seasonal (positive in the summer hemisphere), and semiannual variation factors.
+    fn variations(state: &AtmosphereState) -> (f64, f64, f64) {
+        let latitude = state.latitude_deg.to_radians();
+        let doy = state.day_of_year();
+
+        let diurnal =
+            latitude.cos() * (2.0 * PI * (state.local_solar_time_h() - 14.0) / 24.0).cos();
+        let seasonal = latitude.sin() * (2.0 * PI * (doy - 172.0) / 365.25).cos();
+        let semiannual = (4.0 * PI * (doy - 100.0) / 365.25).cos();
+
+        (diurnal, seasonal, semiannual)
+    }
+
+    /// Returns the shape factor of the Bates temperature profile, in 1/km.
+    fn shape_factor(state: &AtmosphereState, exo_temp_k: f64) -> f64 {
+        Self::LOWER_BOUNDARY_TEMP_GRADIENT_K_KM
+            * (1.0 + 2.8e-3 * (state.space_weather.f107a - 150.0))
+            / (exo_temp_k - Self::LOWER_BOUNDARY_TEMP_K)
+    }
+
+    /// Returns the geopotential height above the lower boundary, in km.
+    fn geopotential_height_km(height_km: f64) -> f64 {
+        let height_km = height_km.max(Self::LOWER_BOUNDARY_KM);
+        (height_km - Self::LOWER_BOUNDARY_KM) * (Self::EARTH_RADIUS_KM + Self::LOWER_BOUNDARY_KM)
+            / (Self::EARTH_RADIUS_KM + height_km)
+    }
+
+    /// Returns the density in kg/m^3 of the thermosphere, i.e. at or above the lower boundary.
+    fn thermosphere_density_kg_m3(&self, state: &AtmosphereState) -> f64 {
+        let (diurnal, seasonal, semiannual) = Self::variations(state);
+
+        let exo_temp_k = self.exospheric_temp_k(state);
+        let sigma = Self::shape_factor(state, exo_temp_k);
+        let xi = Self::geopotential_height_km(state.height_km);
+        let temp_k = exo_temp_k - (exo_temp_k - Self::LOWER_BOUNDARY_TEMP_K) * (-sigma * xi).exp();
+
+        let gravity_m_s2 = Self::STD_GRAVITY_M_S2
+            * (Self::EARTH_RADIUS_KM / (Self::EARTH_RADIUS_KM + Self::LOWER_BOUNDARY_KM)).powi(2);
+
+        // Variation of the densities at the lower boundary common to all species
+        let common =
+            0.28 * diurnal + 2.87e-3 * (state.space_weather.f107a - 150.0) + 0.18 * semiannual;
+
+        Self::SPECIES
+            .iter()
+            .map(|(mass_amu, density_m3, alpha, seasonal_coeff)| {
+                let mass_kg = mass_amu * Self::AMU_KG;
+                // Ratio of the scale height of the temperature profile to that of the species, note the conversion of sigma to 1/m.
+                let gamma =
+                    mass_kg * gravity_m_s2 / (sigma * 1e-3 * Self::BOLTZMANN_J_K * exo_temp_k);
+
+                mass_kg
+                    * density_m3
+                    * (common + seasonal_coeff * seasonal).exp()
+                    * (Self::LOWER_BOUNDARY_TEMP_K / temp_k).powf(1.0 + alpha + gamma)
+                    * (-sigma * gamma * xi).exp()
+            })
+            .sum()
+    }
+}
+
+impl Density for ReducedMsis {
+    fn density_kg_m3(&self, state: &AtmosphereState) -> f64 {
+        if state.height_km >= Self::LOWER_BOUNDARY_KM {
+            self.thermosphere_density_kg_m3(state)
+        } else {
+            let std_atm = StdAtm1976 {
+                max_alt_m: Self::LOWER_BOUNDARY_KM * 1e3,
+            };
+            let boundary = AtmosphereState {
+                height_km: Self::LOWER_BOUNDARY_KM,
+                ..*state
+            };
+
+            self.thermosphere_density_kg_m3(&boundary) * std_atm.density_kg_m3(state)
+                / std_atm.density_kg_m3(&boundary)
+        }
+    }
+}
+
+/// Source of the ballistic coefficient (BC = m / (Cd⋅A), in kg/m^2) used by the [Drag] models.
+///
+/// The effective ballistic coefficient of a spacecraft changes with its attitude and configuration. A schedule allows modeling this
+/// without coupling the attitude to the orbital dynamics.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum BallisticCoefficient {
+    /// Computed from the mass and the drag configuration (Cd and area) of the spacecraft (default)
+    #[default]
+    FromSpacecraft,
+    /// Constant ballistic coefficient in kg/m^2
+    Constant(f64),
+    /// Chronological schedule of the ballistic coefficient in kg/m^2, linearly interpolated between epochs and held constant before
+    /// the first and after the last entry. Repeat an epoch with the new value to model a step change at that epoch.
+    Schedule(Vec<(Epoch, f64)>),
+}
+
+impl BallisticCoefficient {
+    /// Returns the ballistic coefficient of the provided spacecraft at its current epoch, in kg/m^2.
+    pub fn at(&self, sc: &Spacecraft) -> f64 {
+        match self {
+            Self::FromSpacecraft => sc.mass_kg() / (sc.drag.cd * sc.drag.area_m2),
+            Self::Constant(bc) => *bc,
+            Self::Schedule(entries) if entries.is_empty() => Self::FromSpacecraft.at(sc),
+            Self::Schedule(entries) => {
+                let epoch = sc.epoch();
+                // Number of entries at or before the current epoch
+                let idx = entries.partition_point(|(entry_epoch, _)| *entry_epoch <= epoch);
+                if idx == 0 {
+                    entries[0].1
+                } else if idx == entries.len() {
+                    entries[idx - 1].1
+                } else {
+                    let (prev_epoch, prev_bc) = entries[idx - 1];
+                    let (next_epoch, next_bc) = entries[idx];
+                    prev_bc
+                        + (next_bc - prev_bc) * (epoch - prev_epoch).to_seconds()
+                            / (next_epoch - prev_epoch).to_seconds()
+                }
+            }
+        }
+    }
+
+    /// Returns the product of the coefficient of drag and of the drag area (Cd⋅A, in m^2) of the provided spacecraft at its current epoch.
+    pub fn cd_area_m2(&self, sc: &Spacecraft) -> f64 {
+        match self {
+            Self::FromSpacecraft => sc.drag.cd * sc.drag.area_m2,
+            _ => sc.mass_kg() / self.at(sc),
+        }
+    }
+}
+
+/// Returns the velocity of the spacecraft relative to the atmosphere, i.e. v - ω × r, where the atmosphere co-rotates with the planet
+/// at the provided rate about the Z axis of the integration frame.
+fn relative_velocity_km_s(ctx: &Spacecraft, rotation_rate_rad_s: f64) -> Vector3<f64> {
+    let omega = Vector3::new(0.0, 0.0, rotation_rate_rad_s);
+    ctx.orbit.velocity_km_s - omega.cross(&ctx.orbit.radius_km)
 }
 
-/// `ConstantDrag` implements a constant drag model as defined in Vallado, 4th ed., page 551, with an important caveat.
+/// `ConstantDrag` implements a constant drag model as defined in Vallado, 4th ed., page 551.
 ///
-/// **WARNING:** This basic model assumes that the velocity of the spacecraft is identical to the velocity of the upper atmosphere,
-/// This is a **bad** assumption and **should not** be used for high fidelity simulations.
-/// This will be resolved after https://gitlab.com/chrisrabotin/nyx/issues/93 is implemented.
+/// The atmosphere co-rotates with the planet: the drag is computed from the velocity of the spacecraft relative to the atmosphere,
+/// assuming that the planet rotates about the Z axis of the integration frame.
 #[derive(Clone)]
 pub struct ConstantDrag {
     /// atmospheric density in kg/m^3
@@ -49,6 +365,8 @@ pub struct ConstantDrag {
     pub drag_frame: Frame,
     /// Set to true to estimate the coefficient of drag
     pub estimate: bool,
+    /// Rotation rate of the atmosphere in rad/s, i.e. that of the planet (e.g. [EARTH_ROTATION_RATE_RAD_S])
+    pub rotation_rate_rad_s: f64,
 }
 
 impl fmt::Display for ConstantDrag {
@@ -70,30 +388,43 @@ impl ForceModel for ConstantDrag {
         }
     }
 
-    fn eom(&self, ctx: &Spacecraft, almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
-        let osc = almanac
-            .transform_to(ctx.orbit, self.drag_frame, None)
-            .context(DynamicsAlmanacSnafu {
-                action: "transforming into drag frame",
-            })?;
-
-        let velocity = osc.velocity_km_s;
+    fn eom(&self, ctx: &Spacecraft, _almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
+        let velocity = relative_velocity_km_s(ctx, self.rotation_rate_rad_s);
         // Note the 1e3 factor to convert drag units from ((kg * km^2 * s^-2) / m^1) to (kg * km * s^-2)
         Ok(-0.5 * 1e3 * self.rho * ctx.drag.cd * ctx.drag.area_m2 * velocity.norm() * velocity)
     }
 
+    /// Returns the position partials (due to the co-rotation of the atmosphere) and the partial wrt Cd. The velocity partials are
+    /// only available with [ForceModel::dual_eom_pos_vel].
     fn dual_eom(
         &self,
-        _osc_ctx: &Spacecraft,
-        _almanac: Arc<Almanac>,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
     ) -> Result<(Vector3<f64>, Matrix4x3<f64>), DynamicsError> {
-        Err(DynamicsError::DynamicsAstro {
-            source: AstroError::PartialsUndefined,
-        })
+        let (force, partials, wrt_cd) = self.dual_eom_pos_vel(osc_ctx, almanac)?;
+        Ok((force, position_grad(&partials, &wrt_cd)))
+    }
+
+    /// The position and velocity partials are computed by central finite differences.
+    fn dual_eom_pos_vel(
+        &self,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3x6<f64>, Vector3<f64>), DynamicsError> {
+        let force = self.eom(osc_ctx, almanac.clone())?;
+        let partials = finite_diff_partials(osc_ctx, |sc| self.eom(sc, almanac.clone()))?;
+
+        // Compute the partial wrt to Cd.
+        let wrt_cd = force / osc_ctx.drag.cd;
+
+        Ok((force, partials, wrt_cd))
     }
 }
 
-/// `Drag` implements all three drag models.
+/// `Drag` implements all of the atmospheric density models.
+///
+/// The atmosphere co-rotates with the planet: the drag is computed from the velocity of the spacecraft relative to the atmosphere,
+/// assuming that the planet rotates about the Z axis of the integration frame.
 #[derive(Clone)]
 pub struct Drag {
     /// Density computation method
@@ -102,42 +433,95 @@ pub struct Drag {
     pub drag_frame: Frame,
     /// Set to true to estimate the coefficient of drag
     pub estimate: bool,
+    /// Source of the ballistic coefficient, defaults to the drag configuration of the spacecraft
+    pub ballistic_coeff: BallisticCoefficient,
+    /// Rotation rate of the atmosphere in rad/s, i.e. that of the planet
+    pub rotation_rate_rad_s: f64,
 }
 
 impl Drag {
     /// Common exponential drag model for the Earth
     pub fn earth_exp(almanac: Arc<Almanac>) -> Result<Arc<Self>, DynamicsError> {
-        Ok(Arc::new(Self {
-            density: AtmDensity::Exponential {
+        Self::earth(
+            AtmDensity::Exponential {
                 rho0: 3.614e-13,
                 r0: 700_000.0,
                 ref_alt_m: 88_667.0,
             },
-            drag_frame: almanac.frame_from_uid(IAU_EARTH_FRAME).context({
-                DynamicsPlanetarySnafu {
-                    action: "planetary data from third body not loaded",
-                }
-            })?,
-            estimate: false,
-        }))
+            almanac,
+        )
     }
 
     /// Drag model which uses the standard atmosphere 1976 model for atmospheric density
     pub fn std_atm1976(almanac: Arc<Almanac>) -> Result<Arc<Self>, DynamicsError> {
-        Ok(Arc::new(Self {
-            density: AtmDensity::StdAtm {
+        Self::earth(
+            AtmDensity::StdAtm {
                 max_alt_m: 1_000_000.0,
             },
+            almanac,
+        )
+    }
+
+    /// Drag model of the Earth using the provided atmospheric density model, evaluated with the provided space weather.
+    pub fn earth_model(
+        model: Arc<dyn Density>,
+        space_weather: SpaceWeather,
+        almanac: Arc<Almanac>,
+    ) -> Result<Arc<Self>, DynamicsError> {
+        Self::earth(
+            AtmDensity::Model {
+                model,
+                space_weather,
+            },
+            almanac,
+        )
+    }
+
+    fn earth(density: AtmDensity, almanac: Arc<Almanac>) -> Result<Arc<Self>, DynamicsError> {
+        Ok(Arc::new(Self {
+            density,
             drag_frame: almanac.frame_from_uid(IAU_EARTH_FRAME).context({
                 DynamicsPlanetarySnafu {
                     action: "planetary data from third body not loaded",
                 }
             })?,
             estimate: false,
+            ballistic_coeff: BallisticCoefficient::default(),
+            rotation_rate_rad_s: EARTH_ROTATION_RATE_RAD_S,
         }))
     }
 }
 
+impl Drag {
+    /// Returns a copy of this drag model using the provided source of the ballistic coefficient.
+    pub fn with_ballistic_coefficient(&self, ballistic_coeff: BallisticCoefficient) -> Arc<Self> {
+        let mut me = self.clone();
+        me.ballistic_coeff = ballistic_coeff;
+        Arc::new(me)
+    }
+
+    /// Returns the location and conditions of the atmosphere at the provided state, which must be in the drag frame.
+    fn atmosphere_state(
+        &self,
+        osc_drag_frame: &Orbit,
+        space_weather: SpaceWeather,
+    ) -> Result<AtmosphereState, DynamicsError> {
+        Ok(AtmosphereState {
+            epoch: osc_drag_frame.epoch,
+            height_km: osc_drag_frame
+                .height_km()
+                .context(AstroPhysicsSnafu)
+                .context(DynamicsAstroSnafu)?,
+            latitude_deg: osc_drag_frame
+                .latitude_deg()
+                .context(AstroPhysicsSnafu)
+                .context(DynamicsAstroSnafu)?,
+            longitude_deg: osc_drag_frame.longitude_deg(),
+            space_weather,
+        })
+    }
+}
+
 impl fmt::Display for Drag {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -158,7 +542,7 @@ impl ForceModel for Drag {
     }
 
     fn eom(&self, ctx: &Spacecraft, almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
-        let integration_frame = ctx.orbit.frame;
+        let cd_area_m2 = self.ballistic_coeff.cd_area_m2(ctx);
 
         let osc_drag_frame = almanac
             .transform_to(ctx.orbit, self.drag_frame, None)
@@ -166,12 +550,8 @@ impl ForceModel for Drag {
                 action: "transforming into drag frame",
             })?;
 
-        match self.density {
-            AtmDensity::Constant(rho) => {
-                let velocity = osc_drag_frame.velocity_km_s;
-                // Note the 1e3 factor to convert drag units from ((kg * km^2 * s^-2) / m^1) to (kg * km * s^-2)
-                Ok(-0.5 * 1e3 * rho * ctx.drag.cd * ctx.drag.area_m2 * velocity.norm() * velocity)
-            }
+        let rho = match &self.density {
+            AtmDensity::Constant(rho) => *rho,
 
             AtmDensity::Exponential {
                 rho0,
@@ -179,79 +559,59 @@ impl ForceModel for Drag {
                 ref_alt_m,
             } => {
                 // Compute rho in the drag frame.
-                let rho = rho0
-                    * (-(osc_drag_frame.rmag_km()
-                        - (r0
-                            + self
-                                .drag_frame
-                                .mean_equatorial_radius_km()
-                                .context(AstroPhysicsSnafu)
-                                .context(DynamicsAstroSnafu)?))
-                        / ref_alt_m)
-                        .exp();
-
-                // TODO: Drag modeling will be improved in https://github.com/nyx-space/nyx/issues/317
-                // The frame will be double checked in this PR as well.
-                // let velocity_integr_frame = self.cosm.frame_chg(&osc, integration_frame).velocity();
-                let velocity_integr_frame = almanac
-                    .transform_to(osc_drag_frame, integration_frame, None)
-                    .context(DynamicsAlmanacSnafu {
-                        action: "rotating into the integration frame",
-                    })?
-                    .velocity_km_s;
-
-                let velocity = velocity_integr_frame - osc_drag_frame.velocity_km_s;
-                // Note the 1e3 factor to convert drag units from ((kg * km^2 * s^-2) / m^1) to (kg * km * s^-2)
-                Ok(-0.5 * 1e3 * rho * ctx.drag.cd * ctx.drag.area_m2 * velocity.norm() * velocity)
+                rho0 * (-(osc_drag_frame.rmag_km()
+                    - (r0
+                        + self
+                            .drag_frame
+                            .mean_equatorial_radius_km()
+                            .context(AstroPhysicsSnafu)
+                            .context(DynamicsAstroSnafu)?))
+                    / ref_alt_m)
+                    .exp()
             }
 
-            AtmDensity::StdAtm { max_alt_m } => {
-                let altitude_km = osc_drag_frame.rmag_km()
-                    - self
-                        .drag_frame
-                        .mean_equatorial_radius_km()
-                        .context(AstroPhysicsSnafu)
-                        .context(DynamicsAstroSnafu)?;
-                let rho = if altitude_km > max_alt_m / 1_000.0 {
-                    // Use a constant density
-                    10.0_f64.powf((-7e-5) * altitude_km - 14.464)
-                } else {
-                    // Code from AVS/Schaub's Basilisk
-                    // Calculating the density based on a scaled 6th order polynomial fit to the log of density
-                    let scale = (altitude_km - 526.8000) / 292.8563;
-                    let logdensity =
-                        0.34047 * scale.powi(6) - 0.5889 * scale.powi(5) - 0.5269 * scale.powi(4)
-                            + 1.0036 * scale.powi(3)
-                            + 0.60713 * scale.powi(2)
-                            - 2.3024 * scale
-                            - 12.575;
-
-                    /* Calculating density by raising 10 to the log of density */
-                    10.0_f64.powf(logdensity)
-                };
-
-                // let velocity_integr_frame = self.cosm.frame_chg(&osc, integration_frame).velocity();
-                let velocity_integr_frame = almanac
-                    .transform_to(osc_drag_frame, integration_frame, None)
-                    .context(DynamicsAlmanacSnafu {
-                        action: "rotating into the integration frame",
-                    })?
-                    .velocity_km_s;
-
-                let velocity = velocity_integr_frame - osc_drag_frame.velocity_km_s;
-                // Note the 1e3 factor to convert drag units from ((kg * km^2 * s^-2) / m^1) to (kg * km * s^-2)
-                Ok(-0.5 * 1e3 * rho * ctx.drag.cd * ctx.drag.area_m2 * velocity.norm() * velocity)
+            AtmDensity::StdAtm { max_alt_m } => StdAtm1976 {
+                max_alt_m: *max_alt_m,
             }
-        }
+            .density_kg_m3(&self.atmosphere_state(&osc_drag_frame, SpaceWeather::default())?),
+
+            AtmDensity::Model {
+                model,
+                space_weather,
+            } => model.density_kg_m3(&self.atmosphere_state(&osc_drag_frame, *space_weather)?),
+        };
+
+        let velocity = relative_velocity_km_s(ctx, self.rotation_rate_rad_s);
+        // Note the 1e3 factor to convert drag units from ((kg * km^2 * s^-2) / m^1) to (kg * km * s^-2)
+        Ok(-0.5 * 1e3 * rho * cd_area_m2 * velocity.norm() * velocity)
     }
 
+    /// Returns the position partials and the partial wrt Cd. The velocity partials are only available with [ForceModel::dual_eom_pos_vel].
     fn dual_eom(
         &self,
-        _osc_ctx: &Spacecraft,
-        _almanac: Arc<Almanac>,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
     ) -> Result<(Vector3<f64>, Matrix4x3<f64>), DynamicsError> {
-        Err(DynamicsError::DynamicsAstro {
-            source: AstroError::PartialsUndefined,
-        })
+        let (force, partials, wrt_cd) = self.dual_eom_pos_vel(osc_ctx, almanac)?;
+        Ok((force, position_grad(&partials, &wrt_cd)))
+    }
+
+    /// The position and velocity partials are computed by central finite differences, which supports all of the density models.
+    fn dual_eom_pos_vel(
+        &self,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3x6<f64>, Vector3<f64>), DynamicsError> {
+        let force = self.eom(osc_ctx, almanac.clone())?;
+        let partials = finite_diff_partials(osc_ctx, |sc| self.eom(sc, almanac.clone()))?;
+
+        // Compute the partial wrt to Cd, which only applies if the drag uses the Cd of the spacecraft.
+        let wrt_cd = if self.ballistic_coeff == BallisticCoefficient::FromSpacecraft {
+            force / osc_ctx.drag.cd
+        } else {
+            Vector3::zeros()
+        };
+
+        Ok((force, partials, wrt_cd))
     }
 }
@@ -186,6 +186,21 @@ impl LocalFrame {
     }
 }
 
+/// Applies an impulsive maneuver, i.e. an instantaneous change in velocity, to an orbit.
+pub trait ImpulsiveManeuver: Sized {
+    /// Returns a copy of this orbit whose velocity is incremented by the provided delta-v (in km/s), expressed in the provided local frame.
+    /// The position and epoch are unchanged. Note that the RIC frame is also known as the RTN frame.
+    fn with_dv(&self, dv_km_s: Vector3<f64>, frame: LocalFrame) -> PhysicsResult<Self>;
+}
+
+impl ImpulsiveManeuver for Orbit {
+    fn with_dv(&self, dv_km_s: Vector3<f64>, frame: LocalFrame) -> PhysicsResult<Self> {
+        let mut orbit = *self;
+        orbit.velocity_km_s += frame.dcm_to_inertial(*self)? * dv_km_s;
+        Ok(orbit)
+    }
+}
+
 #[test]
 fn ra_dec_from_vec() {
     use std::f64::consts::{FRAC_PI_2, PI, TAU};
@@ -0,0 +1,247 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use snafu::ResultExt;
+use std::fmt::{self, Write};
+use std::sync::Arc;
+
+use super::{Dynamics, DynamicsAstroSnafu, DynamicsError, ForceModel, OrbitalDynamics};
+use crate::cosmic::{AstroError, AstroPhysicsSnafu, Equinoctial, Orbit, Spacecraft};
+use crate::errors::StateError;
+use crate::linalg::{Const, OVector, Vector3, Vector6};
+use crate::md::StateParameter;
+use crate::time::Epoch;
+use crate::State;
+
+/// A spacecraft whose orbit is propagated in modified equinoctial elements, cf. [Equinoctial].
+///
+/// The propagated vector is [sma (km), f, g, h, k, true longitude (rad)], where the true longitude is not wrapped so that it
+/// varies continuously. Only the orbit is propagated: the mass, Cr and Cd of the spacecraft are held constant.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct EquinoctialState {
+    /// The spacecraft, whose orbit is updated from the elements after each step
+    pub sc: Spacecraft,
+    elements: [f64; 6],
+}
+
+impl EquinoctialState {
+    /// Initializes the equinoctial state of this spacecraft, whose orbit must be elliptical and not retrograde equatorial.
+    pub fn new(sc: Spacecraft) -> Result<Self, AstroError> {
+        let orbit = sc.orbit;
+        let elements = [
+            orbit.sma_km().context(AstroPhysicsSnafu)?,
+            orbit.equinoctial_f()?,
+            orbit.equinoctial_g()?,
+            orbit.equinoctial_h()?,
+            orbit.equinoctial_k()?,
+            orbit.true_longitude()?.to_radians(),
+        ];
+        Ok(Self { sc, elements })
+    }
+
+    /// Returns the elements as [sma (km), f, g, h, k, true longitude (rad)].
+    pub fn elements(&self) -> Vector6<f64> {
+        Vector6::from_column_slice(&self.elements)
+    }
+}
+
+impl fmt::Display for EquinoctialState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.sc, f)
+    }
+}
+
+impl fmt::LowerExp for EquinoctialState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerExp::fmt(&self.sc, f)
+    }
+}
+
+impl State for EquinoctialState {
+    type Size = Const<6>;
+    type VecLength = Const<6>;
+
+    fn to_vector(&self) -> OVector<f64, Const<6>> {
+        self.elements()
+    }
+
+    fn unset_stm(&mut self) {}
+
+    fn set(&mut self, epoch: Epoch, vector: &OVector<f64, Const<6>>) {
+        self.elements.copy_from_slice(vector.as_slice());
+        self.sc.orbit.epoch = epoch;
+        // The gravitational parameter of the frame is checked at initialization, so the conversion cannot fail.
+        if let Ok(orbit) = Orbit::from_equinoctial(
+            vector[0],
+            vector[1],
+            vector[2],
+            vector[3],
+            vector[4],
+            vector[5].to_degrees(),
+            epoch,
+            self.sc.orbit.frame,
+        ) {
+            self.sc.orbit = orbit;
+        }
+    }
+
+    fn epoch(&self) -> Epoch {
+        self.sc.orbit.epoch
+    }
+
+    fn set_epoch(&mut self, epoch: Epoch) {
+        self.sc.orbit.epoch = epoch
+    }
+
+    fn value(&self, param: StateParameter) -> Result<f64, StateError> {
+        self.sc.value(param)
+    }
+}
+
+/// `GVEDynamics` propagates the orbit in modified equinoctial elements with the Gauss variational equations, driven by the
+/// perturbing accelerations of the orbital dynamics (the two body term is the Keplerian motion of the elements) and of the force models.
+///
+/// The elements vary slowly under small perturbations, so they can be integrated with larger steps than the Cartesian state.
+#[derive(Clone)]
+pub struct GVEDynamics {
+    pub orbital_dyn: OrbitalDynamics,
+    pub force_models: Vec<Arc<dyn ForceModel>>,
+}
+
+impl GVEDynamics {
+    /// Initialize the Gauss variational equations with the perturbations of the provided orbital dynamics.
+    pub fn new(orbital_dyn: OrbitalDynamics) -> Self {
+        Self {
+            orbital_dyn,
+            force_models: Vec::new(),
+        }
+    }
+
+    /// Initialize the Gauss variational equations with the provided orbital dynamics and force model.
+    pub fn from_model(orbital_dyn: OrbitalDynamics, force_model: Arc<dyn ForceModel>) -> Self {
+        Self::from_models(orbital_dyn, vec![force_model])
+    }
+
+    /// Initialize the Gauss variational equations with the provided orbital dynamics and force models.
+    pub fn from_models(
+        orbital_dyn: OrbitalDynamics,
+        force_models: Vec<Arc<dyn ForceModel>>,
+    ) -> Self {
+        Self {
+            orbital_dyn,
+            force_models,
+        }
+    }
+
+    /// Returns the perturbing acceleration in km/s^2, in the frame of the orbit, i.e. excluding the two body acceleration.
+    pub fn perturbation(
+        &self,
+        sc: &Spacecraft,
+        almanac: Arc<Almanac>,
+    ) -> Result<Vector3<f64>, DynamicsError> {
+        let mut accel = Vector3::zeros();
+        for model in &self.orbital_dyn.accel_models {
+            accel += model.eom(&sc.orbit, almanac.clone())?;
+        }
+        for model in &self.force_models {
+            accel += model.eom(sc, almanac.clone())? / sc.mass_kg();
+        }
+        Ok(accel)
+    }
+
+    /// Returns the rates of the elements [sma (km/s), f, g, h, k (per second), true longitude (rad/s)] of the provided state,
+    /// cf. Walker, Ireland and Owens (1985). The rate of the semi-major axis is computed from the rate of the orbital energy.
+    pub fn element_rates(
+        &self,
+        state: &EquinoctialState,
+        almanac: Arc<Almanac>,
+    ) -> Result<Vector6<f64>, DynamicsError> {
+        let orbit = state.sc.orbit;
+        let mu_km3_s2 = orbit
+            .frame
+            .mu_km3_s2()
+            .context(AstroPhysicsSnafu)
+            .context(DynamicsAstroSnafu)?;
+        let [sma_km, f, g, h, k, l_rad] = state.elements;
+
+        // Project the perturbation onto the radial, transverse, and normal directions.
+        let accel_km_s2 = self.perturbation(&state.sc, almanac)?;
+        let r_hat = orbit.radius_km.normalize();
+        let n_hat = orbit.radius_km.cross(&orbit.velocity_km_s).normalize();
+        let t_hat = n_hat.cross(&r_hat);
+        let (a_r, a_t, a_n) = (
+            accel_km_s2.dot(&r_hat),
+            accel_km_s2.dot(&t_hat),
+            accel_km_s2.dot(&n_hat),
+        );
+
+        let p_km = sma_km * (1.0 - f.powi(2) - g.powi(2));
+        let (sin_l, cos_l) = l_rad.sin_cos();
+        let w = 1.0 + f * cos_l + g * sin_l;
+        let s2 = 1.0 + h.powi(2) + k.powi(2);
+        let sqrt_p_mu = (p_km / mu_km3_s2).sqrt();
+        let hk = h * sin_l - k * cos_l;
+
+        Ok(Vector6::new(
+            2.0 * sma_km.powi(2) / mu_km3_s2 * orbit.velocity_km_s.dot(&accel_km_s2),
+            sqrt_p_mu * (a_r * sin_l + ((w + 1.0) * cos_l + f) * a_t / w - hk * g * a_n / w),
+            sqrt_p_mu * (-a_r * cos_l + ((w + 1.0) * sin_l + g) * a_t / w + hk * f * a_n / w),
+            sqrt_p_mu * s2 * a_n * cos_l / (2.0 * w),
+            sqrt_p_mu * s2 * a_n * sin_l / (2.0 * w),
+            (mu_km3_s2 * p_km).sqrt() * (w / p_km).powi(2) + sqrt_p_mu * hk * a_n / w,
+        ))
+    }
+}
+
+impl fmt::Display for GVEDynamics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let force_models: String = if self.force_models.is_empty() {
+            "No force models;".to_string()
+        } else {
+            self.force_models
+                .iter()
+                .fold(String::new(), |mut output, x| {
+                    let _ = write!(output, "{x}; ");
+                    output
+                })
+        };
+        write!(
+            f,
+            "Gauss variational equations: {} {}",
+            self.orbital_dyn, force_models
+        )
+    }
+}
+
+impl Dynamics for GVEDynamics {
+    type HyperdualSize = Const<7>;
+    type StateType = EquinoctialState;
+
+    fn eom(
+        &self,
+        delta_t_s: f64,
+        state: &OVector<f64, Const<6>>,
+        ctx: &Self::StateType,
+        almanac: Arc<Almanac>,
+    ) -> Result<OVector<f64, Const<6>>, DynamicsError> {
+        // Rebuild the osculating state for the EOM context.
+        let osc = ctx.set_with_delta_seconds(delta_t_s, state);
+        self.element_rates(&osc, almanac)
+    }
+}
@@ -18,7 +18,11 @@
 
 use crate::cosmic::{AstroError, Orbit};
 use crate::linalg::allocator::Allocator;
-use crate::linalg::{DefaultAllocator, DimName, Matrix3, Matrix4x3, OMatrix, OVector, Vector3};
+use crate::linalg::{
+    DVector, DefaultAllocator, DimName, Matrix3, Matrix3x6, Matrix4x3, OMatrix, OVector, Vector3,
+};
+use crate::md::trajectory::TrajError;
+use crate::utils::{FiniteDiff, FiniteDiffStep};
 use crate::State;
 use anise::almanac::planetary::PlanetaryDataError;
 use anise::almanac::Almanac;
@@ -66,6 +70,25 @@ pub use self::drag::*;
 pub mod sph_harmonics;
 pub use self::sph_harmonics::*;
 
+/// Define the J2 and J3 zonal harmonics model.
+pub mod zonal_harmonics;
+pub use self::zonal_harmonics::*;
+
+/// Computes the secular rates of the orbital elements by averaging a perturbation over one revolution.
+pub mod perturbations;
+
+/// Propagates the orbit in modified equinoctial elements with the Gauss variational equations.
+pub mod gve;
+pub use self::gve::*;
+
+/// Define the solid body tides model.
+pub mod tides;
+pub use self::tides::*;
+
+/// Define the relativistic correction of the central body gravity.
+pub mod relativity;
+pub use self::relativity::*;
+
 /// The `Dynamics` trait handles and stores any equation of motion *and* the state is integrated.
 ///
 /// Its design is such that several of the provided dynamics can be combined fairly easily. However,
@@ -134,6 +157,9 @@ where
     }
 }
 
+/// Relative step of the finite differences of the partials of force models which are not expressed in hyperdual numbers.
+pub(crate) const PARTIALS_REL_STEP: f64 = 1e-7;
+
 /// The `ForceModel` trait handles immutable dynamics which return a force. Those will be divided by the mass of the spacecraft to compute the acceleration (F = ma).
 ///
 /// Examples include Solar Radiation Pressure, drag, etc., i.e. forces which do not need to save the current state, only act on it.
@@ -152,6 +178,62 @@ pub trait ForceModel: Send + Sync + fmt::Display {
         osc_ctx: &Spacecraft,
         almanac: Arc<Almanac>,
     ) -> Result<(Vector3<f64>, Matrix4x3<f64>), DynamicsError>;
+
+    /// Returns the force, its partials with respect to the position (first three columns) and the velocity (last three columns), and the
+    /// partials of the parameter of this force model wrt the position, as used to build the STM.
+    ///
+    /// By default, the velocity partials are zero and the other partials are those of [ForceModel::dual_eom]: force models which depend on
+    /// the velocity, like drag, must override this function.
+    fn dual_eom_pos_vel(
+        &self,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3x6<f64>, Vector3<f64>), DynamicsError> {
+        let (force, grad) = self.dual_eom(osc_ctx, almanac)?;
+
+        let mut partials = Matrix3x6::zeros();
+        partials
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&grad.fixed_rows::<3>(0));
+
+        Ok((force, partials, grad.row(3).transpose()))
+    }
+}
+
+/// Returns the partials of the provided force with respect to the position (first three columns) and the velocity (last three columns),
+/// computed by central finite differences with a step of [PARTIALS_REL_STEP] times the magnitude of each component (or one if smaller).
+///
+/// This is meant for force models whose eom is not expressed in hyperdual numbers and which depend on the velocity (e.g. drag with tabulated
+/// or third party atmospheric densities), such that they can still be composed with other models when propagating the STM.
+pub(crate) fn finite_diff_partials<F>(
+    osc_ctx: &Spacecraft,
+    force: F,
+) -> Result<Matrix3x6<f64>, DynamicsError>
+where
+    F: Fn(&Spacecraft) -> Result<Vector3<f64>, DynamicsError>,
+{
+    let partials = FiniteDiff::central()
+        .with_step(FiniteDiffStep::Relative(PARTIALS_REL_STEP))
+        .try_jacobian(
+            |pos_vel| {
+                let mut sc = *osc_ctx;
+                sc.orbit.radius_km = Vector3::new(pos_vel[0], pos_vel[1], pos_vel[2]);
+                sc.orbit.velocity_km_s = Vector3::new(pos_vel[3], pos_vel[4], pos_vel[5]);
+                force(&sc).map(|force| DVector::from_column_slice(force.as_slice()))
+            },
+            &DVector::from_column_slice(osc_ctx.orbit.to_cartesian_pos_vel().as_slice()),
+        )?;
+
+    Ok(Matrix3x6::from_column_slice(partials.as_slice()))
+}
+
+/// Builds the gradient of [ForceModel::dual_eom] from the position partials and the partial wrt Cd.
+pub(crate) fn position_grad(partials: &Matrix3x6<f64>, wrt_cd: &Vector3<f64>) -> Matrix4x3<f64> {
+    let mut grad = Matrix4x3::zeros();
+    grad.fixed_rows_mut::<3>(0)
+        .copy_from(&partials.fixed_columns::<3>(0));
+    grad.set_row(3, &wrt_cd.transpose());
+    grad
 }
 
 /// The `AccelModel` trait handles immutable dynamics which return an acceleration. Those can be added directly to Orbital Dynamics for example.
@@ -173,9 +255,6 @@ pub trait AccelModel: Send + Sync + fmt::Display {
 /// Stores dynamical model errors
 #[derive(Debug, PartialEq, Snafu)]
 pub enum DynamicsError {
-    /// Fuel exhausted at the provided spacecraft state
-    #[snafu(display("fuel exhausted at {sc}"))]
-    FuelExhausted { sc: Box<Spacecraft> },
     #[snafu(display("expected STM to be set"))]
     StateTransitionMatrixUnset,
     #[snafu(display("dynamical model encountered an astro error: {source}"))]
@@ -193,4 +272,9 @@ pub enum DynamicsError {
         action: &'static str,
         source: PlanetaryDataError,
     },
+    #[snafu(display("dynamical model could not query the nominal trajectory: {source}"))]
+    DynamicsTraj { source: TrajError },
+    #[cfg(feature = "python")]
+    #[snafu(display("Python force model failed: {msg}"))]
+    DynamicsPython { msg: String },
 }
@@ -18,9 +18,13 @@
 
 use super::{
     AccelModel, DynamicsAlmanacSnafu, DynamicsAstroSnafu, DynamicsError, DynamicsPlanetarySnafu,
+    DynamicsTrajSnafu,
 };
 use crate::cosmic::{AstroPhysicsSnafu, Frame, Orbit};
 use crate::linalg::{Const, Matrix3, Matrix6, OVector, Vector3, Vector6};
+use crate::md::trajectory::Traj;
+use crate::time::{Duration, Epoch, Unit};
+use crate::Spacecraft;
 
 use anise::almanac::Almanac;
 use anise::astro::Aberration;
@@ -32,6 +36,7 @@ use std::fmt;
 use std::sync::Arc;
 
 pub use super::sph_harmonics::Harmonics;
+pub use super::zonal_harmonics::ZonalHarmonics;
 
 /// `OrbitalDynamics` provides the equations of motion for any celestial dynamic, without state transition matrix computation.
 #[derive(Clone)]
@@ -47,11 +52,31 @@ impl OrbitalDynamics {
         Self::new(vec![PointMasses::new(celestial_objects)])
     }
 
+    /// Initializes the point masses gravities with the provided list of bodies, whose positions are queried from the
+    /// almanac with the provided aberration correction (e.g. light time), cf. [PointMasses] for the frame conventions.
+    pub fn point_masses_with_correction(
+        celestial_objects: Vec<i32>,
+        correction: Aberration,
+    ) -> Self {
+        Self::new(vec![Arc::new(PointMasses::with_correction(
+            celestial_objects,
+            correction,
+        ))])
+    }
+
     /// Initializes a OrbitalDynamics which does not simulate the gravity pull of other celestial objects but the primary one.
     pub fn two_body() -> Self {
         Self::new(vec![])
     }
 
+    /// Initializes the orbital dynamics with the J2 and J3 zonal harmonics of the body of the provided body fixed frame,
+    /// whose gravitational parameter and shape must be set, e.g. `IAU_EARTH_FRAME` loaded from the almanac.
+    ///
+    /// The J2 and J3 coefficients are unnormalized, cf. [ZonalHarmonics::earth_jgm3] for the Earth values.
+    pub fn with_zonal_harmonics(j2: f64, j3: f64, body_frame: Frame) -> Self {
+        Self::from_model(ZonalHarmonics::new(j2, j3, body_frame))
+    }
+
     /// Initialize orbital dynamics with a list of acceleration models
     pub fn new(accel_models: Vec<Arc<dyn AccelModel + Sync>>) -> Self {
         Self { accel_models }
@@ -166,9 +191,69 @@ impl OrbitalDynamics {
         // This is why we don't multiply the gradient (A matrix) with the previous STM
         Ok((dx, grad))
     }
+
+    /// Computes the state transition matrix from `start` to `end` by only integrating the variational equations along the
+    /// provided nominal trajectory, i.e. without propagating the nominal state again. If `end` is before `start`, the STM
+    /// is integrated backward.
+    ///
+    /// The variational equations are integrated with a fixed step fourth order Runge Kutta, whose step should be small compared
+    /// to the orbital period, e.g. one second in low Earth orbit. The nominal state at each stage is interpolated from the
+    /// trajectory, so both epochs must be within its bounds. Only the partials of these orbital dynamics are accounted for:
+    /// the STM of spacecraft force models (e.g. solar radiation pressure or drag) is not included.
+    pub fn stm_between(
+        &self,
+        traj: &Traj<Spacecraft>,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        almanac: Arc<Almanac>,
+    ) -> Result<Matrix6<f64>, DynamicsError> {
+        let total_s = (end - start).to_seconds();
+        let mut stm = Matrix6::identity();
+        if total_s == 0.0 {
+            return Ok(stm);
+        }
+
+        let num_steps = (total_s.abs() / step.abs().to_seconds()).ceil().max(1.0) as usize;
+        let step_s = total_s / num_steps as f64;
+
+        // Gradient of the equations of motion at the provided number of half steps from the start epoch
+        let jacobian = |half_steps: usize| -> Result<Matrix6<f64>, DynamicsError> {
+            let delta_t_s = 0.5 * step_s * half_steps as f64;
+            // Avoid rounding past the end of the trajectory on the last step
+            let epoch = if half_steps == 2 * num_steps {
+                end
+            } else {
+                start + delta_t_s * Unit::Second
+            };
+            let osc = traj.at(epoch).context(DynamicsTrajSnafu)?.orbit;
+            Ok(self.dual_eom(delta_t_s, &osc, almanac.clone())?.1)
+        };
+
+        let mut grad_0 = jacobian(0)?;
+        for i in 0..num_steps {
+            let grad_half = jacobian(2 * i + 1)?;
+            let grad_1 = jacobian(2 * i + 2)?;
+
+            let k1 = grad_0 * stm;
+            let k2 = grad_half * (stm + 0.5 * step_s * k1);
+            let k3 = grad_half * (stm + 0.5 * step_s * k2);
+            let k4 = grad_1 * (stm + step_s * k3);
+
+            stm += (step_s / 6.0) * (k1 + 2.0 * k2 + 2.0 * k3 + k4);
+            grad_0 = grad_1;
+        }
+
+        Ok(stm)
+    }
 }
 
 /// PointMasses model
+///
+/// The third body accelerations are the differential (direct minus indirect) terms, computed and summed in the
+/// integration frame of the orbit. Each perturber's position is queried relative to the origin of that frame at
+/// the orbit epoch, with the aberration correction if one is set. If the origin of the integration frame is in the
+/// list of bodies, it is skipped: its gravity is already accounted for by the two body term of [OrbitalDynamics].
 pub struct PointMasses {
     pub celestial_objects: Vec<i32>,
     /// Light-time correction computation if extra point masses are needed
@@ -0,0 +1,199 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use anise::prelude::Orbit;
+use snafu::ResultExt;
+use std::f64::consts::TAU;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{AccelModel, DynamicsAstroSnafu, DynamicsError, ForceModel};
+use crate::cosmic::{AstroPhysicsSnafu, Spacecraft};
+use crate::linalg::Vector3;
+use crate::time::Unit;
+
+/// Number of samples in mean anomaly used to average the Gauss variational equations over one revolution.
+pub const AVERAGING_SAMPLES: usize = 360;
+
+/// Secular rates of the Keplerian orbital elements.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ElementRates {
+    /// Rate of the semi-major axis, in km/s
+    pub sma_km_s: f64,
+    /// Rate of the eccentricity, per second
+    pub ecc_s: f64,
+    /// Rate of the inclination, in rad/s
+    pub inc_rad_s: f64,
+    /// Rate of the right ascension of the ascending node, in rad/s
+    pub raan_rad_s: f64,
+    /// Rate of the argument of periapsis, in rad/s
+    pub aop_rad_s: f64,
+}
+
+impl fmt::Display for ElementRates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let day_s = Unit::Day.in_seconds();
+        write!(
+            f,
+            "sma = {:.6} km/day\tecc = {:.6e} /day\tinc = {:.6} deg/day\traan = {:.6} deg/day\taop = {:.6} deg/day",
+            self.sma_km_s * day_s,
+            self.ecc_s * day_s,
+            self.inc_rad_s.to_degrees() * day_s,
+            self.raan_rad_s.to_degrees() * day_s,
+            self.aop_rad_s.to_degrees() * day_s,
+        )
+    }
+}
+
+/// Computes the secular rates of the orbital elements due to the provided perturbing acceleration model (e.g. zonal harmonics),
+/// by averaging the Gauss variational equations over one revolution of the unperturbed orbit.
+///
+/// The orbit must be elliptical and neither circular nor equatorial, else the rates of the angles are undefined.
+pub fn orbit_averaged_rates(
+    orbit: &Orbit,
+    accel_model: &dyn AccelModel,
+    almanac: Arc<Almanac>,
+) -> Result<ElementRates, DynamicsError> {
+    average_gauss_equations(orbit, |osc| accel_model.eom(osc, almanac.clone()))
+}
+
+/// Computes the secular rates of the orbital elements of the spacecraft due to the provided force model (e.g. drag or solar radiation
+/// pressure), by averaging the Gauss variational equations over one revolution of the unperturbed orbit. The spacecraft properties
+/// (mass, drag and SRP configuration) are held constant over the revolution.
+///
+/// The orbit must be elliptical and neither circular nor equatorial, else the rates of the angles are undefined.
+pub fn orbit_averaged_force_rates(
+    sc: &Spacecraft,
+    force_model: &dyn ForceModel,
+    almanac: Arc<Almanac>,
+) -> Result<ElementRates, DynamicsError> {
+    average_gauss_equations(&sc.orbit, |osc| {
+        Ok(force_model.eom(&sc.with_orbit(*osc), almanac.clone())? / sc.mass_kg())
+    })
+}
+
+/// Averages the Gauss variational equations with the provided perturbing acceleration (in km/s^2, in the frame of the orbit), sampled
+/// uniformly in mean anomaly, i.e. uniformly in time, over one revolution starting at the epoch of the orbit.
+fn average_gauss_equations<F>(orbit: &Orbit, accel: F) -> Result<ElementRates, DynamicsError>
+where
+    F: Fn(&Orbit) -> Result<Vector3<f64>, DynamicsError>,
+{
+    let mu_km3_s2 = orbit
+        .frame
+        .mu_km3_s2()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?;
+    let sma_km = orbit
+        .sma_km()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?;
+    let ecc = orbit
+        .ecc()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?;
+    let inc_rad = orbit
+        .inc_deg()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?
+        .to_radians();
+    let raan_deg = orbit
+        .raan_deg()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?;
+    let aop_rad = orbit
+        .aop_deg()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?
+        .to_radians();
+    let ma0_rad = orbit
+        .ma_deg()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?
+        .to_radians();
+
+    let mean_motion_rad_s = (mu_km3_s2 / sma_km.powi(3)).sqrt();
+    let p_km = sma_km * (1.0 - ecc.powi(2));
+    let h_km2_s = (mu_km3_s2 * p_km).sqrt();
+
+    let mut rates = ElementRates::default();
+
+    for k in 0..AVERAGING_SAMPLES {
+        let dma_rad = TAU * (k as f64) / (AVERAGING_SAMPLES as f64);
+        let ta_rad = true_anomaly_rad(ma0_rad + dma_rad, ecc);
+
+        let sample = Orbit::keplerian(
+            sma_km,
+            ecc,
+            inc_rad.to_degrees(),
+            raan_deg,
+            aop_rad.to_degrees(),
+            ta_rad.to_degrees(),
+            orbit.epoch + (dma_rad / mean_motion_rad_s) * Unit::Second,
+            orbit.frame,
+        );
+
+        // Project the perturbation onto the radial, transverse, and normal directions.
+        let r_km = sample.rmag_km();
+        let r_hat = sample.radius_km / r_km;
+        let w_hat = sample.radius_km.cross(&sample.velocity_km_s).normalize();
+        let s_hat = w_hat.cross(&r_hat);
+
+        let accel_km_s2 = accel(&sample)?;
+        let (f_r, f_s, f_w) = (
+            accel_km_s2.dot(&r_hat),
+            accel_km_s2.dot(&s_hat),
+            accel_km_s2.dot(&w_hat),
+        );
+
+        let (sin_ta, cos_ta) = ta_rad.sin_cos();
+        let (sin_u, cos_u) = (aop_rad + ta_rad).sin_cos();
+
+        rates.sma_km_s += 2.0 * sma_km.powi(2) / h_km2_s * (ecc * sin_ta * f_r + p_km / r_km * f_s);
+        rates.ecc_s +=
+            (p_km * sin_ta * f_r + ((p_km + r_km) * cos_ta + r_km * ecc) * f_s) / h_km2_s;
+        rates.inc_rad_s += r_km * cos_u / h_km2_s * f_w;
+        let raan_rate = r_km * sin_u / (h_km2_s * inc_rad.sin()) * f_w;
+        rates.raan_rad_s += raan_rate;
+        rates.aop_rad_s += (-p_km * cos_ta * f_r + (p_km + r_km) * sin_ta * f_s) / (h_km2_s * ecc)
+            - raan_rate * inc_rad.cos();
+    }
+
+    let n = AVERAGING_SAMPLES as f64;
+    rates.sma_km_s /= n;
+    rates.ecc_s /= n;
+    rates.inc_rad_s /= n;
+    rates.raan_rad_s /= n;
+    rates.aop_rad_s /= n;
+
+    Ok(rates)
+}
+
+/// Returns the true anomaly (rad) from the mean anomaly (rad) of an elliptical orbit, solving Kepler's equation by Newton iteration.
+fn true_anomaly_rad(ma_rad: f64, ecc: f64) -> f64 {
+    let mut ea_rad = ma_rad;
+    for _ in 0..50 {
+        let delta = (ea_rad - ecc * ea_rad.sin() - ma_rad) / (1.0 - ecc * ea_rad.cos());
+        ea_rad -= delta;
+        if delta.abs() < 1e-14 {
+            break;
+        }
+    }
+    2.0 * ((1.0 + ecc).sqrt() * (ea_rad / 2.0).sin())
+        .atan2((1.0 - ecc).sqrt() * (ea_rad / 2.0).cos())
+}
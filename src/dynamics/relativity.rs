@@ -0,0 +1,131 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::prelude::Almanac;
+use snafu::ResultExt;
+
+use crate::cosmic::{AstroPhysicsSnafu, Orbit, SPEED_OF_LIGHT_KM_S};
+use crate::dynamics::AccelModel;
+use crate::linalg::{Matrix3, Vector3, U7};
+use hyperdual::{hyperspace_from_vector, Float, OHyperdual};
+use std::fmt;
+use std::sync::Arc;
+
+use super::{DynamicsAstroSnafu, DynamicsError};
+
+/// Schwarzschild (post-Newtonian) correction to the point mass acceleration of the central body.
+///
+/// This is the dominant relativistic term of the IERS Conventions 2010 (eq. 10.12) with β = γ = 1, computed from the position
+/// and velocity relative to the central body, i.e. the center of the integration frame, whose gravitational parameter must be set.
+/// It is about 3e-10 m/s² at GPS altitude: small, but it causes a secular drift of the argument of periapsis.
+/// This is an acceleration model (independent of the spacecraft mass), so it is composed with the point masses in the `OrbitalDynamics`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Relativity;
+
+impl Relativity {
+    /// Initializes the Schwarzschild correction of the central body of the integration frame.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    /// Acceleration for the provided position, velocity, gravitational parameter and speed of light, generic to support the partials.
+    fn schwarzschild<T>(radius: [T; 3], velocity: [T; 3], mu: T, c2: T) -> [T; 3]
+    where
+        T: Float + From<f64>,
+    {
+        let four: T = From::from(4.0);
+        let r2 = radius[0] * radius[0] + radius[1] * radius[1] + radius[2] * radius[2];
+        let r = r2.sqrt();
+        let v2 = velocity[0] * velocity[0] + velocity[1] * velocity[1] + velocity[2] * velocity[2];
+        let r_dot_v = radius[0] * velocity[0] + radius[1] * velocity[1] + radius[2] * velocity[2];
+
+        let factor = mu / (c2 * r2 * r);
+        let radial = four * mu / r - v2;
+        let along_track = four * r_dot_v;
+
+        [0, 1, 2].map(|i| factor * (radial * radius[i] + along_track * velocity[i]))
+    }
+}
+
+impl fmt::Display for Relativity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Schwarzschild relativistic correction")
+    }
+}
+
+impl AccelModel for Relativity {
+    fn eom(&self, osc: &Orbit, _almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
+        let mu_km3_s2 = osc
+            .frame
+            .mu_km3_s2()
+            .context(AstroPhysicsSnafu)
+            .context(DynamicsAstroSnafu)?;
+
+        let accel = Self::schwarzschild(
+            [osc.radius_km.x, osc.radius_km.y, osc.radius_km.z],
+            [
+                osc.velocity_km_s.x,
+                osc.velocity_km_s.y,
+                osc.velocity_km_s.z,
+            ],
+            mu_km3_s2,
+            SPEED_OF_LIGHT_KM_S.powi(2),
+        );
+
+        Ok(Vector3::from(accel))
+    }
+
+    /// Returns the acceleration and its partials with respect to the position; the partials with respect to the velocity are not part of the `AccelModel` and are neglected.
+    fn dual_eom(
+        &self,
+        osc: &Orbit,
+        _almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3<f64>), DynamicsError> {
+        let mu_km3_s2 = osc
+            .frame
+            .mu_km3_s2()
+            .context(AstroPhysicsSnafu)
+            .context(DynamicsAstroSnafu)?;
+
+        let radius: Vector3<OHyperdual<f64, U7>> = hyperspace_from_vector(&osc.radius_km);
+        let velocity = [
+            osc.velocity_km_s.x,
+            osc.velocity_km_s.y,
+            osc.velocity_km_s.z,
+        ]
+        .map(OHyperdual::<f64, U7>::from);
+
+        let accel_dual = Self::schwarzschild(
+            [radius[0], radius[1], radius[2]],
+            velocity,
+            OHyperdual::<f64, U7>::from(mu_km3_s2),
+            OHyperdual::<f64, U7>::from(SPEED_OF_LIGHT_KM_S.powi(2)),
+        );
+
+        let mut accel = Vector3::zeros();
+        let mut grad = Matrix3::zeros();
+        for (i, accel_i) in accel_dual.into_iter().enumerate() {
+            accel[i] = accel_i.real();
+            for j in 1..4 {
+                grad[(i, j - 1)] = accel_i[j];
+            }
+        }
+
+        Ok((accel, grad))
+    }
+}
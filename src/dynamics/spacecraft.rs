@@ -34,25 +34,23 @@ use std::sync::Arc;
 
 use crate::cosmic::AstroError;
 #[cfg(feature = "python")]
-use crate::io::ConfigRepr;
+use crate::python::mission_design::PyForceModel;
 #[cfg(feature = "python")]
 use crate::python::PythonError;
 #[cfg(feature = "python")]
 use pyo3::class::basic::CompareOp;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
-#[cfg(feature = "python")]
-use pyo3::types::PyType;
-#[cfg(feature = "python")]
-use pythonize::depythonize;
-#[cfg(feature = "python")]
-use std::collections::BTreeMap;
 
 const NORM_ERR: f64 = 1e-4;
 
 /// A generic spacecraft dynamics with associated force models, guidance law, and flag specifying whether to decrement the fuel mass or not.
 /// Note: when developing new guidance laws, it is recommended to _not_ enable fuel decrement until the guidance law seems to work without proper physics.
-/// Note: if the spacecraft runs out of fuel, the propagation segment will return an error.
+/// Note: if the spacecraft runs out of fuel, the thrust cuts off and the spacecraft coasts for the rest of the propagation.
+///
+/// The STM is propagated alongside the state when it is enabled on the spacecraft (e.g. [Spacecraft::with_stm]), whatever the composition of models:
+/// its rate of change is assembled from the partials of the acceleration models of the orbital dynamics and of each force model, using hyperdual numbers
+/// or finite differences (drag, including its velocity partials) depending on the model. A guidance law prevents the computation of the STM.
 #[derive(Clone)]
 #[cfg_attr(feature = "python", pyclass)]
 #[cfg_attr(feature = "python", pyo3(module = "nyx_space.mission_design"))]
@@ -120,6 +118,18 @@ impl SpacecraftDynamics {
         me
     }
 
+    /// Initialize new spacecraft dynamics with a vector of force models and a guidance law.
+    /// By default, the mass of the vehicle will be decremented as propellant is consumed.
+    pub fn from_models_and_guidance_law(
+        orbital_dyn: OrbitalDynamics,
+        force_models: Vec<Arc<dyn ForceModel>>,
+        guid_law: Arc<dyn GuidanceLaw>,
+    ) -> Self {
+        let mut me = Self::from_guidance_law(orbital_dyn, guid_law);
+        me.force_models = force_models;
+        me
+    }
+
     /// A shortcut to spacecraft.guid_law if a guidance law is defined for these dynamics
     pub fn guidance_achieved(&self, state: &Spacecraft) -> Result<bool, GuidanceError> {
         match &self.guid_law {
@@ -142,45 +152,12 @@ impl SpacecraftDynamics {
 #[cfg_attr(feature = "python", pymethods)]
 impl SpacecraftDynamics {
     #[cfg(feature = "python")]
-    #[classmethod]
-    fn load(_cls: &PyType, path: &str) -> Result<Self, ConfigError> {
-        let serde = DynamicsSerde::load(path)?;
-
-        let cosm = Cosm::de438();
-
-        Self::from_config(serde, cosm)
-    }
-
-    #[cfg(feature = "python")]
-    #[classmethod]
-    fn load_many(_cls: &PyType, path: &str) -> Result<Vec<Self>, ConfigError> {
-        let orbits = DynamicsSerde::load_many(path)?;
-
-        let cosm = Cosm::de438();
-
-        let mut selves = Vec::with_capacity(orbits.len());
-
-        for serde in orbits {
-            selves.push(Self::from_config(serde, cosm.clone())?);
-        }
-
-        Ok(selves)
-    }
-
-    #[cfg(feature = "python")]
-    #[classmethod]
-    fn load_named(_cls: &PyType, path: &str) -> Result<BTreeMap<String, Self>, ConfigError> {
-        let orbits = DynamicsSerde::load_named(path)?;
-
-        let cosm = Cosm::de438();
-
-        let mut selves = BTreeMap::new();
-
-        for (k, v) in orbits {
-            selves.insert(k, Self::from_config(v, cosm.clone())?);
-        }
-
-        Ok(selves)
+    #[new]
+    #[pyo3(text_signature = "(point_masses)")]
+    /// Initializes the dynamics of a spacecraft subjected to the gravity of the provided point masses (as NAIF IDs, e.g. 399 for the Earth)
+    /// and to no force model. Force models defined in Python may then be added with `with_force_model`.
+    fn py_new(point_masses: Vec<i32>) -> Self {
+        Self::new(OrbitalDynamics::point_masses(point_masses))
     }
 
     #[cfg(feature = "python")]
@@ -198,13 +175,12 @@ impl SpacecraftDynamics {
     }
 
     #[cfg(feature = "python")]
-    #[classmethod]
-    /// Loads the SpacecraftDynamics from its YAML representation
-    fn loads(_cls: &PyType, state: &PyAny) -> Result<Self, ConfigError> {
-        <Self as Configurable>::from_config(
-            depythonize(state).map_err(|e| ConfigError::InvalidConfig { msg: e.to_string() })?,
-            Cosm::de438(),
-        )
+    /// Returns a copy of these dynamics with an additional force model defined in Python, i.e. a callable which takes the
+    /// osculating spacecraft and returns its acceleration as a list of three floats in km/s^2, in the integration frame.
+    fn with_force_model(&self, callable: PyObject) -> Self {
+        let mut me = self.clone();
+        me.force_models.push(Arc::new(PyForceModel { callable }));
+        me
     }
 }
 
@@ -236,14 +212,25 @@ impl Dynamics for SpacecraftDynamics {
 
     fn finally(
         &self,
-        next_state: Self::StateType,
+        mut next_state: Self::StateType,
         almanac: Arc<Almanac>,
     ) -> Result<Self::StateType, DynamicsError> {
         if next_state.fuel_mass_kg < 0.0 {
-            error!("negative fuel mass at {}", next_state.epoch());
-            return Err(DynamicsError::FuelExhausted {
-                sc: Box::new(next_state),
-            });
+            // The thrust cuts off when the tank is empty, but the integration step may have overshot the depletion.
+            // Remove the velocity gained by burning the propellant which was not in the tank, per the rocket equation.
+            if let (Some(guid_law), Some(thruster)) = (&self.guid_law, next_state.thruster) {
+                let overshoot_kg = -next_state.fuel_mass_kg;
+                let direction = guid_law
+                    .direction(&next_state)
+                    .context(DynamicsGuidanceSnafu)?;
+                let excess_dv_km_s = thruster.isp_s
+                    * STD_GRAVITY
+                    * 1e-3
+                    * (next_state.dry_mass_kg / (next_state.dry_mass_kg - overshoot_kg)).ln();
+                next_state.orbit.velocity_km_s -= excess_dv_km_s * direction;
+            }
+            info!("fuel exhausted at {}", next_state.epoch());
+            next_state.fuel_mass_kg = 0.0;
         }
 
         if let Some(guid_law) = &self.guid_law {
@@ -324,6 +311,9 @@ impl Dynamics for SpacecraftDynamics {
                             ratio: thrust_throttle_lvl,
                         },
                     });
+                } else if self.decrement_mass && osc_sc.fuel_mass_kg <= 0.0 {
+                    // Flameout: no propellant left
+                    (Vector3::zeros(), 0.0)
                 } else if thrust_throttle_lvl > 0.0 {
                     // Thrust arc
                     let thrust_inertial =
@@ -407,19 +397,20 @@ impl Dynamics for SpacecraftDynamics {
         // Call the EOMs
         let total_mass = ctx.mass_kg();
         for model in &self.force_models {
-            let (model_frc, model_grad) = model.dual_eom(ctx, almanac.clone())?;
+            let (model_frc, model_partials, model_param_partials) =
+                model.dual_eom_pos_vel(ctx, almanac.clone())?;
             for i in 0..3 {
                 // Add the velocity changes
                 d_x[i + 3] += model_frc[i] / total_mass;
-                // Add the velocity partials
-                for j in 0..3 {
-                    grad[(i + 3, j)] += model_grad[(i, j)] / total_mass;
+                // Add the partials of the acceleration wrt the position and the velocity
+                for j in 0..6 {
+                    grad[(i + 3, j)] += model_partials[(i, j)] / total_mass;
                 }
             }
             // Add this force model's estimation if applicable.
             if let Some(idx) = model.estimation_index() {
                 for j in 0..3 {
-                    grad[(j + 3, idx)] += model_grad[(3, j)] / total_mass;
+                    grad[(j + 3, idx)] += model_param_partials[j] / total_mass;
                 }
             }
         }
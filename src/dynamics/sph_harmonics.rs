@@ -16,6 +16,7 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use anise::constants::celestial_objects::{MOON, SUN};
 use anise::errors::OrientationSnafu;
 use anise::prelude::Almanac;
 use snafu::ResultExt;
@@ -24,18 +25,21 @@ use crate::cosmic::{AstroPhysicsSnafu, Frame, Orbit};
 use crate::dynamics::AccelModel;
 use crate::io::gravity::HarmonicsMem;
 use crate::linalg::{DMatrix, Matrix3, Vector3, U7};
+use crate::time::Epoch;
 use hyperdual::linalg::norm;
 use hyperdual::{hyperspace_from_vector, Float, OHyperdual};
 use std::cmp::min;
 use std::fmt;
 use std::sync::Arc;
 
+use super::tides::{TidalCoefficients, TidalForce, EARTH_LOVE_NUMBERS_K2};
 use super::{DynamicsAlmanacSnafu, DynamicsAstroSnafu, DynamicsError};
 
 #[derive(Clone)]
 pub struct Harmonics {
     compute_frame: Frame,
     stor: HarmonicsMem,
+    tides: Option<TidalForce>,
     a_nm: DMatrix<f64>,
     b_nm: DMatrix<f64>,
     c_nm: DMatrix<f64>,
@@ -51,6 +55,23 @@ pub struct Harmonics {
 impl Harmonics {
     /// Create a new Harmonics dynamical model from the provided gravity potential storage instance.
     pub fn from_stor(compute_frame: Frame, stor: HarmonicsMem) -> Arc<Self> {
+        Arc::new(Self::build(compute_frame, stor, None))
+    }
+
+    /// Create a new Harmonics dynamical model where the degree 2 coefficients include the solid Earth tides raised by the Moon and the Sun.
+    ///
+    /// The tidal corrections use the frequency independent Love numbers `EARTH_LOVE_NUMBERS_K2` and are recomputed from the lunar and solar positions
+    /// at every evaluation. Do not also add a `TidalForce` to the dynamics, or the tides would be counted twice.
+    pub fn with_solid_tides(compute_frame: Frame, stor: HarmonicsMem) -> Arc<Self> {
+        let tides = TidalForce {
+            love_numbers: EARTH_LOVE_NUMBERS_K2,
+            compute_frame,
+            celestial_objects: vec![MOON, SUN],
+        };
+        Arc::new(Self::build(compute_frame, stor, Some(tides)))
+    }
+
+    fn build(compute_frame: Frame, stor: HarmonicsMem, tides: Option<TidalForce>) -> Self {
         let degree_np2 = stor.max_degree_n() + 2;
         let mut a_nm = DMatrix::from_element(degree_np2 + 1, degree_np2 + 1, 0.0);
         let mut b_nm = DMatrix::from_element(degree_np2, degree_np2, 0.0);
@@ -117,9 +138,10 @@ impl Harmonics {
             }
         }
 
-        Arc::new(Self {
+        Self {
             compute_frame,
             stor,
+            tides,
             a_nm,
             b_nm,
             c_nm,
@@ -130,7 +152,46 @@ impl Harmonics {
             c_nm_h,
             vr01_h,
             vr11_h,
-        })
+        }
+    }
+
+    /// Returns the normalized C_nm and S_nm at the provided epoch, including the solid tides corrections if enabled.
+    pub fn cs_nm(
+        &self,
+        degree: usize,
+        order: usize,
+        epoch: Epoch,
+        almanac: Arc<Almanac>,
+    ) -> Result<(f64, f64), DynamicsError> {
+        let tides = self.tidal_coefficients(epoch, almanac)?;
+        Ok(self.corrected_cs_nm(degree, order, tides.as_ref()))
+    }
+
+    /// Returns the solid tides corrections at the provided epoch, if enabled.
+    fn tidal_coefficients(
+        &self,
+        epoch: Epoch,
+        almanac: Arc<Almanac>,
+    ) -> Result<Option<TidalCoefficients>, DynamicsError> {
+        self.tides
+            .as_ref()
+            .map(|tides| tides.coefficients(epoch, almanac))
+            .transpose()
+    }
+
+    fn corrected_cs_nm(
+        &self,
+        degree: usize,
+        order: usize,
+        tides: Option<&TidalCoefficients>,
+    ) -> (f64, f64) {
+        let (c_val, s_val) = self.stor.cs_nm(degree, order);
+        match (degree, order, tides) {
+            (2, 0, Some(delta)) => (c_val + delta.c20, s_val),
+            (2, 1, Some(delta)) => (c_val + delta.c21, s_val + delta.s21),
+            (2, 2, Some(delta)) => (c_val + delta.c22, s_val + delta.s22),
+            _ => (c_val, s_val),
+        }
     }
 }
 
@@ -138,10 +199,15 @@ impl fmt::Display for Harmonics {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{} gravity field {}x{} (order x degree)",
+            "{} gravity field {}x{} (order x degree){}",
             self.compute_frame,
             self.stor.max_order_m(),
             self.stor.max_degree_n(),
+            if self.tides.is_some() {
+                " with solid tides"
+            } else {
+                ""
+            }
         )
     }
 }
@@ -206,6 +272,9 @@ impl AccelModel for Harmonics {
             .context(AstroPhysicsSnafu)
             .context(DynamicsAstroSnafu)?;
 
+        // Solid tides corrections to the degree 2 coefficients, if enabled
+        let tides = self.tidal_coefficients(osc.epoch, almanac.clone())?;
+
         let rho = eq_radius_km / r_;
         let mut rho_np1 = mu_km3_s2 / r_ * rho;
         let mut a0 = 0.0;
@@ -221,7 +290,7 @@ impl AccelModel for Harmonics {
             rho_np1 *= rho;
 
             for m in 0..=min(n, max_order) {
-                let (c_val, s_val) = self.stor.cs_nm(n, m);
+                let (c_val, s_val) = self.corrected_cs_nm(n, m, tides.as_ref());
                 let d_ = (c_val * r_m[m] + s_val * i_m[m]) * 2.0.sqrt();
                 let e_ = if m == 0 {
                     0.0
@@ -326,6 +395,9 @@ impl AccelModel for Harmonics {
             .context(AstroPhysicsSnafu)
             .context(DynamicsAstroSnafu)?;
 
+        // Solid tides corrections to the degree 2 coefficients, if enabled
+        let tides = self.tidal_coefficients(osc.epoch, almanac.clone())?;
+
         let eq_radius = OHyperdual::<f64, U7>::from(real_eq_radius_km);
         let rho = eq_radius / r_;
         let mut rho_np1 = OHyperdual::<f64, U7>::from(real_mu_km3_s2) / r_ * rho;
@@ -344,7 +416,7 @@ impl AccelModel for Harmonics {
             rho_np1 *= rho;
 
             for m in 0..=min(n, max_order) {
-                let (c_valf64, s_valf64) = self.stor.cs_nm(n, m);
+                let (c_valf64, s_valf64) = self.corrected_cs_nm(n, m, tides.as_ref());
                 let c_val = OHyperdual::<f64, U7>::from(c_valf64);
                 let s_val = OHyperdual::<f64, U7>::from(s_valf64);
 
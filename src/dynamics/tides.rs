@@ -0,0 +1,261 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::constants::celestial_objects::{MOON, SUN};
+use anise::prelude::Almanac;
+use snafu::ResultExt;
+
+use crate::cosmic::{AstroPhysicsSnafu, Frame, Orbit};
+use crate::dynamics::AccelModel;
+use crate::linalg::{Matrix3, Vector3, U7};
+use crate::time::Epoch;
+use hyperdual::{hyperspace_from_vector, Float, OHyperdual};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::sync::Arc;
+
+use super::zonal_harmonics::{body_fixed, mu_and_radius};
+use super::{DynamicsAlmanacSnafu, DynamicsAstroSnafu, DynamicsError, DynamicsPlanetarySnafu};
+
+/// Nominal degree 2 Love numbers k<sub>20</sub>, k<sub>21</sub> and k<sub>22</sub> of the Earth (anelastic, IERS Conventions 2010, table 6.3).
+pub const EARTH_LOVE_NUMBERS_K2: [f64; 3] = [0.30190, 0.29830, 0.30102];
+
+/// Factors N<sub>2m</sub> from the normalized to the unnormalized degree 2 coefficients.
+const NORM_20: f64 = 2.236_067_977_499_79; // sqrt(5)
+const NORM_21: f64 = 1.290_994_448_735_805_6; // sqrt(5/3)
+const NORM_22: f64 = 0.645_497_224_367_902_8; // sqrt(5/12)
+
+/// Corrections to the _normalized_ degree 2 coefficients of the gravity field due to the solid tides.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TidalCoefficients {
+    pub c20: f64,
+    pub c21: f64,
+    pub s21: f64,
+    pub c22: f64,
+    pub s22: f64,
+}
+
+impl TidalCoefficients {
+    /// Returns the equivalent change of the unnormalized J<sub>2</sub>, i.e. -sqrt(5) ΔC̄<sub>20</sub>.
+    pub fn delta_j2(&self) -> f64 {
+        -NORM_20 * self.c20
+    }
+}
+
+impl fmt::Display for TidalCoefficients {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ΔC20 = {:e}\tΔC21 = {:e}\tΔS21 = {:e}\tΔC22 = {:e}\tΔS22 = {:e}",
+            self.c20, self.c21, self.s21, self.c22, self.s22
+        )
+    }
+}
+
+/// Solid body tides raised by the Sun, the Moon, or any other celestial object on the body of the compute frame.
+///
+/// The tides deform the body, which changes the degree 2 coefficients of its gravity field following the step 1 of the IERS Conventions 2010 (eq. 6.6),
+/// i.e. with frequency independent Love numbers. The acceleration due to these corrections is computed in the body fixed frame and rotated into the integration frame.
+/// This is an acceleration model (independent of the spacecraft mass), so it is composed with the harmonics field in the `OrbitalDynamics`.
+#[derive(Clone, Debug)]
+pub struct TidalForce {
+    /// Love numbers k<sub>20</sub>, k<sub>21</sub> and k<sub>22</sub> of the deformed body
+    pub love_numbers: [f64; 3],
+    /// Body fixed frame of the deformed body, which must have its gravitational parameter and shape set
+    pub compute_frame: Frame,
+    /// Celestial objects raising the tides
+    pub celestial_objects: Vec<i32>,
+}
+
+impl TidalForce {
+    /// Initializes the solid tides of the body of the compute frame raised by the provided celestial objects.
+    pub fn new(
+        love_numbers: [f64; 3],
+        compute_frame: Frame,
+        celestial_objects: Vec<i32>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            love_numbers,
+            compute_frame,
+            celestial_objects,
+        })
+    }
+
+    /// Initializes the solid Earth tides raised by the Moon and the Sun, e.g. in the IAU Earth frame.
+    pub fn earth(compute_frame: Frame) -> Arc<Self> {
+        Self::new(EARTH_LOVE_NUMBERS_K2, compute_frame, vec![MOON, SUN])
+    }
+
+    /// Returns the corrections to the normalized degree 2 coefficients at the provided epoch.
+    pub fn coefficients(
+        &self,
+        epoch: Epoch,
+        almanac: Arc<Almanac>,
+    ) -> Result<TidalCoefficients, DynamicsError> {
+        let (mu_km3_s2, eq_radius_km) = mu_and_radius(self.compute_frame)?;
+
+        let mut coeffs = TidalCoefficients::default();
+
+        for celestial_object in self.celestial_objects.iter().copied() {
+            let perturber_frame = almanac
+                .frame_from_uid(Frame::from_ephem_j2000(celestial_object))
+                .context(DynamicsPlanetarySnafu {
+                    action: "planetary data from tide raising body not loaded",
+                })?;
+
+            let perturber_mu_km3_s2 = perturber_frame
+                .mu_km3_s2()
+                .context(AstroPhysicsSnafu)
+                .context(DynamicsAstroSnafu)?;
+
+            // Position of the perturber in the body fixed frame
+            let perturber = almanac
+                .transform(perturber_frame, self.compute_frame, epoch, None)
+                .context(DynamicsAlmanacSnafu {
+                    action: "computing tide raising body position",
+                })?;
+
+            let r_j = perturber.rmag_km();
+            let u = perturber.radius_km / r_j;
+
+            let factor = (perturber_mu_km3_s2 / mu_km3_s2) * (eq_radius_km / r_j).powi(3) / 5.0;
+
+            // Normalized Legendre functions times cos(mλ) and sin(mλ), in Cartesian form
+            let p20 = NORM_20 * 0.5 * (3.0 * u.z * u.z - 1.0);
+            let p21_cos = NORM_21 * 3.0 * u.z * u.x;
+            let p21_sin = NORM_21 * 3.0 * u.z * u.y;
+            let p22_cos = NORM_22 * 3.0 * (u.x * u.x - u.y * u.y);
+            let p22_sin = NORM_22 * 3.0 * 2.0 * u.x * u.y;
+
+            coeffs.c20 += self.love_numbers[0] * factor * p20;
+            coeffs.c21 += self.love_numbers[1] * factor * p21_cos;
+            coeffs.s21 += self.love_numbers[1] * factor * p21_sin;
+            coeffs.c22 += self.love_numbers[2] * factor * p22_cos;
+            coeffs.s22 += self.love_numbers[2] * factor * p22_sin;
+        }
+
+        Ok(coeffs)
+    }
+
+    /// Returns the potential term of the unnormalized coefficients at the provided position in the body fixed frame,
+    /// i.e. the potential is mu R² V / r⁵, and V is a quadratic form of the position.
+    fn quadratic_form<T>(coeffs: &[f64; 5], x: T, y: T, z: T, r2: T) -> T
+    where
+        T: Copy + From<f64> + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let [c20, c21, s21, c22, s22] = coeffs.map(T::from);
+        let half = T::from(0.5);
+        let three = T::from(3.0);
+        let six = T::from(6.0);
+
+        c20 * half * (three * z * z - r2)
+            + three * z * (c21 * x + s21 * y)
+            + three * c22 * (x * x - y * y)
+            + six * s22 * x * y
+    }
+
+    /// Returns the unnormalized coefficients (C20, C21, S21, C22, S22) at the provided epoch
+    fn unnormalized(&self, epoch: Epoch, almanac: Arc<Almanac>) -> Result<[f64; 5], DynamicsError> {
+        let coeffs = self.coefficients(epoch, almanac)?;
+        Ok([
+            NORM_20 * coeffs.c20,
+            NORM_21 * coeffs.c21,
+            NORM_21 * coeffs.s21,
+            NORM_22 * coeffs.c22,
+            NORM_22 * coeffs.s22,
+        ])
+    }
+}
+
+impl fmt::Display for TidalForce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} solid tides raised by {:?}",
+            self.compute_frame, self.celestial_objects
+        )
+    }
+}
+
+impl AccelModel for TidalForce {
+    fn eom(&self, osc: &Orbit, almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
+        let (mu_km3_s2, eq_radius_km) = mu_and_radius(self.compute_frame)?;
+        let coeffs = self.unnormalized(osc.epoch, almanac.clone())?;
+        let (radius_km, dcm) = body_fixed(self.compute_frame, osc, almanac)?;
+
+        let (x, y, z) = (radius_km.x, radius_km.y, radius_km.z);
+        let [c20, c21, s21, c22, s22] = coeffs;
+        let r2 = radius_km.norm_squared();
+        let r5 = r2.powi(2) * r2.sqrt();
+
+        let v = Self::quadratic_form(&coeffs, x, y, z, r2);
+        let grad_v = Vector3::new(
+            -c20 * x + 3.0 * c21 * z + 6.0 * (c22 * x + s22 * y),
+            -c20 * y + 3.0 * s21 * z + 6.0 * (s22 * x - c22 * y),
+            2.0 * c20 * z + 3.0 * (c21 * x + s21 * y),
+        );
+
+        let accel =
+            mu_km3_s2 * eq_radius_km.powi(2) * (grad_v / r5 - 5.0 * v * radius_km / (r5 * r2));
+
+        Ok(dcm * accel)
+    }
+
+    fn dual_eom(
+        &self,
+        osc: &Orbit,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3<f64>), DynamicsError> {
+        let (mu_km3_s2, eq_radius_km) = mu_and_radius(self.compute_frame)?;
+        let coeffs = self.unnormalized(osc.epoch, almanac.clone())?;
+        let (radius_km, dcm) = body_fixed(self.compute_frame, osc, almanac)?;
+
+        let radius: Vector3<OHyperdual<f64, U7>> = hyperspace_from_vector(&radius_km);
+        let (x, y, z) = (radius[0], radius[1], radius[2]);
+        let [c20, c21, s21, c22, s22] = coeffs.map(OHyperdual::<f64, U7>::from);
+        let two = OHyperdual::<f64, U7>::from(2.0);
+        let three = OHyperdual::<f64, U7>::from(3.0);
+        let five = OHyperdual::<f64, U7>::from(5.0);
+        let six = OHyperdual::<f64, U7>::from(6.0);
+
+        let r2 = x * x + y * y + z * z;
+        let r5 = r2.powi(2) * r2.sqrt();
+        let mu_r2 = OHyperdual::<f64, U7>::from(mu_km3_s2 * eq_radius_km.powi(2));
+
+        let v = Self::quadratic_form(&coeffs, x, y, z, r2);
+        let grad_v = [
+            -c20 * x + three * c21 * z + six * (c22 * x + s22 * y),
+            -c20 * y + three * s21 * z + six * (s22 * x - c22 * y),
+            two * c20 * z + three * (c21 * x + s21 * y),
+        ];
+
+        // Extract the acceleration and its partials in the compute frame
+        let mut accel_body = Vector3::zeros();
+        let mut grad_body = Matrix3::zeros();
+        for (i, coord) in [x, y, z].into_iter().enumerate() {
+            let accel_i = mu_r2 * (grad_v[i] / r5 - five * v * coord / (r5 * r2));
+            accel_body[i] = accel_i.real();
+            for j in 1..4 {
+                grad_body[(i, j - 1)] = accel_i[j];
+            }
+        }
+
+        // Rotate both into the integration frame
+        Ok((dcm * accel_body, dcm * grad_body * dcm.transpose()))
+    }
+}
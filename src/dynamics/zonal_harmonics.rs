@@ -0,0 +1,186 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::errors::OrientationSnafu;
+use anise::prelude::Almanac;
+use snafu::ResultExt;
+
+use crate::cosmic::{AstroPhysicsSnafu, Frame, Orbit};
+use crate::dynamics::AccelModel;
+use crate::io::gravity::{EARTH_J2_JGM3, EARTH_J3_JGM3};
+use crate::linalg::{Matrix3, Vector3, U7};
+use hyperdual::linalg::norm;
+use hyperdual::{hyperspace_from_vector, Float, OHyperdual};
+use std::fmt;
+use std::sync::Arc;
+
+use super::{DynamicsAlmanacSnafu, DynamicsAstroSnafu, DynamicsError};
+
+/// J<sub>2</sub> and J<sub>3</sub> zonal harmonics of the gravity field of a body.
+///
+/// The accelerations are computed in the body fixed frame (whose Z axis is the pole of the body) and rotated into the integration frame.
+/// This is equivalent to a 3x0 spherical harmonics field (cf. [super::Harmonics]) but much cheaper to evaluate.
+#[derive(Clone, Debug)]
+pub struct ZonalHarmonics {
+    /// Unnormalized J<sub>2</sub> coefficient (positive for an oblate body)
+    pub j2: f64,
+    /// Unnormalized J<sub>3</sub> coefficient
+    pub j3: f64,
+    /// Body fixed frame in which the zonal harmonics are computed, which must have its gravitational parameter and shape set
+    pub compute_frame: Frame,
+}
+
+impl ZonalHarmonics {
+    /// Initializes the zonal harmonics from the unnormalized J<sub>2</sub> and J<sub>3</sub> coefficients of the body of the compute frame.
+    pub fn new(j2: f64, j3: f64, compute_frame: Frame) -> Arc<Self> {
+        Arc::new(Self {
+            j2,
+            j3,
+            compute_frame,
+        })
+    }
+
+    /// Initializes the zonal harmonics of the Earth from the JGM3 model, e.g. in the IAU Earth frame.
+    pub fn earth_jgm3(compute_frame: Frame) -> Arc<Self> {
+        Self::new(EARTH_J2_JGM3, EARTH_J3_JGM3, compute_frame)
+    }
+}
+
+/// Returns the gravitational parameter (km^3/s^2) and the equatorial radius (km) of the provided body fixed frame.
+pub(crate) fn mu_and_radius(compute_frame: Frame) -> Result<(f64, f64), DynamicsError> {
+    let mu_km3_s2 = compute_frame
+        .mu_km3_s2()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?;
+
+    let eq_radius_km = compute_frame
+        .mean_equatorial_radius_km()
+        .context(AstroPhysicsSnafu)
+        .context(DynamicsAstroSnafu)?;
+
+    Ok((mu_km3_s2, eq_radius_km))
+}
+
+/// Returns the position in the provided body fixed frame and the rotation matrix from that frame into the integration frame.
+pub(crate) fn body_fixed(
+    compute_frame: Frame,
+    osc: &Orbit,
+    almanac: Arc<Almanac>,
+) -> Result<(Vector3<f64>, Matrix3<f64>), DynamicsError> {
+    let state = almanac
+        .transform_to(*osc, compute_frame, None)
+        .context(DynamicsAlmanacSnafu {
+            action: "transforming into body fixed frame",
+        })?;
+
+    let dcm = almanac
+        .rotate_from_to(compute_frame, osc.frame, osc.epoch)
+        .context(OrientationSnafu {
+            action: "transform state dcm",
+        })
+        .context(DynamicsAlmanacSnafu {
+            action: "transforming from body fixed frame",
+        })?;
+
+    Ok((state.radius_km, dcm.rot_mat))
+}
+
+impl fmt::Display for ZonalHarmonics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} zonal harmonics (J2 = {:e}, J3 = {:e})",
+            self.compute_frame, self.j2, self.j3
+        )
+    }
+}
+
+impl AccelModel for ZonalHarmonics {
+    fn eom(&self, osc: &Orbit, almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
+        let (mu_km3_s2, eq_radius_km) = mu_and_radius(self.compute_frame)?;
+        let (radius_km, dcm) = body_fixed(self.compute_frame, osc, almanac)?;
+
+        let (x, y, z) = (radius_km.x, radius_km.y, radius_km.z);
+        let r2 = radius_km.norm_squared();
+        let r = r2.sqrt();
+        let z2_r2 = z * z / r2;
+
+        let j2_factor = -1.5 * self.j2 * mu_km3_s2 * eq_radius_km.powi(2) / r.powi(5);
+        let j3_factor = -2.5 * self.j3 * mu_km3_s2 * eq_radius_km.powi(3) / r.powi(7);
+
+        // The X and Y components share the same factor
+        let xy_factor = j2_factor * (1.0 - 5.0 * z2_r2) + j3_factor * z * (3.0 - 7.0 * z2_r2);
+        let accel = Vector3::new(
+            x * xy_factor,
+            y * xy_factor,
+            j2_factor * z * (3.0 - 5.0 * z2_r2)
+                + j3_factor * (6.0 * z * z - 7.0 * z * z * z2_r2 - 0.6 * r2),
+        );
+
+        // The rotation of the body does not change the acceleration, so there's no transport theorem here.
+        Ok(dcm * accel)
+    }
+
+    fn dual_eom(
+        &self,
+        osc: &Orbit,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3<f64>), DynamicsError> {
+        let (mu_km3_s2, eq_radius_km) = mu_and_radius(self.compute_frame)?;
+        let (radius_km, dcm) = body_fixed(self.compute_frame, osc, almanac)?;
+
+        let radius: Vector3<OHyperdual<f64, U7>> = hyperspace_from_vector(&radius_km);
+        let (x, y, z) = (radius[0], radius[1], radius[2]);
+
+        let r = norm(&radius);
+        let r2 = r * r;
+        let z2_r2 = z * z / r2;
+
+        let one = OHyperdual::<f64, U7>::from(1.0);
+        let three = OHyperdual::<f64, U7>::from(3.0);
+        let five = OHyperdual::<f64, U7>::from(5.0);
+        let six = OHyperdual::<f64, U7>::from(6.0);
+        let seven = OHyperdual::<f64, U7>::from(7.0);
+
+        let j2_factor =
+            OHyperdual::from(-1.5 * self.j2 * mu_km3_s2 * eq_radius_km.powi(2)) / r.powi(5);
+        let j3_factor =
+            OHyperdual::from(-2.5 * self.j3 * mu_km3_s2 * eq_radius_km.powi(3)) / r.powi(7);
+
+        let xy_factor = j2_factor * (one - five * z2_r2) + j3_factor * z * (three - seven * z2_r2);
+        let accel = Vector3::new(
+            x * xy_factor,
+            y * xy_factor,
+            j2_factor * z * (three - five * z2_r2)
+                + j3_factor * (six * z * z - seven * z * z * z2_r2 - OHyperdual::from(0.6) * r2),
+        );
+
+        // Extract the acceleration and its partials in the compute frame
+        let mut accel_body = Vector3::zeros();
+        let mut grad_body = Matrix3::zeros();
+        for i in 0..3 {
+            accel_body[i] = accel[i].real();
+            for j in 1..4 {
+                grad_body[(i, j - 1)] = accel[i][j];
+            }
+        }
+
+        // Rotate both into the integration frame
+        Ok((dcm * accel_body, dcm * grad_body * dcm.transpose()))
+    }
+}
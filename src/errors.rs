@@ -38,6 +38,8 @@ pub enum NyxError {
     LambertNotReasonablePhi,
     #[snafu(display("Use the Izzo algorithm for multi-rev transfers"))]
     LambertMultiRevNotSupported,
+    #[snafu(display("Lambert transfer is not feasible: {msg}"))]
+    LambertNotFeasible { msg: String },
     #[snafu(display("Unavailable parameter {param:?}: {msg}"))]
     StateParameterUnavailable { param: StateParameter, msg: String },
     #[snafu(display("Could not load file: {msg}"))]
@@ -67,6 +69,10 @@ pub enum NyxError {
     MonteCarlo { msg: String },
     #[snafu(display("CCSDS error: {msg}"))]
     CCSDS { msg: String },
+    #[snafu(display("TLE line {line}: {msg}"))]
+    TleParsing { line: usize, msg: String },
+    #[snafu(display("SGP4 error: {msg}"))]
+    Sgp4 { msg: String },
     #[snafu(display("Error: {msg}"))]
     CustomError { msg: String },
     #[snafu(display("Trajectory error: {source}"))]
@@ -0,0 +1,207 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use super::{AlmanacLoadingSnafu, InputOutputError};
+use anise::almanac::Almanac;
+use snafu::ResultExt;
+use std::env;
+use std::path::PathBuf;
+
+/// Name of the environment variable pointing to the directory of the Nyx data files.
+pub const NYX_DATA_DIR: &str = "NYX_DATA_DIR";
+/// File name of the default planetary ephemeris.
+pub const DEFAULT_EPHEMERIS: &str = "de440s.bsp";
+/// File name of the default planetary constants.
+pub const DEFAULT_PLANETARY_CONSTANTS: &str = "pck08.pca";
+/// File name of the default high precision Earth orientation parameters.
+pub const DEFAULT_EARTH_ORIENTATION: &str = "earth_latest_high_prec.bpc";
+
+/// Returns the directories searched for data files, in order of priority:
+/// 1. the directory in the `NYX_DATA_DIR` environment variable, if set;
+/// 2. the current working directory;
+/// 3. the user configuration directory, i.e. `$XDG_CONFIG_HOME/nyx-space` or `$HOME/.config/nyx-space` (or `%APPDATA%\nyx-space` on Windows).
+pub fn data_search_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Ok(data_dir) = env::var(NYX_DATA_DIR) {
+        paths.push(PathBuf::from(data_dir));
+    }
+
+    if let Ok(cur_dir) = env::current_dir() {
+        paths.push(cur_dir);
+    }
+
+    let config_dir = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|_| env::var("APPDATA").map(PathBuf::from));
+
+    if let Ok(config_dir) = config_dir {
+        paths.push(config_dir.join("nyx-space"));
+    }
+
+    paths
+}
+
+/// Searches for the provided data file in the data search paths (cf. [data_search_paths]) and returns the path of the first match.
+pub fn find_data_file(file_name: &str) -> Result<PathBuf, InputOutputError> {
+    let searched = data_search_paths();
+    for dir in &searched {
+        let path = dir.join(file_name);
+        if path.is_file() {
+            return Ok(path);
+        }
+    }
+
+    Err(InputOutputError::DataNotFound {
+        which: file_name.to_string(),
+        searched,
+    })
+}
+
+/// Builds an Almanac from the default data files found in the data search paths (cf. [data_search_paths]).
+///
+/// The planetary ephemeris (`de440s.bsp`) and the planetary constants (`pck08.pca`) are required.
+/// The high precision Earth orientation parameters (`earth_latest_high_prec.bpc`) are loaded if found.
+pub fn try_default_almanac() -> Result<Almanac, InputOutputError> {
+    let pck = find_data_file(DEFAULT_PLANETARY_CONSTANTS)?;
+    let ephem = find_data_file(DEFAULT_EPHEMERIS)?;
+
+    let mut almanac = Almanac::new(&pck.to_string_lossy()).context(AlmanacLoadingSnafu {
+        action: "loading default planetary constants",
+    })?;
+
+    almanac = almanac
+        .load(&ephem.to_string_lossy())
+        .context(AlmanacLoadingSnafu {
+            action: "loading default planetary ephemeris",
+        })?;
+
+    match find_data_file(DEFAULT_EARTH_ORIENTATION) {
+        Ok(eop) => {
+            almanac = almanac
+                .load(&eop.to_string_lossy())
+                .context(AlmanacLoadingSnafu {
+                    action: "loading default Earth orientation parameters",
+                })?;
+        }
+        Err(e) => warn!("{e}: high precision Earth orientation will not be available"),
+    }
+
+    Ok(almanac)
+}
+
+#[cfg(test)]
+mod ut_data {
+    use super::*;
+    use std::ffi::OsString;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+
+    /// Serializes the tests which modify the environment variables of the process.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Sets the provided environment variables (or removes them if None) and restores their previous values when dropped.
+    struct EnvGuard {
+        previous: Vec<(&'static str, Option<OsString>)>,
+    }
+
+    impl EnvGuard {
+        fn set(vars: &[(&'static str, Option<&Path>)]) -> Self {
+            let previous = vars
+                .iter()
+                .map(|(key, _)| (*key, env::var_os(key)))
+                .collect();
+            for (key, value) in vars {
+                match value {
+                    Some(value) => env::set_var(key, value),
+                    None => env::remove_var(key),
+                }
+            }
+            Self { previous }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for (key, value) in &self.previous {
+                match value {
+                    Some(value) => env::set_var(key, value),
+                    None => env::remove_var(key),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn default_almanac_from_env() {
+        let _lock = ENV_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let data_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("data");
+
+        // Empty configuration directory, such that the search does not depend on the data installed on this machine
+        let config_dir = env::temp_dir().join(format!("nyx-data-search-{}", std::process::id()));
+        let nyx_config_dir = config_dir.join("nyx-space");
+        fs::create_dir_all(&nyx_config_dir).unwrap();
+
+        {
+            let _env = EnvGuard::set(&[
+                (NYX_DATA_DIR, Some(&data_dir)),
+                ("XDG_CONFIG_HOME", Some(&config_dir)),
+            ]);
+            assert_eq!(data_search_paths()[0], data_dir);
+            assert_eq!(*data_search_paths().last().unwrap(), nyx_config_dir);
+            assert_eq!(
+                find_data_file(DEFAULT_EPHEMERIS).unwrap(),
+                data_dir.join(DEFAULT_EPHEMERIS)
+            );
+            assert!(try_default_almanac().is_ok());
+        }
+
+        {
+            let _env =
+                EnvGuard::set(&[(NYX_DATA_DIR, None), ("XDG_CONFIG_HOME", Some(&config_dir))]);
+            assert!(!data_search_paths().contains(&data_dir));
+
+            // Files are found in the configuration directory
+            let config_file = nyx_config_dir.join("nyx-data-search.txt");
+            fs::write(&config_file, "test").unwrap();
+            assert_eq!(find_data_file("nyx-data-search.txt").unwrap(), config_file);
+
+            match find_data_file("not-a-nyx-file.bsp") {
+                Err(InputOutputError::DataNotFound { which, searched }) => {
+                    assert_eq!(which, "not-a-nyx-file.bsp");
+                    assert_eq!(searched, data_search_paths());
+                }
+                other => panic!("expected a DataNotFound error, got {other:?}"),
+            }
+
+            // The data files are neither in the current directory (the crate root) nor in the empty configuration directory,
+            // so the loading fails with a descriptive error.
+            let err = try_default_almanac().unwrap_err();
+            let msg = format!("{err}");
+            println!("{msg}");
+            assert!(msg.contains(&nyx_config_dir.to_string_lossy().to_string()));
+        }
+
+        fs::remove_dir_all(&config_dir).unwrap();
+    }
+}
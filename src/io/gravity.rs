@@ -23,6 +23,12 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::str::FromStr;
 
+/// Unnormalized J<sub>2</sub> of the Earth from the JGM3 model, i.e. the same value as [HarmonicsMem::j2_jgm3] once unnormalized.
+pub const EARTH_J2_JGM3: f64 = 1.082_626_690_597_816_5e-3;
+
+/// Unnormalized J<sub>3</sub> of the Earth from the JGM3 model.
+pub const EARTH_J3_JGM3: f64 = -2.532_410_518_567_722_5e-6;
+
 /// `HarmonicsMem` loads the requested gravity potential files and stores them in memory (in a HashMap).
 ///
 /// WARNING: This memory backend may require a lot of RAM (e.g. EMG2008 2190x2190 requires nearly 400 MB of RAM).
@@ -50,7 +56,7 @@ impl HarmonicsMem {
 
     /// Initialize `HarmonicsMem` as an EARTH J<sub>2</sub> only using the JGM3 model (available in GMAT)
     ///
-    /// Use the embedded Earth parameter. If others are needed, load from `from_shadr` or `from_egm`.
+    /// Use the embedded Earth parameter. If others are needed, load from `from_shadr`, `from_egm` or `from_gfc`.
     /// *WARNING:* This is an EARTH gravity model, and _should not_ be used around any other body.
     pub fn j2_jgm3() -> HarmonicsMem {
         Self::from_j2(-4.841_653_748_864_70e-04)
@@ -58,7 +64,7 @@ impl HarmonicsMem {
 
     /// Initialize `HarmonicsMem` as an EARTH J<sub>2</sub> only using the JGM2 model (available in GMAT)
     ///
-    /// Use the embedded Earth parameter. If others are needed, load from `from_shadr` or `from_egm`.
+    /// Use the embedded Earth parameter. If others are needed, load from `from_shadr`, `from_egm` or `from_gfc`.
     /// *WARNING:* This is an EARTH gravity model, and _should not_ be used around any other body.
     pub fn j2_jgm2() -> HarmonicsMem {
         Self::from_j2(-4.841_653_9e-04)
@@ -320,6 +326,115 @@ impl HarmonicsMem {
         })
     }
 
+    /// Initialize `HarmonicsMem` from an ICGEM gravity field file (`.gfc`), e.g. EGM2008 as distributed by ICGEM.
+    ///
+    /// Only the static `gfc` and `gfct` coefficients are loaded, time variable terms (`trnd`, `acos`, `asin`) are ignored.
+    /// The coefficients must be fully normalized, as expected by the `Harmonics` model.
+    pub fn from_gfc(
+        filepath: &str,
+        degree: usize,
+        order: usize,
+        gunzipped: bool,
+    ) -> Result<HarmonicsMem, NyxError> {
+        let mut f = File::open(filepath).map_err(|_| NyxError::FileUnreadable {
+            msg: format!("File not found: {filepath}"),
+        })?;
+        let mut buffer = vec![0; 0];
+        if gunzipped {
+            let mut d = GzDecoder::new(f);
+            d.read_to_end(&mut buffer)
+                .map_err(|_| NyxError::FileUnreadable {
+                    msg: "could not read file as gunzip".to_string(),
+                })?;
+        } else {
+            f.read_to_end(&mut buffer)
+                .map_err(|_| NyxError::FileUnreadable {
+                    msg: "could not read file to end".to_string(),
+                })?;
+        }
+
+        let data_as_str = String::from_utf8(buffer).map_err(|_| NyxError::FileUnreadable {
+            msg: "could not decode file contents as utf8".to_string(),
+        })?;
+
+        let mut c_nm_mat = DMatrix::from_element(degree + 1, degree + 1, 0.0);
+        let mut s_nm_mat = DMatrix::from_element(degree + 1, degree + 1, 0.0);
+        let mut max_order: usize = 0;
+        let mut max_degree: usize = 0;
+        let mut in_header = true;
+        for (lno, line) in data_as_str.lines().enumerate() {
+            let mut items = line.split_whitespace();
+            let key = match items.next() {
+                Some(key) => key,
+                None => continue,
+            };
+
+            if in_header {
+                if key == "end_of_head" {
+                    in_header = false;
+                } else if key == "norm" && items.next() != Some("fully_normalized") {
+                    return Err(NyxError::FileUnreadable {
+                        msg: format!("Harmonics file: {filepath} is not fully normalized"),
+                    });
+                }
+                continue;
+            }
+
+            if key != "gfc" && key != "gfct" {
+                continue; // Time variable terms are not supported
+            }
+
+            let parse_usize = |item: Option<&str>, what: &str| -> Result<usize, NyxError> {
+                item.and_then(|item| usize::from_str(item).ok())
+                    .ok_or_else(|| NyxError::FileUnreadable {
+                        msg: format!("Harmonics file: could not parse {what} on line {lno}"),
+                    })
+            };
+            let parse_f64 = |item: Option<&str>, what: &str| -> Result<f64, NyxError> {
+                item.and_then(|item| f64::from_str(&item.replace(['D', 'd'], "E")).ok())
+                    .ok_or_else(|| NyxError::FileUnreadable {
+                        msg: format!("Harmonics file: could not parse {what} on line {lno}"),
+                    })
+            };
+
+            let cur_degree = parse_usize(items.next(), "degree")?;
+            let cur_order = parse_usize(items.next(), "order")?;
+            let c_nm = parse_f64(items.next(), "C_nm")?;
+            let s_nm = parse_f64(items.next(), "S_nm")?;
+            // We aren't storing the covariance of these harmonics
+
+            if cur_degree > degree || cur_order > order {
+                // ICGEM files may be sorted by order instead of by degree, so we can't stop reading early.
+                continue;
+            }
+
+            c_nm_mat[(cur_degree, cur_order)] = c_nm;
+            s_nm_mat[(cur_degree, cur_order)] = s_nm;
+            max_order = max_order.max(cur_order);
+            max_degree = max_degree.max(cur_degree);
+        }
+
+        if in_header {
+            return Err(NyxError::FileUnreadable {
+                msg: format!("Harmonics file: {filepath} has no `end_of_head` line"),
+            });
+        }
+
+        if max_degree < degree || max_order < order {
+            warn!(
+                "{filepath} only contained (degree, order) of ({max_degree}, {max_order}) instead of requested ({degree}, {order})",
+            );
+        } else {
+            info!("{filepath} loaded with (degree, order) = ({degree}, {order})");
+        }
+        Ok(HarmonicsMem {
+            degree: max_degree,
+            order: max_order,
+            c_nm: c_nm_mat,
+            s_nm: s_nm_mat,
+        })
+    }
+
     /// `load` handles the actual loading in memory.
     fn load(
         gunzipped: bool,
@@ -477,4 +592,7 @@ fn test_load_harmonic_files() {
 
     HarmonicsMem::from_shadr("data/Luna_jggrx_1500e_sha.tab.gz", 1500, 1500, true)
         .expect("could not load jggrx");
+
+    HarmonicsMem::from_gfc("data/EGM2008_to20_TideFree.gfc", 20, 20, false)
+        .expect("could not load EGM2008 gfc");
 }
@@ -20,12 +20,13 @@ use crate::errors::NyxError;
 use crate::md::StateParameter;
 use crate::time::Epoch;
 
+use anise::errors::AlmanacError;
 use arrow::error::ArrowError;
 use parquet::errors::ParquetError;
 use snafu::prelude::*;
 pub(crate) mod watermark;
 use hifitime::prelude::{Format, Formatter};
-use hifitime::Duration;
+use hifitime::{Duration, TimeScale};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer};
 use serde::{Serialize, Serializer};
@@ -42,6 +43,8 @@ use typed_builder::TypedBuilder;
 
 /// Handles writing to an XYZV file
 pub mod cosmo;
+/// Searches for the default data files and builds an Almanac from them.
+pub mod data;
 pub mod estimate;
 /// Handles loading of gravity models using files of NASA PDS and GMAT COF. Several gunzipped files are provided with nyx.
 pub mod gravity;
@@ -77,6 +80,14 @@ pub struct ExportCfg {
     /// Set to true to append the timestamp to the filename
     #[builder(default)]
     pub timestamp: bool,
+    /// Set to true to also export the state deviation of orbit determination estimates in the RIC frame (ignored for trajectories)
+    #[builder(default)]
+    #[serde(default)]
+    pub ric_state_deviation: bool,
+    /// Time scale of the epochs of CCSDS OEM exports (e.g. UTC or TAI), defaults to the time scale of the first state (ignored for Parquet)
+    #[builder(default, setter(strip_option))]
+    #[serde(default)]
+    pub time_scale: Option<TimeScale>,
 }
 
 impl ExportCfg {
@@ -100,6 +111,14 @@ impl ExportCfg {
         }
     }
 
+    /// Initialize a new default configuration which only exports the provided fields, e.g. the Keplerian elements.
+    pub fn from_fields(fields: &[StateParameter]) -> Self {
+        Self {
+            fields: Some(fields.to_vec()),
+            ..Default::default()
+        }
+    }
+
     pub fn append_field(&mut self, field: StateParameter) {
         if let Some(fields) = self.fields.as_mut() {
             fields.push(field);
@@ -204,6 +223,17 @@ pub enum InputOutputError {
     ParseDhall { data: String, err: String },
     #[snafu(display("error serializing {what} to Dhall: {err}"))]
     SerializeDhall { what: String, err: String },
+    #[snafu(display("could not find `{which}`, searched in {searched:?}"))]
+    DataNotFound {
+        which: String,
+        searched: Vec<PathBuf>,
+    },
+    #[snafu(display("{action} encountered an Almanac error: {source}"))]
+    AlmanacLoading {
+        #[snafu(source(from(AlmanacError, Box::new)))]
+        source: Box<AlmanacError>,
+        action: &'static str,
+    },
 }
 
 impl PartialEq for InputOutputError {
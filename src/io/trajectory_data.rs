@@ -32,7 +32,7 @@ use crate::{
 };
 
 #[cfg(feature = "python")]
-use crate::python::mission_design::{OrbitTraj as OrbitTrajPy, SpacecraftTraj as ScTrajPy};
+use crate::python::mission_design::SpacecraftTraj as ScTrajPy;
 #[cfg(feature = "python")]
 use crate::python::PythonError;
 #[cfg(feature = "python")]
@@ -40,11 +40,15 @@ use crate::NyxError;
 #[cfg(feature = "python")]
 use crate::Spacecraft;
 #[cfg(feature = "python")]
+use anise::almanac::Almanac;
+#[cfg(feature = "python")]
 use log::warn;
 #[cfg(feature = "python")]
 use pyo3::class::basic::CompareOp;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use std::sync::Arc;
 
 use super::{InputOutputError, ParquetSnafu, StdIOSnafu};
 
@@ -291,16 +295,18 @@ impl Display for TrajectoryLoader {
 #[cfg(feature = "python")]
 #[pymethods]
 impl TrajectoryLoader {
-    /// Initializes a new dynamic trajectory from the provided file, and the format kind
+    /// Initializes a new dynamic trajectory from the provided file, and the format kind.
+    /// Loading an OEM requires the almanac, which provides the frame data of the trajectory.
     #[new]
     #[pyo3(
-        text_signature = "(path, format='parquet', parquet_path=None, spacecraft_template=None)"
+        text_signature = "(path, format='parquet', parquet_path=None, spacecraft_template=None, almanac=None)"
     )]
     fn new(
         path: String,
         format: Option<String>,
         parquet_path: Option<String>,
         spacecraft_template: Option<Spacecraft>,
+        almanac: Option<Almanac>,
     ) -> Result<Self, NyxError> {
         if format.is_none() {
             Self::from_parquet(path).map_err(|e| NyxError::CustomError { msg: e.to_string() })
@@ -317,6 +323,9 @@ impl TrajectoryLoader {
                                 .to_string(),
                         });
                     }
+                    let almanac = almanac.ok_or_else(|| NyxError::CustomError {
+                        msg: "Loading an OEM requires the `almanac` parameter".to_string(),
+                    })?;
                     let sc_tpl = match spacecraft_template {
                         Some(sc) => sc,
                         None => {
@@ -325,10 +334,14 @@ impl TrajectoryLoader {
                         }
                     };
 
-                    let traj = Traj::<Spacecraft>::from_oem_file(path, sc_tpl)?;
+                    let traj = Traj::<Spacecraft>::from_oem_file_with_almanac(
+                        path,
+                        Some(sc_tpl),
+                        &almanac,
+                    )?;
                     let out_pq = parquet_path.unwrap();
                     // Convert to parquet
-                    traj.to_parquet_simple(&out_pq)
+                    traj.to_parquet_simple(&out_pq, Arc::new(almanac))
                         .map_err(|e| NyxError::CustomError { msg: e.to_string() })?;
                     // Return Self with this path
                     Self::from_parquet(out_pq)
@@ -341,16 +354,7 @@ impl TrajectoryLoader {
         }
     }
 
-    /// Converts this loaded trajectory into an Orbit trajectory (no spacecraft data)
-    fn to_orbit_traj(&self) -> Result<OrbitTrajPy, NyxError> {
-        Ok(OrbitTrajPy {
-            inner: self
-                .to_traj()
-                .map_err(|e| NyxError::CustomError { msg: e.to_string() })?,
-        })
-    }
-
-    /// Converts this loaded trajectory into an Orbit trajectory (no spacecraft data)
+    /// Converts this loaded trajectory into a spacecraft trajectory
     fn to_spacecraft_traj(&self) -> Result<ScTrajPy, NyxError> {
         Ok(ScTrajPy {
             inner: self
@@ -35,4 +35,4 @@ mod multivariate;
 pub use multivariate::MultivariateNormal;
 
 mod results;
-pub use results::{Results, Stats};
+pub use results::{FinalStateStats, Results, Stats};
@@ -21,14 +21,15 @@ use crate::dynamics::Dynamics;
 use crate::linalg::allocator::Allocator;
 use crate::linalg::DefaultAllocator;
 use crate::mc::results::{PropResult, Results, Run};
-use crate::mc::DispersedState;
+use crate::mc::{DispersedState, MultivariateNormal};
 use crate::md::trajectory::Interpolatable;
 use crate::md::EventEvaluator;
+use crate::od::estimate::KfEstimate;
 use crate::propagators::{ErrorCtrl, Propagator};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::time::Unit;
 use crate::time::{Duration, Epoch};
-use crate::State;
+use crate::{Spacecraft, State};
 use anise::almanac::Almanac;
 use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
 use log::info;
@@ -36,6 +37,7 @@ use rand::SeedableRng;
 use rand_distr::Distribution;
 use rayon::prelude::ParallelIterator;
 use rayon::prelude::*;
+use std::error::Error;
 use std::fmt;
 use std::sync::mpsc::channel;
 use std::sync::Arc;
@@ -185,6 +187,28 @@ where
         }
     }
 
+    /// Generate states and propagate each independently for the provided duration past the epoch of the nominal state.
+    #[must_use = "Monte Carlo result must be used"]
+    #[allow(clippy::needless_lifetimes)]
+    pub fn run_for_duration<'a, D, E>(
+        self,
+        prop: Propagator<'a, D, E>,
+        almanac: Arc<Almanac>,
+        duration: Duration,
+        num_runs: usize,
+    ) -> Results<S, PropResult<S>>
+    where
+        D: Dynamics<StateType = S>,
+        E: ErrorCtrl,
+        DefaultAllocator: Allocator<<D::StateType as State>::Size>
+            + Allocator<<D::StateType as State>::Size, <D::StateType as State>::Size>
+            + Allocator<<D::StateType as State>::VecLength>,
+        <DefaultAllocator as Allocator<<D::StateType as State>::VecLength>>::Buffer<f64>: Send,
+    {
+        let end_epoch = self.nominal_state.epoch() + duration;
+        self.resume_run_until_epoch(prop, almanac, 0, end_epoch, num_runs)
+    }
+
     /// Generate states and propagate each independently until a specific event is found `trigger` times.
     #[must_use = "Monte Carlo result must be used"]
     #[allow(clippy::needless_lifetimes)]
@@ -301,6 +325,23 @@ where
     }
 }
 
+impl MonteCarlo<Spacecraft, MultivariateNormal> {
+    /// Initializes a Monte Carlo whose states are drawn from the multivariate normal distribution of the provided estimate,
+    /// i.e. centered on its state (nominal state plus state deviation) and dispersed by its covariance.
+    pub fn from_estimate(
+        estimate: &KfEstimate<Spacecraft>,
+        scenario: String,
+        seed: Option<u128>,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(
+            estimate.nominal_state,
+            estimate.to_random_variable()?,
+            scenario,
+            seed,
+        ))
+    }
+}
+
 impl<S: Interpolatable, Distr: Distribution<DispersedState<S>>> fmt::Display
     for MonteCarlo<S, Distr>
 where
@@ -26,12 +26,13 @@ use crate::errors::{MonteCarloError, NoSuccessfulRunsSnafu, StateError};
 use crate::io::watermark::pq_writer;
 use crate::io::{ExportCfg, InputOutputError};
 use crate::linalg::allocator::Allocator;
-use crate::linalg::DefaultAllocator;
+use crate::linalg::{DefaultAllocator, DimName, OVector};
 use crate::md::prelude::GuidanceMode;
 use crate::md::trajectory::{Interpolatable, Traj};
 use crate::md::{EventEvaluator, StateParameter};
 use crate::propagators::PropagationError;
 use crate::time::{Duration, Epoch, TimeUnits};
+use crate::State;
 use anise::almanac::Almanac;
 use anise::constants::frames::EARTH_J2000;
 use arrow::array::{Array, Float64Builder, Int32Builder, StringBuilder};
@@ -80,6 +81,21 @@ where
     pub traj: Traj<S>,
 }
 
+/// Per-component statistics of the final states of the successful runs of a Monte Carlo, in the order of the state vector
+/// (e.g. X, Y, Z, VX, VY, VZ, Cr, Cd, and fuel mass for a spacecraft).
+#[derive(Clone, Debug)]
+pub struct FinalStateStats<S: State>
+where
+    DefaultAllocator: Allocator<S::Size> + Allocator<S::Size, S::Size> + Allocator<S::VecLength>,
+{
+    /// Number of successful runs used to compute these statistics
+    pub num_runs: usize,
+    /// Mean of each component of the final states
+    pub mean: OVector<f64, S::Size>,
+    /// Sample standard deviation of each component of the final states, i.e. the final dispersion
+    pub std_dev: OVector<f64, S::Size>,
+}
+
 impl<S: Interpolatable> Results<S, PropResult<S>>
 where
     DefaultAllocator: Allocator<S::Size> + Allocator<S::Size, S::Size> + Allocator<S::VecLength>,
@@ -239,6 +255,54 @@ where
         Ok(report)
     }
 
+    /// Returns the per-component mean and standard deviation of the final states of the successful runs.
+    pub fn final_state_stats(&self) -> Result<FinalStateStats<S>, MonteCarloError> {
+        let finals = self
+            .runs
+            .iter()
+            .filter_map(|run| run.result.as_ref().ok())
+            .map(|success| {
+                OVector::<f64, S::Size>::from_iterator(
+                    success
+                        .state
+                        .to_vector()
+                        .iter()
+                        .copied()
+                        .take(S::Size::dim()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        ensure!(
+            !finals.is_empty(),
+            NoSuccessfulRunsSnafu {
+                action: "compute the final state statistics",
+                num_runs: self.runs.len()
+            }
+        );
+
+        let num_runs = finals.len();
+        let mut mean = OVector::<f64, S::Size>::zeros();
+        for state in &finals {
+            mean += state;
+        }
+        mean /= num_runs as f64;
+
+        let mut variance = OVector::<f64, S::Size>::zeros();
+        if num_runs > 1 {
+            for state in &finals {
+                variance += (state - &mean).map(|delta| delta.powi(2));
+            }
+            variance /= (num_runs - 1) as f64;
+        }
+
+        Ok(FinalStateStats {
+            num_runs,
+            mean,
+            std_dev: variance.map(|var| var.sqrt()),
+        })
+    }
+
     pub fn to_parquet<P: AsRef<Path>>(
         &self,
         path: P,
@@ -0,0 +1,132 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::almanac::Almanac;
+use anise::prelude::Frame;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+use super::ScTraj;
+use crate::cosmic::eclipse::{line_of_sight, EclipseState};
+use crate::errors::{FromAlmanacSnafu, NyxError};
+use crate::time::{Duration, Epoch, TimeSeries, Unit};
+use crate::State;
+
+/// Precision to which the start and end of each access interval is computed
+const ACCESS_EPOCH_PRECISION: Unit = Unit::Millisecond;
+
+/// Computes the intervals during which the line between the spacecraft of both trajectories is not obstructed by the provided body.
+///
+/// The trajectories are sampled every `step` over the time span they have in common, and each change of visibility is then
+/// refined by bisection to within one millisecond. Access changes which happen faster than the step may be missed.
+/// The occultation geometry models the body as a sphere of its mean equatorial radius, cf. [line_of_sight].
+#[allow(clippy::identity_op)]
+pub fn crosslink(
+    traj_a: &ScTraj,
+    traj_b: &ScTraj,
+    body: Frame,
+    step: Duration,
+    almanac: Arc<Almanac>,
+) -> Result<Vec<(Epoch, Epoch)>, NyxError> {
+    if traj_a.states.is_empty() || traj_b.states.is_empty() {
+        return Err(NyxError::NoStateData {
+            msg: "cannot compute crosslink access with an empty trajectory".to_string(),
+        });
+    }
+
+    if step <= Duration::ZERO {
+        return Err(NyxError::CustomError {
+            msg: format!("crosslink access requires a positive step, got {step}"),
+        });
+    }
+
+    let start = traj_a.first().epoch().max(traj_b.first().epoch());
+    let end = traj_a.last().epoch().min(traj_b.last().epoch());
+
+    if start >= end {
+        return Err(NyxError::NoStateData {
+            msg: format!(
+                "trajectories do not overlap: [{}, {}] and [{}, {}]",
+                traj_a.first().epoch(),
+                traj_a.last().epoch(),
+                traj_b.first().epoch(),
+                traj_b.last().epoch()
+            ),
+        });
+    }
+
+    // Ensure that the occulting body has its shape information
+    let body = if body.mean_equatorial_radius_km().is_err() {
+        almanac.frame_from_uid(body).context(FromAlmanacSnafu {
+            action: "fetching the occulting body for crosslink access",
+        })?
+    } else {
+        body
+    };
+
+    let visible = |epoch: Epoch| -> Result<bool, NyxError> {
+        let sc_a = traj_a.at(epoch)?;
+        let sc_b = traj_b.at(epoch)?;
+        Ok(
+            line_of_sight(sc_a.orbit, sc_b.orbit, body, &almanac).context(FromAlmanacSnafu {
+                action: "computing line of sight for crosslink access",
+            })? == EclipseState::Visibilis,
+        )
+    };
+
+    let mut epochs: Vec<Epoch> = TimeSeries::inclusive(start, end, step).collect();
+    if epochs.last() != Some(&end) {
+        epochs.push(end);
+    }
+
+    let mut intervals = Vec::new();
+    let mut prev_epoch = start;
+    let mut prev_visible = visible(start)?;
+    let mut access_start = if prev_visible { Some(start) } else { None };
+
+    for epoch in epochs.into_iter().skip(1) {
+        let cur_visible = visible(epoch)?;
+        if cur_visible != prev_visible {
+            // Bisect the change of visibility
+            let mut lower = prev_epoch;
+            let mut upper = epoch;
+            while upper - lower > 1 * ACCESS_EPOCH_PRECISION {
+                let mid = lower + 0.5 * (upper - lower);
+                if visible(mid)? == prev_visible {
+                    lower = mid;
+                } else {
+                    upper = mid;
+                }
+            }
+
+            if cur_visible {
+                access_start = Some(upper);
+            } else if let Some(access_start) = access_start.take() {
+                intervals.push((access_start, lower));
+            }
+        }
+        prev_epoch = epoch;
+        prev_visible = cur_visible;
+    }
+
+    if let Some(access_start) = access_start {
+        intervals.push((access_start, end));
+    }
+
+    Ok(intervals)
+}
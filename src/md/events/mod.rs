@@ -154,6 +154,20 @@ impl Event {
         Self::new(StateParameter::Apoapsis, 180.0)
     }
 
+    /// Match the impact with the surface of the ellipsoid of the provided body fixed frame, i.e. a zero height, to the meter.
+    ///
+    /// Use this as the terminal condition of `until_event` to stop the propagation at the impact state instead of
+    /// propagating into negative heights, e.g. for re-entry or lithobraking scenarios.
+    pub fn surface_impact(body_fixed_frame: Frame) -> Self {
+        Self {
+            parameter: StateParameter::Height,
+            desired_value: 0.0,
+            epoch_precision: Unit::Millisecond,
+            value_precision: 1e-3,
+            obs_frame: Some(body_fixed_frame),
+        }
+    }
+
     /// Match a specific event in another frame, using the default epoch precision and value.
     pub fn in_frame(parameter: StateParameter, desired_value: f64, target_frame: Frame) -> Self {
         warn!("Searching for an event in another frame is slow: you should instead convert the trajectory into that other frame");
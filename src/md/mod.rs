@@ -26,7 +26,7 @@ use snafu::prelude::*;
 pub mod prelude {
     pub use super::{
         optimizer::*,
-        trajectory::{ExportCfg, Interpolatable, Traj},
+        trajectory::{Conjunction, ExportCfg, Interpolatable, Traj},
         Event, ScTraj, StateParameter,
     };
     pub use crate::cosmic::{try_achieve_b_plane, BPlane, BPlaneTarget, GuidanceMode, OrbitDual};
@@ -47,11 +47,18 @@ pub mod prelude {
 
 pub mod trajectory;
 
+/// Access intervals between trajectories
+pub mod access;
+
 pub(crate) mod events;
+pub use events::details::{EventDetails, EventEdge};
 pub use events::{Event, EventEvaluator};
 
 pub mod objective;
 pub mod opti;
+
+/// Analytic impulsive transfers between circular orbits (Hohmann and bi-elliptic)
+pub mod transfers;
 pub use opti::optimizer;
 pub type ScTraj = trajectory::Traj<Spacecraft>;
 // pub type Ephemeris = trajectory::Traj<Orbit>;
@@ -28,6 +28,7 @@ pub use crate::md::{Variable, Vary};
 use crate::polyfit::CommonPolynomial;
 use crate::propagators::error_ctrl::ErrorCtrl;
 use crate::pseudo_inverse;
+use crate::utils::{FiniteDiff, FiniteDiffStep};
 use hifitime::TimeUnits;
 use rayon::prelude::*;
 use snafu::{ensure, ResultExt};
@@ -301,176 +302,178 @@ impl<'a, E: ErrorCtrl, const V: usize, const O: usize> Optimizer<'a, E, V, O> {
                     .collect();
 
                 pert_calc.par_iter_mut().for_each(|(_, var, jac_val)| {
-                    let mut this_xi = xi;
+                    let mut opposed_pert = false;
 
-                    let mut this_prop = self.prop.clone();
-                    let mut this_mnvr = mnvr;
+                    let eval = |pert: f64| -> f64 {
+                        let mut this_xi = xi;
 
-                    let mut opposed_pert = false;
+                        let mut this_prop = self.prop.clone();
+                        let mut this_mnvr = mnvr;
 
-                    if var.component.is_finite_burn() {
-                        // Modify the burn itself
-                        let pert = var.perturbation;
-                        // Modify the maneuver, but do not change the epochs of the maneuver unless the change is greater than one millisecond
-                        match var.component {
-                            Vary::Duration => {
-                                if pert.abs() > 1e-3 {
-                                    this_mnvr.end = mnvr.start + pert.seconds()
+                        if var.component.is_finite_burn() {
+                            // Modify the burn itself
+                            // Modify the maneuver, but do not change the epochs of the maneuver unless the change is greater than one millisecond
+                            match var.component {
+                                Vary::Duration => {
+                                    if pert.abs() > 1e-3 {
+                                        this_mnvr.end = mnvr.start + pert.seconds()
+                                    }
                                 }
-                            }
-                            Vary::EndEpoch => {
-                                if pert.abs() > 1e-3 {
-                                    this_mnvr.end = mnvr.end + pert.seconds()
+                                Vary::EndEpoch => {
+                                    if pert.abs() > 1e-3 {
+                                        this_mnvr.end = mnvr.end + pert.seconds()
+                                    }
                                 }
-                            }
-                            Vary::StartEpoch => {
-                                if pert.abs() > 1e-3 {
-                                    this_mnvr.start = mnvr.start + pert.seconds()
+                                Vary::StartEpoch => {
+                                    if pert.abs() > 1e-3 {
+                                        this_mnvr.start = mnvr.start + pert.seconds()
+                                    }
                                 }
-                            }
-                            Vary::MnvrAlpha | Vary::MnvrAlphaDot | Vary::MnvrAlphaDDot => {
-                                this_mnvr.alpha_inplane_radians = mnvr
-                                    .alpha_inplane_radians
-                                    .add_val_in_order(pert, var.component.vec_index())
-                                    .unwrap();
-                            }
-                            Vary::MnvrDelta | Vary::MnvrDeltaDot | Vary::MnvrDeltaDDot => {
-                                this_mnvr.delta_outofplane_radians = mnvr
-                                    .delta_outofplane_radians
-                                    .add_val_in_order(pert, var.component.vec_index())
-                                    .unwrap();
-                            }
-                            Vary::ThrustX | Vary::ThrustY | Vary::ThrustZ => {
-                                let mut vector = this_mnvr.direction();
-                                vector[var.component.vec_index()] += var.perturbation;
-                                if !var.check_bounds(vector[var.component.vec_index()]).1 {
-                                    // Oops, bound was hit, go the other way
-                                    vector[var.component.vec_index()] -= 2.0 * var.perturbation;
-                                    opposed_pert = true;
+                                Vary::MnvrAlpha | Vary::MnvrAlphaDot | Vary::MnvrAlphaDDot => {
+                                    this_mnvr.alpha_inplane_radians = mnvr
+                                        .alpha_inplane_radians
+                                        .add_val_in_order(pert, var.component.vec_index())
+                                        .unwrap();
                                 }
-                                this_mnvr.set_direction(vector).unwrap();
-                            }
-                            Vary::ThrustRateX | Vary::ThrustRateY | Vary::ThrustRateZ => {
-                                let mut vector = this_mnvr.rate();
-                                vector[(var.component.vec_index() - 1) % 3] += var.perturbation;
-                                if !var
-                                    .check_bounds(vector[(var.component.vec_index() - 1) % 3])
-                                    .1
-                                {
-                                    // Oops, bound was hit, go the other way
-                                    vector[(var.component.vec_index() - 1) % 3] -=
-                                        2.0 * var.perturbation;
-                                    opposed_pert = true;
+                                Vary::MnvrDelta | Vary::MnvrDeltaDot | Vary::MnvrDeltaDDot => {
+                                    this_mnvr.delta_outofplane_radians = mnvr
+                                        .delta_outofplane_radians
+                                        .add_val_in_order(pert, var.component.vec_index())
+                                        .unwrap();
                                 }
-                                this_mnvr.set_rate(vector).unwrap();
-                            }
-                            Vary::ThrustAccelX | Vary::ThrustAccelY | Vary::ThrustAccelZ => {
-                                let mut vector = this_mnvr.accel();
-                                vector[(var.component.vec_index() - 1) % 3] += var.perturbation;
-                                if !var
-                                    .check_bounds(vector[(var.component.vec_index() - 1) % 3])
-                                    .1
-                                {
-                                    // Oops, bound was hit, go the other way
-                                    vector[(var.component.vec_index() - 1) % 3] -=
-                                        2.0 * var.perturbation;
-                                    opposed_pert = true;
+                                Vary::ThrustX | Vary::ThrustY | Vary::ThrustZ => {
+                                    let mut vector = this_mnvr.direction();
+                                    vector[var.component.vec_index()] += pert;
+                                    if !var.check_bounds(vector[var.component.vec_index()]).1 {
+                                        // Oops, bound was hit, go the other way
+                                        vector[var.component.vec_index()] -= 2.0 * pert;
+                                        opposed_pert = true;
+                                    }
+                                    this_mnvr.set_direction(vector).unwrap();
                                 }
-                                this_mnvr.set_accel(vector).unwrap();
-                            }
-                            Vary::ThrustLevel => {
-                                this_mnvr.thrust_prct += var.perturbation;
-                                this_mnvr.thrust_prct = this_mnvr.thrust_prct.clamp(0.0, 1.0);
+                                Vary::ThrustRateX | Vary::ThrustRateY | Vary::ThrustRateZ => {
+                                    let mut vector = this_mnvr.rate();
+                                    vector[(var.component.vec_index() - 1) % 3] += pert;
+                                    if !var
+                                        .check_bounds(vector[(var.component.vec_index() - 1) % 3])
+                                        .1
+                                    {
+                                        // Oops, bound was hit, go the other way
+                                        vector[(var.component.vec_index() - 1) % 3] -= 2.0 * pert;
+                                        opposed_pert = true;
+                                    }
+                                    this_mnvr.set_rate(vector).unwrap();
+                                }
+                                Vary::ThrustAccelX | Vary::ThrustAccelY | Vary::ThrustAccelZ => {
+                                    let mut vector = this_mnvr.accel();
+                                    vector[(var.component.vec_index() - 1) % 3] += pert;
+                                    if !var
+                                        .check_bounds(vector[(var.component.vec_index() - 1) % 3])
+                                        .1
+                                    {
+                                        // Oops, bound was hit, go the other way
+                                        vector[(var.component.vec_index() - 1) % 3] -= 2.0 * pert;
+                                        opposed_pert = true;
+                                    }
+                                    this_mnvr.set_accel(vector).unwrap();
+                                }
+                                Vary::ThrustLevel => {
+                                    this_mnvr.thrust_prct += pert;
+                                    this_mnvr.thrust_prct = this_mnvr.thrust_prct.clamp(0.0, 1.0);
+                                }
+                                _ => unreachable!(),
                             }
-                            _ => unreachable!(),
-                        }
-                    } else {
-                        let mut state_correction = Vector6::<f64>::zeros();
-                        state_correction[var.component.vec_index()] += var.perturbation;
-                        // Now, let's apply the correction to the initial state
-                        if let Some(frame) = self.correction_frame {
-                            // The following will error if the frame is not local
-                            let dcm_vnc2inertial = frame
-                                .dcm_to_inertial(this_xi.orbit)
-                                .context(AstroPhysicsSnafu)
-                                .context(AstroSnafu)
-                                .unwrap()
-                                .rot_mat;
-
-                            let velocity_correction =
-                                dcm_vnc2inertial * state_correction.fixed_rows::<3>(3);
-                            this_xi.orbit.apply_dv_km_s(velocity_correction);
                         } else {
-                            this_xi = xi + state_correction;
+                            let mut state_correction = Vector6::<f64>::zeros();
+                            state_correction[var.component.vec_index()] += pert;
+                            // Now, let's apply the correction to the initial state
+                            if let Some(frame) = self.correction_frame {
+                                // The following will error if the frame is not local
+                                let dcm_vnc2inertial = frame
+                                    .dcm_to_inertial(this_xi.orbit)
+                                    .context(AstroPhysicsSnafu)
+                                    .context(AstroSnafu)
+                                    .unwrap()
+                                    .rot_mat;
+
+                                let velocity_correction =
+                                    dcm_vnc2inertial * state_correction.fixed_rows::<3>(3);
+                                this_xi.orbit.apply_dv_km_s(velocity_correction);
+                            } else {
+                                this_xi = xi + state_correction;
+                            }
                         }
-                    }
 
-                    let this_xf = if finite_burn_target {
-                        // Propagate normally until start of maneuver
-                        let pre_mnvr = this_prop
-                            .with(cur_xi, almanac.clone())
-                            .until_epoch(this_mnvr.start)
-                            .unwrap();
-                        // Add this maneuver to the dynamics, make sure that we don't over-step this maneuver
-                        let prop_opts = this_prop.opts;
-                        this_prop.set_max_step(this_mnvr.duration());
-                        this_prop.dynamics =
-                            this_prop.dynamics.with_guidance_law(Arc::new(this_mnvr));
-                        let post_mnvr = this_prop
-                            .with(
-                                pre_mnvr.with_guidance_mode(GuidanceMode::Thrust),
-                                almanac.clone(),
-                            )
-                            .until_epoch(this_mnvr.end)
-                            .unwrap();
-                        // Reset the propagator options to their previous configuration
-                        this_prop.opts = prop_opts;
-                        // And propagate until the achievement epoch
-                        this_prop
-                            .with(post_mnvr, almanac.clone())
-                            .until_epoch(achievement_epoch)
-                            .unwrap()
-                            .orbit
-                    } else {
-                        this_prop
-                            .with(this_xi, almanac.clone())
-                            .until_epoch(achievement_epoch)
-                            .unwrap()
-                            .orbit
-                    };
-
-                    let xf_dual_obj_frame = match &self.objective_frame {
-                        Some(frame) => {
-                            let orbit_obj_frame = almanac
-                                .transform_to(this_xf, *frame, None)
-                                .context(AstroAlmanacSnafu)
-                                .context(AstroSnafu)
+                        let this_xf = if finite_burn_target {
+                            // Propagate normally until start of maneuver
+                            let pre_mnvr = this_prop
+                                .with(cur_xi, almanac.clone())
+                                .until_epoch(this_mnvr.start)
                                 .unwrap();
+                            // Add this maneuver to the dynamics, make sure that we don't over-step this maneuver
+                            let prop_opts = this_prop.opts;
+                            this_prop.set_max_step(this_mnvr.duration());
+                            this_prop.dynamics =
+                                this_prop.dynamics.with_guidance_law(Arc::new(this_mnvr));
+                            let post_mnvr = this_prop
+                                .with(
+                                    pre_mnvr.with_guidance_mode(GuidanceMode::Thrust),
+                                    almanac.clone(),
+                                )
+                                .until_epoch(this_mnvr.end)
+                                .unwrap();
+                            // Reset the propagator options to their previous configuration
+                            this_prop.opts = prop_opts;
+                            // And propagate until the achievement epoch
+                            this_prop
+                                .with(post_mnvr, almanac.clone())
+                                .until_epoch(achievement_epoch)
+                                .unwrap()
+                                .orbit
+                        } else {
+                            this_prop
+                                .with(this_xi, almanac.clone())
+                                .until_epoch(achievement_epoch)
+                                .unwrap()
+                                .orbit
+                        };
+
+                        let xf_dual_obj_frame = match &self.objective_frame {
+                            Some(frame) => {
+                                let orbit_obj_frame = almanac
+                                    .transform_to(this_xf, *frame, None)
+                                    .context(AstroAlmanacSnafu)
+                                    .context(AstroSnafu)
+                                    .unwrap();
 
-                            OrbitDual::from(orbit_obj_frame)
-                        }
-                        None => OrbitDual::from(this_xf),
-                    };
+                                OrbitDual::from(orbit_obj_frame)
+                            }
+                            None => OrbitDual::from(this_xf),
+                        };
 
-                    let b_plane = if is_bplane_tgt {
-                        Some(BPlane::from_dual(xf_dual_obj_frame).unwrap())
-                    } else {
-                        None
-                    };
+                        let b_plane = if is_bplane_tgt {
+                            Some(BPlane::from_dual(xf_dual_obj_frame).unwrap())
+                        } else {
+                            None
+                        };
+
+                        let partial = if obj.parameter.is_b_plane() {
+                            match obj.parameter {
+                                StateParameter::BdotR => b_plane.unwrap().b_r,
+                                StateParameter::BdotT => b_plane.unwrap().b_t,
+                                StateParameter::BLTOF => b_plane.unwrap().ltof_s,
+                                _ => unreachable!(),
+                            }
+                        } else {
+                            xf_dual_obj_frame.partial_for(obj.parameter).unwrap()
+                        };
 
-                    let partial = if obj.parameter.is_b_plane() {
-                        match obj.parameter {
-                            StateParameter::BdotR => b_plane.unwrap().b_r,
-                            StateParameter::BdotT => b_plane.unwrap().b_t,
-                            StateParameter::BLTOF => b_plane.unwrap().ltof_s,
-                            _ => unreachable!(),
-                        }
-                    } else {
-                        xf_dual_obj_frame.partial_for(obj.parameter).unwrap()
+                        partial.real()
                     };
 
-                    let this_achieved = partial.real();
-                    *jac_val = (this_achieved - achieved) / var.perturbation;
+                    let fd =
+                        FiniteDiff::forward().with_step(FiniteDiffStep::Fixed(var.perturbation));
+                    *jac_val = fd.derivative_from(eval, 0.0, achieved);
                     if opposed_pert {
                         // We opposed the perturbation to ensure we don't over step a min/max bound
                         *jac_val = -*jac_val;
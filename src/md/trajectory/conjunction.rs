@@ -0,0 +1,53 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::linalg::Vector3;
+use crate::time::Epoch;
+use std::fmt;
+
+/// The closest approach between two trajectories, cf. `Traj::closest_approach`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Conjunction {
+    /// Time of closest approach (TCA)
+    pub tca: Epoch,
+    /// Distance between both objects at the TCA, in km
+    pub miss_distance_km: f64,
+    /// Position of the other object with respect to this one at the TCA, in km
+    pub rel_radius_km: Vector3<f64>,
+    /// Velocity of the other object with respect to this one at the TCA, in km/s
+    pub rel_velocity_km_s: Vector3<f64>,
+}
+
+impl Conjunction {
+    /// Returns the relative speed of both objects at the TCA, in km/s
+    pub fn rel_speed_km_s(&self) -> f64 {
+        self.rel_velocity_km_s.norm()
+    }
+}
+
+impl fmt::Display for Conjunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TCA {}: miss distance = {:.6} km, relative speed = {:.6} km/s",
+            self.tca,
+            self.miss_distance_km,
+            self.rel_speed_km_s()
+        )
+    }
+}
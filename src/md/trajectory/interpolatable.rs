@@ -97,13 +97,30 @@ impl Interpolatable for Spacecraft {
             self.orbit.frame,
         );
 
-        // Fuel is linearly interpolated -- should really be a Lagrange interpolation here
-        let first = states.first().unwrap();
-        let last = states.last().unwrap();
-        let fuel_kg_dt =
-            (last.fuel_mass_kg - first.fuel_mass_kg) / (last.epoch() - first.epoch()).to_seconds();
-
-        self.fuel_mass_kg += fuel_kg_dt * (epoch - first.epoch()).to_seconds();
+        // The fuel mass and the coefficients of reflectivity and drag are linearly interpolated between the states surrounding
+        // the requested epoch. Contrary to a polynomial fit over all of the samples, this remains exact on either side of the
+        // start or the end of a finite burn, where the mass flow rate is discontinuous.
+        if n > 1 {
+            let after_idx = states
+                .iter()
+                .position(|state| state.epoch() >= epoch)
+                .unwrap_or(n - 1)
+                .clamp(1, n - 1);
+            let before = &states[after_idx - 1];
+            let after = &states[after_idx];
+
+            let span_s = (after.epoch() - before.epoch()).to_seconds();
+            let frac = if span_s.abs() > 0.0 {
+                (epoch - before.epoch()).to_seconds() / span_s
+            } else {
+                0.0
+            };
+            let lerp = |start: f64, end: f64| start + (end - start) * frac;
+
+            self.fuel_mass_kg = lerp(before.fuel_mass_kg, after.fuel_mass_kg);
+            self.srp.cr = lerp(before.srp.cr, after.srp.cr);
+            self.drag.cd = lerp(before.drag.cd, after.drag.cd);
+        }
 
         Ok(self)
     }
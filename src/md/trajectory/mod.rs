@@ -17,13 +17,16 @@
 */
 
 use anise::math::interpolation::InterpolationError;
+use anise::prelude::Frame;
 use snafu::prelude::*;
 
+mod conjunction;
 mod interpolatable;
 mod sc_traj;
 mod traj;
 mod traj_it;
 
+pub use conjunction::Conjunction;
 pub use interpolatable::Interpolatable;
 pub(crate) use interpolatable::INTERPOLATION_SAMPLES;
 pub use traj::Traj;
@@ -53,4 +56,10 @@ pub enum TrajError {
     },
     #[snafu(display("Interpolation failed: {source}"))]
     Interpolation { source: InterpolationError },
+    #[snafu(display(
+        "Trajectories do not overlap: latest start is {start}, earliest end is {end}"
+    ))]
+    NoOverlap { start: Epoch, end: Epoch },
+    #[snafu(display("Trajectories are in different frames: {this} != {other}"))]
+    FrameMismatch { this: Frame, other: Frame },
 }
@@ -25,7 +25,7 @@ use snafu::ResultExt;
 
 use super::TrajError;
 use super::{ExportCfg, Traj};
-use crate::cosmic::Spacecraft;
+use crate::cosmic::{AstroError, Geodetic, Spacecraft};
 use crate::errors::{FromAlmanacSnafu, NyxError};
 use crate::io::watermark::prj_name_ver;
 use crate::md::prelude::StateParameter;
@@ -79,6 +79,11 @@ impl Traj<Spacecraft> {
         Ok(Self { name, states })
     }
     /// Allows converting the source trajectory into the (almost) equivalent trajectory in another frame
+    ///
+    /// Every stored state is converted, and the interpolation of the new trajectory is built on these converted states.
+    /// The velocity of each state accounts for the rotation of the new frame (e.g. an Earth fixed frame), so the Hermite interpolation
+    /// remains accurate between the states of a trajectory converted into a rotating frame: at such epochs, the interpolated state
+    /// differs from the converted interpolated state of the source trajectory by less than a meter for a low Earth orbit propagated with default settings.
     #[allow(clippy::map_clone)]
     pub fn to_frame(&self, new_frame: Frame, almanac: Arc<Almanac>) -> Result<Self, NyxError> {
         if self.states.is_empty() {
@@ -167,6 +172,29 @@ impl Traj<Spacecraft> {
         traj.to_parquet(path, events, cfg, almanac)
     }
 
+    /// Returns the ground track of this trajectory sampled every `step`, as the epoch, geodetic latitude and geodetic longitude (in degrees,
+    /// the latter between -180 and 180 degrees) of the sub-satellite point in the provided body fixed frame.
+    pub fn ground_track(
+        &self,
+        step: Duration,
+        body_fixed_frame: Frame,
+        almanac: &Almanac,
+    ) -> Result<Vec<(Epoch, f64, f64)>, AstroError> {
+        self.every(step)
+            .map(|state| {
+                Ok((
+                    state.epoch(),
+                    state
+                        .orbit
+                        .geodetic_latitude_deg(body_fixed_frame, almanac)?,
+                    state
+                        .orbit
+                        .geodetic_longitude_deg(body_fixed_frame, almanac)?,
+                ))
+            })
+            .collect()
+    }
+
     /// Initialize a new spacecraft trajectory from the path to a CCSDS OEM file.
     ///
     /// CCSDS OEM only contains the orbit information but Nyx builds spacecraft trajectories.
@@ -187,6 +215,7 @@ impl Traj<Spacecraft> {
         let mut time_system = String::new();
 
         let ignored_tokens: HashSet<_> = [
+            "CCSDS_OEM_VERS".to_string(),
             "CCSDS_OMM_VERS".to_string(),
             "CREATION_DATE".to_string(),
             "ORIGINATOR".to_string(),
@@ -239,12 +268,18 @@ impl Traj<Spacecraft> {
                 warn!("[line: {}] Skipping covariance in OEM parsing", lno + 1);
                 parse = false;
             } else if parse {
-                let frame = Frame::from_name(
-                    center_name.clone().unwrap().as_str(),
-                    orient_name.clone().unwrap().as_str(),
-                )
-                .map_err(|e| NyxError::CCSDS {
-                    msg: format!("frame error `{center_name:?} {orient_name:?}`: {e}"),
+                let (Some(center), Some(orient)) = (&center_name, &orient_name) else {
+                    return Err(NyxError::CCSDS {
+                        msg: format!(
+                            "[line: {}] state data before CENTER_NAME and REF_FRAME",
+                            lno + 1
+                        ),
+                    });
+                };
+                let frame = Frame::from_name(center.as_str(), orient.as_str()).map_err(|e| {
+                    NyxError::CCSDS {
+                        msg: format!("frame error `{center} {orient}`: {e}"),
+                    }
                 })?;
                 // Split the line into components
                 let parts: Vec<&str> = line.split_whitespace().collect();
@@ -289,6 +324,30 @@ impl Traj<Spacecraft> {
         Ok(traj)
     }
 
+    /// Initialize a new spacecraft trajectory from the path to a CCSDS OEM file, fetching the frame information of each state
+    /// (e.g. the gravitational parameter of the central body) from the provided almanac. This frame information is required to
+    /// compute orbital elements and to transform the trajectory into other frames.
+    ///
+    /// Comments, covariance blocks, and unknown keywords of the OEM file are ignored.
+    pub fn from_oem_file_with_almanac<P: AsRef<Path>>(
+        path: P,
+        tpl_option: Option<Spacecraft>,
+        almanac: &Almanac,
+    ) -> Result<Self, NyxError> {
+        let mut traj = Self::from_oem_file(path, tpl_option)?;
+
+        for state in &mut traj.states {
+            state.orbit.frame =
+                almanac
+                    .frame_from_uid(state.orbit.frame)
+                    .map_err(|e| NyxError::CCSDS {
+                        msg: format!("frame error `{}`: {e}", state.orbit.frame),
+                    })?;
+        }
+
+        Ok(traj)
+    }
+
     pub fn to_oem_file<P: AsRef<Path>>(
         &self,
         path: P,
@@ -329,9 +388,13 @@ impl Traj<Spacecraft> {
 
         // Epoch formmatter.
         let iso8601_no_ts = Format::from_str("%Y-%m-%dT%H:%M:%S.%f").unwrap();
+        // All of the epochs are written in the same time system.
+        let time_scale = cfg.time_scale.unwrap_or(states[0].orbit.epoch.time_scale);
+        let fmt_epoch =
+            |epoch: Epoch| Formatter::new(epoch.to_time_scale(time_scale), iso8601_no_ts);
 
         // Write mandatory metadata
-        writeln!(writer, "CCSDS_OMM_VERS = 2.0").map_err(err_hdlr)?;
+        writeln!(writer, "CCSDS_OEM_VERS = 2.0").map_err(err_hdlr)?;
         writeln!(
             writer,
             "CREATION_DATE = {}",
@@ -379,30 +442,25 @@ impl Traj<Spacecraft> {
 
         writeln!(writer, "CENTER_NAME = {center}",).map_err(err_hdlr)?;
 
-        writeln!(writer, "TIME_SYSTEM = {}", first_orbit.epoch.time_scale).map_err(err_hdlr)?;
+        writeln!(writer, "TIME_SYSTEM = {time_scale}").map_err(err_hdlr)?;
 
-        writeln!(
-            writer,
-            "START_TIME = {}",
-            Formatter::new(states[0].epoch(), iso8601_no_ts)
-        )
-        .map_err(err_hdlr)?;
+        writeln!(writer, "START_TIME = {}", fmt_epoch(states[0].epoch())).map_err(err_hdlr)?;
         writeln!(
             writer,
             "USEABLE_START_TIME = {}",
-            Formatter::new(states[0].epoch(), iso8601_no_ts)
+            fmt_epoch(states[0].epoch())
         )
         .map_err(err_hdlr)?;
         writeln!(
             writer,
             "USEABLE_STOP_TIME = {}",
-            Formatter::new(states[states.len() - 1].epoch(), iso8601_no_ts)
+            fmt_epoch(states[states.len() - 1].epoch())
         )
         .map_err(err_hdlr)?;
         writeln!(
             writer,
             "STOP_TIME = {}",
-            Formatter::new(states[states.len() - 1].epoch(), iso8601_no_ts)
+            fmt_epoch(states[states.len() - 1].epoch())
         )
         .map_err(err_hdlr)?;
 
@@ -420,7 +478,7 @@ impl Traj<Spacecraft> {
             writeln!(
                 writer,
                 "{} {:E} {:E} {:E} {:E} {:E} {:E}",
-                Formatter::new(state.epoch, iso8601_no_ts),
+                fmt_epoch(state.epoch),
                 state.radius_km.x,
                 state.radius_km.y,
                 state.radius_km.z,
@@ -452,7 +510,7 @@ mod ut_ccsds_oem {
     use crate::Spacecraft;
     use crate::{io::ExportCfg, md::prelude::Traj, Orbit};
     use anise::almanac::Almanac;
-    use anise::constants::frames::MOON_J2000;
+    use anise::constants::frames::{EARTH_J2000, MOON_J2000};
     use pretty_env_logger;
     use std::env;
     use std::str::FromStr;
@@ -482,6 +540,64 @@ mod ut_ccsds_oem {
         assert_eq!(traj.name.unwrap(), "TEST_OBJ".to_string());
     }
 
+    #[test]
+    fn test_load_oem_covar_interpolation() {
+        let manifest_dir =
+            PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or(".".to_string()));
+
+        let almanac = Almanac::new(&manifest_dir.join("data/pck08.pca").to_string_lossy())
+            .unwrap()
+            .load(&manifest_dir.join("data/de440s.bsp").to_string_lossy())
+            .unwrap();
+
+        // Excerpt of the LEO OEM with an unknown keyword, comments, and a covariance block, which are all ignored.
+        let path = manifest_dir.join("data/tests/ccsds/oem/LEO_covar.oem");
+
+        let _ = pretty_env_logger::try_init();
+
+        let traj: Traj<Spacecraft> =
+            Traj::from_oem_file_with_almanac(path, None, &almanac).unwrap();
+
+        assert_eq!(traj.states.len(), 13);
+        assert_eq!(traj.name.as_ref().unwrap(), &"TEST_OBJ".to_string());
+        assert_eq!(
+            traj.first().epoch(),
+            Epoch::from_str("2020-06-01T12:00:00 UTC").unwrap()
+        );
+        // The frame information is loaded from the almanac
+        assert!(traj.first().orbit.frame.mu_km3_s2().is_ok());
+        assert!(traj.first().orbit.sma_km().is_ok());
+
+        // Remove one of the listed states, and check that interpolating the trajectory at its epoch matches the state line:
+        // 2020-06-01T12:01:00.000000  -4.659443185713036e+03  -3.299871874607716e+03   3.680574084331019e+03   9.649363656794152e-01  -6.233151071975111e+00  -4.351015833932221e+00
+        let epoch = Epoch::from_str("2020-06-01T12:01:00 UTC").unwrap();
+        let mut decimated = traj.clone();
+        decimated.states.retain(|state| state.epoch() != epoch);
+        assert_eq!(decimated.states.len(), 12);
+
+        let listed = Orbit::new(
+            -4.659443185713036e+03,
+            -3.299871874607716e+03,
+            3.680574084331019e+03,
+            9.649363656794152e-01,
+            -6.233151071975111e+00,
+            -4.351015833932221e+00,
+            epoch,
+            traj.first().orbit.frame,
+        );
+
+        let interp = decimated.at(epoch).unwrap().orbit;
+        let pos_err_km = (interp.radius_km - listed.radius_km).norm();
+        let vel_err_km_s = (interp.velocity_km_s - listed.velocity_km_s).norm();
+        println!(
+            "interpolation error: {:.3e} m\t{:.3e} m/s",
+            pos_err_km * 1e3,
+            vel_err_km_s * 1e3
+        );
+        assert!(pos_err_km < 1e-6);
+        assert!(vel_err_km_s < 1e-8);
+    }
+
     #[test]
     fn test_load_oem_meo() {
         // All three samples were taken from https://github.com/bradsease/oem/blob/main/tests/samples/real/
@@ -632,4 +748,76 @@ mod ut_ccsds_oem {
 
         assert_eq!(traj, traj_reloaded);
     }
+
+    #[test]
+    fn test_oem_time_system_roundtrip() {
+        use hifitime::TimeScale;
+        use std::fs::read_to_string;
+
+        let manifest_dir =
+            PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or(".".to_string()));
+
+        let almanac = Almanac::new(&manifest_dir.join("data/pck08.pca").to_string_lossy())
+            .unwrap()
+            .load(&manifest_dir.join("data/de440s.bsp").to_string_lossy())
+            .unwrap();
+
+        let epoch = Epoch::from_str("2023-03-04T05:06:07 UTC").unwrap();
+        let orbit = Orbit::try_keplerian_altitude(
+            500.0,
+            0.001,
+            51.6,
+            45.0,
+            85.0,
+            10.0,
+            epoch,
+            almanac.frame_from_uid(EARTH_J2000).unwrap(),
+        )
+        .unwrap();
+
+        let (_, traj) = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()))
+            .with(orbit.into(), Arc::new(almanac))
+            .for_duration_with_traj(2.hours())
+            .unwrap();
+
+        for time_scale in [TimeScale::UTC, TimeScale::TAI] {
+            // Sample the trajectory every minute
+            let cfg = ExportCfg::builder()
+                .step(1.minutes())
+                .time_scale(time_scale)
+                .build();
+
+            let path: PathBuf = [
+                env!("CARGO_MANIFEST_DIR"),
+                "output_data",
+                &format!("leo_2h_{time_scale}.oem"),
+            ]
+            .iter()
+            .collect();
+
+            let out_path = traj.to_oem_file(path, cfg).unwrap();
+
+            // Check the required metadata
+            let contents = read_to_string(&out_path).unwrap();
+            assert!(contents.starts_with("CCSDS_OEM_VERS = 2.0"));
+            for key in ["CENTER_NAME = ", "REF_FRAME = ICRF"] {
+                assert!(contents.contains(key), "missing `{key}`");
+            }
+            assert!(contents.contains(&format!("TIME_SYSTEM = {time_scale}")));
+            for key in ["START_TIME", "STOP_TIME"] {
+                assert!(contents.contains(&format!("\n{key} = ")), "missing {key}");
+            }
+
+            // And re-parse it
+            let traj_reloaded: Traj<Spacecraft> = Traj::from_oem_file(out_path, None).unwrap();
+
+            assert_eq!(traj_reloaded.states.len(), 121);
+            assert_eq!(traj_reloaded.first().epoch(), traj.first().epoch());
+            assert_eq!(traj_reloaded.last().epoch(), traj.last().epoch());
+            assert_eq!(traj_reloaded.first().epoch().time_scale, time_scale);
+            assert!(
+                (traj_reloaded.last().orbit.radius_km - traj.last().orbit.radius_km).norm() < 1e-9
+            );
+        }
+    }
 }
@@ -17,8 +17,8 @@
 */
 
 use super::traj_it::TrajIterator;
+use super::{Conjunction, Interpolatable, TrajError};
 use super::{ExportCfg, InterpolationSnafu, INTERPOLATION_SAMPLES};
-use super::{Interpolatable, TrajError};
 use crate::errors::NyxError;
 use crate::io::watermark::pq_writer;
 use crate::io::InputOutputError;
@@ -86,37 +86,339 @@ where
                 // Oh wow, we actually had this exact state!
                 Ok(self.states[idx])
             }
-            Err(idx) => {
-                if idx == 0 || idx >= self.states.len() {
-                    // The binary search returns where we should insert the data, so if it's at either end of the list, then we're out of bounds.
-                    // This condition should have been handled by the check at the start of this function.
-                    return Err(TrajError::NoInterpolationData { epoch });
-                }
-                // This is the closest index, so let's grab the items around it.
-                // NOTE: This is essentially the same code as in ANISE for the Hermite SPK type 13
+            Err(idx) => self.interpolate_at(idx, epoch),
+        }
+    }
+
+    /// Evaluates the trajectory at each of the provided epochs, which need not be sorted, and returns the states in the same order as the epochs.
+    ///
+    /// This walks through the trajectory only once, instead of searching the trajectory for each epoch as a repeated call to `at` would.
+    /// Returns an error if any epoch is outside of the trajectory.
+    pub fn evaluate_many(&self, epochs: &[Epoch]) -> Result<Vec<S>, TrajError> {
+        if let Some(epoch) = epochs.iter().find(|epoch| {
+            self.states.is_empty()
+                || self.first().epoch() > **epoch
+                || self.last().epoch() < **epoch
+        }) {
+            return Err(TrajError::NoInterpolationData { epoch: *epoch });
+        }
 
-                // We didn't find it, so let's build an interpolation here.
-                let num_left = INTERPOLATION_SAMPLES / 2;
+        let mut order: Vec<usize> = (0..epochs.len()).collect();
+        order.sort_by_key(|&i| epochs[i]);
 
-                // Ensure that we aren't fetching out of the window
-                let mut first_idx = idx.saturating_sub(num_left);
-                let last_idx = self.states.len().min(first_idx + INTERPOLATION_SAMPLES);
+        let mut states: Vec<Option<S>> = vec![None; epochs.len()];
+        // Index of the first state whose epoch is not before the current epoch
+        let mut idx = 0;
+        for i in order {
+            let epoch = epochs[i];
+            while self.states[idx].epoch() < epoch {
+                idx += 1;
+            }
 
-                // Check that we have enough samples
-                if last_idx == self.states.len() {
-                    first_idx = last_idx.saturating_sub(2 * num_left);
-                }
+            states[i] = Some(if self.states[idx].epoch() == epoch {
+                self.states[idx]
+            } else {
+                self.interpolate_at(idx, epoch)?
+            });
+        }
+
+        Ok(states.into_iter().flatten().collect())
+    }
+
+    /// Computes the closest approach between this trajectory and the other one: the time of closest approach (TCA), and the distance
+    /// and relative velocity between both at that time.
+    ///
+    /// The relative distance is sampled every minute over the span covered by both trajectories, and the minimum is refined by successive
+    /// parabolic interpolation. Both trajectories must be in the same frame (cf. `to_frame`). Refer to `closest_approach_with_step` to change the sampling step.
+    pub fn closest_approach(&self, other: &Self) -> Result<Conjunction, TrajError> {
+        self.closest_approach_with_step(other, 1.minutes())
+    }
+
+    /// Computes the closest approach between this trajectory and the other one, cf. `closest_approach`.
+    ///
+    /// The relative distance is sampled with the provided step over the span covered by both trajectories. The step should be small compared
+    /// to the duration of the encounter, otherwise the closest approach may be missed between two samples.
+    pub fn closest_approach_with_step(
+        &self,
+        other: &Self,
+        step: Duration,
+    ) -> Result<Conjunction, TrajError> {
+        if self.states.is_empty() || other.states.is_empty() {
+            return Err(TrajError::CreationError {
+                msg: "cannot compute the closest approach of an empty trajectory".to_string(),
+            });
+        }
+
+        if self.first().frame() != other.first().frame() {
+            return Err(TrajError::FrameMismatch {
+                this: self.first().frame(),
+                other: other.first().frame(),
+            });
+        }
+
+        let start = self.first().epoch().max(other.first().epoch());
+        let end = self.last().epoch().min(other.last().epoch());
+        if start > end {
+            return Err(TrajError::NoOverlap { start, end });
+        }
+
+        // Squared distance is smooth even when the trajectories intersect, so the parabolic fits are done on it.
+        let dist2_km2 = |epoch: Epoch| -> Result<f64, TrajError> {
+            let this_orbit = *self.at(epoch)?.orbit();
+            let other_orbit = *other.at(epoch)?.orbit();
+            Ok((this_orbit.radius_km - other_orbit.radius_km).norm_squared())
+        };
 
-                let mut states = Vec::with_capacity(last_idx - first_idx);
-                for idx in first_idx..last_idx {
-                    states.push(self.states[idx]);
+        // Coarse search
+        let mut epochs = TimeSeries::exclusive(start, end, step).collect::<Vec<Epoch>>();
+        epochs.push(end);
+        let this_states = self.evaluate_many(&epochs)?;
+        let other_states = other.evaluate_many(&epochs)?;
+        let samples = this_states
+            .iter()
+            .zip(other_states.iter())
+            .map(|(this_state, other_state)| {
+                (this_state.orbit().radius_km - other_state.orbit().radius_km).norm_squared()
+            })
+            .collect::<Vec<f64>>();
+
+        let (min_idx, _) = samples
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap();
+
+        // Builds the conjunction at the provided epoch
+        let conjunction = |tca: Epoch| -> Result<Conjunction, TrajError> {
+            let this_orbit = *self.at(tca)?.orbit();
+            let other_orbit = *other.at(tca)?.orbit();
+            let rel_radius_km = other_orbit.radius_km - this_orbit.radius_km;
+            Ok(Conjunction {
+                tca,
+                miss_distance_km: rel_radius_km.norm(),
+                rel_radius_km,
+                rel_velocity_km_s: other_orbit.velocity_km_s - this_orbit.velocity_km_s,
+            })
+        };
+
+        let mut best = (epochs[min_idx], samples[min_idx]);
+        if epochs.len() < 2 {
+            return conjunction(best.0);
+        }
+
+        // Bracket the minimum, in seconds past the start of the overlap.
+        let secs = |epoch: Epoch| (epoch - start).to_seconds();
+        let (mut a, mut fa) = if min_idx > 0 {
+            (secs(epochs[min_idx - 1]), samples[min_idx - 1])
+        } else {
+            (secs(epochs[0]), samples[0])
+        };
+        let (mut c, mut fc) = if min_idx + 1 < epochs.len() {
+            (secs(epochs[min_idx + 1]), samples[min_idx + 1])
+        } else {
+            (secs(epochs[min_idx]), samples[min_idx])
+        };
+        let (mut b, mut fb) = if min_idx == 0 || min_idx + 1 == epochs.len() {
+            // The minimum is at the edge of the overlap, so start from the middle of the edge interval.
+            let mid = 0.5 * (a + c);
+            (mid, dist2_km2(start + mid.seconds())?)
+        } else {
+            (secs(epochs[min_idx]), samples[min_idx])
+        };
+
+        // Successive parabolic interpolation, with a bisection fallback when the parabola is degenerate or leaves the bracket.
+        for _ in 0..100 {
+            if c - a < 1e-6 {
+                break;
+            }
+
+            let num = (b - a).powi(2) * (fb - fc) - (b - c).powi(2) * (fb - fa);
+            let den = (b - a) * (fb - fc) - (b - c) * (fb - fa);
+            let mut x = if den.abs() > f64::EPSILON {
+                b - 0.5 * num / den
+            } else {
+                f64::NAN
+            };
+
+            if !(x > a && x < c) || (x - b).abs() < 1e-9 {
+                // Bisect the largest side of the bracket
+                x = if b - a > c - b {
+                    0.5 * (a + b)
+                } else {
+                    0.5 * (b + c)
+                };
+            }
+
+            let fx = dist2_km2(start + x.seconds())?;
+
+            if fx < fb {
+                if x < b {
+                    (c, fc) = (b, fb);
+                } else {
+                    (a, fa) = (b, fb);
                 }
+                (b, fb) = (x, fx);
+            } else if x < b {
+                (a, fa) = (x, fx);
+            } else {
+                (c, fc) = (x, fx);
+            }
+        }
 
-                self.states[idx]
-                    .interpolate(epoch, &states)
-                    .context(InterpolationSnafu)
+        if fb < best.1 {
+            best = (start + b.seconds(), fb);
+        }
+
+        conjunction(best.0)
+    }
+
+    /// Interpolates the trajectory at the provided epoch, where `idx` is the index of the first state after that epoch.
+    fn interpolate_at(&self, idx: usize, epoch: Epoch) -> Result<S, TrajError> {
+        if idx == 0 || idx >= self.states.len() {
+            // The binary search returns where we should insert the data, so if it's at either end of the list, then we're out of bounds.
+            // This condition should have been handled by the check at the start of this function.
+            return Err(TrajError::NoInterpolationData { epoch });
+        }
+        // This is the closest index, so let's grab the items around it.
+        // NOTE: This is essentially the same code as in ANISE for the Hermite SPK type 13
+
+        // We didn't find it, so let's build an interpolation here.
+        let num_left = INTERPOLATION_SAMPLES / 2;
+
+        // Ensure that we aren't fetching out of the window
+        let mut first_idx = idx.saturating_sub(num_left);
+        let mut last_idx = self.states.len().min(first_idx + INTERPOLATION_SAMPLES);
+
+        // Check that we have enough samples
+        if last_idx == self.states.len() {
+            first_idx = last_idx.saturating_sub(2 * num_left);
+        }
+
+        // Never interpolate across an impulsive maneuver, stored as two subsequent states at the same epoch (cf. `splice`).
+        for k in first_idx..last_idx - 1 {
+            let mnvr_epoch = self.states[k].epoch();
+            if mnvr_epoch == self.states[k + 1].epoch() {
+                if epoch < mnvr_epoch {
+                    last_idx = k + 1;
+                    break;
+                } else {
+                    first_idx = k + 1;
+                }
             }
         }
+
+        let mut states = Vec::with_capacity(last_idx - first_idx);
+        for idx in first_idx..last_idx {
+            states.push(self.states[idx]);
+        }
+
+        self.states[idx]
+            .interpolate(epoch, &states)
+            .context(InterpolationSnafu)
+    }
+
+    /// Splices the arc following an impulsive maneuver onto this trajectory, which must span the first epoch of the post-maneuver arc.
+    ///
+    /// The states of this trajectory after the maneuver epoch are dropped, and both the pre-maneuver and post-maneuver states are kept at the maneuver epoch.
+    /// Interpolation never spans the maneuver, so evaluating the spliced trajectory just before the maneuver returns the pre-maneuver
+    /// arc and just after returns the post-maneuver arc.
+    ///
+    /// # Warning
+    /// Finalizing the spliced trajectory (e.g. by adding trajectories) removes the post-maneuver state at the maneuver epoch.
+    pub fn splice(&self, post_mnvr: &Self) -> Result<Self, NyxError> {
+        if self.states.is_empty() || post_mnvr.states.is_empty() {
+            return Err(NyxError::Trajectory {
+                source: TrajError::CreationError {
+                    msg: "cannot splice an empty trajectory".to_string(),
+                },
+            });
+        }
+
+        let mnvr_epoch = post_mnvr.first().epoch();
+
+        if self.first().frame() != post_mnvr.first().frame() {
+            Err(NyxError::Trajectory {
+                source: TrajError::CreationError {
+                    msg: format!(
+                        "Frame mismatch in splice operation: {} != {}",
+                        self.first().frame(),
+                        post_mnvr.first().frame()
+                    ),
+                },
+            })
+        } else if mnvr_epoch < self.first().epoch() || mnvr_epoch > self.last().epoch() {
+            Err(NyxError::Trajectory {
+                source: TrajError::CreationError {
+                    msg: format!(
+                        "maneuver epoch {mnvr_epoch} is outside of the pre-maneuver trajectory from {} to {}",
+                        self.first().epoch(),
+                        self.last().epoch()
+                    ),
+                },
+            })
+        } else {
+            let mut states: Vec<S> = self
+                .states
+                .iter()
+                .copied()
+                .filter(|s| s.epoch() < mnvr_epoch)
+                .collect();
+            // Ensure that the pre-maneuver arc ends exactly at the maneuver epoch
+            states.push(self.at(mnvr_epoch)?);
+            states.extend(post_mnvr.states.iter().copied());
+
+            Ok(Self {
+                name: self.name.clone(),
+                states,
+            })
+        }
+    }
+
+    /// Appends the arc which follows this trajectory, e.g. the next arc of a propagation, in place.
+    ///
+    /// The other trajectory must be in the same frame and start at or after the last epoch of this trajectory: if both share that epoch,
+    /// the state of this trajectory is kept. Unlike adding trajectories, overlapping arcs are an error. A warning is printed if both arcs are
+    /// separated by a time gap.
+    pub fn append(&mut self, other: Self) -> Result<(), NyxError> {
+        if other.states.is_empty() {
+            return Ok(());
+        } else if self.states.is_empty() {
+            self.states = other.states;
+            return Ok(());
+        }
+
+        if self.first().frame() != other.first().frame() {
+            return Err(NyxError::Trajectory {
+                source: TrajError::CreationError {
+                    msg: format!(
+                        "Frame mismatch in append operation: {} != {}",
+                        self.first().frame(),
+                        other.first().frame()
+                    ),
+                },
+            });
+        }
+
+        let end = self.last().epoch();
+        let start = other.first().epoch();
+
+        if start < end {
+            return Err(NyxError::Trajectory {
+                source: TrajError::CreationError {
+                    msg: format!("appended arc starts at {start}, before the end of this trajectory at {end}"),
+                },
+            });
+        } else if start > end {
+            warn!(
+                "Appended trajectory will have a time-gap of {} starting at {end}",
+                start - end
+            );
+        }
+
+        self.states
+            .extend(other.states.into_iter().filter(|s| s.epoch() > end));
+
+        Ok(())
     }
 
     /// Returns the first state in this ephemeris
@@ -317,6 +619,12 @@ where
             traj.states.push(state);
         }
 
+        // Preserve the last state if the duration is not a multiple of the step
+        if traj.states.last().map(|state| state.epoch()) != Some(self.last().epoch()) {
+            traj.states.push(*self.last());
+        }
+
+        traj.name.clone_from(&self.name);
         traj.finalize();
 
         Ok(traj)
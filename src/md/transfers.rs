@@ -0,0 +1,156 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::cosmic::Orbit;
+use crate::time::{Duration, Epoch, Unit};
+use anise::errors::{MathError, PhysicsError, PhysicsResult};
+use anise::prelude::Frame;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// An impulsive transfer between two coplanar circular orbits, with tangential burns at the apsides of the transfer ellipses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransferPlan {
+    /// Magnitude of each burn in km/s, in chronological order
+    pub burns_km_s: Vec<f64>,
+    /// Sum of the magnitudes of all of the burns, in km/s
+    pub total_dv_km_s: f64,
+    /// Time of flight from the first to the last burn
+    pub tof: Duration,
+    /// The initial circular orbit, then each transfer ellipse just after the burn starting it, then the final circular orbit.
+    /// Each orbit is at the epoch of the burn it starts with, such that it can be propagated to verify the transfer.
+    pub orbits: Vec<Orbit>,
+}
+
+impl fmt::Display for TransferPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} burns totaling {:.6} km/s over {}: {:?} km/s",
+            self.burns_km_s.len(),
+            self.total_dv_km_s,
+            self.tof,
+            self.burns_km_s
+        )
+    }
+}
+
+/// Computes the Hohmann transfer from the circular orbit of radius `r1_km` to the circular orbit of radius `r2_km`,
+/// both in the XY plane of the provided frame, which must have its gravitational parameter set. The first burn occurs at the provided epoch,
+/// on the X axis.
+///
+/// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., algorithm 36.
+pub fn hohmann(r1_km: f64, r2_km: f64, epoch: Epoch, frame: Frame) -> PhysicsResult<TransferPlan> {
+    transfer(&[r1_km, r2_km], epoch, frame)
+}
+
+/// Computes the bi-elliptic transfer from the circular orbit of radius `r1_km` to the circular orbit of radius `r2_km` through the
+/// apoapsis radius `rb_km` of both transfer ellipses. The orbits are in the XY plane of the provided frame, which must have its
+/// gravitational parameter set, and the first burn occurs at the provided epoch, on the X axis.
+///
+/// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., algorithm 37.
+pub fn bielliptic(
+    r1_km: f64,
+    rb_km: f64,
+    r2_km: f64,
+    epoch: Epoch,
+    frame: Frame,
+) -> PhysicsResult<TransferPlan> {
+    transfer(&[r1_km, rb_km, r2_km], epoch, frame)
+}
+
+/// Chains half ellipses between each consecutive radius, starting and ending on circular orbits.
+fn transfer(radii_km: &[f64], epoch: Epoch, frame: Frame) -> PhysicsResult<TransferPlan> {
+    let mu_km3_s2 = frame.mu_km3_s2()?;
+
+    for r_km in radii_km {
+        if *r_km <= 0.0 {
+            return Err(PhysicsError::AppliedMath {
+                source: MathError::DomainError {
+                    value: *r_km,
+                    msg: "transfer radii must be strictly positive",
+                },
+            });
+        }
+    }
+
+    // Speed at radius r on an orbit of semi major axis a (vis-viva)
+    let speed = |r_km: f64, sma_km: f64| (mu_km3_s2 * (2.0 / r_km - 1.0 / sma_km)).sqrt();
+
+    let mut burns_km_s = Vec::with_capacity(radii_km.len());
+    let mut orbits = Vec::with_capacity(radii_km.len() + 1);
+    let mut tof_s = 0.0;
+
+    let r1_km = radii_km[0];
+    let mut prev_speed_km_s = speed(r1_km, r1_km);
+    orbits.push(tangent_orbit(r1_km, prev_speed_km_s, 0.0, epoch, frame));
+
+    for (i, leg) in radii_km.windows(2).enumerate() {
+        let (from_km, to_km) = (leg[0], leg[1]);
+        let sma_km = 0.5 * (from_km + to_km);
+
+        let depart_speed_km_s = speed(from_km, sma_km);
+        burns_km_s.push((depart_speed_km_s - prev_speed_km_s).abs());
+
+        // Each half ellipse starts on the X axis for even legs, and on the opposite side for odd legs.
+        let angle_rad = i as f64 * PI;
+        orbits.push(tangent_orbit(
+            from_km,
+            depart_speed_km_s,
+            angle_rad,
+            epoch + tof_s * Unit::Second,
+            frame,
+        ));
+
+        tof_s += PI * (sma_km.powi(3) / mu_km3_s2).sqrt();
+        prev_speed_km_s = speed(to_km, sma_km);
+    }
+
+    let r2_km = radii_km[radii_km.len() - 1];
+    let final_speed_km_s = speed(r2_km, r2_km);
+    burns_km_s.push((final_speed_km_s - prev_speed_km_s).abs());
+    orbits.push(tangent_orbit(
+        r2_km,
+        final_speed_km_s,
+        (radii_km.len() - 1) as f64 * PI,
+        epoch + tof_s * Unit::Second,
+        frame,
+    ));
+
+    Ok(TransferPlan {
+        total_dv_km_s: burns_km_s.iter().sum(),
+        burns_km_s,
+        tof: tof_s * Unit::Second,
+        orbits,
+    })
+}
+
+/// Builds the orbit at the provided angle from the X axis in the XY plane, with a prograde velocity perpendicular to the radius.
+fn tangent_orbit(r_km: f64, speed_km_s: f64, angle_rad: f64, epoch: Epoch, frame: Frame) -> Orbit {
+    let (sin, cos) = angle_rad.sin_cos();
+    Orbit::cartesian(
+        r_km * cos,
+        r_km * sin,
+        0.0,
+        -speed_km_s * sin,
+        speed_km_s * cos,
+        0.0,
+        epoch,
+        frame,
+    )
+}
@@ -0,0 +1,249 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::linalg::allocator::Allocator;
+use crate::linalg::{DefaultAllocator, DimName, OMatrix, OVector};
+use crate::md::trajectory::Interpolatable;
+use crate::od::estimate::Estimate;
+use crate::od::process::ODProcess;
+use crate::od::{
+    Dynamics, EstimateFrom, Filter, Measurement, ODAlmanacSnafu, ODError, State,
+    TooFewMeasurementsSnafu, TrackingDeviceSim,
+};
+use crate::propagators::error_ctrl::ErrorCtrl;
+use crate::time::Epoch;
+use snafu::{ensure, ResultExt};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Add;
+
+/// Epoch state deviation and covariance estimated by a batch least squares and by a sequential filter from the same data.
+///
+/// For a linear problem with Gaussian noise and without process noise, both estimators solve the same problem, so the
+/// discrepancies should be at the level of the numerical noise.
+#[derive(Clone, Debug)]
+pub struct BatchSequentialComparison<N: DimName>
+where
+    DefaultAllocator: Allocator<N> + Allocator<N, N>,
+{
+    /// Epoch at which both solutions are compared, i.e. the epoch of the a priori estimate
+    pub epoch: Epoch,
+    /// Number of scalar measurements used by the batch solution
+    pub num_msr: usize,
+    /// State deviation estimated by the batch least squares
+    pub batch_state_deviation: OVector<f64, N>,
+    /// Covariance of the batch least squares
+    pub batch_covar: OMatrix<f64, N, N>,
+    /// State deviation of the last filter estimate, mapped back to the epoch
+    pub sequential_state_deviation: OVector<f64, N>,
+    /// Covariance of the last filter estimate, mapped back to the epoch
+    pub sequential_covar: OMatrix<f64, N, N>,
+}
+
+impl<N: DimName> BatchSequentialComparison<N>
+where
+    DefaultAllocator: Allocator<N> + Allocator<N, N>,
+{
+    /// Returns the batch state deviation minus the sequential one.
+    pub fn state_discrepancy(&self) -> OVector<f64, N> {
+        &self.batch_state_deviation - &self.sequential_state_deviation
+    }
+
+    /// Returns the batch covariance minus the sequential one.
+    pub fn covar_discrepancy(&self) -> OMatrix<f64, N, N> {
+        &self.batch_covar - &self.sequential_covar
+    }
+
+    /// Returns the norm of the position discrepancy, in km, assuming the first three components of the state are the position.
+    pub fn position_discrepancy_km(&self) -> f64 {
+        self.state_discrepancy().rows(0, 3).norm()
+    }
+
+    /// Returns the norm of the velocity discrepancy, in km/s, assuming the components three to six of the state are the velocity.
+    pub fn velocity_discrepancy_km_s(&self) -> f64 {
+        self.state_discrepancy().rows(3, 3).norm()
+    }
+}
+
+impl<N: DimName> fmt::Display for BatchSequentialComparison<N>
+where
+    DefaultAllocator: Allocator<N> + Allocator<N, N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Batch vs sequential @ {} ({} measurements)",
+            self.epoch, self.num_msr
+        )?;
+        writeln!(
+            f,
+            "\tstate discrepancy: {:e}",
+            self.state_discrepancy().norm()
+        )?;
+        write!(
+            f,
+            "\tcovariance discrepancy: {:e}",
+            self.covar_discrepancy().norm()
+        )
+    }
+}
+
+/// Compares the epoch state estimated by a batch least squares with that of the sequential filter of the provided orbit determination process.
+///
+/// The orbit determination process must have processed the provided measurements with a classical (non extended) filter
+/// and without process noise, starting from the provided a priori estimate.
+/// The batch solution is computed from the same linearization: it reuses the prefit residuals of the accepted measurements, the nominal
+/// states of the filter estimates, and the STMs retained between subsequent estimates.
+/// The filter solution is the last estimate mapped back to the epoch of the a priori estimate.
+///
+/// # Errors
+/// + The a priori covariance is singular;
+/// + No measurement was accepted by the filter;
+/// + The batch information matrix or the STM to the last estimate is singular.
+pub fn compare_batch_sequential<'a, D, E, Msr, A, S, K, Dev>(
+    odp: &ODProcess<'a, D, E, Msr, A, S, K>,
+    apriori: &K::Estimate,
+    measurements: &[(String, Msr)],
+    devices: &mut BTreeMap<String, Dev>,
+) -> Result<BatchSequentialComparison<S::Size>, ODError>
+where
+    D: Dynamics,
+    E: ErrorCtrl,
+    Msr: Measurement,
+    A: DimName,
+    S: EstimateFrom<D::StateType, Msr> + Interpolatable,
+    K: Filter<S, A, Msr::MeasurementSize>,
+    Dev: TrackingDeviceSim<S, Msr>,
+    D::StateType: Interpolatable + Add<OVector<f64, <S as State>::Size>, Output = D::StateType>,
+    <DefaultAllocator as Allocator<<D::StateType as State>::VecLength>>::Buffer<f64>: Send,
+    DefaultAllocator: Allocator<<D::StateType as State>::Size>
+        + Allocator<Msr::MeasurementSize>
+        + Allocator<Msr::MeasurementSize, S::Size>
+        + Allocator<S::Size>
+        + Allocator<Msr::MeasurementSize, Msr::MeasurementSize>
+        + Allocator<Msr::MeasurementSize, <D::StateType as State>::Size>
+        + Allocator<Msr::MeasurementSize, <S as State>::Size>
+        + Allocator<<D::StateType as State>::Size, Msr::MeasurementSize>
+        + Allocator<<S as State>::Size, Msr::MeasurementSize>
+        + Allocator<<D::StateType as State>::Size, <D::StateType as State>::Size>
+        + Allocator<<D::StateType as State>::VecLength>
+        + Allocator<A>
+        + Allocator<A, A>
+        + Allocator<<D::StateType as State>::Size, A>
+        + Allocator<A, <D::StateType as State>::Size>
+        + Allocator<<S as State>::Size>
+        + Allocator<<S as State>::VecLength>
+        + Allocator<<S as State>::Size, <S as State>::Size>
+        + Allocator<<S as State>::Size, A>
+        + Allocator<A, <S as State>::Size>,
+{
+    let apriori_info = apriori
+        .covar()
+        .try_inverse()
+        .ok_or(ODError::SingularInformationMatrix {
+            action: "inverting the a priori covariance",
+        })?;
+
+    // Normal equations of the batch, initialized with the a priori information
+    let mut info = apriori_info.clone();
+    let mut rhs = apriori_info * apriori.state_deviation();
+    let mut num_msr = 0;
+
+    // STM from the a priori epoch to the current estimate
+    let mut phi = OMatrix::<f64, S::Size, S::Size>::identity();
+
+    for (est, residual) in odp.estimates.iter().zip(odp.residuals.iter()) {
+        phi = est.stm() * phi;
+
+        let residual = match residual {
+            Some(residual) if !residual.rejected => residual,
+            _ => continue,
+        };
+
+        let (device_name, msr) = match measurements.iter().find(|(name, msr)| {
+            msr.epoch() == residual.epoch && residual.tracker.as_ref() == Some(name)
+        }) {
+            Some((name, msr)) => (name, msr),
+            None => {
+                warn!("No measurement found for the residual @ {}", residual.epoch);
+                continue;
+            }
+        };
+
+        let device = match devices.get_mut(device_name) {
+            Some(device) => device,
+            None => {
+                error!("Measurement references {device_name} which is not in the list of configured devices");
+                continue;
+            }
+        };
+
+        let nominal_state = est.nominal_state();
+        let device_loc = device
+            .location(residual.epoch, nominal_state.frame(), odp.almanac.clone())
+            .context(ODAlmanacSnafu {
+                action: "computing the device location for the batch",
+            })?;
+
+        let h_tilde = S::sensitivity(msr, nominal_state, device_loc);
+        num_msr += msr.dimension();
+
+        let msr_info = device
+            .measurement_covar(residual.epoch)?
+            .try_inverse()
+            .ok_or(ODError::SingularNoiseRk)?;
+
+        // Map the sensitivity to the a priori epoch
+        let h_mapped = h_tilde * &phi;
+        let ht_w = h_mapped.transpose() * msr_info;
+        info += &ht_w * h_mapped;
+        rhs += ht_w * &residual.prefit;
+    }
+
+    ensure!(
+        num_msr > 0,
+        TooFewMeasurementsSnafu {
+            need: 1_usize,
+            action: "comparing the batch and sequential estimates"
+        }
+    );
+
+    let batch_covar = info
+        .try_inverse()
+        .ok_or(ODError::SingularInformationMatrix {
+            action: "inverting the batch information matrix",
+        })?;
+    let batch_state_deviation = &batch_covar * rhs;
+
+    // Map the last filter estimate back to the epoch
+    let last = odp.estimates.last().ok_or(ODError::ODNoResiduals {
+        action: "compare the batch and sequential estimates",
+    })?;
+    let phi_inv = phi
+        .try_inverse()
+        .ok_or(ODError::SingularStateTransitionMatrix)?;
+
+    Ok(BatchSequentialComparison {
+        epoch: apriori.epoch(),
+        num_msr,
+        batch_state_deviation,
+        batch_covar,
+        sequential_state_deviation: &phi_inv * last.state_deviation(),
+        sequential_covar: &phi_inv * last.covar() * phi_inv.transpose(),
+    })
+}
@@ -0,0 +1,276 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::dynamics::SpacecraftDynamics;
+use crate::linalg::allocator::Allocator;
+use crate::linalg::{Const, DefaultAllocator, Matrix6, OMatrix, Vector6};
+use crate::md::trajectory::Interpolatable;
+use crate::od::estimate::KfEstimate;
+use crate::od::{
+    EstimateFrom, Measurement, ODAlmanacSnafu, ODDynamicsSnafu, ODError, ODPropSnafu, ODTrajSnafu,
+    State, TimeTagged, TooFewMeasurementsSnafu, TrackingDeviceSim,
+};
+use crate::propagators::error_ctrl::ErrorCtrl;
+use crate::propagators::Propagator;
+use crate::time::{Duration, Epoch, Unit};
+use crate::Spacecraft;
+use anise::prelude::Almanac;
+use snafu::{ensure, ResultExt};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A batch least squares estimator of the orbit of a spacecraft at the epoch of the first measurement.
+///
+/// At each iteration, the reference state is propagated through all of the measurements, and the information matrix H^T W H
+/// and the right hand side H^T W y of the weighted normal equations are accumulated, where H is the measurement sensitivity
+/// mapped back to the epoch with the state transition matrix, W the inverse of the measurement noise covariance of each device,
+/// and y the prefit residuals. The state correction is solved via a Cholesky decomposition of the information matrix and applied
+/// to the reference state, until the correction is below the tolerance.
+///
+/// Only the orbit is estimated: the other parameters of the spacecraft (e.g. its mass or its drag coefficient) are held fixed.
+/// The state transition matrix only accounts for the partials of the orbital dynamics (cf. `OrbitalDynamics::stm_between`),
+/// which only affects the convergence rate, not the converged solution.
+pub struct BatchLeastSquares<'a, E: ErrorCtrl> {
+    /// Propagator of the reference trajectory
+    pub prop: &'a Propagator<'a, SpacecraftDynamics, E>,
+    /// Maximum number of iterations before the estimation is considered to have diverged
+    pub max_iterations: usize,
+    /// Convergence tolerance on the norm of the position correction, in km
+    pub tolerance_km: f64,
+    /// Convergence tolerance on the norm of the velocity correction, in km/s
+    pub tolerance_km_s: f64,
+    /// Step of the integration of the state transition matrix along the reference trajectory
+    pub stm_step: Duration,
+}
+
+/// Solution of a batch least squares estimation.
+#[derive(Clone, Debug)]
+pub struct BatchLeastSquaresSolution {
+    /// Estimate at the epoch of the first measurement, whose covariance is the inverse of the information matrix
+    pub estimate: KfEstimate<Spacecraft>,
+    /// Number of iterations needed to converge
+    pub iterations: usize,
+    /// Number of scalar measurements used in the last iteration
+    pub num_msr: usize,
+    /// Root mean square of the prefit residuals of the last iteration, weighted by the measurement noise
+    pub weighted_rms: f64,
+}
+
+impl fmt::Display for BatchLeastSquaresSolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Batch least squares converged in {} iterations ({} measurements, weighted RMS = {:e})\n{}",
+            self.iterations, self.num_msr, self.weighted_rms, self.estimate
+        )
+    }
+}
+
+impl<'a, E: ErrorCtrl> BatchLeastSquares<'a, E> {
+    /// Initializes a new batch least squares estimator with at most ten iterations, converging when the position correction
+    /// is below one millimeter and the velocity correction below one micrometer per second.
+    pub fn new(prop: &'a Propagator<'a, SpacecraftDynamics, E>) -> Self {
+        Self {
+            prop,
+            max_iterations: 10,
+            tolerance_km: 1e-6,
+            tolerance_km_s: 1e-9,
+            stm_step: 10 * Unit::Second,
+        }
+    }
+
+    /// Sets the maximum number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the convergence tolerance on the position (km) and velocity (km/s) corrections.
+    pub fn with_tolerance(mut self, tolerance_km: f64, tolerance_km_s: f64) -> Self {
+        self.tolerance_km = tolerance_km;
+        self.tolerance_km_s = tolerance_km_s;
+        self
+    }
+
+    /// Sets the integration step of the state transition matrix along the reference trajectory.
+    pub fn with_stm_step(mut self, stm_step: Duration) -> Self {
+        self.stm_step = stm_step;
+        self
+    }
+
+    /// Estimates the orbit at the epoch of the first measurement, starting from the provided initial guess.
+    ///
+    /// The initial guess is first propagated to the epoch of the first measurement if needed. Measurements from devices
+    /// which are not in the provided list of devices, or which the device does not see from the reference trajectory, are ignored.
+    ///
+    /// # Errors
+    /// + Fewer than six scalar measurements are available;
+    /// + The measurement noise of a device or the information matrix is singular;
+    /// + The corrections are still above the tolerance after the maximum number of iterations.
+    pub fn estimate<Msr, Dev>(
+        &self,
+        initial_guess: Spacecraft,
+        measurements: &[(String, Msr)],
+        devices: &mut BTreeMap<String, Dev>,
+        almanac: Arc<Almanac>,
+    ) -> Result<BatchLeastSquaresSolution, ODError>
+    where
+        Msr: Measurement,
+        Spacecraft: EstimateFrom<Spacecraft, Msr>,
+        Dev: TrackingDeviceSim<Spacecraft, Msr>,
+        DefaultAllocator: Allocator<Msr::MeasurementSize>
+            + Allocator<Msr::MeasurementSize, Msr::MeasurementSize>
+            + Allocator<Msr::MeasurementSize, Const<9>>
+            + Allocator<Msr::MeasurementSize, Const<6>>
+            + Allocator<Const<6>, Msr::MeasurementSize>,
+    {
+        let mut msr_order: Vec<&(String, Msr)> = measurements.iter().collect();
+        msr_order.sort_by_key(|(_, msr)| msr.epoch());
+
+        let (first_epoch, last_epoch) = match (msr_order.first(), msr_order.last()) {
+            (Some((_, first)), Some((_, last))) => (first.epoch(), last.epoch()),
+            _ => {
+                return Err(ODError::TooFewMeasurements {
+                    need: 6,
+                    action: "batch least squares",
+                })
+            }
+        };
+
+        // Reference state at the epoch of the first measurement
+        let mut epoch_state = if initial_guess.epoch() == first_epoch {
+            initial_guess
+        } else {
+            self.prop
+                .with(initial_guess, almanac.clone())
+                .until_epoch(first_epoch)
+                .context(ODPropSnafu)?
+        };
+
+        for iteration in 1..=self.max_iterations {
+            let (_, traj) = self
+                .prop
+                .with(epoch_state, almanac.clone())
+                .until_epoch_with_traj(last_epoch)
+                .context(ODPropSnafu)?;
+
+            let mut info = Matrix6::zeros();
+            let mut rhs = Vector6::zeros();
+            let mut num_msr = 0;
+            let mut sum_sq = 0.0;
+
+            // STM from the epoch of the first measurement to the previous measurement
+            let mut phi = Matrix6::identity();
+            let mut prev_epoch = first_epoch;
+
+            for (device_name, msr) in &msr_order {
+                let device = match devices.get_mut(device_name) {
+                    Some(device) => device,
+                    None => {
+                        warn!("Measurement references {device_name} which is not in the list of configured devices");
+                        continue;
+                    }
+                };
+
+                let epoch = msr.epoch();
+                let computed_meas = match device.measure(epoch, &traj, None, almanac.clone())? {
+                    Some(computed_meas) => computed_meas,
+                    None => {
+                        debug!("{device_name} does not see the reference state @ {epoch}");
+                        continue;
+                    }
+                };
+
+                phi = self
+                    .prop
+                    .dynamics
+                    .orbital_dyn
+                    .stm_between(&traj, prev_epoch, epoch, self.stm_step, almanac.clone())
+                    .context(ODDynamicsSnafu)?
+                    * phi;
+                prev_epoch = epoch;
+
+                let nominal_state = traj.at(epoch).context(ODTrajSnafu)?;
+                let device_loc = device
+                    .location(epoch, nominal_state.frame(), almanac.clone())
+                    .context(ODAlmanacSnafu {
+                        action: "computing the device location for the batch",
+                    })?;
+
+                let h_tilde = Spacecraft::sensitivity(msr, nominal_state, device_loc);
+
+                let weight = device
+                    .measurement_covar(epoch)?
+                    .try_inverse()
+                    .ok_or(ODError::SingularNoiseRk)?;
+
+                // Sensitivity with respect to the orbit at the epoch of the first measurement
+                let h: OMatrix<f64, Msr::MeasurementSize, Const<6>> =
+                    h_tilde.fixed_columns::<6>(0) * phi;
+                let prefit = msr.residual(&computed_meas.observation());
+
+                let ht_w = h.transpose() * &weight;
+                info += &ht_w * &h;
+                rhs += &ht_w * &prefit;
+
+                num_msr += msr.dimension();
+                sum_sq += (prefit.transpose() * &weight * &prefit)[(0, 0)];
+            }
+
+            ensure!(
+                num_msr >= 6,
+                TooFewMeasurementsSnafu {
+                    need: 6_usize,
+                    action: "batch least squares"
+                }
+            );
+
+            let chol = info.cholesky().ok_or(ODError::SingularInformationMatrix {
+                action: "solving the batch normal equations",
+            })?;
+            let correction = chol.solve(&rhs);
+
+            epoch_state = epoch_state + correction;
+
+            let pos_corr_km = correction.fixed_rows::<3>(0).norm();
+            let vel_corr_km_s = correction.fixed_rows::<3>(3).norm();
+            info!(
+                "Batch iteration #{iteration}: |δr| = {pos_corr_km:e} km, |δv| = {vel_corr_km_s:e} km/s"
+            );
+
+            if pos_corr_km < self.tolerance_km && vel_corr_km_s < self.tolerance_km_s {
+                let mut covar = OMatrix::<f64, Const<9>, Const<9>>::zeros();
+                covar
+                    .fixed_view_mut::<6, 6>(0, 0)
+                    .copy_from(&chol.inverse());
+
+                return Ok(BatchLeastSquaresSolution {
+                    estimate: KfEstimate::from_covar(epoch_state, covar),
+                    iterations: iteration,
+                    num_msr,
+                    weighted_rms: (sum_sq / num_msr as f64).sqrt(),
+                });
+            }
+        }
+
+        Err(ODError::Diverged {
+            loops: self.max_iterations,
+        })
+    }
+}
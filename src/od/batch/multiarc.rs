@@ -0,0 +1,342 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::linalg::{DMatrix, DVector};
+use crate::od::ODError;
+use std::fmt;
+
+/// Contribution of a single arc to the normal equations, split between the local (arc) and global (shared) parameters.
+#[derive(Clone, Debug)]
+struct ArcNormalEquations {
+    /// Inverse of the information matrix of the local parameters
+    n_ll_inv: DMatrix<f64>,
+    /// Cross information between local and global parameters
+    n_lg: DMatrix<f64>,
+    /// Information of the global parameters from this arc only
+    n_gg: DMatrix<f64>,
+    /// Right hand side of the normal equations of the local parameters
+    b_l: DVector<f64>,
+    /// Right hand side of the normal equations of the global parameters
+    b_g: DVector<f64>,
+    /// Number of measurements in this arc
+    num_msr: usize,
+}
+
+/// A multi-arc batch least squares estimator.
+///
+/// Each arc has its own local parameters (e.g. the epoch state of that arc), and all arcs share a set of global parameters
+/// (e.g. a gravitational parameter correction or a station bias). The contribution of each arc to the normal equations is
+/// accumulated with `add_arc`, and the local parameters are eliminated (Schur complement) when solving, such that the
+/// global parameters are estimated from all of the arcs at once while each arc only needs to invert its own local information matrix.
+///
+/// This estimator solves for the _corrections_ to the parameters from the linearized problem. For non-linear problems,
+/// the caller is expected to iterate: apply the corrections to the reference states and parameters, recompute the
+/// residuals and partials, and solve again until convergence.
+#[derive(Clone, Debug)]
+pub struct MultiArcEstimator {
+    num_global: usize,
+    apriori_global_info: Option<DMatrix<f64>>,
+    arcs: Vec<ArcNormalEquations>,
+}
+
+/// Solution of a multi-arc batch least squares estimation.
+#[derive(Clone, Debug)]
+pub struct MultiArcSolution {
+    /// Correction to the global parameters
+    pub global_correction: DVector<f64>,
+    /// Covariance of the global parameters
+    pub global_covar: DMatrix<f64>,
+    /// Correction to the local parameters, in the order in which the arcs were added
+    pub local_corrections: Vec<DVector<f64>>,
+    /// Covariance of the local parameters (accounting for the uncertainty of the global parameters), in the order in which the arcs were added
+    pub local_covars: Vec<DMatrix<f64>>,
+}
+
+impl MultiArcEstimator {
+    /// Initializes a new multi-arc estimator with the provided number of global parameters, and without a priori information on those.
+    pub fn new(num_global: usize) -> Self {
+        Self {
+            num_global,
+            apriori_global_info: None,
+            arcs: Vec::new(),
+        }
+    }
+
+    /// Sets the a priori covariance of the global parameters.
+    pub fn with_apriori_global_covar(mut self, covar: DMatrix<f64>) -> Result<Self, ODError> {
+        self.check_dims(covar.nrows(), "setting the a priori global covariance")?;
+        self.check_dims(covar.ncols(), "setting the a priori global covariance")?;
+        let info = covar
+            .try_inverse()
+            .ok_or(ODError::SingularInformationMatrix {
+                action: "inverting the a priori global covariance",
+            })?;
+        self.apriori_global_info = Some(info);
+        Ok(self)
+    }
+
+    /// Number of global parameters
+    pub fn num_global(&self) -> usize {
+        self.num_global
+    }
+
+    /// Number of arcs added to this estimator
+    pub fn num_arcs(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// Total number of measurements across all arcs
+    pub fn num_msr(&self) -> usize {
+        self.arcs.iter().map(|arc| arc.num_msr).sum()
+    }
+
+    /// Adds the contribution of an arc without any a priori information on its local parameters, and returns the index of this arc.
+    ///
+    /// Each row corresponds to one scalar measurement:
+    /// + `h_local` are the partials of the measurements with respect to the local parameters of this arc;
+    /// + `h_global` are the partials of the measurements with respect to the global parameters;
+    /// + `residuals` are the differences between the observed and the computed measurements;
+    /// + `weights` are the inverse of the variances of each measurement.
+    pub fn add_arc(
+        &mut self,
+        h_local: &DMatrix<f64>,
+        h_global: &DMatrix<f64>,
+        residuals: &DVector<f64>,
+        weights: &DVector<f64>,
+    ) -> Result<usize, ODError> {
+        self.add_arc_with_apriori(h_local, h_global, residuals, weights, None)
+    }
+
+    /// Adds the contribution of an arc with an optional a priori covariance on its local parameters, and returns the index of this arc.
+    /// Refer to `add_arc` for the definition of each input.
+    pub fn add_arc_with_apriori(
+        &mut self,
+        h_local: &DMatrix<f64>,
+        h_global: &DMatrix<f64>,
+        residuals: &DVector<f64>,
+        weights: &DVector<f64>,
+        apriori_local_covar: Option<&DMatrix<f64>>,
+    ) -> Result<usize, ODError> {
+        let num_msr = residuals.len();
+        for (rows, action) in [
+            (h_local.nrows(), "adding an arc: local partials"),
+            (h_global.nrows(), "adding an arc: global partials"),
+            (weights.len(), "adding an arc: weights"),
+        ] {
+            if rows != num_msr {
+                return Err(ODError::InconsistentDimensions {
+                    action,
+                    expected: num_msr,
+                    got: rows,
+                });
+            }
+        }
+        self.check_dims(h_global.ncols(), "adding an arc: global partials")?;
+
+        if num_msr == 0 {
+            return Err(ODError::TooFewMeasurements {
+                need: 1,
+                action: "adding an arc to the multi-arc estimator",
+            });
+        }
+
+        let w = DMatrix::from_diagonal(weights);
+        let ht_l_w = h_local.transpose() * &w;
+        let ht_g_w = h_global.transpose() * &w;
+
+        let mut n_ll = &ht_l_w * h_local;
+        if let Some(covar) = apriori_local_covar {
+            if covar.nrows() != n_ll.nrows() || covar.ncols() != n_ll.ncols() {
+                return Err(ODError::InconsistentDimensions {
+                    action: "adding an arc: a priori local covariance",
+                    expected: n_ll.nrows(),
+                    got: covar.nrows(),
+                });
+            }
+            n_ll += covar
+                .clone()
+                .try_inverse()
+                .ok_or(ODError::SingularInformationMatrix {
+                    action: "inverting the a priori local covariance",
+                })?;
+        }
+
+        let n_ll_inv = n_ll
+            .try_inverse()
+            .ok_or(ODError::SingularInformationMatrix {
+                action: "inverting the local information of an arc",
+            })?;
+
+        self.arcs.push(ArcNormalEquations {
+            n_ll_inv,
+            n_lg: &ht_l_w * h_global,
+            n_gg: &ht_g_w * h_global,
+            b_l: ht_l_w * residuals,
+            b_g: ht_g_w * residuals,
+            num_msr,
+        });
+
+        Ok(self.arcs.len() - 1)
+    }
+
+    /// Solves the normal equations of all arcs for the corrections of the global and local parameters.
+    pub fn solve(&self) -> Result<MultiArcSolution, ODError> {
+        if self.arcs.is_empty() {
+            return Err(ODError::TooFewMeasurements {
+                need: 1,
+                action: "solving the multi-arc estimation",
+            });
+        }
+
+        // Reduce the normal equations to the global parameters only.
+        let mut n_red = match &self.apriori_global_info {
+            Some(info) => info.clone(),
+            None => DMatrix::zeros(self.num_global, self.num_global),
+        };
+        let mut b_red = DVector::zeros(self.num_global);
+
+        for arc in &self.arcs {
+            let n_gl_n_ll_inv = arc.n_lg.transpose() * &arc.n_ll_inv;
+            n_red += &arc.n_gg - &n_gl_n_ll_inv * &arc.n_lg;
+            b_red += &arc.b_g - &n_gl_n_ll_inv * &arc.b_l;
+        }
+
+        let global_covar = n_red
+            .try_inverse()
+            .ok_or(ODError::SingularInformationMatrix {
+                action: "inverting the reduced information of the global parameters",
+            })?;
+        let global_correction = &global_covar * b_red;
+
+        // Back-substitute the global correction in each arc.
+        let mut local_corrections = Vec::with_capacity(self.arcs.len());
+        let mut local_covars = Vec::with_capacity(self.arcs.len());
+        for arc in &self.arcs {
+            local_corrections.push(&arc.n_ll_inv * (&arc.b_l - &arc.n_lg * &global_correction));
+            let gain = &arc.n_ll_inv * &arc.n_lg;
+            local_covars.push(&arc.n_ll_inv + &gain * &global_covar * gain.transpose());
+        }
+
+        Ok(MultiArcSolution {
+            global_correction,
+            global_covar,
+            local_corrections,
+            local_covars,
+        })
+    }
+
+    fn check_dims(&self, got: usize, action: &'static str) -> Result<(), ODError> {
+        if got != self.num_global {
+            Err(ODError::InconsistentDimensions {
+                action,
+                expected: self.num_global,
+                got,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl MultiArcSolution {
+    /// Returns the 1-sigma uncertainty of the global parameter of the provided index.
+    pub fn global_sigma(&self, idx: usize) -> f64 {
+        self.global_covar[(idx, idx)].sqrt()
+    }
+}
+
+impl fmt::Display for MultiArcSolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Multi-arc solution over {} arcs",
+            self.local_corrections.len()
+        )?;
+        for (idx, correction) in self.global_correction.iter().enumerate() {
+            writeln!(
+                f,
+                "\tglobal #{idx}: correction = {correction:e} ± {:e}",
+                self.global_sigma(idx)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ut_multiarc {
+    use super::*;
+
+    #[test]
+    fn linear_shared_offset() {
+        // Two arcs observing y = a_i * t + c, where the slope a_i is local to each arc and the offset c is shared.
+        let c_true = 2.0;
+        let slopes = [0.5, -1.5];
+
+        let mut estimator = MultiArcEstimator::new(1);
+        for slope in slopes {
+            let times: Vec<f64> = (0..10).map(f64::from).collect();
+            let h_local = DMatrix::from_iterator(10, 1, times.iter().copied());
+            let h_global = DMatrix::from_element(10, 1, 1.0);
+            let residuals = DVector::from_iterator(10, times.iter().map(|t| slope * t + c_true));
+            let weights = DVector::from_element(10, 1.0);
+            estimator
+                .add_arc(&h_local, &h_global, &residuals, &weights)
+                .unwrap();
+        }
+
+        assert_eq!(estimator.num_arcs(), 2);
+        assert_eq!(estimator.num_msr(), 20);
+
+        let sol = estimator.solve().unwrap();
+        println!("{sol}");
+        assert!((sol.global_correction[0] - c_true).abs() < 1e-12);
+        for (correction, slope) in sol.local_corrections.iter().zip(slopes) {
+            assert!((correction[0] - slope).abs() < 1e-12);
+        }
+
+        // A single arc cannot determine the offset as well as both arcs combined.
+        let mut single = MultiArcEstimator::new(1);
+        let times: Vec<f64> = (0..10).map(f64::from).collect();
+        single
+            .add_arc(
+                &DMatrix::from_iterator(10, 1, times.iter().copied()),
+                &DMatrix::from_element(10, 1, 1.0),
+                &DVector::from_iterator(10, times.iter().map(|t| 0.5 * t + c_true)),
+                &DVector::from_element(10, 1.0),
+            )
+            .unwrap();
+        let single_sol = single.solve().unwrap();
+        assert!(sol.global_sigma(0) < single_sol.global_sigma(0));
+
+        // Dimension errors are reported
+        assert_eq!(
+            estimator.add_arc(
+                &DMatrix::zeros(3, 1),
+                &DMatrix::zeros(3, 2),
+                &DVector::zeros(3),
+                &DVector::zeros(3)
+            ),
+            Err(ODError::InconsistentDimensions {
+                action: "adding an arc: global partials",
+                expected: 1,
+                got: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,134 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::linalg::{Matrix2, Matrix2x3, Matrix3, Vector2, Vector3};
+use crate::od::estimate::{Estimate, KfEstimate};
+use crate::od::{
+    DegenerateEncounterSnafu, EstimateFrameMismatchSnafu, NonOverlappingEstimatesSnafu, ODError,
+};
+use crate::Spacecraft;
+use snafu::ensure;
+use std::f64::consts::PI;
+
+/// Below this relative speed, in km/s, the encounter is not short enough for the conjunction plane to be defined.
+pub const MIN_REL_SPEED_KM_S: f64 = 1e-6;
+
+/// Number of intervals of the Simpson quadrature along the radius of the hard body disk
+const RADIAL_INTERVALS: usize = 200;
+/// Number of samples of the trapezoidal quadrature around the hard body disk
+const ANGULAR_SAMPLES: usize = 360;
+
+/// Computes the two dimensional probability of collision (Pc) of two objects at their time of closest approach (TCA),
+/// following Foster's method, given the combined hard body radius (HBR) in km.
+///
+/// The encounter is assumed short, i.e. the relative motion is rectilinear and the position uncertainties are constant over the
+/// encounter. The combined position covariance (the estimates are assumed uncorrelated) is projected into the conjunction plane,
+/// normal to the relative velocity, and the resulting two dimensional Gaussian centered on the miss vector is integrated over the
+/// hard body disk. Both estimates must be in the same frame and at the same epoch, otherwise an error is returned.
+///
+/// Returns an error if the relative speed is less than [MIN_REL_SPEED_KM_S], where the conjunction plane is not defined.
+pub fn pc_2d(
+    primary: &KfEstimate<Spacecraft>,
+    secondary: &KfEstimate<Spacecraft>,
+    hbr_km: f64,
+) -> Result<f64, ODError> {
+    ensure!(
+        primary.epoch() == secondary.epoch(),
+        NonOverlappingEstimatesSnafu {
+            epoch_a: primary.epoch(),
+            epoch_b: secondary.epoch()
+        }
+    );
+
+    let primary_orbit = primary.state().orbit;
+    let secondary_orbit = secondary.state().orbit;
+
+    ensure!(
+        primary_orbit.frame == secondary_orbit.frame,
+        EstimateFrameMismatchSnafu {
+            frame_a: primary_orbit.frame,
+            frame_b: secondary_orbit.frame
+        }
+    );
+
+    let rel_radius_km = secondary_orbit.radius_km - primary_orbit.radius_km;
+    let rel_velocity_km_s = secondary_orbit.velocity_km_s - primary_orbit.velocity_km_s;
+    let rel_speed_km_s = rel_velocity_km_s.norm();
+
+    ensure!(
+        rel_speed_km_s >= MIN_REL_SPEED_KM_S,
+        DegenerateEncounterSnafu { rel_speed_km_s }
+    );
+
+    // Basis of the conjunction plane, with the first axis along the miss vector when it is defined.
+    let v_hat = rel_velocity_km_s / rel_speed_km_s;
+    let miss_in_plane_km = rel_radius_km - rel_radius_km.dot(&v_hat) * v_hat;
+    let x_hat = if miss_in_plane_km.norm() > f64::EPSILON * rel_radius_km.norm().max(1.0) {
+        miss_in_plane_km.normalize()
+    } else {
+        // The miss vector is along the relative velocity: any direction of the plane will do.
+        let not_parallel = if v_hat.x.abs() < 0.9 {
+            Vector3::x()
+        } else {
+            Vector3::y()
+        };
+        v_hat.cross(&not_parallel).normalize()
+    };
+    let z_hat = v_hat.cross(&x_hat);
+
+    let proj = Matrix2x3::from_rows(&[x_hat.transpose(), z_hat.transpose()]);
+
+    let combined_covar_km2: Matrix3<f64> =
+        primary.covar.fixed_view::<3, 3>(0, 0) + secondary.covar.fixed_view::<3, 3>(0, 0);
+    let covar_2d_km2: Matrix2<f64> = proj * combined_covar_km2 * proj.transpose();
+    let miss_2d_km: Vector2<f64> = proj * rel_radius_km;
+
+    let info_2d = covar_2d_km2
+        .try_inverse()
+        .ok_or(ODError::SingularInformationMatrix {
+            action: "projecting the combined covariance into the conjunction plane",
+        })?;
+    let norm_factor = 1.0 / (2.0 * PI * covar_2d_km2.determinant().sqrt());
+
+    // Integrate the density in polar coordinates centered on the hard body disk:
+    // Simpson along the radius, and trapezoid around the disk which is exact for a periodic integrand.
+    let d_rho = hbr_km / RADIAL_INTERVALS as f64;
+    let d_theta = 2.0 * PI / ANGULAR_SAMPLES as f64;
+    let mut pc = 0.0;
+    for i in 0..=RADIAL_INTERVALS {
+        let rho = i as f64 * d_rho;
+        let simpson_weight = if i == 0 || i == RADIAL_INTERVALS {
+            1.0
+        } else if i % 2 == 1 {
+            4.0
+        } else {
+            2.0
+        };
+
+        let mut ring = 0.0;
+        for j in 0..ANGULAR_SAMPLES {
+            let theta = j as f64 * d_theta;
+            let dx = Vector2::new(rho * theta.cos(), rho * theta.sin()) - miss_2d_km;
+            ring += (-0.5 * (dx.transpose() * info_2d * dx)[0]).exp();
+        }
+
+        pc += simpson_weight * rho * ring;
+    }
+
+    Ok(norm_factor * pc * d_theta * d_rho / 3.0)
+}
@@ -0,0 +1,419 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::dynamics::SpacecraftDynamics;
+use crate::io::ConfigError;
+use crate::linalg::{Matrix6, Vector6};
+use crate::md::prelude::Traj;
+use crate::od::estimate::{Estimate, KfEstimate};
+use crate::od::filter::kalman::KF;
+use crate::od::msr::RangeDoppler;
+use crate::od::process::ODProcess;
+use crate::od::simulator::{TrackingArcSim, TrkConfig};
+use crate::od::snc::SNC3;
+use crate::od::{GroundStation, NonOverlappingEstimatesSnafu, ODConfigSnafu, ODError, ODTrajSnafu};
+use crate::propagators::{Propagator, RSSCartesianStep};
+use crate::time::Epoch;
+use crate::Spacecraft;
+use anise::almanac::Almanac;
+use rand_distr::Distribution;
+use rand_pcg::Pcg64Mcg;
+use rayon::prelude::*;
+use snafu::{ensure, ResultExt};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Orbit determination scenario run by [monte_carlo_consistency] for each seed.
+///
+/// For the filter to be consistent, the dynamics of the propagator should match those used to build the truth trajectory,
+/// and the noise of the devices should be the one used to simulate the measurements.
+#[derive(Clone)]
+pub struct ODScenario<'a> {
+    /// Truth trajectory of the spacecraft, from which the measurements are simulated and the errors computed
+    pub truth: Traj<Spacecraft>,
+    /// Tracking devices, used both to simulate and to process the measurements
+    pub devices: Vec<GroundStation>,
+    /// Configuration of each device
+    pub configs: BTreeMap<String, TrkConfig>,
+    /// Initial estimate: its nominal state should be the truth, and its covariance is used to disperse the initial state of each run
+    pub initial_estimate: KfEstimate<Spacecraft>,
+    /// Propagator of the filter
+    pub prop: Propagator<'a, SpacecraftDynamics, RSSCartesianStep>,
+    /// Optional process noise of the filter
+    pub process_noise: Option<SNC3>,
+    pub almanac: Arc<Almanac>,
+}
+
+/// Ensemble statistics of the orbit determination errors at a given epoch, across all of the runs.
+///
+/// The errors are the estimated minus the true Cartesian position (km) and velocity (km/s).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EnsembleStats {
+    pub epoch: Epoch,
+    /// Number of runs with an estimate at this epoch
+    pub num_runs: usize,
+    /// Ensemble mean of the errors, should be close to zero for an unbiased filter
+    pub mean_error: Vector6<f64>,
+    /// Ensemble root mean square of the errors
+    pub rms_error: Vector6<f64>,
+    /// Root mean square of the 1-sigma uncertainty reported by the filter, should match the RMS error for a consistent filter
+    pub mean_sigma: Vector6<f64>,
+    /// Average normalized estimation error squared (ANEES) across the runs
+    pub anees: f64,
+}
+
+/// Consistency report of a filter, built by [monte_carlo_consistency].
+///
+/// The normalized estimation error squared (NEES) of each estimate is `e^T P^-1 e`, where `e` is the Cartesian position and
+/// velocity error and `P` the associated covariance. For a consistent filter, it follows a chi-square distribution with six degrees of freedom.
+#[derive(Clone, Debug)]
+pub struct ConsistencyReport {
+    /// Seed of the first run, each run `i` uses `seed + i`
+    pub seed: u64,
+    pub num_runs: usize,
+    /// Ensemble statistics at each estimate epoch, ordered chronologically
+    pub epochs: Vec<EnsembleStats>,
+    /// NEES of every estimate of every run
+    pub nees: Vec<f64>,
+}
+
+impl ConsistencyReport {
+    /// Degrees of freedom of the NEES, i.e. the Cartesian position and velocity
+    pub const DOF: usize = 6;
+
+    /// Returns the average NEES across all of the estimates of all of the runs, should be close to [Self::DOF].
+    pub fn anees(&self) -> f64 {
+        self.nees.iter().sum::<f64>() / (self.nees.len() as f64)
+    }
+
+    /// Returns the (lower, upper) bounds of the ANEES of an epoch over `num_runs` consistent runs, at the provided number of sigmas
+    /// of the normal distribution (e.g. 1.96 for a 95% two-sided confidence).
+    ///
+    /// The sum of the NEES of the runs follows a chi-square distribution with `num_runs * DOF` degrees of freedom,
+    /// whose quantiles are computed with the Wilson–Hilferty approximation.
+    pub fn anees_bounds(&self, num_sigmas: f64) -> (f64, f64) {
+        let k = (self.num_runs * Self::DOF) as f64;
+        let quantile = |z: f64| k * (1.0 - 2.0 / (9.0 * k) + z * (2.0 / (9.0 * k)).sqrt()).powi(3);
+        let n = self.num_runs as f64;
+        (quantile(-num_sigmas) / n, quantile(num_sigmas) / n)
+    }
+
+    /// Returns the fraction of the epochs after the provided one (included) whose ANEES is within the bounds at the provided number of sigmas.
+    pub fn fraction_within_bounds(&self, num_sigmas: f64, after: Epoch) -> f64 {
+        let (lower, upper) = self.anees_bounds(num_sigmas);
+        let epochs = self.epochs.iter().filter(|stats| stats.epoch >= after);
+        let (count, within) = epochs.fold((0, 0), |(count, within), stats| {
+            let is_within = stats.anees >= lower && stats.anees <= upper;
+            (count + 1, within + usize::from(is_within))
+        });
+        within as f64 / count as f64
+    }
+
+    /// Returns the histogram of the NEES of all of the estimates, with `num_bins` bins of equal width from zero to `max_nees`.
+    /// The last bin also counts the NEES greater than `max_nees`.
+    pub fn nees_histogram(&self, num_bins: usize, max_nees: f64) -> Vec<usize> {
+        let mut bins = vec![0; num_bins];
+        for nees in &self.nees {
+            let idx = ((nees / max_nees) * num_bins as f64).floor() as usize;
+            bins[idx.min(num_bins - 1)] += 1;
+        }
+        bins
+    }
+}
+
+impl fmt::Display for ConsistencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (lower, upper) = self.anees_bounds(1.96);
+        write!(
+            f,
+            "Consistency over {} runs (seed {}) and {} epochs: ANEES = {:.3} (DOF = {}, 95% bounds per epoch [{:.3}, {:.3}])",
+            self.num_runs,
+            self.seed,
+            self.epochs.len(),
+            self.anees(),
+            Self::DOF,
+            lower,
+            upper
+        )
+    }
+}
+
+/// Errors and NEES of a single orbit determination run.
+struct ConsistencyRun {
+    epochs: Vec<Epoch>,
+    errors: Vec<Vector6<f64>>,
+    variances: Vec<Vector6<f64>>,
+    nees: Vec<f64>,
+}
+
+/// Running sums of the errors of all of the runs at a given epoch.
+struct Accumulator {
+    count: usize,
+    sum_error: Vector6<f64>,
+    sum_sq_error: Vector6<f64>,
+    sum_variance: Vector6<f64>,
+    sum_nees: f64,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum_error: Vector6::zeros(),
+            sum_sq_error: Vector6::zeros(),
+            sum_variance: Vector6::zeros(),
+            sum_nees: 0.0,
+        }
+    }
+}
+
+/// Runs the orbit determination of the scenario for `n_runs` seeds in parallel, and aggregates the ensemble statistics of the errors.
+///
+/// Each run `i` uses the seed `seed + i` to disperse its initial state from the covariance of the initial estimate and
+/// to simulate the noise of the measurements. The tracking schedule is built once, so all of the runs have estimates at the same epochs.
+pub fn monte_carlo_consistency(
+    scenario: &ODScenario,
+    n_runs: usize,
+    seed: u64,
+) -> Result<ConsistencyReport, ODError> {
+    let almanac = scenario.almanac.clone();
+
+    // Build the schedule once, it does not depend on the noise.
+    let mut arc_sim = TrackingArcSim::<Spacecraft, RangeDoppler, _>::with_seed(
+        scenario.devices.clone(),
+        scenario.truth.clone(),
+        scenario.configs.clone(),
+        seed,
+    )
+    .context(ODConfigSnafu)?;
+    arc_sim
+        .build_schedule(almanac.clone())
+        .map_err(|e| ODError::ODConfigError {
+            source: ConfigError::InvalidConfig { msg: e.to_string() },
+        })?;
+    let configs = arc_sim.configs;
+
+    let initial_rv = scenario
+        .initial_estimate
+        .to_random_variable()
+        .map_err(|e| ODError::ODConfigError {
+            source: ConfigError::InvalidConfig {
+                msg: format!("initial estimate cannot be dispersed: {e}"),
+            },
+        })?;
+
+    let runs = (0..n_runs)
+        .into_par_iter()
+        .map_with(
+            scenario.prop.clone(),
+            |prop, run| -> Result<ConsistencyRun, ODError> {
+                let run_seed = seed.wrapping_add(run as u64);
+
+                let mut arc_sim = TrackingArcSim::<Spacecraft, RangeDoppler, _>::with_seed(
+                    scenario.devices.clone(),
+                    scenario.truth.clone(),
+                    configs.clone(),
+                    run_seed,
+                )
+                .context(ODConfigSnafu)?;
+                let arc = arc_sim
+                    .generate_measurements(almanac.clone())
+                    .map_err(|e| ODError::ODConfigError {
+                        source: ConfigError::InvalidConfig { msg: e.to_string() },
+                    })?;
+
+                let mut rng = Pcg64Mcg::new(run_seed as u128);
+                let dispersed = initial_rv.sample(&mut rng).state;
+                let initial_estimate =
+                    KfEstimate::from_covar(dispersed, scenario.initial_estimate.covar);
+                let kf = match scenario.process_noise.clone() {
+                    Some(snc) => KF::new(initial_estimate, snc),
+                    None => KF::no_snc(initial_estimate),
+                };
+
+                let mut odp = ODProcess::ckf(
+                    prop.with(dispersed.with_stm(), almanac.clone()),
+                    kf,
+                    None,
+                    almanac.clone(),
+                );
+                odp.process_arc::<GroundStation>(&arc)?;
+
+                let mut result = ConsistencyRun {
+                    epochs: Vec::with_capacity(odp.estimates.len()),
+                    errors: Vec::with_capacity(odp.estimates.len()),
+                    variances: Vec::with_capacity(odp.estimates.len()),
+                    nees: Vec::with_capacity(odp.estimates.len()),
+                };
+
+                for estimate in &odp.estimates {
+                    let truth = scenario.truth.at(estimate.epoch()).context(ODTrajSnafu)?;
+                    let error = estimate.state().orbit.to_cartesian_pos_vel()
+                        - truth.orbit.to_cartesian_pos_vel();
+                    let covar: Matrix6<f64> = estimate.covar().fixed_view::<6, 6>(0, 0).into();
+                    let info = covar
+                        .try_inverse()
+                        .ok_or(ODError::SingularInformationMatrix {
+                            action: "computing the NEES",
+                        })?;
+
+                    result.epochs.push(estimate.epoch());
+                    result.errors.push(error);
+                    result.variances.push(covar.diagonal());
+                    result.nees.push((error.transpose() * info * error)[0]);
+                }
+
+                Ok(result)
+            },
+        )
+        .collect::<Result<Vec<ConsistencyRun>, ODError>>()?;
+
+    // Aggregate the runs by epoch
+    let mut accumulators = BTreeMap::<Epoch, Accumulator>::new();
+    let mut nees = Vec::new();
+    for run in &runs {
+        for (i, epoch) in run.epochs.iter().enumerate() {
+            let acc = accumulators.entry(*epoch).or_default();
+            acc.count += 1;
+            acc.sum_error += run.errors[i];
+            acc.sum_sq_error += run.errors[i].component_mul(&run.errors[i]);
+            acc.sum_variance += run.variances[i];
+            acc.sum_nees += run.nees[i];
+        }
+        nees.extend_from_slice(&run.nees);
+    }
+
+    let epochs = accumulators
+        .into_iter()
+        .map(|(epoch, acc)| {
+            let n = acc.count as f64;
+            EnsembleStats {
+                epoch,
+                num_runs: acc.count,
+                mean_error: acc.sum_error / n,
+                rms_error: (acc.sum_sq_error / n).map(f64::sqrt),
+                mean_sigma: (acc.sum_variance / n).map(f64::sqrt),
+                anees: acc.sum_nees / n,
+            }
+        })
+        .collect();
+
+    Ok(ConsistencyReport {
+        seed,
+        num_runs: n_runs,
+        epochs,
+        nees,
+    })
+}
+
+/// Result of the overlap test of two orbit determination solutions at the same epoch, built by [overlap_consistency].
+///
+/// The squared Mahalanobis distance of the difference between both solutions, given their combined covariance, follows a chi-square
+/// distribution with six degrees of freedom if the solutions are independent and consistent.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OverlapResult {
+    pub epoch: Epoch,
+    /// Difference of the Cartesian position (km) and velocity (km/s) of the first solution minus the second
+    pub difference: Vector6<f64>,
+    /// Mahalanobis distance of the difference given the sum of the covariances of both solutions
+    pub mahalanobis: f64,
+    /// Probability that a chi-square with [Self::DOF] degrees of freedom exceeds the squared Mahalanobis distance
+    pub p_value: f64,
+}
+
+impl OverlapResult {
+    /// Degrees of freedom of the test, i.e. the Cartesian position and velocity
+    pub const DOF: usize = 6;
+
+    /// Returns whether both solutions are consistent at the provided significance level, e.g. 0.01 for a 99% confidence.
+    pub fn is_consistent(&self, significance: f64) -> bool {
+        self.p_value >= significance
+    }
+}
+
+impl fmt::Display for OverlapResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Overlap @ {}: Δr = {:.3} m, Δv = {:.3} mm/s, Mahalanobis distance = {:.3} (p-value = {:.3e})",
+            self.epoch,
+            self.difference.fixed_rows::<3>(0).norm() * 1e3,
+            self.difference.fixed_rows::<3>(3).norm() * 1e6,
+            self.mahalanobis,
+            self.p_value
+        )
+    }
+}
+
+/// Checks the consistency of two independent orbit determination solutions at the same epoch, e.g. from overlapping tracking arcs,
+/// by computing the Mahalanobis distance of their Cartesian difference given their combined covariance.
+///
+/// Both estimates must be in the same frame. Only the position and velocity are compared.
+pub fn overlap_consistency(
+    est_a: &KfEstimate<Spacecraft>,
+    est_b: &KfEstimate<Spacecraft>,
+) -> Result<OverlapResult, ODError> {
+    ensure!(
+        est_a.epoch() == est_b.epoch(),
+        NonOverlappingEstimatesSnafu {
+            epoch_a: est_a.epoch(),
+            epoch_b: est_b.epoch()
+        }
+    );
+
+    ensure!(
+        est_a.state().orbit.frame == est_b.state().orbit.frame,
+        EstimateFrameMismatchSnafu {
+            frame_a: est_a.state().orbit.frame,
+            frame_b: est_b.state().orbit.frame
+        }
+    );
+
+    let difference =
+        est_a.state().orbit.to_cartesian_pos_vel() - est_b.state().orbit.to_cartesian_pos_vel();
+    let covar_a: Matrix6<f64> = est_a.covar().fixed_view::<6, 6>(0, 0).into();
+    let covar_b: Matrix6<f64> = est_b.covar().fixed_view::<6, 6>(0, 0).into();
+    let info = (covar_a + covar_b)
+        .try_inverse()
+        .ok_or(ODError::SingularInformationMatrix {
+            action: "computing the overlap consistency",
+        })?;
+
+    let chi_sq = (difference.transpose() * info * difference)[0];
+
+    Ok(OverlapResult {
+        epoch: est_a.epoch(),
+        difference,
+        mahalanobis: chi_sq.sqrt(),
+        p_value: chi_square_survival(chi_sq, OverlapResult::DOF),
+    })
+}
+
+/// Returns the probability that a chi-square distribution with an even number of degrees of freedom exceeds `x`,
+/// computed from its closed form `exp(-x/2) * sum_{i < dof/2} (x/2)^i / i!`.
+fn chi_square_survival(x: f64, dof: usize) -> f64 {
+    let half_x = 0.5 * x;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for i in 1..dof / 2 {
+        term *= half_x / i as f64;
+        sum += term;
+    }
+    (-half_x).exp() * sum
+}
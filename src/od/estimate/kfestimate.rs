@@ -18,13 +18,15 @@
 
 use super::{Estimate, State};
 use crate::cosmic::AstroError;
+use crate::dynamics::guidance::LocalFrame;
 use crate::linalg::allocator::Allocator;
-use crate::linalg::{DefaultAllocator, DimName, Matrix, OMatrix, OVector};
+use crate::linalg::{DefaultAllocator, DimName, Matrix, OMatrix, OVector, Vector3};
 use crate::mc::{MultivariateNormal, StateDispersion};
 use crate::md::prelude::OrbitDual;
 use crate::md::StateParameter;
 use crate::Spacecraft;
-use na::SMatrix;
+use anise::astro::PhysicsResult;
+use na::{SMatrix, SVector};
 use nalgebra::Const;
 use rand::SeedableRng;
 use rand_distr::Distribution;
@@ -148,6 +150,86 @@ impl KfEstimate<Spacecraft> {
         })
     }
 
+    /// Builds an estimate whose covariance is diagonal in the inertial frame of the nominal state, from the 1-sigma uncertainty
+    /// on each component of the position (in km) and of the velocity (in km/s).
+    ///
+    /// The covariance of the Cr, Cd, and mass is zero. Refer to `from_ric_sigmas` to specify the uncertainties in the RIC frame instead.
+    pub fn from_pos_vel_sigmas(nominal_state: Spacecraft, pos_km: f64, vel_km_s: f64) -> Self {
+        let mut diag = OVector::<f64, Const<9>>::zeros();
+        for i in 0..3 {
+            diag[i] = pos_km.powi(2);
+            diag[i + 3] = vel_km_s.powi(2);
+        }
+
+        Self::from_covar(nominal_state, Matrix::from_diagonal(&diag))
+    }
+
+    /// Builds an estimate whose covariance is diagonal in the RIC frame of the nominal state, from the 1-sigma uncertainties
+    /// on the radial, in-track, and cross-track components of the position (in km) and of the velocity (in km/s).
+    ///
+    /// The covariance is rotated into the inertial frame of the nominal state, accounting for the rotation rate of the RIC frame.
+    /// The covariance of the Cr, Cd, and mass is zero.
+    pub fn from_ric_sigmas(
+        nominal_state: Spacecraft,
+        pos_ric_km: Vector3<f64>,
+        vel_ric_km_s: Vector3<f64>,
+    ) -> PhysicsResult<Self> {
+        let dcm_ric2inertial = LocalFrame::RIC
+            .dcm_to_inertial(nominal_state.orbit)?
+            .state_dcm();
+
+        let ric_covar = SMatrix::<f64, 6, 6>::from_diagonal(&OVector::<f64, Const<6>>::new(
+            pos_ric_km.x.powi(2),
+            pos_ric_km.y.powi(2),
+            pos_ric_km.z.powi(2),
+            vel_ric_km_s.x.powi(2),
+            vel_ric_km_s.y.powi(2),
+            vel_ric_km_s.z.powi(2),
+        ));
+
+        let mut covar = OMatrix::<f64, Const<9>, Const<9>>::zeros();
+        covar
+            .fixed_view_mut::<6, 6>(0, 0)
+            .copy_from(&(dcm_ric2inertial * ric_covar * dcm_ric2inertial.transpose()));
+
+        Ok(Self::from_covar(nominal_state, covar))
+    }
+
+    /// Returns the position and velocity part of the state deviation (i.e. the correction to the nominal state) in the RIC frame of the nominal state,
+    /// in km and km/s. This helps identifying whether the errors of the nominal state are radial, in-track, or cross-track.
+    ///
+    /// The velocity accounts for the rotation rate of the RIC frame.
+    pub fn state_deviation_ric(&self) -> PhysicsResult<SVector<f64, 6>> {
+        self.state_deviation_in_frame(LocalFrame::RIC)
+    }
+
+    /// Returns the position and velocity part of the state deviation in the provided local frame of the nominal state, in km and km/s.
+    ///
+    /// The velocity accounts for the rotation rate of the local frame, if any.
+    pub fn state_deviation_in_frame(&self, frame: LocalFrame) -> PhysicsResult<SVector<f64, 6>> {
+        let dcm_inertial2local = frame
+            .dcm_to_inertial(self.nominal_state.orbit)?
+            .transpose()
+            .state_dcm();
+
+        Ok(dcm_inertial2local * self.state_deviation.fixed_rows::<6>(0))
+    }
+
+    /// Returns the 6x6 covariance of the position and velocity in the provided local frame of the nominal state (e.g. RIC or VNC),
+    /// in km and km/s. This is the covariance used for the uncertainties along and across the velocity, for example.
+    ///
+    /// The velocity accounts for the rotation rate of the local frame, if any.
+    pub fn covar_in_frame(&self, frame: LocalFrame) -> PhysicsResult<SMatrix<f64, 6, 6>> {
+        let dcm_inertial2local = frame
+            .dcm_to_inertial(self.nominal_state.orbit)?
+            .transpose()
+            .state_dcm();
+
+        Ok(dcm_inertial2local
+            * self.covar.fixed_view::<6, 6>(0, 0)
+            * dcm_inertial2local.transpose())
+    }
+
     /// Builds a multivariate random variable from this estimate's nominal state and covariance, zero mean.
     pub fn to_random_variable(&self) -> Result<MultivariateNormal, Box<dyn Error>> {
         MultivariateNormal::from_spacecraft_cov(
@@ -349,6 +431,8 @@ where
 
 #[cfg(test)]
 mod ut_kfest {
+    use crate::dynamics::guidance::LocalFrame;
+    use crate::linalg::Vector3;
     use crate::{
         mc::StateDispersion, md::StateParameter, od::estimate::KfEstimate, Spacecraft,
         GMAT_EARTH_GM,
@@ -403,4 +487,111 @@ mod ut_kfest {
         assert!(delta.velocity_km_s.y < initial_estimate.covar[(4, 4)].sqrt());
         assert!(delta.velocity_km_s.z < initial_estimate.covar[(5, 5)].sqrt());
     }
+
+    #[test]
+    fn test_estimate_from_pos_vel_sigmas() {
+        let eme2k = EARTH_J2000.with_mu_km3_s2(GMAT_EARTH_GM);
+        let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+        let initial_state = Spacecraft::builder()
+            .orbit(Orbit::keplerian(
+                7000.0, 0.01, 28.5, 15.0, 55.0, 123.0, dt, eme2k,
+            ))
+            .build();
+
+        // 100 m in position and 1 m/s in velocity
+        let estimate = KfEstimate::from_pos_vel_sigmas(initial_state, 0.1, 1e-3);
+        println!("{estimate}");
+
+        assert_eq!(estimate.nominal_state, initial_state);
+        assert_eq!(estimate.state_deviation.norm(), 0.0);
+        for i in 0..9 {
+            let expected = if i < 3 {
+                1e-2
+            } else if i < 6 {
+                1e-6
+            } else {
+                0.0
+            };
+            assert!((estimate.covar[(i, i)] - expected).abs() < 1e-18);
+            for j in 0..9 {
+                if i != j {
+                    assert_eq!(estimate.covar[(i, j)], 0.0);
+                }
+            }
+        }
+        assert_eq!(estimate.covar, estimate.covar_bar);
+    }
+
+    #[test]
+    fn test_estimate_from_ric_sigmas() {
+        let eme2k = EARTH_J2000.with_mu_km3_s2(GMAT_EARTH_GM);
+        let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+        let initial_state = Spacecraft::builder()
+            .orbit(Orbit::keplerian(
+                7000.0, 0.01, 28.5, 15.0, 55.0, 123.0, dt, eme2k,
+            ))
+            .build();
+
+        let pos_ric_km = Vector3::new(0.1, 1.0, 0.05);
+        let vel_ric_km_s = Vector3::new(1e-3, 1e-4, 5e-5);
+
+        let estimate =
+            KfEstimate::from_ric_sigmas(initial_state, pos_ric_km, vel_ric_km_s).unwrap();
+        println!("{estimate}");
+
+        // The inertial covariance is not diagonal, but its trace is preserved for the position.
+        assert!(estimate.covar[(0, 1)].abs() > 1e-6);
+        assert!(
+            (estimate.covar.fixed_view::<3, 3>(0, 0).trace() - pos_ric_km.norm_squared()).abs()
+                < 1e-12
+        );
+
+        // Rotate back into the RIC frame
+        let dcm_inertial2ric = LocalFrame::RIC
+            .dcm_to_inertial(initial_state.orbit)
+            .unwrap()
+            .state_dcm()
+            .try_inverse()
+            .unwrap();
+        let ric_covar = dcm_inertial2ric
+            * estimate.covar.fixed_view::<6, 6>(0, 0)
+            * dcm_inertial2ric.transpose();
+
+        let expected = [
+            pos_ric_km.x,
+            pos_ric_km.y,
+            pos_ric_km.z,
+            vel_ric_km_s.x,
+            vel_ric_km_s.y,
+            vel_ric_km_s.z,
+        ];
+        for i in 0..6 {
+            for j in 0..6 {
+                let expected_ij = if i == j { expected[i].powi(2) } else { 0.0 };
+                assert!(
+                    (ric_covar[(i, j)] - expected_ij).abs() < 1e-12,
+                    "RIC covariance mismatch at ({i}, {j}): {} != {expected_ij}",
+                    ric_covar[(i, j)]
+                );
+            }
+        }
+
+        // Cr, Cd, and mass are not estimated.
+        for i in 6..9 {
+            assert_eq!(estimate.covar[(i, i)], 0.0);
+        }
+
+        // The covariance rotation helper matches for any local frame, and preserves the trace of the position covariance.
+        let covar_ric = estimate.covar_in_frame(LocalFrame::RIC).unwrap();
+        assert!((covar_ric - ric_covar).norm() < 1e-12);
+        let covar_inertial = estimate.covar_in_frame(LocalFrame::Inertial).unwrap();
+        assert_eq!(covar_inertial, estimate.covar.fixed_view::<6, 6>(0, 0));
+        for frame in [LocalFrame::VNC, LocalFrame::RCN] {
+            let covar = estimate.covar_in_frame(frame).unwrap();
+            assert!(
+                (covar.fixed_view::<3, 3>(0, 0).trace() - pos_ric_km.norm_squared()).abs() < 1e-12,
+                "{frame:?} position covariance trace not preserved"
+            );
+        }
+    }
 }
@@ -18,14 +18,41 @@
 
 pub use crate::errors::NyxError;
 use crate::linalg::allocator::Allocator;
-use crate::linalg::{DefaultAllocator, DimName, OMatrix, OVector, U3};
+use crate::linalg::{DMatrix, DefaultAllocator, DimName, OMatrix, OVector, Vector3, U3};
+use crate::md::StateParameter;
 pub use crate::od::estimate::{Estimate, KfEstimate, Residual};
 use crate::od::process::ResidRejectCrit;
 pub use crate::od::snc::SNC;
-use crate::od::{Filter, ODDynamicsSnafu, ODError, State};
+use crate::od::{Filter, ODDynamicsSnafu, ODError, ODStateSnafu, State};
 pub use crate::time::{Epoch, Unit};
 use snafu::prelude::*;
 
+/// Formulation of the measurement update of the [KF].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum KfUpdate {
+    /// Conventional covariance update, using the Joseph formulation to preserve the symmetry of the covariance.
+    #[default]
+    Joseph,
+    /// Potter square root update: the measurements are whitened and processed one scalar at a time on the square root of the
+    /// covariance, which guarantees that the updated covariance is positive semi-definite (cf. Bierman, Factorization Methods
+    /// for Discrete Sequential Estimation, 1977). Slower than the Joseph update, but numerically robust.
+    Potter,
+}
+
+/// Consider parameters of a Schmidt Kalman filter, i.e. parameters whose uncertainty is accounted for in the measurement update,
+/// but which are not estimated themselves (e.g. station biases).
+///
+/// The consider parameters are assumed to be constant and to only affect the measurements, through a constant sensitivity matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsiderParams {
+    /// Covariance of the consider parameters, never updated by the filter.
+    pub covar: DMatrix<f64>,
+    /// Sensitivity of the measurements to the consider parameters, one row per measurement component and one column per consider parameter.
+    pub mapping: DMatrix<f64>,
+    /// Cross covariance between the estimated state and the consider parameters.
+    pub cross_covar: DMatrix<f64>,
+}
+
 /// Defines both a Classical and an Extended Kalman filter (CKF and EKF)
 /// T: Type of state
 /// A: Acceleration size (for SNC)
@@ -57,6 +84,10 @@ where
     /// Determines whether this KF should operate as a Conventional/Classical Kalman filter or an Extended Kalman Filter.
     /// Recall that one should switch to an Extended KF only once the estimate is good (i.e. after a few good measurement updates on a CKF).
     pub ekf: bool,
+    /// Selects the formulation of the measurement update, defaults to the Joseph covariance update.
+    pub update: KfUpdate,
+    /// Consider parameters, if any, making this KF a Schmidt Kalman filter.
+    pub consider: Option<ConsiderParams>,
     h_tilde: OMatrix<f64, M, <T as State>::Size>,
     h_tilde_updated: bool,
     prev_used_snc: usize,
@@ -97,6 +128,8 @@ where
             prev_estimate: initial_estimate,
             process_noise: vec![process_noise],
             ekf: false,
+            update: KfUpdate::default(),
+            consider: None,
             h_tilde: OMatrix::<f64, M, <T as State>::Size>::zeros(),
             h_tilde_updated: false,
             prev_used_snc: 0,
@@ -122,11 +155,59 @@ where
             prev_estimate: initial_estimate,
             process_noise: process_noises,
             ekf: false,
+            update: KfUpdate::default(),
+            consider: None,
+            h_tilde: OMatrix::<f64, M, <T as State>::Size>::zeros(),
+            h_tilde_updated: false,
+            prev_used_snc: 0,
+        }
+    }
+
+    /// Initializes this KF as a Schmidt Kalman filter, without SNC, which considers the uncertainty of some parameters in the
+    /// measurement update without estimating them.
+    ///
+    /// The `consider_covar` is the covariance of the consider parameters, and the `mapping` is the sensitivity of the measurements
+    /// to these parameters, e.g. a column of `[1, 0]` for a range bias on range and Doppler measurements.
+    /// The Schmidt update is used regardless of the selected update formulation.
+    pub fn with_consider(
+        initial_estimate: KfEstimate<T>,
+        consider_covar: DMatrix<f64>,
+        mapping: DMatrix<f64>,
+    ) -> Self {
+        assert_eq!(
+            consider_covar.nrows(),
+            consider_covar.ncols(),
+            "consider covariance must be square"
+        );
+        assert_eq!(
+            mapping.shape(),
+            (M::dim(), consider_covar.ncols()),
+            "consider mapping must have one row per measurement and one column per consider parameter"
+        );
+
+        let cross_covar = DMatrix::zeros(<T as State>::Size::dim(), consider_covar.ncols());
+
+        Self {
+            prev_estimate: initial_estimate,
+            process_noise: Vec::new(),
+            ekf: false,
+            update: KfUpdate::default(),
+            consider: Some(ConsiderParams {
+                covar: consider_covar,
+                mapping,
+                cross_covar,
+            }),
             h_tilde: OMatrix::<f64, M, <T as State>::Size>::zeros(),
             h_tilde_updated: false,
             prev_used_snc: 0,
         }
     }
+
+    /// Sets the formulation of the measurement update of this KF.
+    pub fn with_update(mut self, update: KfUpdate) -> Self {
+        self.update = update;
+        self
+    }
 }
 
 impl<T, M> KF<T, U3, M>
@@ -152,6 +233,8 @@ where
             prev_estimate: initial_estimate,
             process_noise: Vec::new(),
             ekf: false,
+            update: KfUpdate::default(),
+            consider: None,
             h_tilde: OMatrix::<f64, M, <T as State>::Size>::zeros(),
             h_tilde_updated: false,
             prev_used_snc: 0,
@@ -188,6 +271,10 @@ where
 
     fn set_previous_estimate(&mut self, est: &Self::Estimate) {
         self.prev_estimate = *est;
+        // Restart without any correlation with the consider parameters
+        if let Some(consider) = self.consider.as_mut() {
+            consider.cross_covar.fill(0.0);
+        }
     }
 
     /// Update the sensitivity matrix (or "H tilde"). This function **must** be called prior to each
@@ -205,46 +292,17 @@ where
         let mut covar_bar = stm * self.prev_estimate.covar * stm.transpose();
 
         // Try to apply an SNC, if applicable
-        for (i, snc) in self.process_noise.iter().enumerate().rev() {
-            if let Some(snc_matrix) = snc.to_matrix(nominal_state.epoch()) {
-                // Check if we're using another SNC than the one before
-                if self.prev_used_snc != i {
-                    info!("Switched to {}-th {}", i, snc);
-                    self.prev_used_snc = i;
-                }
+        if let Some(snc_covar) = process_noise_covar::<T, A>(
+            &self.process_noise,
+            &mut self.prev_used_snc,
+            &nominal_state,
+            self.prev_estimate.epoch(),
+        )? {
+            covar_bar += snc_covar;
+        }
 
-                // Let's compute the Gamma matrix, an approximation of the time integral
-                // which assumes that the acceleration is constant between these two measurements.
-                let mut gamma = OMatrix::<f64, <T as State>::Size, A>::zeros();
-                let delta_t = (nominal_state.epoch() - self.prev_estimate.epoch()).to_seconds();
-                for blk in 0..A::dim() / 3 {
-                    for i in 0..3 {
-                        let idx_i = i + A::dim() * blk;
-                        let idx_j = i + 3 * blk;
-                        let idx_k = i + 3 + A::dim() * blk;
-                        // For first block
-                        // (0, 0) (1, 1) (2, 2) <=> \Delta t^2/2
-                        // (3, 0) (4, 1) (5, 2) <=> \Delta t
-                        // Second block
-                        // (6, 3) (7, 4) (8, 5) <=> \Delta t^2/2
-                        // (9, 3) (10, 4) (11, 5) <=> \Delta t
-                        // * \Delta t^2/2
-                        // (i, i) when blk = 0
-                        // (i + A::dim() * blk, i + 3) when blk = 1
-                        // (i + A::dim() * blk, i + 3 * blk)
-                        // * \Delta t
-                        // (i + 3, i) when blk = 0
-                        // (i + 3, i + 9) when blk = 1 (and I think i + 12 + 3)
-                        // (i + 3 + A::dim() * blk, i + 3 * blk)
-                        gamma[(idx_i, idx_j)] = delta_t.powi(2) / 2.0;
-                        gamma[(idx_k, idx_j)] = delta_t;
-                    }
-                }
-                // Let's add the process noise
-                covar_bar += &gamma * snc_matrix * &gamma.transpose();
-                // And break so we don't add any more process noise
-                break;
-            }
+        if let Some(consider) = self.consider.as_mut() {
+            consider.cross_covar = to_dmatrix(&stm) * &consider.cross_covar;
         }
 
         let state_bar = if self.ekf {
@@ -292,14 +350,28 @@ where
         let h_tilde_t = &self.h_tilde.transpose();
         let h_p_ht = &self.h_tilde * covar_bar * h_tilde_t;
         // Account for state uncertainty in the measurement noise. Equation 4.10 of ODTK MathSpec.
-        let r_k = &h_p_ht + measurement_covar;
+        let mut r_k = &h_p_ht + &measurement_covar;
+
+        // Account for the uncertainty of the consider parameters and their correlation with the state
+        let cross_covar_bar = self
+            .consider
+            .as_ref()
+            .map(|consider| to_dmatrix(&stm) * &consider.cross_covar);
+        if let (Some(consider), Some(cross_covar_bar)) = (&self.consider, &cross_covar_bar) {
+            let h_pxc_hct =
+                to_dmatrix(&self.h_tilde) * cross_covar_bar * consider.mapping.transpose();
+            let consider_noise = &h_pxc_hct
+                + h_pxc_hct.transpose()
+                + &consider.mapping * &consider.covar * consider.mapping.transpose();
+            r_k += OMatrix::<f64, M, M>::from_fn(|i, j| consider_noise[(i, j)]);
+        }
 
         // Compute observation deviation (usually marked as y_i)
         let prefit = real_obs - computed_obs;
 
         // Compute the prefit ratio for the automatic rejection
         let r_k_inv = r_k.clone().try_inverse().ok_or(ODError::SingularNoiseRk)?;
-        let ratio_mat = prefit.transpose() * r_k_inv * &prefit;
+        let ratio_mat = prefit.transpose() * &r_k_inv * &prefit;
         let ratio = ratio_mat[0].sqrt();
 
         if let Some(resid_reject) = resid_rejection {
@@ -313,37 +385,68 @@ where
             }
         }
 
-        // Compute the Kalman gain but first adding the measurement noise to H⋅P⋅H^T
-        let mut innovation_covar = h_p_ht + &r_k;
-        if !innovation_covar.try_inverse_mut() {
-            return Err(ODError::SingularKalmanGain);
-        }
-
-        let gain = covar_bar * h_tilde_t * &innovation_covar;
-
-        // Compute the state estimate
-        let (state_hat, res) = if self.ekf {
-            let state_hat = &gain * &prefit;
-            let postfit = &prefit - (&self.h_tilde * state_hat);
-            (
-                state_hat,
-                Residual::accepted(epoch, prefit, postfit, ratio, r_k.diagonal()),
-            )
+        let state_bar = if self.ekf {
+            OVector::<f64, <T as State>::Size>::zeros()
         } else {
             // Must do a time update first
-            let state_bar = stm * self.prev_estimate.state_deviation;
-            let postfit = &prefit - (&self.h_tilde * state_bar);
-            (
-                state_bar + &gain * &postfit,
-                Residual::accepted(epoch, prefit, postfit, ratio, r_k.diagonal()),
-            )
+            stm * self.prev_estimate.state_deviation
+        };
+
+        let (state_hat, covar, cross_covar) = match (&self.consider, cross_covar_bar, self.update) {
+            (Some(consider), Some(cross_covar_bar), _) => {
+                let (state_hat, covar, cross_covar) = schmidt_update(
+                    &covar_bar,
+                    &self.h_tilde,
+                    state_bar,
+                    &prefit,
+                    &r_k,
+                    &cross_covar_bar,
+                    consider,
+                )?;
+                (state_hat, covar, Some(cross_covar))
+            }
+            (_, _, KfUpdate::Joseph) => {
+                // Compute the Kalman gain from the innovation covariance r_k = H⋅P⋅H^T + R, i.e. with the same measurement noise R
+                // as the Potter update.
+                let gain = covar_bar * h_tilde_t * &r_k_inv;
+
+                // Compute the state estimate
+                let state_hat = if self.ekf {
+                    &gain * &prefit
+                } else {
+                    state_bar + &gain * (&prefit - (&self.h_tilde * state_bar))
+                };
+
+                // Compute covariance (Joseph update)
+                let first_term = OMatrix::<f64, <T as State>::Size, <T as State>::Size>::identity()
+                    - &gain * &self.h_tilde;
+                let covar = first_term * covar_bar * first_term.transpose()
+                    + &gain * &measurement_covar * &gain.transpose();
+
+                (state_hat, covar, None)
+            }
+            (_, _, KfUpdate::Potter) => {
+                let (state_hat, covar) = potter_update(
+                    &covar_bar,
+                    &self.h_tilde,
+                    state_bar,
+                    &prefit,
+                    &measurement_covar,
+                )?;
+                (state_hat, covar, None)
+            }
         };
 
-        // Compute covariance (Joseph update)
-        let first_term = OMatrix::<f64, <T as State>::Size, <T as State>::Size>::identity()
-            - &gain * &self.h_tilde;
-        let covar =
-            first_term * covar_bar * first_term.transpose() + &gain * &r_k * &gain.transpose();
+        if let (Some(consider), Some(cross_covar)) = (self.consider.as_mut(), cross_covar) {
+            consider.cross_covar = cross_covar;
+        }
+
+        let postfit = if self.ekf {
+            &prefit - (&self.h_tilde * state_hat)
+        } else {
+            &prefit - (&self.h_tilde * state_bar)
+        };
+        let res = Residual::accepted(epoch, prefit, postfit, ratio, r_k.diagonal());
 
         // And wrap up
         let estimate = KfEstimate {
@@ -377,3 +480,215 @@ where
         self.process_noise = vec![snc];
     }
 }
+
+/// Computes the process noise covariance (Γ⋅Q⋅Γᵀ) to add to the predicted covariance from `prev_epoch` to `epoch`, using the
+/// last of the provided SNCs which applies at that epoch, if any. The index of that SNC is stored in `prev_used_snc`.
+pub(crate) fn process_noise_covar<T, A>(
+    process_noise: &[SNC<A>],
+    prev_used_snc: &mut usize,
+    nominal_state: &T,
+    prev_epoch: Epoch,
+) -> Result<Option<OMatrix<f64, <T as State>::Size, <T as State>::Size>>, ODError>
+where
+    A: DimName,
+    T: State,
+    DefaultAllocator: Allocator<<T as State>::Size>
+        + Allocator<<T as State>::VecLength>
+        + Allocator<<T as State>::Size, <T as State>::Size>
+        + Allocator<A>
+        + Allocator<A, A>
+        + Allocator<<T as State>::Size, A>
+        + Allocator<A, <T as State>::Size>,
+{
+    let epoch = nominal_state.epoch();
+    for (i, snc) in process_noise.iter().enumerate().rev() {
+        if let Some(mut snc_matrix) = snc.to_matrix(epoch) {
+            // Check if we're using another SNC than the one before
+            if *prev_used_snc != i {
+                info!("Switched to {}-th {}", i, snc);
+                *prev_used_snc = i;
+            }
+
+            // Rotate the SNC into the inertial frame if it is expressed in the local frame of the nominal state
+            if snc.ric_frame {
+                let mut pos_vel = [0.0; 6];
+                for (value, param) in pos_vel.iter_mut().zip([
+                    StateParameter::X,
+                    StateParameter::Y,
+                    StateParameter::Z,
+                    StateParameter::VX,
+                    StateParameter::VY,
+                    StateParameter::VZ,
+                ]) {
+                    *value = nominal_state.value(param).context(ODStateSnafu {
+                        action: "rotating the RIC process noise into the inertial frame",
+                    })?;
+                }
+                let radius_km = Vector3::new(pos_vel[0], pos_vel[1], pos_vel[2]);
+                let velocity_km_s = Vector3::new(pos_vel[3], pos_vel[4], pos_vel[5]);
+                if let Some(dcm) = snc.dcm_to_inertial(&radius_km, &velocity_km_s) {
+                    snc_matrix = &dcm * snc_matrix * dcm.transpose();
+                }
+            }
+
+            // Let's compute the Gamma matrix, an approximation of the time integral
+            // which assumes that the acceleration is constant between these two measurements.
+            let mut gamma = OMatrix::<f64, <T as State>::Size, A>::zeros();
+            let delta_t = (epoch - prev_epoch).to_seconds();
+            for blk in 0..A::dim() / 3 {
+                for i in 0..3 {
+                    let idx_i = i + A::dim() * blk;
+                    let idx_j = i + 3 * blk;
+                    let idx_k = i + 3 + A::dim() * blk;
+                    // For first block
+                    // (0, 0) (1, 1) (2, 2) <=> \Delta t^2/2
+                    // (3, 0) (4, 1) (5, 2) <=> \Delta t
+                    // Second block
+                    // (6, 3) (7, 4) (8, 5) <=> \Delta t^2/2
+                    // (9, 3) (10, 4) (11, 5) <=> \Delta t
+                    // * \Delta t^2/2
+                    // (i, i) when blk = 0
+                    // (i + A::dim() * blk, i + 3) when blk = 1
+                    // (i + A::dim() * blk, i + 3 * blk)
+                    // * \Delta t
+                    // (i + 3, i) when blk = 0
+                    // (i + 3, i + 9) when blk = 1 (and I think i + 12 + 3)
+                    // (i + 3 + A::dim() * blk, i + 3 * blk)
+                    gamma[(idx_i, idx_j)] = delta_t.powi(2) / 2.0;
+                    gamma[(idx_k, idx_j)] = delta_t;
+                }
+            }
+            // Only the last applicable process noise is used
+            return Ok(Some(&gamma * snc_matrix * &gamma.transpose()));
+        }
+    }
+    Ok(None)
+}
+
+/// Schmidt's consider measurement update, where the innovation covariance `r_k` already includes the contribution of the consider parameters.
+/// Returns the updated state deviation, starting from `state_bar`, the updated covariance, and the updated cross covariance with the
+/// consider parameters. The covariance of the consider parameters themselves is not updated.
+fn schmidt_update<N, M>(
+    covar_bar: &OMatrix<f64, N, N>,
+    h_tilde: &OMatrix<f64, M, N>,
+    state_bar: OVector<f64, N>,
+    prefit: &OVector<f64, M>,
+    r_k: &OMatrix<f64, M, M>,
+    cross_covar_bar: &DMatrix<f64>,
+    consider: &ConsiderParams,
+) -> Result<(OVector<f64, N>, OMatrix<f64, N, N>, DMatrix<f64>), ODError>
+where
+    N: DimName,
+    M: DimName,
+    DefaultAllocator: Allocator<N>
+        + Allocator<M>
+        + Allocator<N, N>
+        + Allocator<M, M>
+        + Allocator<M, N>
+        + Allocator<N, M>,
+{
+    let h_tilde_dyn = to_dmatrix(h_tilde);
+    let r_k_inv = to_dmatrix(r_k)
+        .try_inverse()
+        .ok_or(ODError::SingularKalmanGain)?;
+
+    // The gain accounts for the correlation between the state and the consider parameters
+    let gain_dyn = (to_dmatrix(covar_bar) * h_tilde_dyn.transpose()
+        + cross_covar_bar * consider.mapping.transpose())
+        * r_k_inv;
+    let gain = OMatrix::<f64, N, M>::from_fn(|i, j| gain_dyn[(i, j)]);
+
+    let state_hat = state_bar + &gain * (prefit - h_tilde * state_bar);
+
+    let covar = covar_bar - &gain * r_k * gain.transpose();
+    let cross_covar = cross_covar_bar
+        - &gain_dyn * (&h_tilde_dyn * cross_covar_bar + &consider.mapping * &consider.covar);
+
+    Ok((state_hat, (&covar + covar.transpose()) * 0.5, cross_covar))
+}
+
+/// Copies a statically sized matrix into a dynamically sized one.
+fn to_dmatrix<R, C>(mat: &OMatrix<f64, R, C>) -> DMatrix<f64>
+where
+    R: DimName,
+    C: DimName,
+    DefaultAllocator: Allocator<R, C>,
+{
+    DMatrix::from_fn(R::dim(), C::dim(), |i, j| mat[(i, j)])
+}
+
+/// Potter's square root measurement update. The measurements are whitened with the Cholesky factor of their covariance and
+/// applied one scalar at a time on the square root of the covariance.
+/// Returns the updated state deviation, starting from `state_bar`, and the updated covariance.
+fn potter_update<N, M>(
+    covar_bar: &OMatrix<f64, N, N>,
+    h_tilde: &OMatrix<f64, M, N>,
+    state_bar: OVector<f64, N>,
+    prefit: &OVector<f64, M>,
+    measurement_covar: &OMatrix<f64, M, M>,
+) -> Result<(OVector<f64, N>, OMatrix<f64, N, N>), ODError>
+where
+    N: DimName,
+    M: DimName,
+    DefaultAllocator:
+        Allocator<N> + Allocator<M> + Allocator<N, N> + Allocator<M, M> + Allocator<M, N>,
+{
+    let noise_sqrt = measurement_covar
+        .clone()
+        .cholesky()
+        .ok_or(ODError::SingularNoiseRk)?
+        .unpack();
+    let h_white = noise_sqrt
+        .solve_lower_triangular(h_tilde)
+        .ok_or(ODError::SingularNoiseRk)?;
+    let prefit_white = noise_sqrt
+        .solve_lower_triangular(prefit)
+        .ok_or(ODError::SingularNoiseRk)?;
+
+    let mut sqrt_covar = psd_sqrt(covar_bar);
+    let mut state_hat = state_bar;
+
+    for i in 0..M::dim() {
+        let h_i = h_white.row(i).transpose();
+        let f = sqrt_covar.tr_mul(&h_i);
+        // The whitened measurement has a unit variance.
+        let alpha = 1.0 / (f.norm_squared() + 1.0);
+        let gamma = 1.0 / (1.0 + alpha.sqrt());
+        let gain = alpha * &sqrt_covar * &f;
+
+        state_hat += &gain * (prefit_white[i] - h_i.dot(&state_hat));
+        sqrt_covar.ger(-gamma, &gain, &f, 1.0);
+    }
+
+    Ok((state_hat, &sqrt_covar * sqrt_covar.transpose()))
+}
+
+/// Computes the lower triangular square root of a positive semi-definite matrix with a Cholesky decomposition.
+/// Contrary to the nalgebra implementation, the rows and columns of the states which are not estimated (i.e. have a zero variance)
+/// are set to zero instead of failing.
+pub(crate) fn psd_sqrt<N>(covar: &OMatrix<f64, N, N>) -> OMatrix<f64, N, N>
+where
+    N: DimName,
+    DefaultAllocator: Allocator<N, N>,
+{
+    let n = N::dim();
+    let mut sqrt = OMatrix::<f64, N, N>::zeros();
+    for j in 0..n {
+        let mut diag = covar[(j, j)];
+        for k in 0..j {
+            diag -= sqrt[(j, k)].powi(2);
+        }
+        if diag <= f64::EPSILON * covar[(j, j)].abs().max(f64::MIN_POSITIVE) {
+            continue;
+        }
+        sqrt[(j, j)] = diag.sqrt();
+        for i in (j + 1)..n {
+            let mut val = covar[(i, j)];
+            for k in 0..j {
+                val -= sqrt[(i, k)] * sqrt[(j, k)];
+            }
+            sqrt[(i, j)] = val / sqrt[(j, j)];
+        }
+    }
+    sqrt
+}
@@ -27,6 +27,7 @@ use crate::linalg::allocator::Allocator;
 use crate::linalg::{DefaultAllocator, DimName, OMatrix, OVector};
 pub use crate::{State, TimeTagged};
 pub mod kalman;
+pub mod unscented;
 
 /// Defines a Filter trait where S is the size of the estimated state, A the number of acceleration components of the EOMs (used for process noise matrix size), M the size of the measurements.
 pub trait Filter<T, A, M>
@@ -94,4 +95,27 @@ where
 
     /// Sets the process noise matrix of the estimated state
     fn set_process_noise(&mut self, snc: SNC<A>);
+
+    /// Returns whether this is a sigma point filter (e.g. the [unscented::UKF]).
+    ///
+    /// If so, the orbit determination process propagates the sigma points of each estimate through the dynamics and the measurement
+    /// model and provides them back to the filter with `set_sigma_points` before each update. It also applies the state deviation of
+    /// each measurement update to its reference state, as for an extended filter.
+    fn is_unscented(&self) -> bool {
+        false
+    }
+
+    /// Returns the sigma points of the previous estimate, as deviations from the reference state of the orbit determination process.
+    fn sigma_points(&self) -> Vec<OVector<f64, <T as State>::Size>> {
+        Vec::new()
+    }
+
+    /// Sets the sigma points propagated to the epoch of the next update, as deviations from its nominal state, and for a measurement
+    /// update, the observations computed at each of these sigma points.
+    fn set_sigma_points(
+        &mut self,
+        _deviations: Vec<OVector<f64, <T as State>::Size>>,
+        _observations: Vec<OVector<f64, M>>,
+    ) {
+    }
 }
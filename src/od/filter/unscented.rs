@@ -0,0 +1,465 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use super::kalman::{process_noise_covar, psd_sqrt};
+use crate::linalg::allocator::Allocator;
+use crate::linalg::{Const, DefaultAllocator, DimName, OMatrix, OVector, U3};
+use crate::od::estimate::{Estimate, KfEstimate, Residual};
+use crate::od::process::ResidRejectCrit;
+use crate::od::snc::SNC;
+use crate::od::{Filter, ODDynamicsSnafu, ODError, State};
+use snafu::prelude::*;
+
+/// Defines an Unscented Kalman Filter (UKF), cf. Wan and van der Merwe (2000), "The unscented Kalman filter for nonlinear estimation".
+///
+/// Instead of linearizing the dynamics and the measurement model about the nominal state like the [super::kalman::KF], the UKF
+/// samples the covariance of each estimate with 2n+1 sigma points, which the orbit determination process propagates through the
+/// full dynamics and measurement model, and recombines them into the predicted mean and covariance. This captures the nonlinearities
+/// which degrade an EKF, e.g. with large initial uncertainties. The UKF operates about its own mean, so it is always "extended":
+/// the state deviation of each measurement update is applied to the reference state of the process.
+///
+/// The spread of the sigma points is set by alpha, beta, and kappa. The default (alpha = 1, beta = 2, kappa = 0) places them at
+/// sqrt(n) standard deviations from the mean with a zero weight on the mean for the state mean, which is well suited to orbit
+/// determination. If the sigma points are not provided by the process (e.g. when only predicting the covariance), they are mapped
+/// with the STM of the nominal state.
+///
+/// T: Type of state
+/// A: Acceleration size (for SNC)
+/// M: Measurement size (used for the sensitivity matrix)
+#[derive(Debug, Clone)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct UKF<T, A, M>
+where
+    A: DimName,
+    M: DimName,
+    T: State,
+    DefaultAllocator: Allocator<M>
+        + Allocator<<T as State>::Size>
+        + Allocator<<T as State>::VecLength>
+        + Allocator<A>
+        + Allocator<M, M>
+        + Allocator<M, <T as State>::Size>
+        + Allocator<<T as State>::Size, <T as State>::Size>
+        + Allocator<A, A>
+        + Allocator<<T as State>::Size, A>
+        + Allocator<A, <T as State>::Size>,
+    <DefaultAllocator as Allocator<<T as State>::Size>>::Buffer<f64>: Copy,
+    <DefaultAllocator as Allocator<<T as State>::Size, <T as State>::Size>>::Buffer<f64>: Copy,
+{
+    /// The previous estimate used in the UKF computations.
+    pub prev_estimate: KfEstimate<T>,
+    /// A sets of process noise (usually noted Q), must be ordered chronologically
+    pub process_noise: Vec<SNC<A>>,
+    /// Spread of the sigma points around the mean, must be positive
+    pub alpha: f64,
+    /// Prior knowledge of the distribution of the state, two is optimal for Gaussian distributions
+    pub beta: f64,
+    /// Secondary scaling of the spread of the sigma points, usually zero
+    pub kappa: f64,
+    h_tilde: OMatrix<f64, M, <T as State>::Size>,
+    h_tilde_updated: bool,
+    prev_used_snc: usize,
+    /// Deviation of the mean of the previous estimate from the reference state of the process
+    mean_deviation: OVector<f64, <T as State>::Size>,
+    /// Sigma points propagated to the next update, as deviations from its nominal state
+    sigma_deviations: Vec<OVector<f64, <T as State>::Size>>,
+    /// Observations computed at each of the propagated sigma points
+    sigma_observations: Vec<OVector<f64, M>>,
+}
+
+impl<T, A, M> UKF<T, A, M>
+where
+    A: DimName,
+    M: DimName,
+    T: State,
+    DefaultAllocator: Allocator<M>
+        + Allocator<<T as State>::Size>
+        + Allocator<<T as State>::VecLength>
+        + Allocator<A>
+        + Allocator<M, M>
+        + Allocator<M, <T as State>::Size>
+        + Allocator<<T as State>::Size, M>
+        + Allocator<<T as State>::Size, <T as State>::Size>
+        + Allocator<A, A>
+        + Allocator<<T as State>::Size, A>
+        + Allocator<A, <T as State>::Size>
+        + Allocator<Const<1>, <T as State>::Size>,
+    <DefaultAllocator as Allocator<<T as State>::Size>>::Buffer<f64>: Copy,
+    <DefaultAllocator as Allocator<<T as State>::Size, <T as State>::Size>>::Buffer<f64>: Copy,
+{
+    /// Initializes this UKF with an initial estimate and one process noise
+    pub fn new(initial_estimate: KfEstimate<T>, process_noise: SNC<A>) -> Self {
+        Self::with_sncs(initial_estimate, vec![process_noise])
+    }
+
+    /// Initializes this UKF with an initial estimate and several process noise
+    /// WARNING: SNCs MUST be ordered chronologically! They will be selected automatically by walking
+    /// the list of SNCs backward until one can be applied!
+    pub fn with_sncs(initial_estimate: KfEstimate<T>, process_noises: Vec<SNC<A>>) -> Self {
+        assert_eq!(
+            A::dim() % 3,
+            0,
+            "SNC can only be applied to accelerations multiple of 3"
+        );
+        let mut process_noises = process_noises;
+        // Set the initial epoch of the SNC
+        for snc in &mut process_noises {
+            snc.init_epoch = Some(initial_estimate.epoch());
+        }
+
+        Self {
+            mean_deviation: initial_estimate.state_deviation,
+            prev_estimate: initial_estimate,
+            process_noise: process_noises,
+            alpha: 1.0,
+            beta: 2.0,
+            kappa: 0.0,
+            h_tilde: OMatrix::<f64, M, <T as State>::Size>::zeros(),
+            h_tilde_updated: false,
+            prev_used_snc: 0,
+            sigma_deviations: Vec::new(),
+            sigma_observations: Vec::new(),
+        }
+    }
+
+    /// Sets the alpha, beta, and kappa parameters of the spread of the sigma points.
+    pub fn with_scaling(mut self, alpha: f64, beta: f64, kappa: f64) -> Self {
+        self.alpha = alpha;
+        self.beta = beta;
+        self.kappa = kappa;
+        self
+    }
+
+    /// Returns the scaling factor of the covariance (n + λ), and the weights of the mean and of the covariance of the central sigma point
+    /// and of the other sigma points.
+    fn weights(&self) -> (f64, [f64; 2], [f64; 2]) {
+        let n = <T as State>::Size::dim() as f64;
+        let lambda = self.alpha.powi(2) * (n + self.kappa) - n;
+        let scale = n + lambda;
+        let w_i = 1.0 / (2.0 * scale);
+        let w_m0 = lambda / scale;
+        let w_c0 = w_m0 + 1.0 - self.alpha.powi(2) + self.beta;
+        (scale, [w_m0, w_i], [w_c0, w_i])
+    }
+
+    /// Returns the mean deviation, followed by the pairs of sigma points along each column of the scaled square root of the covariance.
+    fn generate_sigma_points(&self) -> Vec<OVector<f64, <T as State>::Size>> {
+        let (scale, _, _) = self.weights();
+        let sqrt_covar = psd_sqrt(&(scale * self.prev_estimate.covar));
+
+        let mut sigmas = Vec::with_capacity(2 * <T as State>::Size::dim() + 1);
+        sigmas.push(self.mean_deviation);
+        for col in sqrt_covar.column_iter() {
+            sigmas.push(self.mean_deviation + col);
+            sigmas.push(self.mean_deviation - col);
+        }
+        sigmas
+    }
+
+    /// Predicts the mean deviation and the covariance at the epoch of the nominal state from the propagated sigma points, and
+    /// returns them with the process noise, the STM, and the propagated sigma points.
+    #[allow(clippy::type_complexity)]
+    fn predict(
+        &mut self,
+        nominal_state: T,
+    ) -> Result<
+        (
+            OVector<f64, <T as State>::Size>,
+            OMatrix<f64, <T as State>::Size, <T as State>::Size>,
+            OMatrix<f64, <T as State>::Size, <T as State>::Size>,
+            OMatrix<f64, <T as State>::Size, <T as State>::Size>,
+            Vec<OVector<f64, <T as State>::Size>>,
+        ),
+        ODError,
+    > {
+        let num_sigmas = 2 * <T as State>::Size::dim() + 1;
+        let deviations = std::mem::take(&mut self.sigma_deviations);
+
+        let (stm, deviations) = if deviations.len() == num_sigmas {
+            (
+                nominal_state.stm().unwrap_or_else(|_| {
+                    OMatrix::<f64, <T as State>::Size, <T as State>::Size>::identity()
+                }),
+                deviations,
+            )
+        } else {
+            // The sigma points were not propagated through the dynamics, so map them with the STM.
+            let stm = nominal_state.stm().context(ODDynamicsSnafu)?;
+            let deviations = self
+                .generate_sigma_points()
+                .into_iter()
+                .map(|sigma| stm * sigma)
+                .collect();
+            (stm, deviations)
+        };
+
+        let (_, w_m, w_c) = self.weights();
+
+        let mut mean = OVector::<f64, <T as State>::Size>::zeros();
+        for (i, sigma) in deviations.iter().enumerate() {
+            mean += w_m[i.min(1)] * sigma;
+        }
+
+        let mut covar_bar = OMatrix::<f64, <T as State>::Size, <T as State>::Size>::zeros();
+        for (i, sigma) in deviations.iter().enumerate() {
+            let delta = sigma - mean;
+            covar_bar += w_c[i.min(1)] * delta * delta.transpose();
+        }
+
+        // Try to apply an SNC, if applicable
+        let snc_covar = process_noise_covar::<T, A>(
+            &self.process_noise,
+            &mut self.prev_used_snc,
+            &nominal_state,
+            self.prev_estimate.epoch(),
+        )?
+        .unwrap_or_else(OMatrix::<f64, <T as State>::Size, <T as State>::Size>::zeros);
+        covar_bar += snc_covar;
+
+        Ok((mean, covar_bar, snc_covar, stm, deviations))
+    }
+
+    /// Stores the new estimate and updates the previous epoch of all SNCs.
+    fn store(&mut self, estimate: KfEstimate<T>) {
+        self.prev_estimate = estimate;
+        for snc in &mut self.process_noise {
+            snc.prev_epoch = Some(self.prev_estimate.epoch());
+        }
+    }
+}
+
+impl<T, M> UKF<T, U3, M>
+where
+    M: DimName,
+    T: State,
+    DefaultAllocator: Allocator<M>
+        + Allocator<<T as State>::Size>
+        + Allocator<<T as State>::VecLength>
+        + Allocator<M, M>
+        + Allocator<M, <T as State>::Size>
+        + Allocator<<T as State>::Size, M>
+        + Allocator<<T as State>::Size, <T as State>::Size>
+        + Allocator<U3, U3>
+        + Allocator<<T as State>::Size, U3>
+        + Allocator<U3, <T as State>::Size>
+        + Allocator<Const<1>, <T as State>::Size>,
+    <DefaultAllocator as Allocator<<T as State>::Size>>::Buffer<f64>: Copy,
+    <DefaultAllocator as Allocator<<T as State>::Size, <T as State>::Size>>::Buffer<f64>: Copy,
+{
+    /// Initializes this UKF without SNC
+    pub fn no_snc(initial_estimate: KfEstimate<T>) -> Self {
+        Self::with_sncs(initial_estimate, Vec::new())
+    }
+}
+
+impl<T, A, M> Filter<T, A, M> for UKF<T, A, M>
+where
+    A: DimName,
+    M: DimName,
+    T: State,
+    DefaultAllocator: Allocator<M>
+        + Allocator<<T as State>::Size>
+        + Allocator<<T as State>::VecLength>
+        + Allocator<A>
+        + Allocator<M, M>
+        + Allocator<M, <T as State>::Size>
+        + Allocator<<T as State>::Size, M>
+        + Allocator<<T as State>::Size, <T as State>::Size>
+        + Allocator<A, A>
+        + Allocator<<T as State>::Size, A>
+        + Allocator<A, <T as State>::Size>
+        + Allocator<Const<1>, M>
+        + Allocator<Const<1>, <T as State>::Size>,
+    <DefaultAllocator as Allocator<<T as State>::Size>>::Buffer<f64>: Copy,
+    <DefaultAllocator as Allocator<<T as State>::Size, <T as State>::Size>>::Buffer<f64>: Copy,
+{
+    type Estimate = KfEstimate<T>;
+
+    /// Returns the previous estimate
+    fn previous_estimate(&self) -> &Self::Estimate {
+        &self.prev_estimate
+    }
+
+    fn set_previous_estimate(&mut self, est: &Self::Estimate) {
+        self.prev_estimate = *est;
+        self.mean_deviation = est.state_deviation;
+    }
+
+    /// Update the sensitivity matrix (or "H tilde"). This function **must** be called prior to each
+    /// call to `measurement_update`.
+    fn update_h_tilde(&mut self, h_tilde: OMatrix<f64, M, <T as State>::Size>) {
+        self.h_tilde = h_tilde;
+        self.h_tilde_updated = true;
+    }
+
+    /// Computes a time update/prediction from the propagated sigma points.
+    ///
+    /// May return a FilterError if the sigma points were not propagated and the STM was not updated.
+    fn time_update(&mut self, nominal_state: T) -> Result<Self::Estimate, ODError> {
+        let (mean, covar_bar, _, stm, _) = self.predict(nominal_state)?;
+        self.sigma_observations.clear();
+
+        let estimate = KfEstimate {
+            nominal_state,
+            state_deviation: mean,
+            covar: covar_bar,
+            covar_bar,
+            stm,
+            predicted: true,
+        };
+        // The process does not update its reference state on a time update
+        self.mean_deviation = mean;
+        self.store(estimate);
+        Ok(estimate)
+    }
+
+    /// Computes the measurement update from the observations computed at each propagated sigma point.
+    ///
+    /// If these observations were not provided, they are linearized with the sensitivity matrix about the computed observation.
+    fn measurement_update(
+        &mut self,
+        nominal_state: T,
+        real_obs: &OVector<f64, M>,
+        computed_obs: &OVector<f64, M>,
+        measurement_covar: OMatrix<f64, M, M>,
+        resid_rejection: Option<ResidRejectCrit>,
+    ) -> Result<(Self::Estimate, Residual<M>), ODError> {
+        if !self.h_tilde_updated {
+            return Err(ODError::SensitivityNotUpdated);
+        }
+
+        let epoch = nominal_state.epoch();
+        let (mean, covar_bar, snc_covar, stm, deviations) = self.predict(nominal_state)?;
+
+        let mut observations = std::mem::take(&mut self.sigma_observations);
+        if observations.len() != deviations.len() {
+            observations = deviations
+                .iter()
+                .map(|sigma| computed_obs + &self.h_tilde * sigma)
+                .collect();
+        }
+
+        let (_, w_m, w_c) = self.weights();
+
+        let mut obs_mean = OVector::<f64, M>::zeros();
+        for (i, obs) in observations.iter().enumerate() {
+            obs_mean += w_m[i.min(1)] * obs;
+        }
+
+        // The process noise is not sampled by the sigma points, so it is linearly mapped into the measurement space.
+        let h_tilde_t = &self.h_tilde.transpose();
+        let mut innovation_covar = &self.h_tilde * snc_covar * h_tilde_t + &measurement_covar;
+        let mut cross_covar = snc_covar * h_tilde_t;
+        for (i, (sigma, obs)) in deviations.iter().zip(observations.iter()).enumerate() {
+            let delta_obs = obs - &obs_mean;
+            innovation_covar += w_c[i.min(1)] * &delta_obs * delta_obs.transpose();
+            cross_covar += w_c[i.min(1)] * (sigma - mean) * delta_obs.transpose();
+        }
+
+        // Compute observation deviation (usually marked as y_i)
+        let prefit = real_obs - &obs_mean;
+
+        // Compute the prefit ratio for the automatic rejection
+        let mut innovation_covar_inv = innovation_covar.clone();
+        if !innovation_covar_inv.try_inverse_mut() {
+            return Err(ODError::SingularKalmanGain);
+        }
+        let ratio_mat = prefit.transpose() * &innovation_covar_inv * &prefit;
+        let ratio = ratio_mat[0].sqrt();
+
+        if let Some(resid_reject) = resid_rejection {
+            if ratio > resid_reject.num_sigmas {
+                // Reject this whole measurement and only keep the prediction
+                self.h_tilde_updated = false;
+                let estimate = KfEstimate {
+                    nominal_state,
+                    state_deviation: mean,
+                    covar: covar_bar,
+                    covar_bar,
+                    stm,
+                    predicted: true,
+                };
+                // Apply the prediction to the reference state of the process like an update
+                self.mean_deviation = OVector::<f64, <T as State>::Size>::zeros();
+                self.store(estimate);
+                return Ok((
+                    estimate,
+                    Residual::rejected(epoch, prefit, ratio, innovation_covar.diagonal()),
+                ));
+            }
+        }
+
+        let gain = &cross_covar * &innovation_covar_inv;
+
+        let state_hat = mean + &gain * &prefit;
+        let postfit = &prefit - &self.h_tilde * (state_hat - mean);
+
+        let covar = covar_bar - &gain * &innovation_covar * gain.transpose();
+        // Ensure the covariance remains symmetric
+        let covar = 0.5 * (covar + covar.transpose());
+
+        let estimate = KfEstimate {
+            nominal_state,
+            state_deviation: state_hat,
+            covar,
+            covar_bar,
+            stm,
+            predicted: false,
+        };
+
+        self.h_tilde_updated = false;
+        // The process applies this deviation to its reference state
+        self.mean_deviation = OVector::<f64, <T as State>::Size>::zeros();
+        self.store(estimate);
+
+        Ok((
+            estimate,
+            Residual::accepted(epoch, prefit, postfit, ratio, innovation_covar.diagonal()),
+        ))
+    }
+
+    /// The UKF is not linearized about its reference state, so this is always false.
+    fn is_extended(&self) -> bool {
+        false
+    }
+
+    /// The UKF cannot be switched to an extended filter, so this does nothing.
+    fn set_extended(&mut self, _status: bool) {}
+
+    /// Overwrites all of the process noises to the one provided
+    fn set_process_noise(&mut self, snc: SNC<A>) {
+        self.process_noise = vec![snc];
+    }
+
+    fn is_unscented(&self) -> bool {
+        true
+    }
+
+    fn sigma_points(&self) -> Vec<OVector<f64, <T as State>::Size>> {
+        self.generate_sigma_points()
+    }
+
+    fn set_sigma_points(
+        &mut self,
+        deviations: Vec<OVector<f64, <T as State>::Size>>,
+        observations: Vec<OVector<f64, M>>,
+    ) {
+        self.sigma_deviations = deviations;
+        self.sigma_observations = observations;
+    }
+}
@@ -17,15 +17,18 @@
 */
 
 use anise::astro::{AzElRange, PhysicsResult};
-use anise::errors::AlmanacResult;
+use anise::errors::{AlmanacError, AlmanacResult};
 use anise::prelude::{Almanac, Frame, Orbit};
 
-use super::msr::RangeDoppler;
+use super::msr::{AzElMeasurement, MixedMeasurement, RangeDoppler};
 use super::noise::StochasticNoise;
-use super::{ODAlmanacSnafu, ODError, ODPlanetaryDataSnafu, ODTrajSnafu, TrackingDeviceSim};
+use super::{
+    ODAlmanacSnafu, ODError, ODPhysicsSnafu, ODPlanetaryDataSnafu, ODTrajSnafu, TrackingDeviceSim,
+};
 use crate::cosmic::eclipse::{line_of_sight, EclipseState};
+use crate::cosmic::{MEAN_MOON_ANGULAR_VELOCITY_DEG_S, SPEED_OF_LIGHT_KM_S};
 use crate::errors::EventError;
-use crate::io::ConfigRepr;
+use crate::io::{ConfigError, ConfigRepr};
 use crate::md::prelude::{Interpolatable, Traj};
 use crate::md::EventEvaluator;
 use crate::time::Epoch;
@@ -36,11 +39,64 @@ use rand_pcg::Pcg64Mcg;
 use serde_derive::{Deserialize, Serialize};
 use snafu::ResultExt;
 use std::fmt;
+use std::path::Path;
 use std::sync::Arc;
 
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Epoch at which the measurements of a tracking device are time tagged, which anchors the light time solution.
+///
+/// This only matters if the light time correction of the device is enabled.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeTag {
+    /// Tagged when the signal leaves the station: the spacecraft is observed one light time after the measurement epoch
+    Transmit,
+    /// Tagged when the signal is received by the station: the spacecraft is observed one light time before the measurement epoch
+    #[default]
+    Receive,
+    /// Tagged when the signal is reflected or transponded by the spacecraft: the spacecraft is observed at the measurement epoch,
+    /// and the station one light time before (uplink) and after (downlink) it
+    Bounce,
+}
+
+/// Geometric look angles of an object seen from a ground station, in the South-East-Zenith (SEZ) topocentric frame of the station.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LookAngles {
+    /// Epoch of the object
+    pub epoch: Epoch,
+    /// Azimuth, measured clockwise from the North, in degrees in [0, 360)
+    pub azimuth_deg: f64,
+    /// Elevation above the local horizon, in degrees in [-90, 90]: negative if the object is below the horizon
+    pub elevation_deg: f64,
+    /// Range from the station to the object, in km
+    pub range_km: f64,
+    /// Range-rate of the object, positive when receding, in km/s
+    pub range_rate_km_s: f64,
+}
+
+impl From<AzElRange> for LookAngles {
+    fn from(aer: AzElRange) -> Self {
+        Self {
+            epoch: aer.epoch,
+            azimuth_deg: aer.azimuth_deg,
+            elevation_deg: aer.elevation_deg,
+            range_km: aer.range_km,
+            range_rate_km_s: aer.range_rate_km_s,
+        }
+    }
+}
+
+impl fmt::Display for LookAngles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: az. {:.6} deg    el. {:.6} deg    range {:.6} km    range-rate {:.6} km/s",
+            self.epoch, self.azimuth_deg, self.elevation_deg, self.range_km, self.range_rate_km_s
+        )
+    }
+}
+
 /// GroundStation defines a two-way ranging and doppler station.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "python", pyclass)]
@@ -59,13 +115,17 @@ pub struct GroundStation {
     /// Duration needed to generate a measurement (if unset, it is assumed to be instantaneous)
     #[serde(skip)]
     pub integration_time: Option<Duration>,
-    /// Whether to correct for light travel time
+    /// Whether to correct for light travel time: if set, the measurements are computed from the position of the spacecraft
+    /// when the signal left it, and the position of the station when the signal was received (i.e. at the measurement epoch).
     pub light_time_correction: bool,
+    /// Time tagging convention of the measurements, which determines the epoch the light time solution is anchored to
+    #[serde(default)]
+    pub time_tag: TimeTag,
     /// Noise on the timestamp of the measurement
     pub timestamp_noise_s: Option<StochasticNoise>,
-    /// Noise on the range data of the measurement
+    /// Noise on the range data of the measurement, if unset this station does not measure range
     pub range_noise_km: Option<StochasticNoise>,
-    /// Noise on the Doppler data of the measurement
+    /// Noise on the Doppler data of the measurement, if unset this station does not measure Doppler
     pub doppler_noise_km_s: Option<StochasticNoise>,
 }
 
@@ -88,6 +148,7 @@ impl GroundStation {
             frame,
             integration_time: None,
             light_time_correction: false,
+            time_tag: TimeTag::Receive,
             timestamp_noise_s: None,
             range_noise_km: None,
             doppler_noise_km_s: None,
@@ -109,6 +170,7 @@ impl GroundStation {
             frame: iau_earth,
             integration_time: None,
             light_time_correction: false,
+            time_tag: TimeTag::Receive,
             timestamp_noise_s: None,
             range_noise_km: Some(range_noise_km),
             doppler_noise_km_s: Some(doppler_noise_km_s),
@@ -130,6 +192,7 @@ impl GroundStation {
             frame: iau_earth,
             integration_time: None,
             light_time_correction: false,
+            time_tag: TimeTag::Receive,
             timestamp_noise_s: None,
             range_noise_km: Some(range_noise_km),
             doppler_noise_km_s: Some(doppler_noise_km_s),
@@ -151,32 +214,233 @@ impl GroundStation {
             frame: iau_earth,
             integration_time: None,
             light_time_correction: false,
+            time_tag: TimeTag::Receive,
             timestamp_noise_s: None,
             range_noise_km: Some(range_noise_km),
             doppler_noise_km_s: Some(doppler_noise_km_s),
         }
     }
 
+    /// Loads a network of ground stations from the provided YAML definition (cf. [GroundStationNetwork]).
+    ///
+    /// Each station inherits the frame, elevation mask, light time correction, and noise models of the network unless
+    /// it overrides them in its own entry.
+    pub fn load_network<P>(path: P) -> Result<Vec<Self>, ConfigError>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(GroundStationNetwork::load(path)?.into_stations())
+    }
+
     /// Computes the azimuth and elevation of the provided object seen from this ground station, both in degrees.
     /// This is a shortcut to almanac.azimuth_elevation_range_sez.
     pub fn azimuth_elevation_of(&self, rx: Orbit, almanac: &Almanac) -> AlmanacResult<AzElRange> {
-        almanac.azimuth_elevation_range_sez(rx, self.to_orbit(rx.epoch, almanac).unwrap())
+        let station = self
+            .to_orbit(rx.epoch, almanac)
+            .map_err(|e| AlmanacError::GenericError {
+                err: format!("{e} when computing the location of {}", self.name),
+            })?;
+        almanac.azimuth_elevation_range_sez(rx, station)
+    }
+
+    /// Computes the angles-only measurement (azimuth and elevation, and their rates) of the provided object seen from this ground station.
+    /// The measurement is not visible if the object is below the elevation mask of this station.
+    ///
+    /// If light time correction is enabled, the object is observed at its apparent position, i.e. where it was when the light left it
+    /// (or where it will be when the light reaches it if the measurements are tagged at transmission, cf. [TimeTag]).
+    /// As only the state at the measurement epoch is known, the object is assumed to move in a straight line over the light time.
+    pub fn measure_angles(
+        &self,
+        rx: &Orbit,
+        almanac: &Almanac,
+    ) -> Result<AzElMeasurement, ODError> {
+        let station = self.to_orbit(rx.epoch, almanac).context(ODPhysicsSnafu {
+            action: "computing station location for angles",
+        })?;
+
+        let direction = match self.time_tag {
+            TimeTag::Receive => -1.0,
+            TimeTag::Transmit => 1.0,
+            TimeTag::Bounce => 0.0,
+        };
+
+        let mut apparent = *rx;
+        if self.light_time_correction {
+            let station_rx_frame =
+                almanac
+                    .transform_to(station, rx.frame, None)
+                    .context(ODAlmanacSnafu {
+                        action: "computing station location for light time correction",
+                    })?;
+            for _ in 0..3 {
+                let light_time_s =
+                    (apparent.radius_km - station_rx_frame.radius_km).norm() / SPEED_OF_LIGHT_KM_S;
+                apparent.radius_km = rx.radius_km + direction * rx.velocity_km_s * light_time_s;
+            }
+        }
+
+        // Rotate the object into the topocentric frame of the station, as for the elevation event.
+        let rx_fixed = almanac
+            .transform_to(apparent, station.frame, None)
+            .context(ODAlmanacSnafu {
+                action: "rotating the object into the frame of the station",
+            })?;
+        let from = station.frame.orientation_id * 1_000 + 1;
+        let dcm_fixed2topo = station
+            .dcm_from_topocentric_to_body_fixed(from)
+            .context(ODPhysicsSnafu {
+                action: "computing the topocentric frame of the station",
+            })?
+            .transpose();
+
+        let rx_sez = (dcm_fixed2topo * rx_fixed).context(ODPhysicsSnafu {
+            action: "rotating the object into the topocentric frame",
+        })?;
+        let tx_sez = (dcm_fixed2topo * station).context(ODPhysicsSnafu {
+            action: "rotating the station into the topocentric frame",
+        })?;
+
+        Ok(AzElMeasurement::from_sez(
+            rx.epoch,
+            rx_sez.radius_km - tx_sez.radius_km,
+            rx_sez.velocity_km_s - tx_sez.velocity_km_s,
+            self.elevation_mask_deg,
+        ))
+    }
+
+    /// Computes the geometric look angles (azimuth, elevation, range, and range-rate) of the provided object seen from this ground station,
+    /// in the SEZ frame of the station built from its geodetic coordinates. Contrary to the measurements, no noise, light time correction,
+    /// or elevation mask is applied.
+    ///
+    /// The object is rotated into the body-fixed frame of the station at its epoch, so the rotation of the body is accounted for, and its
+    /// velocity in that frame is its velocity relative to the station.
+    pub fn look_angles(&self, rx: &Orbit, almanac: &Almanac) -> AlmanacResult<LookAngles> {
+        Ok(self.azimuth_elevation_of(*rx, almanac)?.into())
+    }
+
+    /// Computes the two-way Doppler shift, in Hz, of the provided carrier frequency (in Hz) transmitted by this ground station,
+    /// coherently transponded by the spacecraft, and received back by this ground station.
+    ///
+    /// This uses the relativistic two-way Doppler ratio (1 - ρ̇/c) / (1 + ρ̇/c), where ρ̇ is the range-rate, so the shift is -2 ρ̇ f / c
+    /// to first order: a receding spacecraft has a negative Doppler shift. For Earth orbits, the higher order terms are at the part
+    /// per billion level of the carrier frequency.
+    pub fn doppler_hz(&self, rx: Orbit, carrier_hz: f64, almanac: &Almanac) -> AlmanacResult<f64> {
+        let beta = self.azimuth_elevation_of(rx, almanac)?.range_rate_km_s / SPEED_OF_LIGHT_KM_S;
+
+        Ok(-2.0 * beta / (1.0 + beta) * carrier_hz)
     }
 
     /// Return this ground station as an orbit in its current frame
     pub fn to_orbit(&self, epoch: Epoch, almanac: &Almanac) -> PhysicsResult<Orbit> {
-        use anise::constants::usual_planetary_constants::MEAN_EARTH_ANGULAR_VELOCITY_DEG_S;
         Orbit::try_latlongalt(
             self.latitude_deg,
             self.longitude_deg,
             self.height_km,
-            MEAN_EARTH_ANGULAR_VELOCITY_DEG_S,
+            self.angular_velocity_deg_s(),
             epoch,
             almanac.frame_from_uid(self.frame).unwrap(),
         )
     }
 
+    /// Returns the mean angular velocity of the body on which this station is located, in degrees per second.
+    /// Stations on the Moon (e.g. in the IAU Moon body-fixed frame) rotate with the Moon, and all other stations are assumed to be on Earth.
+    pub fn angular_velocity_deg_s(&self) -> f64 {
+        use anise::constants::celestial_objects::MOON;
+        use anise::constants::usual_planetary_constants::MEAN_EARTH_ANGULAR_VELOCITY_DEG_S;
+        if self.frame.ephemeris_id == MOON {
+            MEAN_MOON_ANGULAR_VELOCITY_DEG_S
+        } else {
+            MEAN_EARTH_ANGULAR_VELOCITY_DEG_S
+        }
+    }
+
+    /// Returns the state of the spacecraft to use for a measurement of this station tagged at the provided epoch.
+    ///
+    /// If light time correction is enabled, this is the state of the spacecraft when it reflected the signal (one light time
+    /// before the epoch if tagged at reception, one light time after if tagged at transmission), re-tagged at the measurement
+    /// epoch so that it is compared with the station at that epoch. The light time is solved by fixed point iteration, which
+    /// converges to well below a nanosecond in three iterations for Earth orbits.
+    ///
+    /// When the trajectory does not cover the light time, as is the case of measurements tagged at transmission while the
+    /// orbit determination process builds the trajectory up to the measurement epoch, the state is propagated over the light
+    /// time with Keplerian dynamics from the state at the measurement epoch. The perturbations are negligible over a light time.
+    ///
+    /// Measurements tagged at the bounce use the state at the measurement epoch: the motion of the station over the light time
+    /// is accounted for in [GroundStation::measured_aer].
+    fn receiver_state(
+        &self,
+        epoch: Epoch,
+        traj: &Traj<Spacecraft>,
+        almanac: &Arc<Almanac>,
+    ) -> Result<Spacecraft, ODError> {
+        let rx = traj.at(epoch).context(ODTrajSnafu)?;
+        if !self.light_time_correction || self.time_tag == TimeTag::Bounce {
+            return Ok(rx);
+        }
+        let direction = if self.time_tag == TimeTag::Transmit {
+            1.0
+        } else {
+            -1.0
+        };
+
+        let station =
+            self.location(epoch, rx.frame(), almanac.clone())
+                .context(ODAlmanacSnafu {
+                    action: "computing station location for light time correction",
+                })?;
+
+        let mut apparent = rx;
+        for _ in 0..3 {
+            let light_time_s =
+                (apparent.orbit.radius_km - station.radius_km).norm() / SPEED_OF_LIGHT_KM_S;
+            let apparent_epoch = epoch + direction * light_time_s * Unit::Second;
+            apparent = if apparent_epoch >= traj.first().epoch()
+                && apparent_epoch <= traj.last().epoch()
+            {
+                traj.at(apparent_epoch).context(ODTrajSnafu)?
+            } else {
+                rx.with_orbit(rx.orbit.at_epoch(apparent_epoch).context(ODPhysicsSnafu {
+                    action: "propagating over the light time",
+                })?)
+            };
+        }
+        apparent.orbit.epoch = epoch;
+
+        Ok(apparent)
+    }
+
+    /// Computes the azimuth, elevation, range, and range-rate of the provided receiver state as measured by this station.
+    ///
+    /// If light time correction is enabled and the measurements are tagged at the bounce, the receiver is at its position at the
+    /// measurement epoch, but the station transmitted the signal one uplink light time before and receives it one downlink light
+    /// time after: the range and range-rate are the averages of those of both legs, each solved by fixed point iteration.
+    /// Otherwise, the station and the receiver are at the epoch of the receiver, cf. [GroundStation::receiver_state].
+    fn measured_aer(&self, rx: Orbit, almanac: &Almanac) -> AlmanacResult<AzElRange> {
+        let aer = self.azimuth_elevation_of(rx, almanac)?;
+        if !self.light_time_correction || self.time_tag != TimeTag::Bounce {
+            return Ok(aer);
+        }
+
+        let mut legs = [aer, aer];
+        for (leg, direction) in legs.iter_mut().zip([-1.0, 1.0]) {
+            for _ in 0..3 {
+                // Re-tagging the receiver moves the station along its own motion only.
+                let mut retagged = rx;
+                retagged.epoch =
+                    rx.epoch + direction * (leg.range_km / SPEED_OF_LIGHT_KM_S) * Unit::Second;
+                *leg = self.azimuth_elevation_of(retagged, almanac)?;
+            }
+        }
+
+        let mut two_way = aer;
+        two_way.range_km = 0.5 * (legs[0].range_km + legs[1].range_km);
+        two_way.range_rate_km_s = 0.5 * (legs[0].range_rate_km_s + legs[1].range_rate_km_s);
+
+        Ok(two_way)
+    }
+
     /// Returns the timestamp noise, range noise, and doppler noise for this ground station at the provided epoch.
+    /// The noise of a measurement which is not configured is zero: that measurement is not made by this station, cf. [MixedDevice].
     fn noises(
         &mut self,
         epoch: Epoch,
@@ -188,17 +452,26 @@ impl GroundStation {
 
         match rng {
             Some(rng) => {
-                // Add the range noise, or return an error if it's not configured.
-                range_noise_km = self
-                    .range_noise_km
-                    .ok_or(ODError::NoiseNotConfigured { kind: "Range" })?
-                    .sample(epoch, rng);
-
-                // Add the Doppler noise, or return an error if it's not configured.
-                doppler_noise_km_s = self
-                    .doppler_noise_km_s
-                    .ok_or(ODError::NoiseNotConfigured { kind: "Doppler" })?
-                    .sample(epoch, rng);
+                // At least one of the range or Doppler noises must be configured for this station to measure anything.
+                if self.range_noise_km.is_none() && self.doppler_noise_km_s.is_none() {
+                    return Err(ODError::NoiseNotConfigured {
+                        kind: "Range and Doppler",
+                    });
+                }
+
+                // Add the range noise if this station measures range.
+                if let Some(mut range_noise) = self.range_noise_km {
+                    range_noise_km = range_noise.sample(epoch, rng);
+                } else {
+                    range_noise_km = 0.0;
+                }
+
+                // Add the Doppler noise if this station measures Doppler.
+                if let Some(mut doppler_noise) = self.doppler_noise_km_s {
+                    doppler_noise_km_s = doppler_noise.sample(epoch, rng);
+                } else {
+                    doppler_noise_km_s = 0.0;
+                }
 
                 // Only add the epoch noise if it's configured, it's valid to not have any noise on the clock.
                 if let Some(mut timestamp_noise) = self.timestamp_noise_s {
@@ -220,6 +493,143 @@ impl GroundStation {
 
 impl ConfigRepr for GroundStation {}
 
+/// A network of ground stations sharing a common frame, elevation mask, light time correction, and noise models.
+///
+/// This is the representation of the YAML files loaded with [GroundStation::load_network], e.g.:
+/// ```yaml
+/// frame:
+///   ephemeris_id: 399
+///   orientation_id: 399
+///   mu_km3_s2: 398600.435436096
+///   shape: null
+/// elevation_mask_deg: 5.0
+/// range_noise_km:
+///   white_noise:
+///     mean: 0.0
+///     sigma: 5.0e-3 # 5 m
+/// stations:
+///   - name: Madrid
+///     latitude_deg: 40.427222
+///     longitude_deg: 4.250556
+///     height_km: 0.834939
+///   - name: Canberra
+///     latitude_deg: -35.398333
+///     longitude_deg: 148.981944
+///     height_km: 0.691750
+///     elevation_mask_deg: 10.0 # overrides the network mask
+///   - name: Goldstone
+///     latitude_deg: 35.247164
+///     longitude_deg: 243.205
+///     height_km: 1.0712
+///     doppler_noise_km_s:
+///       white_noise:
+///         mean: 0.0
+///         sigma: 1.0e-6 # 1 mm/s
+///     not_inherited: [Range] # Doppler only station
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroundStationNetwork {
+    pub frame: Frame,
+    /// Default elevation mask of the stations, in degrees
+    pub elevation_mask_deg: f64,
+    /// Whether the stations correct for light travel time
+    #[serde(default)]
+    pub light_time_correction: bool,
+    /// Time tagging convention of the measurements of the stations
+    #[serde(default)]
+    pub time_tag: TimeTag,
+    /// Default noise on the timestamp of the measurements
+    pub timestamp_noise_s: Option<StochasticNoise>,
+    /// Default noise on the range data of the measurements
+    pub range_noise_km: Option<StochasticNoise>,
+    /// Default noise on the Doppler data of the measurements
+    pub doppler_noise_km_s: Option<StochasticNoise>,
+    pub stations: Vec<NetworkStation>,
+}
+
+/// Noise models of the measurements of a [GroundStationNetwork], used to opt out of the inheritance of a noise from the network.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkNoise {
+    Timestamp,
+    Range,
+    Doppler,
+}
+
+/// A station of a [GroundStationNetwork]: any unset optional field is inherited from the network, unless listed in `not_inherited`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NetworkStation {
+    pub name: String,
+    /// in degrees
+    pub latitude_deg: f64,
+    /// in degrees
+    pub longitude_deg: f64,
+    /// in km
+    pub height_km: f64,
+    /// in degrees
+    pub elevation_mask_deg: Option<f64>,
+    pub timestamp_noise_s: Option<StochasticNoise>,
+    pub range_noise_km: Option<StochasticNoise>,
+    pub doppler_noise_km_s: Option<StochasticNoise>,
+    /// Noises which are not inherited from the network, e.g. `[Doppler]` for a range only station of a network measuring range and Doppler
+    #[serde(default)]
+    pub not_inherited: Vec<NetworkNoise>,
+}
+
+impl NetworkStation {
+    /// Returns the noise of this station, or that of the network if this noise is unset and inherited.
+    fn noise(
+        &self,
+        kind: NetworkNoise,
+        station_noise: Option<StochasticNoise>,
+        network_noise: Option<StochasticNoise>,
+    ) -> Option<StochasticNoise> {
+        if self.not_inherited.contains(&kind) {
+            station_noise
+        } else {
+            station_noise.or(network_noise)
+        }
+    }
+}
+
+impl GroundStationNetwork {
+    /// Builds each ground station of this network, in the order they are defined.
+    pub fn into_stations(self) -> Vec<GroundStation> {
+        self.stations
+            .into_iter()
+            .map(|station| GroundStation {
+                timestamp_noise_s: station.noise(
+                    NetworkNoise::Timestamp,
+                    station.timestamp_noise_s,
+                    self.timestamp_noise_s,
+                ),
+                range_noise_km: station.noise(
+                    NetworkNoise::Range,
+                    station.range_noise_km,
+                    self.range_noise_km,
+                ),
+                doppler_noise_km_s: station.noise(
+                    NetworkNoise::Doppler,
+                    station.doppler_noise_km_s,
+                    self.doppler_noise_km_s,
+                ),
+                name: station.name,
+                elevation_mask_deg: station
+                    .elevation_mask_deg
+                    .unwrap_or(self.elevation_mask_deg),
+                latitude_deg: station.latitude_deg,
+                longitude_deg: station.longitude_deg,
+                height_km: station.height_km,
+                frame: self.frame,
+                integration_time: None,
+                light_time_correction: self.light_time_correction,
+                time_tag: self.time_tag,
+            })
+            .collect()
+    }
+}
+
+impl ConfigRepr for GroundStationNetwork {}
+
 impl TrackingDeviceSim<Spacecraft, RangeDoppler> for GroundStation {
     /// Perform a measurement from the ground station to the receiver (rx).
     fn measure(
@@ -231,19 +641,19 @@ impl TrackingDeviceSim<Spacecraft, RangeDoppler> for GroundStation {
     ) -> Result<Option<RangeDoppler>, ODError> {
         match self.integration_time {
             Some(integration_time) => {
-                let rx_0 = traj.at(epoch - integration_time).context(ODTrajSnafu)?;
-                let rx_1 = traj.at(epoch).context(ODTrajSnafu)?;
+                let rx_0 = self.receiver_state(epoch - integration_time, traj, &almanac)?;
+                let rx_1 = self.receiver_state(epoch, traj, &almanac)?;
 
-                let aer_t0 =
-                    self.azimuth_elevation_of(rx_0.orbit, &almanac)
-                        .context(ODAlmanacSnafu {
-                            action: "computing AER",
-                        })?;
-                let aer_t1 =
-                    self.azimuth_elevation_of(rx_1.orbit, &almanac)
-                        .context(ODAlmanacSnafu {
-                            action: "computing AER",
-                        })?;
+                let aer_t0 = self
+                    .measured_aer(rx_0.orbit, &almanac)
+                    .context(ODAlmanacSnafu {
+                        action: "computing AER",
+                    })?;
+                let aer_t1 = self
+                    .measured_aer(rx_1.orbit, &almanac)
+                    .context(ODAlmanacSnafu {
+                        action: "computing AER",
+                    })?;
 
                 if aer_t0.elevation_deg < self.elevation_mask_deg
                     || aer_t1.elevation_deg < self.elevation_mask_deg
@@ -290,7 +700,10 @@ impl TrackingDeviceSim<Spacecraft, RangeDoppler> for GroundStation {
                     doppler_noise_km_s,
                 )))
             }
-            None => self.measure_instantaneous(traj.at(epoch).context(ODTrajSnafu)?, rng, almanac),
+            None => {
+                let rx = self.receiver_state(epoch, traj, &almanac)?;
+                self.measure_instantaneous(rx, rng, almanac)
+            }
         }
     }
 
@@ -309,7 +722,7 @@ impl TrackingDeviceSim<Spacecraft, RangeDoppler> for GroundStation {
         almanac: Arc<Almanac>,
     ) -> Result<Option<RangeDoppler>, ODError> {
         let aer = self
-            .azimuth_elevation_of(rx.orbit, &almanac)
+            .measured_aer(rx.orbit, &almanac)
             .context(ODAlmanacSnafu {
                 action: "computing AER",
             })?;
@@ -361,6 +774,7 @@ impl TrackingDeviceSim<Spacecraft, RangeDoppler> for GroundStation {
     /// The measurement noise is computed assuming that all measurements are independent variables, i.e. the measurement matrix is
     /// a diagonal matrix. The first item in the diagonal is the range noise (in km), set to the square of the steady state sigma. The
     /// second item is the Doppler noise (in km/s), set to the square of the steady state sigma of that Gauss Markov process.
+    /// Stations measuring only the range or only the Doppler are processed as a [MixedDevice].
     fn measurement_covar(
         &mut self,
         epoch: Epoch,
@@ -408,6 +822,267 @@ impl fmt::Display for GroundStation {
     }
 }
 
+/// A ground station measuring the azimuth and elevation of the spacecraft (e.g. a telescope), as opposed to the range and Doppler
+/// measured by a [GroundStation].
+///
+/// The location, elevation mask, light time correction, and time tagging convention are those of the underlying ground station,
+/// whose range and Doppler noises are not used. The angles are computed with [GroundStation::measure_angles].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnglesStation {
+    pub station: GroundStation,
+    /// Noise on the azimuth of the measurements, in degrees
+    pub azimuth_noise_deg: StochasticNoise,
+    /// Noise on the elevation of the measurements, in degrees
+    pub elevation_noise_deg: StochasticNoise,
+}
+
+impl AnglesStation {
+    /// Initializes an angles station from the provided ground station and the noises on the azimuth and elevation, in degrees.
+    pub fn new(
+        station: GroundStation,
+        azimuth_noise_deg: StochasticNoise,
+        elevation_noise_deg: StochasticNoise,
+    ) -> Self {
+        Self {
+            station,
+            azimuth_noise_deg,
+            elevation_noise_deg,
+        }
+    }
+}
+
+impl ConfigRepr for AnglesStation {}
+
+impl TrackingDeviceSim<Spacecraft, AzElMeasurement> for AnglesStation {
+    /// Perform an angles measurement from the station to the receiver (rx): the angles are instantaneous, so the integration
+    /// time of the station is not used.
+    fn measure(
+        &mut self,
+        epoch: Epoch,
+        traj: &Traj<Spacecraft>,
+        rng: Option<&mut Pcg64Mcg>,
+        almanac: Arc<Almanac>,
+    ) -> Result<Option<AzElMeasurement>, ODError> {
+        let rx = traj.at(epoch).context(ODTrajSnafu)?;
+        self.measure_instantaneous(rx, rng, almanac)
+    }
+
+    fn name(&self) -> String {
+        self.station.name.clone()
+    }
+
+    fn location(&self, epoch: Epoch, frame: Frame, almanac: Arc<Almanac>) -> AlmanacResult<Orbit> {
+        self.station.location(epoch, frame, almanac)
+    }
+
+    fn measure_instantaneous(
+        &mut self,
+        rx: Spacecraft,
+        rng: Option<&mut Pcg64Mcg>,
+        almanac: Arc<Almanac>,
+    ) -> Result<Option<AzElMeasurement>, ODError> {
+        let mut msr = self.station.measure_angles(&rx.orbit, &almanac)?;
+
+        if !msr.visible() {
+            debug!(
+                "{} {} (el. mask {:.3} deg), object at {:.3} deg -- no measurement",
+                self.station.name,
+                rx.orbit.epoch,
+                self.station.elevation_mask_deg,
+                msr.elevation_deg()
+            );
+            return Ok(None);
+        }
+
+        // Only update the noises if the measurement is valid.
+        if let Some(rng) = rng {
+            msr.obs[0] =
+                (msr.obs[0] + self.azimuth_noise_deg.sample(rx.orbit.epoch, rng)).rem_euclid(360.0);
+            msr.obs[1] += self.elevation_noise_deg.sample(rx.orbit.epoch, rng);
+        }
+
+        Ok(Some(msr))
+    }
+
+    /// Returns the measurement noise of this station: a diagonal matrix of the variances of the azimuth and elevation noises, in degrees squared.
+    fn measurement_covar(
+        &mut self,
+        epoch: Epoch,
+    ) -> Result<
+        OMatrix<
+            f64,
+            <AzElMeasurement as super::Measurement>::MeasurementSize,
+            <AzElMeasurement as super::Measurement>::MeasurementSize,
+        >,
+        ODError,
+    > {
+        let mut msr_noises = OMatrix::<
+            f64,
+            <AzElMeasurement as super::Measurement>::MeasurementSize,
+            <AzElMeasurement as super::Measurement>::MeasurementSize,
+        >::zeros();
+        msr_noises[(0, 0)] = self.azimuth_noise_deg.covariance(epoch);
+        msr_noises[(1, 1)] = self.elevation_noise_deg.covariance(epoch);
+
+        Ok(msr_noises)
+    }
+}
+
+impl fmt::Display for AnglesStation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (angles)", self.station)
+    }
+}
+
+/// A tracking device of any of the kinds of stations, such that stations measuring different kinds of data are processed together
+/// in a single orbit determination process, each measurement being a [MixedMeasurement] of the kind of the station.
+///
+/// A radiometric station measures the range and the Doppler if both noises are configured, and only the one whose noise is
+/// configured otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MixedDevice {
+    /// Range and/or Doppler station
+    Radiometric(GroundStation),
+    /// Azimuth and elevation station
+    Angles(AnglesStation),
+}
+
+impl MixedDevice {
+    /// Converts a range and Doppler measurement of the provided station into the kind of measurement of that station.
+    fn radiometric(
+        station: &GroundStation,
+        msr: RangeDoppler,
+    ) -> Result<MixedMeasurement, ODError> {
+        match (
+            station.range_noise_km.is_some(),
+            station.doppler_noise_km_s.is_some(),
+        ) {
+            (true, true) => Ok(MixedMeasurement::RangeDoppler(msr)),
+            (true, false) => Ok(MixedMeasurement::range(msr.epoch, msr.obs[0])),
+            (false, true) => Ok(MixedMeasurement::doppler(msr.epoch, msr.obs[1])),
+            (false, false) => Err(ODError::NoiseNotConfigured {
+                kind: "Range and Doppler",
+            }),
+        }
+    }
+}
+
+impl ConfigRepr for MixedDevice {}
+
+impl From<GroundStation> for MixedDevice {
+    fn from(station: GroundStation) -> Self {
+        Self::Radiometric(station)
+    }
+}
+
+impl From<AnglesStation> for MixedDevice {
+    fn from(station: AnglesStation) -> Self {
+        Self::Angles(station)
+    }
+}
+
+impl TrackingDeviceSim<Spacecraft, MixedMeasurement> for MixedDevice {
+    fn measure(
+        &mut self,
+        epoch: Epoch,
+        traj: &Traj<Spacecraft>,
+        rng: Option<&mut Pcg64Mcg>,
+        almanac: Arc<Almanac>,
+    ) -> Result<Option<MixedMeasurement>, ODError> {
+        match self {
+            Self::Radiometric(station) => station
+                .measure(epoch, traj, rng, almanac)?
+                .map(|msr| Self::radiometric(station, msr))
+                .transpose(),
+            Self::Angles(station) => Ok(station
+                .measure(epoch, traj, rng, almanac)?
+                .map(MixedMeasurement::AzEl)),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Self::Radiometric(station) => station.name.clone(),
+            Self::Angles(station) => station.station.name.clone(),
+        }
+    }
+
+    fn location(&self, epoch: Epoch, frame: Frame, almanac: Arc<Almanac>) -> AlmanacResult<Orbit> {
+        match self {
+            Self::Radiometric(station) => station.location(epoch, frame, almanac),
+            Self::Angles(station) => station.location(epoch, frame, almanac),
+        }
+    }
+
+    fn measure_instantaneous(
+        &mut self,
+        rx: Spacecraft,
+        rng: Option<&mut Pcg64Mcg>,
+        almanac: Arc<Almanac>,
+    ) -> Result<Option<MixedMeasurement>, ODError> {
+        match self {
+            Self::Radiometric(station) => station
+                .measure_instantaneous(rx, rng, almanac)?
+                .map(|msr| Self::radiometric(station, msr))
+                .transpose(),
+            Self::Angles(station) => Ok(station
+                .measure_instantaneous(rx, rng, almanac)?
+                .map(MixedMeasurement::AzEl)),
+        }
+    }
+
+    /// Returns the measurement noise of the station, cf. [GroundStation] and [AnglesStation]. The variance of the components
+    /// which are not measured is one: their sensitivity and their residual are zero, so they do not contribute to the update.
+    fn measurement_covar(
+        &mut self,
+        epoch: Epoch,
+    ) -> Result<
+        OMatrix<
+            f64,
+            <MixedMeasurement as super::Measurement>::MeasurementSize,
+            <MixedMeasurement as super::Measurement>::MeasurementSize,
+        >,
+        ODError,
+    > {
+        match self {
+            Self::Radiometric(station) => {
+                let mut msr_noises = OMatrix::<
+                    f64,
+                    <MixedMeasurement as super::Measurement>::MeasurementSize,
+                    <MixedMeasurement as super::Measurement>::MeasurementSize,
+                >::identity();
+
+                match (station.range_noise_km, station.doppler_noise_km_s) {
+                    (Some(range_noise_km), Some(doppler_noise_km_s)) => {
+                        msr_noises[(0, 0)] = range_noise_km.covariance(epoch);
+                        msr_noises[(1, 1)] = doppler_noise_km_s.covariance(epoch);
+                    }
+                    (Some(noise), None) | (None, Some(noise)) => {
+                        msr_noises[(0, 0)] = noise.covariance(epoch);
+                    }
+                    (None, None) => {
+                        return Err(ODError::NoiseNotConfigured {
+                            kind: "Range and Doppler",
+                        })
+                    }
+                }
+
+                Ok(msr_noises)
+            }
+            Self::Angles(station) => station.measurement_covar(epoch),
+        }
+    }
+}
+
+impl fmt::Display for MixedDevice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Radiometric(station) => write!(f, "{station}"),
+            Self::Angles(station) => write!(f, "{station}"),
+        }
+    }
+}
+
 impl<S: Interpolatable> EventEvaluator<S> for &GroundStation
 where
     DefaultAllocator: Allocator<S::Size> + Allocator<S::Size, S::Size> + Allocator<S::VecLength>,
@@ -505,6 +1180,7 @@ mod gs_ut {
             longitude_deg: 48.8566,
             height_km: 0.4,
             light_time_correction: false,
+            time_tag: TimeTag::Receive,
             timestamp_noise_s: None,
             integration_time: None,
         };
@@ -551,6 +1227,7 @@ mod gs_ut {
                 longitude_deg: 48.8566,
                 height_km: 0.4,
                 light_time_correction: false,
+                time_tag: TimeTag::Receive,
                 timestamp_noise_s: None,
                 integration_time: None,
             },
@@ -570,6 +1247,21 @@ mod gs_ut {
                 longitude_deg: 148.981944,
                 height_km: 0.691750,
                 light_time_correction: false,
+                time_tag: TimeTag::Receive,
+                timestamp_noise_s: None,
+                integration_time: None,
+            },
+            GroundStation {
+                name: "Goldstone".to_string(),
+                frame: IAU_EARTH_FRAME.with_mu_km3_s2(398600.435436096),
+                elevation_mask_deg: 5.0,
+                range_noise_km,
+                doppler_noise_km_s: None,
+                latitude_deg: 35.247164,
+                longitude_deg: 243.205,
+                height_km: 1.0712,
+                light_time_correction: false,
+                time_tag: TimeTag::Receive,
                 timestamp_noise_s: None,
                 integration_time: None,
             },
@@ -581,4 +1273,70 @@ mod gs_ut {
         let reser = serde_yaml::to_string(&expected).unwrap();
         dbg!(reser);
     }
+
+    #[test]
+    fn test_load_network() {
+        use hifitime::TimeUnits;
+        use std::env;
+        use std::path::PathBuf;
+
+        let test_file: PathBuf = [
+            env::var("CARGO_MANIFEST_DIR").unwrap(),
+            "data".to_string(),
+            "tests".to_string(),
+            "config".to_string(),
+            "two_station_network.yaml".to_string(),
+        ]
+        .iter()
+        .collect();
+
+        let stations = GroundStation::load_network(test_file).unwrap();
+
+        let range_noise_km = Some(StochasticNoise {
+            bias: Some(GaussMarkov::new(1.days(), 5e-3).unwrap()),
+            ..Default::default()
+        });
+
+        let expected = vec![
+            GroundStation {
+                name: "Madrid".to_string(),
+                frame: IAU_EARTH_FRAME.with_mu_km3_s2(398600.435436096),
+                elevation_mask_deg: 5.0,
+                range_noise_km,
+                doppler_noise_km_s: Some(StochasticNoise {
+                    bias: Some(GaussMarkov::new(1.days(), 5e-5).unwrap()),
+                    ..Default::default()
+                }),
+                latitude_deg: 40.427222,
+                longitude_deg: 4.250556,
+                height_km: 0.834939,
+                light_time_correction: false,
+                time_tag: TimeTag::Receive,
+                timestamp_noise_s: None,
+                integration_time: None,
+            },
+            GroundStation {
+                name: "Canberra".to_string(),
+                frame: IAU_EARTH_FRAME.with_mu_km3_s2(398600.435436096),
+                elevation_mask_deg: 10.0,
+                range_noise_km,
+                doppler_noise_km_s: Some(StochasticNoise {
+                    white_noise: Some(WhiteNoise {
+                        mean: 0.0,
+                        sigma: 1e-6,
+                    }),
+                    ..Default::default()
+                }),
+                latitude_deg: -35.398333,
+                longitude_deg: 148.981944,
+                height_km: 0.691750,
+                light_time_correction: false,
+                time_tag: TimeTag::Receive,
+                timestamp_noise_s: None,
+                integration_time: None,
+            },
+        ];
+
+        assert_eq!(expected, stations);
+    }
 }
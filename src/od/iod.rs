@@ -0,0 +1,301 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use anise::prelude::{Frame, Orbit};
+use snafu::ResultExt;
+
+use super::{ODError, ODLambertSnafu, ODPhysicsSnafu};
+use crate::linalg::{DVector, Matrix2, Vector2, Vector3};
+use crate::time::Epoch;
+use crate::tools::lambert::{izzo, TransferDirection};
+use crate::utils::{FiniteDiff, FiniteDiffStep};
+
+/// Maximum angle, in degrees, between the first position and the plane of the two others for the three positions to be considered coplanar.
+pub const COPLANAR_TOL_DEG: f64 = 1.0;
+
+/// Below this angular separation between consecutive positions, in degrees, [three_positions] uses the Herrick-Gibbs method instead of the Gibbs method.
+pub const HERRICK_GIBBS_MAX_SEPARATION_DEG: f64 = 5.0;
+
+/// Computes the orbit at the second of three position vectors (in km) of the same object with the Gibbs method, in the provided frame,
+/// which must have its gravitational parameter set. Only the geometry is used, so the positions must be well separated (more than
+/// a few degrees apart), otherwise use [herrick_gibbs].
+///
+/// Returns an error if the positions are not coplanar within [COPLANAR_TOL_DEG].
+///
+/// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., algorithm 54.
+pub fn gibbs(
+    r1_km: Vector3<f64>,
+    r2_km: Vector3<f64>,
+    r3_km: Vector3<f64>,
+    epoch: Epoch,
+    frame: Frame,
+) -> Result<Orbit, ODError> {
+    check_coplanar(&r1_km, &r2_km, &r3_km)?;
+    let mu_km3_s2 = frame.mu_km3_s2().context(ODPhysicsSnafu {
+        action: "Gibbs IOD",
+    })?;
+
+    let (r1, r2, r3) = (r1_km.norm(), r2_km.norm(), r3_km.norm());
+    let z12 = r1_km.cross(&r2_km);
+    let z23 = r2_km.cross(&r3_km);
+    let z31 = r3_km.cross(&r1_km);
+
+    let n = r1 * z23 + r2 * z31 + r3 * z12;
+    let d = z12 + z23 + z31;
+    let s = (r2 - r3) * r1_km + (r3 - r1) * r2_km + (r1 - r2) * r3_km;
+    let b = d.cross(&r2_km);
+
+    let l_g = (mu_km3_s2 / (n.norm() * d.norm())).sqrt();
+    let v2_km_s = (l_g / r2) * b + l_g * s;
+
+    Ok(Orbit::cartesian(
+        r2_km.x, r2_km.y, r2_km.z, v2_km_s.x, v2_km_s.y, v2_km_s.z, epoch, frame,
+    ))
+}
+
+/// Computes the orbit at the second of three time tagged position vectors (in km) of the same object with the Herrick-Gibbs method,
+/// in the provided frame, which must have its gravitational parameter set. This Taylor series expansion of the motion is suited for
+/// closely spaced positions (less than a few degrees apart), where the Gibbs method loses accuracy.
+///
+/// Returns an error if the positions are not coplanar within [COPLANAR_TOL_DEG].
+///
+/// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., algorithm 55.
+pub fn herrick_gibbs(
+    r1_km: Vector3<f64>,
+    r2_km: Vector3<f64>,
+    r3_km: Vector3<f64>,
+    t1: Epoch,
+    t2: Epoch,
+    t3: Epoch,
+    frame: Frame,
+) -> Result<Orbit, ODError> {
+    check_coplanar(&r1_km, &r2_km, &r3_km)?;
+    let mu_km3_s2 = frame.mu_km3_s2().context(ODPhysicsSnafu {
+        action: "Herrick-Gibbs IOD",
+    })?;
+
+    let dt21_s = (t2 - t1).to_seconds();
+    let dt32_s = (t3 - t2).to_seconds();
+    let dt31_s = (t3 - t1).to_seconds();
+
+    // Contribution of the two body acceleration at each position
+    let accel_term = |r_km: &Vector3<f64>| mu_km3_s2 / (12.0 * r_km.norm().powi(3));
+
+    let v2_km_s = -dt32_s * (1.0 / (dt21_s * dt31_s) + accel_term(&r1_km)) * r1_km
+        + (dt32_s - dt21_s) * (1.0 / (dt21_s * dt32_s) + accel_term(&r2_km)) * r2_km
+        + dt21_s * (1.0 / (dt32_s * dt31_s) + accel_term(&r3_km)) * r3_km;
+
+    Ok(Orbit::cartesian(
+        r2_km.x, r2_km.y, r2_km.z, v2_km_s.x, v2_km_s.y, v2_km_s.z, t2, frame,
+    ))
+}
+
+/// Computes the orbit at the second of three time tagged position vectors (in km) of the same object, with the Herrick-Gibbs method
+/// if both consecutive positions are less than [HERRICK_GIBBS_MAX_SEPARATION_DEG] apart, and with the Gibbs method otherwise.
+pub fn three_positions(
+    r1_km: Vector3<f64>,
+    r2_km: Vector3<f64>,
+    r3_km: Vector3<f64>,
+    t1: Epoch,
+    t2: Epoch,
+    t3: Epoch,
+    frame: Frame,
+) -> Result<Orbit, ODError> {
+    let separation_deg = r1_km.angle(&r2_km).max(r2_km.angle(&r3_km)).to_degrees();
+
+    if separation_deg < HERRICK_GIBBS_MAX_SEPARATION_DEG {
+        herrick_gibbs(r1_km, r2_km, r3_km, t1, t2, t3, frame)
+    } else {
+        gibbs(r1_km, r2_km, r3_km, t2, frame)
+    }
+}
+
+/// Configuration of the [gooding] angles-only initial orbit determination.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GoodingConfig {
+    /// Initial guess of the ranges from the station at the first and third observations, in km.
+    pub range_guess_km: (f64, f64),
+    /// The iteration stops when the predicted position at the second observation is within this distance of its line of sight, in km.
+    pub tolerance_km: f64,
+    /// Maximum number of iterations before returning a divergence error.
+    pub max_iterations: usize,
+}
+
+impl GoodingConfig {
+    /// Initializes a configuration from the range guesses at the first and third observations, with a tolerance of one centimeter
+    /// and at most 50 iterations.
+    pub fn from_range_guess_km(range1_km: f64, range3_km: f64) -> Self {
+        Self {
+            range_guess_km: (range1_km, range3_km),
+            tolerance_km: 1e-5,
+            max_iterations: 50,
+        }
+    }
+}
+
+/// Computes the orbit at the second of three angles-only observations of the same object, using Gooding's method.
+///
+/// The observations are the line of sight vectors from the station to the object, and the station positions at each epoch, both
+/// expressed in the provided inertial frame, which must have its gravitational parameter set. The line of sight vectors need not be normalized.
+///
+/// Starting from the configured range guesses at the first and third observations, the Lambert arc between both positions is
+/// solved and propagated to the second epoch. The ranges are then corrected with Newton iterations (with a central difference
+/// Jacobian) until the predicted position at the second epoch lies on its line of sight, within the configured tolerance.
+/// The observations are assumed to span less than one revolution, and the object to move along the shortest arc between the
+/// first and third positions.
+///
+/// Returns [ODError::Diverged] if the tolerance is not met within the maximum number of iterations.
+///
+/// Source: Gooding, R. H., "A new procedure for the solution of the classical problem of minimal orbit determination from three lines of sight", Celestial Mechanics and Dynamical Astronomy, 1996.
+pub fn gooding(
+    los1: Vector3<f64>,
+    los2: Vector3<f64>,
+    los3: Vector3<f64>,
+    station_positions_km: [Vector3<f64>; 3],
+    epochs: [Epoch; 3],
+    frame: Frame,
+    config: GoodingConfig,
+) -> Result<Orbit, ODError> {
+    let mu_km3_s2 = frame.mu_km3_s2().context(ODPhysicsSnafu {
+        action: "Gooding IOD",
+    })?;
+
+    let (los1, los2, los3) = (los1.normalize(), los2.normalize(), los3.normalize());
+    let [site1_km, site2_km, site3_km] = station_positions_km;
+    let [t1, t2, t3] = epochs;
+
+    // Basis of the plane orthogonal to the second line of sight, in which the miss distance is measured.
+    let ortho = if los2.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let p_hat = los2.cross(&ortho).normalize();
+    let q_hat = los2.cross(&p_hat);
+
+    // Returns the orbit at the first epoch and the miss distance at the second epoch for the provided ranges.
+    let miss = |rho1_km: f64, rho3_km: f64| -> Result<(Orbit, Vector2<f64>), ODError> {
+        let r1_km = site1_km + rho1_km * los1;
+        let r3_km = site3_km + rho3_km * los3;
+
+        let direction = if r1_km.cross(&r3_km).z >= 0.0 {
+            TransferDirection::Prograde
+        } else {
+            TransferDirection::Retrograde
+        };
+
+        let sol =
+            izzo(r1_km, r3_km, t3 - t1, mu_km3_s2, direction, 0, true).context(ODLambertSnafu {
+                action: "Gooding IOD",
+            })?;
+
+        let orbit1 = Orbit::cartesian(
+            r1_km.x,
+            r1_km.y,
+            r1_km.z,
+            sol.v_init.x,
+            sol.v_init.y,
+            sol.v_init.z,
+            t1,
+            frame,
+        );
+
+        let delta_km = orbit1
+            .at_epoch(t2)
+            .context(ODPhysicsSnafu {
+                action: "Gooding IOD",
+            })?
+            .radius_km
+            - site2_km;
+
+        Ok((
+            orbit1,
+            Vector2::new(delta_km.dot(&p_hat), delta_km.dot(&q_hat)),
+        ))
+    };
+
+    let (mut rho1_km, mut rho3_km) = config.range_guess_km;
+
+    for _ in 0..config.max_iterations {
+        let (orbit1, f) = miss(rho1_km, rho3_km)?;
+
+        if f.norm() < config.tolerance_km {
+            return orbit1.at_epoch(t2).context(ODPhysicsSnafu {
+                action: "Gooding IOD",
+            });
+        }
+
+        let jac = FiniteDiff::central()
+            .with_step(FiniteDiffStep::Relative(1e-6))
+            .try_jacobian(
+                |rho_km| {
+                    miss(rho_km[0], rho_km[1])
+                        .map(|(_, f)| DVector::from_column_slice(f.as_slice()))
+                },
+                &DVector::from_vec(vec![rho1_km, rho3_km]),
+            )?;
+
+        let jac = Matrix2::from_column_slice(jac.as_slice());
+        let step_km = match jac.try_inverse() {
+            Some(jac_inv) => -jac_inv * f,
+            None => {
+                return Err(ODError::SingularInformationMatrix {
+                    action: "correcting ranges in Gooding IOD",
+                })
+            }
+        };
+
+        // Ranges must remain positive: halve them instead of stepping through the station.
+        rho1_km = if rho1_km + step_km[0] > 0.0 {
+            rho1_km + step_km[0]
+        } else {
+            0.5 * rho1_km
+        };
+        rho3_km = if rho3_km + step_km[1] > 0.0 {
+            rho3_km + step_km[1]
+        } else {
+            0.5 * rho3_km
+        };
+    }
+
+    Err(ODError::Diverged {
+        loops: config.max_iterations,
+    })
+}
+
+/// Returns an error if the first position is further than [COPLANAR_TOL_DEG] from the plane of the two others.
+fn check_coplanar(
+    r1_km: &Vector3<f64>,
+    r2_km: &Vector3<f64>,
+    r3_km: &Vector3<f64>,
+) -> Result<(), ODError> {
+    let z23 = r2_km.cross(r3_km);
+    let angle_deg = (z23.dot(r1_km) / (z23.norm() * r1_km.norm()))
+        .asin()
+        .to_degrees();
+
+    // Also catches the degenerate case of collinear positions, where the angle is not a number
+    if !(angle_deg.abs() <= COPLANAR_TOL_DEG) {
+        return Err(ODError::NonCoplanarPositions {
+            angle_deg,
+            tol_deg: COPLANAR_TOL_DEG,
+        });
+    }
+
+    Ok(())
+}
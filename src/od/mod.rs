@@ -18,16 +18,19 @@
 
 use crate::dynamics::DynamicsError;
 pub use crate::dynamics::{Dynamics, NyxError};
+use crate::errors::StateError;
 use crate::io::{ConfigError, InputOutputError};
 use crate::linalg::allocator::Allocator;
 use crate::linalg::{DefaultAllocator, DimName, OMatrix, OVector};
 use crate::md::trajectory::TrajError;
 use crate::propagators::PropagationError;
 use crate::time::Epoch;
+use crate::utils::between_pm_180;
 use crate::Orbit;
 pub use crate::{State, TimeTagged};
 use anise::almanac::planetary::PlanetaryDataError;
-use anise::errors::AlmanacError;
+use anise::errors::{AlmanacError, PhysicsError};
+use anise::prelude::Frame;
 use hifitime::Duration;
 use snafu::prelude::Snafu;
 use std::sync::Arc;
@@ -37,7 +40,9 @@ pub use filter::Filter;
 
 /// Provides a range and range rate measuring models.
 mod ground_station;
-pub use ground_station::GroundStation;
+pub use ground_station::{
+    AnglesStation, GroundStation, GroundStationNetwork, LookAngles, MixedDevice, NetworkStation,
+};
 
 /// Provides Estimate handling functionalities.
 pub mod estimate;
@@ -54,9 +59,26 @@ pub mod simulator;
 /// Provides the interfaces to the orbit determination process
 pub mod process;
 
+/// Provides batch least squares estimators, including multi-arc estimation of parameters shared across arcs, and the comparison of a batch with a sequential filter
+pub mod batch;
+pub use batch::compare_batch_sequential;
+
+/// Provides a Monte Carlo driver checking the statistical consistency of a filter over many measurement noise seeds
+pub mod consistency;
+pub use consistency::{
+    monte_carlo_consistency, overlap_consistency, ConsistencyReport, ODScenario, OverlapResult,
+};
+
 use arrow::datatypes::Field;
 pub use simulator::TrackingDeviceSim;
 
+/// Provides the probability of collision between two estimates at their time of closest approach
+pub mod collision;
+pub use collision::pc_2d;
+
+/// Provides initial orbit determination from three position vectors (Gibbs and Herrick-Gibbs) or three lines of sight (Gooding)
+pub mod iod;
+
 /// Provides all state noise compensation functionality
 pub mod snc;
 
@@ -73,8 +95,10 @@ pub type SpacecraftODProcess<'a> = self::process::ODProcess<
 
 #[allow(unused_imports)]
 pub mod prelude {
+    pub use super::batch::*;
     pub use super::estimate::*;
     pub use super::filter::kalman::*;
+    pub use super::filter::unscented::*;
     pub use super::ground_station::*;
     pub use super::msr::*;
     pub use super::noise::{GaussMarkov, StochasticNoise, WhiteNoise};
@@ -105,6 +129,70 @@ pub trait Measurement: Copy + TimeTagged {
     fn observation(&self) -> OVector<f64, Self::MeasurementSize>
     where
         DefaultAllocator: Allocator<Self::MeasurementSize>;
+
+    /// Returns the number of components which are actually measured, which is the measurement size by default.
+    /// Measurements of several kinds (e.g. [msr::MixedMeasurement]) may measure fewer components, the others being zero.
+    fn dimension(&self) -> usize {
+        Self::MeasurementSize::dim()
+    }
+
+    /// Returns whether the component of the observation at the provided index is an angle in degrees.
+    /// By default, no component is an angle.
+    fn is_angle_deg(&self, _component: usize) -> bool {
+        false
+    }
+
+    /// Returns the name and unit of each measured component (e.g. `Range (km)`), which identifies the kind of this measurement once serialized.
+    /// By default, these are the names of the fields of this kind of measurement.
+    fn kind(&self) -> Vec<String> {
+        Self::fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect()
+    }
+
+    /// Initializes a measurement of the provided kind (cf. `kind`) from its measured components,
+    /// or returns None if this kind is not supported or if the number of components does not match.
+    fn from_kind(epoch: Epoch, kind: &[String], obs: &[f64]) -> Option<Self>
+    where
+        DefaultAllocator: Allocator<Self::MeasurementSize>,
+    {
+        let fields = Self::fields();
+        let matches = kind.len() == fields.len()
+            && kind.iter().zip(&fields).all(|(k, field)| k == field.name());
+
+        if matches && obs.len() == Self::MeasurementSize::dim() {
+            Some(Self::from_observation(
+                epoch,
+                OVector::<f64, Self::MeasurementSize>::from_column_slice(obs),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the residual of this observation with respect to the computed observation (real minus computed).
+    ///
+    /// The residual of each angular component (cf. `is_angle_deg`) is wrapped into (-180, 180] degrees, such that an observation
+    /// of 1 degree and a computed observation of 359 degrees lead to a residual of +2 degrees.
+    fn residual(
+        &self,
+        computed: &OVector<f64, Self::MeasurementSize>,
+    ) -> OVector<f64, Self::MeasurementSize>
+    where
+        DefaultAllocator: Allocator<Self::MeasurementSize>,
+    {
+        let mut residual = self.observation() - computed;
+        for (i, value) in residual.iter_mut().enumerate() {
+            if self.is_angle_deg(i) {
+                *value = between_pm_180(*value);
+                if *value <= -180.0 {
+                    *value += 360.0;
+                }
+            }
+        }
+        residual
+    }
 }
 
 /// The Estimate trait defines the interface that is the opposite of a `SolveFor`.
@@ -193,6 +281,8 @@ pub enum ODError {
     SingularKalmanGain,
     #[snafu(display("Noise matrix is singular"))]
     SingularNoiseRk,
+    #[snafu(display("predicted covariance is singular"))]
+    SingularPredictedCovariance,
     #[snafu(display("{kind} noise not configured"))]
     NoiseNotConfigured { kind: &'static str },
     #[snafu(display("during an OD encountered {source}"))]
@@ -213,6 +303,110 @@ pub enum ODError {
         source: Box<PlanetaryDataError>,
         action: &'static str,
     },
+    #[snafu(display("OD failed due to physics: {action} {source}"))]
+    ODPhysics {
+        source: PhysicsError,
+        action: &'static str,
+    },
+    #[snafu(display("OD failed due to state: {action} {source}"))]
+    ODState {
+        source: StateError,
+        action: &'static str,
+    },
+    #[snafu(display("OD failed due to Lambert solver: {action} {source}"))]
+    ODLambert {
+        #[snafu(source(from(NyxError, Box::new)))]
+        source: Box<NyxError>,
+        action: &'static str,
+    },
+    #[snafu(display(
+        "positions are not coplanar: {angle_deg} deg out of plane (tolerance {tol_deg} deg)"
+    ))]
+    NonCoplanarPositions { angle_deg: f64, tol_deg: f64 },
     #[snafu(display("not enough residuals to {action}"))]
     ODNoResiduals { action: &'static str },
+    #[snafu(display("information matrix is singular when {action}"))]
+    SingularInformationMatrix { action: &'static str },
+    #[snafu(display("inconsistent dimensions when {action}: expected {expected}, got {got}"))]
+    InconsistentDimensions {
+        action: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    #[snafu(display("estimates must be at the same epoch, got {epoch_a} and {epoch_b}"))]
+    NonOverlappingEstimates { epoch_a: Epoch, epoch_b: Epoch },
+    #[snafu(display("estimates must be in the same frame, got {frame_a} and {frame_b}"))]
+    EstimateFrameMismatch { frame_a: Frame, frame_b: Frame },
+    #[snafu(display(
+        "relative speed of {rel_speed_km_s} km/s is too low to define the conjunction plane"
+    ))]
+    DegenerateEncounter { rel_speed_km_s: f64 },
+}
+
+#[cfg(test)]
+mod ut_measurement {
+    use super::*;
+    use crate::linalg::{Vector2, U2};
+
+    /// Azimuth and elevation measurement, both in degrees
+    #[derive(Copy, Clone, Debug)]
+    struct AzElMsr {
+        epoch: Epoch,
+        obs: Vector2<f64>,
+    }
+
+    impl TimeTagged for AzElMsr {
+        fn epoch(&self) -> Epoch {
+            self.epoch
+        }
+
+        fn set_epoch(&mut self, epoch: Epoch) {
+            self.epoch = epoch
+        }
+    }
+
+    impl Measurement for AzElMsr {
+        type MeasurementSize = U2;
+
+        fn fields() -> Vec<Field> {
+            Vec::new()
+        }
+
+        fn from_observation(epoch: Epoch, obs: Vector2<f64>) -> Self {
+            Self { epoch, obs }
+        }
+
+        fn observation(&self) -> Vector2<f64> {
+            self.obs
+        }
+
+        fn is_angle_deg(&self, component: usize) -> bool {
+            component < 2
+        }
+    }
+
+    #[test]
+    fn angular_residual_wraparound() {
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+        let real = AzElMsr::from_observation(epoch, Vector2::new(1.0, 45.0));
+        let computed = AzElMsr::from_observation(epoch, Vector2::new(359.0, 44.0));
+
+        let residual = real.residual(&computed.observation());
+        assert!((residual[0] - 2.0).abs() < 1e-12);
+        assert!((residual[1] - 1.0).abs() < 1e-12);
+
+        // And the other way around
+        let residual = computed.residual(&real.observation());
+        assert!((residual[0] + 2.0).abs() < 1e-12);
+
+        // Half a revolution is mapped to +180 degrees
+        let residual = AzElMsr::from_observation(epoch, Vector2::new(0.0, 0.0))
+            .residual(&Vector2::new(180.0, 0.0));
+        assert_eq!(residual[0], 180.0);
+
+        // Non angular measurements are not wrapped
+        let residual = msr::RangeMsr::from_observation(epoch, crate::linalg::Vector1::new(1.0))
+            .residual(&crate::linalg::Vector1::new(359.0));
+        assert_eq!(residual[0], -358.0);
+    }
 }
@@ -25,7 +25,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::io::watermark::pq_writer;
-use crate::io::{ConfigError, ExportCfg};
+use crate::io::{epoch_from_str, epoch_to_str, ConfigError, ExportCfg};
 use crate::linalg::allocator::Allocator;
 use crate::linalg::{DefaultAllocator, DimName};
 use crate::md::trajectory::Interpolatable;
@@ -38,6 +38,30 @@ use arrow::record_batch::RecordBatch;
 use hifitime::prelude::{Duration, Epoch, Unit};
 use hifitime::TimeScale;
 use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A single measurement of a tracking arc in a self-describing format, which can be serialized and reloaded with serde.
+///
+/// The observation stores the measured values as simulated or received, i.e. including the noise realization of the device.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementRecord {
+    /// Epoch of the measurement, serialized with its time scale
+    #[serde(serialize_with = "epoch_to_str", deserialize_with = "epoch_from_str")]
+    pub epoch: Epoch,
+    /// Name of the tracking device which produced this measurement
+    pub device: String,
+    /// Name and unit of each component of the observation, e.g. `Range (km)`
+    pub kind: Vec<String>,
+    /// Values of the observation
+    pub observation: Vec<f64>,
+}
+
+/// Serialized layout of a tracking arc
+#[derive(Serialize, Deserialize)]
+struct TrackingArcRecords {
+    device_cfg: String,
+    measurements: Vec<MeasurementRecord>,
+}
 
 /// Tracking arc contains the tracking data generated by the tracking devices defined in this structure.
 /// This structure is shared between both simulated and real tracking arcs.
@@ -187,6 +211,52 @@ where
         Ok(path_buf)
     }
 
+    /// Returns the measurements of this tracking arc as serializable records, each with the kind and the measured components of its measurement.
+    pub fn to_records(&self) -> Vec<MeasurementRecord> {
+        self.measurements
+            .iter()
+            .map(|(device, msr)| MeasurementRecord {
+                epoch: msr.epoch(),
+                device: device.clone(),
+                kind: msr.kind(),
+                observation: msr
+                    .observation()
+                    .iter()
+                    .take(msr.dimension())
+                    .copied()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Rebuilds a tracking arc from serialized records, ensuring that each record is of a kind of measurement supported by this arc.
+    pub fn from_records(
+        device_cfg: String,
+        records: &[MeasurementRecord],
+    ) -> Result<Self, ConfigError> {
+        let mut measurements = Vec::with_capacity(records.len());
+        for record in records {
+            let msr = Msr::from_kind(record.epoch, &record.kind, &record.observation).ok_or_else(
+                || ConfigError::InvalidConfig {
+                    msg: format!(
+                        "measurement from {} at {} is of kind {:?} with {} values, which is not supported by this arc",
+                        record.device,
+                        record.epoch,
+                        record.kind,
+                        record.observation.len()
+                    ),
+                },
+            )?;
+
+            measurements.push((record.device.clone(), msr));
+        }
+
+        Ok(Self {
+            device_cfg,
+            measurements,
+        })
+    }
+
     /// Returns the set of devices from which measurements were taken. This accounts for the availability of measurements, so if a device was not available, it will not appear in this set.
     pub fn device_names(&self) -> HashSet<&String> {
         let mut set = HashSet::new();
@@ -320,3 +390,38 @@ where
         Ok(())
     }
 }
+
+impl<Msr> Serialize for TrackingArc<Msr>
+where
+    Msr: Measurement,
+    DefaultAllocator:
+        Allocator<Msr::MeasurementSize> + Allocator<Msr::MeasurementSize, Msr::MeasurementSize>,
+{
+    /// Serializes the device configuration and all of the measurements as records.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        TrackingArcRecords {
+            device_cfg: self.device_cfg.clone(),
+            measurements: self.to_records(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, Msr> Deserialize<'de> for TrackingArc<Msr>
+where
+    Msr: Measurement,
+    DefaultAllocator:
+        Allocator<Msr::MeasurementSize> + Allocator<Msr::MeasurementSize, Msr::MeasurementSize>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let records = TrackingArcRecords::deserialize(deserializer)?;
+        Self::from_records(records.device_cfg, &records.measurements)
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,183 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::cosmic::Orbit;
+use crate::linalg::allocator::Allocator;
+use crate::linalg::{DefaultAllocator, OMatrix, OVector, Vector2, Vector3, U2};
+use crate::od::{EstimateFrom, Measurement};
+use crate::{Spacecraft, TimeTagged};
+use arrow::datatypes::{DataType, Field};
+use hifitime::Epoch;
+use std::collections::HashMap;
+
+/// An angles-only measurement of the topocentric azimuth and elevation of an object, in degrees, e.g. from optical tracking.
+///
+/// The azimuth is measured clockwise from the North, and the elevation from the local horizon, as in the SEZ frame of the station.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AzElMeasurement {
+    /// Epoch of the observation
+    pub epoch: Epoch,
+    /// Observation vector of the azimuth and elevation, in degrees
+    pub obs: Vector2<f64>,
+    /// Rates of the azimuth and elevation, in degrees per second
+    pub rates_deg_s: Vector2<f64>,
+    visible: bool,
+}
+
+impl AzElMeasurement {
+    /// Initializes a new angles measurement from the position and velocity of the object in the SEZ frame of the station.
+    /// The measurement is visible if the elevation is at or above the provided elevation mask, in degrees.
+    pub fn from_sez(
+        epoch: Epoch,
+        rho_sez_km: Vector3<f64>,
+        rho_dot_sez_km_s: Vector3<f64>,
+        elevation_mask_deg: f64,
+    ) -> Self {
+        let (s, e, z) = (rho_sez_km.x, rho_sez_km.y, rho_sez_km.z);
+        let (s_dot, e_dot, z_dot) = (rho_dot_sez_km_s.x, rho_dot_sez_km_s.y, rho_dot_sez_km_s.z);
+
+        let horizontal_km2 = s.powi(2) + e.powi(2);
+        let horizontal_km = horizontal_km2.sqrt();
+        let range_km = rho_sez_km.norm();
+        let range_rate_km_s = rho_sez_km.dot(&rho_dot_sez_km_s) / range_km;
+
+        // Source: Vallado, section 4.4.3 (the North axis is the opposite of the South axis)
+        let azimuth_deg = e.atan2(-s).to_degrees().rem_euclid(360.0);
+        let elevation_deg = z.atan2(horizontal_km).to_degrees();
+
+        let azimuth_rate_deg_s = ((e * s_dot - s * e_dot) / horizontal_km2).to_degrees();
+        let elevation_rate_deg_s =
+            ((z_dot - z * range_rate_km_s / range_km) / horizontal_km).to_degrees();
+
+        Self {
+            epoch,
+            obs: Vector2::new(azimuth_deg, elevation_deg),
+            rates_deg_s: Vector2::new(azimuth_rate_deg_s, elevation_rate_deg_s),
+            visible: elevation_deg >= elevation_mask_deg,
+        }
+    }
+
+    /// Azimuth in degrees, in [0, 360)
+    pub fn azimuth_deg(&self) -> f64 {
+        self.obs[0]
+    }
+
+    /// Elevation in degrees, in [-90, 90]
+    pub fn elevation_deg(&self) -> f64 {
+        self.obs[1]
+    }
+
+    /// Returns whether the object is above the elevation mask of the station which generated this measurement.
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl TimeTagged for AzElMeasurement {
+    fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    fn set_epoch(&mut self, epoch: Epoch) {
+        self.epoch = epoch
+    }
+}
+
+impl Measurement for AzElMeasurement {
+    type MeasurementSize = U2;
+
+    /// Returns this measurement as a vector of azimuth and elevation
+    ///
+    /// **Units:** degrees, degrees
+    fn observation(&self) -> Vector2<f64> {
+        self.obs
+    }
+
+    fn fields() -> Vec<Field> {
+        let mut meta = HashMap::new();
+        meta.insert("unit".to_string(), "deg".to_string());
+
+        vec![
+            Field::new("Azimuth (deg)", DataType::Float64, false).with_metadata(meta.clone()),
+            Field::new("Elevation (deg)", DataType::Float64, false).with_metadata(meta),
+        ]
+    }
+
+    fn from_observation(epoch: Epoch, obs: OVector<f64, Self::MeasurementSize>) -> Self {
+        Self {
+            epoch,
+            obs,
+            rates_deg_s: Vector2::zeros(),
+            visible: true,
+        }
+    }
+
+    fn is_angle_deg(&self, _component: usize) -> bool {
+        true
+    }
+}
+
+impl EstimateFrom<Spacecraft, AzElMeasurement> for Spacecraft {
+    fn extract(from: Spacecraft) -> Self {
+        from
+    }
+
+    /// Returns the partials of the azimuth and elevation (in degrees) with respect to the position of the receiver.
+    ///
+    /// The topocentric frame is rebuilt from the transmitter: its velocity is that of the rotation of its body, so it points East,
+    /// and the Zenith is approximated by its geocentric radius. This approximation is within the difference between the geodetic
+    /// and geocentric latitudes, i.e. less than 0.2 degrees for stations on Earth, which is negligible for the linearization.
+    fn sensitivity(
+        _msr: &AzElMeasurement,
+        receiver: Self,
+        transmitter: Orbit,
+    ) -> OMatrix<f64, <AzElMeasurement as Measurement>::MeasurementSize, Self::Size>
+    where
+        DefaultAllocator: Allocator<<AzElMeasurement as Measurement>::MeasurementSize, Self::Size>,
+    {
+        let zenith = transmitter.radius_km.normalize();
+        let east = if transmitter.velocity_km_s.norm() > f64::EPSILON {
+            transmitter.velocity_km_s.normalize()
+        } else {
+            // The station does not rotate, so use the Z axis of the frame as the pole.
+            Vector3::z().cross(&zenith).normalize()
+        };
+        let north = zenith.cross(&east);
+
+        let rho = receiver.orbit.radius_km - transmitter.radius_km;
+        let rho_north = rho.dot(&north);
+        let rho_east = rho.dot(&east);
+        let rho_zenith = rho.dot(&zenith);
+        let horizontal_km2 = rho_north.powi(2) + rho_east.powi(2);
+        let horizontal_km = horizontal_km2.sqrt();
+
+        let d_az = ((rho_north * east - rho_east * north) / horizontal_km2) * 1.0_f64.to_degrees();
+        let d_el = ((horizontal_km2 * zenith - rho_zenith * (rho_north * north + rho_east * east))
+            / (horizontal_km * rho.norm_squared()))
+            * 1.0_f64.to_degrees();
+
+        let mut h_tilde =
+            OMatrix::<f64, <AzElMeasurement as Measurement>::MeasurementSize, Self::Size>::zeros();
+        for i in 0..3 {
+            h_tilde[(0, i)] = d_az[i];
+            h_tilde[(1, i)] = d_el[i];
+        }
+
+        h_tilde
+    }
+}
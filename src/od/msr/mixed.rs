@@ -0,0 +1,226 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::cosmic::Orbit;
+use crate::linalg::allocator::Allocator;
+use crate::linalg::{DefaultAllocator, OMatrix, OVector, Vector1, Vector2, U2};
+use crate::od::msr::{AzElMeasurement, RangeDoppler, RangeMsr, RangeRate};
+use crate::od::{EstimateFrom, Measurement};
+use crate::{Spacecraft, TimeTagged};
+use arrow::datatypes::{DataType, Field};
+use hifitime::Epoch;
+use std::collections::HashMap;
+
+/// A measurement of one of several kinds, such that a single orbit determination process can ingest a time ordered stream of
+/// measurements of different kinds and dimensions, e.g. range and Doppler from a deep space station and angles from a telescope.
+///
+/// Each measurement carries its kind: the sensitivity and the residual are those of that kind, and the noise is that of the device
+/// which made the measurement (cf. [crate::od::MixedDevice]). The observation vector is of the size of the largest kind:
+/// the components beyond the dimension of the kind are zero, and so is their sensitivity, so they do not contribute to the update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MixedMeasurement {
+    /// Range only, in km
+    Range(RangeMsr),
+    /// Doppler only, in km/s
+    Doppler(RangeRate),
+    /// Simultaneous range and Doppler, in km and km/s
+    RangeDoppler(RangeDoppler),
+    /// Azimuth and elevation, in degrees
+    AzEl(AzElMeasurement),
+}
+
+impl MixedMeasurement {
+    /// Initializes a range only measurement, in km.
+    pub fn range(epoch: Epoch, range_km: f64) -> Self {
+        Self::Range(RangeMsr::from_observation(epoch, Vector1::new(range_km)))
+    }
+
+    /// Initializes a Doppler only measurement, in km/s.
+    pub fn doppler(epoch: Epoch, doppler_km_s: f64) -> Self {
+        Self::Doppler(RangeRate::from_observation(
+            epoch,
+            Vector1::new(doppler_km_s),
+        ))
+    }
+}
+
+impl TimeTagged for MixedMeasurement {
+    fn epoch(&self) -> Epoch {
+        match self {
+            Self::Range(msr) => msr.epoch(),
+            Self::Doppler(msr) => msr.epoch(),
+            Self::RangeDoppler(msr) => msr.epoch(),
+            Self::AzEl(msr) => msr.epoch(),
+        }
+    }
+
+    fn set_epoch(&mut self, epoch: Epoch) {
+        match self {
+            Self::Range(msr) => msr.set_epoch(epoch),
+            Self::Doppler(msr) => msr.set_epoch(epoch),
+            Self::RangeDoppler(msr) => msr.set_epoch(epoch),
+            Self::AzEl(msr) => msr.set_epoch(epoch),
+        }
+    }
+}
+
+impl Measurement for MixedMeasurement {
+    type MeasurementSize = U2;
+
+    /// Returns the components of this measurement, followed by zeros up to the measurement size
+    ///
+    /// **Units:** those of the kind of measurement
+    fn observation(&self) -> Vector2<f64> {
+        match self {
+            Self::Range(msr) => Vector2::new(msr.obs[0], 0.0),
+            Self::Doppler(msr) => Vector2::new(msr.obs[0], 0.0),
+            Self::RangeDoppler(msr) => msr.obs,
+            Self::AzEl(msr) => msr.obs,
+        }
+    }
+
+    fn fields() -> Vec<Field> {
+        let mut meta = HashMap::new();
+        meta.insert("unit".to_string(), "mixed".to_string());
+
+        vec![
+            Field::new("Component #1 (mixed)", DataType::Float64, false)
+                .with_metadata(meta.clone()),
+            Field::new("Component #2 (mixed)", DataType::Float64, false).with_metadata(meta),
+        ]
+    }
+
+    /// Initializes a range and Doppler measurement: the kind of a mixed measurement is not part of its observation vector,
+    /// use `from_kind` to initialize a measurement of a given kind.
+    fn from_observation(epoch: Epoch, obs: OVector<f64, Self::MeasurementSize>) -> Self {
+        Self::RangeDoppler(RangeDoppler::from_observation(epoch, obs))
+    }
+
+    fn dimension(&self) -> usize {
+        match self {
+            Self::Range(_) | Self::Doppler(_) => 1,
+            Self::RangeDoppler(_) | Self::AzEl(_) => 2,
+        }
+    }
+
+    fn is_angle_deg(&self, component: usize) -> bool {
+        match self {
+            Self::AzEl(msr) => msr.is_angle_deg(component),
+            _ => false,
+        }
+    }
+
+    /// Returns the kind of the underlying measurement, e.g. `Azimuth (deg)` and `Elevation (deg)` for angles.
+    fn kind(&self) -> Vec<String> {
+        match self {
+            Self::Range(msr) => msr.kind(),
+            Self::Doppler(msr) => msr.kind(),
+            Self::RangeDoppler(msr) => msr.kind(),
+            Self::AzEl(msr) => msr.kind(),
+        }
+    }
+
+    fn from_kind(epoch: Epoch, kind: &[String], obs: &[f64]) -> Option<Self> {
+        RangeMsr::from_kind(epoch, kind, obs)
+            .map(Self::Range)
+            .or_else(|| RangeRate::from_kind(epoch, kind, obs).map(Self::Doppler))
+            .or_else(|| RangeDoppler::from_kind(epoch, kind, obs).map(Self::RangeDoppler))
+            .or_else(|| AzElMeasurement::from_kind(epoch, kind, obs).map(Self::AzEl))
+    }
+}
+
+impl From<RangeDoppler> for MixedMeasurement {
+    fn from(msr: RangeDoppler) -> Self {
+        Self::RangeDoppler(msr)
+    }
+}
+
+impl From<AzElMeasurement> for MixedMeasurement {
+    fn from(msr: AzElMeasurement) -> Self {
+        Self::AzEl(msr)
+    }
+}
+
+impl EstimateFrom<Spacecraft, MixedMeasurement> for Spacecraft {
+    fn extract(from: Spacecraft) -> Self {
+        from
+    }
+
+    /// Returns the sensitivity of the kind of the measurement, whose rows beyond the dimension of that kind are zero.
+    fn sensitivity(
+        msr: &MixedMeasurement,
+        receiver: Self,
+        transmitter: Orbit,
+    ) -> OMatrix<f64, <MixedMeasurement as Measurement>::MeasurementSize, Self::Size>
+    where
+        DefaultAllocator: Allocator<<MixedMeasurement as Measurement>::MeasurementSize, Self::Size>,
+    {
+        match msr {
+            MixedMeasurement::RangeDoppler(msr) => <Self as EstimateFrom<
+                Spacecraft,
+                RangeDoppler,
+            >>::sensitivity(
+                msr, receiver, transmitter
+            ),
+            MixedMeasurement::AzEl(msr) => {
+                <Self as EstimateFrom<Spacecraft, AzElMeasurement>>::sensitivity(
+                    msr,
+                    receiver,
+                    transmitter,
+                )
+            }
+            MixedMeasurement::Range(msr) => {
+                // The sensitivity of the range only depends on the range.
+                let range_doppler = RangeDoppler {
+                    epoch: msr.epoch(),
+                    obs: Vector2::new(msr.obs[0], 0.0),
+                };
+                let mut h_tilde = <Self as EstimateFrom<Spacecraft, RangeDoppler>>::sensitivity(
+                    &range_doppler,
+                    receiver,
+                    transmitter,
+                );
+                h_tilde.row_mut(1).fill(0.0);
+                h_tilde
+            }
+            MixedMeasurement::Doppler(msr) => {
+                // The sensitivity of the Doppler depends on the range, which is not measured, so it is computed from the geometry.
+                let range_doppler = RangeDoppler {
+                    epoch: msr.epoch(),
+                    obs: Vector2::new(
+                        (receiver.orbit.radius_km - transmitter.radius_km).norm(),
+                        msr.obs[0],
+                    ),
+                };
+                let range_doppler_h_tilde =
+                    <Self as EstimateFrom<Spacecraft, RangeDoppler>>::sensitivity(
+                        &range_doppler,
+                        receiver,
+                        transmitter,
+                    );
+                let mut h_tilde = OMatrix::<
+                    f64,
+                    <MixedMeasurement as Measurement>::MeasurementSize,
+                    Self::Size,
+                >::zeros();
+                h_tilde.row_mut(0).copy_from(&range_doppler_h_tilde.row(1));
+                h_tilde
+            }
+        }
+    }
+}
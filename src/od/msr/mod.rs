@@ -17,11 +17,15 @@
 */
 
 mod arc;
+mod azel;
+mod mixed;
 mod range;
 mod range_doppler;
 mod rangerate;
 
-pub use arc::TrackingArc;
+pub use arc::{MeasurementRecord, TrackingArc};
+pub use azel::AzElMeasurement;
+pub use mixed::MixedMeasurement;
 pub use range::RangeMsr;
 pub use range_doppler::RangeDoppler;
 pub use rangerate::RangeRate;
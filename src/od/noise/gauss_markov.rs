@@ -16,9 +16,13 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+#[cfg(feature = "python")]
+use super::StochasticNoise;
 use crate::io::{ConfigError, ConfigRepr};
 #[cfg(feature = "python")]
 use crate::python::pyo3utils::pyany_to_value;
+#[cfg(feature = "python")]
+use crate::NyxError;
 use hifitime::{Duration, Epoch, TimeUnits};
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
@@ -182,40 +186,16 @@ impl GaussMarkov {
 
     #[cfg(feature = "python")]
     #[new]
-    #[pyo3(text_signature = "(tau, sigma, state_state)")]
-    fn py_new(
-        tau: Option<Duration>,
-        sigma: Option<f64>,
-        steady_state: Option<f64>,
-        bias: Option<f64>,
-        epoch: Option<Epoch>,
-    ) -> Result<Self, ConfigError> {
-        if tau.is_none() && sigma.is_none() && steady_state.is_none() {
+    #[pyo3(text_signature = "(tau, process_noise)")]
+    fn py_new(tau: Option<Duration>, process_noise: Option<f64>) -> Result<Self, ConfigError> {
+        match (tau, process_noise) {
             // We're called from pickle, return a non initialized state
-            return Ok(Self::ZERO);
-        } else if tau.is_none() || sigma.is_none() || steady_state.is_none() {
-            return Err(ConfigError::InvalidConfig {
-                msg: "tau, sigma, and steady_state must be specified".to_string(),
-            });
+            (None, None) => Ok(Self::ZERO),
+            (Some(tau), Some(process_noise)) => Self::new(tau, process_noise),
+            _ => Err(ConfigError::InvalidConfig {
+                msg: "tau and process_noise must be specified".to_string(),
+            }),
         }
-
-        let tau = tau.unwrap();
-        let sigma = sigma.unwrap();
-        let steady_state = steady_state.unwrap();
-
-        if tau <= Duration::ZERO {
-            return Err(ConfigError::InvalidConfig {
-                msg: format!("tau must be positive but got {tau}"),
-            });
-        }
-
-        Ok(Self {
-            tau,
-            bias_sigma: sigma,
-            steady_state_sigma: steady_state,
-            bias,
-            epoch,
-        })
     }
 
     #[cfg(feature = "python")]
@@ -233,24 +213,49 @@ impl GaussMarkov {
 
     #[cfg(feature = "python")]
     #[getter]
-    fn get_bias(&self) -> Option<f64> {
-        self.bias
+    fn get_process_noise(&self) -> f64 {
+        self.process_noise
     }
 
     #[cfg(feature = "python")]
     #[setter]
-    fn set_sampling(&mut self, bias: f64) -> PyResult<()> {
-        self.bias_sigma = bias;
+    fn set_process_noise(&mut self, process_noise: f64) -> PyResult<()> {
+        self.process_noise = process_noise;
         Ok(())
     }
 
     /// Initializes a new Gauss Markov process for the provided kind of model.
     ///
-    /// Available models are: `Range`, `Doppler`, `RangeHP`, `Doppler HP` (HP stands for high precision).
+    /// Available models are: `Range` and `Doppler`, from the Deep Space Network.
     #[cfg(feature = "python")]
     #[classmethod]
     fn default(_cls: &PyType, kind: String) -> Result<Self, NyxError> {
-        Self::from_default(kind)
+        match kind.as_str() {
+            "Range" => Ok(Self::default_range_km()),
+            "Doppler" => Ok(Self::default_doppler_km_s()),
+            _ => Err(NyxError::CustomError {
+                msg: format!("No default Gauss Markov model for `{kind}`"),
+            }),
+        }
+    }
+
+    /// Simulate this process as a bias and store it in a parquet file, cf. [StochasticNoise::simulate].
+    #[cfg(feature = "python")]
+    #[pyo3(text_signature = "(path, runs=25, unit=None)")]
+    fn simulate(
+        &self,
+        path: String,
+        runs: Option<u32>,
+        unit: Option<String>,
+    ) -> Result<(), NyxError> {
+        StochasticNoise {
+            white_noise: None,
+            bias: Some(*self),
+        }
+        .simulate(path, runs, unit)
+        .map_err(|e| NyxError::CustomError { msg: e.to_string() })?;
+
+        Ok(())
     }
 
     #[cfg(feature = "python")]
@@ -271,15 +276,8 @@ impl GaussMarkov {
         <Self as ConfigRepr>::load_named(path)
     }
 
-    /// Create a new `GaussMarkov` process as if it were purely a white noise, i.c. without any time correlation.
-    #[cfg(feature = "python")]
-    #[classmethod]
-    fn white(_cls: &PyType, sigma: f64) -> Result<Self, NyxError> {
-        Ok(Self::white_noise(sigma))
-    }
-
     #[cfg(feature = "python")]
-    /// Loads the SpacecraftDynamics from its YAML representation
+    /// Loads the GaussMarkov processes from their YAML representation
     #[classmethod]
     fn loads(_cls: &PyType, data: &PyAny) -> Result<Vec<Self>, ConfigError> {
         use snafu::ResultExt;
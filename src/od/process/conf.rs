@@ -35,6 +35,10 @@ pub enum SmoothingArc {
     Epoch(Epoch),
     /// Stop smoothing at the first prediction
     Prediction,
+    /// Only smooth the estimates between the provided start and end epochs (inclusive)
+    TimeWindow(Epoch, Epoch),
+    /// Only smooth the last N estimates (including the very last one, which is left unchanged by the smoother)
+    Last(usize),
     /// Only stop once all estimates have been processed
     All,
 }
@@ -46,6 +50,8 @@ impl fmt::Display for SmoothingArc {
             SmoothingArc::Epoch(e) => write!(f, "{e}"),
             SmoothingArc::TimeGap(g) => write!(f, "time gap of {g}"),
             SmoothingArc::Prediction => write!(f, "first prediction"),
+            SmoothingArc::TimeWindow(start, end) => write!(f, "window from {start} to {end}"),
+            SmoothingArc::Last(n) => write!(f, "last {n} estimates"),
         }
     }
 }
@@ -58,6 +64,10 @@ impl Default for SmoothingArc {
 
 /// Defines a filter iteration configuration. Allows iterating on an OD solution until convergence criteria is met.
 /// The root mean squared of the prefit residuals ratios is used to assess convergence between iterations.
+/// Optionally, the iterations also stop once the smoothed epoch state no longer changes between iterations, cf. `state_tol_km`.
+///
+/// By default, this iterates at most 15 times, with an absolute tolerance of 1e-1, a relative tolerance of 1e-2, at most 3 subsequent divergences,
+/// and without any tolerance on the change of the epoch state.
 #[derive(Clone, Copy, Debug, TypedBuilder)]
 #[cfg_attr(feature = "python", pyclass)]
 pub struct IterationConf {
@@ -79,6 +89,10 @@ pub struct IterationConf {
     /// Set to true to force an ODP failure when the convergence criteria is not met
     #[builder(default = false)]
     pub force_failure: bool,
+    /// Stop iterating once the position of the smoothed epoch state changes by less than this many kilometers between two subsequent iterations.
+    /// Disabled if unset.
+    #[builder(default, setter(strip_option))]
+    pub state_tol_km: Option<f64>,
 }
 
 impl IterationConf {
@@ -104,6 +118,7 @@ impl IterationConf {
         max_iterations: Option<usize>,
         max_divergences: Option<usize>,
         force_failure: Option<bool>,
+        state_tol_km: Option<f64>,
     ) -> Result<Self, NyxError> {
         let mut me = Self {
             smoother: if let Some(strategy) = strategy {
@@ -148,6 +163,8 @@ impl IterationConf {
             me.force_failure = force_failure;
         }
 
+        me.state_tol_km = state_tol_km;
+
         Ok(me)
     }
 
@@ -170,18 +187,22 @@ impl Default for IterationConf {
             max_iterations: 15,
             max_divergences: 3,
             force_failure: false,
+            state_tol_km: None,
         }
     }
 }
 
 impl fmt::Display for IterationConf {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Iterate until abs = {:.2e}, or rel = {:.2e}, or {} iterations, or {} subsequent divergences with smoothing condition of {}",
+        write!(f, "Iterate until abs = {:.2e}, or rel = {:.2e}, or {} iterations, or {} subsequent divergences",
             self.absolute_tol,
             self.relative_tol,
             self.max_iterations,
-            self.max_divergences,
-            self.smoother)
+            self.max_divergences)?;
+        if let Some(state_tol_km) = self.state_tol_km {
+            write!(f, ", or epoch state change < {state_tol_km:.2e} km")?;
+        }
+        write!(f, " with smoothing condition of {}", self.smoother)
     }
 }
 
@@ -71,7 +71,16 @@ where
         + Allocator<A, <Spacecraft as State>::Size>,
     Spacecraft: EstimateFrom<D::StateType, Msr>,
 {
-    /// Store the estimates and residuals in a parquet file
+    /// Store the estimates and residuals in a parquet file, with one row per estimate and the following columns, in order:
+    /// + `Epoch (UTC)`: the epoch of the estimate as an ISO formatted string in UTC;
+    /// + the nominal state parameters in the integration frame (by default those of `Spacecraft::export_params`), and the sigmas
+    ///   of the non-Cartesian parameters;
+    /// + `Covariance A*B (frame) (unit)`: the upper triangle of the covariance of the estimate;
+    /// + `Sigma A (frame) (unit)` and `Sigma A (RIC) (unit)`: the uncertainties in the integration frame and the RIC frame;
+    /// + `Deviation A (RIC) (unit)`, only if `ric_state_deviation` is set in the export configuration;
+    /// + the prefit and postfit residuals, the measurement noise, the residual ratio, the rejection flag, and the tracker, which are null for predicted estimates.
+    ///
+    /// The frame of the estimates is stored in the metadata of the state parameter columns.
     pub fn to_parquet<P: AsRef<Path>>(&self, path: P, cfg: ExportCfg) -> Result<PathBuf, ODError> {
         ensure!(
             !self.estimates.is_empty(),
@@ -220,6 +229,17 @@ where
             ));
         }
 
+        // Add the state deviation in the RIC frame, if requested
+        if cfg.ric_state_deviation {
+            for (i, coord) in state_items.iter().enumerate().take(6) {
+                hdrs.push(Field::new(
+                    format!("Deviation {coord} (RIC) ({})", state_units[i]),
+                    DataType::Float64,
+                    false,
+                ));
+            }
+        }
+
         // Add the fields of the residuals
         let mut msr_fields = Vec::new();
         for f in Msr::fields() {
@@ -357,6 +377,26 @@ where
             record.push(Arc::new(data.finish()));
         }
 
+        // Add the state deviation in the RIC frame, if requested
+        if cfg.ric_state_deviation {
+            let ric_deviations = estimates
+                .iter()
+                .map(|s| {
+                    s.state_deviation_ric().context(ODPhysicsSnafu {
+                        action: "computing the RIC state deviation for export",
+                    })
+                })
+                .collect::<Result<Vec<_>, ODError>>()?;
+
+            for i in 0..6 {
+                let mut data = Float64Builder::new();
+                for deviation in &ric_deviations {
+                    data.append_value(deviation[i]);
+                }
+                record.push(Arc::new(data.finish()));
+            }
+        }
+
         // Finally, add the residuals.
         // Prefits
         for i in 0..Msr::MeasurementSize::dim() {
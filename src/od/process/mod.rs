@@ -17,7 +17,7 @@
 */
 
 use crate::linalg::allocator::Allocator;
-use crate::linalg::{DefaultAllocator, DimName};
+use crate::linalg::{DMatrix, DefaultAllocator, DimName, OMatrix};
 use crate::md::trajectory::{Interpolatable, Traj};
 pub use crate::od::estimate::*;
 pub use crate::od::ground_station::*;
@@ -76,10 +76,18 @@ pub struct ODProcess<
     pub prop: PropInstance<'a, D, E>,
     /// Kalman filter itself
     pub kf: K,
-    /// Vector of estimates available after a pass
+    /// Vector of estimates available after a pass.
+    /// Each estimate retains the STM from the previous estimate, such that smoothing never requires re-propagating the trajectory.
     pub estimates: Vec<K::Estimate>,
     /// Vector of residuals available after a pass
     pub residuals: Vec<Option<Residual<Msr::MeasurementSize>>>,
+    /// Whether to record the a priori (time updated) estimate of each measurement update in `apriori_estimates`
+    pub record_apriori: bool,
+    /// A priori estimates at each measurement epoch, i.e. before the measurement update, in the same order as the residuals which are set.
+    /// Only recorded if `record_apriori` is set.
+    pub apriori_estimates: Vec<K::Estimate>,
+    /// Change in the position (in km) of the smoothed epoch state between subsequent iterations, set by `iterate`.
+    pub iteration_state_changes_km: Vec<f64>,
     pub ekf_trigger: Option<EkfTrigger>,
     /// Residual rejection criteria allows preventing bad measurements from affecting the estimation.
     pub resid_crit: Option<ResidRejectCrit>,
@@ -135,6 +143,9 @@ where
             kf,
             estimates: Vec::with_capacity(10_000),
             residuals: Vec::with_capacity(10_000),
+            record_apriori: false,
+            apriori_estimates: Vec::new(),
+            iteration_state_changes_km: Vec::new(),
             ekf_trigger,
             resid_crit,
             almanac,
@@ -157,6 +168,9 @@ where
             kf,
             estimates: Vec::with_capacity(10_000),
             residuals: Vec::with_capacity(10_000),
+            record_apriori: false,
+            apriori_estimates: Vec::new(),
+            iteration_state_changes_km: Vec::new(),
             ekf_trigger: Some(trigger),
             resid_crit,
             almanac,
@@ -168,21 +182,33 @@ where
     /// Allows to smooth the provided estimates. Returns the smoothed estimates or an error.
     ///
     /// Estimates must be ordered in chronological order. This function will smooth the
-    /// estimates from the last in the list to the first one.
+    /// estimates from the last one of the smoothing arc to the first one. The estimates outside of the smoothing arc
+    /// are returned unchanged, such that the same indexing can be done between the estimates and the smoothed estimates.
+    ///
+    /// This is a Rauch-Tung-Striebel smoother: each estimate is smoothed from the smoothed next estimate, with the STM retained
+    /// between both estimates during the filter pass and the predicted covariance of the next estimate.
     pub fn smooth(&self, condition: SmoothingArc) -> Result<Vec<K::Estimate>, ODError> {
-        let l = self.estimates.len() - 1;
+        // Index of the last estimate of the smoothing arc, which cannot be smoothed itself
+        let last = match condition {
+            SmoothingArc::TimeWindow(_, end) => {
+                match self.estimates.iter().rposition(|est| est.epoch() <= end) {
+                    Some(last) => last,
+                    None => {
+                        warn!("No estimate before the end of the smoothing window {end}");
+                        return Ok(self.estimates.clone());
+                    }
+                }
+            }
+            _ => self.estimates.len() - 1,
+        };
 
-        info!("Smoothing {} estimates until {}", l + 1, condition);
-        let mut smoothed = Vec::with_capacity(self.estimates.len());
-        // Set the first item of the smoothed estimates to the last estimate (we cannot smooth the very last estimate)
-        smoothed.push(self.estimates.last().unwrap().clone());
+        info!("Smoothing {} estimates until {}", last + 1, condition);
+        let mut smoothed = self.estimates.clone();
+        let mut num_smoothed = 1;
 
-        loop {
-            let k = l - smoothed.len();
-            // Borrow the previously smoothed estimate of the k+1 estimate
-            let sm_est_kp1 = &self.estimates[k + 1];
-            let x_kp1_l = sm_est_kp1.state_deviation();
-            let p_kp1_l = sm_est_kp1.covar();
+        let mut k = last;
+        while k > 0 {
+            k -= 1;
             // Borrow the k-th estimate, which we're smoothing with the next estimate
             let est_k = &self.estimates[k];
             // Borrow the k+1-th estimate, which we're smoothing with the next estimate
@@ -206,24 +232,36 @@ where
                         break;
                     }
                 }
+                SmoothingArc::TimeWindow(start, _) => {
+                    if est_k.epoch() < start {
+                        break;
+                    }
+                }
+                SmoothingArc::Last(n) => {
+                    if num_smoothed >= n {
+                        break;
+                    }
+                }
                 SmoothingArc::All => {}
             }
 
-            // Compute the STM between both steps taken by the filter
-            // The filter will reset the STM between each estimate it computes, time update or measurement update.
-            // Therefore, the STM is simply the inverse of the one we used previously.
-            // est_kp1 is the estimate that used the STM from time k to time k+1. So the STM stored there
-            // is \Phi_{k \to k+1}. Let's invert that.
-            let phi_kp1_k = &est_kp1
-                .stm()
-                .clone()
-                .try_inverse()
-                .ok_or(ODError::SingularStateTransitionMatrix)?;
-
+            // The filter resets the STM after each estimate it computes (time update or measurement update), so the STM
+            // retained in the k+1-th estimate is \Phi_{k \to k+1}: smoothing does not require re-propagating the trajectory.
+            let phi_k_kp1 = est_kp1.stm();
+            // The predicted covariance of the k+1-th estimate includes the process noise.
+            let p_kp1_k = est_kp1.predicted_covar();
+            let p_kp1_k_inv =
+                estimated_inverse(&p_kp1_k).ok_or(ODError::SingularPredictedCovariance)?;
+
+            // Compute the smoother gain
+            let gain = est_k.covar() * phi_k_kp1.transpose() * p_kp1_k_inv;
+            // Borrow the previously smoothed k+1-th estimate
+            let sm_est_kp1 = &smoothed[k + 1];
             // Compute smoothed state deviation
-            let x_k_l = phi_kp1_k * x_kp1_l;
+            let x_k_l = est_k.state_deviation()
+                + &gain * (sm_est_kp1.state_deviation() - phi_k_kp1 * est_k.state_deviation());
             // Compute smoothed covariance
-            let p_k_l = phi_kp1_k * p_kp1_l * phi_kp1_k.transpose();
+            let p_k_l = est_k.covar() + &gain * (sm_est_kp1.covar() - p_kp1_k) * gain.transpose();
             // Store into vector
             let mut smoothed_est_k = est_k.clone();
             // Compute the smoothed state deviation
@@ -232,37 +270,17 @@ where
             smoothed_est_k.set_covar(p_k_l);
 
             // Move on
-            smoothed.push(smoothed_est_k);
-            if smoothed.len() == self.estimates.len() {
-                break;
-            }
+            smoothed[k] = smoothed_est_k;
+            num_smoothed += 1;
         }
 
-        // Note that we have yet to reverse the list, so we print them backward
         info!(
             "Smoothed {} estimates (from {} to {})",
-            smoothed.len(),
-            smoothed.last().unwrap().epoch(),
-            smoothed[0].epoch(),
+            num_smoothed,
+            smoothed[last + 1 - num_smoothed].epoch(),
+            smoothed[last].epoch(),
         );
 
-        // Now, let's add all of the other estimates so that the same indexing can be done
-        // between all the estimates and the smoothed estimates
-        if smoothed.len() < self.estimates.len() {
-            // Add the estimates that might have been skipped.
-            let mut k = self.estimates.len() - smoothed.len();
-            loop {
-                smoothed.push(self.estimates[k].clone());
-                if k == 0 {
-                    break;
-                }
-                k -= 1;
-            }
-        }
-
-        // And reverse to maintain the order of estimates
-        smoothed.reverse();
-
         Ok(smoothed)
     }
 
@@ -275,6 +293,56 @@ where
         (sum / (self.residuals.len() as f64)).sqrt()
     }
 
+    /// Returns the time between each consecutive measurement processed by the filter, i.e. excluding rejected measurements.
+    pub fn measurement_intervals(&self) -> Vec<Duration> {
+        self.processed_epochs()
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .collect()
+    }
+
+    /// Returns the start epoch, end epoch, and duration of each span between consecutive processed measurements that lasts longer than the threshold.
+    /// Rejected measurements do not update the filter, so they do not close a data gap.
+    pub fn data_gaps(&self, threshold: Duration) -> Vec<(Epoch, Epoch, Duration)> {
+        self.processed_epochs()
+            .windows(2)
+            .filter_map(|w| {
+                let gap = w[1] - w[0];
+                if gap > threshold {
+                    Some((w[0], w[1], gap))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Enables measurement editing: any measurement whose prefit residual ratio, computed with the innovation covariance,
+    /// exceeds the provided number of sigmas is rejected instead of being incorporated in the estimate.
+    pub fn set_measurement_editing(&mut self, num_sigmas: f64) {
+        self.resid_crit = Some(ResidRejectCrit { num_sigmas });
+    }
+
+    /// Returns the epochs of the measurements which were rejected by the measurement editing.
+    pub fn rejected_epochs(&self) -> Vec<Epoch> {
+        self.residuals
+            .iter()
+            .flatten()
+            .filter(|residual| residual.rejected)
+            .map(|residual| residual.epoch)
+            .collect()
+    }
+
+    /// Epochs of the measurements which were processed and not rejected.
+    fn processed_epochs(&self) -> Vec<Epoch> {
+        self.residuals
+            .iter()
+            .flatten()
+            .filter(|residual| !residual.rejected)
+            .map(|residual| residual.epoch)
+            .collect()
+    }
+
     /// Allows iterating on the filter solution. Requires specifying a smoothing condition to know where to stop the smoothing.
     pub fn iterate<Dev>(
         &mut self,
@@ -292,6 +360,8 @@ where
         let mut previous_rms = best_rms;
         let mut divergence_cnt = 0;
         let mut iter_cnt = 0;
+        let mut prev_epoch_state: Option<S> = None;
+        self.iteration_state_changes_km.clear();
         loop {
             if best_rms <= config.absolute_tol {
                 info!("*****************");
@@ -318,11 +388,35 @@ where
 
             // First, smooth the estimates
             let smoothed = self.smooth(config.smoother)?;
+
+            // Check whether the epoch state has changed since the previous iteration
+            let epoch_state = smoothed[0].state();
+            if let Some(prev_epoch_state) = prev_epoch_state {
+                let state_change_km =
+                    (epoch_state.orbit().radius_km - prev_epoch_state.orbit().radius_km).norm();
+                self.iteration_state_changes_km.push(state_change_km);
+
+                if let Some(state_tol_km) = config.state_tol_km {
+                    if state_change_km < state_tol_km {
+                        info!("*****************");
+                        info!("*** CONVERGED ***");
+                        info!("*****************");
+                        info!(
+                            "Filter converged on epoch state change ({:.2e} km < {:.2e} km) after {} iterations",
+                            state_change_km, state_tol_km, iter_cnt
+                        );
+                        break;
+                    }
+                }
+            }
+            prev_epoch_state = Some(epoch_state);
+
             // Reset the propagator
             self.prop.state = self.init_state;
             // Empty the estimates and add the first smoothed estimate as the initial estimate
             self.estimates = Vec::with_capacity(measurements.len().max(self.estimates.len()));
             self.residuals = Vec::with_capacity(measurements.len().max(self.estimates.len()));
+            self.apriori_estimates.clear();
 
             self.kf.set_previous_estimate(&smoothed[0]);
             // And re-run the filter
@@ -540,6 +634,13 @@ where
                 }
                 traj.states.truncate(index);
 
+                // Sigma points of an unscented filter, at the epoch of the reference state
+                let sigma_ref = if self.kf.is_unscented() {
+                    Some((self.prop.state, self.kf.sigma_points()))
+                } else {
+                    None
+                };
+
                 debug!("propagate for {next_step_size} (Δt to next msr: {delta_t})");
                 let (_, traj_covar) = self
                     .prop
@@ -557,6 +658,13 @@ where
                 // Get the datetime and info needed to compute the theoretical measurement according to the model
                 epoch = nominal_state.epoch();
 
+                let sigma_states = match sigma_ref {
+                    Some((reference, deviations)) => {
+                        self.propagate_sigma_points(reference, &deviations, next_step_size)?
+                    }
+                    None => Vec::new(),
+                };
+
                 // Perform a measurement update
                 if nominal_state.epoch() == next_msr_epoch {
                     // Get the computed observations
@@ -582,10 +690,60 @@ where
 
                                 self.kf.update_h_tilde(h_tilde);
 
+                                // Store what is needed to rebuild the a priori estimate after the update
+                                let prev_state_deviation =
+                                    self.kf.previous_estimate().state_deviation();
+                                let was_extended = self.kf.is_extended();
+
+                                // Shift the computed observation such that the filter's prefit residual is the wrapped residual of angular measurements.
+                                let real_obs = msr.observation();
+                                let computed_obs =
+                                    &real_obs - msr.residual(&computed_meas.observation());
+
+                                if self.kf.is_unscented() {
+                                    // The observations at each sigma point are offset from the computed observation by the difference of their instantaneous measurements.
+                                    let nominal_inst = device.measure_instantaneous(
+                                        nominal_state,
+                                        None,
+                                        self.almanac.clone(),
+                                    )?;
+                                    let mut sigma_obs = Vec::with_capacity(sigma_states.len());
+                                    for sigma_state in &sigma_states {
+                                        let mut obs = computed_obs.clone();
+                                        match (
+                                            &nominal_inst,
+                                            device.measure_instantaneous(
+                                                *sigma_state,
+                                                None,
+                                                self.almanac.clone(),
+                                            )?,
+                                        ) {
+                                            (Some(nominal_inst), Some(sigma_inst)) => {
+                                                obs += sigma_inst
+                                                    .residual(&nominal_inst.observation());
+                                            }
+                                            _ => debug!(
+                                                "{device_name} does not see sigma point @ {epoch}"
+                                            ),
+                                        }
+                                        sigma_obs.push(obs);
+                                    }
+
+                                    self.kf.set_sigma_points(
+                                        sigma_states
+                                            .iter()
+                                            .map(|sigma_state| {
+                                                deviation_from(sigma_state, &nominal_state)
+                                            })
+                                            .collect(),
+                                        sigma_obs,
+                                    );
+                                }
+
                                 match self.kf.measurement_update(
                                     nominal_state,
-                                    &msr.observation(),
-                                    &computed_meas.observation(),
+                                    &real_obs,
+                                    &computed_obs,
                                     device.measurement_covar(epoch)?,
                                     self.resid_crit,
                                 ) {
@@ -594,7 +752,25 @@ where
 
                                         residual.tracker = Some(device.name());
 
-                                        if !residual.rejected {
+                                        if self.record_apriori {
+                                            // The a priori state deviation is zero for an EKF, since the previous deviation was applied to the reference.
+                                            let mut apriori = estimate.clone();
+                                            apriori.set_covar(estimate.predicted_covar());
+                                            apriori.set_state_deviation(if was_extended {
+                                                OVector::<f64, <S as State>::Size>::zeros()
+                                            } else {
+                                                estimate.stm() * prev_state_deviation
+                                            });
+                                            self.apriori_estimates.push(apriori);
+                                        }
+
+                                        if residual.rejected {
+                                            info!(
+                                                "rejected msr #{msr_cnt} from {} @ {epoch} (prefit ratio of {:.3})",
+                                                device.name(),
+                                                residual.ratio
+                                            );
+                                        } else {
                                             msr_accepted_cnt += 1;
                                         }
 
@@ -620,6 +796,12 @@ where
                                             }
                                         }
 
+                                        // The unscented filter always operates about its own mean.
+                                        if self.kf.is_unscented() {
+                                            self.prop.state =
+                                                self.prop.state + estimate.state_deviation();
+                                        }
+
                                         self.prop.state.reset_stm();
 
                                         self.estimates.push(estimate);
@@ -655,6 +837,15 @@ where
                 } else {
                     // No measurement can be used here, let's just do a time update and continue advancing the propagator.
                     debug!("time update {epoch}");
+                    if self.kf.is_unscented() {
+                        self.kf.set_sigma_points(
+                            sigma_states
+                                .iter()
+                                .map(|sigma_state| deviation_from(sigma_state, &nominal_state))
+                                .collect(),
+                            Vec::new(),
+                        );
+                    }
                     match self.kf.time_update(nominal_state) {
                         Ok(est) => {
                             // State deviation is always zero for an EKF time update
@@ -682,8 +873,39 @@ where
         Ok(())
     }
 
+    /// Propagates the sigma points of an unscented filter, provided as deviations from the reference state, for the provided duration.
+    fn propagate_sigma_points(
+        &self,
+        reference: D::StateType,
+        deviations: &[OVector<f64, <S as State>::Size>],
+        duration: Duration,
+    ) -> Result<Vec<S>, ODError> {
+        deviations
+            .iter()
+            .map(|deviation| {
+                let mut sigma_state = reference + deviation.clone();
+                sigma_state.unset_stm();
+                let sigma_state = self
+                    .prop
+                    .prop
+                    .with(sigma_state, self.almanac.clone())
+                    .quiet()
+                    .for_duration(duration)
+                    .context(ODPropSnafu)?;
+                Ok(S::extract(sigma_state))
+            })
+            .collect()
+    }
+
     /// Continuously predicts the trajectory until the provided end epoch, with covariance mapping at each step. In other words, this performs a time update.
-    pub fn predict_until(&mut self, step: Duration, end_epoch: Epoch) -> Result<(), ODError> {
+    ///
+    /// The predicted estimates, which account for the process noise if any, are appended to the estimates (without residuals) and also returned.
+    pub fn predict_until(
+        &mut self,
+        step: Duration,
+        end_epoch: Epoch,
+    ) -> Result<Vec<K::Estimate>, ODError> {
+        let first_prediction = self.estimates.len();
         let prop_time = end_epoch - self.kf.previous_estimate().epoch();
         info!("Mapping covariance for {prop_time} with {step} step");
 
@@ -719,11 +941,17 @@ where
             }
         }
 
-        Ok(())
+        Ok(self.estimates[first_prediction..].to_vec())
     }
 
     /// Continuously predicts the trajectory for the provided duration, with covariance mapping at each step. In other words, this performs a time update.
-    pub fn predict_for(&mut self, step: Duration, duration: Duration) -> Result<(), ODError> {
+    ///
+    /// The predicted estimates, which account for the process noise if any, are appended to the estimates (without residuals) and also returned.
+    pub fn predict_for(
+        &mut self,
+        step: Duration,
+        duration: Duration,
+    ) -> Result<Vec<K::Estimate>, ODError> {
         let end_epoch = self.kf.previous_estimate().epoch() + duration;
         self.predict_until(step, end_epoch)
     }
@@ -796,6 +1024,9 @@ where
             kf,
             estimates: Vec::with_capacity(10_000),
             residuals: Vec::with_capacity(10_000),
+            record_apriori: false,
+            apriori_estimates: Vec::new(),
+            iteration_state_changes_km: Vec::new(),
             resid_crit,
             ekf_trigger: None,
             init_state,
@@ -804,3 +1035,43 @@ where
         }
     }
 }
+
+/// Returns the difference between the estimated components of a state and of its reference.
+fn deviation_from<S: State>(state: &S, reference: &S) -> OVector<f64, <S as State>::Size>
+where
+    DefaultAllocator: Allocator<<S as State>::Size>
+        + Allocator<<S as State>::Size, <S as State>::Size>
+        + Allocator<<S as State>::VecLength>,
+{
+    OVector::<f64, <S as State>::Size>::from_iterator(
+        state
+            .to_vector()
+            .iter()
+            .zip(reference.to_vector().iter())
+            .take(<S as State>::Size::dim())
+            .map(|(val, ref_val)| val - ref_val),
+    )
+}
+
+/// Inverts the provided covariance over the parameters whose variance is not zero, i.e. those which are estimated: the rows
+/// and columns of the other parameters (e.g. the coefficient of reflectivity if it is not estimated) are zero in the inverse.
+fn estimated_inverse<N: DimName>(covar: &OMatrix<f64, N, N>) -> Option<OMatrix<f64, N, N>>
+where
+    DefaultAllocator: Allocator<N, N>,
+{
+    let estimated: Vec<usize> = (0..N::dim()).filter(|&i| covar[(i, i)] > 0.0).collect();
+
+    let estimated_inv = DMatrix::from_fn(estimated.len(), estimated.len(), |i, j| {
+        covar[(estimated[i], estimated[j])]
+    })
+    .try_inverse()?;
+
+    let mut inverse = OMatrix::<f64, N, N>::zeros();
+    for (i, row) in estimated.iter().enumerate() {
+        for (j, col) in estimated.iter().enumerate() {
+            inverse[(*row, *col)] = estimated_inv[(i, j)];
+        }
+    }
+
+    Some(inverse)
+}
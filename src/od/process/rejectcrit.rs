@@ -47,35 +47,21 @@ pub struct ResidRejectCrit {
 #[pymethods]
 impl ResidRejectCrit {
     #[new]
-    #[pyo3(text_signature = "(min_accepted=None, num_sigmas=None)")]
-    fn py_new(min_accepted: Option<usize>, num_sigmas: Option<f64>) -> Self {
+    #[pyo3(text_signature = "(num_sigmas=None)")]
+    fn py_new(num_sigmas: Option<f64>) -> Self {
         let mut me = Self::default();
-        if let Some(min_accepted) = min_accepted {
-            me.min_accepted = min_accepted;
-        }
         if let Some(num_sigmas) = num_sigmas {
             me.num_sigmas = num_sigmas;
         }
         me
     }
 
-    #[getter]
-    fn get_min_accepted(&self) -> usize {
-        self.min_accepted
-    }
-
-    #[setter(orbit)]
-    fn py_set_min_accepted(&mut self, min_accepted: usize) -> PyResult<()> {
-        self.min_accepted = min_accepted;
-        Ok(())
-    }
-
     #[getter]
     fn get_num_sigmas(&self) -> f64 {
         self.num_sigmas
     }
 
-    #[setter(orbit)]
+    #[setter(num_sigmas)]
     fn py_set_num_sigmas(&mut self, num_sigmas: f64) -> PyResult<()> {
         self.num_sigmas = num_sigmas;
         Ok(())
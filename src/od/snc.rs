@@ -18,7 +18,7 @@
 
 use crate::cosmic::Frame;
 use crate::linalg::allocator::Allocator;
-use crate::linalg::{DefaultAllocator, DimName, OMatrix, OVector, U3, U6};
+use crate::linalg::{DefaultAllocator, DimName, OMatrix, OVector, Vector3, U3, U6};
 use crate::time::{Duration, Epoch};
 
 use std::fmt;
@@ -38,6 +38,8 @@ where
     pub start_time: Option<Epoch>,
     /// Specify the frame of this SNC -- CURRENTLY UNIMPLEMENTED
     pub frame: Option<Frame>,
+    /// Whether the diagonal is expressed in the RIC frame of the nominal state at each time update, instead of the inertial frame
+    pub ric_frame: bool,
     /// Enables state noise compensation (process noise) only be applied if the time between measurements is less than the disable_time
     pub disable_time: Duration,
     // Stores the initial epoch when the SNC is requested, needed for decay. Kalman filter will edit this automatically.
@@ -66,8 +68,9 @@ where
         }
         write!(
             f,
-            "SNC: diag({}) {}",
+            "SNC: diag({}){} {}",
             fmt_cov.join(", "),
+            if self.ric_frame { " in RIC" } else { "" },
             if let Some(start) = self.start_time {
                 format!("starting at {start}")
             } else {
@@ -109,12 +112,21 @@ where
             disable_time,
             start_time: None,
             frame: None,
+            ric_frame: false,
             decay_diag: None,
             init_epoch: None,
             prev_epoch: None,
         }
     }
 
+    /// Initialize a state noise compensation structure from the diagonal values expressed in the RIC frame, i.e. the radial,
+    /// in-track, and cross-track components of each block of three. This frame is recomputed from the nominal state at each time update.
+    pub fn ric(disable_time: Duration, values: &[f64]) -> Self {
+        let mut me = Self::from_diagonal(disable_time, values);
+        me.ric_frame = true;
+        me
+    }
+
     /// Initialize an SNC with a time at which it should start
     pub fn with_start_time(disable_time: Duration, values: &[f64], start_time: Epoch) -> Self {
         let mut me = Self::from_diagonal(disable_time, values);
@@ -185,6 +197,32 @@ where
 
         Some(snc)
     }
+
+    /// Returns the rotation from the frame of this SNC into the inertial frame of the provided position and velocity,
+    /// with one RIC rotation block per three components, or None if this SNC is already in the inertial frame.
+    pub fn dcm_to_inertial(
+        &self,
+        radius_km: &Vector3<f64>,
+        velocity_km_s: &Vector3<f64>,
+    ) -> Option<OMatrix<f64, A, A>> {
+        if !self.ric_frame {
+            return None;
+        }
+
+        let r_hat = radius_km.normalize();
+        let c_hat = radius_km.cross(velocity_km_s).normalize();
+        let i_hat = c_hat.cross(&r_hat);
+
+        let mut dcm = OMatrix::<f64, A, A>::zeros();
+        for blk in 0..A::dim() / 3 {
+            for (col, axis) in [r_hat, i_hat, c_hat].iter().enumerate() {
+                for row in 0..3 {
+                    dcm[(3 * blk + row, 3 * blk + col)] = axis[row];
+                }
+            }
+        }
+        Some(dcm)
+    }
 }
 
 #[test]
@@ -204,3 +242,24 @@ fn test_snc_init() {
     );
     println!("{}", snc_std);
 }
+
+#[test]
+fn test_snc_ric_dcm() {
+    use crate::time::Unit;
+    let snc_ric = SNC3::ric(2 * Unit::Minute, &[0.0, 0.0, 1e-12]);
+    println!("{}", snc_ric);
+
+    let radius_km = Vector3::new(7000.0, 100.0, -50.0);
+    let velocity_km_s = Vector3::new(0.1, 7.5, 1.2);
+    let dcm = snc_ric.dcm_to_inertial(&radius_km, &velocity_km_s).unwrap();
+
+    // The rotation is orthonormal and maps the radial and cross-track axes onto the position and angular momentum
+    assert!((dcm.transpose() * dcm - OMatrix::<f64, U3, U3>::identity()).norm() < 1e-12);
+    assert!((dcm.column(0) - radius_km.normalize()).norm() < 1e-12);
+    assert!((dcm.column(2) - radius_km.cross(&velocity_km_s).normalize()).norm() < 1e-12);
+
+    // Inertial SNCs are not rotated
+    assert!(SNC3::from_diagonal(2 * Unit::Minute, &[1e-12; 3])
+        .dcm_to_inertial(&radius_km, &velocity_km_s)
+        .is_none());
+}
@@ -0,0 +1,63 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::linalg::allocator::Allocator;
+use crate::linalg::{DefaultAllocator, DimName, OVector};
+use crate::time::{Duration, Epoch};
+
+/// Stores the continuous extension (dense output) of a single integration step, i.e. the coefficients of the
+/// 7th order interpolating polynomial of a `DOP853` integrator over that step.
+///
+/// The polynomial matches the state at the start of the step and at the end of the step exactly,
+/// so the interpolation is continuous across step boundaries.
+#[derive(Clone, Debug)]
+pub struct DenseStep<N: DimName>
+where
+    DefaultAllocator: Allocator<N>,
+{
+    /// Epoch at the start of this step
+    pub epoch: Epoch,
+    /// Step size, negative when propagating backward
+    pub step: Duration,
+    pub(crate) coeffs: Vec<OVector<f64, N>>,
+}
+
+impl<N: DimName> DenseStep<N>
+where
+    DefaultAllocator: Allocator<N>,
+{
+    /// Returns whether the provided epoch is within this step (boundaries included)
+    pub fn contains(&self, epoch: Epoch) -> bool {
+        let end = self.epoch + self.step;
+        if self.step.is_negative() {
+            epoch <= self.epoch && epoch >= end
+        } else {
+            epoch >= self.epoch && epoch <= end
+        }
+    }
+
+    /// Evaluates the interpolating polynomial at the provided epoch, which should be within this step.
+    pub fn evaluate(&self, epoch: Epoch) -> OVector<f64, N> {
+        let s = (epoch - self.epoch).to_seconds() / self.step.to_seconds();
+        let s1 = 1.0 - s;
+        let c = &self.coeffs;
+        // Horner-like evaluation from Hairer's `contd8`
+        let conpar = &c[4] + s * (&c[5] + s1 * (&c[6] + s * &c[7]));
+        &c[0] + s * (&c[1] + s1 * (&c[2] + s * (&c[3] + s1 * conpar)))
+    }
+}
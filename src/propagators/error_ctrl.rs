@@ -40,6 +40,21 @@ where
     ) -> f64
     where
         DefaultAllocator: Allocator<N>;
+
+    /// Computes the actual error of the current step with the settings of this error controller, e.g. its tolerances.
+    ///
+    /// This is the error used by the propagators, and defaults to [ErrorCtrl::estimate] for the error controllers without settings.
+    fn estimate_error<N: DimName>(
+        &self,
+        error_est: &OVector<f64, N>,
+        candidate: &OVector<f64, N>,
+        cur_state: &OVector<f64, N>,
+    ) -> f64
+    where
+        DefaultAllocator: Allocator<N>,
+    {
+        Self::estimate(error_est, candidate, cur_state)
+    }
 }
 
 /// A largest error control which effectively computes the largest error at each component
@@ -63,7 +78,7 @@ impl ErrorCtrl for LargestError {
         let state_delta = candidate - cur_state;
         let mut max_err = 0.0;
         for (i, prop_err_i) in error_est.iter().enumerate() {
-            let err = if state_delta[i] > REL_ERR_THRESH {
+            let err = if state_delta[i].abs() > REL_ERR_THRESH {
                 (prop_err_i / state_delta[i]).abs()
             } else {
                 prop_err_i.abs()
@@ -318,3 +333,117 @@ impl ErrorCtrl for RSSCartesianStepStm {
         max_err
     }
 }
+
+/// A weighted root mean square error control with separate absolute and relative tolerances for each component, as done in CVODE.
+///
+/// Each component of the local error estimate is weighted by `1 / (rel_tol * |y_i| + abs_tol)`, where `y_i` is that component of
+/// the current state, and the error is the root mean square of these weighted errors. The step is hence acceptable when this error is
+/// at most one: use [PropOpts::with_per_component_tol](crate::propagators::PropOpts::with_per_component_tol) to set that tolerance.
+///
+/// The first six components (e.g. position and velocity) each have their own tolerances, and all other components (e.g. mass
+/// or STM) share the same tolerances. Note that [ErrorCtrl::estimate] has no access to the settings, and uses the default tolerances.
+/// (Source)[https://sundials.readthedocs.io/en/latest/cvode/Mathematics_link.html#nonlinear-solve]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerComponentTol {
+    /// Absolute tolerance of each of the first six components, e.g. in km for the position and km/s for the velocity
+    pub abs_tol: [f64; 6],
+    /// Relative tolerance of each of the first six components
+    pub rel_tol: [f64; 6],
+    /// Absolute tolerance of all remaining components
+    pub abs_tol_other: f64,
+    /// Relative tolerance of all remaining components
+    pub rel_tol_other: f64,
+}
+
+impl PerComponentTol {
+    /// Initializes tolerances shared by all position components, and by all velocity components.
+    /// The remaining components use the tolerances of the velocity.
+    pub fn cartesian(
+        pos_abs_tol_km: f64,
+        pos_rel_tol: f64,
+        vel_abs_tol_km_s: f64,
+        vel_rel_tol: f64,
+    ) -> Self {
+        Self {
+            abs_tol: [
+                pos_abs_tol_km,
+                pos_abs_tol_km,
+                pos_abs_tol_km,
+                vel_abs_tol_km_s,
+                vel_abs_tol_km_s,
+                vel_abs_tol_km_s,
+            ],
+            rel_tol: [
+                pos_rel_tol,
+                pos_rel_tol,
+                pos_rel_tol,
+                vel_rel_tol,
+                vel_rel_tol,
+                vel_rel_tol,
+            ],
+            abs_tol_other: vel_abs_tol_km_s,
+            rel_tol_other: vel_rel_tol,
+        }
+    }
+}
+
+impl Default for PerComponentTol {
+    /// By default, the position is integrated to the millimeter and the velocity to the micrometer per second, with a relative tolerance of 1e-10.
+    fn default() -> Self {
+        Self::cartesian(1e-6, 1e-10, 1e-9, 1e-10)
+    }
+}
+
+impl ErrorCtrl for PerComponentTol {
+    fn estimate<N: DimName>(
+        error_est: &OVector<f64, N>,
+        candidate: &OVector<f64, N>,
+        cur_state: &OVector<f64, N>,
+    ) -> f64
+    where
+        DefaultAllocator: Allocator<N>,
+    {
+        Self::default().estimate_error(error_est, candidate, cur_state)
+    }
+
+    fn estimate_error<N: DimName>(
+        &self,
+        error_est: &OVector<f64, N>,
+        _candidate: &OVector<f64, N>,
+        cur_state: &OVector<f64, N>,
+    ) -> f64
+    where
+        DefaultAllocator: Allocator<N>,
+    {
+        let mut sum_sq = 0.0;
+        for i in 0..N::dim() {
+            let (abs_tol, rel_tol) = if i < 6 {
+                (self.abs_tol[i], self.rel_tol[i])
+            } else {
+                (self.abs_tol_other, self.rel_tol_other)
+            };
+            sum_sq += (error_est[i] / (rel_tol * cur_state[i].abs() + abs_tol)).powi(2);
+        }
+        (sum_sq / N::dim() as f64).sqrt()
+    }
+}
+
+#[test]
+fn per_component_tol_weights() {
+    use crate::linalg::Vector6;
+
+    let tol = PerComponentTol::cartesian(1e-3, 0.0, 1e-9, 0.0);
+    let state = Vector6::new(7000.0, 0.0, 0.0, 0.0, 7.5, 0.0);
+
+    // The same error is negligible on the position but dominates on the velocity.
+    let pos_err = Vector6::new(1e-6, 0.0, 0.0, 0.0, 0.0, 0.0);
+    let vel_err = Vector6::new(0.0, 0.0, 0.0, 0.0, 1e-6, 0.0);
+    assert!(tol.estimate_error(&pos_err, &state, &state) < 1e-2);
+    assert!(tol.estimate_error(&vel_err, &state, &state) > 100.0);
+
+    // The relative tolerance scales with the magnitude of each component.
+    let tol = PerComponentTol::cartesian(0.0, 1e-10, 0.0, 1e-10);
+    let err = Vector6::new(7e-7, 0.0, 0.0, 0.0, 7.5e-10, 0.0);
+    let state = Vector6::new(7000.0, 1.0, 1.0, 1.0, 7.5, 1.0);
+    assert!((tol.estimate_error(&err, &state, &state) - (2.0_f64 / 6.0).sqrt()).abs() < 1e-12);
+}
@@ -17,12 +17,16 @@
 */
 
 use super::error_ctrl::ErrorCtrl;
-use super::{DynamicsSnafu, IntegrationDetails, PropagationError, Propagator};
+use super::{
+    DenseStep, DynamicsSnafu, FallbackIntegrator, IntegrationDetails, PropStats, PropagationError,
+    Propagator, StepDetail, ABM8, DOP853,
+};
 use crate::dynamics::Dynamics;
+use crate::errors::EventError;
 use crate::linalg::allocator::Allocator;
 use crate::linalg::{DefaultAllocator, OVector};
 use crate::md::trajectory::{Interpolatable, Traj};
-use crate::md::EventEvaluator;
+use crate::md::{EventEvaluator, StateParameter};
 use crate::propagators::TrajectoryEventSnafu;
 use crate::time::{Duration, Epoch, Unit};
 use crate::State;
@@ -30,12 +34,16 @@ use anise::almanac::Almanac;
 use rayon::iter::ParallelBridge;
 use rayon::prelude::ParallelIterator;
 use snafu::ResultExt;
+use std::collections::VecDeque;
 use std::f64;
 use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
 
+/// Maximum number of iterations of the Brent solver when refining the epoch of an event during the propagation
+const MAX_EVENT_ITER: usize = 50;
+
 /// A Propagator allows propagating a set of dynamics forward or backward in time.
 /// It is an EventTracker, without any event tracking. It includes the options, the integrator
 /// details of the previous step, and the set of coefficients used for the monomorphic instance.
@@ -53,6 +61,14 @@ where
     pub details: IntegrationDetails,
     /// Should progress reports be logged
     pub log_progress: bool,
+    /// Statistics of the propagation
+    pub(crate) stats: PropStats,
+    /// Dense output of each step, if enabled
+    pub(crate) dense: Option<Vec<DenseStep<<D::StateType as State>::VecLength>>>,
+    /// Details of each accepted step, if enabled
+    pub(crate) step_history: Option<Vec<StepDetail>>,
+    /// Epochs and derivatives of the previous states, used by the multistep integrator
+    pub(crate) history: VecDeque<(Epoch, OVector<f64, <D::StateType as State>::VecLength>)>,
     pub(crate) almanac: Arc<Almanac>,
     pub(crate) step_size: Duration, // Stores the adapted step for the _next_ call
     pub(crate) fixed_step: bool,
@@ -78,6 +94,88 @@ where
         self
     }
 
+    /// Enables the monitoring of the specific energy and of the magnitude of the specific angular momentum, reported in the propagation stats.
+    /// The drifts are computed with respect to the current state.
+    ///
+    /// This is meaningful only for conservative dynamics (e.g. two body or spherical harmonics, without drag or thrust). If the state does not
+    /// support these parameters, a warning is logged and the monitor is not enabled.
+    pub fn with_conservation_monitor(mut self) -> Self {
+        match (
+            self.state.value(StateParameter::Energy),
+            self.state.value(StateParameter::Hmag),
+        ) {
+            (Ok(energy_km2_s2), Ok(hmag_km2_s)) => {
+                self.stats = PropStats {
+                    initial_energy_km2_s2: Some(energy_km2_s2),
+                    initial_hmag_km2_s: Some(hmag_km2_s),
+                    ..Default::default()
+                };
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("conservation monitor not enabled: {e}");
+            }
+        }
+        self
+    }
+
+    /// Enables the recording of the dense output of each integration step, such that the state can be evaluated at any epoch
+    /// of the propagated arc with `interpolate`, at the accuracy of the integrator.
+    ///
+    /// This is only supported by integrators with a continuous extension (e.g. `DOP853`). Otherwise, a warning is logged and
+    /// the dense output is not enabled.
+    pub fn with_dense_output(mut self) -> Self {
+        if self.prop.dense_output {
+            self.dense = Some(Vec::new());
+        } else {
+            warn!("dense output not enabled: integrator does not support it");
+        }
+        self
+    }
+
+    /// Returns the dense output steps recorded so far, if the dense output is enabled.
+    pub fn dense_steps(&self) -> Option<&[DenseStep<<D::StateType as State>::VecLength>]> {
+        self.dense.as_deref()
+    }
+
+    /// Enables the recording of the details (step size, error estimate and attempts) of each accepted integration step,
+    /// e.g. to diagnose how an adaptive step integrator adapts along an eccentric orbit.
+    pub fn with_step_history(mut self) -> Self {
+        self.step_history = Some(Vec::new());
+        self
+    }
+
+    /// Returns the details of the accepted steps recorded so far, if the step history is enabled.
+    pub fn step_history(&self) -> Option<&[StepDetail]> {
+        self.step_history.as_deref()
+    }
+
+    /// Evaluates the state at the provided epoch using the dense output of the propagation so far.
+    /// If several propagated steps contain this epoch (e.g. after propagating forward then backward), the latest step is used.
+    pub fn interpolate(&self, epoch: Epoch) -> Result<D::StateType, PropagationError> {
+        let steps = self
+            .dense
+            .as_ref()
+            .ok_or_else(|| PropagationError::DenseOutputError {
+                msg: "not enabled on this propagator instance".to_string(),
+            })?;
+
+        match steps.iter().rev().find(|step| step.contains(epoch)) {
+            Some(step) => {
+                let mut state = self.state;
+                state.set(epoch, &step.evaluate(epoch));
+                Ok(state)
+            }
+            None => Err(PropagationError::DenseOutputError {
+                msg: format!("{epoch} is not within the propagated arc"),
+            }),
+        }
+    }
+
+    /// Returns the statistics of the propagation so far.
+    pub fn stats(&self) -> PropStats {
+        self.stats
+    }
+
     /// Allows setting the step size of the propagator
     pub fn set_step(&mut self, step_size: Duration, fixed: bool) {
         self.step_size = step_size;
@@ -122,6 +220,9 @@ where
             {
                 if stop_time == epoch {
                     // No propagation necessary
+                    if backprop {
+                        self.step_size = -self.step_size; // Restore to a positive step size
+                    }
                     #[cfg(not(target_arch = "wasm32"))]
                     {
                         if self.log_progress {
@@ -186,7 +287,7 @@ where
         }
     }
 
-    /// This method propagates the provided Dynamics for the provided duration.
+    /// This method propagates the provided Dynamics for the provided duration, which may be negative to propagate backward in time.
     pub fn for_duration(&mut self, duration: Duration) -> Result<D::StateType, PropagationError> {
         self.for_duration_channel_option(duration, None)
     }
@@ -251,8 +352,7 @@ where
     }
 
     /// Propagates the provided Dynamics until the provided epoch and generate the trajectory of these dynamics on its own thread.
-    /// Returns the end state and the trajectory.
-    /// Known bug #190: Cannot generate a valid trajectory when propagating backward
+    /// Returns the end state and the trajectory, whose states are in chronological order even when propagating backward.
     pub fn until_epoch_with_traj(
         &mut self,
         end_time: Epoch,
@@ -266,7 +366,7 @@ where
     }
 
     /// Propagate until a specific event is found once.
-    /// Returns the state found and the trajectory until `max_duration`
+    /// Returns the state found and the trajectory until that state.
     pub fn until_event<F: EventEvaluator<D::StateType>>(
         &mut self,
         max_duration: Duration,
@@ -280,31 +380,246 @@ where
     }
 
     /// Propagate until a specific event is found `trigger` times.
-    /// Returns the state found and the trajectory until `max_duration`
+    /// Returns the state found and the trajectory until that state.
     pub fn until_nth_event<F: EventEvaluator<D::StateType>>(
         &mut self,
         max_duration: Duration,
         event: &F,
         trigger: usize,
     ) -> Result<(D::StateType, Traj<D::StateType>), PropagationError>
+    where
+        <DefaultAllocator as Allocator<<D::StateType as State>::VecLength>>::Buffer<f64>: Send,
+        D::StateType: Interpolatable,
+    {
+        self.until_nth_event_with_max_iter(max_duration, event, trigger, MAX_EVENT_ITER)
+    }
+
+    /// Propagate until a specific event is found `trigger` times, and halt there: the state of this instance is the event state.
+    /// Returns the state found and the trajectory until that state.
+    ///
+    /// The event is evaluated after each step. When its value changes sign over a step, the crossing is refined with a Brent solver of at most
+    /// `max_iter` iterations, where each evaluation propagates from the state at the start of that step. The solver stops when the
+    /// event value is within its value precision. A sign change for which the solver brackets the crossing to the epoch precision without reaching
+    /// the value precision is a discontinuity of the event function (e.g. a true anomaly wrapping around) and is not counted as an event.
+    ///
+    /// # Errors
+    /// + The event is not found `trigger` times within `max_duration`;
+    /// + The Brent solver does not converge within `max_iter` iterations.
+    pub fn until_nth_event_with_max_iter<F: EventEvaluator<D::StateType>>(
+        &mut self,
+        max_duration: Duration,
+        event: &F,
+        trigger: usize,
+        max_iter: usize,
+    ) -> Result<(D::StateType, Traj<D::StateType>), PropagationError>
     where
         <DefaultAllocator as Allocator<<D::StateType as State>::VecLength>>::Buffer<f64>: Send,
         D::StateType: Interpolatable,
     {
         info!("Searching for {}", event);
 
-        let (_, traj) = self.for_duration_with_traj(max_duration)?;
-        // Now, find the requested event
-        let events = traj
-            .find(event, self.almanac.clone())
+        let stop_time = self.state.epoch() + max_duration;
+        let value_precision = event.value_precision().abs();
+
+        let mut traj = Traj::new();
+        let mut prev_state = self.state;
+        let mut prev_value = event
+            .eval(&prev_state, self.almanac.clone())
             .context(TrajectoryEventSnafu)?;
-        match events.get(trigger) {
-            Some(event_state) => Ok((event_state.state, traj)),
-            None => Err(PropagationError::NthEventError {
-                nth: trigger,
-                found: events.len(),
-            }),
+        traj.states.push(prev_state);
+
+        let mut found = 0;
+        if prev_value.abs() <= value_precision {
+            if found == trigger {
+                traj.finalize();
+                return Ok((prev_state, traj));
+            }
+            found += 1;
+        }
+
+        let backprop = max_duration.is_negative();
+        if backprop {
+            self.step_size = -self.step_size; // Invert the step size
         }
+
+        let rslt = loop {
+            let remaining = stop_time - self.state.epoch();
+            if remaining == Duration::ZERO {
+                break Err(PropagationError::NthEventError {
+                    nth: trigger,
+                    found,
+                });
+            }
+
+            if remaining.abs() < self.step_size.abs() {
+                // Take one final step of exactly the needed duration until the stop time
+                let prev_step_size = self.step_size;
+                let prev_step_kind = self.fixed_step;
+                self.set_step(remaining, true);
+                let step = self.single_step();
+                self.set_step(prev_step_size, prev_step_kind);
+                if let Err(e) = step {
+                    break Err(e);
+                }
+            } else if let Err(e) = self.single_step() {
+                break Err(e);
+            }
+
+            let next_state = self.state;
+            let next_value = match event.eval(&next_state, self.almanac.clone()) {
+                Ok(value) => value,
+                Err(source) => break Err(PropagationError::TrajectoryEventError { source }),
+            };
+
+            let event_state = if prev_value.abs() <= value_precision {
+                // The previous state is the event (or already within its precision)
+                None
+            } else if next_value.abs() <= value_precision {
+                Some(next_state)
+            } else if prev_value * next_value < 0.0 {
+                match self.refine_event(
+                    prev_state, next_state, prev_value, next_value, event, max_iter,
+                ) {
+                    Ok(maybe_state) => maybe_state,
+                    Err(e) => break Err(e),
+                }
+            } else {
+                None
+            };
+
+            if let Some(event_state) = event_state {
+                if found == trigger {
+                    // Halt at the event
+                    debug!("{event} -- found @ {}", event_state.epoch());
+                    self.state = event_state;
+                    traj.states.push(event_state);
+                    break Ok(event_state);
+                }
+                found += 1;
+            }
+
+            traj.states.push(next_state);
+            prev_state = next_state;
+            prev_value = next_value;
+        };
+
+        if backprop {
+            self.step_size = -self.step_size; // Restore to a positive step size
+        }
+
+        let event_state = rslt?;
+        traj.finalize();
+        Ok((event_state, traj))
+    }
+
+    /// Refines the crossing of the event between the two provided states (whose event values have opposite signs) with a Brent solver.
+    /// Returns None if the crossing is a discontinuity of the event function.
+    fn refine_event<F: EventEvaluator<D::StateType>>(
+        &self,
+        start_state: D::StateType,
+        end_state: D::StateType,
+        start_value: f64,
+        end_value: f64,
+        event: &F,
+        max_iter: usize,
+    ) -> Result<Option<D::StateType>, PropagationError> {
+        let value_precision = event.value_precision().abs();
+        let tol = 0.5 * event.epoch_precision().to_seconds();
+
+        // Evaluates the event at the provided number of seconds from the start state
+        let eval_at = |delta_t_s: f64| -> Result<(D::StateType, f64), PropagationError> {
+            let state = self
+                .prop
+                .with(start_state, self.almanac.clone())
+                .quiet()
+                .for_duration(delta_t_s * Unit::Second)?;
+            let value = event
+                .eval(&state, self.almanac.clone())
+                .context(TrajectoryEventSnafu)?;
+            Ok((state, value))
+        };
+
+        // Brent's method, cf. Numerical Recipes, where the root is between b and c, and b is the best estimate.
+        let (mut a, mut fa, mut state_a) = (0.0, start_value, start_state);
+        let (mut b, mut fb, mut state_b) = (
+            (end_state.epoch() - start_state.epoch()).to_seconds(),
+            end_value,
+            end_state,
+        );
+        let (mut c, mut fc, mut state_c) = (b, fb, state_b);
+        let mut d = b - a;
+        let mut e = d;
+
+        for _ in 0..max_iter {
+            if (fb > 0.0) == (fc > 0.0) {
+                (c, fc, state_c) = (a, fa, state_a);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa, state_a) = (b, fb, state_b);
+                (b, fb, state_b) = (c, fc, state_c);
+                (c, fc, state_c) = (a, fa, state_a);
+            }
+
+            if fb.abs() <= value_precision {
+                return Ok(Some(state_b));
+            }
+
+            let xm = 0.5 * (c - b);
+            if xm.abs() <= tol {
+                debug!(
+                    "{event} -- discontinuity between {} and {}",
+                    start_state.epoch(),
+                    end_state.epoch()
+                );
+                return Ok(None);
+            }
+
+            if e.abs() >= tol && fa.abs() > fb.abs() {
+                // Attempt an inverse quadratic interpolation (or a secant step)
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (2.0 * xm * s, 1.0 - s)
+                } else {
+                    let q = fa / fc;
+                    let r = fb / fc;
+                    (
+                        s * (2.0 * xm * q * (q - r) - (b - a) * (r - 1.0)),
+                        (q - 1.0) * (r - 1.0) * (s - 1.0),
+                    )
+                };
+                if p > 0.0 {
+                    q = -q;
+                }
+                p = p.abs();
+                if 2.0 * p < (3.0 * xm * q - (tol * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    // Interpolation failed, use a bisection
+                    d = xm;
+                    e = d;
+                }
+            } else {
+                // Bounds decreasing too slowly, use a bisection
+                d = xm;
+                e = d;
+            }
+
+            (a, fa, state_a) = (b, fb, state_b);
+            b += if d.abs() > tol { d } else { tol.copysign(xm) };
+            (state_b, fb) = eval_at(b)?;
+        }
+
+        error!("Brent solver failed after {max_iter} iterations");
+        Err(PropagationError::TrajectoryEventError {
+            source: EventError::NotFound {
+                start: start_state.epoch(),
+                end: end_state.epoch(),
+                event: format!("{event}"),
+            },
+        })
     }
 
     /// Take a single propagator step and emit the result on the TX channel (if enabled)
@@ -317,6 +632,24 @@ where
             .finally(self.state, self.almanac.clone())
             .context(DynamicsSnafu)?;
 
+        if let Some(history) = self.step_history.as_mut() {
+            history.push(StepDetail {
+                epoch: self.state.epoch(),
+                step: t,
+                error: self.details.error,
+                attempts: self.details.attempts,
+            });
+        }
+
+        if self.stats.monitors_conservation() {
+            self.stats.update(
+                self.state.value(StateParameter::Energy).ok(),
+                self.state.value(StateParameter::Hmag).ok(),
+            );
+        } else {
+            self.stats.update(None, None);
+        }
+
         Ok(())
     }
 
@@ -328,6 +661,11 @@ where
         &mut self,
     ) -> Result<(Duration, OVector<f64, <D::StateType as State>::VecLength>), PropagationError>
     {
+        if self.prop.multistep {
+            if let Some(step) = self.multistep_derive()? {
+                return Ok(step);
+            }
+        }
         let state_vec = &self.state.to_vector();
         let state_ctx = &self.state;
         // Reset the number of attempts used (we don't reset the error because it's set before it's read)
@@ -385,12 +723,38 @@ where
             if self.fixed_step {
                 // Using a fixed step, no adaptive step necessary
                 self.details.step = self.step_size;
+                self.record_dense_step(step_size, state_vec, &next_state)?;
                 return Ok(((self.details.step), next_state));
             } else {
                 // Compute the error estimate.
-                self.details.error = E::estimate(&error_est, &next_state, state_vec);
+                self.details.error =
+                    self.prop
+                        .opts
+                        .error_ctrl
+                        .estimate_error(&error_est, &next_state, state_vec);
+                // The step is adapted in magnitude, and negative when propagating backward.
+                let direction = step_size.signum();
+                if self.details.error > self.prop.opts.tolerance
+                    && step_size.abs() <= self.prop.opts.min_step.to_seconds()
+                    && self.dense.is_none()
+                {
+                    if let Some(fallback) = self.prop.opts.fallback {
+                        // The error is too high even at the minimum step: integrate this step with the fallback integrator instead.
+                        let (fallback_state, fallback_error) =
+                            self.fallback_derive(&fallback, step_size, state_vec)?;
+                        debug!(
+                            "fallback integrator used @ {}: error {:.3e} -> {:.3e}",
+                            state_ctx.epoch(),
+                            self.details.error,
+                            fallback_error
+                        );
+                        next_state = fallback_state;
+                        self.details.error = fallback_error;
+                        self.stats.fallback_steps += 1;
+                    }
+                }
                 if self.details.error <= self.prop.opts.tolerance
-                    || step_size <= self.prop.opts.min_step.to_seconds()
+                    || step_size.abs() <= self.prop.opts.min_step.to_seconds()
                     || self.details.attempts >= self.prop.opts.attempts
                 {
                     if self.details.attempts >= self.prop.opts.attempts {
@@ -401,18 +765,21 @@ where
                     }
 
                     self.details.step = step_size * Unit::Second;
+                    self.record_dense_step(step_size, state_vec, &next_state)?;
                     if self.details.error < self.prop.opts.tolerance {
                         // Let's increase the step size for the next iteration.
                         // Error is less than tolerance, let's attempt to increase the step for the next iteration.
-                        let proposed_step = 0.9
-                            * step_size
-                            * (self.prop.opts.tolerance / self.details.error)
-                                .powf(1.0 / f64::from(self.prop.order));
-                        step_size = if proposed_step > self.prop.opts.max_step.to_seconds() {
-                            self.prop.opts.max_step.to_seconds()
-                        } else {
-                            proposed_step
-                        };
+                        let proposed_step = step_size.abs()
+                            * self
+                                .prop
+                                .opts
+                                .step_ratio(self.details.error, 1.0 / f64::from(self.prop.order));
+                        step_size = direction
+                            * if proposed_step > self.prop.opts.max_step.to_seconds() {
+                                self.prop.opts.max_step.to_seconds()
+                            } else {
+                                proposed_step
+                            };
                     }
                     // In all cases, let's update the step size to whatever was the adapted step size
                     self.step_size = step_size * Unit::Second;
@@ -421,21 +788,258 @@ where
                     // Error is too high and we aren't using the smallest step, and we haven't hit the max number of attempts.
                     // So let's adapt the step size.
                     self.details.attempts += 1;
-                    let proposed_step = 0.9
-                        * step_size
-                        * (self.prop.opts.tolerance / self.details.error)
-                            .powf(1.0 / f64::from(self.prop.order - 1));
-                    step_size = if proposed_step < self.prop.opts.min_step.to_seconds() {
-                        self.prop.opts.min_step.to_seconds()
-                    } else {
-                        proposed_step
-                    };
+                    let proposed_step = step_size.abs()
+                        * self
+                            .prop
+                            .opts
+                            .step_ratio(self.details.error, 1.0 / f64::from(self.prop.order - 1));
+                    step_size = direction
+                        * if proposed_step < self.prop.opts.min_step.to_seconds() {
+                            self.prop.opts.min_step.to_seconds()
+                        } else {
+                            proposed_step
+                        };
                     // Note that we don't set self.step_size, that will be updated right before we return
                 }
             }
         }
     }
 
+    /// Integrates a step of the provided size with the fallback integrator, splitting it into twice as many substeps as
+    /// previously until the error of each substep is within the tolerance or until the maximum number of substeps is reached.
+    ///
+    /// Returns the state at the end of the step and the largest error of its substeps.
+    fn fallback_derive(
+        &self,
+        fallback: &FallbackIntegrator,
+        step_size: f64,
+        state_vec: &OVector<f64, <D::StateType as State>::VecLength>,
+    ) -> Result<(OVector<f64, <D::StateType as State>::VecLength>, f64), PropagationError> {
+        let state_ctx = &self.state;
+        let mut num_substeps = 1;
+        loop {
+            let substep = step_size / f64::from(num_substeps);
+            let mut sub_state = state_vec.clone();
+            let mut max_error: f64 = 0.0;
+
+            for n in 0..num_substeps {
+                let t0 = substep * f64::from(n);
+                let mut k = Vec::with_capacity(fallback.stages);
+                k.push(
+                    self.prop
+                        .dynamics
+                        .eom(t0, &sub_state, state_ctx, self.almanac.clone())
+                        .context(DynamicsSnafu)?,
+                );
+                let mut a_idx: usize = 0;
+                for i in 0..(fallback.stages - 1) {
+                    let mut ci: f64 = 0.0;
+                    let mut wi =
+                        OVector::<f64, <D::StateType as State>::VecLength>::from_element(0.0);
+                    for kj in &k[0..i + 1] {
+                        let a_ij = fallback.a_coeffs[a_idx];
+                        ci += a_ij;
+                        wi += a_ij * kj;
+                        a_idx += 1;
+                    }
+                    k.push(
+                        self.prop
+                            .dynamics
+                            .eom(
+                                t0 + ci * substep,
+                                &(&sub_state + substep * wi),
+                                state_ctx,
+                                self.almanac.clone(),
+                            )
+                            .context(DynamicsSnafu)?,
+                    );
+                }
+
+                let mut next_state = sub_state.clone();
+                let mut error_est =
+                    OVector::<f64, <D::StateType as State>::VecLength>::from_element(0.0);
+                for (i, ki) in k.iter().enumerate() {
+                    let b_i = fallback.b_coeffs[i];
+                    let b_i_star = fallback.b_coeffs[i + fallback.stages];
+                    error_est += substep * (b_i - b_i_star) * ki;
+                    next_state += substep * b_i * ki;
+                }
+
+                max_error = max_error.max(self.prop.opts.error_ctrl.estimate_error(
+                    &error_est,
+                    &next_state,
+                    &sub_state,
+                ));
+                sub_state = next_state;
+            }
+
+            if max_error <= self.prop.opts.tolerance || num_substeps >= fallback.max_substeps {
+                return Ok((sub_state, max_error));
+            }
+            num_substeps = (2 * num_substeps).min(fallback.max_substeps);
+        }
+    }
+
+    /// Takes an Adams-Bashforth-Moulton step in PECE mode if the history of derivatives allows it, and returns None otherwise
+    /// such that the step is taken by the bootstrapping Runge Kutta. In all cases, the derivative at the current state is recorded.
+    ///
+    /// The error of the integration details is set to the error estimate of the corrector residual, i.e. the difference between the
+    /// predicted and corrected states.
+    #[allow(clippy::type_complexity)]
+    fn multistep_derive(
+        &mut self,
+    ) -> Result<
+        Option<(Duration, OVector<f64, <D::StateType as State>::VecLength>)>,
+        PropagationError,
+    > {
+        let epoch = self.state.epoch();
+        let state_vec = self.state.to_vector();
+        let state_ctx = self.state;
+
+        // The history is only valid if the derivatives are exactly one step apart.
+        let contiguous = match self.history.back() {
+            Some((prev_epoch, _)) => *prev_epoch == epoch || epoch - *prev_epoch == self.step_size,
+            None => true,
+        };
+        if !contiguous {
+            self.history.clear();
+        }
+        if self.history.back().map(|(prev_epoch, _)| *prev_epoch) != Some(epoch) {
+            let f_n = self
+                .prop
+                .dynamics
+                .eom(0.0, &state_vec, &state_ctx, self.almanac.clone())
+                .context(DynamicsSnafu)?;
+            self.history.push_back((epoch, f_n));
+        }
+        while self.history.len() > ABM8::STEPS {
+            self.history.pop_front();
+        }
+
+        // The step size may differ from the spacing of the history, e.g. for the final step of a propagation.
+        let uniform = self.history.len() < 2
+            || self.history[self.history.len() - 1].0 - self.history[self.history.len() - 2].0
+                == self.step_size;
+        if !uniform {
+            let latest = self.history.pop_back();
+            self.history.clear();
+            self.history.extend(latest);
+        }
+
+        if !self.fixed_step || self.history.len() < ABM8::STEPS {
+            // Bootstrap with the Runge Kutta
+            return Ok(None);
+        }
+
+        let step_size = self.step_size.to_seconds();
+
+        // Predict
+        let mut predicted = state_vec.clone();
+        for (beta, (_, f_i)) in ABM8::AB_COEFFS.iter().zip(self.history.iter().rev()) {
+            predicted += step_size * beta * f_i;
+        }
+        // Evaluate
+        let f_predicted = self
+            .prop
+            .dynamics
+            .eom(step_size, &predicted, &state_ctx, self.almanac.clone())
+            .context(DynamicsSnafu)?;
+        // Correct
+        let mut corrected = &state_vec + step_size * ABM8::AM_COEFFS[0] * f_predicted;
+        for (beta, (_, f_i)) in ABM8::AM_COEFFS[1..].iter().zip(self.history.iter().rev()) {
+            corrected += step_size * beta * f_i;
+        }
+        // Evaluate
+        let f_corrected = self
+            .prop
+            .dynamics
+            .eom(step_size, &corrected, &state_ctx, self.almanac.clone())
+            .context(DynamicsSnafu)?;
+        self.history
+            .push_back((epoch + self.step_size, f_corrected));
+
+        self.details.attempts = 1;
+        self.details.step = self.step_size;
+        self.details.error = self.prop.opts.error_ctrl.estimate_error(
+            &(&corrected - &predicted),
+            &corrected,
+            &state_vec,
+        );
+
+        Ok(Some((self.details.step, corrected)))
+    }
+
+    /// Computes the additional stages of the `DOP853` continuous extension for the step which was just accepted, and stores
+    /// the coefficients of the interpolating polynomial. This is a no-op if the dense output is not enabled.
+    fn record_dense_step(
+        &mut self,
+        step_size: f64,
+        state_vec: &OVector<f64, <D::StateType as State>::VecLength>,
+        next_state: &OVector<f64, <D::StateType as State>::VecLength>,
+    ) -> Result<(), PropagationError> {
+        if self.dense.is_none() {
+            return Ok(());
+        }
+        let state_ctx = self.state;
+        // Stages of the step, followed by the derivative at the end of the step and the additional stages.
+        let mut k = self.k.clone();
+        k.push(
+            self.prop
+                .dynamics
+                .eom(step_size, next_state, &state_ctx, self.almanac.clone())
+                .context(DynamicsSnafu)?,
+        );
+
+        let mut a_idx: usize = 0;
+        for _ in 0..DOP853::DENSE_STAGES {
+            let mut ci: f64 = 0.0;
+            let mut wi = OVector::<f64, <D::StateType as State>::VecLength>::from_element(0.0);
+            for kj in &k {
+                let a_ij = DOP853::DENSE_A_COEFFS[a_idx];
+                ci += a_ij;
+                wi += a_ij * kj;
+                a_idx += 1;
+            }
+            let ki = self
+                .prop
+                .dynamics
+                .eom(
+                    ci * step_size,
+                    &(state_vec + step_size * wi),
+                    &state_ctx,
+                    self.almanac.clone(),
+                )
+                .context(DynamicsSnafu)?;
+            k.push(ki);
+        }
+
+        // Coefficients of the interpolating polynomial, cf. Hairer's `dop853.f`
+        let ydiff = next_state - state_vec;
+        let bspl = step_size * &k[0] - &ydiff;
+        let mut coeffs = Vec::with_capacity(8);
+        coeffs.push(state_vec.clone());
+        coeffs.push(ydiff.clone());
+        coeffs.push(bspl.clone());
+        coeffs.push(ydiff - step_size * &k[self.prop.stages] - bspl);
+        for d_row in DOP853::DENSE_D_COEFFS.chunks(k.len()) {
+            let mut coeff = OVector::<f64, <D::StateType as State>::VecLength>::from_element(0.0);
+            for (d_ij, kj) in d_row.iter().zip(&k) {
+                coeff += *d_ij * kj;
+            }
+            coeffs.push(step_size * coeff);
+        }
+
+        if let Some(dense) = self.dense.as_mut() {
+            dense.push(DenseStep {
+                epoch: state_ctx.epoch(),
+                step: step_size * Unit::Second,
+                coeffs,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Copy the details of the latest integration step.
     pub fn latest_details(&self) -> IntegrationDetails {
         self.details
@@ -32,8 +32,15 @@ mod rk_methods;
 pub use rk_methods::*;
 mod options;
 pub use options::*;
+mod dense;
+pub use dense::*;
 
-use crate::{dynamics::DynamicsError, errors::EventError, io::ConfigError, time::Duration};
+use crate::{
+    dynamics::DynamicsError,
+    errors::EventError,
+    io::ConfigError,
+    time::{Duration, Epoch},
+};
 
 /// Stores the details of the previous integration step of a given propagator. Access as `my_prop.clone().latest_details()`.
 #[derive(Copy, Clone, Debug)]
@@ -56,6 +63,92 @@ impl fmt::Display for IntegrationDetails {
     }
 }
 
+/// Details of an accepted integration step, recorded when the step history is enabled (cf. `PropInstance::with_step_history`).
+#[derive(Copy, Clone, Debug)]
+pub struct StepDetail {
+    /// Epoch at the end of the step
+    pub epoch: Epoch,
+    /// step size used, negative when propagating backward
+    pub step: Duration,
+    /// error estimate of the step, as computed by the error control
+    pub error: f64,
+    /// number of attempts needed by an adaptive step size to be within the tolerance
+    pub attempts: u8,
+}
+
+impl fmt::Display for StepDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: step = {}, error = {:.3e}, attempts = {}",
+            self.epoch, self.step, self.error, self.attempts
+        )
+    }
+}
+
+/// Statistics of a propagator instance over its propagation. Access as `my_prop.stats()`.
+///
+/// If the conservation monitor is enabled (cf. `PropInstance::with_conservation_monitor`), this also tracks the relative change
+/// in specific orbital energy and in the magnitude of the specific angular momentum since the monitor was enabled.
+/// Both are constant under conservative two body dynamics, so a growing drift flags an inadequate integrator or tolerance.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PropStats {
+    /// number of integration steps taken
+    pub steps: usize,
+    /// specific energy (km^2/s^2) when the monitor was enabled, None if the conservation monitor is disabled
+    pub initial_energy_km2_s2: Option<f64>,
+    /// magnitude of the specific angular momentum (km^2/s) when the monitor was enabled, None if the conservation monitor is disabled
+    pub initial_hmag_km2_s: Option<f64>,
+    /// relative change in specific energy at the latest step
+    pub energy_drift: f64,
+    /// largest relative change in specific energy over all of the steps
+    pub max_energy_drift: f64,
+    /// relative change in the magnitude of the specific angular momentum at the latest step
+    pub hmag_drift: f64,
+    /// largest relative change in the magnitude of the specific angular momentum over all of the steps
+    pub max_hmag_drift: f64,
+    /// number of steps integrated with the fallback integrator (cf. `FallbackIntegrator`)
+    pub fallback_steps: usize,
+}
+
+impl PropStats {
+    /// Updates the statistics after a step, using the provided energy and angular momentum magnitude if the monitor is enabled.
+    pub(crate) fn update(&mut self, energy_km2_s2: Option<f64>, hmag_km2_s: Option<f64>) {
+        self.steps += 1;
+        if let (Some(init), Some(cur)) = (self.initial_energy_km2_s2, energy_km2_s2) {
+            self.energy_drift = ((cur - init) / init).abs();
+            self.max_energy_drift = self.max_energy_drift.max(self.energy_drift);
+        }
+        if let (Some(init), Some(cur)) = (self.initial_hmag_km2_s, hmag_km2_s) {
+            self.hmag_drift = ((cur - init) / init).abs();
+            self.max_hmag_drift = self.max_hmag_drift.max(self.hmag_drift);
+        }
+    }
+
+    /// Returns whether the conservation monitor is enabled
+    pub fn monitors_conservation(&self) -> bool {
+        self.initial_energy_km2_s2.is_some() || self.initial_hmag_km2_s.is_some()
+    }
+}
+
+impl fmt::Display for PropStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.monitors_conservation() {
+            write!(
+                f,
+                "PropStats {{steps: {}, energy drift: {:.3e} (max {:.3e}), momentum drift: {:.3e} (max {:.3e})}}",
+                self.steps,
+                self.energy_drift,
+                self.max_energy_drift,
+                self.hmag_drift,
+                self.max_hmag_drift
+            )
+        } else {
+            write!(f, "PropStats {{steps: {}}}", self.steps)
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Snafu)]
 pub enum PropagationError {
     #[snafu(display("encountered a dynamics error {source}"))]
@@ -66,4 +159,6 @@ pub enum PropagationError {
     NthEventError { nth: usize, found: usize },
     #[snafu(display("propagation failed because {source}"))]
     PropConfigError { source: ConfigError },
+    #[snafu(display("dense output unavailable: {msg}"))]
+    DenseOutputError { msg: String },
 }
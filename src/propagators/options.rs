@@ -20,9 +20,35 @@ use std::fmt;
 
 use crate::time::{Duration, Unit};
 
-use super::{ErrorCtrl, RSSCartesianStep};
+use super::{ErrorCtrl, PerComponentTol, RSSCartesianStep, RK};
 use typed_builder::TypedBuilder;
 
+/// A Runge Kutta integrator used by an adaptive propagator for the steps whose error is still above the tolerance at the minimum step size.
+///
+/// Such a step is split into substeps integrated with this integrator, doubling the number of substeps until the error of each substep
+/// is within the tolerance or until the maximum number of substeps is reached. This is meant for robustness through difficult regions
+/// (e.g. a deep periapsis under drag), where a lower order but more stable integrator (e.g. `CashKarp45`) may succeed where the main one fails.
+#[derive(Clone, Copy, Debug)]
+pub struct FallbackIntegrator {
+    pub(crate) stages: usize,
+    pub(crate) a_coeffs: &'static [f64],
+    pub(crate) b_coeffs: &'static [f64],
+    /// Maximum number of substeps into which the minimum step may be split
+    pub max_substeps: u32,
+}
+
+impl FallbackIntegrator {
+    /// Initializes a fallback with the provided adaptive Runge Kutta integrator, splitting the minimum step into at most `max_substeps` substeps.
+    pub fn new<T: RK>(max_substeps: u32) -> Self {
+        Self {
+            stages: T::STAGES,
+            a_coeffs: T::A_COEFFS,
+            b_coeffs: T::B_COEFFS,
+            max_substeps: max_substeps.max(1),
+        }
+    }
+}
+
 /// PropOpts stores the integrator options, including the minimum and maximum step sizes, and the
 /// max error size.
 ///
@@ -45,6 +71,18 @@ pub struct PropOpts<E: ErrorCtrl> {
     pub attempts: u8,
     #[builder(default = false)]
     pub fixed_step: bool,
+    /// Safety factor applied to the optimal step size computed by the adaptive step controller, defaults to 0.9
+    #[builder(default = 0.9)]
+    pub safety_factor: f64,
+    /// Smallest factor by which the step size may be multiplied between two consecutive steps (or attempts), defaults to no limit
+    #[builder(default = 0.0)]
+    pub min_step_ratio: f64,
+    /// Largest factor by which the step size may be multiplied between two consecutive steps, defaults to no limit
+    #[builder(default = f64::INFINITY)]
+    pub max_step_ratio: f64,
+    /// Integrator used when the error is still above the tolerance at the minimum step size, defaults to none (the step is accepted as is)
+    #[builder(default, setter(strip_option))]
+    pub fallback: Option<FallbackIntegrator>,
     pub error_ctrl: E,
 }
 
@@ -64,6 +102,10 @@ impl<E: ErrorCtrl> PropOpts<E> {
             tolerance,
             attempts: 50,
             fixed_step: false,
+            safety_factor: 0.9,
+            min_step_ratio: 0.0,
+            max_step_ratio: f64::INFINITY,
+            fallback: None,
             error_ctrl,
         }
     }
@@ -102,6 +144,25 @@ impl<E: ErrorCtrl> PropOpts<E> {
         }
         self.min_step = min_step;
     }
+
+    /// Set the bounds on the factor by which the step size may change between two consecutive steps.
+    /// For example, a `max_step_ratio` of 2.0 prevents the step size from more than doubling after an easy region.
+    pub fn set_step_ratios(&mut self, min_step_ratio: f64, max_step_ratio: f64) {
+        self.min_step_ratio = min_step_ratio;
+        self.max_step_ratio = max_step_ratio;
+    }
+
+    /// Set the integrator used when the error is still above the tolerance at the minimum step size (cf. [FallbackIntegrator]).
+    pub fn set_fallback(&mut self, fallback: FallbackIntegrator) {
+        self.fallback = Some(fallback);
+    }
+
+    /// Computes the factor by which to multiply the current step size given the error of the step and the exponent
+    /// of the error ratio, applying the safety factor and the bounds on the step ratios.
+    pub(crate) fn step_ratio(&self, error: f64, exponent: f64) -> f64 {
+        let ratio = self.safety_factor * (self.tolerance / error).powf(exponent);
+        ratio.clamp(self.min_step_ratio, self.max_step_ratio)
+    }
 }
 
 impl<E: ErrorCtrl> fmt::Display for PropOpts<E> {
@@ -111,13 +172,39 @@ impl<E: ErrorCtrl> fmt::Display for PropOpts<E> {
         } else {
             write!(
                 f,
-                "min_step: {:e}, max_step: {:e}, tol: {:e}, attempts: {}",
-                self.min_step, self.max_step, self.tolerance, self.attempts,
-            )
+                "min_step: {:e}, max_step: {:e}, tol: {:e}, attempts: {}, safety factor: {}, step ratio: [{}, {}]",
+                self.min_step,
+                self.max_step,
+                self.tolerance,
+                self.attempts,
+                self.safety_factor,
+                self.min_step_ratio,
+                self.max_step_ratio,
+            )?;
+            if let Some(fallback) = &self.fallback {
+                write!(
+                    f,
+                    ", fallback: {} stages up to {} substeps",
+                    fallback.stages, fallback.max_substeps
+                )?;
+            }
+            Ok(())
         }
     }
 }
 
+impl PropOpts<PerComponentTol> {
+    /// Initializes adaptive step options with per component tolerances: since the weighted error of [PerComponentTol] is normalized
+    /// by the tolerances, a step is accepted if that error is at most one.
+    pub fn with_per_component_tol(
+        min_step: Duration,
+        max_step: Duration,
+        error_ctrl: PerComponentTol,
+    ) -> Self {
+        Self::with_adaptive_step(min_step, max_step, 1.0, error_ctrl)
+    }
+}
+
 impl PropOpts<RSSCartesianStep> {
     /// `with_fixed_step` initializes an `PropOpts` such that the integrator is used with a fixed
     ///  step size.
@@ -129,6 +216,10 @@ impl PropOpts<RSSCartesianStep> {
             tolerance: 0.0,
             fixed_step: true,
             attempts: 0,
+            safety_factor: 0.9,
+            min_step_ratio: 0.0,
+            max_step_ratio: f64::INFINITY,
+            fallback: None,
             error_ctrl: RSSCartesianStep {},
         }
     }
@@ -164,6 +255,10 @@ impl Default for PropOpts<RSSCartesianStep> {
             tolerance: 1e-12,
             attempts: 50,
             fixed_step: false,
+            safety_factor: 0.9,
+            min_step_ratio: 0.0,
+            max_step_ratio: f64::INFINITY,
+            fallback: None,
             error_ctrl: RSSCartesianStep {},
         }
     }
@@ -200,4 +295,18 @@ fn test_options() {
     assert!((opts.tolerance - 1e-12).abs() < f64::EPSILON);
     assert_eq!(opts.attempts, 50);
     assert!(!opts.fixed_step);
+
+    let mut opts = PropOpts::builder()
+        .error_ctrl(RSSCartesianStep {})
+        .safety_factor(0.8)
+        .build();
+    assert!((opts.safety_factor - 0.8).abs() < f64::EPSILON);
+    assert!(opts.max_step_ratio.is_infinite());
+    // Error 1024 times smaller than the tolerance with a fifth order method: optimal ratio is 4.
+    assert!((opts.step_ratio(1e-12 / 1024.0, 0.2) - 3.2).abs() < 1e-12);
+    opts.set_step_ratios(0.2, 2.0);
+    assert!((opts.step_ratio(1e-12 / 1024.0, 0.2) - 2.0).abs() < f64::EPSILON);
+    assert!((opts.step_ratio(1.0, 0.2) - 0.2).abs() < f64::EPSILON);
+    // A zero error does not lead to an infinite step ratio when bounded.
+    assert!((opts.step_ratio(0.0, 0.2) - 2.0).abs() < f64::EPSILON);
 }
@@ -16,12 +16,17 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use anise::almanac::Almanac;
+use rayon::prelude::*;
 
 use super::error_ctrl::{ErrorCtrl, RSSCartesianStep};
-use super::{Dormand78, IntegrationDetails, PropInstance, PropOpts, RK, RK89};
+use super::{
+    Dormand78, IntegrationDetails, PropInstance, PropOpts, PropStats, PropagationError, ABM8,
+    DOP853, RK, RK89,
+};
 use crate::dynamics::Dynamics;
 use crate::linalg::allocator::Allocator;
 use crate::linalg::{DefaultAllocator, OVector};
@@ -45,6 +50,8 @@ where
     pub(crate) stages: usize, // Number of stages, i.e. how many times the derivatives will be called
     pub(crate) a_coeffs: &'a [f64],
     pub(crate) b_coeffs: &'a [f64],
+    pub(crate) dense_output: bool, // Whether the integrator supports dense output
+    pub(crate) multistep: bool,    // Whether this is the ABM8 multistep integrator
 }
 
 /// The `Propagator` trait defines the functions of a propagator and of an event tracker.
@@ -64,6 +71,8 @@ where
             order: T::ORDER,
             a_coeffs: T::A_COEFFS,
             b_coeffs: T::B_COEFFS,
+            dense_output: T::DENSE_OUTPUT,
+            multistep: T::MULTISTEP,
         }
     }
 
@@ -92,6 +101,11 @@ where
         Self::new::<Dormand78>(dynamics, opts)
     }
 
+    /// A Dormand Prince 8(5,3) propagator with custom propagator options, which supports dense output (cf. `PropInstance::with_dense_output`).
+    pub fn dop853(dynamics: D, opts: PropOpts<E>) -> Self {
+        Self::new::<DOP853>(dynamics, opts)
+    }
+
     pub fn with(&'a self, state: D::StateType, almanac: Arc<Almanac>) -> PropInstance<'a, D, E> {
         // Pre-allocate the k used in the propagator
         let mut k = Vec::with_capacity(self.stages + 1);
@@ -107,12 +121,37 @@ where
                 attempts: 1,
             },
             log_progress: true,
+            stats: PropStats::default(),
+            dense: None,
+            step_history: None,
+            history: VecDeque::with_capacity(ABM8::STEPS + 1),
             almanac,
             step_size: self.opts.init_step,
             fixed_step: self.opts.fixed_step,
             k,
         }
     }
+
+    /// Propagates each of the provided states independently for the provided duration, in parallel, and returns the final states in
+    /// the same order as the initial states.
+    ///
+    /// The dynamics and the almanac are shared (read-only) by all of the propagations, but each has its own integrator instance.
+    /// A failed propagation is reported in its own result and does not abort the other ones.
+    pub fn for_duration_many(
+        &'a self,
+        states: &[D::StateType],
+        duration: Duration,
+        almanac: Arc<Almanac>,
+    ) -> Vec<Result<D::StateType, PropagationError>> {
+        states
+            .par_iter()
+            .map(|state| {
+                self.with(*state, almanac.clone())
+                    .quiet()
+                    .for_duration(duration)
+            })
+            .collect()
+    }
 }
 
 impl<'a, D: Dynamics> Propagator<'a, D, RSSCartesianStep>
@@ -0,0 +1,66 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use super::{RK, RK89};
+
+/// `ABM8` is an 8th order Adams-Bashforth-Moulton multistep predictor-corrector integrator, in PECE mode.
+///
+/// Each step only requires two evaluations of the equations of motion, compared to sixteen for an `RK89` step,
+/// which makes it well suited to long arcs with expensive force models. This is a fixed step method: it must be
+/// used with fixed step options (`PropOpts::with_fixed_step`), otherwise it behaves like an `RK89`.
+///
+/// The first seven steps (or any step after a change of step size) are taken with an `RK89` of the same step size
+/// to build the history of derivatives, so there is no discontinuity when switching to the multistep scheme.
+/// The corrector residual of each step is available in the `error` of the integration details.
+#[allow(clippy::upper_case_acronyms)]
+pub struct ABM8 {}
+
+impl ABM8 {
+    /// Number of previous derivatives used by the predictor (and by the corrector, including the predicted one)
+    pub(crate) const STEPS: usize = 8;
+    /// Adams-Bashforth coefficients, applied to f_n, f_{n-1}, ..., f_{n-7}
+    pub(crate) const AB_COEFFS: &'static [f64] = &[
+        434_241.0 / 120_960.0,
+        -1_152_169.0 / 120_960.0,
+        2_183_877.0 / 120_960.0,
+        -2_664_477.0 / 120_960.0,
+        2_102_243.0 / 120_960.0,
+        -1_041_723.0 / 120_960.0,
+        295_767.0 / 120_960.0,
+        -36_799.0 / 120_960.0,
+    ];
+    /// Adams-Moulton coefficients, applied to f_{n+1}, f_n, ..., f_{n-6}
+    pub(crate) const AM_COEFFS: &'static [f64] = &[
+        36_799.0 / 120_960.0,
+        139_849.0 / 120_960.0,
+        -121_797.0 / 120_960.0,
+        123_133.0 / 120_960.0,
+        -88_547.0 / 120_960.0,
+        41_499.0 / 120_960.0,
+        -11_351.0 / 120_960.0,
+        1_375.0 / 120_960.0,
+    ];
+}
+
+impl RK for ABM8 {
+    const ORDER: u8 = RK89::ORDER;
+    const STAGES: usize = RK89::STAGES;
+    const A_COEFFS: &'static [f64] = RK89::A_COEFFS;
+    const B_COEFFS: &'static [f64] = RK89::B_COEFFS;
+    const MULTISTEP: bool = true;
+}
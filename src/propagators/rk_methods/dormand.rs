@@ -182,3 +182,231 @@ impl RK for Dormand78 {
         0.0,
     ];
 }
+
+/// `DOP853` is the Dormand-Prince 8(5,3) integrator of Hairer, Nørsett and Wanner, with a 7th order continuous extension (dense output).
+///
+/// The step is adapted using the 5th order embedded error estimate. Enable the dense output on a propagator instance with
+/// `PropInstance::with_dense_output` to evaluate the state at any epoch of the propagated arc with `PropInstance::interpolate`.
+///
+/// Coefficients taken from E. Hairer's `dop853.f` (Solving Ordinary Differential Equations I, Hairer, Nørsett and Wanner, 1993).
+#[allow(clippy::upper_case_acronyms)]
+pub struct DOP853 {}
+
+impl DOP853 {
+    /// Number of additional stages needed to build the dense output
+    pub(crate) const DENSE_STAGES: usize = 3;
+    /// A coefficients of the additional stages, where the first row applies to the 12 stages of the step and to
+    /// the derivative at the end of the step (13 values), and each subsequent row applies to one more stage.
+    pub(crate) const DENSE_A_COEFFS: &'static [f64] = &[
+        0.056167502283047954,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.25350021021662483,
+        -0.2462390374708025,
+        -0.12419142326381637,
+        0.15329179827876568,
+        0.00820105229563469,
+        0.007567897660545699,
+        -0.008298,
+        0.03183464816350214,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.028300909672366776,
+        0.053541988307438566,
+        -0.05492374857139099,
+        0.0,
+        0.0,
+        -0.00010834732869724932,
+        0.0003825710908356584,
+        -0.00034046500868740456,
+        0.1413124436746325,
+        -0.42889630158379194,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        -4.697621415361164,
+        7.683421196062599,
+        4.06898981839711,
+        0.3567271874552811,
+        0.0,
+        0.0,
+        0.0,
+        -0.0013990241651590145,
+        2.9475147891527724,
+        -9.15095847217987,
+    ];
+    /// D coefficients of the 5th to 8th terms of the interpolating polynomial, applied to the 16 stages (12 step
+    /// stages, the derivative at the end of the step, and the 3 additional stages).
+    pub(crate) const DENSE_D_COEFFS: &'static [f64] = &[
+        -8.428938276109013,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.5667149535193777,
+        -3.0689499459498917,
+        2.38466765651207,
+        2.117034582445028,
+        -0.871391583777973,
+        2.2404374302607883,
+        0.6315787787694688,
+        -0.08899033645133331,
+        18.148505520854727,
+        -9.194632392478356,
+        -4.436036387594894,
+        10.427508642579134,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        242.28349177525817,
+        165.20045171727028,
+        -374.5467547226902,
+        -22.113666853125306,
+        7.733432668472264,
+        -30.674084731089398,
+        -9.332130526430229,
+        15.697238121770845,
+        -31.139403219565178,
+        -9.35292435884448,
+        35.81684148639408,
+        19.985053242002433,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        -387.0373087493518,
+        -189.17813819516758,
+        527.8081592054236,
+        -11.57390253995963,
+        6.8812326946963,
+        -1.0006050966910838,
+        0.7777137798053443,
+        -2.778205752353508,
+        -60.19669523126412,
+        84.32040550667716,
+        11.99229113618279,
+        -25.69393346270375,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        -154.18974869023643,
+        -231.5293791760455,
+        357.6391179106141,
+        93.40532418362432,
+        -37.45832313645163,
+        104.0996495089623,
+        29.8402934266605,
+        -43.53345659001114,
+        96.32455395918828,
+        -39.17726167561544,
+        -149.72683625798564,
+    ];
+}
+
+impl RK for DOP853 {
+    const ORDER: u8 = 8;
+    const STAGES: usize = 12;
+    const DENSE_OUTPUT: bool = true;
+    const A_COEFFS: &'static [f64] = &[
+        0.05260015195876773,
+        0.0197250569845379,
+        0.0591751709536137,
+        0.02958758547680685,
+        0.0,
+        0.08876275643042054,
+        0.2413651341592667,
+        0.0,
+        -0.8845494793282861,
+        0.924834003261792,
+        0.037037037037037035,
+        0.0,
+        0.0,
+        0.17082860872947386,
+        0.12546768756682242,
+        0.037109375,
+        0.0,
+        0.0,
+        0.17025221101954405,
+        0.06021653898045596,
+        -0.017578125,
+        0.03709200011850479,
+        0.0,
+        0.0,
+        0.17038392571223998,
+        0.10726203044637328,
+        -0.015319437748624402,
+        0.008273789163814023,
+        0.6241109587160757,
+        0.0,
+        0.0,
+        -3.3608926294469414,
+        -0.868219346841726,
+        27.59209969944671,
+        20.154067550477894,
+        -43.48988418106996,
+        0.47766253643826434,
+        0.0,
+        0.0,
+        -2.4881146199716677,
+        -0.590290826836843,
+        21.230051448181193,
+        15.279233632882423,
+        -33.28821096898486,
+        -0.020331201708508627,
+        -0.9371424300859873,
+        0.0,
+        0.0,
+        5.186372428844064,
+        1.0914373489967295,
+        -8.149787010746927,
+        -18.52006565999696,
+        22.739487099350505,
+        2.4936055526796523,
+        -3.0467644718982196,
+        2.273310147516538,
+        0.0,
+        0.0,
+        -10.53449546673725,
+        -2.0008720582248625,
+        -17.9589318631188,
+        27.94888452941996,
+        -2.8589982771350235,
+        -8.87285693353063,
+        12.360567175794303,
+        0.6433927460157636,
+    ];
+    const B_COEFFS: &'static [f64] = &[
+        0.054293734116568765,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        4.450312892752409,
+        1.8915178993145003,
+        -5.801203960010585,
+        0.3111643669578199,
+        -0.1521609496625161,
+        0.20136540080403034,
+        0.04471061572777259,
+        0.04117368912237389,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        5.675469339128614,
+        2.3872768489717506,
+        -7.465581142465571,
+        0.6614932157077935,
+        -0.48634006837553356,
+        0.11944219431891463,
+        0.06706592359165889,
+    ];
+}
@@ -56,3 +56,28 @@ impl RK for Fehlberg45 {
         0.0,
     ];
 }
+
+/// `RKF45` is the classic Runge Kutta Fehlberg 4(5) integrator: unlike [Fehlberg45], it propagates the fourth order solution,
+/// and only uses the fifth order solution to estimate the error of the step.
+#[allow(clippy::upper_case_acronyms)]
+pub struct RKF45 {}
+
+impl RK for RKF45 {
+    const ORDER: u8 = 5;
+    const STAGES: usize = 6;
+    const A_COEFFS: &'static [f64] = Fehlberg45::A_COEFFS;
+    const B_COEFFS: &'static [f64] = &[
+        25.0 / 216.0,
+        0.0,
+        1408.0 / 2565.0,
+        2197.0 / 4104.0,
+        -1.0 / 5.0,
+        0.0,
+        16.0 / 135.0,
+        0.0,
+        6656.0 / 12825.0,
+        28561.0 / 56430.0,
+        -9.0 / 50.0,
+        2.0 / 55.0,
+    ];
+}
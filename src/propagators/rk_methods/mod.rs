@@ -24,6 +24,8 @@ mod fehlberg;
 pub use self::fehlberg::*;
 mod verner;
 pub use self::verner::*;
+mod abm;
+pub use self::abm::*;
 
 /// The `RK` trait defines a Runge Kutta integrator.
 #[allow(clippy::upper_case_acronyms)]
@@ -46,4 +48,10 @@ where
     /// Returns a pointer to a list of f64 corresponding to the b_i and b^*_i coefficients of the
     /// Butcher table for that RK. `Self.a_coeffs().len()` must be of size (order+1)*2.
     const B_COEFFS: &'static [f64];
+
+    /// Returns whether this integrator provides a continuous extension (dense output) of its steps, cf. `DOP853`.
+    const DENSE_OUTPUT: bool = false;
+
+    /// Returns whether this is the Adams-Bashforth-Moulton multistep integrator (`ABM8`), whose Runge Kutta coefficients are only used to bootstrap it.
+    const MULTISTEP: bool = false;
 }
@@ -0,0 +1,97 @@
+/*
+    Nyx, blazing fast astrodynamics
+    Copyright (C) 2018-onwards Christopher Rabotin <christopher.rabotin@gmail.com>
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published
+    by the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use std::fmt;
+use std::sync::Arc;
+
+use anise::almanac::Almanac;
+use pyo3::prelude::*;
+
+use crate::dynamics::{finite_diff_partials, position_grad, DynamicsError, ForceModel};
+use crate::linalg::{Matrix3x6, Matrix4x3, Vector3};
+use crate::Spacecraft;
+
+/// A force model defined in Python: a callable which takes the osculating spacecraft and returns its acceleration
+/// as a list of three floats in km/s^2, in the integration frame. It is called at every step of the integrator.
+#[derive(Clone)]
+pub(crate) struct PyForceModel {
+    pub(crate) callable: PyObject,
+}
+
+impl fmt::Display for PyForceModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = Python::with_gil(|py| {
+            self.callable
+                .getattr(py, "__name__")
+                .and_then(|name| name.extract::<String>(py))
+                .unwrap_or_else(|_| "callable".to_string())
+        });
+        write!(f, "Python force model `{name}`")
+    }
+}
+
+impl ForceModel for PyForceModel {
+    fn estimation_index(&self) -> Option<usize> {
+        None
+    }
+
+    fn eom(&self, ctx: &Spacecraft, _almanac: Arc<Almanac>) -> Result<Vector3<f64>, DynamicsError> {
+        let accel_km_s2: Vec<f64> = Python::with_gil(|py| {
+            self.callable
+                .call1(py, (*ctx,))
+                .and_then(|accel| accel.extract(py))
+        })
+        .map_err(|e| DynamicsError::DynamicsPython { msg: e.to_string() })?;
+
+        if accel_km_s2.len() != 3 {
+            return Err(DynamicsError::DynamicsPython {
+                msg: format!(
+                    "expected an acceleration of three components, got {}",
+                    accel_km_s2.len()
+                ),
+            });
+        }
+
+        // The spacecraft dynamics divide the force by the mass of the spacecraft.
+        Ok(Vector3::from_column_slice(&accel_km_s2) * ctx.mass_kg())
+    }
+
+    /// Returns the position partials of the Python force model. The velocity partials are only available with
+    /// [ForceModel::dual_eom_pos_vel].
+    fn dual_eom(
+        &self,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix4x3<f64>), DynamicsError> {
+        let (force, partials, wrt_cd) = self.dual_eom_pos_vel(osc_ctx, almanac)?;
+        Ok((force, position_grad(&partials, &wrt_cd)))
+    }
+
+    /// The position and velocity partials are computed by central finite differences, since the Python callable cannot
+    /// be evaluated in hyperdual numbers. The Python force model does not depend on the Cd.
+    fn dual_eom_pos_vel(
+        &self,
+        osc_ctx: &Spacecraft,
+        almanac: Arc<Almanac>,
+    ) -> Result<(Vector3<f64>, Matrix3x6<f64>, Vector3<f64>), DynamicsError> {
+        let force = self.eom(osc_ctx, almanac.clone())?;
+        let partials = finite_diff_partials(osc_ctx, |sc| self.eom(sc, almanac.clone()))?;
+
+        Ok((force, partials, Vector3::zeros()))
+    }
+}
@@ -24,15 +24,18 @@ use crate::propagators::{
     CashKarp45, Dormand45, Dormand78, Fehlberg45, PropagationError, RK2Fixed, RK4Fixed, Verner56,
 };
 use crate::{NyxError, Orbit, Spacecraft};
+use anise::almanac::Almanac;
+use anise::errors::PhysicsError;
 use hifitime::{Duration, Epoch, Unit};
 use pyo3::{prelude::*, py_run};
 use rayon::prelude::*;
+use std::sync::Arc;
 
-pub(crate) use self::orbit_trajectory::OrbitTraj;
+pub(crate) use self::force_model::PyForceModel;
 pub(crate) use self::sc_trajectory::SpacecraftTraj;
 
 mod events;
-mod orbit_trajectory;
+mod force_model;
 mod sc_trajectory;
 pub mod spacecraft;
 
@@ -46,7 +49,6 @@ pub(crate) fn register_md(py: Python<'_>, parent_module: &PyModule) -> PyResult<
     sm.add_class::<Event>()?;
     sm.add_class::<ExportCfg>()?;
     sm.add_class::<sc_trajectory::SpacecraftTraj>()?;
-    sm.add_class::<orbit_trajectory::OrbitTraj>()?;
     sm.add_function(wrap_pyfunction!(propagate, sm)?)?;
     sm.add_function(wrap_pyfunction!(two_body, sm)?)?;
 
@@ -59,16 +61,17 @@ pub(crate) fn register_md(py: Python<'_>, parent_module: &PyModule) -> PyResult<
     Ok(())
 }
 
-/// Propagates the provided spacecraft with the provided dynamics until the provided stopping condition (duration, epoch, or event [and optionally the count]).
+/// Propagates the provided spacecraft with the provided dynamics and almanac until the provided stopping condition (duration, epoch, or event [and optionally the count]).
 ///
 /// Available methods: rk89, dormand78, dormand45, rk45 (or fehlberg45), cashkarp45, verner56, rk4, rk2
 #[pyfunction]
 #[pyo3(
-    text_signature = "(spacecraft, dynamics, duration=None, epoch=None, event=None, event_count=None, min_step=None, max_step=None, fixed_step=None, tolerance=None, method='rk89')"
+    text_signature = "(spacecraft, dynamics, almanac, duration=None, epoch=None, event=None, event_count=None, min_step=None, max_step=None, fixed_step=None, tolerance=None, method='rk89')"
 )]
 fn propagate(
     spacecraft: Spacecraft,
     dynamics: SpacecraftDynamics,
+    almanac: Almanac,
     duration: Option<Duration>,
     epoch: Option<Epoch>,
     event: Option<Event>,
@@ -97,11 +100,14 @@ fn propagate(
     };
     info!("Propagator options: {opts}");
 
+    let almanac = Arc::new(almanac);
+
     let prop_setup = match method {
         Some(value) => match value.to_lowercase().as_str() {
             "rk89" => Propagator::rk89(dynamics, opts),
             "dormand78" => Propagator::new::<Dormand78>(dynamics, opts),
             "dormand45" => Propagator::new::<Dormand45>(dynamics, opts),
+            "dop853" => Propagator::dop853(dynamics, opts),
             "rk45" | "fehlberg45" => Propagator::new::<Fehlberg45>(dynamics, opts),
             "cashkarp45" => Propagator::new::<CashKarp45>(dynamics, opts),
             "verner56" => Propagator::new::<Verner56>(dynamics, opts),
@@ -130,23 +136,25 @@ fn propagate(
         let (sc, traj) = match event_count {
             Some(count) => {
                 prop_setup
-                    .with(spacecraft)
+                    .with(spacecraft, almanac)
                     .until_nth_event(max_duration, &event, count)?
             }
             None => prop_setup
-                .with(spacecraft)
+                .with(spacecraft, almanac)
                 .until_event(max_duration, &event)?,
         };
 
         Ok((sc, SpacecraftTraj { inner: traj }))
     } else if let Some(duration) = duration {
         let (sc, traj) = prop_setup
-            .with(spacecraft)
+            .with(spacecraft, almanac)
             .for_duration_with_traj(duration)?;
 
         Ok((sc, SpacecraftTraj { inner: traj }))
     } else if let Some(epoch) = epoch {
-        let (sc, traj) = prop_setup.with(spacecraft).until_epoch_with_traj(epoch)?;
+        let (sc, traj) = prop_setup
+            .with(spacecraft, almanac)
+            .until_epoch_with_traj(epoch)?;
 
         Ok((sc, SpacecraftTraj { inner: traj }))
     } else {
@@ -224,7 +232,7 @@ fn two_body(
     Ok((orbits, epochs)
         .into_par_iter()
         .map(|(orbit, epoch)| orbit.at_epoch(epoch))
-        .collect::<Vec<Result<Orbit, NyxError>>>()
+        .collect::<Vec<Result<Orbit, PhysicsError>>>()
         .into_iter()
         .filter(|result| match result {
             Ok(_) => true,
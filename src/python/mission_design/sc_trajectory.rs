@@ -16,21 +16,19 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use anise::almanac::Almanac;
+use anise::prelude::Frame;
 use hifitime::{Duration, Epoch, Unit};
 use pyo3::prelude::*;
 
 use crate::md::trajectory::{ExportCfg, TrajError};
-use crate::python::cosmic::Frame;
 use crate::{
-    md::{
-        prelude::{Cosm, Traj as TrajRs},
-        Event, EventEvaluator,
-    },
+    md::{prelude::Traj as TrajRs, Event, EventEvaluator},
     NyxError, Spacecraft, State,
 };
 
-use super::OrbitTraj;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A structure that stores a spacecraft structure generated from a propagation.
 /// Cannot be pickled in Python, so you must export it to Parquet first and use the TrajectoryLoader.
@@ -41,13 +39,6 @@ pub(crate) struct SpacecraftTraj {
 
 #[pymethods]
 impl SpacecraftTraj {
-    /// Convert this spacecraft trajectory into an Orbit trajectory, loosing all references to the spacecraft
-    pub fn downcast(&self) -> OrbitTraj {
-        OrbitTraj {
-            inner: self.inner.downcast(),
-        }
-    }
-
     /// Returns the state at the provided epoch, or raises an exception if the epoch is outside of the bounds of the trajectory
     fn at(&self, epoch: Epoch) -> Result<Spacecraft, TrajError> {
         self.inner.at(epoch)
@@ -81,30 +72,30 @@ impl SpacecraftTraj {
     ///
     /// If a start or end epoch is provided (or both are provided), this function will return a list of a single event.
     /// If none are provided, this function will search the whole trajectory for the event and return all of the states where such event happens.
+    #[pyo3(text_signature = "(event, almanac, start=None, end=None)")]
     fn find(
         &self,
         event: Event,
+        almanac: Almanac,
         start: Option<Epoch>,
         end: Option<Epoch>,
     ) -> Result<Vec<Spacecraft>, NyxError> {
+        let almanac = Arc::new(almanac);
         if start.is_some() || end.is_some() {
-            let start = if let Some(start) = start {
-                start
-            } else {
-                self.inner.first().epoch()
-            };
-
-            let end = if let Some(end) = end {
-                end
-            } else {
-                self.inner.last().epoch()
-            };
-
-            Ok(vec![self.inner.find_bracketed(start, end, &event)?.state])
+            let start = start.unwrap_or_else(|| self.inner.first().epoch());
+            let end = end.unwrap_or_else(|| self.inner.last().epoch());
+
+            Ok(vec![
+                self.inner
+                    .find_bracketed(start, end, &event, almanac)
+                    .map_err(|e| NyxError::CustomError { msg: e.to_string() })?
+                    .state,
+            ])
         } else {
             Ok(self
                 .inner
-                .find(&event)?
+                .find(&event, almanac)
+                .map_err(|e| NyxError::CustomError { msg: e.to_string() })?
                 .iter()
                 .map(|details| details.state)
                 .collect::<Vec<Spacecraft>>())
@@ -116,65 +107,43 @@ impl SpacecraftTraj {
         &self,
         event: Event,
         precision: Unit,
+        almanac: Almanac,
     ) -> Result<(Spacecraft, Spacecraft), NyxError> {
-        self.inner.find_minmax(&event, precision)
+        self.inner
+            .find_minmax(&event, precision, Arc::new(almanac))
+            .map_err(|e| NyxError::CustomError { msg: e.to_string() })
     }
 
     /// Saves this trajectory to a parquet file, optionally adding the event columns to append and metadata.
     /// Set the groundtrack parameter to a body fixed frame to export this trajectory with latitude, longitude, and height columns in that body fixed frame.
-    #[pyo3(text_signature = "(path, events=None, metadata=None, groundtrack=None")]
+    #[pyo3(text_signature = "(path, almanac, events=None, metadata=None, groundtrack=None)")]
     fn to_parquet(
         &self,
         path: String,
+        almanac: Almanac,
         events: Option<Vec<Event>>,
         metadata: Option<HashMap<String, String>>,
         groundtrack: Option<Frame>,
     ) -> Result<String, NyxError> {
-        let maybe = match events {
-            None => match groundtrack {
-                None => {
-                    let cfg = match metadata {
-                        None => ExportCfg::default(),
-                        Some(_) => ExportCfg {
-                            metadata,
-                            ..Default::default()
-                        },
-                    };
-                    self.inner.to_parquet(path, None, cfg)
-                }
-                Some(py_frame) => self.inner.to_groundtrack_parquet(
-                    path,
-                    py_frame.inner,
-                    None,
+        let almanac = Arc::new(almanac);
+        let events = events.as_ref().map(|events| {
+            events
+                .iter()
+                .map(|e| e as &dyn EventEvaluator<Spacecraft>)
+                .collect::<Vec<&dyn EventEvaluator<Spacecraft>>>()
+        });
+
+        let maybe = match groundtrack {
+            None => {
+                let cfg = ExportCfg {
                     metadata,
-                    Cosm::de438(),
-                ),
-            },
-            Some(events) => {
-                let events = events
-                    .iter()
-                    .map(|e| e as &dyn EventEvaluator<Spacecraft>)
-                    .collect::<Vec<&dyn EventEvaluator<Spacecraft>>>();
-
-                match groundtrack {
-                    None => {
-                        let cfg = match metadata {
-                            None => ExportCfg::default(),
-                            Some(_) => ExportCfg {
-                                metadata,
-                                ..Default::default()
-                            },
-                        };
-                        self.inner.to_parquet(path, Some(events), cfg)
-                    }
-                    Some(py_frame) => self.inner.to_groundtrack_parquet(
-                        path,
-                        py_frame.inner,
-                        Some(events),
-                        metadata,
-                        Cosm::de438(),
-                    ),
-                }
+                    ..Default::default()
+                };
+                self.inner.to_parquet(path, events, cfg, almanac)
+            }
+            Some(body_fixed_frame) => {
+                self.inner
+                    .to_groundtrack_parquet(path, body_fixed_frame, events, metadata, almanac)
             }
         };
 
@@ -186,12 +155,8 @@ impl SpacecraftTraj {
 
     /// Allows converting the source trajectory into the (almost) equivalent trajectory in another frame.
     /// This simply converts each state into the other frame and may lead to aliasing due to the Nyquist–Shannon sampling theorem.
-    fn to_frame(&self, new_frame: String) -> Result<Self, NyxError> {
-        let cosm = Cosm::de438();
-
-        let frame = cosm.try_frame(&new_frame)?;
-
-        let conv_traj = self.inner.to_frame(frame, cosm)?;
+    fn to_frame(&self, new_frame: Frame, almanac: Almanac) -> Result<Self, NyxError> {
+        let conv_traj = self.inner.to_frame(new_frame, Arc::new(almanac))?;
 
         Ok(Self { inner: conv_traj })
     }
@@ -202,18 +167,17 @@ impl SpacecraftTraj {
         path: String,
         cfg: Option<ExportCfg>,
     ) -> Result<String, NyxError> {
-        match self.inner.ric_diff_to_parquet(
-            &other.inner,
-            path,
-            cfg.unwrap_or_else(|| ExportCfg::default()),
-        ) {
+        match self
+            .inner
+            .ric_diff_to_parquet(&other.inner, path, cfg.unwrap_or_default())
+        {
             Ok(path) => Ok(format!("{}", path.to_str().unwrap())),
             Err(e) => Err(NyxError::CustomError { msg: e.to_string() }),
         }
     }
 
     fn __add__(&self, rhs: &Self) -> Result<Self, NyxError> {
-        let inner = (self.inner.clone() + rhs.inner.clone())?;
+        let inner = (&self.inner + &rhs.inner)?;
 
         Ok(Self { inner })
     }
@@ -15,6 +15,8 @@
     You should have received a copy of the GNU Affero General Public License
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
+use crate::dynamics::DynamicsError;
+use crate::errors::StateError;
 use crate::python::PythonError;
 use crate::{
     cosmic::{DragConfig, SrpConfig},
@@ -138,7 +140,7 @@ impl Spacecraft {
     }
 
     /// Returns the value of the provided state parameter if available
-    fn value_of(&self, param: StateParameter) -> Result<f64, NyxError> {
+    fn value_of(&self, param: StateParameter) -> Result<f64, StateError> {
         self.value(param)
     }
 
@@ -149,6 +151,22 @@ impl Spacecraft {
     fn drag(&self) -> DragConfig {
         self.drag
     }
+
+    /// Returns a copy of this spacecraft with its STM initialized to identity, such that it is propagated with the state.
+    #[pyo3(name = "with_stm")]
+    fn py_with_stm(&self) -> Self {
+        self.with_stm()
+    }
+
+    /// Returns the state transition matrix as a list of rows, or raises an exception if the STM is not set.
+    #[pyo3(name = "stm")]
+    fn py_stm(&self) -> Result<Vec<Vec<f64>>, DynamicsError> {
+        let stm = self.stm()?;
+        Ok(stm
+            .row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect())
+    }
 }
 
 #[pymethods]
@@ -19,6 +19,8 @@
 use snafu::prelude::*;
 
 use crate::cosmic::AstroError;
+use crate::dynamics::DynamicsError;
+use crate::errors::{EventError, StateError};
 use crate::io::{ConfigError, InputOutputError};
 use crate::md::trajectory::TrajError;
 use crate::od::ODError;
@@ -74,6 +76,24 @@ impl From<AstroError> for PyErr {
     }
 }
 
+impl From<StateError> for PyErr {
+    fn from(err: StateError) -> PyErr {
+        PyException::new_err(err.to_string())
+    }
+}
+
+impl From<DynamicsError> for PyErr {
+    fn from(err: DynamicsError) -> PyErr {
+        PyException::new_err(err.to_string())
+    }
+}
+
+impl From<EventError> for PyErr {
+    fn from(err: EventError) -> PyErr {
+        PyException::new_err(err.to_string())
+    }
+}
+
 impl From<NyxError> for PyErr {
     fn from(err: NyxError) -> PyErr {
         PyException::new_err(err.to_string())
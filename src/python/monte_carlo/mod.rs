@@ -16,10 +16,10 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use crate::mc::GaussianGenerator;
+use crate::mc::{MultivariateNormal, StateDispersion};
 use crate::md::StateParameter;
 use crate::Orbit;
-use crate::{NyxError, Spacecraft};
+use crate::{NyxError, Spacecraft, State};
 use pyo3::{prelude::*, py_run};
 use rand::SeedableRng;
 use rand_distr::Distribution;
@@ -52,30 +52,12 @@ fn generate_orbits(
     kind: String,
     seed: Option<u64>,
 ) -> Result<Vec<Orbit>, NyxError> {
-    let generator = match kind.as_str() {
-        "abs" => GaussianGenerator::from_3std_devs(orbit, &parameters)?,
-        "prct" => GaussianGenerator::from_3std_dev_prcts(orbit, &parameters)?,
-        _ => {
-            return Err(NyxError::CustomError {
-                msg: format!(
-                    "Unknown kind of distribution: {} (should be 'abs' or 'prct')",
-                    kind
-                ),
-            })
-        }
-    };
-
-    let rng = match seed {
-        Some(seed) => Pcg64Mcg::new(seed.into()),
-        None => Pcg64Mcg::from_entropy(),
-    };
-
-    // Generate multithreaded
-    Ok(generator
-        .sample_iter(rng)
-        .take(count)
-        .map(|disp_state| disp_state.state)
-        .collect::<Vec<Orbit>>())
+    Ok(
+        generate_spacecraft(Spacecraft::from(orbit), parameters, count, kind, seed)?
+            .iter()
+            .map(|sc| sc.orbit)
+            .collect::<Vec<Orbit>>(),
+    )
 }
 
 /// Generates spacecraft from the provided template spacecraft, the parameters to disperse, and whether these are absolute standard deviations or a percentage of the parameter's value.
@@ -88,25 +70,36 @@ fn generate_spacecraft(
     kind: String,
     seed: Option<u64>,
 ) -> Result<Vec<Spacecraft>, NyxError> {
-    let generator = match kind.as_str() {
-        "abs" => GaussianGenerator::from_3std_devs(spacecraft, &parameters)?,
-        "prct" => GaussianGenerator::from_3std_dev_prcts(spacecraft, &parameters)?,
-        _ => {
-            return Err(NyxError::CustomError {
-                msg: format!(
-                    "Unknown kind of distribution: {} (should be 'abs' or 'prct')",
-                    kind
-                ),
-            })
-        }
-    };
+    let mut dispersions = Vec::with_capacity(parameters.len());
+    for (param, std_dev) in parameters {
+        let std_dev = match kind.as_str() {
+            "abs" => std_dev,
+            "prct" => {
+                std_dev
+                    * spacecraft
+                        .value(param)
+                        .map_err(|e| NyxError::MonteCarlo { msg: e.to_string() })?
+            }
+            _ => {
+                return Err(NyxError::CustomError {
+                    msg: format!(
+                        "Unknown kind of distribution: {} (should be 'abs' or 'prct')",
+                        kind
+                    ),
+                })
+            }
+        };
+        dispersions.push(StateDispersion::zero_mean(param, std_dev));
+    }
+
+    let generator = MultivariateNormal::new(spacecraft, dispersions)
+        .map_err(|e| NyxError::MonteCarlo { msg: e.to_string() })?;
 
     let rng = match seed {
         Some(seed) => Pcg64Mcg::new(seed.into()),
         None => Pcg64Mcg::from_entropy(),
     };
 
-    // Generate multithreaded
     Ok(generator
         .sample_iter(rng)
         .take(count)
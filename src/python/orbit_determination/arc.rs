@@ -16,30 +16,27 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use crate::cosmic::Cosm;
 use crate::io::trajectory_data::TrajectoryLoader;
 use crate::io::ExportCfg;
 use crate::od::msr::RangeDoppler;
 use crate::od::simulator::TrackingArcSim;
 pub use crate::od::simulator::TrkConfig;
 pub use crate::{io::ConfigError, od::prelude::GroundStation};
-use crate::{NyxError, Orbit, Spacecraft};
-use either::Either;
+use crate::{NyxError, Spacecraft};
+use anise::almanac::Almanac;
 use pyo3::prelude::*;
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 #[derive(Clone)]
 #[pyclass]
 pub struct GroundTrackingArcSim {
-    inner: Either<
-        TrackingArcSim<Spacecraft, RangeDoppler, GroundStation>,
-        TrackingArcSim<Orbit, RangeDoppler, GroundStation>,
-    >,
+    inner: TrackingArcSim<Spacecraft, RangeDoppler, GroundStation>,
 }
 
 #[pymethods]
 impl GroundTrackingArcSim {
-    /// Initializes a new tracking arc simulation from the provided devices, trajectory, and the random number generator seed.
+    /// Initializes a new tracking arc simulation from the provided devices, spacecraft trajectory, and the random number generator seed.
     #[new]
     pub fn with_seed(
         devices: Vec<GroundStation>,
@@ -48,20 +45,14 @@ impl GroundTrackingArcSim {
         seed: u64,
     ) -> Result<Self, ConfigError> {
         // Try to convert the dynamic trajectory into a trajectory
-        let inner = if let Ok(sc_traj) = trajectory.to_traj::<Spacecraft>() {
-            let inner = TrackingArcSim::with_seed(devices, sc_traj, configs, seed)?;
+        let sc_traj =
+            trajectory
+                .to_traj::<Spacecraft>()
+                .map_err(|e| ConfigError::InvalidConfig {
+                    msg: format!("trajectory could not be parsed as a spacecraft trajectory: {e}"),
+                })?;
 
-            Either::Left(inner)
-        } else if let Ok(traj) = trajectory.to_traj::<Orbit>() {
-            let inner = TrackingArcSim::with_seed(devices, traj, configs, seed)?;
-
-            Either::Right(inner)
-        } else {
-            return Err(ConfigError::InvalidConfig {
-                msg: "Trajectory could neither be parsed as an orbit nor spacecraft trajectory"
-                    .to_string(),
-            });
-        };
+        let inner = TrackingArcSim::with_seed(devices, sc_traj, configs, seed)?;
 
         Ok(Self { inner })
     }
@@ -72,12 +63,9 @@ impl GroundTrackingArcSim {
         &mut self,
         path: String,
         export_cfg: ExportCfg,
+        almanac: Almanac,
     ) -> Result<String, NyxError> {
-        let cosm = Cosm::de438();
-        let arc = match &mut self.inner {
-            Either::Left(arc_sim) => arc_sim.generate_measurements(cosm)?,
-            Either::Right(arc_sim) => arc_sim.generate_measurements(cosm)?,
-        };
+        let arc = self.inner.generate_measurements(Arc::new(almanac))?;
 
         // Save the tracking arc
         let maybe = arc.to_parquet(path, export_cfg);
@@ -89,21 +77,16 @@ impl GroundTrackingArcSim {
     }
 
     /// Generates a tracking schedule
-    pub fn generate_schedule(&self) -> Result<BTreeMap<String, TrkConfig>, NyxError> {
-        let cosm = Cosm::de438();
-        match &self.inner {
-            Either::Left(arc_sim) => arc_sim.generate_schedule(cosm),
-            Either::Right(arc_sim) => arc_sim.generate_schedule(cosm),
-        }
+    pub fn generate_schedule(
+        &self,
+        almanac: Almanac,
+    ) -> Result<BTreeMap<String, TrkConfig>, NyxError> {
+        self.inner.generate_schedule(Arc::new(almanac))
     }
 
     /// Builds a tracking schedule by generating it and storing it in this object.
-    pub fn build_schedule(&mut self) -> Result<(), NyxError> {
-        let cosm = Cosm::de438();
-        match &mut self.inner {
-            Either::Left(arc_sim) => arc_sim.build_schedule(cosm),
-            Either::Right(arc_sim) => arc_sim.build_schedule(cosm),
-        }
+    pub fn build_schedule(&mut self, almanac: Almanac) -> Result<(), NyxError> {
+        self.inner.build_schedule(Arc::new(almanac))
     }
 
     pub fn __repr__(&self) -> String {
@@ -16,16 +16,15 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::collections::BTreeMap;
 
+use crate::linalg::{Matrix6, OMatrix};
 use crate::python::PythonError;
 use crate::{
-    cosmic::Cosm,
-    io::{estimate::OrbitEstimateSerde, ConfigRepr, Configurable},
+    io::{estimate::OrbitEstimateSerde, ConfigRepr},
     od::estimate::KfEstimate,
-    NyxError, Orbit,
+    NyxError, Orbit, Spacecraft, State,
 };
-use nalgebra::Matrix6;
 use numpy::{PyReadonlyArrayDyn, PyUntypedArrayMethods};
 use pyo3::class::basic::CompareOp;
 use pyo3::prelude::*;
@@ -37,23 +36,29 @@ use super::ConfigError;
 /// An estimate of an orbit with its covariance, the latter should be a numpy array of size 36.
 #[derive(Debug, Clone, PartialEq)]
 #[pyclass]
-pub(crate) struct OrbitEstimate(pub(crate) KfEstimate<Orbit>);
-
-impl Configurable for OrbitEstimate {
-    type IntermediateRepr = OrbitEstimateSerde;
-
-    fn from_config(cfg: Self::IntermediateRepr, _almanac: Arc<Almanac>) -> Result<Self, ConfigError>
-    where
-        Self: Sized,
-    {
-        Ok(Self(KfEstimate::from_covar(
-            Orbit::from(cfg.nominal),
-            cfg.covar.to_matrix(),
-        )))
+pub(crate) struct OrbitEstimate {
+    pub(crate) nominal: Orbit,
+    pub(crate) covar: Matrix6<f64>,
+}
+
+impl OrbitEstimate {
+    /// Builds the estimate of the provided spacecraft, whose orbit is replaced by the nominal orbit of this estimate.
+    /// The covariance of the other parameters of the spacecraft (Cr, Cd, fuel mass) is zero.
+    pub(crate) fn to_spacecraft_estimate(&self, spacecraft: Spacecraft) -> KfEstimate<Spacecraft> {
+        let mut covar =
+            OMatrix::<f64, <Spacecraft as State>::Size, <Spacecraft as State>::Size>::zeros();
+        covar.fixed_view_mut::<6, 6>(0, 0).copy_from(&self.covar);
+
+        KfEstimate::from_covar(spacecraft.with_orbit(self.nominal), covar)
     }
+}
 
-    fn to_config(&self) -> Result<Self::IntermediateRepr, ConfigError> {
-        todo!()
+impl From<OrbitEstimateSerde> for OrbitEstimate {
+    fn from(serde: OrbitEstimateSerde) -> Self {
+        Self {
+            nominal: serde.nominal,
+            covar: serde.covar.to_matrix(),
+        }
     }
 }
 
@@ -82,82 +87,61 @@ impl OrbitEstimate {
                 })
             }
         };
-        Ok(Self(KfEstimate::from_covar(nominal, mat6)))
+        Ok(Self {
+            nominal,
+            covar: mat6,
+        })
     }
 
     #[classmethod]
     fn load(_cls: &PyType, path: &str) -> Result<Self, ConfigError> {
-        let serde = OrbitEstimateSerde::load(path)?;
-
-        let cosm = Cosm::de438();
-
-        Self::from_config(serde, cosm)
+        Ok(OrbitEstimateSerde::load(path)?.into())
     }
 
     #[classmethod]
     fn load_many(_cls: &PyType, path: &str) -> Result<Vec<Self>, ConfigError> {
-        let stations = OrbitEstimateSerde::load_many(path)?;
-
-        let cosm = Cosm::de438();
-
-        let mut selves = Vec::with_capacity(stations.len());
-
-        for serde in stations {
-            selves.push(Self::from_config(serde, cosm.clone())?);
-        }
-
-        Ok(selves)
+        Ok(OrbitEstimateSerde::load_many(path)?
+            .into_iter()
+            .map(Self::from)
+            .collect())
     }
 
     #[classmethod]
     fn load_named(_cls: &PyType, path: &str) -> Result<BTreeMap<String, Self>, ConfigError> {
-        let orbits = OrbitEstimateSerde::load_named(path)?;
-
-        let cosm = Cosm::de438();
-
-        let mut selves = BTreeMap::new();
-
-        for (k, v) in orbits {
-            selves.insert(k, Self::from_config(v, cosm.clone())?);
-        }
-
-        Ok(selves)
+        Ok(OrbitEstimateSerde::load_named(path)?
+            .into_iter()
+            .map(|(k, v)| (k, v.into()))
+            .collect())
     }
 
     fn __richcmp__(&self, other: &Self, op: CompareOp) -> Result<bool, PythonError> {
         match op {
-            CompareOp::Eq => Ok(self.__repr__() == other.__repr__()),
-            CompareOp::Ne => Ok(self.__repr__() != other.__repr__()),
+            CompareOp::Eq => Ok(self == other),
+            CompareOp::Ne => Ok(self != other),
             _ => Err(PythonError::OperationError { op }),
         }
     }
 
     #[classmethod]
-    /// Loads the SpacecraftDynamics from its YAML representation
+    /// Loads the OrbitEstimate from its YAML representation
     fn loads(_cls: &PyType, state: &PyAny) -> Result<Self, ConfigError> {
-        <Self as Configurable>::from_config(
-            depythonize(state).map_err(|e| ConfigError::InvalidConfig { msg: e.to_string() })?,
-            Cosm::de438(),
-        )
+        let serde: OrbitEstimateSerde =
+            depythonize(state).map_err(|e| ConfigError::InvalidConfig { msg: e.to_string() })?;
+        Ok(serde.into())
     }
 
     // Manual getter/setters -- waiting on https://github.com/PyO3/pyo3/pull/2786
 
     #[getter]
     fn get_orbit(&self) -> PyResult<Orbit> {
-        Ok(self.0.nominal_state)
-    }
-
-    #[getter]
-    fn get_is_predicted(&self) -> PyResult<bool> {
-        Ok(self.0.predicted)
+        Ok(self.nominal)
     }
 
     fn __str__(&self) -> String {
-        format!("{}", self.0)
+        format!("{}\n{:.6e}", self.nominal, self.covar)
     }
 
     fn __repr__(&self) -> String {
-        format!("{:?}", self.0)
+        format!("{self:?}")
     }
 }
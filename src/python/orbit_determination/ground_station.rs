@@ -17,18 +17,21 @@
 */
 
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
-use crate::cosmic::{Cosm, Orbit};
 use crate::io::{ConfigRepr, ParseSnafu};
 use crate::od::simulator::TrackingDeviceSim;
 pub use crate::od::simulator::TrkConfig;
-use crate::od::ODError;
+use crate::od::{ODAlmanacSnafu, ODError};
 use crate::python::PythonError;
 use crate::time::Duration;
-use crate::NyxError;
-pub use crate::{io::ConfigError, od::noise::GaussMarkov, od::prelude::GroundStation};
+pub use crate::{
+    io::ConfigError,
+    od::prelude::{GroundStation, TimeTag},
+};
+use crate::{NyxError, Spacecraft};
+use anise::prelude::{Almanac, Frame, Orbit};
 
-use crate::python::cosmic::Cosm as CosmPy;
 use crate::python::pyo3utils::pyany_to_value;
 
 use pyo3::class::basic::CompareOp;
@@ -39,6 +42,7 @@ use snafu::ResultExt;
 
 #[pymethods]
 impl GroundStation {
+    /// Initializes a noiseless ground station: the noise models are set by loading the station from its configuration.
     #[new]
     fn new(
         name: String,
@@ -46,61 +50,37 @@ impl GroundStation {
         latitude_deg: f64,
         longitude_deg: f64,
         height_km: f64,
-        frame: String,
+        frame: Frame,
         light_time_correction: bool,
         integration_time: Option<Duration>,
-        timestamp_noise_s: Option<GaussMarkov>,
-        range_noise_km: Option<GaussMarkov>,
-        doppler_noise_km_s: Option<GaussMarkov>,
-    ) -> Result<Self, NyxError> {
-        let cosm = Cosm::de438();
-        let frame_obj = cosm.try_frame(&frame)?;
-        Ok(Self {
+    ) -> Self {
+        Self {
             name,
             elevation_mask_deg,
             latitude_deg,
             longitude_deg,
             height_km,
-            frame: frame_obj,
+            frame,
             integration_time,
             light_time_correction,
-            timestamp_noise_s,
-            range_noise_km,
-            doppler_noise_km_s,
-        })
+            time_tag: TimeTag::default(),
+            timestamp_noise_s: None,
+            range_noise_km: None,
+            doppler_noise_km_s: None,
+        }
     }
 
-    fn __getnewargs__(
-        &self,
-    ) -> Result<
+    fn __getnewargs__(&self) -> (String, f64, f64, f64, f64, Frame, bool, Option<Duration>) {
         (
-            String,
-            f64,
-            f64,
-            f64,
-            f64,
-            String,
-            bool,
-            Option<Duration>,
-            Option<GaussMarkov>,
-            Option<GaussMarkov>,
-            Option<GaussMarkov>,
-        ),
-        NyxError,
-    > {
-        Ok((
             self.name.clone(),
             self.elevation_mask_deg,
             self.latitude_deg,
             self.longitude_deg,
             self.height_km,
-            self.frame.to_string(),
+            self.frame,
             self.light_time_correction,
             self.integration_time,
-            self.timestamp_noise_s,
-            self.range_noise_km,
-            self.doppler_noise_km_s,
-        ))
+        )
     }
 
     #[classmethod]
@@ -180,20 +160,32 @@ impl GroundStation {
         pythonize(py, &self).map_err(|e| NyxError::CustomError { msg: e.to_string() })
     }
 
-    /// Perform a one-way measurement of the given orbit at the epoch stored in that orbit instance.
+    /// Perform a one-way measurement of the given spacecraft at the epoch stored in that spacecraft instance.
     /// Returns the range in kilometers and the Doppler measurement in kilometers per second.
-    fn measure(&mut self, orbit: Orbit) -> Result<Option<(f64, f64)>, ODError> {
-        match self.measure_instantaneous(orbit, None, Cosm::de438())? {
+    fn measure(
+        &mut self,
+        spacecraft: Spacecraft,
+        almanac: Almanac,
+    ) -> Result<Option<(f64, f64)>, ODError> {
+        match self.measure_instantaneous(spacecraft, None, Arc::new(almanac))? {
             Some(msr) => Ok(Some((msr.obs[0], msr.obs[1]))),
             None => Ok(None),
         }
     }
 
     /// Computes the azimuth and elevation of the provided object seen from this ground station, both in degrees.
-    fn compute_azimuth_elevation(&self, receiver: Orbit, cosm: &CosmPy) -> (f64, f64) {
-        let (az_deg, el_deg, _, _) = self.azimuth_elevation_of(receiver, &cosm.inner);
+    fn compute_azimuth_elevation(
+        &self,
+        receiver: Orbit,
+        almanac: &Almanac,
+    ) -> Result<(f64, f64), ODError> {
+        let aer = self
+            .azimuth_elevation_of(receiver, almanac)
+            .context(ODAlmanacSnafu {
+                action: "computing the azimuth and elevation",
+            })?;
 
-        (az_deg, el_deg)
+        Ok((aer.azimuth_deg, aer.elevation_deg))
     }
 
     // Manual getter/setters -- waiting on https://github.com/PyO3/pyo3/pull/2786
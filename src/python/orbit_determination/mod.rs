@@ -19,7 +19,7 @@
 use crate::io::tracking_data::DynamicTrackingArc;
 use crate::io::ExportCfg;
 use crate::od::noise::GaussMarkov;
-use crate::od::process::{FltResid, IterationConf};
+use crate::od::process::{IterationConf, ResidRejectCrit};
 pub use crate::od::simulator::{Scheduler, Strand, TrkConfig};
 pub use crate::{io::ConfigError, od::prelude::GroundStation};
 use pyo3::{prelude::*, py_run};
@@ -44,7 +44,7 @@ pub(crate) fn register_od(py: Python<'_>, parent_module: &PyModule) -> PyResult<
     sm.add_class::<Strand>()?;
     sm.add_class::<OrbitEstimate>()?;
     sm.add_class::<GaussMarkov>()?;
-    sm.add_class::<FltResid>()?;
+    sm.add_class::<ResidRejectCrit>()?;
     sm.add_class::<IterationConf>()?;
     sm.add_class::<ExportCfg>()?;
     sm.add_function(wrap_pyfunction!(process_tracking_arc, sm)?)?;
@@ -16,18 +16,21 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use std::sync::Arc;
+
+use anise::almanac::Almanac;
 use hifitime::{Duration, Epoch};
-use nalgebra::Matrix2;
 use pyo3::prelude::*;
 use snafu::ResultExt;
 
 use crate::{
     io::tracking_data::DynamicTrackingArc,
     io::ExportCfg,
-    md::prelude::{Cosm, Propagator, SpacecraftDynamics},
+    md::prelude::{Propagator, SpacecraftDynamics},
     od::{
         filter::kalman::KF,
-        process::{EkfTrigger, FltResid, IterationConf, ODIOSnafu, ODProcess},
+        msr::RangeDoppler,
+        process::{EkfTrigger, IterationConf, ODIOSnafu, ODProcess, ResidRejectCrit},
         snc::SNC3,
         ODError,
     },
@@ -37,18 +40,19 @@ use crate::{
 use super::{estimate::OrbitEstimate, ConfigError, GroundStation};
 
 /// Runs an orbit determination process and returns the path to those results.
+/// The measurement noise is that of the ground stations used to build the tracking arc.
 #[pyfunction]
 pub(crate) fn process_tracking_arc(
     dynamics: SpacecraftDynamics,
     spacecraft: Spacecraft,
     initial_estimate: OrbitEstimate,
-    measurement_noise: Vec<f64>,
     arc: &DynamicTrackingArc,
     export_path: String,
+    almanac: Almanac,
     export_cfg: Option<ExportCfg>,
     ekf_num_meas: Option<usize>,
     ekf_disable_time: Option<Duration>,
-    resid_crit: Option<FltResid>,
+    resid_crit: Option<ResidRejectCrit>,
     predict_until: Option<Epoch>,
     predict_for: Option<Duration>,
     predict_step: Option<Duration>,
@@ -56,9 +60,8 @@ pub(crate) fn process_tracking_arc(
     snc_disable_time: Option<Duration>,
     snc_diagonals: Option<Vec<f64>>,
 ) -> Result<String, ODError> {
-    let msr_noise = Matrix2::from_iterator(measurement_noise);
-
-    let init_sc = spacecraft.with_orbit(initial_estimate.0.nominal_state.with_stm());
+    let init_estimate = initial_estimate.to_spacecraft_estimate(spacecraft);
+    let init_sc = init_estimate.nominal_state.with_stm();
 
     // Build KF without SNC
     let kf = if (snc_disable_time.is_some() && snc_diagonals.as_ref().is_none())
@@ -73,9 +76,9 @@ pub(crate) fn process_tracking_arc(
         });
     } else if snc_disable_time.is_some() && snc_diagonals.is_some() {
         let snc = SNC3::from_diagonal(snc_disable_time.unwrap(), &snc_diagonals.unwrap());
-        KF::new(initial_estimate.0, snc, msr_noise)
+        KF::new(init_estimate, snc)
     } else {
-        KF::no_snc(initial_estimate.0, msr_noise)
+        KF::no_snc(init_estimate)
     };
 
     let prop = Propagator::default(dynamics);
@@ -97,9 +100,9 @@ pub(crate) fn process_tracking_arc(
         None => None,
     };
 
-    let mut odp = ODProcess::new(prop_est, kf, trigger, resid_crit, Cosm::de438());
+    let mut odp = ODProcess::new(prop_est, kf, trigger, resid_crit, Arc::new(almanac));
 
-    let concrete_arc = arc.to_tracking_arc().context(ODIOSnafu)?;
+    let concrete_arc = arc.to_tracking_arc::<RangeDoppler>().context(ODIOSnafu)?;
 
     odp.process_arc::<GroundStation>(&concrete_arc)?;
 
@@ -139,17 +142,17 @@ pub(crate) fn predictor(
     initial_estimate: OrbitEstimate,
     step: Duration,
     export_path: String,
+    almanac: Almanac,
     export_cfg: Option<ExportCfg>,
     predict_until: Option<Epoch>,
     predict_for: Option<Duration>,
 ) -> Result<String, ODError> {
     // TODO: Return a navigation trajectory or use a class that mimics the better ODProcess -- https://github.com/nyx-space/nyx/issues/134
-    let msr_noise = Matrix2::from_iterator(vec![1e-10, 0.0, 0.0, 1e-10]);
-
-    let init_sc = spacecraft.with_orbit(initial_estimate.0.nominal_state.with_stm());
+    let init_estimate = initial_estimate.to_spacecraft_estimate(spacecraft);
+    let init_sc = init_estimate.nominal_state.with_stm();
 
     // Build KF without SNC
-    let kf = KF::no_snc(initial_estimate.0, msr_noise);
+    let kf = KF::no_snc(init_estimate);
 
     let prop = Propagator::default(dynamics);
     let prop_est = prop.with(init_sc);
@@ -164,7 +167,8 @@ pub(crate) fn predictor(
         });
     }
 
-    let mut odp = ODProcess::ckf(prop_est, kf, None, Cosm::de438());
+    let mut odp =
+        ODProcess::<_, _, RangeDoppler, _, _, _>::ckf(prop_est, kf, None, Arc::new(almanac));
 
     if let Some(epoch) = predict_until {
         odp.predict_until(step, epoch)?;
@@ -18,6 +18,7 @@
 
 use crate::errors::NyxError;
 use crate::linalg::Vector3;
+use crate::time::Duration;
 use std::f64::consts::PI;
 
 const TAU: f64 = 2.0 * PI;
@@ -27,6 +28,8 @@ const LAMBERT_EPSILON_RAD: f64 = (5e-5 / 180.0) * PI; // 0.00005 degrees
 /// Maximum number of iterations allowed in the Lambert problem solver.
 /// This is a safety measure to prevent infinite loops in case a solution cannot be found.
 const MAX_ITERATIONS: usize = 1000;
+/// Absolute and relative tolerance on the Izzo free parameter `x`.
+const IZZO_TOLERANCE: f64 = 1e-12;
 
 /// Define the transfer kind for a Lambert
 pub enum TransferKind {
@@ -190,6 +193,271 @@ pub fn standard(
     })
 }
 
+/// Direction of motion of a Lambert transfer, with respect to the Z axis of the frame of the radius vectors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransferDirection {
+    /// The angular momentum of the transfer has a positive (or zero) Z component, e.g. counterclockwise in the XY plane.
+    Prograde,
+    /// The angular momentum of the transfer has a negative Z component.
+    Retrograde,
+}
+
+/// Solve the Lambert boundary problem using Izzo's algorithm (2015, "Revisiting Lambert's problem").
+///
+/// Unlike [standard], this supports transfers with `nrevs` complete revolutions. For a given number of revolutions, there are
+/// two solutions: `low_path` selects the one with the lower semi-major axis, and is ignored for single revolution transfers.
+///
+/// When both radii are collinear, the transfer plane is undefined: the plane containing the initial radius and closest to the
+/// Z axis is used.
+///
+/// # Errors
+///
+/// + `TargetsTooClose` if both radii are the same;
+/// + `LambertNotFeasible` if the time of flight or the gravitational parameter is not positive, or if the time of flight is too short for the requested number of revolutions;
+/// + `MaxIterReached` if the solver did not converge.
+pub fn izzo(
+    r_init: Vector3<f64>,
+    r_final: Vector3<f64>,
+    tof: Duration,
+    gm: f64,
+    direction: TransferDirection,
+    nrevs: u8,
+    low_path: bool,
+) -> Result<LambertSolution, NyxError> {
+    let tof_s = tof.to_seconds();
+    if tof_s <= 0.0 || gm <= 0.0 {
+        return Err(NyxError::LambertNotFeasible {
+            msg: format!(
+                "time of flight ({tof}) and gravitational parameter ({gm}) must be positive"
+            ),
+        });
+    }
+
+    let r_init_norm = r_init.norm();
+    let r_final_norm = r_final.norm();
+    let chord = (r_final - r_init).norm();
+    if r_init_norm < f64::EPSILON || r_final_norm < f64::EPSILON {
+        return Err(NyxError::LambertNotFeasible {
+            msg: "radii must not be zero".to_string(),
+        });
+    }
+    if chord < f64::EPSILON.sqrt() * r_init_norm.max(r_final_norm) {
+        return Err(NyxError::TargetsTooClose);
+    }
+
+    // Semi perimeter of the triangle formed by both radii
+    let semi_p = (r_init_norm + r_final_norm + chord) / 2.0;
+
+    let i_r1 = r_init / r_init_norm;
+    let i_r2 = r_final / r_final_norm;
+    let mut i_h = i_r1.cross(&i_r2);
+    if i_h.norm() < f64::EPSILON.sqrt() {
+        // Collinear radii: use the plane containing the initial radius which is closest to the Z axis.
+        let axis = if i_r1.z.abs() < 1.0 - f64::EPSILON.sqrt() {
+            Vector3::z()
+        } else {
+            Vector3::y()
+        };
+        i_h = axis - axis.dot(&i_r1) * i_r1;
+    }
+    i_h /= i_h.norm();
+
+    let mut lambda = (1.0 - (chord / semi_p).min(1.0)).sqrt();
+    let (mut i_t1, mut i_t2) = if i_h.z < 0.0 {
+        // The transfer angle is greater than 180 degrees in the prograde direction.
+        lambda = -lambda;
+        (i_r1.cross(&i_h), i_r2.cross(&i_h))
+    } else {
+        (i_h.cross(&i_r1), i_h.cross(&i_r2))
+    };
+
+    if direction == TransferDirection::Retrograde {
+        lambda = -lambda;
+        i_t1 = -i_t1;
+        i_t2 = -i_t2;
+    }
+
+    // Non dimensional time of flight
+    let tof_nd = (2.0 * gm / semi_p.powi(3)).sqrt() * tof_s;
+
+    let x = izzo_find_x(lambda, tof_nd, nrevs, low_path)?;
+    let y = izzo_y(x, lambda);
+
+    // Reconstruct the terminal velocities
+    let gamma = (gm * semi_p / 2.0).sqrt();
+    let rho = (r_init_norm - r_final_norm) / chord;
+    let sigma = (1.0 - rho.powi(2)).max(0.0).sqrt();
+
+    let v_r1 = gamma * ((lambda * y - x) - rho * (lambda * y + x)) / r_init_norm;
+    let v_r2 = -gamma * ((lambda * y - x) + rho * (lambda * y + x)) / r_final_norm;
+    let v_t1 = gamma * sigma * (y + lambda * x) / r_init_norm;
+    let v_t2 = gamma * sigma * (y + lambda * x) / r_final_norm;
+
+    // The change in eccentric (or hyperbolic) anomaly is twice psi, plus the complete revolutions.
+    let psi = izzo_psi(x, y, lambda);
+    let phi = if x < 1.0 {
+        (2.0 * (psi + f64::from(nrevs) * PI)).powi(2)
+    } else {
+        -(2.0 * psi).powi(2)
+    };
+
+    Ok(LambertSolution {
+        v_init: v_r1 * i_r1 + v_t1 * i_t1,
+        v_final: v_r2 * i_r2 + v_t2 * i_t2,
+        phi,
+    })
+}
+
+/// Finds the free parameter `x` of the Izzo formulation matching the non dimensional time of flight.
+fn izzo_find_x(lambda: f64, tof_nd: f64, nrevs: u8, low_path: bool) -> Result<f64, NyxError> {
+    let nrevs_f64 = f64::from(nrevs);
+    let mut max_revs = (tof_nd / PI).floor();
+    let tof_00 = lambda.acos() + lambda * (1.0 - lambda.powi(2)).sqrt();
+
+    if max_revs > 0.0 && tof_nd < tof_00 + max_revs * PI {
+        // Refine the maximum number of revolutions with the minimum time of flight of that many revolutions.
+        let x_min = izzo_halley_tof_min(lambda, max_revs)?;
+        if tof_nd < izzo_tof(x_min, lambda, max_revs) {
+            max_revs -= 1.0;
+        }
+    }
+
+    if nrevs_f64 > max_revs {
+        return Err(NyxError::LambertNotFeasible {
+            msg: format!("time of flight is too short for {nrevs} revolution(s)"),
+        });
+    }
+
+    // Initial guess
+    let x_0 = if nrevs == 0 {
+        let tof_1 = 2.0 * (1.0 - lambda.powi(3)) / 3.0;
+        if tof_nd >= tof_00 {
+            (tof_00 / tof_nd).powf(2.0 / 3.0) - 1.0
+        } else if tof_nd < tof_1 {
+            2.5 * tof_1 / tof_nd * (tof_1 - tof_nd) / (1.0 - lambda.powi(5)) + 1.0
+        } else {
+            // Interpolates between x = 0 at tof_00 and x = 1 at tof_1
+            (tof_00 / tof_nd).powf(2.0_f64.ln() / (tof_00 / tof_1).ln()) - 1.0
+        }
+    } else {
+        let left = ((nrevs_f64 * PI + PI) / (8.0 * tof_nd)).powf(2.0 / 3.0);
+        let right = ((8.0 * tof_nd) / (nrevs_f64 * PI)).powf(2.0 / 3.0);
+        let x_0l = (left - 1.0) / (left + 1.0);
+        let x_0r = (right - 1.0) / (right + 1.0);
+        if low_path {
+            x_0l.max(x_0r)
+        } else {
+            x_0l.min(x_0r)
+        }
+    };
+
+    // Householder iterations
+    let mut x = x_0;
+    for _ in 0..MAX_ITERATIONS {
+        let y = izzo_y(x, lambda);
+        let tof_x = izzo_tof(x, lambda, nrevs_f64);
+        let f = tof_x - tof_nd;
+        let (d1, d2, d3) = izzo_tof_derivatives(x, y, tof_x, lambda);
+
+        let x_new = x - f
+            * ((d1.powi(2) - f * d2 / 2.0) / (d1 * (d1.powi(2) - f * d2) + d3 * f.powi(2) / 6.0));
+
+        if !x_new.is_finite() {
+            break;
+        }
+        if (x_new - x).abs() < IZZO_TOLERANCE * (1.0 + x.abs()) {
+            return Ok(x_new);
+        }
+        x = x_new;
+    }
+
+    Err(NyxError::MaxIterReached {
+        msg: format!("Izzo Lambert solver failed after {MAX_ITERATIONS} iterations"),
+    })
+}
+
+/// Finds the `x` of the minimum time of flight for the provided number of revolutions using Halley's method.
+fn izzo_halley_tof_min(lambda: f64, nrevs: f64) -> Result<f64, NyxError> {
+    let mut x = 0.1;
+    for _ in 0..MAX_ITERATIONS {
+        let y = izzo_y(x, lambda);
+        let (d1, d2, d3) = izzo_tof_derivatives(x, y, izzo_tof(x, lambda, nrevs), lambda);
+        if d2.abs() < f64::EPSILON {
+            break;
+        }
+        let x_new = x - 2.0 * d1 * d2 / (2.0 * d2.powi(2) - d1 * d3);
+        if !x_new.is_finite() {
+            break;
+        }
+        if (x_new - x).abs() < IZZO_TOLERANCE * (1.0 + x.abs()) {
+            return Ok(x_new);
+        }
+        x = x_new;
+    }
+
+    Err(NyxError::MaxIterReached {
+        msg: "Izzo Lambert solver failed to find the minimum time of flight".to_string(),
+    })
+}
+
+fn izzo_y(x: f64, lambda: f64) -> f64 {
+    (1.0 - lambda.powi(2) * (1.0 - x.powi(2))).sqrt()
+}
+
+fn izzo_psi(x: f64, y: f64, lambda: f64) -> f64 {
+    if x < 1.0 {
+        (x * y + lambda * (1.0 - x.powi(2))).clamp(-1.0, 1.0).acos()
+    } else if x > 1.0 {
+        ((y - x * lambda) * (x.powi(2) - 1.0).sqrt()).asinh()
+    } else {
+        0.0
+    }
+}
+
+/// Non dimensional time of flight for the provided free parameter.
+fn izzo_tof(x: f64, lambda: f64, nrevs: f64) -> f64 {
+    let y = izzo_y(x, lambda);
+    if nrevs == 0.0 && x > 0.6_f64.sqrt() && x < 1.4_f64.sqrt() {
+        // Close to parabolic: use the hypergeometric series (Battin)
+        let eta = y - lambda * x;
+        let s_1 = (1.0 - lambda - x * eta) / 2.0;
+        let q = 4.0 / 3.0 * hypergeometric_2f1b(s_1);
+        (eta.powi(3) * q + 4.0 * lambda * eta) / 2.0
+    } else {
+        let psi = izzo_psi(x, y, lambda);
+        ((psi + nrevs * PI) / (1.0 - x.powi(2)).abs().sqrt() - x + lambda * y) / (1.0 - x.powi(2))
+    }
+}
+
+/// First, second and third derivatives of the non dimensional time of flight with respect to the free parameter.
+fn izzo_tof_derivatives(x: f64, y: f64, tof: f64, lambda: f64) -> (f64, f64, f64) {
+    let one_m_x2 = 1.0 - x.powi(2);
+    let one_m_l2 = 1.0 - lambda.powi(2);
+    let d1 = (3.0 * tof * x - 2.0 + 2.0 * lambda.powi(3) * x / y) / one_m_x2;
+    let d2 = (3.0 * tof + 5.0 * x * d1 + 2.0 * one_m_l2 * lambda.powi(3) / y.powi(3)) / one_m_x2;
+    let d3 = (7.0 * x * d2 + 8.0 * d1 - 6.0 * one_m_l2 * lambda.powi(5) * x / y.powi(5)) / one_m_x2;
+    (d1, d2, d3)
+}
+
+/// Gaussian hypergeometric function 2F1(3, 1, 5/2, x), for x < 1.
+fn hypergeometric_2f1b(x: f64) -> f64 {
+    if x >= 1.0 {
+        return f64::INFINITY;
+    }
+    let mut res = 1.0;
+    let mut term = 1.0;
+    for ii in 0..MAX_ITERATIONS {
+        let iif64 = ii as f64;
+        term *= (3.0 + iif64) * (1.0 + iif64) / (2.5 + iif64) * x / (iif64 + 1.0);
+        let prev = res;
+        res += term;
+        if prev == res {
+            break;
+        }
+    }
+    res
+}
+
 #[test]
 fn test_lambert_vallado_shortway() {
     let ri = Vector3::new(15945.34, 0.0, 0.0);
@@ -221,3 +489,97 @@ fn test_lambert_vallado_lonway() {
     assert!((sol.v_init - exp_vi).norm() < 1e-6);
     assert!((sol.v_final - exp_vf).norm() < 1e-6);
 }
+
+#[test]
+fn test_lambert_izzo_vallado() {
+    use crate::time::Unit;
+
+    let ri = Vector3::new(15945.34, 0.0, 0.0);
+    let rf = Vector3::new(12214.83899, 10249.46731, 0.0);
+    let tof = 76.0 * Unit::Minute;
+    let gm = 3.98600433e5;
+
+    // The short way is prograde here ...
+    let sol = izzo(ri, rf, tof, gm, TransferDirection::Prograde, 0, true).unwrap();
+    assert!((sol.v_init - Vector3::new(2.058913, 2.915965, 0.0)).norm() < 1e-5);
+    assert!((sol.v_final - Vector3::new(-3.451565, 0.910315, 0.0)).norm() < 1e-5);
+
+    // ... and the long way is retrograde.
+    let sol = izzo(ri, rf, tof, gm, TransferDirection::Retrograde, 0, true).unwrap();
+    assert!((sol.v_init - Vector3::new(-3.811158, -2.003854, 0.0)).norm() < 1e-5);
+    assert!((sol.v_final - Vector3::new(4.207569, 0.914724, 0.0)).norm() < 1e-5);
+
+    // Both solvers agree
+    let std_sol = standard(ri, rf, tof.to_seconds(), gm, TransferKind::ShortWay).unwrap();
+    let izzo_sol = izzo(ri, rf, tof, gm, TransferDirection::Prograde, 0, true).unwrap();
+    assert!((std_sol.v_init - izzo_sol.v_init).norm() < 1e-6);
+    assert!(izzo_sol.phi > 0.0);
+}
+
+#[test]
+fn test_lambert_izzo_multi_rev_and_degenerate() {
+    use crate::cosmic::Orbit;
+    use crate::time::{Epoch, Unit};
+    use anise::constants::frames::EARTH_J2000;
+
+    let gm = 3.98600433e5;
+    let frame = EARTH_J2000.with_mu_km3_s2(gm);
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // Checks that the initial state reaches the final radius with the final velocity in two body dynamics.
+    let check = |ri: Vector3<f64>, rf: Vector3<f64>, tof, sol: &LambertSolution| {
+        let init = Orbit::cartesian(
+            ri.x,
+            ri.y,
+            ri.z,
+            sol.v_init.x,
+            sol.v_init.y,
+            sol.v_init.z,
+            epoch,
+            frame,
+        );
+        let arrival = init.at_epoch(epoch + tof).unwrap();
+        assert!(
+            (arrival.radius_km - rf).norm() < 1e-3,
+            "missed by {:e} km",
+            (arrival.radius_km - rf).norm()
+        );
+        assert!((arrival.velocity_km_s - sol.v_final).norm() < 1e-6);
+    };
+
+    let ri = Vector3::new(7000.0, 0.0, 0.0);
+    let rf = Vector3::new(-3000.0, 8000.0, 2000.0);
+    let tof = 10.0 * Unit::Hour;
+
+    // Single and one revolution transfers, in both directions and on both paths
+    for direction in [TransferDirection::Prograde, TransferDirection::Retrograde] {
+        let sol = izzo(ri, rf, tof, gm, direction, 0, true).unwrap();
+        check(ri, rf, tof, &sol);
+        let h = ri.cross(&sol.v_init);
+        assert_eq!(h.z > 0.0, direction == TransferDirection::Prograde);
+
+        let low = izzo(ri, rf, tof, gm, direction, 1, true).unwrap();
+        let high = izzo(ri, rf, tof, gm, direction, 1, false).unwrap();
+        check(ri, rf, tof, &low);
+        check(ri, rf, tof, &high);
+        assert!((low.v_init - high.v_init).norm() > 1e-3);
+    }
+
+    // Too many revolutions for this time of flight
+    assert!(matches!(
+        izzo(ri, rf, tof, gm, TransferDirection::Prograde, 10, true),
+        Err(NyxError::LambertNotFeasible { .. })
+    ));
+    assert!(izzo(ri, rf, -tof, gm, TransferDirection::Prograde, 0, true).is_err());
+    assert!(matches!(
+        izzo(ri, ri, tof, gm, TransferDirection::Prograde, 0, true),
+        Err(NyxError::TargetsTooClose)
+    ));
+
+    // Collinear radii: a 180 degree transfer in the XY plane
+    let rf = Vector3::new(-9000.0, 0.0, 0.0);
+    let tof = 3.0 * Unit::Hour;
+    let sol = izzo(ri, rf, tof, gm, TransferDirection::Prograde, 0, true).unwrap();
+    check(ri, rf, tof, &sol);
+    assert!(sol.v_init.z.abs() < 1e-9);
+}
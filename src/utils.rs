@@ -18,9 +18,11 @@
 
 use crate::cosmic::Orbit;
 use crate::linalg::{
-    allocator::Allocator, DefaultAllocator, DimName, Matrix3, OVector, Vector3, Vector6,
+    allocator::Allocator, DMatrix, DVector, DefaultAllocator, DimName, Matrix3, OVector, Vector3,
+    Vector6,
 };
 use nalgebra::Complex;
+use std::convert::Infallible;
 
 /// Returns the skew-symmetric matrix (also known as the tilde matrix)
 /// corresponding to the provided 3D vector.
@@ -697,6 +699,141 @@ pub fn spherical_to_cartesian(range_ρ: f64, θ: f64, φ: f64) -> Vector3<f64> {
     }
 }
 
+/// Selection of the perturbation step of a finite difference.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FiniteDiffStep {
+    /// The same absolute step is used for all variables.
+    Fixed(f64),
+    /// The step is this fraction of the magnitude of each variable (or of one if the variable is smaller than one).
+    Relative(f64),
+    /// The step balancing the truncation and round-off errors of the scheme, scaled by the magnitude of each variable (or one if smaller):
+    /// the cube root of the machine epsilon for central differences, and its square root for forward differences.
+    Optimal,
+}
+
+/// Finite difference computation of derivatives and Jacobians, with a configurable step and scheme.
+///
+/// Finite differences are only used where analytical or hyperdual partials are not available, e.g. when the partials go
+/// through a propagation or a root finding.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FiniteDiff {
+    /// Perturbation step selection
+    pub step: FiniteDiffStep,
+    /// Whether to use central differences (second order, two evaluations per variable) instead of forward differences
+    /// (first order, one evaluation per variable and one at the nominal point).
+    pub central: bool,
+}
+
+impl FiniteDiff {
+    /// Central differences with the optimal step.
+    pub fn central() -> Self {
+        Self {
+            step: FiniteDiffStep::Optimal,
+            central: true,
+        }
+    }
+
+    /// Forward differences with the optimal step.
+    pub fn forward() -> Self {
+        Self {
+            step: FiniteDiffStep::Optimal,
+            central: false,
+        }
+    }
+
+    /// Sets the perturbation step selection.
+    pub fn with_step(mut self, step: FiniteDiffStep) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Returns the perturbation step for a variable of the provided value.
+    ///
+    /// The step is adjusted such that `x + h` and `x` differ by exactly `h` in floating point arithmetic.
+    pub fn step_for(&self, x: f64) -> f64 {
+        let scale = x.abs().max(1.0);
+        let h = match self.step {
+            FiniteDiffStep::Fixed(h) => h,
+            FiniteDiffStep::Relative(rel) => rel * scale,
+            FiniteDiffStep::Optimal => {
+                if self.central {
+                    f64::EPSILON.cbrt() * scale
+                } else {
+                    f64::EPSILON.sqrt() * scale
+                }
+            }
+        };
+        (x + h) - x
+    }
+
+    /// Computes the derivative of the scalar function at the provided value.
+    pub fn derivative<F: FnMut(f64) -> f64>(&self, mut f: F, x: f64) -> f64 {
+        let h = self.step_for(x);
+        if self.central {
+            (f(x + h) - f(x - h)) / (2.0 * h)
+        } else {
+            (f(x + h) - f(x)) / h
+        }
+    }
+
+    /// Computes the derivative of the scalar function at the provided value, where `f_x` is the already known value of the function at `x`.
+    /// This saves one evaluation of the function with forward differences.
+    pub fn derivative_from<F: FnMut(f64) -> f64>(&self, mut f: F, x: f64, f_x: f64) -> f64 {
+        let h = self.step_for(x);
+        if self.central {
+            (f(x + h) - f(x - h)) / (2.0 * h)
+        } else {
+            (f(x + h) - f_x) / h
+        }
+    }
+
+    /// Computes the Jacobian of the vector function at the provided point, i.e. the matrix whose (i, j) element is the partial of the i-th output with respect to the j-th input.
+    pub fn jacobian<F: FnMut(&DVector<f64>) -> DVector<f64>>(
+        &self,
+        mut f: F,
+        x: &DVector<f64>,
+    ) -> DMatrix<f64> {
+        match self.try_jacobian(|x| Ok::<_, Infallible>(f(x)), x) {
+            Ok(jac) => jac,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Computes the Jacobian of the fallible vector function at the provided point, returning the first error of the function.
+    pub fn try_jacobian<E, F: FnMut(&DVector<f64>) -> Result<DVector<f64>, E>>(
+        &self,
+        mut f: F,
+        x: &DVector<f64>,
+    ) -> Result<DMatrix<f64>, E> {
+        let nominal = if self.central { None } else { Some(f(x)?) };
+
+        let mut columns = Vec::with_capacity(x.len());
+        for j in 0..x.len() {
+            let h = self.step_for(x[j]);
+            let mut x_plus = x.clone();
+            x_plus[j] += h;
+            let column = match &nominal {
+                Some(f_x) => (f(&x_plus)? - f_x) / h,
+                None => {
+                    let mut x_minus = x.clone();
+                    x_minus[j] -= h;
+                    (f(&x_plus)? - f(&x_minus)?) / (2.0 * h)
+                }
+            };
+            columns.push(column);
+        }
+
+        Ok(DMatrix::from_columns(&columns))
+    }
+}
+
+impl Default for FiniteDiff {
+    /// Central differences with the optimal step
+    fn default() -> Self {
+        Self::central()
+    }
+}
+
 #[rustfmt::skip]
 #[test]
 fn test_diagonality() {
@@ -809,3 +946,40 @@ fn spherical() {
         assert!(rss_errors(v, &v_prime) < 1e-12, "{} != {}", v, &v_prime);
     }
 }
+
+#[test]
+fn test_finite_diff_two_body_jacobian() {
+    let mu_km3_s2 = 398_600.441_5;
+    let accel = |r: &DVector<f64>| -> DVector<f64> { -mu_km3_s2 / r.norm().powi(3) * r };
+
+    let r = DVector::from_vec(vec![-2436.45, -2436.45, 6891.037]);
+    let r_norm = r.norm();
+    let analytic = -mu_km3_s2 / r_norm.powi(3)
+        * (DMatrix::identity(3, 3) - 3.0 * &r * r.transpose() / r_norm.powi(2));
+
+    let central = FiniteDiff::default().jacobian(accel, &r);
+    let central_err = (&central - &analytic).norm() / analytic.norm();
+    println!("central: {central_err:e}");
+    assert!(central_err < 1e-8);
+
+    let forward = FiniteDiff::forward().jacobian(accel, &r);
+    let forward_err = (&forward - &analytic).norm() / analytic.norm();
+    println!("forward: {forward_err:e}");
+    assert!(forward_err < 1e-6);
+    assert!(central_err < forward_err);
+
+    // A poor step degrades the accuracy
+    let poor = FiniteDiff::central()
+        .with_step(FiniteDiffStep::Fixed(100.0))
+        .jacobian(accel, &r);
+    assert!((&poor - &analytic).norm() / analytic.norm() > central_err);
+
+    // Relative steps scale with the variable
+    let rel = FiniteDiff::forward().with_step(FiniteDiffStep::Relative(1e-6));
+    assert!((rel.step_for(7000.0) - 7e-3).abs() < 1e-12);
+    assert!((rel.step_for(0.1) - 1e-6).abs() < 1e-16);
+
+    // Scalar derivative
+    let deriv = FiniteDiff::default().derivative(f64::sin, 0.5);
+    assert!((deriv - 0.5_f64.cos()).abs() < 1e-10);
+}
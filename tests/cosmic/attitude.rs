@@ -0,0 +1,56 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_J2000, SUN_J2000};
+use anise::prelude::{Almanac, Orbit};
+use nyx::cosmic::attitude::{from_mode, PointingMode};
+use nyx::linalg::Vector3;
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn nadir_pointing_circular(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+
+    let circular = Orbit::keplerian(7_000.0, 0.0, 28.5, 10.0, 0.0, 0.0, epoch, eme2k);
+
+    for ta_deg in [0.0, 135.0, 290.0] {
+        let orbit = Orbit::keplerian(7_000.0, 0.0, 28.5, 10.0, 0.0, ta_deg, epoch, eme2k);
+        let q = from_mode(orbit, &almanac, PointingMode::Nadir).unwrap();
+
+        // The body +Z axis points to the center of the Earth
+        let z_inertial = q * Vector3::z();
+        let nadir = -orbit.radius_km.normalize();
+        println!("{q}\n+Z = {z_inertial}\tnadir = {nadir}");
+        assert!((z_inertial - nadir).norm() < 1e-12);
+
+        // In a circular orbit, the body +X axis is along the velocity, and +Y is opposite to the orbit normal.
+        let x_inertial = q * Vector3::x();
+        assert!((x_inertial - orbit.velocity_km_s.normalize()).norm() < 1e-12);
+        let y_inertial = q * Vector3::y();
+        let normal = orbit.radius_km.cross(&orbit.velocity_km_s).normalize();
+        assert!((y_inertial + normal).norm() < 1e-12);
+    }
+
+    // Velocity aligned
+    let q = from_mode(circular, &almanac, PointingMode::VelocityAligned).unwrap();
+    assert!((q * Vector3::z() - circular.velocity_km_s.normalize()).norm() < 1e-12);
+    assert!((q * Vector3::x() + circular.radius_km.normalize()).norm() < 1e-12);
+
+    // Sun pointing
+    let q = from_mode(circular, &almanac, PointingMode::SunPointing).unwrap();
+    let sun = almanac.transform_to(circular, SUN_J2000, None).unwrap();
+    let sun_dir = -sun.radius_km.normalize();
+    assert!((q * Vector3::z() - sun_dir).norm() < 1e-12);
+    // The +X axis is perpendicular to the Sun direction, on the side of the nadir.
+    let x_inertial = q * Vector3::x();
+    assert!(x_inertial.dot(&sun_dir).abs() < 1e-12);
+    assert!(x_inertial.dot(&-circular.radius_km) >= 0.0);
+}
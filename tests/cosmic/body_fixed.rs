@@ -0,0 +1,103 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_ITRF93, EARTH_J2000, IAU_MARS_FRAME};
+use anise::constants::orientations::J2000;
+use anise::prelude::{Almanac, Frame, Orbit};
+use nyx::linalg::Matrix3;
+use nyx::time::{Epoch, Unit};
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn mars_fixed_round_trip(almanac: Arc<Almanac>) {
+    let iau_mars = almanac.frame_from_uid(IAU_MARS_FRAME).unwrap();
+    // Inertial frame centered on Mars, such that only the IAU rotation model is involved in the transformations
+    let mars_j2k = Frame::new(iau_mars.ephemeris_id, J2000);
+
+    // Olympus Mons
+    let (lat_deg, long_deg, height_km) = (18.65, 226.2, 0.0);
+    // Rotation rate of the prime meridian of the IAU model, in degrees per day
+    let w_rate_deg_day = 350.891_982_26;
+    let sidereal_day = (360.0 / w_rate_deg_day) * Unit::Day;
+
+    let start = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+    for day in 0..5 {
+        let epoch = start + day * Unit::Day;
+        let fixed =
+            Orbit::try_latlongalt(lat_deg, long_deg, height_km, 0.0, epoch, iau_mars).unwrap();
+
+        let inertial = almanac.transform_to(fixed, mars_j2k, None).unwrap();
+        let back = almanac.transform_to(inertial, iau_mars, None).unwrap();
+        assert!(
+            (back.radius_km - fixed.radius_km).norm() < 1e-6,
+            "round trip failed on {epoch}"
+        );
+
+        // The surface point tracks the rotation of Mars: back to the same inertial position after a sidereal day ...
+        let fixed_next = Orbit::try_latlongalt(
+            lat_deg,
+            long_deg,
+            height_km,
+            0.0,
+            epoch + sidereal_day,
+            iau_mars,
+        )
+        .unwrap();
+        let inertial_next = almanac.transform_to(fixed_next, mars_j2k, None).unwrap();
+        let err_km = (inertial_next.radius_km - inertial.radius_km).norm();
+        println!("{epoch}: inertial position after a sidereal day off by {err_km:.3e} km");
+        assert!(err_km < 0.1);
+
+        // ... and on the other side of the rotation axis after half a sidereal day
+        let fixed_half = Orbit::try_latlongalt(
+            lat_deg,
+            long_deg,
+            height_km,
+            0.0,
+            epoch + 0.5 * sidereal_day,
+            iau_mars,
+        )
+        .unwrap();
+        let inertial_half = almanac.transform_to(fixed_half, mars_j2k, None).unwrap();
+        let axis_dist_km = fixed.radius_km.xy().norm();
+        assert!(
+            ((inertial_half.radius_km - inertial.radius_km).norm() - 2.0 * axis_dist_km).abs()
+                < 0.1
+        );
+    }
+}
+
+#[rstest]
+fn eme2000_to_itrf93_sofa(almanac: Arc<Almanac>) {
+    // Worked example of the SOFA Earth attitude cookbook, on 2007 April 05 at 12:00 UTC: EME2000 to ITRS matrix of the
+    // IAU 1976/1980 equinox based chain, including the celestial pole offsets and the polar motion of that day.
+    let epoch = Epoch::from_gregorian_utc_at_noon(2007, 4, 5);
+    let sofa = Matrix3::new(
+        0.973_104_317_712_772,
+        0.230_363_826_174_782,
+        -0.000_703_163_477_127,
+        -0.230_363_800_391_868,
+        0.973_104_570_648_022,
+        0.000_118_545_116_892,
+        0.000_711_560_100_206,
+        0.000_046_626_645_796,
+        0.999_999_745_754_058,
+    );
+
+    // The rotation is evaluated from the high precision Earth orientation kernel.
+    let dcm = almanac
+        .rotate_from_to(EARTH_J2000, EARTH_ITRF93, epoch)
+        .unwrap()
+        .rot_mat;
+
+    let err_arcsec = (dcm - sofa).abs().max().to_degrees() * 3600.0;
+    println!("EME2000 to ITRF93 differs from SOFA by {err_arcsec:.4} arcsec");
+    // The differences stem from the frame bias and the interpolation of the kernel.
+    assert!(err_arcsec < 0.05);
+}
@@ -2,7 +2,7 @@ extern crate nyx_space as nyx;
 
 use anise::constants::celestial_objects::{JUPITER_BARYCENTER, MOON, SUN};
 use anise::constants::frames::MOON_J2000;
-use nyx::cosmic::{try_achieve_b_plane, BPlane, BPlaneTarget, Orbit};
+use nyx::cosmic::{try_achieve_b_plane, AstroError, BPlane, BPlaneTarget, Orbit, OrbitBPlane};
 use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
 use nyx::md::Event;
 use nyx::propagators::Propagator;
@@ -209,6 +209,16 @@ fn b_plane_davis(almanac: Arc<Almanac>) {
     assert!((bp.b_dot_r() - 10606.210428).abs() < 1e-5, "incorrect B_R");
     println!("{} km/s\n{}", orbit.vmag_km_s(), bp);
 
+    // Same B-Plane from the orbit itself
+    let bp_orbit = orbit.b_plane().unwrap();
+    assert_eq!(bp_orbit.b_dot_t(), bp.b_dot_t());
+    assert_eq!(bp_orbit.b_dot_r(), bp.b_dot_r());
+    assert_eq!(bp_orbit.ltof(), bp.ltof());
+
+    // Bound orbits do not have a B-Plane
+    let elliptical = Orbit::keplerian(7_000.0, 0.1, 28.5, 0.0, 0.0, 0.0, orbit.epoch, orbit.frame);
+    assert_eq!(elliptical.b_plane().unwrap_err(), AstroError::NotHyperbolic);
+
     // Check reciprocity between the gravity assist functions.
     let phi = orbit.vinf_turn_angle_deg(300.0).unwrap();
     let rp = orbit.vinf_periapsis_km(phi).unwrap();
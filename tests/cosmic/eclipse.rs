@@ -1,11 +1,11 @@
 extern crate nyx_space as nyx;
 
 use anise::constants::celestial_objects::{JUPITER_BARYCENTER, SUN};
-use anise::constants::frames::SUN_J2000;
+use anise::constants::frames::{MOON_J2000, SUN_J2000};
 use nyx::cosmic::eclipse::{EclipseLocator, EclipseState};
-use nyx::cosmic::Orbit;
+use nyx::cosmic::{Orbit, Spacecraft};
 use nyx::dynamics::orbital::OrbitalDynamics;
-use nyx::dynamics::SpacecraftDynamics;
+use nyx::dynamics::{ForceModel, SolarPressure, SpacecraftDynamics};
 use nyx::propagators::{PropOpts, Propagator};
 use nyx::time::{Epoch, Unit};
 use std::sync::{mpsc, Arc};
@@ -113,3 +113,155 @@ fn geo_sun_earth_eclipses(almanac: Arc<Almanac>) {
 
     assert_eq!(cnt_changes, 15, "wrong number of eclipse state changes");
 }
+
+#[rstest]
+fn moon_orbiter_sun_earth_moon_eclipses(almanac: Arc<Almanac>) {
+    // A low lunar orbiter may be eclipsed by the Moon itself or by its parent planet, the Earth.
+    // This uses the total lunar eclipse of 2022 November 08, when the Moon is fully within the Earth's umbra.
+    let moon_j2k = almanac.frame_from_uid(MOON_J2000).unwrap();
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let radius_km = 1837.4;
+
+    let moon_only = EclipseLocator::new(vec![MOON_J2000], &almanac).unwrap();
+    let moon_earth = EclipseLocator::new(vec![MOON_J2000, EARTH_J2000], &almanac).unwrap();
+    println!("{moon_earth}");
+
+    let srp = SolarPressure::new(vec![moon_j2k, eme2k], almanac.clone()).unwrap();
+
+    // Build the lunar orbiter on the sunlit or on the night side of the Moon
+    let orbiter = |epoch: Epoch, sunlit: bool| {
+        let sun_dir = almanac
+            .transform(SUN_J2000, MOON_J2000, epoch, None)
+            .unwrap()
+            .radius_km
+            .normalize();
+        let side = if sunlit { 1.0 } else { -1.0 };
+        let r_km = side * radius_km * sun_dir;
+        Orbit::from_position(r_km.x, r_km.y, r_km.z, epoch, moon_j2k)
+    };
+
+    let greatest_eclipse = Epoch::from_gregorian_utc_hms(2022, 11, 8, 10, 59, 0);
+    let day_before = greatest_eclipse - Unit::Day;
+
+    // The day before the eclipse, only the Moon shadows the orbiter.
+    for e_loc in [&moon_only, &moon_earth] {
+        assert_eq!(
+            e_loc
+                .compute(orbiter(day_before, true), almanac.clone())
+                .unwrap(),
+            EclipseState::Visibilis
+        );
+        assert_eq!(
+            e_loc
+                .compute(orbiter(day_before, false), almanac.clone())
+                .unwrap(),
+            EclipseState::Umbra
+        );
+    }
+
+    // During totality, the orbiter is in the shadow of the Earth even on the sunlit side of the Moon.
+    let sunlit = orbiter(greatest_eclipse, true);
+    assert_eq!(
+        moon_only.compute(sunlit, almanac.clone()).unwrap(),
+        EclipseState::Visibilis
+    );
+    assert_eq!(
+        moon_earth.compute(sunlit, almanac.clone()).unwrap(),
+        EclipseState::Umbra
+    );
+
+    // The SRP points away from the Sun when lit, and vanishes in the shadow of the Earth.
+    let sc_lit = Spacecraft::from_srp_defaults(orbiter(day_before, true), 100.0, 1.0);
+    let srp_lit = srp.eom(&sc_lit, almanac.clone()).unwrap();
+    let anti_sun = almanac
+        .transform_to(sc_lit.orbit, SUN_J2000, None)
+        .unwrap()
+        .radius_km
+        .normalize();
+    println!("SRP when lit: {srp_lit} km/s^2");
+    assert!(srp_lit.norm() > 0.0);
+    assert!(srp_lit.normalize().dot(&anti_sun) > 1.0 - 1e-12);
+
+    let sc_shadow = Spacecraft::from_srp_defaults(sunlit, 100.0, 1.0);
+    let srp_shadow = srp.eom(&sc_shadow, almanac.clone()).unwrap();
+    println!("SRP in Earth shadow: {srp_shadow} km/s^2");
+    assert_eq!(srp_shadow.norm(), 0.0);
+}
+
+#[rstest]
+fn leo_next_umbra_entry(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let start_time = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+    // Start on the Sun side of the Earth: in early January, the Sun is at a right ascension of about 281 deg and a declination of -23 deg.
+    let (ra, dec) = (281.0_f64.to_radians(), (-23.0_f64).to_radians());
+    let sun_hat = nyx::linalg::Vector3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let r_km = 7_000.0 * sun_hat;
+    let v_km_s = (eme2k.mu_km3_s2().unwrap() / 7_000.0).sqrt()
+        * nyx::linalg::Vector3::z().cross(&sun_hat).normalize();
+    let leo = Orbit::cartesian(
+        r_km.x, r_km.y, r_km.z, v_km_s.x, v_km_s.y, v_km_s.z, start_time, eme2k,
+    );
+
+    let e_loc = EclipseLocator {
+        light_source: almanac.frame_from_uid(SUN_J2000).unwrap(),
+        shadow_bodies: vec![eme2k],
+    };
+    assert_eq!(
+        e_loc.compute(leo, almanac.clone()).unwrap(),
+        EclipseState::Visibilis
+    );
+
+    let setup = Propagator::rk89(
+        SpacecraftDynamics::new(OrbitalDynamics::two_body()),
+        PropOpts::with_max_step(60.0 * Unit::Second),
+    );
+    let window = 2.0 * Unit::Hour;
+
+    // From sunlight, the next transition is the entry into the penumbra, shortly followed by the entry into the umbra.
+    let (penumbra_epoch, penumbra) = e_loc
+        .next_transition(leo.into(), &setup, window, almanac.clone())
+        .unwrap()
+        .unwrap();
+    assert!(matches!(penumbra, EclipseState::Penumbra(_)));
+
+    let at_penumbra = setup
+        .with(leo.into(), almanac.clone())
+        .until_epoch(penumbra_epoch)
+        .unwrap();
+    let (umbra_epoch, umbra) = e_loc
+        .next_transition(at_penumbra, &setup, window, almanac.clone())
+        .unwrap()
+        .unwrap();
+    assert_eq!(umbra, EclipseState::Umbra);
+    println!("penumbra entry @ {penumbra_epoch}, umbra entry @ {umbra_epoch}");
+    assert!(umbra_epoch - penumbra_epoch < 1.0 * Unit::Minute);
+
+    // Grid search of the umbra entry with a 0.1 second step, starting a minute before the penumbra entry
+    let (_, traj) = setup
+        .with(leo.into(), almanac.clone())
+        .for_duration_with_traj(window)
+        .unwrap();
+    let mut grid_epoch = penumbra_epoch - 1 * Unit::Minute;
+    while e_loc
+        .compute(traj.at(grid_epoch).unwrap().orbit, almanac.clone())
+        .unwrap()
+        != EclipseState::Umbra
+    {
+        grid_epoch += 0.1 * Unit::Second;
+        assert!(grid_epoch < start_time + window, "no umbra in grid search");
+    }
+    println!("grid search umbra entry @ {grid_epoch}");
+    assert!((grid_epoch - umbra_epoch).abs() < 0.2 * Unit::Second);
+
+    // No transition within a window which ends before the entry into the penumbra
+    assert!(e_loc
+        .next_transition(
+            leo.into(),
+            &setup,
+            (penumbra_epoch - start_time) - 1.0 * Unit::Second,
+            almanac
+        )
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,86 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::Almanac;
+use nyx::cosmic::{Equinoctial, Orbit};
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+fn assert_round_trip(orbit: Orbit) {
+    let rebuilt = Orbit::from_equinoctial(
+        orbit.sma_km().unwrap(),
+        orbit.equinoctial_f().unwrap(),
+        orbit.equinoctial_g().unwrap(),
+        orbit.equinoctial_h().unwrap(),
+        orbit.equinoctial_k().unwrap(),
+        orbit.true_longitude().unwrap(),
+        orbit.epoch,
+        orbit.frame,
+    )
+    .unwrap();
+
+    let pos_err_km = (rebuilt.radius_km - orbit.radius_km).norm();
+    let vel_err_km_s = (rebuilt.velocity_km_s - orbit.velocity_km_s).norm();
+    println!("{orbit:x}\n{rebuilt:x}\nerr = {pos_err_km:e} km\t{vel_err_km_s:e} km/s");
+    assert!(pos_err_km < 1e-9, "position error {pos_err_km:e} km");
+    assert!(vel_err_km_s < 1e-12, "velocity error {vel_err_km_s:e} km/s");
+}
+
+#[rstest]
+fn equinoctial_circular_equatorial(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let mu_km3_s2 = eme2k.mu_km3_s2().unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+    let r_km = 7_000.0;
+    let v_km_s = (mu_km3_s2 / r_km).sqrt();
+
+    // Circular (e = 0) and equatorial (i = 0): all of the elements but the true longitude vanish
+    let leo = Orbit::cartesian(0.0, r_km, 0.0, -v_km_s, 0.0, 0.0, epoch, eme2k);
+    assert!(leo.equinoctial_f().unwrap().abs() < 1e-12);
+    assert!(leo.equinoctial_g().unwrap().abs() < 1e-12);
+    assert!(leo.equinoctial_h().unwrap().abs() < 1e-12);
+    assert!(leo.equinoctial_k().unwrap().abs() < 1e-12);
+    assert!((leo.true_longitude().unwrap() - 90.0).abs() < 1e-12);
+    assert_round_trip(leo);
+
+    // The constructor matches the Cartesian representation exactly
+    let built = Orbit::from_equinoctial(r_km, 0.0, 0.0, 0.0, 0.0, 90.0, epoch, eme2k).unwrap();
+    assert!((built.radius_km - leo.radius_km).norm() < 1e-9);
+    assert!((built.velocity_km_s - leo.velocity_km_s).norm() < 1e-12);
+
+    // Near-circular and near-equatorial orbits
+    for (ecc, inc_deg) in [(0.0, 28.5), (0.1, 0.0), (1e-9, 1e-9), (1e-12, 0.0)] {
+        let orbit = Orbit::keplerian(r_km, ecc, inc_deg, 30.0, 40.0, 50.0, epoch, eme2k);
+        assert!((orbit.true_longitude().unwrap() - 120.0).abs() < 1e-6);
+        assert_round_trip(orbit);
+    }
+
+    // Elements of a generic orbit match their definition from the Keplerian elements
+    let (ecc, inc_deg, raan_deg, aop_deg, ta_deg) = (0.2, 30.0, 60.0, 40.0, 115.0);
+    let orbit = Orbit::keplerian(
+        8_000.0, ecc, inc_deg, raan_deg, aop_deg, ta_deg, epoch, eme2k,
+    );
+    let lonper_rad = (raan_deg + aop_deg).to_radians();
+    let tan_half_inc = (inc_deg.to_radians() / 2.0).tan();
+    assert!((orbit.equinoctial_f().unwrap() - ecc * lonper_rad.cos()).abs() < 1e-12);
+    assert!((orbit.equinoctial_g().unwrap() - ecc * lonper_rad.sin()).abs() < 1e-12);
+    assert!(
+        (orbit.equinoctial_h().unwrap() - tan_half_inc * raan_deg.to_radians().cos()).abs() < 1e-12
+    );
+    assert!(
+        (orbit.equinoctial_k().unwrap() - tan_half_inc * raan_deg.to_radians().sin()).abs() < 1e-12
+    );
+    assert!((orbit.true_longitude().unwrap() - (raan_deg + aop_deg + ta_deg)).abs() < 1e-9);
+    assert_round_trip(orbit);
+
+    // Retrograde equatorial orbits are the singularity of these elements
+    let retro = Orbit::cartesian(r_km, 0.0, 0.0, 0.0, -v_km_s, 0.0, epoch, eme2k);
+    assert!(retro.equinoctial_h().is_err());
+}
@@ -0,0 +1,100 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME};
+use anise::constants::usual_planetary_constants::MEAN_EARTH_ANGULAR_VELOCITY_DEG_S;
+use anise::prelude::Almanac;
+use nyx::cosmic::{Geodetic, Orbit};
+use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
+use nyx::propagators::Propagator;
+use nyx::time::{Epoch, Unit};
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn geodetic_sub_satellite_point(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let epoch = Epoch::from_gregorian_utc_hms(2024, 3, 20, 12, 0, 0);
+
+    // A spacecraft 500 km above Madrid, whose longitude is west of Greenwich
+    let (lat_deg, long_deg, height_km) = (40.4168, -3.7038, 500.0);
+    let above_madrid = Orbit::try_latlongalt(
+        lat_deg,
+        long_deg,
+        height_km,
+        MEAN_EARTH_ANGULAR_VELOCITY_DEG_S,
+        epoch,
+        iau_earth,
+    )
+    .unwrap();
+    let inertial = almanac.transform_to(above_madrid, eme2k, None).unwrap();
+
+    // The inertial state does not have the same longitude as the body fixed one
+    assert!((inertial.longitude_deg() - long_deg).abs() > 1.0);
+
+    let geo_lat_deg = inertial
+        .geodetic_latitude_deg(IAU_EARTH_FRAME, &almanac)
+        .unwrap();
+    let geo_long_deg = inertial
+        .geodetic_longitude_deg(IAU_EARTH_FRAME, &almanac)
+        .unwrap();
+    let geo_height_km = inertial
+        .geodetic_height_km(IAU_EARTH_FRAME, &almanac)
+        .unwrap();
+
+    println!("lat = {geo_lat_deg} deg\tlong = {geo_long_deg} deg\theight = {geo_height_km} km");
+    assert!((geo_lat_deg - lat_deg).abs() < 1e-8);
+    assert!((geo_long_deg - long_deg).abs() < 1e-8);
+    assert!((geo_height_km - height_km).abs() < 1e-6);
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn traj_ground_track(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+    let iss = Orbit::keplerian(6_790.0, 5e-4, 51.6, 120.0, 80.0, 0.0, epoch, eme2k);
+
+    let (_, traj) = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()))
+        .with(iss.into(), almanac.clone())
+        .for_duration_with_traj(0.25 * Unit::Day)
+        .unwrap();
+
+    let track = traj
+        .ground_track(1 * Unit::Minute, IAU_EARTH_FRAME, &almanac)
+        .unwrap();
+
+    assert_eq!(track.len(), 6 * 60 + 1);
+    assert_eq!(track[0].0, epoch);
+    assert!(
+        (track[0].1
+            - iss
+                .geodetic_latitude_deg(IAU_EARTH_FRAME, &almanac)
+                .unwrap())
+        .abs()
+            < 1e-9
+    );
+
+    let mut wraps = 0;
+    for window in track.windows(2) {
+        let (_, lat_deg, long_deg) = window[1];
+        // The geodetic latitude slightly exceeds the inclination of the orbit
+        assert!(lat_deg.abs() < 51.6 + 0.5, "{lat_deg}");
+        assert!((-180.0..=180.0).contains(&long_deg), "{long_deg}");
+        // Prograde orbit: the longitude only jumps from +180 to -180
+        if (long_deg - window[0].2).abs() > 180.0 {
+            assert!(long_deg < window[0].2);
+            wraps += 1;
+        }
+    }
+    // About four revolutions in six hours
+    assert!((3..=5).contains(&wraps), "{wraps} wraps");
+}
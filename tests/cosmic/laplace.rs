@@ -0,0 +1,67 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::celestial_objects::EARTH;
+use anise::constants::frames::{EARTH_J2000, IAU_MOON_FRAME, MOON_J2000};
+use anise::prelude::{Almanac, Orbit};
+use nyx::cosmic::laplace::{body_laplace_plane, laplace_frozen_orbit, MOON_J2};
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn lunar_laplace_plane(almanac: Arc<Almanac>) {
+    let moon_j2k = almanac.frame_from_uid(MOON_J2000).unwrap();
+    let iau_moon = almanac.frame_from_uid(IAU_MOON_FRAME).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // Normal to the orbital plane of the Earth about the Moon
+    let earth = almanac
+        .transform(EARTH_J2000, MOON_J2000, epoch, None)
+        .unwrap();
+    let earth_moon_normal = earth.radius_km.cross(&earth.velocity_km_s).normalize();
+
+    let mut prev_tilt_deg = 0.0;
+    for sma_km in [1_837.4, 3_000.0, 5_000.0, 10_000.0, 20_000.0] {
+        let orbit = Orbit::keplerian(sma_km, 0.0, 60.0, 0.0, 0.0, 0.0, epoch, moon_j2k);
+        let plane = body_laplace_plane(&almanac, &orbit, iau_moon, MOON_J2, EARTH).unwrap();
+
+        println!(
+            "a = {sma_km} km\tLaplace radius = {:.1} km\ttilt = {:.3} deg\tobliquity = {:.3} deg",
+            plane.laplace_radius_km, plane.tilt_deg, plane.obliquity_deg
+        );
+
+        assert!((3_000.0..3_800.0).contains(&plane.laplace_radius_km));
+        // The lunar equator is inclined by about 6.7 degrees on the orbit of the Moon
+        assert!((6.0..7.5).contains(&plane.obliquity_deg));
+        assert!((plane.normal.norm() - 1.0).abs() < 1e-12);
+        // The Laplace plane tilts from the lunar equator towards the Earth-Moon orbital plane as the orbit widens
+        assert!(plane.tilt_deg > prev_tilt_deg);
+        assert!(plane.tilt_deg < plane.obliquity_deg);
+        prev_tilt_deg = plane.tilt_deg;
+
+        if sma_km < 2_000.0 {
+            // Low lunar orbits precess about the lunar equator
+            assert!(plane.tilt_deg < 0.5);
+        } else if sma_km > 15_000.0 {
+            // Distant lunar orbits precess about the Earth-Moon orbital plane
+            let angle_deg = plane.normal.dot(&earth_moon_normal).acos().to_degrees();
+            assert!(
+                angle_deg < 0.01,
+                "{angle_deg} deg from the Earth-Moon plane"
+            );
+
+            let frozen = laplace_frozen_orbit(&orbit, &plane).unwrap();
+            println!("{frozen:x}");
+            let frozen_normal = frozen.radius_km.cross(&frozen.velocity_km_s).normalize();
+            assert!((frozen_normal - plane.normal).norm() < 1e-12);
+            assert!(frozen.ecc().unwrap() < 1e-12);
+            assert!((frozen.sma_km().unwrap() - sma_km).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_J2000, IAU_MOON_FRAME, MOON_J2000};
+use anise::prelude::{Almanac, Orbit};
+use nyx::cosmic::MEAN_MOON_ANGULAR_VELOCITY_DEG_S;
+use nyx::od::GroundStation;
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn sub_earth_point(almanac: Arc<Almanac>) {
+    let iau_moon = almanac.frame_from_uid(IAU_MOON_FRAME).unwrap();
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // The IAU Moon frame is aligned with the mean Earth direction, so the sub-Earth point only moves with the optical librations.
+    for month in 1..=12 {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, month, 1);
+        let earth = Orbit::from_position(0.0, 0.0, 0.0, epoch, eme2k);
+        let earth_iau_moon = almanac.transform_to(earth, iau_moon, None).unwrap();
+
+        let r = earth_iau_moon.radius_km;
+        let lat_deg = (r.z / r.norm()).asin().to_degrees();
+        let long_deg = r.y.atan2(r.x).to_degrees();
+        println!("{epoch}: sub-Earth point at {lat_deg:.3} deg, {long_deg:.3} deg");
+
+        assert!(lat_deg.abs() < 7.0, "latitude libration too large");
+        assert!(long_deg.abs() < 8.5, "longitude libration too large");
+    }
+}
+
+#[rstest]
+fn lunar_ground_station(almanac: Arc<Almanac>) {
+    let iau_moon = almanac.frame_from_uid(IAU_MOON_FRAME).unwrap();
+    let moon_j2k = almanac.frame_from_uid(MOON_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+
+    let station = GroundStation::from_point("Lunar equator".to_string(), 0.0, 30.0, 0.0, iau_moon);
+    assert_eq!(
+        station.angular_velocity_deg_s(),
+        MEAN_MOON_ANGULAR_VELOCITY_DEG_S
+    );
+
+    let body_fixed = station.to_orbit(epoch, &almanac).unwrap();
+    // The station rotates with the Moon, not with the Earth.
+    let expected_speed_km_s =
+        MEAN_MOON_ANGULAR_VELOCITY_DEG_S.to_radians() * body_fixed.radius_km.norm();
+    println!(
+        "station speed: {:.6} km/s (expected {expected_speed_km_s:.6} km/s)",
+        body_fixed.velocity_km_s.norm()
+    );
+    assert!((body_fixed.velocity_km_s.norm() - expected_speed_km_s).abs() < 1e-6);
+
+    // Round trip through the inertial frame preserves the selenographic coordinates.
+    let inertial = almanac.transform_to(body_fixed, moon_j2k, None).unwrap();
+    assert!((inertial.radius_km - body_fixed.radius_km).norm() > 1.0);
+    let back = almanac.transform_to(inertial, iau_moon, None).unwrap();
+    let (lat_deg, long_deg, alt_km) = back.latlongalt().unwrap();
+    println!("{lat_deg:.9} deg, {long_deg:.9} deg, {alt_km:.6} km");
+    assert!(lat_deg.abs() < 1e-7);
+    assert!((long_deg - 30.0).abs() < 1e-7);
+    assert!(alt_km.abs() < 1e-6);
+}
@@ -0,0 +1,170 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME};
+use anise::prelude::Almanac;
+use nyx::cosmic::mean_elements::{BrouwerLyddane, KozaiMeanElements};
+use nyx::cosmic::AstroError;
+use nyx::cosmic::Orbit;
+use nyx::dynamics::sph_harmonics::Harmonics;
+use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
+use nyx::io::gravity::{HarmonicsMem, EARTH_J2_JGM3};
+use nyx::propagators::Propagator;
+use nyx::time::{Epoch, Unit};
+use nyx::Spacecraft;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn kozai_mean_elements_roundtrip(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_tai_at_noon(2000, 1, 1);
+
+    for (sma_km, ecc, inc_deg) in [
+        (7_000.0, 0.01, 51.6),
+        (7_000.0, 1e-4, 97.8),
+        (8_000.0, 0.1, 63.4),
+        (26_560.0, 0.01, 55.0),
+    ] {
+        let osc = Orbit::keplerian(sma_km, ecc, inc_deg, 30.0, 45.0, 10.0, epoch, eme2k);
+        let mean = KozaiMeanElements::from_osculating(&osc, EARTH_J2_JGM3).unwrap();
+        println!("{mean}");
+
+        // The short-periodic terms are small but not negligible in LEO.
+        assert!((mean.sma_km - sma_km).abs() < 10.0);
+        assert!((mean.inc_deg - inc_deg).abs() < 0.05);
+
+        let roundtrip = mean.to_osculating().unwrap();
+        let err_km = (roundtrip.radius_km - osc.radius_km).norm();
+        let err_km_s = (roundtrip.velocity_km_s - osc.velocity_km_s).norm();
+        assert!(err_km < 1e-6, "position round trip error: {err_km:e} km");
+        assert!(
+            err_km_s < 1e-9,
+            "velocity round trip error: {err_km_s:e} km/s"
+        );
+    }
+
+    // Hyperbolic orbits have no mean elements.
+    let hyperbola = Orbit::cartesian(7_000.0, 0.0, 0.0, 0.0, 12.0, 0.0, epoch, eme2k);
+    assert!(KozaiMeanElements::from_osculating(&hyperbola, EARTH_J2_JGM3).is_err());
+}
+
+#[rstest]
+fn brouwer_lyddane_mean_elements_roundtrip(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_tai_at_noon(2000, 1, 1);
+
+    for (sma_km, ecc, inc_deg, aop_deg) in [
+        (7_000.0, 0.01, 51.6, 45.0),
+        (7_000.0, 0.01, 51.6, 0.0),
+        // Near circular and near equatorial orbits are guarded by Lyddane's modification.
+        (7_000.0, 1e-5, 97.8, 45.0),
+        (7_000.0, 1e-3, 0.1, 120.0),
+        (8_000.0, 0.1, 30.0, 60.0),
+        (26_560.0, 0.01, 55.0, 10.0),
+    ] {
+        let osc = Orbit::keplerian(sma_km, ecc, inc_deg, 30.0, aop_deg, 10.0, epoch, eme2k);
+        let mean = osc.to_mean_elements(EARTH_J2_JGM3).unwrap();
+        println!("{mean}");
+
+        // The mean elements differ from the osculating ones by at most the amplitude of the periodic terms.
+        assert!((mean.sma_km - sma_km).abs() < 10.0);
+        assert!((mean.ecc - ecc).abs() < 5e-3);
+        assert!((mean.inc_deg - inc_deg).abs() < 0.05);
+
+        let roundtrip = Orbit::from_mean_elements(&mean).unwrap();
+        let err_km = (roundtrip.radius_km - osc.radius_km).norm();
+        let err_km_s = (roundtrip.velocity_km_s - osc.velocity_km_s).norm();
+        assert!(err_km < 1e-6, "position round trip error: {err_km:e} km");
+        assert!(
+            err_km_s < 1e-9,
+            "velocity round trip error: {err_km_s:e} km/s"
+        );
+    }
+
+    // The long-periodic terms are singular at the critical inclination.
+    let critical = Orbit::keplerian(8_000.0, 0.1, 63.43, 30.0, 45.0, 10.0, epoch, eme2k);
+    assert_eq!(
+        critical.to_mean_elements(EARTH_J2_JGM3),
+        Err(AstroError::CriticalInclination)
+    );
+
+    // Hyperbolic orbits have no mean elements.
+    let hyperbola = Orbit::cartesian(7_000.0, 0.0, 0.0, 0.0, 12.0, 0.0, epoch, eme2k);
+    assert!(hyperbola.to_mean_elements(EARTH_J2_JGM3).is_err());
+}
+
+#[rstest]
+fn brouwer_lyddane_long_periodic_terms(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_tai_at_noon(2000, 1, 1);
+
+    // With an argument of periapsis of zero, the long-periodic eccentricity term is at its largest.
+    let osc = Orbit::keplerian(8_000.0, 0.1, 30.0, 30.0, 0.0, 10.0, epoch, eme2k);
+    let brouwer = osc.to_mean_elements(EARTH_J2_JGM3).unwrap();
+    let kozai = KozaiMeanElements::from_osculating(&osc, EARTH_J2_JGM3).unwrap();
+
+    // The Kozai mean elements keep the long-periodic terms, which the Brouwer mean elements remove.
+    let d_ecc = (brouwer.ecc - kozai.ecc).abs();
+    println!("{brouwer}\n{kozai}\nlong-periodic eccentricity: {d_ecc:e}");
+    assert!(d_ecc > 1e-6);
+    assert!(d_ecc < 1e-3);
+}
+
+#[rstest]
+fn kozai_mean_elements_vs_numerical_j2(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    // At the J2000 reference epoch, the pole of the body fixed frame matches the Z axis of the inertial frame.
+    let epoch = Epoch::from_gregorian_tai_at_noon(2000, 1, 1);
+
+    let osc = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, epoch, eme2k);
+
+    let j2_dyn = SpacecraftDynamics::new(OrbitalDynamics::from_model(Harmonics::from_stor(
+        iau_earth,
+        HarmonicsMem::j2_jgm3(),
+    )));
+
+    let prop_time = 7 * Unit::Day;
+    let truth = Propagator::default(j2_dyn)
+        .with(Spacecraft::from(osc), almanac)
+        .for_duration(prop_time)
+        .unwrap()
+        .orbit;
+
+    // Analytic propagation seeded with the mean elements
+    let mean = KozaiMeanElements::from_osculating(&osc, EARTH_J2_JGM3).unwrap();
+    let analytic = mean
+        .at_epoch(epoch + prop_time)
+        .unwrap()
+        .to_osculating()
+        .unwrap();
+    let mean_err_km = (analytic.radius_km - truth.radius_km).norm();
+
+    // Seeding the same analytic propagation with the osculating elements instead leads to a large along track drift.
+    let mut naive = mean;
+    naive.sma_km = osc.sma_km().unwrap();
+    naive.ecc = osc.ecc().unwrap();
+    naive.inc_deg = osc.inc_deg().unwrap();
+    naive.raan_deg = osc.raan_deg().unwrap();
+    naive.aop_deg = osc.aop_deg().unwrap();
+    naive.ma_deg = osc.ma_deg().unwrap();
+    let naive = naive
+        .at_epoch(epoch + prop_time)
+        .unwrap()
+        .to_osculating()
+        .unwrap();
+    let naive_err_km = (naive.radius_km - truth.radius_km).norm();
+
+    println!("position error after {prop_time}: {mean_err_km:.3} km with mean elements, {naive_err_km:.3} km with osculating elements");
+
+    assert!(mean_err_km < 20.0);
+    assert!(naive_err_km > 10.0 * mean_err_km);
+}
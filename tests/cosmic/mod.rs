@@ -1,3 +1,16 @@
+mod attitude;
+mod body_fixed;
 mod bplane;
 mod eclipse;
+mod equinoctial;
+mod geodetic;
+mod laplace;
+mod lunar_frame;
+mod mean_elements;
 mod orbit_dual;
+mod orbit_type;
+mod precession;
+mod ric;
+mod soi;
+mod tle;
+mod true_of_date;
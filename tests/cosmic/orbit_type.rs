@@ -0,0 +1,55 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::Almanac;
+use nyx::cosmic::{Orbit, OrbitClassification, OrbitType, PARABOLIC_ECC_TOL};
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn orbit_type_across_eccentricities(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let mu_km3_s2 = eme2k.mu_km3_s2().unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+    let rp_km = 7_000.0;
+
+    // Build each orbit at periapsis, where the eccentricity is set by the velocity: e = rp * v^2 / mu - 1
+    let at_periapsis = |ecc: f64| {
+        let vp_km_s = (mu_km3_s2 * (1.0 + ecc) / rp_km).sqrt();
+        Orbit::cartesian(rp_km, 0.0, 0.0, 0.0, vp_km_s, 0.0, epoch, eme2k)
+    };
+
+    for (ecc, expected) in [
+        (0.0, OrbitType::Circular),
+        (1e-4, OrbitType::Elliptical),
+        (0.1, OrbitType::Elliptical),
+        (0.74, OrbitType::Elliptical),
+        (0.99, OrbitType::Elliptical),
+        // Near-parabolic edge cases on either side of the tolerance
+        (1.0 - 10.0 * PARABOLIC_ECC_TOL, OrbitType::Elliptical),
+        (1.0 - 0.1 * PARABOLIC_ECC_TOL, OrbitType::Parabolic),
+        (1.0, OrbitType::Parabolic),
+        (1.0 + 0.1 * PARABOLIC_ECC_TOL, OrbitType::Parabolic),
+        (1.0 + 10.0 * PARABOLIC_ECC_TOL, OrbitType::Hyperbolic),
+        (1.5, OrbitType::Hyperbolic),
+        (4.0, OrbitType::Hyperbolic),
+    ] {
+        let orbit = at_periapsis(ecc);
+        let orbit_type = orbit.orbit_type().unwrap();
+        println!("e = {:.9} => {orbit_type}", orbit.ecc().unwrap());
+        assert_eq!(orbit_type, expected, "wrong type for e = {ecc}");
+        assert_eq!(orbit_type.is_bound(), ecc < 1.0 - PARABOLIC_ECC_TOL);
+    }
+
+    // A Keplerian orbit is classified consistently with its eccentricity
+    let molniya = Orbit::keplerian(26_600.0, 0.74, 63.4, 0.0, 270.0, 0.0, epoch, eme2k);
+    assert_eq!(molniya.orbit_type().unwrap(), OrbitType::Elliptical);
+    assert_eq!(OrbitType::from_ecc(0.0), OrbitType::Circular);
+}
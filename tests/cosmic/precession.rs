@@ -0,0 +1,97 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::Almanac;
+use nyx::cosmic::precession::*;
+use nyx::cosmic::Orbit;
+use nyx::io::gravity::EARTH_J2_JGM3;
+use nyx::time::{Epoch, Unit};
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn j2_precession_periods(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+
+    // Molniya orbit, at the critical inclination: the line of apsides does not precess.
+    let molniya = Orbit::keplerian(26_600.0, 0.74, 63.4, 0.0, 270.0, 0.0, epoch, eme2k);
+    assert!(apsidal_precession_period(&molniya, EARTH_J2_JGM3)
+        .unwrap()
+        .is_none());
+    assert!(nodal_precession_period(&molniya, EARTH_J2_JGM3)
+        .unwrap()
+        .is_some());
+
+    // Same at the retrograde critical inclination
+    let retro = Orbit::keplerian(26_600.0, 0.74, 116.6, 0.0, 270.0, 0.0, epoch, eme2k);
+    assert!(apsidal_precession_period(&retro, EARTH_J2_JGM3)
+        .unwrap()
+        .is_none());
+
+    // Sun synchronous orbit: the node precesses eastward by one revolution per year.
+    let sso = Orbit::keplerian(7_000.0, 1e-4, 97.87, 0.0, 0.0, 0.0, epoch, eme2k);
+    assert!(nodal_precession_rate_rad_s(&sso, EARTH_J2_JGM3).unwrap() > 0.0);
+    let nodal_period = nodal_precession_period(&sso, EARTH_J2_JGM3)
+        .unwrap()
+        .unwrap();
+    println!("SSO nodal precession period: {nodal_period}");
+    assert!((nodal_period.to_unit(Unit::Day) - 365.25).abs() < 5.0);
+
+    // Below the critical inclination, the periapsis moves forward, and backward above it.
+    let iss = Orbit::keplerian(6_790.0, 5e-4, 51.6, 0.0, 0.0, 0.0, epoch, eme2k);
+    assert!(apsidal_precession_rate_rad_s(&iss, EARTH_J2_JGM3).unwrap() > 0.0);
+    assert!(apsidal_precession_rate_rad_s(&sso, EARTH_J2_JGM3).unwrap() < 0.0);
+    let apsidal_period = apsidal_precession_period(&iss, EARTH_J2_JGM3)
+        .unwrap()
+        .unwrap();
+    println!("ISS apsidal precession period: {apsidal_period}");
+    assert!((apsidal_period.to_unit(Unit::Day) - 100.0).abs() < 10.0);
+
+    // A polar orbit has no nodal precession.
+    let polar = Orbit::keplerian(7_000.0, 1e-4, 90.0, 0.0, 0.0, 0.0, epoch, eme2k);
+    assert!(nodal_precession_period(&polar, EARTH_J2_JGM3)
+        .unwrap()
+        .is_none());
+}
+
+#[rstest]
+fn j2_orbit_averaged_rates(almanac: Arc<Almanac>) {
+    use anise::constants::frames::IAU_EARTH_FRAME;
+    use nyx::dynamics::perturbations::orbit_averaged_rates;
+    use nyx::dynamics::ZonalHarmonics;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+
+    let j2_only = ZonalHarmonics::new(EARTH_J2_JGM3, 0.0, iau_earth);
+
+    for orbit in [
+        Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, epoch, eme2k),
+        Orbit::keplerian(12_000.0, 0.2, 28.5, 120.0, 270.0, 200.0, epoch, eme2k),
+    ] {
+        let rates = orbit_averaged_rates(&orbit, j2_only.as_ref(), almanac.clone()).unwrap();
+        println!("{rates}");
+
+        let raan_rate = nodal_precession_rate_rad_s(&orbit, EARTH_J2_JGM3).unwrap();
+        let aop_rate = apsidal_precession_rate_rad_s(&orbit, EARTH_J2_JGM3).unwrap();
+
+        println!(
+            "analytic raan = {:.6} deg/day\taop = {:.6} deg/day",
+            raan_rate.to_degrees() * 86_400.0,
+            aop_rate.to_degrees() * 86_400.0
+        );
+
+        assert!(((rates.raan_rad_s - raan_rate) / raan_rate).abs() < 0.02);
+        assert!(((rates.aop_rad_s - aop_rate) / aop_rate).abs() < 0.02);
+        // J2 is conservative: no secular change in the semi-major axis
+        assert!(rates.sma_km_s.abs() < 1e-9);
+    }
+}
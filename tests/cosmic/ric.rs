@@ -0,0 +1,58 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+use anise::prelude::Almanac;
+use nyx::cosmic::Orbit;
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn ric_difference_along_track(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+    let sma_km = 7_000.0;
+
+    // The deputy trails the chief by 1 km along the same circular orbit.
+    let sep_km = 1.0;
+    let dtheta_deg = (sep_km / sma_km).to_degrees();
+    let chief = Orbit::keplerian(sma_km, 0.0, 51.6, 30.0, 0.0, 45.0, epoch, eme2k);
+    let deputy = Orbit::keplerian(
+        sma_km,
+        0.0,
+        51.6,
+        30.0,
+        0.0,
+        45.0 - dtheta_deg,
+        epoch,
+        eme2k,
+    );
+
+    // Sanity check that the inertial separation is as expected
+    let inertial_sep_km = (chief.radius_km - deputy.radius_km).norm();
+    println!("inertial separation = {inertial_sep_km} km");
+    assert!((inertial_sep_km - sep_km).abs() < 1e-6);
+
+    let ric = chief.ric_difference(&deputy).unwrap();
+    println!("RIC position (m): {:.6}", ric.radius_km * 1e3);
+    println!("RIC velocity (m/s): {:.6}", ric.velocity_km_s * 1e3);
+
+    // The separation is in-track, up to the curvature of the orbit in radial: r (1 - cos(dtheta)), i.e. 7 cm here.
+    let dtheta_rad = sep_km / sma_km;
+    assert!((ric.radius_km.y.abs() - sma_km * dtheta_rad.sin()).abs() < 1e-9);
+    assert!((ric.radius_km.x.abs() - sma_km * (1.0 - dtheta_rad.cos())).abs() < 1e-9);
+    assert!(ric.radius_km.x.abs() < 1e-4);
+    assert!(ric.radius_km.z.abs() < 1e-9);
+    assert!(ric.velocity_km_s.z.abs() < 1e-12);
+
+    // The RIC difference is only defined between orbits in the same frame.
+    let moon_j2k = almanac.frame_from_uid(MOON_J2000).unwrap();
+    let lunar = Orbit::keplerian(sma_km, 0.0, 51.6, 30.0, 0.0, 45.0, epoch, moon_j2k);
+    assert!(chief.ric_difference(&lunar).is_err());
+}
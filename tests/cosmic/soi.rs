@@ -0,0 +1,33 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::celestial_objects::{EARTH, MOON, SUN};
+use anise::prelude::Almanac;
+use nyx::cosmic::soi::body_sphere_of_influence_km;
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn earth_moon_soi(almanac: Arc<Almanac>) {
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+
+    let earth_soi_km = body_sphere_of_influence_km(&almanac, EARTH, SUN, epoch).unwrap();
+    println!("Earth SOI: {earth_soi_km:.0} km");
+    assert!(
+        (earth_soi_km - 925_000.0).abs() < 5_000.0,
+        "Earth SOI should be about 925,000 km"
+    );
+
+    let moon_soi_km = body_sphere_of_influence_km(&almanac, MOON, EARTH, epoch).unwrap();
+    println!("Moon SOI: {moon_soi_km:.0} km");
+    assert!(
+        (moon_soi_km - 66_100.0).abs() < 3_000.0,
+        "Moon SOI should be about 66,100 km"
+    );
+}
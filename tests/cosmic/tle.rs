@@ -0,0 +1,80 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::Almanac;
+use nyx::cosmic::tle::*;
+use nyx::time::{Epoch, TimeUnits, Unit};
+use nyx::NyxError;
+use rstest::*;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+const ISS_TLE: &str = "ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537";
+
+#[rstest]
+fn tle_iss(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let tle = Tle::from_str(ISS_TLE).unwrap();
+    println!("{tle}");
+
+    assert_eq!(tle.name.as_deref(), Some("ISS (ZARYA)"));
+    assert_eq!(tle.norad_id, 25544);
+    let expected_epoch = Epoch::from_gregorian_utc(2008, 9, 20, 12, 25, 40, 104_192_000);
+    assert!((tle.epoch - expected_epoch).abs() < 1.milliseconds());
+    assert!((tle.inclination_deg - 51.6416).abs() < 1e-12);
+    assert!((tle.eccentricity - 0.0006703).abs() < 1e-12);
+    assert!((tle.bstar + 0.11606e-4).abs() < 1e-12);
+
+    // The ISS stays in LEO over a few orbits.
+    let period = (1.0 / tle.mean_motion_rev_day) * Unit::Day;
+    let mut epoch = tle.epoch;
+    while epoch <= tle.epoch + 3 * period {
+        let teme = tle.propagate(epoch).unwrap();
+        assert!(
+            teme.rmag_km() > 6_650.0 && teme.rmag_km() < 6_800.0,
+            "{epoch}: {} km",
+            teme.rmag_km()
+        );
+        assert!(teme.vmag_km_s() > 7.5 && teme.vmag_km_s() < 7.9);
+
+        // The rotation into EME2000 preserves the magnitudes, and mostly accounts for the precession since 2000.
+        let eme = tle.propagate_eme2000(epoch, eme2k).unwrap();
+        assert!((eme.rmag_km() - teme.rmag_km()).abs() < 1e-6);
+        let offset_km = (eme.radius_km - teme.radius_km).norm();
+        assert!(offset_km > 1.0 && offset_km < 30.0, "{offset_km} km");
+
+        epoch += 5.minutes();
+    }
+}
+
+#[test]
+fn tle_parsing_errors() {
+    // Two line TLE without a title
+    let two_lines = ISS_TLE.lines().skip(1).collect::<Vec<&str>>().join("\n");
+    assert!(Tle::from_str(&two_lines).unwrap().name.is_none());
+
+    // Bad checksum on the second line
+    let bad_checksum = ISS_TLE.replace("15.72125391563537", "15.72125391563538");
+    assert!(matches!(
+        Tle::from_str(&bad_checksum),
+        Err(NyxError::TleParsing { line: 2, .. })
+    ));
+
+    // Truncated first line
+    let truncated = ISS_TLE.replace(" 0  2927", "");
+    assert!(matches!(
+        Tle::from_str(&truncated),
+        Err(NyxError::TleParsing { line: 1, .. })
+    ));
+
+    assert!(Tle::from_str("not a TLE").is_err());
+}
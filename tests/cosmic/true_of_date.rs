@@ -0,0 +1,57 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::{Almanac, Orbit};
+use nyx::cosmic::true_of_date::{from_true_of_date, to_true_of_date, TRUE_OF_DATE};
+use nyx::time::Epoch;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+#[rstest]
+fn true_of_date_inclination(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // The pole of date has precessed towards the J2000 vernal equinox by about 2004" per century, i.e. by about 0.13 degrees
+    // since J2000, so the inclination of an orbit whose node is at 90 degrees decreases by as much.
+    let orbit = Orbit::keplerian(7_000.0, 0.001, 51.6, 90.0, 30.0, 45.0, epoch, eme2k);
+    let tod = to_true_of_date(&orbit).unwrap();
+    assert_eq!(tod.frame.orientation_id, TRUE_OF_DATE);
+
+    let delta_inc_deg = tod.inc_deg().unwrap() - orbit.inc_deg().unwrap();
+    println!(
+        "J2000 inc = {:.6} deg\tTOD inc = {:.6} deg",
+        orbit.inc_deg().unwrap(),
+        tod.inc_deg().unwrap()
+    );
+    assert!(
+        (-0.15..-0.12).contains(&delta_inc_deg),
+        "unexpected inclination change of {delta_inc_deg} deg"
+    );
+
+    // The shape of the orbit is unchanged by a rotation
+    assert!((tod.sma_km().unwrap() - orbit.sma_km().unwrap()).abs() < 1e-9);
+    assert!((tod.ecc().unwrap() - orbit.ecc().unwrap()).abs() < 1e-12);
+
+    // With the node at the J2000 vernal equinox, only the nutation (a few arcseconds) changes the inclination
+    let orbit = Orbit::keplerian(7_000.0, 0.001, 51.6, 0.0, 30.0, 45.0, epoch, eme2k);
+    let tod = to_true_of_date(&orbit).unwrap();
+    let delta_inc_deg = tod.inc_deg().unwrap() - orbit.inc_deg().unwrap();
+    assert!(delta_inc_deg.abs() < 0.01);
+
+    // Round trip
+    let j2k = from_true_of_date(&tod).unwrap();
+    assert_eq!(j2k.frame, orbit.frame);
+    assert!((j2k.radius_km - orbit.radius_km).norm() < 1e-9);
+    assert!((j2k.velocity_km_s - orbit.velocity_km_s).norm() < 1e-12);
+
+    // Only Earth J2000 orbits may be rotated to the true of date frame
+    assert!(to_true_of_date(&tod).is_err());
+    assert!(from_true_of_date(&orbit).is_err());
+}
@@ -0,0 +1,102 @@
+extern crate nyx_space as nyx;
+
+use nyx::cosmic::{Orbit, Spacecraft};
+use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
+use nyx::md::access::crosslink;
+use nyx::propagators::Propagator;
+use nyx::time::{Epoch, Unit};
+
+use anise::{constants::frames::EARTH_J2000, prelude::Almanac};
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+/// Angle between the position vectors of both orbits, in radians
+fn separation_rad(a: &Orbit, b: &Orbit) -> f64 {
+    (a.radius_km.dot(&b.radius_km) / (a.rmag_km() * b.rmag_km()))
+        .clamp(-1.0, 1.0)
+        .acos()
+}
+
+#[rstest]
+fn crosslink_coplanar_opposite_phases(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let radius_km = eme2k.mean_equatorial_radius_km().unwrap();
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2023, 1, 1);
+
+    // Both spacecraft are on circular coplanar orbits of different altitudes, starting on opposite sides of the Earth.
+    let sma_a_km = 7000.0;
+    let sma_b_km = 8000.0;
+    let orbit_a = Orbit::keplerian(sma_a_km, 0.0, 30.0, 40.0, 0.0, 0.0, epoch, eme2k);
+    let orbit_b = Orbit::keplerian(sma_b_km, 0.0, 30.0, 40.0, 0.0, 180.0, epoch, eme2k);
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (_, traj_a) = setup
+        .with(Spacecraft::from(orbit_a), almanac.clone())
+        .for_duration_with_traj(Unit::Day * 1)
+        .unwrap();
+    let (_, traj_b) = setup
+        .with(Spacecraft::from(orbit_b), almanac.clone())
+        .for_duration_with_traj(Unit::Day * 1)
+        .unwrap();
+
+    let intervals = crosslink(&traj_a, &traj_b, eme2k, Unit::Minute * 1, almanac).unwrap();
+
+    // The line between both spacecraft is tangent to the sphere when the separation is the sum of each tangent angle.
+    let max_sep_rad = (radius_km / sma_a_km).acos() + (radius_km / sma_b_km).acos();
+
+    println!("max separation: {:.3} deg", max_sep_rad.to_degrees());
+    for (start, end) in &intervals {
+        println!("{start} -> {end} ({})", *end - *start);
+    }
+
+    // The spacecraft start opposite from each other, so they cannot see each other initially.
+    assert!(!intervals.is_empty(), "no access found");
+    assert!(intervals[0].0 > epoch);
+    // The synodic period is about 8.9 hours, so there are three access intervals in a day.
+    assert_eq!(intervals.len(), 3);
+
+    for (start, end) in &intervals {
+        assert!(start < end);
+        for (boundary, is_open) in [(*start, true), (*end, false)] {
+            // Access boundaries on either end of the trajectory are not due to the geometry.
+            if (boundary == traj_a.last().orbit.epoch) && !is_open {
+                continue;
+            }
+            let sep_rad = separation_rad(
+                &traj_a.at(boundary).unwrap().orbit,
+                &traj_b.at(boundary).unwrap().orbit,
+            );
+            assert!(
+                (sep_rad - max_sep_rad).abs() < 1e-4,
+                "separation at {boundary} is {:.6} deg but should be {:.6} deg",
+                sep_rad.to_degrees(),
+                max_sep_rad.to_degrees()
+            );
+        }
+
+        // Within the access, the separation is less than the max separation.
+        let mid = *start + 0.5 * (*end - *start);
+        let sep_rad = separation_rad(
+            &traj_a.at(mid).unwrap().orbit,
+            &traj_b.at(mid).unwrap().orbit,
+        );
+        assert!(sep_rad < max_sep_rad);
+    }
+
+    // And between accesses, the separation is greater than the max separation.
+    for window in intervals.windows(2) {
+        let mid = window[0].1 + 0.5 * (window[1].0 - window[0].1);
+        let sep_rad = separation_rad(
+            &traj_a.at(mid).unwrap().orbit,
+            &traj_b.at(mid).unwrap().orbit,
+        );
+        assert!(sep_rad > max_sep_rad);
+    }
+}
@@ -1,9 +1,12 @@
 extern crate nyx_space as nyx;
 
 use nyx::cosmic::{Orbit, Spacecraft};
-use nyx::dynamics::{Drag, OrbitalDynamics, SolarPressure, SpacecraftDynamics};
+use nyx::dynamics::{
+    AtmosphereState, ConstantDrag, Density, Drag, ForceModel, OrbitalDynamics, ReducedMsis,
+    SolarPressure, SpaceWeather, SpacecraftDynamics, StdAtm1976, EARTH_ROTATION_RATE_RAD_S,
+};
 use nyx::linalg::Vector6;
-use nyx::propagators::Propagator;
+use nyx::propagators::{PropOpts, Propagator};
 use nyx::time::{Epoch, Unit};
 use nyx::utils::rss_orbit_vec_errors;
 
@@ -348,3 +351,507 @@ fn std_atm_drag_earth_low(almanac: Arc<Almanac>) {
 
     */
 }
+
+#[rstest]
+fn leo_full_force_stack(almanac: Arc<Almanac>) {
+    use anise::constants::celestial_objects::{MOON, SUN};
+    use anise::constants::frames::IAU_EARTH_FRAME;
+    use nyx::cosmic::{GuidanceMode, STD_GRAVITY};
+    use nyx::dynamics::guidance::{LocalFrame, Mnvr, Thruster};
+    use nyx::dynamics::{Harmonics, PointMasses};
+    use nyx::io::gravity::HarmonicsMem;
+    use nyx::linalg::Vector3;
+
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+    let iau_earth = almanac
+        .frame_from_uid(IAU_EARTH_FRAME)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_gregorian_tai_at_midnight(2000, 1, 1);
+
+    let orbit =
+        Orbit::try_keplerian_altitude(300.0, 1e-3, 51.6, 10.0, 20.0, 30.0, dt, eme2k).unwrap();
+
+    let prop_time = 1 * Unit::Day;
+
+    // Point masses and J2 for the orbital dynamics
+    let orbital_dyn = OrbitalDynamics::new(vec![
+        PointMasses::new(vec![MOON, SUN]),
+        Harmonics::from_stor(iau_earth, HarmonicsMem::j2_jgm3()),
+    ]);
+
+    let srp = SolarPressure::default(eme2k, almanac.clone()).unwrap();
+    let drag = Drag::earth_exp(almanac.clone()).unwrap();
+
+    let monoprop = Thruster {
+        thrust_N: 1.0,
+        isp_s: 220.0,
+    };
+    let dry_mass_kg = 300.0;
+    let fuel_mass_kg = 20.0;
+
+    let sc = Spacecraft::from_thruster(
+        orbit,
+        dry_mass_kg,
+        fuel_mass_kg,
+        monoprop,
+        GuidanceMode::Coast,
+    )
+    .with_srp(2.0, 1.5)
+    .with_drag(2.0, 2.2);
+
+    // Propagate without drag to serve as a reference
+    let no_drag_dyn = SpacecraftDynamics::from_model(orbital_dyn.clone(), srp.clone());
+    let no_drag_state = Propagator::default(no_drag_dyn)
+        .with(sc, almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    let sc_dyn =
+        SpacecraftDynamics::from_models(orbital_dyn.clone(), vec![srp.clone(), drag.clone()]);
+    let final_state = Propagator::default(sc_dyn)
+        .with(sc, almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    println!("{}\n{}", no_drag_state, final_state);
+
+    // The drag must have decayed the orbit compared to the same propagation without drag
+    let decay_km = no_drag_state.orbit.sma_km().unwrap() - final_state.orbit.sma_km().unwrap();
+    println!(
+        "SMA decay due to drag over {prop_time}: {:.3} m",
+        decay_km * 1e3
+    );
+    assert!(decay_km > 0.0, "drag did not decay the orbit");
+    assert!(
+        final_state.orbit.energy_km2_s2().unwrap() < no_drag_state.orbit.energy_km2_s2().unwrap(),
+        "drag must dissipate orbital energy"
+    );
+
+    // Without any burn, the mass must remain unchanged
+    assert!(
+        (final_state.mass_kg() - sc.mass_kg()).abs() < f64::EPSILON,
+        "mass changed without a burn"
+    );
+
+    // Add a short prograde burn at the start and check that the fuel is consumed
+    let burn_time = 5 * Unit::Minute;
+    let mnvr = Mnvr::from_time_invariant(
+        dt,
+        dt + burn_time,
+        1.0,
+        Vector3::new(1.0, 0.0, 0.0),
+        LocalFrame::VNC,
+    );
+
+    let sc_burn_dyn = SpacecraftDynamics::from_models_and_guidance_law(
+        orbital_dyn,
+        vec![srp, drag],
+        Arc::new(mnvr),
+    );
+    // Use a fixed step which ends exactly at the end of the burn
+    let burn_state = Propagator::rk89(sc_burn_dyn, PropOpts::with_fixed_step(10 * Unit::Second))
+        .with(sc, almanac)
+        .for_duration(prop_time)
+        .unwrap();
+
+    println!("{}", burn_state);
+
+    let expected_fuel_kg =
+        fuel_mass_kg - monoprop.thrust_N / (monoprop.isp_s * STD_GRAVITY) * burn_time.to_seconds();
+    let fuel_err_kg = (burn_state.fuel_mass_kg - expected_fuel_kg).abs();
+    println!("Fuel mass error: {:.3e} kg", fuel_err_kg);
+    assert!(fuel_err_kg < 1e-9, "incorrect fuel mass after the burn");
+    assert!(
+        burn_state.orbit.sma_km().unwrap() > final_state.orbit.sma_km().unwrap(),
+        "the prograde burn should have raised the orbit"
+    );
+}
+
+#[rstest]
+fn srp_direction_and_eclipse(almanac: Arc<Almanac>) {
+    use anise::constants::frames::SUN_J2000;
+    use nyx::cosmic::eclipse::EclipseState;
+    use nyx::dynamics::ForceModel;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_tai_at_midnight(2021, 3, 20);
+
+    let srp = SolarPressure::default(eme2k, almanac.clone()).unwrap();
+
+    // Unit vector from the Earth to the Sun
+    let sun = almanac
+        .transform(SUN_J2000, EARTH_J2000, epoch, None)
+        .unwrap();
+    let earth_sun_unit = sun.radius_km / sun.rmag_km();
+
+    // Spacecraft between the Earth and the Sun: fully lit, and pushed away from the Sun.
+    let lit = Spacecraft::from_srp_defaults(
+        Orbit::cartesian(
+            7_000.0 * earth_sun_unit.x,
+            7_000.0 * earth_sun_unit.y,
+            7_000.0 * earth_sun_unit.z,
+            0.0,
+            0.0,
+            0.0,
+            epoch,
+            eme2k,
+        ),
+        300.0,
+        16.0,
+    );
+    assert_eq!(
+        srp.e_loc.compute(lit.orbit, almanac.clone()).unwrap(),
+        EclipseState::Visibilis
+    );
+
+    let force = srp.eom(&lit, almanac.clone()).unwrap();
+    println!("SRP when lit: {force:e}");
+    let cos_angle = force.dot(&earth_sun_unit) / force.norm();
+    assert!(
+        cos_angle < -(1.0 - 1e-6),
+        "SRP not pointing away from Sun: {cos_angle}"
+    );
+
+    // The magnitude matches the flux pressure at about 1 AU (within the variation of the Earth-Sun distance).
+    let expected = 1e-3 * lit.srp.cr * lit.srp.area_m2 * 1367.0 / 299_792_458.0;
+    assert!((force.norm() - expected).abs() / expected < 0.05);
+
+    // Doubling the flux doubles the force.
+    let srp_2x = SolarPressure::with_flux(2.0 * 1367.0, vec![eme2k], almanac.clone()).unwrap();
+    let force_2x = srp_2x.eom(&lit, almanac.clone()).unwrap();
+    assert!((force_2x - 2.0 * force).norm() < 1e-12 * force.norm());
+
+    // Spacecraft behind the Earth: in umbra, so the force is zero.
+    let mut shadowed = lit;
+    shadowed.orbit.radius_km = -lit.orbit.radius_km;
+    assert_eq!(
+        srp.e_loc.compute(shadowed.orbit, almanac.clone()).unwrap(),
+        EclipseState::Umbra
+    );
+    assert_eq!(srp.eom(&shadowed, almanac).unwrap().norm(), 0.0);
+}
+
+#[rstest]
+fn drag_ballistic_coefficient_schedule(almanac: Arc<Almanac>) {
+    use nyx::dynamics::{BallisticCoefficient, ForceModel};
+    use nyx::time::TimeUnits;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let dt = Epoch::from_gregorian_tai_at_midnight(2000, 1, 1);
+    let step_epoch = dt + 30.minutes();
+
+    let orbit = Orbit::keplerian(6_378.0 + 300.0, 0.001, 51.6, 0.0, 0.0, 0.0, dt, eme2k);
+    let sc = Spacecraft::from_srp_defaults(orbit, 300.0, 1.0).with_drag(1.5, 2.2);
+
+    // The ballistic coefficient doubles at the step epoch, e.g. after switching to a low drag attitude.
+    let schedule = BallisticCoefficient::Schedule(vec![
+        (dt, 50.0),
+        (step_epoch, 50.0),
+        (step_epoch, 100.0),
+        (dt + 1.hours(), 100.0),
+    ]);
+    let drag = Drag::std_atm1976(almanac.clone()).unwrap();
+    let sched_drag = drag.with_ballistic_coefficient(schedule.clone());
+
+    let mut before = sc;
+    before.orbit.epoch = step_epoch - 1.microseconds();
+    let mut after = sc;
+    after.orbit.epoch = step_epoch;
+
+    assert_eq!(schedule.at(&before), 50.0);
+    assert_eq!(schedule.at(&after), 100.0);
+
+    let force_before = sched_drag.eom(&before, almanac.clone()).unwrap();
+    let force_after = sched_drag.eom(&after, almanac.clone()).unwrap();
+    println!("drag before step: {force_before}\tafter step: {force_after}");
+
+    // Same state, so the drag force is halved with twice the ballistic coefficient, and in the same direction.
+    assert!((force_after.norm() / force_before.norm() - 0.5).abs() < 1e-6);
+    assert!(force_after.normalize().dot(&force_before.normalize()) > 1.0 - 1e-9);
+
+    // The default source uses the drag configuration of the spacecraft, i.e. BC = m / (2.2 * 1.5 m^2).
+    let default_force = drag.eom(&before, almanac.clone()).unwrap();
+    let sc_bc = sc.mass_kg() / (2.2 * 1.5);
+    assert!((BallisticCoefficient::FromSpacecraft.at(&before) - sc_bc).abs() < 1e-12);
+    assert!((default_force.norm() / force_before.norm() - 50.0 / sc_bc).abs() < 1e-9);
+
+    // The schedule changes the orbital decay: propagate with either constant coefficient and with the schedule.
+    let prop_time = 1.hours();
+    let mut final_smas = Vec::new();
+    for bc in [
+        BallisticCoefficient::Constant(50.0),
+        schedule,
+        BallisticCoefficient::Constant(100.0),
+    ] {
+        let sc_dyn = SpacecraftDynamics::from_model(
+            OrbitalDynamics::two_body(),
+            drag.with_ballistic_coefficient(bc),
+        );
+        let final_state = Propagator::default(sc_dyn)
+            .with(sc, almanac.clone())
+            .for_duration(prop_time)
+            .unwrap();
+        final_smas.push(final_state.orbit.sma_km().unwrap());
+    }
+    println!("final SMAs: {final_smas:?}");
+
+    // Lower ballistic coefficients decay faster.
+    assert!(final_smas[0] < final_smas[1]);
+    assert!(final_smas[1] < final_smas[2]);
+}
+
+#[test]
+fn std_atm_density_400km() {
+    let dt = Epoch::from_gregorian_utc_at_noon(2000, 1, 1);
+
+    let state = AtmosphereState {
+        epoch: dt,
+        height_km: 400.0,
+        latitude_deg: 0.0,
+        longitude_deg: 0.0,
+        space_weather: SpaceWeather::default(),
+    };
+
+    assert!((state.local_solar_time_h() - 12.0).abs() < 1e-9);
+    assert!(
+        (AtmosphereState {
+            longitude_deg: -90.0,
+            ..state
+        }
+        .local_solar_time_h()
+            - 6.0)
+            .abs()
+            < 1e-9
+    );
+
+    // U.S. Standard Atmosphere 1976, table I
+    let rho_table_kg_m3 = 2.803e-12;
+    let rho_kg_m3 = StdAtm1976 {
+        max_alt_m: 1_000_000.0,
+    }
+    .density_kg_m3(&state);
+
+    println!("density at 400 km: {rho_kg_m3:.3e} kg/m^3");
+    assert!((rho_kg_m3 - rho_table_kg_m3).abs() / rho_table_kg_m3 < 0.1);
+}
+
+#[test]
+fn reduced_msis_density() {
+    // Reference cases of the NRLMSISE-00 distribution (Picone et al., 2002) which were _not_ used to fit the reduced model: day of year,
+    // universal time, height, latitude, local solar time, space weather, and the total mass density converted from g/cm^3 to kg/m^3.
+    // The reduced model was fitted to the cases at day 172 and 81, at the equator, at 4 h local solar time, and at an average flux of 70 sfu.
+    // This model has no longitudinal terms, so the longitude is chosen such that the local solar time matches that of the reference case.
+    // Hence, the reference case at a longitude of zero only differs from the nominal fitted case by the longitudinal terms of NRLMSISE-00.
+    let day_172 = Epoch::from_gregorian_utc(2001, 6, 21, 8, 3, 20, 0);
+    let day_172_late = Epoch::from_gregorian_utc(2001, 6, 21, 20, 50, 0, 0);
+
+    let nominal = SpaceWeather {
+        f107: 150.0,
+        f107a: 150.0,
+        ap: 4.0,
+    };
+
+    for (case, epoch, height_km, space_weather, rho_ref_kg_m3) in [
+        ("1000 km", day_172_late, 1000.0, nominal, 2.756772e-15),
+        ("longitude 0 deg", day_172, 400.0, nominal, 4.355866e-12),
+        (
+            "F10.7 = 180 sfu",
+            day_172,
+            400.0,
+            SpaceWeather {
+                f107: 180.0,
+                ..nominal
+            },
+            4.564420e-12,
+        ),
+        (
+            "Ap = 40",
+            day_172,
+            400.0,
+            SpaceWeather {
+                ap: 40.0,
+                ..nominal
+            },
+            4.974543e-12,
+        ),
+    ] {
+        let lst_h = 16.0;
+        let mut state = AtmosphereState {
+            epoch,
+            height_km,
+            latitude_deg: 60.0,
+            longitude_deg: 0.0,
+            space_weather,
+        };
+        state.longitude_deg = 15.0 * (lst_h - state.local_solar_time_h());
+        assert!((state.local_solar_time_h() - lst_h).abs() < 1e-9);
+
+        let rho_kg_m3 = ReducedMsis.density_kg_m3(&state);
+        let rel_err = (rho_kg_m3 - rho_ref_kg_m3).abs() / rho_ref_kg_m3;
+        println!(
+            "{case}: {rho_kg_m3:.4e} kg/m^3 (NRLMSISE-00 {rho_ref_kg_m3:.4e}, {:.2} %)",
+            rel_err * 100.0
+        );
+        assert!(rel_err < 0.1);
+    }
+
+    // The density is continuous at the lower boundary of the thermosphere, below which the standard atmosphere is scaled.
+    let state = AtmosphereState {
+        epoch: day_172,
+        height_km: 120.0,
+        latitude_deg: 0.0,
+        longitude_deg: 0.0,
+        space_weather: nominal,
+    };
+    let rho_above_kg_m3 = ReducedMsis.density_kg_m3(&AtmosphereState {
+        height_km: 120.0 + 1e-6,
+        ..state
+    });
+    let rho_below_kg_m3 = ReducedMsis.density_kg_m3(&AtmosphereState {
+        height_km: 120.0 - 1e-6,
+        ..state
+    });
+    assert!((rho_above_kg_m3 - rho_below_kg_m3).abs() / rho_above_kg_m3 < 1e-6);
+    assert!(
+        ReducedMsis.density_kg_m3(&AtmosphereState {
+            height_km: 100.0,
+            ..state
+        }) > rho_above_kg_m3
+    );
+}
+
+#[rstest]
+fn reduced_msis_drag_space_weather(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_gregorian_tai_at_midnight(2000, 1, 1);
+
+    let orbit = Orbit::try_keplerian_altitude(400.0, 0.0, 51.6, 0.0, 0.0, 0.0, dt, eme2k).unwrap();
+
+    let sc = Spacecraft::from_srp_defaults(orbit, 300.0, 1.0).with_drag(1.0, 2.0);
+
+    // The orbit decays faster during high solar activity.
+    let mut final_smas = Vec::new();
+    for f107 in [70.0, 150.0, 250.0] {
+        let space_weather = SpaceWeather {
+            f107,
+            f107a: f107,
+            ..Default::default()
+        };
+        let drag =
+            Drag::earth_model(Arc::new(ReducedMsis), space_weather, almanac.clone()).unwrap();
+        let sc_dyn = SpacecraftDynamics::from_model(OrbitalDynamics::two_body(), drag);
+        let final_state = Propagator::default_dp78(sc_dyn)
+            .with(sc, almanac.clone())
+            .for_duration(1 * Unit::Day)
+            .unwrap();
+        println!("F10.7 = {f107}: {}", final_state.orbit);
+        final_smas.push(final_state.orbit.sma_km().unwrap());
+    }
+
+    assert!(final_smas[0] < orbit.sma_km().unwrap());
+    assert!(final_smas[1] < final_smas[0]);
+    assert!(final_smas[2] < final_smas[1]);
+}
+
+#[rstest]
+fn density_model_drag_earth_low(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_gregorian_tai_at_midnight(2000, 1, 1);
+
+    let orbit = Orbit::try_keplerian_altitude(400.0, 0.0, 51.6, 0.0, 0.0, 0.0, dt, eme2k).unwrap();
+
+    let sc = Spacecraft::from_srp_defaults(orbit, 300.0, 1.0).with_drag(1.0, 2.0);
+
+    // The standard atmosphere used as a density model must match the built-in standard atmosphere.
+    let model_drag = Drag::earth_model(
+        Arc::new(StdAtm1976 {
+            max_alt_m: 1_000_000.0,
+        }),
+        SpaceWeather::default(),
+        almanac.clone(),
+    )
+    .unwrap();
+    let std_drag = Drag::std_atm1976(almanac.clone()).unwrap();
+
+    let mut final_states = Vec::new();
+    for drag in [model_drag, std_drag] {
+        let sc_dyn = SpacecraftDynamics::from_model(OrbitalDynamics::two_body(), drag);
+        let setup = Propagator::default_dp78(sc_dyn);
+        let final_state = setup
+            .with(sc, almanac.clone())
+            .for_duration(1 * Unit::Day)
+            .unwrap();
+        println!("{}", final_state.orbit);
+        final_states.push(final_state);
+    }
+
+    assert_eq!(final_states[0], final_states[1]);
+    // Drag decays the orbit
+    assert!(final_states[0].orbit.sma_km().unwrap() < orbit.sma_km().unwrap());
+}
+
+#[rstest]
+fn drag_corotating_atmosphere(almanac: Arc<Almanac>) {
+    use anise::constants::frames::IAU_EARTH_FRAME;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let dt = Epoch::from_gregorian_tai_at_midnight(2000, 1, 1);
+
+    // Prograde equatorial LEO: the atmosphere moves along with the spacecraft.
+    let orbit = Orbit::try_keplerian_altitude(400.0, 0.0, 0.0, 0.0, 0.0, 0.0, dt, eme2k).unwrap();
+    let sc = Spacecraft::from_srp_defaults(orbit, 300.0, 1.0).with_drag(1.0, 2.0);
+
+    let speed_ratio = EARTH_ROTATION_RATE_RAD_S * orbit.rmag_km() / orbit.vmag_km_s();
+    println!("atmospheric to orbital speed ratio: {speed_ratio:.6}");
+
+    let constant = ConstantDrag {
+        rho: 1e-12,
+        drag_frame: iau_earth,
+        estimate: false,
+        rotation_rate_rad_s: EARTH_ROTATION_RATE_RAD_S,
+    };
+    // Previous models assumed a static atmosphere
+    let static_constant = ConstantDrag {
+        rotation_rate_rad_s: 0.0,
+        ..constant.clone()
+    };
+
+    let exp_drag = Drag::earth_exp(almanac.clone()).unwrap();
+    let mut static_exp_drag = (*exp_drag).clone();
+    static_exp_drag.rotation_rate_rad_s = 0.0;
+
+    let models: [(Arc<dyn ForceModel>, Arc<dyn ForceModel>); 2] = [
+        (Arc::new(constant), Arc::new(static_constant)),
+        (exp_drag, Arc::new(static_exp_drag)),
+    ];
+
+    for (corotating, static_atm) in models {
+        let corotating_acc = corotating.eom(&sc, almanac.clone()).unwrap();
+        let static_acc = static_atm.eom(&sc, almanac.clone()).unwrap();
+
+        let ratio = corotating_acc.norm() / static_acc.norm();
+        println!("drag magnitude ratio: {ratio:.6}");
+
+        // Drag opposes the relative velocity, which remains along the inertial velocity for an equatorial orbit.
+        assert!(corotating_acc.dot(&orbit.velocity_km_s) < 0.0);
+        assert!((ratio - (1.0 - speed_ratio).powi(2)).abs() < 1e-9);
+        // i.e. the drag drops by about twice the ratio of atmospheric to orbital speed
+        assert!(((1.0 - ratio) - 2.0 * speed_ratio).abs() <= speed_ratio.powi(2) + 1e-9);
+    }
+}
@@ -1,4 +1,6 @@
+mod access;
 mod force_models;
 mod multishoot;
 mod orbitaldyn;
 mod targeter;
+mod transfers;
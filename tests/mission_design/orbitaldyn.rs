@@ -2,7 +2,7 @@ extern crate nalgebra as na;
 extern crate nyx_space as nyx;
 
 use anise::constants::celestial_objects::{EARTH, JUPITER_BARYCENTER, MOON, SUN};
-use anise::constants::frames::IAU_EARTH_FRAME;
+use anise::constants::frames::{IAU_EARTH_FRAME, MOON_J2000};
 use hifitime::MJD_J2000;
 use na::{Const, OMatrix};
 use nyx::cosmic::{assert_orbit_eq_or_abs, Orbit};
@@ -1017,6 +1017,55 @@ fn val_earth_sph_harmonics_12x12(almanac_gmat: Arc<Almanac>) {
     assert!(err_v < 2e-16, "velocity error too large for 12x12 gravity");
 }
 
+#[rstest]
+fn earth_sph_harmonics_gfc_20x20(almanac: Arc<Almanac>) {
+    use nyx::dynamics::sph_harmonics::Harmonics;
+    use nyx::dynamics::AccelModel;
+    use nyx::io::gravity::*;
+
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let egm_stor = HarmonicsMem::from_egm("data/EGM2008_to2190_TideFree.gz", 20, 20, true).unwrap();
+    let gfc_stor = HarmonicsMem::from_gfc("data/EGM2008_to20_TideFree.gfc", 20, 20, false).unwrap();
+
+    assert_eq!(gfc_stor.max_degree_n(), 20);
+    assert_eq!(gfc_stor.max_order_m(), 20);
+    for n in 2..=20 {
+        for m in 0..=n {
+            assert_eq!(gfc_stor.cs_nm(n, m), egm_stor.cs_nm(n, m), "C/S {n} {m}");
+        }
+    }
+
+    // Fixed body fixed position above the Himalayas, so the rotation into the propagation frame is the identity
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+    let state = Orbit::cartesian(
+        1_520.742, 5_417.236, 4_097.621, 0.0, 0.0, 0.0, epoch, iau_earth,
+    );
+
+    let egm_accel = Harmonics::from_stor(iau_earth, egm_stor)
+        .eom(&state, almanac.clone())
+        .unwrap();
+    let gfc_accel = Harmonics::from_stor(iau_earth, gfc_stor)
+        .eom(&state, almanac.clone())
+        .unwrap();
+    let j2_accel = Harmonics::from_stor(iau_earth, HarmonicsMem::j2_egm2008())
+        .eom(&state, almanac)
+        .unwrap();
+
+    println!("EGM2008 20x20 from gfc: {gfc_accel:e} km/s^2");
+    println!("J2 only: {j2_accel:e} km/s^2");
+
+    assert!(
+        (egm_accel - gfc_accel).norm() < 1e-20,
+        "gfc and EGM loaders disagree by {:e} km/s^2",
+        (egm_accel - gfc_accel).norm()
+    );
+    // J2 dominates the non-spherical acceleration at LEO altitudes, with the higher degrees within a few percent of it.
+    assert!(j2_accel.norm() > 5e-6 && j2_accel.norm() < 2e-5);
+    let rel = (gfc_accel - j2_accel).norm() / j2_accel.norm();
+    assert!(rel > 0.0 && rel < 0.05, "higher degrees contribute {rel:e}");
+}
+
 #[allow(clippy::identity_op)]
 #[rstest]
 fn val_earth_sph_harmonics_70x70(almanac_gmat: Arc<Almanac>) {
@@ -1231,3 +1280,361 @@ fn val_cislunar_dynamics(almanac_gmat: Arc<Almanac>) {
         err_v
     );
 }
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn zonal_harmonics_sso_raan_drift(almanac: Arc<Almanac>) {
+    use nyx::cosmic::precession::nodal_precession_rate_rad_s;
+    use nyx::dynamics::{Harmonics, ZonalHarmonics};
+    use nyx::io::gravity::HarmonicsMem;
+    use nyx::io::gravity::{EARTH_J2_JGM3, EARTH_J3_JGM3};
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    // Sun synchronous orbit at about 800 km altitude
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let sso = Orbit::keplerian(7_178.0, 1e-3, 98.6, 30.0, 90.0, 0.0, dt, eme2k);
+
+    let dynamics = SpacecraftDynamics::new(OrbitalDynamics::with_zonal_harmonics(
+        EARTH_J2_JGM3,
+        EARTH_J3_JGM3,
+        iau_earth,
+    ));
+
+    let prop_time = 1 * Unit::Day;
+    let (_, traj) = Propagator::default(dynamics)
+        .with(sso.into(), almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+
+    // Fit the secular RAAN rate by linear least squares to average out the short period terms.
+    let (mut sum_t, mut sum_raan, mut sum_tt, mut sum_traan, mut num) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for state in traj.every(1 * Unit::Minute) {
+        let t_s = (state.orbit.epoch - dt).to_seconds();
+        let raan_rad = state.orbit.raan_deg().unwrap().to_radians();
+        sum_t += t_s;
+        sum_raan += raan_rad;
+        sum_tt += t_s * t_s;
+        sum_traan += t_s * raan_rad;
+        num += 1.0;
+    }
+    let raan_rate_rad_s = (num * sum_traan - sum_t * sum_raan) / (num * sum_tt - sum_t * sum_t);
+
+    let expected_rad_s = nodal_precession_rate_rad_s(&sso, EARTH_J2_JGM3).unwrap();
+    let rel_err = (raan_rate_rad_s - expected_rad_s).abs() / expected_rad_s.abs();
+    println!(
+        "RAAN drift: {:.6} deg/day (analytical: {:.6} deg/day, rel. error {:.3e})",
+        raan_rate_rad_s.to_degrees() * 86_400.0,
+        expected_rad_s.to_degrees() * 86_400.0,
+        rel_err
+    );
+    assert!(rel_err < 0.01, "RAAN drift off by {:.2}%", rel_err * 100.0);
+
+    // Without J3, the zonal model matches the J2 spherical harmonics model.
+    let j2_only = SpacecraftDynamics::new(OrbitalDynamics::from_model(ZonalHarmonics::new(
+        EARTH_J2_JGM3,
+        0.0,
+        iau_earth,
+    )));
+    let j2_harmonics = SpacecraftDynamics::new(OrbitalDynamics::from_model(Harmonics::from_stor(
+        iau_earth,
+        HarmonicsMem::j2_jgm3(),
+    )));
+
+    let zonal = Propagator::default(j2_only)
+        .with(sso.into(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+    let harmonics = Propagator::default(j2_harmonics)
+        .with(sso.into(), almanac)
+        .for_duration(prop_time)
+        .unwrap();
+
+    let (err_r, err_v) = rss_orbit_errors(&zonal.orbit, &harmonics.orbit);
+    println!("J2 zonal vs spherical harmonics: {err_r:.3e} km\t{err_v:.3e} km/s");
+    assert!(err_r < 1e-2, "position error: {err_r:e} km");
+    assert!(err_v < 1e-5, "velocity error: {err_v:e} km/s");
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn solid_earth_tides(almanac: Arc<Almanac>) {
+    use nyx::dynamics::{AccelModel, TidalForce};
+    use nyx::time::TimeSeries;
+    use std::f64::consts::TAU;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let moon = almanac.frame_from_uid(MOON_J2000).unwrap();
+
+    let tides = TidalForce::earth(iau_earth);
+    let start = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let duration = 28 * Unit::Day;
+
+    let mut times_h = Vec::new();
+    let mut c20 = Vec::new();
+    let mut c22 = Vec::new();
+    for epoch in TimeSeries::inclusive(start, start + duration, 1 * Unit::Hour) {
+        let coeffs = tides.coefficients(epoch, almanac.clone()).unwrap();
+        // The J2 equivalent of the tides is small, yet not negligible
+        assert!(coeffs.delta_j2().abs() < 1e-8, "{coeffs}");
+        times_h.push((epoch - start).to_unit(Unit::Hour));
+        c20.push(coeffs.c20);
+        c22.push(coeffs.c22);
+    }
+
+    // Amplitude of the Fourier component of the signal (minus its mean) at the provided period in hours
+    let amplitude = |signal: &[f64], period_h: f64| -> f64 {
+        let mean = signal.iter().sum::<f64>() / signal.len() as f64;
+        let (mut re, mut im) = (0.0, 0.0);
+        for (t_h, val) in times_h.iter().zip(signal) {
+            let phase = TAU * t_h / period_h;
+            re += (val - mean) * phase.cos();
+            im -= (val - mean) * phase.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / signal.len() as f64
+    };
+
+    // The zonal term is modulated by the declination of the Moon, i.e. at the lunar fortnightly (Mf) frequency.
+    let mf = amplitude(&c20, 327.86);
+    let off_mf = amplitude(&c20, 5.0 * 24.0);
+    println!("ΔC20: Mf amplitude {mf:e}, 5 day amplitude {off_mf:e}");
+    assert!(mf > 5.0 * off_mf);
+
+    // The sectorial term is modulated at the principal lunar semi-diurnal (M2) frequency.
+    let m2 = amplitude(&c22, 12.420_6);
+    let off_m2 = amplitude(&c22, 9.0);
+    println!("ΔC22: M2 amplitude {m2:e}, 9 hour amplitude {off_m2:e}");
+    assert!(m2 > 5.0 * off_m2);
+
+    // With the same Love number for all orders, the acceleration matches the direct formula of the tides raised by the Moon.
+    let k2 = 0.3;
+    let lunar_tides = TidalForce::new([k2; 3], iau_earth, vec![MOON]);
+    let orbit = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, start, eme2k);
+    let accel = lunar_tides.eom(&orbit, almanac.clone()).unwrap();
+
+    let r_moon = almanac
+        .transform(moon, eme2k, start, None)
+        .unwrap()
+        .radius_km;
+    let s_hat = r_moon.normalize();
+    let r_hat = orbit.radius_km.normalize();
+    let cos_theta = r_hat.dot(&s_hat);
+    let eq_radius_km = iau_earth.mean_equatorial_radius_km().unwrap();
+    let expected = k2 * moon.mu_km3_s2().unwrap() * eq_radius_km.powi(5)
+        / (2.0 * r_moon.norm().powi(3) * orbit.rmag_km().powi(4))
+        * ((3.0 - 15.0 * cos_theta.powi(2)) * r_hat + 6.0 * cos_theta * s_hat);
+
+    println!("tidal acceleration: {accel:e} (expected {expected:e})");
+    assert!((accel - expected).norm() < 1e-9 * expected.norm());
+
+    // The partials match the acceleration
+    let (dual_accel, _) = lunar_tides.dual_eom(&orbit, almanac).unwrap();
+    assert!((dual_accel - accel).norm() < 1e-12 * accel.norm());
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn earth_sph_harmonics_solid_tides(almanac: Arc<Almanac>) {
+    use nyx::dynamics::{AccelModel, Harmonics, TidalForce};
+    use nyx::io::gravity::HarmonicsMem;
+    use nyx::time::TimeSeries;
+    use std::f64::consts::TAU;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let stor = HarmonicsMem::from_cof("data/JGM3.cof.gz", 8, 8, true).unwrap();
+    let (static_c20, _) = stor.cs_nm(2, 0);
+    let harmonics = Harmonics::from_stor(iau_earth, stor.clone());
+    let tidal_harmonics = Harmonics::with_solid_tides(iau_earth, stor);
+    println!("{tidal_harmonics}");
+
+    let start = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let duration = 28 * Unit::Day;
+
+    let mut times_h = Vec::new();
+    let mut c20 = Vec::new();
+    for epoch in TimeSeries::inclusive(start, start + duration, 1 * Unit::Hour) {
+        // Without the tides, the coefficients are static
+        assert_eq!(
+            harmonics.cs_nm(2, 0, epoch, almanac.clone()).unwrap().0,
+            static_c20
+        );
+        let (c20_tidal, _) = tidal_harmonics.cs_nm(2, 0, epoch, almanac.clone()).unwrap();
+        assert!((c20_tidal - static_c20).abs() < 1e-8);
+        times_h.push((epoch - start).to_unit(Unit::Hour));
+        c20.push(c20_tidal);
+    }
+
+    // Amplitude of the Fourier component of the signal (minus its mean) at the provided period in hours
+    let amplitude = |signal: &[f64], period_h: f64| -> f64 {
+        let mean = signal.iter().sum::<f64>() / signal.len() as f64;
+        let (mut re, mut im) = (0.0, 0.0);
+        for (t_h, val) in times_h.iter().zip(signal) {
+            let phase = TAU * t_h / period_h;
+            re += (val - mean) * phase.cos();
+            im -= (val - mean) * phase.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / signal.len() as f64
+    };
+
+    // C20 oscillates at the lunar fortnightly (Mf) period
+    let mf = amplitude(&c20, 327.86);
+    let off_mf = amplitude(&c20, 5.0 * 24.0);
+    println!("C20: Mf amplitude {mf:e}, 5 day amplitude {off_mf:e}");
+    assert!(mf > 5.0 * off_mf);
+
+    // The harmonics field with tides matches the static field plus the tidal force, since the potential is linear in the coefficients
+    let orbit = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, start, eme2k);
+    let accel_static = harmonics.eom(&orbit, almanac.clone()).unwrap();
+    let accel_tides = tidal_harmonics.eom(&orbit, almanac.clone()).unwrap();
+    let expected = TidalForce::earth(iau_earth)
+        .eom(&orbit, almanac.clone())
+        .unwrap();
+
+    let delta = accel_tides - accel_static;
+    println!("tidal acceleration: {delta:e} (expected {expected:e})");
+    assert!((delta - expected).norm() < 1e-3 * expected.norm());
+
+    // The partials match the acceleration
+    let (dual_accel, _) = tidal_harmonics.dual_eom(&orbit, almanac).unwrap();
+    assert!((dual_accel - accel_tides).norm() < 1e-10 * accel_tides.norm());
+}
+
+#[rstest]
+fn schwarzschild_gps(almanac: Arc<Almanac>) {
+    use nyx::cosmic::SPEED_OF_LIGHT_KM_S;
+    use nyx::dynamics::{AccelModel, Relativity};
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // Circular GPS orbit
+    let gps = Orbit::keplerian(26_560.0, 0.0, 55.0, 30.0, 0.0, 45.0, epoch, eme2k);
+
+    let relativity = Relativity::new();
+    let accel = relativity.eom(&gps, almanac.clone()).unwrap();
+
+    // About 3e-13 km/s² (i.e. 3e-10 m/s²) at GPS altitude
+    println!("{relativity}: {accel:e} ({:e} km/s²)", accel.norm());
+    assert!(accel.norm() > 1e-13 && accel.norm() < 1e-12);
+
+    // On a circular orbit, r·v = 0 and v² = μ/r so the acceleration is radially outward with magnitude 3μ²/(c² r³)
+    let mu_km3_s2 = eme2k.mu_km3_s2().unwrap();
+    let expected = 3.0 * mu_km3_s2.powi(2) / (SPEED_OF_LIGHT_KM_S.powi(2) * gps.rmag_km().powi(3));
+    assert!((accel.norm() - expected).abs() < 1e-6 * expected);
+    assert!(accel.normalize().dot(&gps.radius_km.normalize()) > 1.0 - 1e-6);
+
+    // The partials match the acceleration
+    let (dual_accel, grad) = relativity.dual_eom(&gps, almanac).unwrap();
+    assert!((dual_accel - accel).norm() < 1e-12 * accel.norm());
+    assert!(grad.norm() > 0.0);
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn geo_lunar_perturbation_light_time(almanac: Arc<Almanac>) {
+    use anise::astro::Aberration;
+    use nyx::dynamics::AccelModel;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let moon = almanac.frame_from_uid(MOON_J2000).unwrap();
+
+    let start = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let geo = Orbit::keplerian(42_164.0, 1e-4, 0.05, 75.0, 0.0, 10.0, start, eme2k);
+
+    let lt = Aberration::LT.unwrap();
+    // The Earth is the integration frame origin and must not be counted twice.
+    let lunar = PointMasses::with_correction(vec![EARTH, MOON], lt);
+    let accel = lunar.eom(&geo, almanac.clone()).unwrap();
+
+    // Reference: direct minus indirect term of the light time corrected Moon position
+    let r_moon = almanac
+        .transform(moon, eme2k, start, Aberration::LT)
+        .unwrap()
+        .radius_km;
+    let r_sc_moon = r_moon - geo.radius_km;
+    let expected = moon.mu_km3_s2().unwrap()
+        * (r_sc_moon / r_sc_moon.norm().powi(3) - r_moon / r_moon.norm().powi(3));
+
+    println!("lunar acceleration at GEO: {accel:e} (expected {expected:e})");
+    assert!((accel - expected).norm() < 1e-12 * expected.norm());
+    assert!(accel.norm() > 1e-9 && accel.norm() < 2e-8);
+
+    // Propagate for a day with and without light time correction, and without the Moon altogether
+    let prop_time = 1 * Unit::Day;
+    let sc: Spacecraft = geo.into();
+
+    let final_state = |dynamics: OrbitalDynamics| {
+        Propagator::default(SpacecraftDynamics::new(dynamics))
+            .with(sc, almanac.clone())
+            .for_duration(prop_time)
+            .unwrap()
+            .orbit
+    };
+
+    let with_lt = final_state(OrbitalDynamics::point_masses_with_correction(
+        vec![MOON],
+        lt,
+    ));
+    let without_lt = final_state(OrbitalDynamics::point_masses(vec![MOON]));
+    let two_body = final_state(OrbitalDynamics::two_body());
+
+    let (lunar_effect_km, _) = rss_orbit_errors(&with_lt, &two_body);
+    let (lt_effect_km, _) = rss_orbit_errors(&with_lt, &without_lt);
+    println!(
+        "lunar effect: {:.3} km\tlight time effect: {:.3} m",
+        lunar_effect_km,
+        lt_effect_km * 1e3
+    );
+
+    // The Moon perturbs a GEO by kilometers per day, and its light time by much less
+    assert!(lunar_effect_km > 1.0);
+    assert!(lt_effect_km > 0.0);
+    assert!(lt_effect_km < 1e-2);
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn gve_j2_propagation(almanac: Arc<Almanac>) {
+    use nyx::dynamics::{EquinoctialState, GVEDynamics, ZonalHarmonics};
+    use nyx::io::gravity::EARTH_J2_JGM3;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let leo = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, dt, eme2k);
+    let prop_time = 1 * Unit::Day;
+
+    let j2 = OrbitalDynamics::from_model(ZonalHarmonics::new(EARTH_J2_JGM3, 0.0, iau_earth));
+
+    let cowell = Propagator::default(SpacecraftDynamics::new(j2.clone()))
+        .with(leo.into(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    let gve_state = EquinoctialState::new(leo.into()).unwrap();
+    let gve = Propagator::new::<RK89>(
+        GVEDynamics::new(j2),
+        PropOpts::with_fixed_step(30 * Unit::Second),
+    )
+    .with(gve_state, almanac)
+    .for_duration(prop_time)
+    .unwrap();
+
+    assert_eq!(gve.sc.orbit.epoch, cowell.orbit.epoch);
+    // The true longitude is integrated without wrapping, i.e. it increased by about 15 revolutions.
+    assert!(gve.elements()[5] > gve_state.elements()[5] + 14.0 * 2.0 * std::f64::consts::PI);
+
+    let (err_r, err_v) = rss_orbit_errors(&gve.sc.orbit, &cowell.orbit);
+    println!(
+        "GVE vs Cowell J2 propagation: {:.3} m\t{:.3} mm/s",
+        err_r * 1e3,
+        err_v * 1e6
+    );
+    assert!(err_r < 1e-3, "position error: {:.3} m", err_r * 1e3);
+    assert!(err_v < 1e-6, "velocity error: {:.3} mm/s", err_v * 1e6);
+}
@@ -624,3 +624,56 @@ fn tgt_aop_from_peri(almanac: Arc<Almanac>) {
         "Finite differencing result different from GMAT (greater than 6 m/s)."
     );
 }
+
+#[rstest]
+fn tgt_periapsis_altitude_in_track(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let orig_dt = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+
+    // Start at apoapsis so that an in-track burn only raises the periapsis
+    let xi_orig = Orbit::keplerian(8_000.0, 0.1, 30.0, 60.0, 60.0, 180.0, orig_dt, eme2k);
+
+    let spacecraft = Spacecraft::from_srp_defaults(xi_orig, 100.0, 0.0);
+
+    let dynamics = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::default_dp78(dynamics);
+
+    // Raise the periapsis altitude by 100 km
+    let eq_radius_km = eme2k.mean_equatorial_radius_km().unwrap();
+    let xf_desired_peri_alt = xi_orig.periapsis_km().unwrap() - eq_radius_km + 100.0;
+
+    let objectives = [Objective::within_tolerance(
+        StateParameter::PeriapsisRadius,
+        xf_desired_peri_alt + eq_radius_km,
+        1e-3,
+    )];
+
+    // Single in-track (velocity direction of the VNC frame) burn with a 1 mm/s perturbation
+    let variables = [Variable::from(Vary::VelocityX).with_pert(1e-6)];
+
+    let tgt = Optimizer::vnc_with_components(&setup, variables, objectives);
+
+    println!("{}", tgt);
+
+    let achievement_epoch = orig_dt + xi_orig.period().unwrap() / 4.0;
+
+    let solution = tgt
+        .try_achieve_from(spacecraft, orig_dt, achievement_epoch, almanac)
+        .unwrap();
+
+    println!("{}", solution);
+
+    let achieved_peri_alt = solution.achieved_state.orbit.periapsis_km().unwrap() - eq_radius_km;
+    assert!(
+        (achieved_peri_alt - xf_desired_peri_alt).abs() < 1e-3,
+        "periapsis altitude not achieved: {achieved_peri_alt} km"
+    );
+    assert!(solution.iterations <= tgt.iterations);
+    // The correction is purely in-track and prograde
+    assert!(solution.correction[0] > 0.0);
+    assert!(solution.correction[1].abs() < f64::EPSILON);
+    assert!(solution.correction[2].abs() < f64::EPSILON);
+}
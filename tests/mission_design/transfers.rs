@@ -0,0 +1,81 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::Almanac;
+use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
+use nyx::md::transfers::{bielliptic, hohmann};
+use nyx::propagators::Propagator;
+use nyx::time::{Epoch, Unit};
+use nyx::Spacecraft;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+/// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Example 6-1
+#[rstest]
+fn hohmann_leo_to_geo(almanac: Arc<Almanac>) {
+    let frame = EARTH_J2000.with_mu_km3_s2(398_600.4418);
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let r_leo_km = 6_378.137 + 191.344_11;
+    let r_geo_km = 6_378.137 + 35_781.348_57;
+
+    let plan = hohmann(r_leo_km, r_geo_km, epoch, frame).unwrap();
+    println!("{plan}");
+
+    assert_eq!(plan.burns_km_s.len(), 2);
+    assert!((plan.burns_km_s[0] - 2.457).abs() < 1e-3);
+    assert!((plan.burns_km_s[1] - 1.478).abs() < 1e-3);
+    assert!((plan.total_dv_km_s - 3.935).abs() < 1e-3);
+    assert!((plan.tof.to_unit(Unit::Hour) - 5.256).abs() < 1e-3);
+
+    // Propagating the transfer ellipse for the time of flight reaches the final orbit, up to the second burn.
+    assert_eq!(plan.orbits.len(), 3);
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let arrival = setup
+        .with(Spacecraft::from(plan.orbits[1]), almanac)
+        .for_duration(plan.tof)
+        .unwrap()
+        .orbit;
+    let target = plan.orbits[2];
+
+    assert_eq!(arrival.epoch, target.epoch);
+    assert!((arrival.radius_km - target.radius_km).norm() < 1e-3);
+    let dv2_km_s = (target.velocity_km_s - arrival.velocity_km_s).norm();
+    assert!((dv2_km_s - plan.burns_km_s[1]).abs() < 1e-6);
+}
+
+/// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Example 6-2
+#[test]
+fn bielliptic_beats_hohmann() {
+    let frame = EARTH_J2000.with_mu_km3_s2(398_600.4418);
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let r1_km = 6_378.137 + 191.344_11;
+    let rb_km = 6_378.137 + 503_873.0;
+    let r2_km = 6_378.137 + 376_310.0;
+
+    let plan = bielliptic(r1_km, rb_km, r2_km, epoch, frame).unwrap();
+    println!("{plan}");
+
+    assert_eq!(plan.burns_km_s.len(), 3);
+    assert_eq!(plan.orbits.len(), 4);
+    assert!((plan.total_dv_km_s - 3.904).abs() < 1e-3);
+    assert!((plan.tof.to_unit(Unit::Hour) - 593.92).abs() < 1e-2);
+
+    // For this ratio of radii, the bi-elliptic transfer is cheaper than the Hohmann transfer, but much longer.
+    let direct = hohmann(r1_km, r2_km, epoch, frame).unwrap();
+    assert!(plan.total_dv_km_s < direct.total_dv_km_s);
+    assert!(plan.tof > direct.tof);
+
+    // Each orbit of the plan starts at the epoch of its burn
+    assert_eq!(plan.orbits[0].epoch, epoch);
+    assert_eq!(plan.orbits[3].epoch, epoch + plan.tof);
+
+    assert!(hohmann(-1.0, r2_km, epoch, frame).is_err());
+}
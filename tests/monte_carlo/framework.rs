@@ -88,3 +88,64 @@ fn test_monte_carlo_epoch(almanac: Arc<Almanac>) {
     println!("Average final SMA = {} km", average_final_sma);
     println!("Average SMA = {} km", average_sma);
 }
+
+#[rstest]
+fn test_monte_carlo_estimate_dispersion(almanac: Arc<Almanac>) {
+    use nyx::linalg::SVector;
+    use nyx::od::estimate::KfEstimate;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_utc_at_midnight(2021, 1, 31);
+    let nominal_state = Spacecraft::from(Orbit::keplerian(
+        7_000.0, 1e-3, 28.5, 306.614, 314.19, 99.887_7, dt, eme2k,
+    ));
+
+    let prop = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let prop_time = 2 * Unit::Hour;
+
+    // A zero covariance does not disperse the states
+    let estimate = KfEstimate::from_diag(nominal_state, SVector::<f64, 9>::zeros());
+    let rslts = MonteCarlo::from_estimate(&estimate, "zero covariance".to_string(), Some(0))
+        .unwrap()
+        .run_for_duration(prop.clone(), almanac.clone(), prop_time, 10);
+
+    let stats = rslts.final_state_stats().unwrap();
+    assert_eq!(stats.num_runs, 10);
+    assert_eq!(stats.std_dev.norm(), 0.0);
+
+    let nominal_final = prop
+        .with(nominal_state, almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+    assert!((stats.mean.fixed_rows::<3>(0) - nominal_final.orbit.radius_km).norm() < 1e-9);
+
+    // With a 100 m and 10 cm/s position and velocity uncertainty, the final states are dispersed and reproducible with the seed
+    let mut diag = SVector::<f64, 9>::zeros();
+    for i in 0..3 {
+        diag[i] = 0.1_f64.powi(2);
+        diag[i + 3] = 1e-4_f64.powi(2);
+    }
+    let estimate = KfEstimate::from_diag(nominal_state, diag);
+
+    let run = |seed| {
+        MonteCarlo::from_estimate(&estimate, "dispersed".to_string(), Some(seed))
+            .unwrap()
+            .run_for_duration(prop.clone(), almanac.clone(), prop_time, 25)
+            .final_state_stats()
+            .unwrap()
+    };
+
+    let stats = run(0);
+    println!("mean: {}\nstd dev: {}", stats.mean, stats.std_dev);
+    for i in 0..3 {
+        assert!(stats.std_dev[i] > 0.01);
+    }
+    // The Cr, Cd, and fuel mass are not dispersed
+    for i in 6..9 {
+        assert_eq!(stats.std_dev[i], 0.0);
+    }
+
+    assert_eq!(stats.mean, run(0).mean);
+    assert_eq!(stats.std_dev, run(0).std_dev);
+    assert_ne!(stats.std_dev, run(1).std_dev);
+}
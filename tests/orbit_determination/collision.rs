@@ -0,0 +1,156 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::{EARTH_J2000, MOON_J2000};
+use nyx::cosmic::Orbit;
+use nyx::linalg::SVector;
+use nyx::od::estimate::KfEstimate;
+use nyx::od::{pc_2d, ODError};
+use nyx::time::{Epoch, Unit};
+use nyx::Spacecraft;
+use std::f64::consts::PI;
+
+/// Builds an estimate with the provided position variances (in km^2) on each axis.
+fn estimate(
+    radius_km: [f64; 3],
+    velocity_km_s: [f64; 3],
+    epoch: Epoch,
+    pos_var_km2: [f64; 3],
+) -> KfEstimate<Spacecraft> {
+    let frame = EARTH_J2000.with_mu_km3_s2(398_600.4418);
+    let orbit = Orbit::cartesian(
+        radius_km[0],
+        radius_km[1],
+        radius_km[2],
+        velocity_km_s[0],
+        velocity_km_s[1],
+        velocity_km_s[2],
+        epoch,
+        frame,
+    );
+    let mut diag = SVector::<f64, 9>::from_element(1e-6);
+    diag[0] = pos_var_km2[0];
+    diag[1] = pos_var_km2[1];
+    diag[2] = pos_var_km2[2];
+    KfEstimate::from_diag(Spacecraft::from(orbit), diag)
+}
+
+#[test]
+fn pc_2d_isotropic_head_on() {
+    let tca = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // Both objects are at the same position at the TCA, with an isotropic combined position covariance of 100 m (1-sigma).
+    let primary = estimate([7_000.0, 0.0, 0.0], [0.0, 7.5, 0.0], tca, [0.005; 3]);
+    let secondary = estimate([7_000.0, 0.0, 0.0], [0.0, 0.0, 7.5], tca, [0.005; 3]);
+
+    // The integral of an isotropic Gaussian over a centered disk is 1 - exp(-R^2 / (2 sigma^2)) (e.g. Chan, 2008).
+    let hbr_km = 0.02;
+    let sigma_km: f64 = 0.1;
+    let expected = 1.0 - (-hbr_km.powi(2) / (2.0 * sigma_km.powi(2))).exp();
+
+    let pc = pc_2d(&primary, &secondary, hbr_km).unwrap();
+    println!("Pc = {pc:.9e}\texpected = {expected:.9e}");
+    assert!(((pc - expected) / expected).abs() < 1e-8);
+
+    // The probability is symmetric
+    let pc_rev = pc_2d(&secondary, &primary, hbr_km).unwrap();
+    assert!((pc_rev - pc).abs() < 1e-12);
+}
+
+#[test]
+fn pc_2d_small_hard_body() {
+    let tca = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // The relative velocity is along Y, so the uncertainty along Y does not contribute.
+    let primary = estimate(
+        [7_000.0, 0.0, 0.0],
+        [0.0, 7.5, 0.0],
+        tca,
+        [0.02, 50.0, 0.005],
+    );
+    let secondary = estimate(
+        [7_000.3, 0.0, 0.2],
+        [0.0, -7.5, 0.0],
+        tca,
+        [0.02, 50.0, 0.005],
+    );
+
+    // For a hard body much smaller than the uncertainty, the Pc is the area of the disk times the density at its center.
+    let hbr_km = 1e-3;
+    let (var_x, var_z) = (0.04, 0.01);
+    let density = (-0.5 * (0.3_f64.powi(2) / var_x + 0.2_f64.powi(2) / var_z)).exp()
+        / (2.0 * PI * (var_x * var_z).sqrt());
+    let expected = PI * hbr_km.powi(2) * density;
+
+    let pc = pc_2d(&primary, &secondary, hbr_km).unwrap();
+    println!("Pc = {pc:.9e}\texpected = {expected:.9e}");
+    assert!(((pc - expected) / expected).abs() < 1e-3);
+
+    // Moving the secondary along the relative velocity does not change the Pc
+    let shifted = estimate(
+        [7_000.3, 1.0, 0.2],
+        [0.0, -7.5, 0.0],
+        tca,
+        [0.02, 50.0, 0.005],
+    );
+    let pc_shifted = pc_2d(&primary, &shifted, hbr_km).unwrap();
+    assert!(((pc_shifted - pc) / pc).abs() < 1e-12);
+}
+
+#[test]
+fn pc_2d_published_case() {
+    let tca = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    // Test case 1 of Serra et al., "Fast and Accurate Computation of Orbital Collision Probability for Short-Term Encounters", JGCD, 2016:
+    // in the conjunction plane, σx = 50 m and σy = 25 m, the miss vector is (10, 0) m, and the combined hard body radius is 5 m.
+    // The relative velocity is along (0, -1, 1), so the conjunction plane is spanned by X and (0, 1, 1). The combined covariance
+    // is split evenly between both objects.
+    let primary = estimate(
+        [7_000.0, 0.0, 0.0],
+        [0.0, 7.5, 0.0],
+        tca,
+        [1.25e-3, 3.125e-4, 3.125e-4],
+    );
+    let secondary = estimate(
+        [7_000.01, 0.0, 0.0],
+        [0.0, 0.0, 7.5],
+        tca,
+        [1.25e-3, 3.125e-4, 3.125e-4],
+    );
+
+    let pc = pc_2d(&primary, &secondary, 5e-3).unwrap();
+    println!("Pc = {pc:.9e}\tpublished = 9.742e-3");
+    assert!((pc - 9.742e-3).abs() < 1e-6);
+}
+
+#[test]
+fn pc_2d_errors() {
+    let tca = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+    let primary = estimate([7_000.0, 0.0, 0.0], [0.0, 7.5, 0.0], tca, [0.01; 3]);
+
+    // Co-moving objects have no conjunction plane
+    let co_moving = estimate([7_000.1, 0.0, 0.0], [0.0, 7.5, 0.0], tca, [0.01; 3]);
+    assert!(matches!(
+        pc_2d(&primary, &co_moving, 0.01),
+        Err(ODError::DegenerateEncounter { .. })
+    ));
+
+    // Both estimates must be at the TCA
+    let later = estimate(
+        [7_000.0, 0.0, 0.0],
+        [0.0, 0.0, 7.5],
+        tca + 1 * Unit::Second,
+        [0.01; 3],
+    );
+    assert!(matches!(
+        pc_2d(&primary, &later, 0.01),
+        Err(ODError::NonOverlappingEstimates { .. })
+    ));
+
+    // And in the same frame
+    let mut lunar = estimate([7_000.0, 0.0, 0.0], [0.0, 0.0, 7.5], tca, [0.01; 3]);
+    lunar.nominal_state.orbit.frame = MOON_J2000;
+    assert!(matches!(
+        pc_2d(&primary, &lunar, 0.01),
+        Err(ODError::EstimateFrameMismatch { .. })
+    ));
+}
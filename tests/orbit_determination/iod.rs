@@ -0,0 +1,144 @@
+extern crate nyx_space as nyx;
+
+use anise::constants::frames::EARTH_J2000;
+use anise::prelude::Almanac;
+use nyx::cosmic::Orbit;
+use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
+use nyx::linalg::Vector3;
+use nyx::od::iod::{gibbs, gooding, herrick_gibbs, three_positions, GoodingConfig};
+use nyx::od::ODError;
+use nyx::propagators::Propagator;
+use nyx::time::{Epoch, Unit};
+use nyx::Spacecraft;
+use rstest::*;
+use std::sync::Arc;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+/// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Example 7-3
+#[test]
+fn gibbs_vallado() {
+    let frame = EARTH_J2000.with_mu_km3_s2(398_600.4418);
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let r1_km = Vector3::new(0.0, 0.0, 6_378.137);
+    let r2_km = Vector3::new(0.0, -4_464.696, -5_102.509);
+    let r3_km = Vector3::new(0.0, 5_740.323, 3_189.068);
+
+    let orbit = gibbs(r1_km, r2_km, r3_km, epoch, frame).unwrap();
+    println!("{orbit:x}");
+
+    assert_eq!(orbit.epoch, epoch);
+    assert_eq!(orbit.radius_km, r2_km);
+    let v2_err = (orbit.velocity_km_s - Vector3::new(0.0, 5.531148, -5.191806)).norm();
+    assert!(v2_err < 1e-5, "velocity error of {v2_err:e} km/s");
+}
+
+/// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Example 7-4
+#[test]
+fn herrick_gibbs_vallado() {
+    let frame = EARTH_J2000.with_mu_km3_s2(398_600.4418);
+    let t1 = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+    let t2 = t1 + 76.48 * Unit::Second;
+    let t3 = t2 + 76.56 * Unit::Second;
+
+    let r1_km = Vector3::new(3_419.85564, 6_019.82602, 2_784.60022);
+    let r2_km = Vector3::new(2_935.91195, 6_326.18324, 2_660.59584);
+    let r3_km = Vector3::new(2_434.95202, 6_597.38674, 2_521.52311);
+
+    let orbit = herrick_gibbs(r1_km, r2_km, r3_km, t1, t2, t3, frame).unwrap();
+    println!("{orbit:x}");
+
+    assert_eq!(orbit.epoch, t2);
+    let v2_err = (orbit.velocity_km_s - Vector3::new(-6.441557, 3.777560, -1.720568)).norm();
+    assert!(v2_err < 1e-5, "velocity error of {v2_err:e} km/s");
+
+    // These positions are only a few degrees apart, so Herrick-Gibbs must be selected.
+    let selected = three_positions(r1_km, r2_km, r3_km, t1, t2, t3, frame).unwrap();
+    assert_eq!(selected.velocity_km_s, orbit.velocity_km_s);
+}
+
+#[test]
+fn iod_non_coplanar() {
+    let frame = EARTH_J2000.with_mu_km3_s2(398_600.4418);
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let r1_km = Vector3::new(7_000.0, 0.0, 0.0);
+    let r2_km = Vector3::new(0.0, 7_000.0, 0.0);
+    let r3_km = Vector3::new(0.0, 0.0, 7_000.0);
+
+    match gibbs(r1_km, r2_km, r3_km, epoch, frame) {
+        Err(ODError::NonCoplanarPositions { angle_deg, tol_deg }) => {
+            assert!(angle_deg > tol_deg);
+        }
+        other => panic!("expected a non coplanar error, got {other:?}"),
+    }
+}
+
+#[rstest]
+fn gooding_synthetic(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let t1 = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+    let epochs = [t1, t1 + 5 * Unit::Minute, t1 + 10 * Unit::Minute];
+
+    let truth0 = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, t1, eme2k);
+    let prop = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+
+    // Station on the equator, rotating with the Earth
+    let station_positions_km = epochs.map(|epoch| {
+        let theta = (0.25_f64 + 4.178_074e-3 * (epoch - t1).to_seconds()).to_radians();
+        Vector3::new(6_378.137 * theta.cos(), 6_378.137 * theta.sin(), 0.0)
+    });
+
+    let truths = epochs.map(|epoch| {
+        prop.with(Spacecraft::from(truth0), almanac.clone())
+            .until_epoch(epoch)
+            .unwrap()
+            .orbit
+    });
+
+    let los = [0, 1, 2].map(|i| truths[i].radius_km - station_positions_km[i]);
+
+    // Start ten percent away from the true ranges
+    let config = GoodingConfig::from_range_guess_km(1.1 * los[0].norm(), 0.9 * los[2].norm());
+
+    let orbit = gooding(
+        los[0],
+        los[1],
+        los[2],
+        station_positions_km,
+        epochs,
+        eme2k,
+        config,
+    )
+    .unwrap();
+    println!("{orbit:x}");
+
+    assert_eq!(orbit.epoch, epochs[1]);
+    let r_err_km = (orbit.radius_km - truths[1].radius_km).norm();
+    let v_err_km_s = (orbit.velocity_km_s - truths[1].velocity_km_s).norm();
+    assert!(r_err_km < 1e-2, "position error of {r_err_km:e} km");
+    assert!(v_err_km_s < 1e-5, "velocity error of {v_err_km_s:e} km/s");
+
+    // Non convergence is reported when the iterations are exhausted.
+    let config = GoodingConfig {
+        max_iterations: 1,
+        ..config
+    };
+    assert!(matches!(
+        gooding(
+            los[0],
+            los[1],
+            los[2],
+            station_positions_km,
+            epochs,
+            eme2k,
+            config,
+        ),
+        Err(ODError::Diverged { loops: 1 })
+    ));
+}
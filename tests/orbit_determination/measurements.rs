@@ -45,6 +45,7 @@ fn nil_measurement(almanac: Arc<Almanac>) {
         doppler_noise_km_s: Some(StochasticNoise::MIN),
         integration_time: None,
         light_time_correction: false,
+        time_tag: TimeTag::Receive,
     };
 
     let at_station = Orbit::try_latlongalt(
@@ -268,3 +269,336 @@ fn val_measurements_topo(almanac: Arc<Almanac>) {
         );
     }
 }
+
+#[rstest]
+fn doppler_shift_x_band(almanac: Arc<Almanac>) {
+    use nyx::cosmic::SPEED_OF_LIGHT_KM_S;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let station =
+        GroundStation::dss65_madrid(0.0, StochasticNoise::MIN, StochasticNoise::MIN, iau_earth);
+
+    // X-band downlink carrier
+    let carrier_hz = 8.4e9;
+
+    let orbit = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, epoch, eme2k);
+
+    let range_rate_km_s = station
+        .azimuth_elevation_of(orbit, &almanac)
+        .unwrap()
+        .range_rate_km_s;
+    let doppler_hz = station.doppler_hz(orbit, carrier_hz, &almanac).unwrap();
+    let first_order_hz = -2.0 * range_rate_km_s / SPEED_OF_LIGHT_KM_S * carrier_hz;
+
+    println!("range-rate = {range_rate_km_s:.6} km/s\tDoppler = {doppler_hz:.3} Hz\tfirst order = {first_order_hz:.3} Hz");
+
+    assert!(range_rate_km_s.abs() > 0.1);
+    assert_eq!(doppler_hz.signum(), -range_rate_km_s.signum());
+    // The relativistic correction is at the part per billion level of the carrier.
+    let correction_hz = doppler_hz - first_order_hz;
+    assert!(correction_hz.abs() > 0.0);
+    assert!(correction_hz.abs() < 1e-8 * carrier_hz);
+    assert!(
+        (correction_hz - 2.0 * (range_rate_km_s / SPEED_OF_LIGHT_KM_S).powi(2) * carrier_hz).abs()
+            < 1e-3
+    );
+}
+
+/// Tests that the angles-only measurements of a pass over a DSN station rise monotonically to a single culmination and then set.
+#[rstest]
+fn angles_measurements_pass(almanac: Arc<Almanac>) {
+    use nyx::time::{TimeUnits, Unit};
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let elevation_mask = 10.0;
+    let dss65_madrid = GroundStation::dss65_madrid(
+        elevation_mask,
+        StochasticNoise::MIN,
+        StochasticNoise::MIN,
+        iau_earth,
+    );
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2023, 2, 22);
+    let leo = Orbit::keplerian(6_778.0, 0.001, 51.6, 30.0, 40.0, 0.0, epoch, eme2k);
+
+    let (_, traj) = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()))
+        .with(leo.into(), almanac.clone())
+        .for_duration_with_traj(1 * Unit::Day)
+        .unwrap();
+
+    // Collect the first pass above the elevation mask.
+    let mut pass = Vec::new();
+    for state in traj.every(10.seconds()) {
+        let msr = dss65_madrid.measure_angles(&state.orbit, &almanac).unwrap();
+        if msr.visible() {
+            assert!(msr.elevation_deg() >= elevation_mask);
+            pass.push((state.orbit, msr));
+        } else if !pass.is_empty() {
+            assert!(msr.elevation_deg() < elevation_mask);
+            break;
+        }
+    }
+
+    assert!(pass.len() > 10, "expected a pass over Madrid");
+    println!(
+        "pass from {} to {} ({} msr)",
+        pass[0].1.epoch,
+        pass[pass.len() - 1].1.epoch,
+        pass.len()
+    );
+
+    // The angles match the AER computation of the station.
+    for (orbit, msr) in &pass {
+        let aer = dss65_madrid.azimuth_elevation_of(*orbit, &almanac).unwrap();
+        assert!((aer.elevation_deg - msr.elevation_deg()).abs() < 1e-6);
+        let az_diff = (aer.azimuth_deg - msr.azimuth_deg()).rem_euclid(360.0);
+        assert!(az_diff < 1e-6 || az_diff > 360.0 - 1e-6);
+    }
+
+    // The elevation rises monotonically up to the culmination, and then decreases monotonically.
+    let culmination = pass
+        .iter()
+        .enumerate()
+        .max_by(|(_, (_, a)), (_, (_, b))| a.elevation_deg().total_cmp(&b.elevation_deg()))
+        .map(|(i, _)| i)
+        .unwrap();
+
+    assert!(culmination > 0 && culmination < pass.len() - 1);
+
+    for i in 1..pass.len() {
+        let (prev, this) = (pass[i - 1].1, pass[i].1);
+        if i <= culmination {
+            assert!(this.elevation_deg() > prev.elevation_deg());
+            assert!(this.rates_deg_s[1] > 0.0 || i == culmination);
+        } else {
+            assert!(this.elevation_deg() < prev.elevation_deg());
+            assert!(this.rates_deg_s[1] < 0.0);
+        }
+    }
+
+    // The culmination is near the closest approach, and the elevation rate is about zero.
+    let closest = pass
+        .iter()
+        .enumerate()
+        .min_by(|(_, (a, _)), (_, (b, _))| {
+            let rho_a = dss65_madrid.azimuth_elevation_of(*a, &almanac).unwrap();
+            let rho_b = dss65_madrid.azimuth_elevation_of(*b, &almanac).unwrap();
+            rho_a.range_km.total_cmp(&rho_b.range_km)
+        })
+        .map(|(i, _)| i)
+        .unwrap();
+
+    let culm_msr = pass[culmination].1;
+    println!(
+        "culmination @ {}: el. = {:.3} deg, el. rate = {:.3e} deg/s",
+        culm_msr.epoch,
+        culm_msr.elevation_deg(),
+        culm_msr.rates_deg_s[1]
+    );
+    assert!(culmination.abs_diff(closest) <= 2);
+    assert!(culm_msr.rates_deg_s[1].abs() < 0.25);
+}
+
+/// Tests that the light time solution is anchored to the time tag of the measurements.
+#[allow(clippy::identity_op)]
+#[rstest]
+fn light_time_tagging(almanac: Arc<Almanac>) {
+    use nyx::cosmic::SPEED_OF_LIGHT_KM_S;
+    use nyx::time::Unit;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let start = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let orbit = Orbit::keplerian(26_000.0, 0.3, 55.0, 30.0, 45.0, 60.0, start, eme2k);
+
+    let (_, traj) = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()))
+        .with(orbit.into(), almanac.clone())
+        .for_duration_with_traj(2 * Unit::Hour)
+        .unwrap();
+
+    // Ignore the visibility, only the geometry matters here.
+    let mut station =
+        GroundStation::dss65_madrid(-90.0, StochasticNoise::MIN, StochasticNoise::MIN, iau_earth);
+    station.light_time_correction = true;
+
+    let with_tag = |time_tag: TimeTag| {
+        let mut tagged = station.clone();
+        tagged.time_tag = time_tag;
+        tagged
+    };
+
+    let mut transmit = with_tag(TimeTag::Transmit);
+    let mut receive = with_tag(TimeTag::Receive);
+    let mut bounce = with_tag(TimeTag::Bounce);
+
+    let mut mismatched_resid_km = Vec::new();
+    for minutes in [10.0, 30.0, 50.0, 70.0, 90.0, 110.0] {
+        let epoch = start + minutes * Unit::Minute;
+
+        let tx_msr = transmit
+            .measure(epoch, &traj, None, almanac.clone())
+            .unwrap()
+            .unwrap();
+        let rx_msr = receive
+            .measure(epoch, &traj, None, almanac.clone())
+            .unwrap()
+            .unwrap();
+        let bounce_msr = bounce
+            .measure(epoch, &traj, None, almanac.clone())
+            .unwrap()
+            .unwrap();
+
+        let range_km = bounce_msr.obs[0];
+        let range_rate_km_s = bounce_msr.obs[1];
+        let light_time_s = range_km / SPEED_OF_LIGHT_KM_S;
+
+        // Tagging at transmission observes the spacecraft one light time later than tagging at reception.
+        let delta_km = tx_msr.obs[0] - rx_msr.obs[0];
+        println!(
+            "{epoch}: range = {range_km:.3} km\trange-rate = {range_rate_km_s:.6} km/s\ttransmit - receive = {delta_km:.6} km"
+        );
+        assert!((delta_km - 2.0 * light_time_s * range_rate_km_s).abs() < 1e-3);
+        assert!((bounce_msr.obs[0] - 0.5 * (tx_msr.obs[0] + rx_msr.obs[0])).abs() < 1e-3);
+
+        // Data tagged at transmission: modeling it with the same convention removes the bias.
+        let data = tx_msr;
+        let matched = transmit
+            .measure(epoch, &traj, None, almanac.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(data.obs[0] - matched.obs[0], 0.0);
+        mismatched_resid_km.push(data.obs[0] - rx_msr.obs[0]);
+    }
+
+    // Modeling with the wrong convention leaves a bias of the light-time-worth of motion
+    assert!(mismatched_resid_km
+        .iter()
+        .all(|resid_km| resid_km.abs() > 1e-2));
+}
+
+#[rstest]
+fn look_angles_overhead_and_below(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let dss65_madrid =
+        GroundStation::dss65_madrid(0.0, StochasticNoise::MIN, StochasticNoise::MIN, iau_earth);
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2023, 2, 22);
+    let altitude_km = 500.0;
+
+    // An object fixed 500 km above the station, expressed in the inertial frame so that the rotation of the Earth must be accounted for.
+    let overhead_fixed = Orbit::try_latlongalt(
+        dss65_madrid.latitude_deg,
+        dss65_madrid.longitude_deg,
+        altitude_km,
+        0.0,
+        epoch,
+        iau_earth,
+    )
+    .unwrap();
+    let overhead = almanac.transform_to(overhead_fixed, eme2k, None).unwrap();
+
+    let look = dss65_madrid.look_angles(&overhead, &almanac).unwrap();
+    println!("overhead: {look}");
+    assert!((look.elevation_deg - 90.0).abs() < 1e-3);
+    assert!((look.range_km - (altitude_km - dss65_madrid.height_km)).abs() < 1e-3);
+    // It does not move with respect to the station
+    assert!(look.range_rate_km_s.abs() < 1e-6);
+
+    // An object above the antipode of the station is below its horizon.
+    let below_fixed = Orbit::try_latlongalt(
+        -dss65_madrid.latitude_deg,
+        dss65_madrid.longitude_deg + 180.0,
+        altitude_km,
+        0.0,
+        epoch,
+        iau_earth,
+    )
+    .unwrap();
+    let below = almanac.transform_to(below_fixed, eme2k, None).unwrap();
+
+    let look = dss65_madrid.look_angles(&below, &almanac).unwrap();
+    println!("below: {look}");
+    assert!(look.elevation_deg < -80.0);
+    assert!(look.range_km > 12_000.0);
+    assert!(look.range_rate_km_s.abs() < 1e-6);
+
+    // The look angles match the angles measurement without the noise
+    let leo = Orbit::keplerian(6_778.0, 0.001, 51.6, 30.0, 40.0, 0.0, epoch, eme2k);
+    let look = dss65_madrid.look_angles(&leo, &almanac).unwrap();
+    let msr = dss65_madrid.measure_angles(&leo, &almanac).unwrap();
+    println!("LEO: {look}");
+    assert!((look.azimuth_deg - msr.azimuth_deg()).abs() < 1e-9);
+    assert!((look.elevation_deg - msr.elevation_deg()).abs() < 1e-9);
+}
+
+/// Tests that the sensitivity of the angles measurements matches the finite differences of the measurement model.
+#[rstest]
+fn angles_sensitivity(almanac: Arc<Almanac>) {
+    use nyx::linalg::{DVector, Matrix2x3, Vector3};
+    use nyx::utils::FiniteDiff;
+    use nyx::Spacecraft;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let dss65_madrid =
+        GroundStation::dss65_madrid(0.0, StochasticNoise::ZERO, StochasticNoise::ZERO, iau_earth);
+    let station = AnglesStation::new(dss65_madrid, StochasticNoise::MIN, StochasticNoise::MIN);
+
+    // An object to the North East of the station, away from the zenith and from the wrapping of the azimuth.
+    let rx_fixed = Orbit::try_latlongalt(
+        station.station.latitude_deg + 10.0,
+        station.station.longitude_deg + 20.0,
+        20_000.0,
+        0.0,
+        epoch,
+        iau_earth,
+    )
+    .unwrap();
+    let rx = almanac.transform_to(rx_fixed, eme2k, None).unwrap();
+
+    let msr = station.station.measure_angles(&rx, &almanac).unwrap();
+    println!("{msr:?}");
+    assert!(msr.visible());
+
+    let device_loc = station.location(epoch, eme2k, almanac.clone()).unwrap();
+    let h_tilde = <Spacecraft as EstimateFrom<Spacecraft, AzElMeasurement>>::sensitivity(
+        &msr,
+        Spacecraft::from(rx),
+        device_loc,
+    );
+
+    let jac = FiniteDiff::central().jacobian(
+        |radius_km| {
+            let mut orbit = rx;
+            orbit.radius_km = Vector3::new(radius_km[0], radius_km[1], radius_km[2]);
+            DVector::from_column_slice(
+                station
+                    .station
+                    .measure_angles(&orbit, &almanac)
+                    .unwrap()
+                    .obs
+                    .as_slice(),
+            )
+        },
+        &DVector::from_column_slice(rx.radius_km.as_slice()),
+    );
+
+    let jac = Matrix2x3::from_column_slice(jac.as_slice());
+    let h_pos = h_tilde.fixed_columns::<3>(0).into_owned();
+    let rel_err = (h_pos - jac).norm() / jac.norm();
+    println!("H = {h_pos}\nFD = {jac}\nrelative error = {rel_err:e}");
+    // The topocentric frame of the sensitivity uses the geocentric zenith instead of the geodetic one.
+    assert!(rel_err < 1e-2);
+    // The velocity does not affect the angles
+    assert_eq!(h_tilde.fixed_columns::<3>(3).norm(), 0.0);
+}
@@ -7,7 +7,10 @@ use nyx::Spacecraft;
 use self::nyx::od::prelude::{Estimate, Filter, KfEstimate, KF};
 use self::nyx::State;
 
+mod collision;
+mod iod;
 mod measurements;
+mod multi_arc;
 mod multi_body;
 mod resid_reject;
 mod robust;
@@ -75,3 +78,78 @@ fn filter_errors() {
         }
     }
 }
+
+#[test]
+fn consider_range_bias() {
+    use self::nyx::linalg::{DMatrix, SVector, Vector1};
+    use na::{U1, U3};
+
+    // The range to a distant station along the X axis is only sensitive to the X position, and is affected by a constant bias.
+    let p0 = 1.0_f64;
+    let sigma_bias = 1e-2_f64;
+    let sigma_range = 1e-3_f64;
+
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        p0, p0, p0, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(Spacecraft::zeros().with_stm(), init_covar);
+
+    let mut sensitivity = SMatrix::<f64, 1, 9>::zeros();
+    sensitivity[(0, 0)] = 1.0;
+    let measurement_noise = SMatrix::<f64, 1, 1>::new(sigma_range.powi(2));
+    let obs = Vector1::new(0.0);
+
+    let mut ckf: KF<Spacecraft, U3, U1> = KF::no_snc(initial_estimate);
+    let mut skf: KF<Spacecraft, U3, U1> = KF::with_consider(
+        initial_estimate,
+        DMatrix::from_element(1, 1, sigma_bias.powi(2)),
+        DMatrix::from_element(1, 1, 1.0),
+    );
+
+    let mut ckf_est = initial_estimate;
+    let mut skf_est = initial_estimate;
+    for _ in 0..100 {
+        ckf.update_h_tilde(sensitivity);
+        ckf_est = ckf
+            .measurement_update(
+                Spacecraft::zeros().with_stm(),
+                &obs,
+                &obs,
+                measurement_noise,
+                None,
+            )
+            .unwrap()
+            .0;
+
+        skf.update_h_tilde(sensitivity);
+        skf_est = skf
+            .measurement_update(
+                Spacecraft::zeros().with_stm(),
+                &obs,
+                &obs,
+                measurement_noise,
+                None,
+            )
+            .unwrap()
+            .0;
+    }
+
+    // Even with infinitely many measurements, the X position cannot be known better than the bias allows.
+    let bias_floor = p0 * sigma_bias.powi(2) / (p0 + sigma_bias.powi(2));
+    println!(
+        "X variance: CKF {:.3e}\tSchmidt {:.3e}\tfloor {bias_floor:.3e}",
+        ckf_est.covar[(0, 0)],
+        skf_est.covar[(0, 0)]
+    );
+    assert!(ckf_est.covar[(0, 0)] < 1e-2 * bias_floor);
+    assert!(skf_est.covar[(0, 0)] >= bias_floor * (1.0 - 1e-9));
+
+    // The consider parameter inflates the covariance without changing the state or the unobserved components
+    assert!(skf_est.state_deviation.norm() < f64::EPSILON);
+    assert!((skf_est.covar[(1, 1)] - p0).abs() < f64::EPSILON);
+    assert!((skf_est.covar[(2, 2)] - p0).abs() < f64::EPSILON);
+    // The consider parameter itself is not updated
+    let consider = skf.consider.as_ref().unwrap();
+    assert_eq!(consider.covar[(0, 0)], sigma_bias.powi(2));
+    assert!(consider.cross_covar[(0, 0)] < 0.0);
+}
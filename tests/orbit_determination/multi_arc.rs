@@ -0,0 +1,241 @@
+use anise::{constants::frames::EARTH_J2000, prelude::Almanac};
+use nyx::cosmic::Orbit;
+use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
+use nyx::linalg::{DMatrix, DVector, Vector3};
+use nyx::od::batch::{MultiArcEstimator, MultiArcSolution};
+use nyx::propagators::Propagator;
+use nyx::time::{Duration, Epoch, Unit};
+use nyx::utils::{FiniteDiff, FiniteDiffStep};
+use nyx::Spacecraft;
+use rand_distr::{Distribution, Normal};
+use rand_pcg::Pcg64Mcg;
+use rstest::*;
+use std::sync::Arc;
+
+use crate::propagation::GMAT_EARTH_GM;
+
+#[fixture]
+fn almanac() -> Arc<Almanac> {
+    use crate::test_almanac_arcd;
+    test_almanac_arcd()
+}
+
+const NUM_MSR: usize = 72;
+const MSR_STEP: Duration = Duration::from_parts(0, 300_000_000_000);
+const MSR_SIGMA_KM: f64 = 1e-2;
+
+/// Propagates the orbit under two body dynamics with the provided gravitational parameter and returns the position every measurement step.
+fn positions(orbit: Orbit, mu_km3_s2: f64, almanac: Arc<Almanac>) -> Vec<Vector3<f64>> {
+    let mut orbit = orbit;
+    orbit.frame = orbit.frame.with_mu_km3_s2(mu_km3_s2);
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let mut prop = setup.with(Spacecraft::from(orbit), almanac);
+
+    (0..NUM_MSR)
+        .map(|_| prop.for_duration(MSR_STEP).unwrap().orbit.radius_km)
+        .collect()
+}
+
+/// Computes the partials of the position measurements with respect to the initial state (local) and the GM (global) by central differences.
+fn partials(orbit: Orbit, mu_km3_s2: f64, almanac: Arc<Almanac>) -> (DMatrix<f64>, DMatrix<f64>) {
+    let params = DVector::from_iterator(
+        7,
+        orbit
+            .to_cartesian_pos_vel()
+            .iter()
+            .copied()
+            .chain([mu_km3_s2]),
+    );
+
+    let jac = FiniteDiff::central()
+        .with_step(FiniteDiffStep::Relative(1e-7))
+        .jacobian(
+            |params| {
+                let mut this_orbit = orbit;
+                this_orbit.radius_km = Vector3::new(params[0], params[1], params[2]);
+                this_orbit.velocity_km_s = Vector3::new(params[3], params[4], params[5]);
+                DVector::from_iterator(
+                    3 * NUM_MSR,
+                    positions(this_orbit, params[6], almanac.clone())
+                        .iter()
+                        .flat_map(|pos| pos.iter().copied().collect::<Vec<f64>>()),
+                )
+            },
+            &params,
+        );
+
+    (
+        jac.columns(0, 6).into_owned(),
+        jac.columns(6, 1).into_owned(),
+    )
+}
+
+/// Iterates the multi-arc estimation of the initial states of each arc and of the shared GM.
+fn estimate(
+    nominals: &[Orbit],
+    observations: &[Vec<Vector3<f64>>],
+    mu_km3_s2: f64,
+    almanac: Arc<Almanac>,
+) -> (f64, Vec<Orbit>, MultiArcSolution) {
+    let mut nominals = nominals.to_vec();
+    let mut mu_km3_s2 = mu_km3_s2;
+
+    let mut iteration = 0;
+    loop {
+        iteration += 1;
+        let mut estimator = MultiArcEstimator::new(1);
+        for (nominal, obs) in nominals.iter().zip(observations) {
+            let computed = positions(*nominal, mu_km3_s2, almanac.clone());
+            let residuals = DVector::from_iterator(
+                3 * NUM_MSR,
+                obs.iter()
+                    .zip(computed.iter())
+                    .flat_map(|(o, c)| (o - c).iter().copied().collect::<Vec<f64>>()),
+            );
+            let (h_local, h_global) = partials(*nominal, mu_km3_s2, almanac.clone());
+            let weights = DVector::from_element(3 * NUM_MSR, MSR_SIGMA_KM.powi(-2));
+            estimator
+                .add_arc(&h_local, &h_global, &residuals, &weights)
+                .unwrap();
+        }
+
+        let sol = estimator.solve().unwrap();
+
+        mu_km3_s2 += sol.global_correction[0];
+        for (nominal, correction) in nominals.iter_mut().zip(&sol.local_corrections) {
+            for k in 0..3 {
+                nominal.radius_km[k] += correction[k];
+                nominal.velocity_km_s[k] += correction[k + 3];
+            }
+        }
+
+        if sol.global_correction[0].abs() < 1e-3 * sol.global_sigma(0) || iteration == 5 {
+            return (mu_km3_s2, nominals, sol);
+        }
+    }
+}
+
+#[rstest]
+fn multi_arc_gm_bias(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // The truth uses the GMAT GM, but the filter models a biased GM.
+    let true_mu_km3_s2 = GMAT_EARTH_GM;
+    let model_mu_km3_s2 = true_mu_km3_s2 + 10.0;
+
+    // Two independent arcs, on different orbits.
+    let truths = [
+        Orbit::keplerian(
+            7_500.0,
+            0.01,
+            45.0,
+            10.0,
+            20.0,
+            30.0,
+            Epoch::from_gregorian_utc_at_midnight(2023, 1, 1),
+            eme2k,
+        ),
+        Orbit::keplerian(
+            12_000.0,
+            0.1,
+            75.0,
+            120.0,
+            60.0,
+            0.0,
+            Epoch::from_gregorian_utc_at_midnight(2023, 2, 1),
+            eme2k,
+        ),
+    ];
+
+    let mut rng = Pcg64Mcg::new(2233);
+    let noise = Normal::new(0.0, MSR_SIGMA_KM).unwrap();
+
+    let observations: Vec<Vec<Vector3<f64>>> = truths
+        .iter()
+        .map(|truth| {
+            positions(*truth, true_mu_km3_s2, almanac.clone())
+                .iter()
+                .map(|pos| {
+                    pos + Vector3::new(
+                        noise.sample(&mut rng),
+                        noise.sample(&mut rng),
+                        noise.sample(&mut rng),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    // The initial guess of each arc is offset from the truth.
+    let nominals: Vec<Orbit> = truths
+        .iter()
+        .map(|truth| {
+            let mut nominal = *truth;
+            nominal.radius_km += Vector3::new(0.5, -0.3, 0.2);
+            nominal.velocity_km_s += Vector3::new(-1e-4, 2e-4, 1e-4);
+            nominal
+        })
+        .collect();
+
+    // Estimate the GM with each arc alone.
+    let mut single_sigmas = Vec::new();
+    for idx in 0..truths.len() {
+        let (mu_est, _, sol) = estimate(
+            &nominals[idx..idx + 1],
+            &observations[idx..idx + 1],
+            model_mu_km3_s2,
+            almanac.clone(),
+        );
+        println!(
+            "arc #{idx} alone: GM error = {:.3e} ± {:.3e} km^3/s^2",
+            mu_est - true_mu_km3_s2,
+            sol.global_sigma(0)
+        );
+        assert!((mu_est - true_mu_km3_s2).abs() < 3.0 * sol.global_sigma(0));
+        single_sigmas.push(sol.global_sigma(0));
+    }
+
+    // And with both arcs combined.
+    let (mu_est, estimates, sol) =
+        estimate(&nominals, &observations, model_mu_km3_s2, almanac.clone());
+    println!("{sol}");
+    let mu_err = mu_est - true_mu_km3_s2;
+    println!(
+        "multi-arc: GM error = {:.3e} ± {:.3e} km^3/s^2",
+        mu_err,
+        sol.global_sigma(0)
+    );
+
+    assert!(
+        mu_err.abs() < 3.0 * sol.global_sigma(0),
+        "multi-arc GM estimate inconsistent with its covariance"
+    );
+    assert!(
+        mu_err.abs() < 0.1 * (model_mu_km3_s2 - true_mu_km3_s2).abs(),
+        "multi-arc did not recover the GM bias"
+    );
+    for single_sigma in single_sigmas {
+        assert!(
+            sol.global_sigma(0) < single_sigma,
+            "multi-arc should determine the GM better than any single arc"
+        );
+    }
+
+    // Each arc's epoch state is also recovered.
+    for (idx, (truth, estimate)) in truths.iter().zip(&estimates).enumerate() {
+        let sigma_pos = sol.local_covars[idx]
+            .view((0, 0), (3, 3))
+            .diagonal()
+            .map(f64::sqrt);
+        println!("arc #{idx} position sigmas: {sigma_pos} km");
+        assert!(sigma_pos.iter().all(|s| *s < MSR_SIGMA_KM));
+        let pos_err = estimate.radius_km - truth.radius_km;
+        println!("arc #{idx} position error: {pos_err} km");
+        for k in 0..3 {
+            assert!(pos_err[k].abs() < 5.0 * sigma_pos[k]);
+        }
+    }
+}
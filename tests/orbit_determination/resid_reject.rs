@@ -381,3 +381,80 @@ fn od_resid_reject_default_ckf_two_way(
     assert!(delta.velocity_km_s.y < est.covar[(4, 4)].sqrt());
     assert!(delta.velocity_km_s.z < est.covar[(5, 5)].sqrt());
 }
+
+#[rstest]
+fn od_resid_reject_single_outlier(
+    tracking_arc: TrackingArc<RangeDoppler>,
+    initial_estimate: KfEstimate<Spacecraft>,
+    devices_n_configs: (Vec<GroundStation>, BTreeMap<String, TrkConfig>),
+    almanac: Arc<Almanac>,
+) {
+    let (devices, _configs) = devices_n_configs;
+
+    // Corrupt the range of a single measurement in the middle of the arc by 50 km.
+    let mut corrupted = tracking_arc.measurements.clone();
+    let outlier_idx = corrupted.len() / 2;
+    corrupted[outlier_idx].1.obs[0] += 50.0;
+    let outlier_epoch = corrupted[outlier_idx].1.epoch;
+    let outlier_tracker = corrupted[outlier_idx].0.clone();
+
+    // Use the same dynamics as the truth so that only the outlier may be rejected.
+    let bodies = vec![MOON, SUN, JUPITER_BARYCENTER, SATURN_BARYCENTER];
+    let estimator = SpacecraftDynamics::new(OrbitalDynamics::point_masses(bodies));
+    let setup = Propagator::new::<RK4Fixed>(estimator, PropOpts::with_fixed_step(10.seconds()));
+
+    // Runs the OD and returns the process and the estimate at the outlier measurement
+    let run = |measurements: &[(String, RangeDoppler)], editing: Option<f64>| {
+        let prop_est = setup.with(initial_estimate.nominal_state.with_stm(), almanac.clone());
+        let kf = KF::no_snc(initial_estimate);
+        let mut odp = ODProcess::ckf(prop_est, kf, None, almanac.clone());
+        if let Some(num_sigmas) = editing {
+            odp.set_measurement_editing(num_sigmas);
+        }
+
+        let mut devices_map = devices
+            .iter()
+            .map(|dev| (dev.name.clone(), dev.clone()))
+            .collect::<BTreeMap<_, _>>();
+
+        odp.process(
+            measurements,
+            &mut devices_map,
+            tracking_arc.min_duration_sep().unwrap(),
+        )
+        .unwrap();
+
+        let idx = odp
+            .residuals
+            .iter()
+            .position(|resid| {
+                matches!(resid, Some(resid) if resid.epoch == outlier_epoch
+                    && resid.tracker.as_ref() == Some(&outlier_tracker))
+            })
+            .unwrap();
+        let est_orbit = odp.estimates[idx].state().orbit;
+
+        (odp, est_orbit)
+    };
+
+    let (_, clean_orbit) = run(&tracking_arc.measurements, None);
+    let (edited_odp, edited_orbit) = run(&corrupted, Some(10.0));
+    let (unedited_odp, unedited_orbit) = run(&corrupted, None);
+
+    // The outlier is recorded as rejected only when editing is on.
+    assert!(edited_odp.rejected_epochs().contains(&outlier_epoch));
+    assert!(unedited_odp.rejected_epochs().is_empty());
+
+    let edited_err_km = (edited_orbit - clean_orbit).unwrap().rmag_km();
+    let unedited_err_km = (unedited_orbit - clean_orbit).unwrap().rmag_km();
+    println!("with editing: {edited_err_km:.6} km\twithout editing: {unedited_err_km:.6} km");
+
+    assert!(
+        edited_err_km < 0.1,
+        "estimate should be unaffected by the rejected outlier"
+    );
+    assert!(
+        unedited_err_km > 1.0,
+        "estimate should be corrupted by the outlier"
+    );
+}
@@ -230,6 +230,164 @@ fn od_robust_test_ekf_realistic_one_way(almanac: Arc<Almanac>) {
     );
 }
 
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_robust_test_ekf_potter_one_way(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    // Define the ground stations.
+    let ekf_num_meas = 300;
+    // Set the disable time to be very low to test enable/disable sequence
+    let ekf_disable_time = 3 * Unit::Minute;
+    let elevation_mask = 0.0;
+
+    let dss65_madrid = GroundStation::dss65_madrid(
+        elevation_mask,
+        StochasticNoise::default_range_km(),
+        StochasticNoise::default_doppler_km_s(),
+        iau_earth,
+    );
+    let dss34_canberra = GroundStation::dss34_canberra(
+        elevation_mask,
+        StochasticNoise::default_range_km(),
+        StochasticNoise::default_doppler_km_s(),
+        iau_earth,
+    );
+
+    // Define the tracking configurations
+    let configs = BTreeMap::from([
+        (
+            dss65_madrid.name.clone(),
+            TrkConfig::from_sample_rate(60.seconds()),
+        ),
+        (
+            dss34_canberra.name.clone(),
+            TrkConfig::from_sample_rate(60.seconds()),
+        ),
+    ]);
+
+    let all_stations = vec![dss65_madrid, dss34_canberra];
+
+    // Define the propagator information.
+    let prop_time = 1 * Unit::Day;
+    let step_size = 10.0 * Unit::Second;
+    let opts = PropOpts::with_fixed_step(step_size);
+
+    // Define state information.
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Spacecraft::from(Orbit::keplerian(
+        22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k,
+    ));
+
+    // Same dispersions as the realistic one way test.
+    let initial_estimate = KfEstimate::disperse_from_diag(
+        initial_state,
+        vec![
+            StateDispersion::zero_mean(StateParameter::Inclination, 0.0025),
+            StateDispersion::zero_mean(StateParameter::RAAN, 0.022),
+            StateDispersion::zero_mean(StateParameter::AoP, 0.02),
+        ],
+        Some(0),
+    )
+    .unwrap();
+
+    let initial_state_dev = initial_estimate.nominal_state;
+
+    let bodies = vec![MOON, SUN, JUPITER_BARYCENTER, SATURN_BARYCENTER];
+    let orbital_dyn = OrbitalDynamics::point_masses(bodies);
+    let truth_setup = Propagator::new::<RK4Fixed>(SpacecraftDynamics::new(orbital_dyn), opts);
+    let (_, traj) = truth_setup
+        .with(initial_state, almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+
+    // Simulate tracking data
+    let mut arc_sim = TrackingArcSim::with_seed(all_stations, traj.clone(), configs, 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+
+    let arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+
+    // Estimate without Saturn, and with the Potter square root measurement update.
+    let bodies = vec![MOON, SUN, JUPITER_BARYCENTER];
+    let estimator = SpacecraftDynamics::new(OrbitalDynamics::point_masses(bodies));
+    let setup = Propagator::new::<RK4Fixed>(estimator, opts);
+    let prop_est = setup.with(initial_state_dev.with_stm(), almanac.clone());
+
+    let sigma_q = 5e-10_f64.powi(2);
+    let process_noise = SNC3::from_diagonal(2 * Unit::Minute, &[sigma_q, sigma_q, sigma_q]);
+
+    let kf = KF::new(initial_estimate, process_noise.clone()).with_update(KfUpdate::Potter);
+
+    let trig = EkfTrigger::new(ekf_num_meas, ekf_disable_time);
+
+    let mut odp = ODProcess::ekf(prop_est, kf, trig, None, almanac.clone());
+
+    let subset = arc.filter_by_offset(..3.hours());
+    let remaining = arc.filter_by_offset(3.hours()..);
+
+    odp.process_arc::<GroundStation>(&subset).unwrap();
+    odp.iterate_arc::<GroundStation>(&subset, IterationConf::once())
+        .unwrap();
+
+    odp.process_arc::<GroundStation>(&remaining).unwrap();
+
+    // The square root update guarantees that the covariance remains positive semi-definite -- issue #164
+    for est in &odp.estimates {
+        for i in 0..6 {
+            assert!(
+                est.covar[(i, i)] >= 0.0,
+                "covar diagonal element negative @ [{i}, {i}] = {:.3e} at {}",
+                est.covar[(i, i)],
+                est.epoch()
+            );
+        }
+    }
+
+    let est = &odp.estimates[odp.estimates.len() - 1];
+    let final_truth_state = traj.at(est.epoch()).unwrap();
+
+    println!("Estimate:\n{}", est);
+    println!("Truth:\n{}", final_truth_state);
+
+    let delta = (est.state().orbit - final_truth_state.orbit).unwrap();
+    println!(
+        "RMAG error = {:.6} m\tVMAG error = {:.6} m/s",
+        delta.rmag_km() * 1e3,
+        delta.vmag_km_s() * 1e3
+    );
+
+    assert!(
+        delta.rmag_km() < 0.06,
+        "Position error should be less than 50 meters"
+    );
+    assert!(
+        delta.vmag_km_s() < 2e-4,
+        "Velocity error should be on centimeter level"
+    );
+
+    // The Joseph and Potter updates use the same measurement noise, so they must agree on the same first measurements.
+    let first_msr = arc.filter_by_offset(..30.minutes());
+    let mut covars = Vec::new();
+    for update in [KfUpdate::Joseph, KfUpdate::Potter] {
+        let kf = KF::new(initial_estimate, process_noise.clone()).with_update(update);
+        let prop_est = setup.with(initial_state_dev.with_stm(), almanac.clone());
+        let mut odp = ODProcess::ckf(prop_est, kf, None, almanac.clone());
+        odp.process_arc::<GroundStation>(&first_msr).unwrap();
+        covars.push(odp.estimates[odp.estimates.len() - 1].covar);
+    }
+    for i in 0..6 {
+        let rel_err = ((covars[0][(i, i)] - covars[1][(i, i)]) / covars[0][(i, i)]).abs();
+        assert!(
+            rel_err < 1e-6,
+            "Joseph and Potter covariances differ @ [{i}, {i}]: {:.6e} vs {:.6e}",
+            covars[0][(i, i)],
+            covars[1][(i, i)]
+        );
+    }
+}
+
 #[allow(clippy::identity_op)]
 #[rstest]
 fn od_robust_test_ekf_realistic_two_way(almanac: Arc<Almanac>) {
@@ -332,3 +332,53 @@ fn trkconfig_cadence(traj: Traj<Spacecraft>, devices: Vec<GroundStation>, almana
     // Regression
     assert_eq!(arc.measurements.len(), 215);
 }
+
+#[test]
+fn mixed_arc_records_roundtrip() {
+    use nyx_space::linalg::Vector2;
+
+    let epoch = Epoch::from_str("2023-02-22T19:18:17.16 UTC").unwrap();
+
+    let arc = TrackingArc {
+        device_cfg: String::new(),
+        measurements: vec![
+            (
+                "Madrid".to_string(),
+                MixedMeasurement::RangeDoppler(RangeDoppler {
+                    epoch,
+                    obs: Vector2::new(7_000.0, 1.5),
+                }),
+            ),
+            (
+                "Canberra".to_string(),
+                MixedMeasurement::AzEl(AzElMeasurement::from_observation(
+                    epoch + 10 * Unit::Second,
+                    Vector2::new(123.4, 45.6),
+                )),
+            ),
+            (
+                "Goldstone".to_string(),
+                MixedMeasurement::range(epoch + 20 * Unit::Second, 7_010.0),
+            ),
+            (
+                "Goldstone".to_string(),
+                MixedMeasurement::doppler(epoch + 30 * Unit::Second, -0.5),
+            ),
+        ],
+    };
+
+    // Each record carries the kind of its measurement and only the measured components.
+    let records = arc.to_records();
+    assert_eq!(records[1].kind, vec!["Azimuth (deg)", "Elevation (deg)"]);
+    assert_eq!(records[2].kind, vec!["Range (km)"]);
+    assert_eq!(records[2].observation, vec![7_010.0]);
+    assert_eq!(records[3].kind, vec!["Doppler (km/s)"]);
+
+    let serialized = serde_yaml::to_string(&arc).unwrap();
+    let reloaded: TrackingArc<MixedMeasurement> = serde_yaml::from_str(&serialized).unwrap();
+    assert_eq!(reloaded.measurements, arc.measurements);
+
+    // Angles cannot be reloaded in an arc of range and Doppler measurements.
+    assert!(TrackingArc::<RangeDoppler>::from_records(String::new(), &records).is_err());
+    assert!(TrackingArc::<RangeDoppler>::from_records(String::new(), &records[..1]).is_ok());
+}
@@ -474,7 +474,28 @@ fn od_tb_val_ckf_fixed_step_perfect_stations(
         .iter()
         .collect();
 
-    odp.to_parquet(path, ExportCfg::default()).unwrap();
+    let output_path = odp.to_parquet(path, ExportCfg::default()).unwrap();
+
+    // Read the estimates back to check the schema of the export
+    {
+        use nyx::time::TimeScale;
+        use polars::prelude::{ParquetReader, SerReader};
+        use std::fs::File;
+
+        let df = ParquetReader::new(File::open(output_path).unwrap())
+            .finish()
+            .unwrap();
+        assert_eq!(df.height(), odp.estimates.len());
+
+        let epochs = df.column("Epoch (UTC)").unwrap().str().unwrap();
+        assert_eq!(
+            epochs.get(0).unwrap(),
+            odp.estimates[0]
+                .epoch()
+                .to_time_scale(TimeScale::UTC)
+                .to_isoformat()
+        );
+    }
 
     // Check that there are no duplicates of epochs.
     let mut prev_epoch = odp.estimates[0].epoch();
@@ -926,6 +947,126 @@ fn od_tb_ckf_map_covar(almanac: Arc<Almanac>) {
     }
 }
 
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_predict_covar_snc(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let setup = Propagator::new::<RK4Fixed>(
+        SpacecraftDynamics::new(OrbitalDynamics::two_body()),
+        PropOpts::with_fixed_step(10 * Unit::Second),
+    );
+    let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(Spacecraft::from(initial_state), init_covar);
+
+    let sigma_q = 1e-8_f64.powi(2);
+    let process_noise = SNC3::from_diagonal(2 * Unit::Minute, &[sigma_q, sigma_q, sigma_q]);
+    let ckf = KF::new(initial_estimate, process_noise);
+
+    let mut odp: SpacecraftODProcess = ODProcess::ckf(prop_est, ckf, None, almanac);
+
+    let coast = 6 * Unit::Hour;
+    let predicted = odp.predict_for(30.seconds(), coast).unwrap();
+
+    assert_eq!(predicted.len(), odp.estimates.len());
+    assert_eq!(predicted.last().unwrap().epoch(), dt + coast);
+    assert!(predicted.iter().all(|est| est.predicted()));
+
+    // The STM preserves the volume of the uncertainty ellipsoid and the process noise inflates it,
+    // so the determinant of the position and velocity covariance grows monotonically during the coast.
+    let mut prev_det = init_covar.fixed_view::<6, 6>(0, 0).determinant();
+    let mut prev_epoch = dt;
+    for est in &predicted {
+        assert!(est.epoch() > prev_epoch);
+        let det = est.covar.fixed_view::<6, 6>(0, 0).determinant();
+        assert!(
+            det >= prev_det * (1.0 - 1e-9),
+            "covariance shrunk @ {}",
+            est.epoch()
+        );
+        prev_det = det;
+        prev_epoch = est.epoch();
+    }
+    assert!(prev_det > init_covar.fixed_view::<6, 6>(0, 0).determinant());
+
+    // The predicted estimates build the navigation trajectory
+    let traj = odp.to_traj().unwrap();
+    assert_eq!(traj.last().epoch(), dt + coast);
+}
+
+#[rstest]
+fn od_tb_ckf_predict_ric_snc(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+
+    let coast = 6 * Unit::Hour;
+    let sigma_q = 1e-8_f64.powi(2);
+
+    let mut final_estimates = Vec::new();
+    for process_noise in [
+        SNC3::from_diagonal(2 * Unit::Minute, &[0.0, 0.0, 0.0]),
+        SNC3::ric(2 * Unit::Minute, &[0.0, 0.0, sigma_q]),
+    ] {
+        let setup = Propagator::new::<RK4Fixed>(
+            SpacecraftDynamics::new(OrbitalDynamics::two_body()),
+            PropOpts::with_fixed_step(10 * Unit::Second),
+        );
+        let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+
+        let initial_estimate = KfEstimate::from_covar(Spacecraft::from(initial_state), init_covar);
+        let ckf = KF::new(initial_estimate, process_noise);
+
+        let mut odp: SpacecraftODProcess = ODProcess::ckf(prop_est, ckf, None, almanac.clone());
+        let predicted = odp.predict_for(30.seconds(), coast).unwrap();
+        final_estimates.push(*predicted.last().unwrap());
+    }
+
+    // Express the covariance inflation due to the process noise in the RIC frame of the final state
+    let final_orbit = final_estimates[1].state().orbit;
+    let dcm = SNC6::ric(2 * Unit::Minute, &[0.0; 6])
+        .dcm_to_inertial(&final_orbit.radius_km, &final_orbit.velocity_km_s)
+        .unwrap();
+
+    let delta_covar = final_estimates[1].covar.fixed_view::<6, 6>(0, 0)
+        - final_estimates[0].covar.fixed_view::<6, 6>(0, 0);
+    let delta_covar_ric = dcm.transpose() * delta_covar * dcm;
+    println!("RIC covariance inflation: {delta_covar_ric:.3e}");
+
+    // The cross-track position and velocity variances are inflated ...
+    assert!(delta_covar_ric[(2, 2)] > 0.0);
+    assert!(delta_covar_ric[(5, 5)] > 0.0);
+
+    // ... and in two body dynamics, the cross-track motion is decoupled from the in-plane motion, so nothing else is.
+    let scale = delta_covar_ric[(2, 2)].max(delta_covar_ric[(5, 5)]);
+    for i in 0..6 {
+        for j in 0..6 {
+            if [2, 5].contains(&i) && [2, 5].contains(&j) {
+                continue;
+            }
+            assert!(
+                delta_covar_ric[(i, j)].abs() < 1e-6 * scale,
+                "in-plane covariance ({i}, {j}) inflated by {:.3e}",
+                delta_covar_ric[(i, j)]
+            );
+        }
+    }
+}
+
 #[allow(clippy::identity_op)]
 #[rstest]
 fn od_tb_val_harmonics_ckf_fixed_step_perfect(
@@ -1151,3 +1292,1421 @@ fn od_tb_ckf_fixed_step_perfect_stations_several_snc_covar_map(
     assert!(delta.rmag_km() < 2e-16, "Position error should be zero");
     assert!(delta.vmag_km_s() < 2e-16, "Velocity error should be zero");
 }
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_data_gaps(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    // Define the tracking configurations
+    let cfg = TrkConfig::builder()
+        .sampling(10.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(10.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(1 * Unit::Day).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    // Remove six hours of measurements to create an intentional data gap.
+    let gap_start = dt + 6 * Unit::Hour;
+    let gap_end = dt + 12 * Unit::Hour;
+    arc.measurements
+        .retain(|(_, msr)| msr.epoch() < gap_start || msr.epoch() > gap_end);
+
+    let last_before = arc
+        .measurements
+        .iter()
+        .map(|(_, msr)| msr.epoch())
+        .filter(|epoch| *epoch < gap_start)
+        .max()
+        .unwrap();
+    let first_after = arc
+        .measurements
+        .iter()
+        .map(|(_, msr)| msr.epoch())
+        .filter(|epoch| *epoch > gap_end)
+        .min()
+        .unwrap();
+
+    let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(prop_est, KF::no_snc(initial_estimate), None, almanac);
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    let threshold = 1 * Unit::Hour;
+    let gaps = odp.data_gaps(threshold);
+    for (start, end, duration) in &gaps {
+        println!("data gap from {start} to {end} ({duration})");
+        assert!(*duration > threshold);
+        assert_eq!(*end - *start, *duration);
+    }
+
+    assert!(
+        gaps.contains(&(last_before, first_after, first_after - last_before)),
+        "intentional gap not reported"
+    );
+    assert!(first_after - last_before >= 6 * Unit::Hour);
+
+    // The largest measurement interval is at least as long as the intentional gap.
+    let intervals = odp.measurement_intervals();
+    assert_eq!(
+        intervals.len(),
+        odp.residuals.iter().flatten().count() - 1,
+        "no measurement should have been rejected"
+    );
+    assert!(intervals.iter().max().unwrap() >= &(first_after - last_before));
+
+    // A threshold longer than any interval reports no gap.
+    assert!(odp.data_gaps(*intervals.iter().max().unwrap()).is_empty());
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_light_time_correction(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    // The measurements are formed with light time correction.
+    let sim_devices: Vec<GroundStation> = sim_devices
+        .into_iter()
+        .map(|mut gs| {
+            gs.light_time_correction = true;
+            gs
+        })
+        .collect();
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (final_truth, traj) = prop.for_duration_with_traj(1 * Unit::Day).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+
+    // Process the same measurements with and without light time correction in the measurement model.
+    let mut errors_km = Vec::new();
+    let mut max_prefit_km = Vec::new();
+    for light_time_correction in [true, false] {
+        let devices: Vec<GroundStation> = proc_devices
+            .iter()
+            .cloned()
+            .map(|mut gs| {
+                gs.light_time_correction = light_time_correction;
+                gs
+            })
+            .collect();
+
+        let mut arc = arc.clone();
+        arc.set_devices(devices, configs.clone()).unwrap();
+
+        let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+        let initial_estimate = KfEstimate::from_covar(initial_state.into(), init_covar);
+
+        let mut odp = ODProcess::ckf(
+            prop_est,
+            KF::no_snc(initial_estimate),
+            None,
+            almanac.clone(),
+        );
+        odp.process_arc::<GroundStation>(&arc).unwrap();
+
+        let est = odp.estimates.last().unwrap();
+        let err_km = (est.state().orbit.radius_km - final_truth.orbit.radius_km).norm();
+        let max_prefit = odp
+            .residuals
+            .iter()
+            .flatten()
+            .map(|resid| resid.prefit[0].abs())
+            .fold(0.0, f64::max);
+
+        println!(
+            "light time correction = {light_time_correction}: position error = {:.3} m, max range prefit = {:.3} m",
+            err_km * 1e3,
+            max_prefit * 1e3
+        );
+        errors_km.push(err_km);
+        max_prefit_km.push(max_prefit);
+    }
+
+    // Consistent modeling leads to an estimate on the truth.
+    assert!(errors_km[0] < 1e-3, "consistent modeling should converge");
+    assert!(max_prefit_km[0] < 1e-5);
+    // Inconsistent modeling biases the range residuals by the range rate times the light time, and therefore the estimate.
+    assert!(max_prefit_km[1] > 1e-2, "missing light time residual bias");
+    assert!(
+        errors_km[1] > 10.0 * errors_km[0].max(1e-3),
+        "inconsistent modeling should bias the estimate"
+    );
+}
+
+#[rstest]
+fn od_tb_ckf_light_time_tags(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (final_truth, traj) = prop.for_duration_with_traj(1 * Unit::Day).unwrap();
+
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+
+    // The OD process only knows the trajectory up to the measurement epoch, yet measurements tagged at transmission
+    // observe the spacecraft one light time later, and those tagged at the bounce observe the station over both legs.
+    for time_tag in [TimeTag::Transmit, TimeTag::Bounce] {
+        let with_tag = |devices: &[GroundStation]| -> Vec<GroundStation> {
+            devices
+                .iter()
+                .cloned()
+                .map(|mut gs| {
+                    gs.light_time_correction = true;
+                    gs.time_tag = time_tag;
+                    gs
+                })
+                .collect()
+        };
+
+        let mut arc_sim =
+            TrackingArcSim::with_seed(with_tag(&sim_devices), traj.clone(), configs.clone(), 0)
+                .unwrap();
+        arc_sim.build_schedule(almanac.clone()).unwrap();
+        let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+        arc.set_devices(with_tag(&proc_devices), configs.clone())
+            .unwrap();
+
+        let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+        let initial_estimate = KfEstimate::from_covar(initial_state.into(), init_covar);
+
+        let mut odp = ODProcess::ckf(
+            prop_est,
+            KF::no_snc(initial_estimate),
+            None,
+            almanac.clone(),
+        );
+        odp.process_arc::<GroundStation>(&arc).unwrap();
+
+        let est = odp.estimates.last().unwrap();
+        let err_km = (est.state().orbit.radius_km - final_truth.orbit.radius_km).norm();
+        let max_prefit_km = odp
+            .residuals
+            .iter()
+            .flatten()
+            .map(|resid| resid.prefit[0].abs())
+            .fold(0.0, f64::max);
+
+        println!(
+            "{time_tag:?}: position error = {:.3} m, max range prefit = {:.3} m",
+            err_km * 1e3,
+            max_prefit_km * 1e3
+        );
+
+        assert!(err_km < 1e-3, "{time_tag:?} should converge");
+        assert!(max_prefit_km < 1e-5, "{time_tag:?} residuals are biased");
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_angles_only(almanac: Arc<Almanac>, sim_devices: Vec<GroundStation>) {
+    let _ = pretty_env_logger::try_init();
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(1 * Unit::Day).unwrap();
+
+    // The angles stations track whenever the spacecraft is above their horizon.
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .strands(vec![Strand {
+            start: dt,
+            end: dt + 1 * Unit::Day,
+        }])
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let sim_angles = sim_devices
+        .into_iter()
+        .map(|gs| AnglesStation::new(gs, StochasticNoise::ZERO, StochasticNoise::ZERO))
+        .collect::<Vec<_>>();
+
+    let proc_angles = sim_angles
+        .iter()
+        .cloned()
+        .map(|mut dev| {
+            dev.azimuth_noise_deg = StochasticNoise::MIN;
+            dev.elevation_noise_deg = StochasticNoise::MIN;
+            dev
+        })
+        .collect::<Vec<_>>();
+
+    let mut arc_sim =
+        TrackingArcSim::with_seed(sim_angles, traj.clone(), configs.clone(), 0).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_angles, configs).unwrap();
+    assert!(arc.measurements.len() > 100, "too few angles measurements");
+
+    // Start the filter one kilometer away from the truth.
+    let mut initial_guess = initial_state;
+    initial_guess.radius_km.x += 1.0;
+
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        10.0, 10.0, 10.0, 1e-4, 1e-4, 1e-4, 0.0, 0.0, 0.0,
+    ]));
+
+    let prop_est = setup.with(Spacecraft::from(initial_guess).with_stm(), almanac.clone());
+    let initial_estimate = KfEstimate::from_covar(initial_guess.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(
+        prop_est,
+        KF::no_snc(initial_estimate),
+        None,
+        almanac.clone(),
+    );
+    odp.process_arc::<AnglesStation>(&arc).unwrap();
+
+    let est = odp.estimates.last().unwrap();
+    let truth = traj.at(est.epoch()).unwrap();
+    let err_km = (est.state().orbit.radius_km - truth.orbit.radius_km).norm();
+    println!("angles only position error = {:.3} m", err_km * 1e3);
+
+    assert!(err_km < 5e-2, "angles only OD should converge");
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_apriori_estimates(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(12 * Unit::Hour).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(prop_est, KF::no_snc(initial_estimate), None, almanac);
+    odp.record_apriori = true;
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    // One a priori estimate is recorded per measurement update.
+    let aposteriori: Vec<_> = odp
+        .estimates
+        .iter()
+        .zip(odp.residuals.iter())
+        .filter_map(|(est, resid)| resid.as_ref().map(|_| est))
+        .collect();
+
+    assert!(!aposteriori.is_empty());
+    assert_eq!(odp.apriori_estimates.len(), aposteriori.len());
+
+    for (apriori, aposteriori) in odp.apriori_estimates.iter().zip(aposteriori) {
+        assert_eq!(apriori.epoch(), aposteriori.epoch());
+        assert_eq!(apriori.covar, aposteriori.covar_bar);
+        // The measurement update can only reduce the variances.
+        for i in 0..6 {
+            assert!(
+                apriori.covar[(i, i)] >= aposteriori.covar[(i, i)],
+                "a priori variance #{i} smaller than the a posteriori one @ {}",
+                apriori.epoch()
+            );
+        }
+        assert!(apriori.covar.trace() > aposteriori.covar.trace());
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_ric_state_deviation(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let sma_km = 22000.0;
+    let initial_state = Orbit::keplerian(sma_km, 0.0, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(12 * Unit::Hour).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    // The nominal state lags the truth by one kilometer along its circular orbit: a purely in-track error.
+    let in_track_err_km = 1.0;
+    let initial_state_est = Orbit::keplerian(
+        sma_km,
+        0.0,
+        30.0,
+        80.0,
+        40.0,
+        -(in_track_err_km / sma_km).to_degrees(),
+        dt,
+        eme2k,
+    );
+
+    let prop_est = setup.with(
+        Spacecraft::from(initial_state_est).with_stm(),
+        almanac.clone(),
+    );
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        10.0, 10.0, 10.0, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state_est.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(prop_est, KF::no_snc(initial_estimate), None, almanac);
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    // The correction of the nominal state is predominantly in-track.
+    let deviation_ric = odp.estimates.last().unwrap().state_deviation_ric().unwrap();
+    println!("RIC state deviation: {deviation_ric}");
+
+    assert!((deviation_ric[1] - in_track_err_km).abs() < 0.1 * in_track_err_km);
+    assert!(deviation_ric[0].abs() < 0.1 * deviation_ric[1].abs());
+    assert!(deviation_ric[2].abs() < 0.1 * deviation_ric[1].abs());
+
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "output_data",
+        "tb_ckf_ric_deviation.parquet",
+    ]
+    .iter()
+    .collect();
+
+    odp.to_parquet(path, ExportCfg::builder().ric_state_deviation(true).build())
+        .unwrap();
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_iterate_state_tol(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(12 * Unit::Hour).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    // Start the filter from a dispersed state.
+    let mut initial_state_dev = initial_state;
+    initial_state_dev.radius_km.x += 1.0;
+    initial_state_dev.radius_km.y -= 0.5;
+
+    let prop_est = setup.with(
+        Spacecraft::from(initial_state_dev).with_stm(),
+        almanac.clone(),
+    );
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        10.0, 10.0, 10.0, 1e-4, 1e-4, 1e-4, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state_dev.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(prop_est, KF::no_snc(initial_estimate), None, almanac);
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    // Disable the residual criteria such that only the epoch state change can stop the iterations early.
+    let state_tol_km = 1e-3;
+    let config = IterationConf::builder()
+        .absolute_tol(0.0)
+        .relative_tol(0.0)
+        .max_divergences(usize::MAX)
+        .max_iterations(10)
+        .state_tol_km(state_tol_km)
+        .build();
+    println!("{config}");
+
+    odp.iterate_arc::<GroundStation>(&arc, config).unwrap();
+
+    let changes = &odp.iteration_state_changes_km;
+    println!("epoch state changes: {changes:?}");
+
+    // The iterations stopped as soon as the epoch state change dropped below the tolerance, before the maximum number of iterations.
+    assert!(!changes.is_empty());
+    assert!(changes.len() < config.max_iterations);
+    assert!(*changes.last().unwrap() < state_tol_km);
+    for change in &changes[..changes.len() - 1] {
+        assert!(*change >= state_tol_km);
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_smooth_last(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(12 * Unit::Hour).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    let mut initial_state_dev = initial_state;
+    initial_state_dev.radius_km.x += 1.0;
+    initial_state_dev.radius_km.y -= 0.5;
+
+    let prop_est = setup.with(
+        Spacecraft::from(initial_state_dev).with_stm(),
+        almanac.clone(),
+    );
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        10.0, 10.0, 10.0, 1e-4, 1e-4, 1e-4, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state_dev.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(prop_est, KF::no_snc(initial_estimate), None, almanac);
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    let num_smoothed = 100;
+    let num_est = odp.estimates.len();
+    assert!(num_est > 2 * num_smoothed);
+
+    let smoothed = odp.smooth(SmoothingArc::Last(num_smoothed)).unwrap();
+    assert_eq!(smoothed.len(), num_est);
+
+    // The earlier estimates are untouched.
+    for (smoothed_est, est) in smoothed
+        .iter()
+        .zip(odp.estimates.iter())
+        .take(num_est - num_smoothed)
+    {
+        assert_eq!(smoothed_est, est);
+    }
+
+    // The later ones account for subsequent data, so their uncertainty can only decrease, and does for at least some estimates.
+    let mut improved = 0;
+    for (smoothed_est, est) in smoothed
+        .iter()
+        .zip(odp.estimates.iter())
+        .skip(num_est - num_smoothed)
+    {
+        assert_eq!(smoothed_est.epoch(), est.epoch());
+        let smoothed_pos_var = smoothed_est.covar.fixed_view::<3, 3>(0, 0).trace();
+        let pos_var = est.covar.fixed_view::<3, 3>(0, 0).trace();
+        assert!(smoothed_pos_var <= pos_var * (1.0 + 1e-9));
+        if smoothed_pos_var < pos_var * (1.0 - 1e-6) {
+            improved += 1;
+        }
+    }
+    assert!(improved > 0, "smoothing did not improve any estimate");
+
+    // Same with a time window, which leaves the estimates after the window untouched too.
+    let start = odp.estimates[num_est / 4].epoch();
+    let end = odp.estimates[num_est / 2].epoch();
+    let smoothed = odp.smooth(SmoothingArc::TimeWindow(start, end)).unwrap();
+    assert_eq!(smoothed.len(), num_est);
+    for (smoothed_est, est) in smoothed.iter().zip(odp.estimates.iter()) {
+        if est.epoch() < start || est.epoch() >= end {
+            assert_eq!(smoothed_est, est);
+        }
+    }
+    assert!(smoothed
+        .iter()
+        .zip(odp.estimates.iter())
+        .any(|(smoothed_est, est)| smoothed_est != est));
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_smooth_retained_stm(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(6 * Unit::Hour).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    let mut initial_state_dev = initial_state;
+    initial_state_dev.radius_km.x += 1.0;
+    initial_state_dev.radius_km.y -= 0.5;
+
+    let prop_est = setup.with(
+        Spacecraft::from(initial_state_dev).with_stm(),
+        almanac.clone(),
+    );
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        10.0, 10.0, 10.0, 1e-4, 1e-4, 1e-4, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state_dev.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(
+        prop_est,
+        KF::no_snc(initial_estimate),
+        None,
+        almanac.clone(),
+    );
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    let num_est = odp.estimates.len();
+    let last = &odp.estimates[num_est - 1];
+
+    let smoothed = odp.smooth(SmoothingArc::All).unwrap();
+    assert_eq!(&smoothed[num_est - 1], last);
+
+    // Without process noise, the smoothed estimates are the last estimate, which uses all of the measurements, mapped back to
+    // their epoch. Re-propagate the reference trajectory of the filter to map it back independently of the retained STMs.
+    for k in (0..num_est - 1).step_by(37) {
+        let mut nominal = odp.estimates[k].nominal_state;
+        nominal.reset_stm();
+        let repropagated = setup
+            .with(nominal, almanac.clone())
+            .until_epoch(last.epoch())
+            .unwrap();
+        let phi_inv = repropagated.stm().unwrap().try_inverse().unwrap();
+
+        let x_k = phi_inv * last.state_deviation;
+        let p_k = phi_inv * last.covar * phi_inv.transpose();
+
+        assert!(
+            (smoothed[k].state_deviation - x_k).norm() <= 1e-5 * x_k.norm(),
+            "smoothed state deviation of estimate #{k} differs from the mapped last estimate"
+        );
+        assert!(
+            (smoothed[k].covar - p_k).norm() <= 1e-4 * p_k.norm(),
+            "smoothed covariance of estimate #{k} differs from the mapped last estimate"
+        );
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_heterogeneous_stations(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    // Madrid measures range and Doppler, Canberra the angles, and Goldstone the range only.
+    let mixed = |devices: Vec<GroundStation>, angles_noise_deg: StochasticNoise| {
+        devices
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut gs)| match i {
+                1 => {
+                    MixedDevice::Angles(AnglesStation::new(gs, angles_noise_deg, angles_noise_deg))
+                }
+                2 => {
+                    gs.doppler_noise_km_s = None;
+                    MixedDevice::Radiometric(gs)
+                }
+                _ => MixedDevice::Radiometric(gs),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let sim_devices = mixed(sim_devices, StochasticNoise::ZERO);
+    let proc_devices = mixed(proc_devices, StochasticNoise::MIN);
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(1 * Unit::Day).unwrap();
+
+    // All stations track whenever the spacecraft is above their horizon.
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .strands(vec![Strand {
+            start: dt,
+            end: dt + 1 * Unit::Day,
+        }])
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name(), cfg.clone());
+    }
+
+    let mut arc_sim =
+        TrackingArcSim::with_seed(sim_devices, traj.clone(), configs.clone(), 0).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices.clone(), configs).unwrap();
+
+    // Each station provides the kind of data it measures.
+    let mut num_per_device = BTreeMap::new();
+    for (name, msr) in &arc.measurements {
+        if name == &proc_devices[0].name() {
+            assert!(matches!(msr, MixedMeasurement::RangeDoppler(_)));
+        } else if name == &proc_devices[1].name() {
+            assert!(matches!(msr, MixedMeasurement::AzEl(_)));
+        } else {
+            assert!(matches!(msr, MixedMeasurement::Range(_)));
+            assert_eq!(msr.dimension(), 1);
+        }
+        *num_per_device.entry(name.clone()).or_insert(0) += 1;
+    }
+    println!("{num_per_device:?}");
+    assert_eq!(num_per_device.len(), 3, "all stations should provide data");
+
+    let mut initial_state_dev = initial_state;
+    initial_state_dev.radius_km.x += 1.0;
+    initial_state_dev.radius_km.y -= 0.5;
+
+    let prop_est = setup.with(
+        Spacecraft::from(initial_state_dev).with_stm(),
+        almanac.clone(),
+    );
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        10.0, 10.0, 10.0, 1e-4, 1e-4, 1e-4, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state_dev.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(prop_est, KF::no_snc(initial_estimate), None, almanac);
+    odp.process_arc::<MixedDevice>(&arc).unwrap();
+
+    // The padding of the range only measurements does not contribute to the residuals.
+    for residual in odp.residuals.iter().flatten() {
+        if residual.tracker.as_ref() == Some(&proc_devices[2].name()) {
+            assert_eq!(residual.prefit[1], 0.0);
+            assert_eq!(residual.postfit[1], 0.0);
+        }
+    }
+
+    // The mixed data types lead to a converged solution.
+    let est = odp.estimates.last().unwrap();
+    println!("{est}");
+    let truth = traj.at(est.epoch()).unwrap();
+    let err_km = (est.state().orbit.radius_km - truth.orbit.radius_km).norm();
+    let err_km_s = (est.state().orbit.velocity_km_s - truth.orbit.velocity_km_s).norm();
+    println!("position error: {err_km:.3e} km\tvelocity error: {err_km_s:.3e} km/s");
+    assert!(err_km < 1e-2, "position error too large: {err_km} km");
+    assert!(err_km_s < 1e-5, "velocity error too large: {err_km_s} km/s");
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_batch_sequential_agreement(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+    let mut prop = setup.with(initial_state.into(), almanac.clone());
+    let (_, traj) = prop.for_duration_with_traj(1 * Unit::Day).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    let mut initial_state_dev = initial_state;
+    initial_state_dev.radius_km.x += 0.1;
+    initial_state_dev.velocity_km_s.z -= 1e-5;
+
+    let prop_est = setup.with(
+        Spacecraft::from(initial_state_dev).with_stm(),
+        almanac.clone(),
+    );
+    // The batch requires an invertible a priori covariance.
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1.0, 1.0, 1.0, 1e-6, 1e-6, 1e-6, 1e-2, 1e-2, 1e-2,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state_dev.into(), init_covar);
+
+    let mut odp = ODProcess::ckf(
+        prop_est,
+        KF::no_snc(initial_estimate),
+        None,
+        almanac.clone(),
+    );
+    odp.process_arc::<GroundStation>(&arc).unwrap();
+
+    let mut devices = arc.rebuild_devices::<Spacecraft, GroundStation>().unwrap();
+    let comparison =
+        compare_batch_sequential(&odp, &initial_estimate, &arc.measurements, &mut devices).unwrap();
+    println!("{comparison}");
+
+    assert_eq!(comparison.epoch, dt);
+    assert!(comparison.num_msr > 0);
+
+    // Both estimators recover the initial deviation ...
+    let truth_dev_km = initial_state.radius_km - initial_state_dev.radius_km;
+    let batch_err_km = (comparison.batch_state_deviation.fixed_rows::<3>(0) - truth_dev_km).norm();
+    println!("batch epoch position error: {:.3} m", batch_err_km * 1e3);
+    assert!(batch_err_km < 1e-3, "batch error: {batch_err_km:e} km");
+
+    // ... and agree with each other to sub-meter.
+    let pos_km = comparison.position_discrepancy_km();
+    let vel_km_s = comparison.velocity_discrepancy_km_s();
+    println!(
+        "position discrepancy: {:.3} m\tvelocity discrepancy: {:.3} mm/s",
+        pos_km * 1e3,
+        vel_km_s * 1e6
+    );
+    assert!(pos_km < 1e-3, "position discrepancy: {pos_km:e} km");
+    assert!(vel_km_s < 1e-6, "velocity discrepancy: {vel_km_s:e} km/s");
+
+    // The covariances agree on the position uncertainty to sub-meter too.
+    for i in 0..3 {
+        let batch_sigma_km = comparison.batch_covar[(i, i)].sqrt();
+        let seq_sigma_km = comparison.sequential_covar[(i, i)].sqrt();
+        assert!(
+            (batch_sigma_km - seq_sigma_km).abs() < 1e-3,
+            "sigma #{i}: batch {batch_sigma_km:e} km vs sequential {seq_sigma_km:e} km"
+        );
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ckf_monte_carlo_consistency(almanac: Arc<Almanac>, proc_devices: Vec<GroundStation>) {
+    let _ = pretty_env_logger::try_init();
+
+    let cfg = TrkConfig::builder()
+        .sampling(60.seconds())
+        .scheduler(Scheduler::builder().sample_alignment(60.seconds()).build())
+        .build();
+
+    let mut configs = BTreeMap::new();
+    for device in &proc_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    // The filter uses the same dynamics as the truth and the same noise as the simulation, so it is well tuned.
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup =
+        Propagator::new::<RK4Fixed>(orbital_dyn, PropOpts::with_fixed_step(10.0 * Unit::Second));
+    let (_, truth) = setup
+        .with(initial_state.into(), almanac.clone())
+        .for_duration_with_traj(12 * Unit::Hour)
+        .unwrap();
+
+    let scenario = ODScenario {
+        truth,
+        devices: proc_devices,
+        configs,
+        initial_estimate: KfEstimate::from_pos_vel_sigmas(initial_state.into(), 0.5, 5e-4),
+        prop: setup,
+        process_noise: None,
+        almanac,
+    };
+
+    let num_runs = 16;
+    let report = monte_carlo_consistency(&scenario, num_runs, 0).unwrap();
+    println!("{report}");
+    println!("NEES histogram: {:?}", report.nees_histogram(10, 20.0));
+
+    assert_eq!(report.num_runs, num_runs);
+    assert!(!report.epochs.is_empty());
+    assert!(report.epochs.iter().all(|stats| stats.num_runs == num_runs));
+
+    // Once the filter has converged, the ensemble ANEES should be within the chi-square bounds at most epochs
+    // and the filter-reported sigma should match the ensemble error.
+    let converged = report.epochs[report.epochs.len() / 2].epoch;
+    let within = report.fraction_within_bounds(3.0, converged);
+    println!(
+        "{:.1}% of the converged epochs are within the 3-sigma ANEES bounds",
+        within * 100.0
+    );
+    assert!(within > 0.9, "only {within} of the epochs are consistent");
+
+    let last = report.epochs.last().unwrap();
+    for i in 0..6 {
+        let ratio = last.rms_error[i] / last.mean_sigma[i];
+        assert!(
+            ratio > 0.3 && ratio < 3.0,
+            "component #{i}: RMS error {:e} vs sigma {:e}",
+            last.rms_error[i],
+            last.mean_sigma[i]
+        );
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_ukf_heo_large_uncertainty(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    // Load the tracking configurations
+    let mut configs = BTreeMap::new();
+    let trkconfig_yaml: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "data",
+        "tests",
+        "config",
+        "trk_cfg_od_val.yaml",
+    ]
+    .iter()
+    .collect();
+
+    let cfg = TrkConfig::load(trkconfig_yaml).unwrap();
+
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    // Define the propagator information.
+    let prop_time = 1 * Unit::Day;
+    let step_size = 10.0 * Unit::Second;
+    let opts = PropOpts::with_fixed_step(step_size);
+
+    // A Molniya orbit, starting at periapsis where the dynamics are the most nonlinear.
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(26_600.0, 0.74, 63.4, 80.0, 270.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+
+    let (final_truth, traj) = setup
+        .with(initial_state.into(), almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+    println!("{final_truth}");
+
+    // Simulate tracking data
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    // Large initial uncertainty: 20 km and 20 m/s, with an initial error of about one sigma on each component.
+    let sigma_radius_km = 20.0;
+    let sigma_velocity_km_s = 20.0e-3;
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        sigma_radius_km.powi(2),
+        sigma_radius_km.powi(2),
+        sigma_radius_km.powi(2),
+        sigma_velocity_km_s.powi(2),
+        sigma_velocity_km_s.powi(2),
+        sigma_velocity_km_s.powi(2),
+        0.0,
+        0.0,
+        0.0,
+    ]));
+
+    let mut initial_state_dev = initial_state;
+    initial_state_dev.radius_km.x += 15.0;
+    initial_state_dev.radius_km.y -= 20.0;
+    initial_state_dev.radius_km.z += 10.0;
+    initial_state_dev.velocity_km_s.x -= 15.0e-3;
+    initial_state_dev.velocity_km_s.y += 20.0e-3;
+    initial_state_dev.velocity_km_s.z -= 10.0e-3;
+
+    let initial_estimate = KfEstimate::from_covar(initial_state_dev.into(), init_covar);
+    println!("initial estimate:\n{initial_estimate}");
+
+    // CKF switching to an EKF after a few measurements
+    let mut odp_ekf = ODProcess::ekf(
+        setup.with(
+            Spacecraft::from(initial_state_dev).with_stm(),
+            almanac.clone(),
+        ),
+        KF::no_snc(initial_estimate),
+        EkfTrigger::new(10, 5 * Unit::Minute),
+        None,
+        almanac.clone(),
+    );
+    odp_ekf.process_arc::<GroundStation>(&arc).unwrap();
+
+    // UKF with the default sigma point spread
+    let mut odp_ukf = ODProcess::ckf(
+        setup.with(
+            Spacecraft::from(initial_state_dev).with_stm(),
+            almanac.clone(),
+        ),
+        UKF::no_snc(initial_estimate),
+        None,
+        almanac,
+    );
+    odp_ukf.process_arc::<GroundStation>(&arc).unwrap();
+
+    let ekf_est = odp_ekf.estimates.last().unwrap();
+    let ukf_est = odp_ukf.estimates.last().unwrap();
+    println!("EKF final estimate:\n{ekf_est}\nUKF final estimate:\n{ukf_est}");
+
+    let ekf_delta = (ekf_est.state().orbit - final_truth.orbit).unwrap();
+    let ukf_delta = (ukf_est.state().orbit - final_truth.orbit).unwrap();
+    println!(
+        "EKF: RMAG error = {:.3} m\tVMAG error = {:.3} mm/s",
+        ekf_delta.rmag_km() * 1e3,
+        ekf_delta.vmag_km_s() * 1e6
+    );
+    println!(
+        "UKF: RMAG error = {:.3} m\tVMAG error = {:.3} mm/s",
+        ukf_delta.rmag_km() * 1e3,
+        ukf_delta.vmag_km_s() * 1e6
+    );
+
+    // The UKF converges despite the initial error, and does at least as well as the EKF.
+    assert!(
+        ukf_delta.rmag_km() < 0.1,
+        "UKF position error too large: {:.3} km",
+        ukf_delta.rmag_km()
+    );
+    assert!(ukf_delta.vmag_km_s() < 1e-4, "UKF velocity error too large");
+    assert!(ukf_delta.rmag_km() <= ekf_delta.rmag_km().max(1e-3));
+
+    // And its covariance collapsed from the initial uncertainty
+    for i in 0..6 {
+        assert!(ukf_est.covar[(i, i)] >= 0.0);
+        assert!(ukf_est.covar[(i, i)] < init_covar[(i, i)] * 1e-3);
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_batch_lsq_perfect_stations(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    // Load the tracking configurations
+    let mut configs = BTreeMap::new();
+    let trkconfig_yaml: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "data",
+        "tests",
+        "config",
+        "trk_cfg_od_val.yaml",
+    ]
+    .iter()
+    .collect();
+
+    let cfg = TrkConfig::load(trkconfig_yaml).unwrap();
+
+    for device in &sim_devices {
+        configs.insert(device.name.clone(), cfg.clone());
+    }
+
+    // Define the propagator information.
+    let prop_time = 6 * Unit::Hour;
+    let step_size = 10.0 * Unit::Second;
+    let opts = PropOpts::with_fixed_step(step_size);
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, opts);
+
+    let (_, traj) = setup
+        .with(initial_state.into(), almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+
+    // Simulate perfect tracking data
+    let mut arc_sim =
+        TrackingArcSim::with_seed(sim_devices, traj.clone(), configs.clone(), 0).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+    let mut devices = arc.rebuild_devices::<Spacecraft, GroundStation>().unwrap();
+
+    // Initial guess off by a few kilometers and a few meters per second
+    let mut initial_guess = initial_state;
+    initial_guess.radius_km.x += 5.0;
+    initial_guess.radius_km.y -= 3.0;
+    initial_guess.velocity_km_s.z += 2.0e-3;
+
+    let batch = BatchLeastSquares::new(&setup);
+    let solution = batch
+        .estimate(
+            initial_guess.into(),
+            &arc.measurements,
+            &mut devices,
+            almanac.clone(),
+        )
+        .unwrap();
+    println!("{solution}");
+
+    let first_epoch = arc
+        .measurements
+        .iter()
+        .map(|(_, msr)| msr.epoch())
+        .min()
+        .unwrap();
+    assert_eq!(solution.estimate.epoch(), first_epoch);
+
+    let truth = traj.at(first_epoch).unwrap();
+    let delta = (solution.estimate.state().orbit - truth.orbit).unwrap();
+    println!(
+        "RMAG error = {:.3e} m\tVMAG error = {:.3e} mm/s",
+        delta.rmag_km() * 1e3,
+        delta.vmag_km_s() * 1e6
+    );
+
+    assert!(solution.iterations <= 4, "too many iterations");
+    assert!(delta.rmag_km() < 1e-9, "Position error should be zero");
+    assert!(delta.vmag_km_s() < 1e-12, "Velocity error should be zero");
+
+    // Covariance of the orbit from the information matrix, and none on the other parameters
+    for i in 0..9 {
+        if i < 6 {
+            assert!(solution.estimate.covar[(i, i)] > 0.0);
+        } else {
+            assert_eq!(solution.estimate.covar[(i, i)], 0.0);
+        }
+    }
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_tb_val_arc_serde_reload(
+    almanac: Arc<Almanac>,
+    sim_devices: Vec<GroundStation>,
+    proc_devices: Vec<GroundStation>,
+) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let initial_state = Orbit::keplerian(22000.0, 0.01, 30.0, 80.0, 40.0, 0.0, dt, eme2k);
+
+    let orbital_dyn = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let setup = Propagator::new::<RK4Fixed>(orbital_dyn, PropOpts::with_fixed_step_s(10.0));
+
+    let (_, traj) = setup
+        .with(initial_state.into(), almanac.clone())
+        .for_duration_with_traj(6 * Unit::Hour)
+        .unwrap();
+
+    let trkconfig_yaml: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "data",
+        "tests",
+        "config",
+        "trk_cfg_od_val_arc.yaml",
+    ]
+    .iter()
+    .collect();
+
+    let configs: BTreeMap<String, TrkConfig> = TrkConfig::load_named(trkconfig_yaml).unwrap();
+
+    let mut arc_sim = TrackingArcSim::with_seed(sim_devices, traj, configs.clone(), 1).unwrap();
+    arc_sim.build_schedule(almanac.clone()).unwrap();
+
+    let mut arc = arc_sim.generate_measurements(almanac.clone()).unwrap();
+    arc.set_devices(proc_devices, configs).unwrap();
+
+    // Save the arc and reload it
+    let serialized = serde_yaml::to_string(&arc).unwrap();
+    let reloaded: TrackingArc<RangeDoppler> = serde_yaml::from_str(&serialized).unwrap();
+
+    assert_eq!(reloaded.device_cfg, arc.device_cfg);
+    assert_eq!(reloaded.measurements, arc.measurements);
+
+    // A measurement of another kind cannot be reloaded in this arc
+    let mut records = arc.to_records();
+    records[0].kind = vec!["Azimuth (deg)".to_string()];
+    assert!(TrackingArc::<RangeDoppler>::from_records(arc.device_cfg.clone(), &records).is_err());
+
+    // Processing both arcs leads to identical estimates
+    let init_covar = SMatrix::<f64, 9, 9>::from_diagonal(&SVector::<f64, 9>::from_iterator([
+        1e-6, 1e-6, 1e-6, 1e-6, 1e-6, 1e-6, 0.0, 0.0, 0.0,
+    ]));
+    let initial_estimate = KfEstimate::from_covar(initial_state.into(), init_covar);
+
+    let mut final_estimates = Vec::new();
+    for trk_arc in [&arc, &reloaded] {
+        let prop_est = setup.with(Spacecraft::from(initial_state).with_stm(), almanac.clone());
+        let mut odp = ODProcess::ckf(
+            prop_est,
+            KF::no_snc(initial_estimate),
+            None,
+            almanac.clone(),
+        );
+        odp.process_arc::<GroundStation>(trk_arc).unwrap();
+        final_estimates.push(*odp.estimates.last().unwrap());
+    }
+
+    assert_eq!(final_estimates[0].state(), final_estimates[1].state());
+    assert_eq!(final_estimates[0].covar, final_estimates[1].covar);
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn od_overlap_consistency(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+    let orbit = Orbit::keplerian(7_000.0, 1e-3, 51.6, 30.0, 45.0, 10.0, epoch, eme2k);
+
+    // Both solutions have a 100 m and 1 mm/s uncertainty on each axis
+    let mut diag = SVector::<f64, 9>::zeros();
+    for i in 0..3 {
+        diag[i] = 0.1_f64.powi(2);
+        diag[i + 3] = 1e-6_f64.powi(2);
+    }
+
+    let dispersed = |dr_km: f64, dv_km_s: f64| {
+        let mut sc = Spacecraft::from(orbit);
+        sc.orbit.radius_km.x += dr_km;
+        sc.orbit.velocity_km_s.y += dv_km_s;
+        KfEstimate::from_diag(sc, diag)
+    };
+
+    let est_a = dispersed(0.0, 0.0);
+
+    // Within their uncertainties, the solutions are consistent
+    let consistent = overlap_consistency(&est_a, &dispersed(0.05, -1e-6)).unwrap();
+    println!("{consistent}");
+    assert!(consistent.is_consistent(0.01));
+
+    // A 2 km difference is not
+    let inconsistent = overlap_consistency(&est_a, &dispersed(2.0, 0.0)).unwrap();
+    println!("{inconsistent}");
+    assert!(!inconsistent.is_consistent(0.01));
+    assert!((inconsistent.mahalanobis - 2.0 / 0.02_f64.sqrt()).abs() < 1e-9);
+
+    // The 95% quantile of the chi-square distribution with six degrees of freedom is 12.592
+    let at_quantile =
+        overlap_consistency(&est_a, &dispersed((12.592 * 0.02_f64).sqrt(), 0.0)).unwrap();
+    assert!((at_quantile.p_value - 0.05).abs() < 1e-4);
+
+    // The solutions must be at the same epoch
+    let mut later = Spacecraft::from(orbit);
+    later.orbit.epoch = epoch + 1 * Unit::Minute;
+    let later = KfEstimate::from_diag(later, diag);
+    assert!(overlap_consistency(&est_a, &later).is_err());
+
+    // And in the same frame
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let mut rotated = Spacecraft::from(orbit);
+    rotated.orbit.frame = iau_earth;
+    let rotated = KfEstimate::from_diag(rotated, diag);
+    assert!(matches!(
+        overlap_consistency(&est_a, &rotated),
+        Err(ODError::EstimateFrameMismatch { .. })
+    ));
+}
@@ -159,3 +159,46 @@ fn event_tracker_true_anomaly(almanac: Arc<Almanac>) {
         });
     println!("[eclipses] {} =>\n{}", penumbra_event_loc, pretty);
 }
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn event_eclipse_threshold(almanac: Arc<Almanac>) {
+    use nyx::cosmic::eclipse::{EclipseEvent, EclipseLocator};
+    use nyx::md::prelude::*;
+    use nyx::md::{EventEdge, EventEvaluator};
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // Near the equinox, a low inclination LEO is eclipsed on every revolution
+    let dt = Epoch::from_gregorian_utc_at_noon(2020, 3, 20);
+    let state = Orbit::keplerian(6878.0, 0.001, 5.0, 30.0, 10.0, 0.0, dt, eme2k);
+    let revs_per_day = Unit::Day.to_seconds() / state.period().unwrap().to_seconds();
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (_, traj) = setup
+        .with(state.into(), almanac.clone())
+        .for_duration_with_traj(1 * Unit::Day)
+        .unwrap();
+
+    let e_loc = EclipseLocator::cislunar(almanac.clone());
+    let umbra = EclipseEvent::umbra(&e_loc);
+    let umbra_events = traj.find(&umbra, almanac.clone()).unwrap();
+
+    let entries = umbra_events
+        .iter()
+        .filter(|event| event.edge == EventEdge::Falling)
+        .count();
+    let exits = umbra_events
+        .iter()
+        .filter(|event| event.edge == EventEdge::Rising)
+        .count();
+    println!("{revs_per_day:.2} revolutions: {entries} umbra entries and {exits} exits");
+
+    // Exactly one entry and one exit per revolution, and no spurious event in umbra or in full visibility
+    assert_eq!(entries + exits, umbra_events.len());
+    assert!((entries as f64 - revs_per_day).abs() <= 1.0);
+    assert!((exits as f64 - revs_per_day).abs() <= 1.0);
+    for event in &umbra_events {
+        assert!(event.value.abs() <= umbra.value_precision());
+    }
+}
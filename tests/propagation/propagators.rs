@@ -510,3 +510,492 @@ fn gmat_val_leo_day_fixed(almanac: Arc<Almanac>) {
         println!();
     }
 }
+
+#[rstest]
+fn adaptive_step_ratio_bounds(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    let init = Spacecraft::from(Orbit::cartesian(
+        -2436.45, -2436.45, 6891.037, 5.088_611, -5.088_611, 0.0, dt, eme2k,
+    ));
+
+    // Start with a tiny step: the error of that first step is far below the tolerance (an "easy region").
+    let mut opts = PropOpts::with_tolerance(1e-9);
+    opts.init_step = 1.0 * Unit::Second;
+
+    let setup = Propagator::rk89(SpacecraftDynamics::new(OrbitalDynamics::two_body()), opts);
+    let mut unbounded = setup.with(init, almanac.clone());
+    unbounded.single_step().unwrap();
+    let first_step = unbounded.latest_details().step;
+    unbounded.single_step().unwrap();
+    let unbounded_step = unbounded.latest_details().step;
+
+    // Now limit the increase of the step size to a factor of two per step.
+    opts.set_step_ratios(0.1, 2.0);
+    assert!(format!("{opts}").contains("step ratio: [0.1, 2]"), "{opts}");
+    let setup = Propagator::rk89(SpacecraftDynamics::new(OrbitalDynamics::two_body()), opts);
+    let mut bounded = setup.with(init, almanac);
+    bounded.single_step().unwrap();
+    assert_eq!(bounded.latest_details().step, first_step);
+    bounded.single_step().unwrap();
+    let bounded_step = bounded.latest_details().step;
+
+    println!("first step: {first_step}\tunbounded: {unbounded_step}\tbounded: {bounded_step}");
+
+    assert!(
+        unbounded_step > 2 * first_step,
+        "step size should overshoot without bounds"
+    );
+    assert!(
+        bounded_step <= 2 * first_step,
+        "step size increase was not bounded"
+    );
+}
+
+#[rstest]
+fn conservation_monitor_two_body(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    let init = Spacecraft::from(Orbit::cartesian(
+        -2436.45, -2436.45, 6891.037, 5.088_611, -5.088_611, 0.0, dt, eme2k,
+    ));
+
+    let period = init.orbit.period().unwrap();
+
+    let setup = Propagator::rk89(
+        SpacecraftDynamics::new(OrbitalDynamics::two_body()),
+        PropOpts::with_tolerance(1e-12),
+    );
+
+    // Without the monitor, only the number of steps is tracked.
+    let mut prop = setup.with(init, almanac.clone());
+    prop.for_duration(period).unwrap();
+    let stats = prop.stats();
+    assert!(stats.steps > 0);
+    assert!(!stats.monitors_conservation());
+    assert!(stats.initial_energy_km2_s2.is_none());
+    assert!(stats.initial_hmag_km2_s.is_none());
+
+    // Two body dynamics conserve both the energy and the angular momentum.
+    let mut prop = setup.with(init, almanac).with_conservation_monitor();
+    prop.for_duration(50 * period).unwrap();
+    let stats = prop.stats();
+    println!("{stats}");
+
+    assert!(stats.monitors_conservation());
+    assert!(stats.steps > 0);
+    assert!(stats.energy_drift <= stats.max_energy_drift);
+    assert!(stats.hmag_drift <= stats.max_hmag_drift);
+    assert!(
+        stats.max_energy_drift < 1e-9,
+        "energy drift too large: {:e}",
+        stats.max_energy_drift
+    );
+    assert!(
+        stats.max_hmag_drift < 1e-9,
+        "angular momentum drift too large: {:e}",
+        stats.max_hmag_drift
+    );
+}
+
+#[rstest]
+fn dop853_dense_output(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    let init = Spacecraft::from(Orbit::cartesian(
+        -2436.45, -2436.45, 6891.037, 5.088_611, -5.088_611, 0.0, dt, eme2k,
+    ));
+
+    let dynamics = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let prop_time = 0.5 * Unit::Day;
+
+    // Coarse steps to exercise the interpolation between steps.
+    let setup = Propagator::dop853(
+        dynamics.clone(),
+        PropOpts::with_adaptive_step(
+            1.0 * Unit::Second,
+            10.0 * Unit::Minute,
+            1e-12,
+            RSSCartesianStep {},
+        ),
+    );
+
+    // Without enabling it, the dense output is not available.
+    let mut prop = setup.with(init, almanac.clone());
+    prop.for_duration(1 * Unit::Hour).unwrap();
+    assert!(prop.interpolate(dt + 30 * Unit::Minute).is_err());
+
+    let mut prop = setup.with(init, almanac.clone()).with_dense_output();
+    let final_state = prop.for_duration(prop_time).unwrap();
+
+    // Outside of the propagated arc
+    assert!(prop.interpolate(dt - 1 * Unit::Second).is_err());
+    assert!(prop.interpolate(dt + prop_time + 1 * Unit::Second).is_err());
+
+    // Boundaries of the arc
+    assert_eq!(prop.interpolate(dt).unwrap().orbit, init.orbit);
+    let end = prop.interpolate(dt + prop_time).unwrap();
+    assert!((end.orbit.radius_km - final_state.orbit.radius_km).norm() < 1e-9);
+
+    // The interpolant is continuous across step boundaries.
+    let steps = prop.dense_steps().unwrap();
+    assert!(steps.len() > 10);
+    for pair in steps.windows(2) {
+        assert_eq!(pair[0].epoch + pair[0].step, pair[1].epoch);
+        let end_of_prev = pair[0].evaluate(pair[1].epoch);
+        let start_of_next = pair[1].evaluate(pair[1].epoch);
+        assert!((end_of_prev - start_of_next).norm() < 1e-9);
+    }
+
+    // Compare the interpolated states with a tight propagation to the same epochs.
+    let truth_setup = Propagator::rk89(dynamics, PropOpts::with_tolerance(1e-12));
+    for minutes in [7.3, 66.6, 123.4, 401.1, 700.0] {
+        let epoch = dt + minutes * Unit::Minute;
+        let interp = prop.interpolate(epoch).unwrap();
+        let truth = truth_setup
+            .with(init, almanac.clone())
+            .until_epoch(epoch)
+            .unwrap();
+        assert_eq!(interp.orbit.epoch, epoch);
+        let err_km = (interp.orbit.radius_km - truth.orbit.radius_km).norm();
+        let err_km_s = (interp.orbit.velocity_km_s - truth.orbit.velocity_km_s).norm();
+        println!("{epoch}: {err_km:.3e} km\t{err_km_s:.3e} km/s");
+        assert!(err_km < 1e-5, "position error too large: {err_km:e} km");
+        assert!(
+            err_km_s < 1e-8,
+            "velocity error too large: {err_km_s:e} km/s"
+        );
+    }
+}
+
+#[rstest]
+fn backward_propagation(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    let init = Spacecraft::from(Orbit::cartesian(
+        -2436.45, -2436.45, 6891.037, 5.088_611, -5.088_611, 0.0, dt, eme2k,
+    ));
+
+    let dynamics = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let prop_time = 1 * Unit::Day;
+
+    // Fixed step: forward then backward returns to the initial state.
+    let setup = Propagator::rk89(
+        dynamics.clone(),
+        PropOpts::with_fixed_step(10.0 * Unit::Second),
+    );
+    let mut prop = setup.with(init, almanac.clone());
+    prop.for_duration(prop_time).unwrap();
+    let back = prop.for_duration(-prop_time).unwrap();
+    assert_eq!(back.orbit.epoch, dt);
+    let err_km = (back.orbit.radius_km - init.orbit.radius_km).norm();
+    let err_km_s = (back.orbit.velocity_km_s - init.orbit.velocity_km_s).norm();
+    println!("fixed step round trip: {err_km:.3e} km\t{err_km_s:.3e} km/s");
+    assert!(err_km < 1e-9, "position error: {err_km:e} km");
+    assert!(err_km_s < 1e-12, "velocity error: {err_km_s:e} km/s");
+
+    // Adaptive step: the error control applies to backward steps too.
+    let opts = PropOpts::with_adaptive_step(
+        0.1 * Unit::Second,
+        30.0 * Unit::Second,
+        1e-12,
+        RSSCartesianStep {},
+    );
+    let setup = Propagator::rk89(dynamics, opts);
+    let (end_state, fwd_traj) = setup
+        .with(init, almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+
+    let mut prop = setup.with(end_state, almanac.clone());
+    let (back, back_traj) = prop.for_duration_with_traj(-prop_time).unwrap();
+    assert_eq!(back.orbit.epoch, dt);
+    assert!(prop.latest_details().step.is_negative());
+
+    let err_km = (back.orbit.radius_km - init.orbit.radius_km).norm();
+    println!("adaptive step round trip: {err_km:.3e} km");
+    assert!(err_km < 1e-6, "position error: {err_km:e} km");
+
+    // The backward trajectory is in chronological order and can be evaluated.
+    assert_eq!(back_traj.first().orbit.epoch, dt);
+    assert_eq!(back_traj.last().orbit.epoch, dt + prop_time);
+    for pair in back_traj.states.windows(2) {
+        assert!(pair[0].orbit.epoch < pair[1].orbit.epoch);
+    }
+    let mid = dt + 0.5 * prop_time + 7 * Unit::Second;
+    let fwd_mid = fwd_traj.at(mid).unwrap();
+    let back_mid = back_traj.at(mid).unwrap();
+    let err_km = (fwd_mid.orbit.radius_km - back_mid.orbit.radius_km).norm();
+    println!("mid-arc difference: {err_km:.3e} km");
+    assert!(err_km < 1e-5, "position error: {err_km:e} km");
+}
+
+#[rstest]
+fn abm8_multistep(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    let init = Spacecraft::from(Orbit::cartesian(
+        -2436.45, -2436.45, 6891.037, 5.088_611, -5.088_611, 0.0, dt, eme2k,
+    ));
+
+    let dynamics = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+    let opts = PropOpts::with_fixed_step(10.0 * Unit::Second);
+    let prop_time = 1 * Unit::Day;
+
+    let (rk_state, rk_traj) = Propagator::rk89(dynamics.clone(), opts)
+        .with(init, almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+
+    let mut prop = Propagator::new::<ABM8>(dynamics, opts).with(init, almanac.clone());
+    let (abm_state, abm_traj) = prop.for_duration_with_traj(prop_time).unwrap();
+
+    // No discontinuity when switching from the bootstrapping RK89 to the multistep method.
+    for step in 0..20 {
+        let epoch = dt + step * 10.0 * Unit::Second;
+        let rk = rk_traj.at(epoch).unwrap();
+        let abm = abm_traj.at(epoch).unwrap();
+        let err_km = (rk.orbit.radius_km - abm.orbit.radius_km).norm();
+        assert!(err_km < 1e-9, "step #{step}: {err_km:e} km");
+    }
+
+    let (err_r, err_v) = rss_orbit_errors(&abm_state.orbit, &rk_state.orbit);
+    println!("ABM8 vs RK89 after {prop_time}: {err_r:.3e} km\t{err_v:.3e} km/s");
+    assert!(err_r < 1e-6, "position error: {err_r:e} km");
+    assert!(err_v < 1e-9, "velocity error: {err_v:e} km/s");
+
+    // The corrector residual is reported in the details of a multistep step.
+    prop.single_step().unwrap();
+    let details = prop.latest_details();
+    println!("{details:?}");
+    assert_eq!(details.step, 10.0 * Unit::Second);
+    assert!(details.error > 0.0 && details.error < 1e-9);
+}
+
+#[rstest]
+fn step_history_eccentric(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    // Starting at periapsis (9000 km) of a highly eccentric orbit, with an apoapsis at 51000 km
+    let orbit = Orbit::keplerian(30_000.0, 0.7, 30.0, 0.0, 0.0, 0.0, dt, eme2k);
+    let period = orbit.period().unwrap();
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+
+    // Disabled by default
+    let prop = setup.with(orbit.into(), almanac.clone());
+    assert!(prop.step_history().is_none());
+
+    let mut prop = setup.with(orbit.into(), almanac).with_step_history();
+    prop.for_duration(period).unwrap();
+
+    let history = prop.step_history().unwrap();
+    assert!(history.len() > 10);
+    println!("{} steps, first: {}", history.len(), history[0]);
+
+    // One detail per accepted step, chronologically, within the tolerance
+    assert!(history.windows(2).all(|w| w[1].epoch > w[0].epoch));
+    assert_eq!(history.last().unwrap().epoch, dt + period);
+    for step in history {
+        assert!(step.error <= setup.opts.tolerance || step.attempts >= setup.opts.attempts);
+    }
+
+    let mean_step_s = |start: f64, end: f64| {
+        // Ignore the last step, which is truncated to end exactly at the requested epoch
+        let steps = history[..history.len() - 1]
+            .iter()
+            .filter(|step| {
+                let frac = (step.epoch - dt).to_seconds() / period.to_seconds();
+                frac >= start && frac <= end
+            })
+            .map(|step| step.step.to_seconds())
+            .collect::<Vec<f64>>();
+        assert!(!steps.is_empty());
+        steps.iter().sum::<f64>() / steps.len() as f64
+    };
+
+    // The integrator takes small steps around periapsis and large steps around apoapsis.
+    let near_periapsis_s = mean_step_s(0.95, 1.0);
+    let near_apoapsis_s = mean_step_s(0.4, 0.6);
+    println!(
+        "mean step near periapsis: {near_periapsis_s:.1} s\tnear apoapsis: {near_apoapsis_s:.1} s"
+    );
+    assert!(near_apoapsis_s > 3.0 * near_periapsis_s);
+}
+
+#[rstest]
+fn fallback_integrator_pinned_min_step(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    // Starting at periapsis (9000 km) of a highly eccentric orbit
+    let orbit = Orbit::keplerian(30_000.0, 0.7, 30.0, 0.0, 0.0, 0.0, dt, eme2k);
+    let prop_time = 1 * Unit::Hour;
+
+    // Reference propagation with the default options
+    let truth = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()))
+        .with(orbit.into(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    // A minimum step far too large for the periapsis pass, such that the step size is pinned at the minimum step with an excessive error
+    let min_step = 300.0 * Unit::Second;
+    let opts = PropOpts::with_adaptive_step(min_step, min_step, 1e-12, RSSCartesianStep {});
+
+    let pinned = Propagator::rk89(SpacecraftDynamics::new(OrbitalDynamics::two_body()), opts);
+    let mut prop = pinned
+        .with(orbit.into(), almanac.clone())
+        .with_step_history();
+    let pinned_state = prop.for_duration(prop_time).unwrap();
+    assert_eq!(prop.stats().fallback_steps, 0);
+    let pinned_max_error = prop
+        .step_history()
+        .unwrap()
+        .iter()
+        .map(|step| step.error)
+        .fold(0.0, f64::max);
+
+    let mut fallback_opts = opts;
+    fallback_opts.set_fallback(FallbackIntegrator::new::<CashKarp45>(1024));
+    let fallback = Propagator::rk89(
+        SpacecraftDynamics::new(OrbitalDynamics::two_body()),
+        fallback_opts,
+    );
+    let mut prop = fallback.with(orbit.into(), almanac).with_step_history();
+    let fallback_state = prop.for_duration(prop_time).unwrap();
+    let fallback_max_error = prop
+        .step_history()
+        .unwrap()
+        .iter()
+        .map(|step| step.error)
+        .fold(0.0, f64::max);
+
+    println!("{}", prop.stats());
+    println!(
+        "max step error: {pinned_max_error:.3e} (pinned) vs {fallback_max_error:.3e} (fallback)"
+    );
+
+    // The fallback was invoked and brought the step error within the tolerance
+    assert!(prop.stats().fallback_steps > 0);
+    assert!(pinned_max_error > opts.tolerance);
+    assert!(fallback_max_error <= opts.tolerance);
+
+    // Hence the propagation is closer to the reference
+    let pinned_err_km = (pinned_state.orbit.radius_km - truth.orbit.radius_km).norm();
+    let fallback_err_km = (fallback_state.orbit.radius_km - truth.orbit.radius_km).norm();
+    println!(
+        "position error: {pinned_err_km:.3e} km (pinned) vs {fallback_err_km:.3e} km (fallback)"
+    );
+    assert!(fallback_err_km < pinned_err_km);
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn for_duration_many_matches_sequential(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+
+    // A cloud of independent objects on different orbits
+    let states: Vec<Spacecraft> = (0..32)
+        .map(|i| {
+            let i = f64::from(i);
+            Orbit::keplerian(
+                7_000.0 + 50.0 * i,
+                0.001 * i,
+                10.0 + 2.5 * i,
+                11.25 * i,
+                5.0 * i,
+                7.5 * i,
+                dt,
+                eme2k,
+            )
+            .into()
+        })
+        .collect();
+
+    let prop = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let duration = 6 * Unit::Hour;
+
+    let parallel = prop.for_duration_many(&states, duration, almanac.clone());
+    assert_eq!(parallel.len(), states.len());
+
+    for (state, result) in states.iter().zip(&parallel) {
+        let sequential = prop
+            .with(*state, almanac.clone())
+            .for_duration(duration)
+            .unwrap();
+        // Each propagation has its own integrator, so the results are identical to the sequential ones.
+        assert_eq!(result.as_ref().unwrap(), &sequential);
+    }
+}
+
+#[rstest]
+fn rkf45_per_component_tol(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let dt = Epoch::from_mjd_tai(JD_J2000);
+    let init = Spacecraft::from(Orbit::keplerian(
+        7_000.0, 0.01, 51.6, 30.0, 45.0, 10.0, dt, eme2k,
+    ));
+    let prop_time = Unit::Day * 1;
+    let dynamics = SpacecraftDynamics::new(OrbitalDynamics::two_body());
+
+    // Reference with a high accuracy integrator
+    let reference = Propagator::default(dynamics.clone())
+        .with(init, almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    let propagate = |error_ctrl: PerComponentTol| {
+        let setup = Propagator::new::<RKF45>(
+            dynamics.clone(),
+            PropOpts::with_per_component_tol(
+                Unit::Millisecond * 1,
+                Unit::Second * 2700,
+                error_ctrl,
+            ),
+        );
+        let mut prop = setup.with(init, almanac.clone());
+        let state = prop.for_duration(prop_time).unwrap();
+        let (err_km, err_km_s) = rss_orbit_errors(&state.orbit, &reference.orbit);
+        (prop.stats().steps, err_km, err_km_s)
+    };
+
+    // The position tolerance is loose in both cases, only the velocity tolerance changes.
+    let (loose_steps, loose_err_km, loose_err_km_s) =
+        propagate(PerComponentTol::cartesian(1.0, 0.0, 1e-3, 0.0));
+    let (tight_steps, tight_err_km, tight_err_km_s) =
+        propagate(PerComponentTol::cartesian(1.0, 0.0, 1e-9, 0.0));
+
+    println!(
+        "loose velocity tol: {loose_steps} steps, {loose_err_km:.3e} km, {loose_err_km_s:.3e} km/s"
+    );
+    println!(
+        "tight velocity tol: {tight_steps} steps, {tight_err_km:.3e} km, {tight_err_km_s:.3e} km/s"
+    );
+
+    assert!(
+        tight_steps > 2 * loose_steps,
+        "tight velocity tolerance should reduce the step size"
+    );
+    assert!(tight_err_km_s < loose_err_km_s);
+    assert!(tight_err_km < loose_err_km);
+}
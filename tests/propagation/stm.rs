@@ -4,9 +4,10 @@ use std::sync::Arc;
 use anise::constants::celestial_objects::{MOON, SUN};
 use nyx::cosmic::{Orbit, Spacecraft};
 use nyx::dynamics::orbital::OrbitalDynamics;
-use nyx::linalg::{Const, Matrix6, OVector};
+use nyx::linalg::{Const, DVector, Matrix6, OVector, Vector3};
 use nyx::propagators::*;
 use nyx::time::{Epoch, Unit};
+use nyx::utils::{FiniteDiff, FiniteDiffStep};
 use nyx::State;
 use nyx_space::md::prelude::SpacecraftDynamics;
 
@@ -247,6 +248,89 @@ fn stm_between_steps(almanac: Arc<Almanac>) {
     }
 }
 
+#[rstest]
+fn stm_between_nominal_traj(almanac: Arc<Almanac>) {
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+    let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+    let orbital_dyn = OrbitalDynamics::two_body();
+    let prop = Propagator::default(SpacecraftDynamics::new(orbital_dyn.clone()));
+
+    let init = Spacecraft::from(Orbit::keplerian(
+        8000.0, 0.2, 10.0, 5.0, 25.0, 0.0, epoch, eme2k,
+    ));
+    let prop_time = 10 * Unit::Minute;
+
+    // Full combined propagation of the nominal state and of its STM
+    let (final_state, traj) = prop
+        .with(init.with_stm(), almanac.clone())
+        .for_duration_with_traj(prop_time)
+        .unwrap();
+    let phi_combined = final_state.stm().unwrap().fixed_resize::<6, 6>(0.0);
+
+    // STM only, along the nominal trajectory
+    let phi = orbital_dyn
+        .stm_between(
+            &traj,
+            epoch,
+            epoch + prop_time,
+            Unit::Second * 1,
+            almanac.clone(),
+        )
+        .unwrap();
+
+    println!("combined = {phi_combined}\nbetween = {phi}");
+
+    // Central finite differencing of the nonlinear propagation as the reference
+    let mut phi_fd = Matrix6::<f64>::zeros();
+    let pert = 1e-4;
+    for i in 0..6 {
+        let mut cols = [OVector::<f64, Const<6>>::zeros(); 2];
+        for (k, sign) in [1.0, -1.0].iter().enumerate() {
+            let mut this_init = init;
+            match i {
+                0 => this_init.orbit.radius_km.x += sign * pert,
+                1 => this_init.orbit.radius_km.y += sign * pert,
+                2 => this_init.orbit.radius_km.z += sign * pert,
+                3 => this_init.orbit.velocity_km_s.x += sign * pert,
+                4 => this_init.orbit.velocity_km_s.y += sign * pert,
+                5 => this_init.orbit.velocity_km_s.z += sign * pert,
+                _ => unreachable!(),
+            }
+            cols[k] = prop
+                .with(this_init, almanac.clone())
+                .for_duration(prop_time)
+                .unwrap()
+                .orbit
+                .to_cartesian_pos_vel();
+        }
+        phi_fd.set_column(i, &((cols[0] - cols[1]) / (2.0 * pert)));
+    }
+
+    let fd_err = (phi - phi_fd).norm() / phi_fd.norm();
+    println!("relative error wrt finite differencing: {fd_err:e}");
+    assert!(fd_err < 1e-6, "STM between epochs does not match FD");
+
+    // The STM of the combined propagation is only updated once per integration step, so it is less precise.
+    let combined_err = (phi - phi_combined).norm() / phi_combined.norm();
+    println!("relative error wrt combined propagation: {combined_err:e}");
+    assert!(
+        combined_err < 1e-3,
+        "STM between epochs does not match the combined STM"
+    );
+
+    // Integrating backward inverts the STM
+    let phi_back = orbital_dyn
+        .stm_between(&traj, epoch + prop_time, epoch, Unit::Second * 1, almanac)
+        .unwrap();
+    let inv_err = (phi_back * phi - Matrix6::identity()).norm();
+    println!("error of the backward STM: {inv_err:e}");
+    assert!(inv_err < 1e-9, "backward STM is not the inverse");
+}
+
 #[rstest]
 fn stm_hifi_variable_step(almanac: Arc<Almanac>) {
     // Using higher fidelity dynamics for STM testing
@@ -332,6 +416,243 @@ fn stm_hifi_variable_step(almanac: Arc<Almanac>) {
     }
 }
 
+#[rstest]
+fn stm_two_body_analytic(almanac: Arc<Almanac>) {
+    // Compare the STM integrated from the partials of the dynamics with the analytic two body STM,
+    // i.e. the central differences of the Keplerian propagation.
+
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+    let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let prop_time = 1 * Unit::Hour;
+
+    let prop = Propagator::default_dp78(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+
+    for ecc in [1e-5, 0.2] {
+        let init = Spacecraft::from(Orbit::keplerian(
+            8000.0, ecc, 10.0, 5.0, 25.0, 0.0, epoch, eme2k,
+        ));
+
+        let final_state = prop
+            .with(init.with_stm(), almanac.clone())
+            .for_duration(prop_time)
+            .unwrap();
+
+        let stm = final_state.stm().unwrap().fixed_resize::<6, 6>(0.0);
+
+        let stm_kep = FiniteDiff::central()
+            .with_step(FiniteDiffStep::Relative(1e-6))
+            .jacobian(
+                |x| {
+                    let orbit = Orbit::cartesian(x[0], x[1], x[2], x[3], x[4], x[5], epoch, eme2k);
+                    DVector::from_column_slice(
+                        orbit
+                            .at_epoch(epoch + prop_time)
+                            .unwrap()
+                            .to_cartesian_pos_vel()
+                            .as_slice(),
+                    )
+                },
+                &DVector::from_column_slice(init.orbit.to_cartesian_pos_vel().as_slice()),
+            );
+        let stm_kep = Matrix6::from_column_slice(stm_kep.as_slice());
+
+        let rel_err = (stm - stm_kep).norm() / stm_kep.norm();
+        println!(
+            "ecc = {ecc}\nSTM = {stm}\nanalytic STM = {stm_kep}\nrelative error = {rel_err:e}"
+        );
+        assert!(rel_err < 1e-6);
+    }
+}
+
+#[rstest]
+fn stm_composed_force_models(almanac: Arc<Almanac>) {
+    // The STM is available for any composition of models, here harmonics, SRP and drag.
+    use anise::constants::frames::IAU_EARTH_FRAME;
+    use nyx::dynamics::{Drag, Harmonics, SolarPressure};
+    use nyx::io::gravity::HarmonicsMem;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+    let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let prop_time = 10 * Unit::Minute;
+
+    let stor = HarmonicsMem::from_cof("data/JGM3.cof.gz", 8, 8, true).unwrap();
+    let sc_dyn = SpacecraftDynamics::from_models(
+        OrbitalDynamics::from_model(Harmonics::from_stor(iau_earth, stor)),
+        vec![
+            SolarPressure::default(eme2k, almanac.clone()).unwrap(),
+            Drag::std_atm1976(almanac.clone()).unwrap(),
+        ],
+    );
+
+    // Fixed step such that the finite differences are not affected by the step size control
+    let prop = Propagator::new::<RK89>(sc_dyn, PropOpts::with_fixed_step(10 * Unit::Second));
+    let two_body_prop = Propagator::new::<RK89>(
+        SpacecraftDynamics::new(OrbitalDynamics::two_body()),
+        PropOpts::with_fixed_step(10 * Unit::Second),
+    );
+
+    let init = Spacecraft::from_srp_defaults(
+        Orbit::keplerian(6_878.0, 0.001, 51.6, 30.0, 45.0, 10.0, epoch, eme2k),
+        300.0,
+        1.0,
+    )
+    .with_drag(1.0, 2.2);
+
+    let final_state = prop
+        .with(init.with_stm(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    let stm = final_state.stm().unwrap().fixed_resize::<6, 6>(0.0);
+
+    // Central differences of the propagation with the same dynamics
+    let stm_fd = FiniteDiff::central()
+        .with_step(FiniteDiffStep::Relative(1e-7))
+        .jacobian(
+            |x| {
+                let mut this_init = init;
+                this_init.orbit.radius_km = Vector3::new(x[0], x[1], x[2]);
+                this_init.orbit.velocity_km_s = Vector3::new(x[3], x[4], x[5]);
+                DVector::from_column_slice(
+                    prop.with(this_init, almanac.clone())
+                        .for_duration(prop_time)
+                        .unwrap()
+                        .orbit
+                        .to_cartesian_pos_vel()
+                        .as_slice(),
+                )
+            },
+            &DVector::from_column_slice(init.orbit.to_cartesian_pos_vel().as_slice()),
+        );
+    let stm_fd = Matrix6::from_column_slice(stm_fd.as_slice());
+
+    let rel_err = (stm - stm_fd).norm() / stm_fd.norm();
+    println!("STM = {stm}\nFD STM = {stm_fd}\nrelative error = {rel_err:e}");
+    assert!(rel_err < 1e-5);
+
+    // The perturbations are accounted for in the STM
+    let two_body_stm = two_body_prop
+        .with(init.with_stm(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap()
+        .stm()
+        .unwrap()
+        .fixed_resize::<6, 6>(0.0);
+
+    assert!((stm - two_body_stm).norm() > 10.0 * (stm - stm_fd).norm());
+}
+
+#[rstest]
+fn stm_low_perigee_drag(almanac: Arc<Almanac>) {
+    // In this drag dominated case, the velocity partials of the drag are required for the STM to match the finite differences.
+    use nyx::dynamics::{Drag, DynamicsError, ForceModel};
+    use nyx::linalg::Matrix4x3;
+    use std::fmt;
+
+    /// Drag whose partials are only those with respect to the position.
+    struct PositionPartialsOnly(Arc<Drag>);
+
+    impl fmt::Display for PositionPartialsOnly {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{} (position partials only)", self.0)
+        }
+    }
+
+    impl ForceModel for PositionPartialsOnly {
+        fn estimation_index(&self) -> Option<usize> {
+            self.0.estimation_index()
+        }
+
+        fn eom(
+            &self,
+            ctx: &Spacecraft,
+            almanac: Arc<Almanac>,
+        ) -> Result<Vector3<f64>, DynamicsError> {
+            self.0.eom(ctx, almanac)
+        }
+
+        fn dual_eom(
+            &self,
+            osc_ctx: &Spacecraft,
+            almanac: Arc<Almanac>,
+        ) -> Result<(Vector3<f64>, Matrix4x3<f64>), DynamicsError> {
+            self.0.dual_eom(osc_ctx, almanac)
+        }
+    }
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+    let prop_time = 10 * Unit::Minute;
+
+    let drag = Drag::std_atm1976(almanac.clone()).unwrap();
+    let prop = Propagator::new::<RK89>(
+        SpacecraftDynamics::from_model(OrbitalDynamics::two_body(), drag.clone()),
+        PropOpts::with_fixed_step(10 * Unit::Second),
+    );
+    let prop_pos_only = Propagator::new::<RK89>(
+        SpacecraftDynamics::from_model(
+            OrbitalDynamics::two_body(),
+            Arc::new(PositionPartialsOnly(drag)),
+        ),
+        PropOpts::with_fixed_step(10 * Unit::Second),
+    );
+
+    // Perigee at about 160 km, starting at perigee, with a large area to mass ratio
+    let init = Spacecraft::from_srp_defaults(
+        Orbit::keplerian(6_600.0, 0.01, 51.6, 30.0, 45.0, 0.0, epoch, eme2k),
+        100.0,
+        0.0,
+    )
+    .with_drag(10.0, 2.2);
+
+    let stm = prop
+        .with(init.with_stm(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap()
+        .stm()
+        .unwrap()
+        .fixed_resize::<6, 6>(0.0);
+
+    let stm_pos_only = prop_pos_only
+        .with(init.with_stm(), almanac.clone())
+        .for_duration(prop_time)
+        .unwrap()
+        .stm()
+        .unwrap()
+        .fixed_resize::<6, 6>(0.0);
+
+    let stm_fd = FiniteDiff::central()
+        .with_step(FiniteDiffStep::Relative(1e-7))
+        .jacobian(
+            |x| {
+                let mut this_init = init;
+                this_init.orbit.radius_km = Vector3::new(x[0], x[1], x[2]);
+                this_init.orbit.velocity_km_s = Vector3::new(x[3], x[4], x[5]);
+                DVector::from_column_slice(
+                    prop.with(this_init, almanac.clone())
+                        .for_duration(prop_time)
+                        .unwrap()
+                        .orbit
+                        .to_cartesian_pos_vel()
+                        .as_slice(),
+                )
+            },
+            &DVector::from_column_slice(init.orbit.to_cartesian_pos_vel().as_slice()),
+        );
+    let stm_fd = Matrix6::from_column_slice(stm_fd.as_slice());
+
+    let rel_err = (stm - stm_fd).norm() / stm_fd.norm();
+    let rel_err_pos_only = (stm_pos_only - stm_fd).norm() / stm_fd.norm();
+    println!("STM = {stm}\nFD STM = {stm_fd}\nrelative error = {rel_err:e}\nwithout the velocity partials = {rel_err_pos_only:e}");
+    assert!(rel_err < 1e-6);
+    // Neglecting the velocity partials of the drag degrades the STM
+    assert!(rel_err_pos_only > 10.0 * rel_err);
+}
+
 #[rstest]
 fn orbit_set_unset_static(almanac: Arc<Almanac>) {
     let eme2k = almanac
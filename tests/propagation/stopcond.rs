@@ -394,3 +394,89 @@ fn event_and_combination(almanac: Arc<Almanac>) {
         }
     }
 }
+
+#[rstest]
+fn stop_cond_halts_at_event(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let start_dt = Epoch::from_mjd_tai(JD_J2000);
+    // Starting at periapsis, with a radius between 7200 km and 8800 km
+    let state = Orbit::keplerian(8000.0, 0.1, 30.0, 45.0, 45.0, 0.0, start_dt, eme2k);
+    let period = state.period().unwrap();
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+
+    // Propagate until the radius reaches 8000 km, and no further.
+    let rmag_event = Event::new(StateParameter::Rmag, 8000.0);
+    let mut prop = setup.with(state.into(), almanac.clone());
+    let (rmag_state, traj) = prop.until_event(3 * period, &rmag_event).unwrap();
+    println!("{rmag_state:x} => rmag = {} km", rmag_state.orbit.rmag_km());
+
+    assert!((rmag_state.orbit.rmag_km() - 8000.0).abs() < rmag_event.value_precision);
+    assert!(rmag_state.orbit.epoch < start_dt + 0.5 * period);
+    assert_eq!(prop.state.orbit.epoch, rmag_state.orbit.epoch);
+    assert_eq!(traj.first().orbit.epoch, start_dt);
+    assert_eq!(traj.last().orbit.epoch, rmag_state.orbit.epoch);
+
+    // The second crossing happens on the way back to periapsis.
+    let mut prop = setup.with(state.into(), almanac.clone());
+    let (second, _) = prop.until_nth_event(3 * period, &rmag_event, 1).unwrap();
+    assert!((second.orbit.rmag_km() - 8000.0).abs() < rmag_event.value_precision);
+    assert!(second.orbit.epoch > start_dt + 0.5 * period);
+    assert!(second.orbit.epoch < start_dt + period);
+
+    // Too few iterations to refine the crossing.
+    let mut prop = setup.with(state.into(), almanac.clone());
+    assert!(prop
+        .until_nth_event_with_max_iter(3 * period, &rmag_event, 0, 1)
+        .is_err());
+
+    // The radius never reaches 9000 km.
+    let mut prop = setup.with(state.into(), almanac);
+    assert!(prop
+        .until_event(period, &Event::new(StateParameter::Rmag, 9000.0))
+        .is_err());
+}
+
+#[rstest]
+fn stop_cond_surface_impact(almanac: Arc<Almanac>) {
+    use anise::constants::frames::IAU_EARTH_FRAME;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_midnight(2024, 3, 1);
+    // Sub-orbital trajectory: at apoapsis 100 km above the surface, too slow to stay in orbit.
+    let state = Orbit::cartesian(6_478.0, 0.0, 0.0, 0.0, 6.0, 1.0, start_dt, eme2k);
+    assert!(state.periapsis_km().unwrap() < 6_357.0);
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let impact_event = Event::surface_impact(iau_earth);
+
+    let mut prop = setup.with(state.into(), almanac.clone());
+    let (impact, traj) = prop.until_event(2.hours(), &impact_event).unwrap();
+
+    let impact_fixed = almanac.transform_to(impact.orbit, iau_earth, None).unwrap();
+    println!(
+        "impact at {} after {}: lat = {:.3} deg, lon = {:.3} deg, height = {:.3} m",
+        impact.epoch(),
+        impact.epoch() - start_dt,
+        impact_fixed.latitude_deg().unwrap(),
+        impact_fixed.longitude_deg(),
+        impact_fixed.height_km().unwrap() * 1e3
+    );
+
+    // The propagation stops at the surface, and never goes underground.
+    assert!(impact_fixed.height_km().unwrap().abs() < impact_event.value_precision);
+    assert_eq!(prop.state.orbit.epoch, impact.orbit.epoch);
+    assert!(impact.epoch() - start_dt < 1.hours());
+    for state in traj.states.iter() {
+        let fixed = almanac.transform_to(state.orbit, iau_earth, None).unwrap();
+        assert!(fixed.height_km().unwrap() > -impact_event.value_precision);
+    }
+
+    // An orbit that never hits the surface does not stop.
+    let leo = Orbit::keplerian(7_000.0, 0.01, 30.0, 0.0, 0.0, 0.0, start_dt, eme2k);
+    let mut prop = setup.with(leo.into(), almanac);
+    assert!(prop.until_event(2.hours(), &impact_event).is_err());
+}
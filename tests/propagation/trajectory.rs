@@ -5,9 +5,10 @@ use anise::constants::frames::{EARTH_J2000, MOON_J2000};
 use hifitime::TimeUnits;
 use nyx::cosmic::eclipse::EclipseLocator;
 use nyx::cosmic::{GuidanceMode, Orbit, Spacecraft};
-use nyx::dynamics::guidance::{GuidanceLaw, Ruggiero, Thruster};
+use nyx::dynamics::guidance::{GuidanceLaw, ImpulsiveManeuver, LocalFrame, Ruggiero, Thruster};
 use nyx::dynamics::{OrbitalDynamics, SpacecraftDynamics};
 use nyx::io::trajectory_data::TrajectoryLoader;
+use nyx::linalg::Vector3;
 use nyx::md::prelude::{ExportCfg, Objective};
 use nyx::md::StateParameter;
 use nyx::propagators::*;
@@ -426,6 +427,92 @@ fn traj_spacecraft(almanac: Arc<Almanac>) {
     );
 }
 
+#[rstest]
+fn traj_spacecraft_finite_burn_mass(almanac: Arc<Almanac>) {
+    use nyx::cosmic::STD_GRAVITY;
+    use nyx::dynamics::guidance::{FiniteBurns, Mnvr};
+
+    // Test that the fuel mass of a spacecraft trajectory is correctly interpolated before, during, and after a finite burn.
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let orbit = Orbit::keplerian(8_000.0, 0.01, 10.0, 20.0, 30.0, 40.0, start_dt, eme2k);
+
+    let thruster = Thruster {
+        thrust_N: 10.0,
+        isp_s: 300.0,
+    };
+    let start_state = Spacecraft::from_thruster(orbit, 300.0, 50.0, thruster, GuidanceMode::Coast);
+
+    let burn_start = start_dt + 10.minutes();
+    let burn_end = start_dt + 40.minutes();
+    let burn = FiniteBurns::from_mnvrs(vec![Mnvr::from_time_invariant(
+        burn_start,
+        burn_end,
+        1.0,
+        Vector3::x(),
+        LocalFrame::VNC,
+    )]);
+
+    let setup = Propagator::default(SpacecraftDynamics::from_guidance_law(
+        OrbitalDynamics::two_body(),
+        burn,
+    ));
+
+    let (end_state, traj) = setup
+        .with(start_state, almanac.clone())
+        .for_duration_with_traj(1 * Unit::Hour)
+        .unwrap();
+
+    let mass_rate_kg_s = thruster.thrust_N / (thruster.isp_s * STD_GRAVITY);
+    let burned_kg = mass_rate_kg_s * (burn_end - burn_start).to_seconds();
+    println!(
+        "fuel burned: {:.6} kg (expected {burned_kg:.6} kg)",
+        start_state.fuel_mass_kg - end_state.fuel_mass_kg
+    );
+    assert!((start_state.fuel_mass_kg - end_state.fuel_mass_kg - burned_kg).abs() < 1e-3);
+
+    // Before the burn, the fuel mass is constant, even if the interpolation window includes the start of the burn.
+    let last_coast_idx = traj
+        .states
+        .iter()
+        .rposition(|state| state.epoch() < burn_start)
+        .unwrap();
+    let pre_burn_epoch = traj.states[last_coast_idx - 1].epoch()
+        + (traj.states[last_coast_idx].epoch() - traj.states[last_coast_idx - 1].epoch()) * 0.5;
+    let pre_burn = traj.at(pre_burn_epoch).unwrap();
+    assert_eq!(pre_burn.fuel_mass_kg, start_state.fuel_mass_kg);
+
+    // Mid-burn, the fuel mass is that of a propagation up to that epoch.
+    let mid_burn_epoch = burn_start + 12.minutes() + 3.7.seconds();
+    let mid_burn = traj.at(mid_burn_epoch).unwrap();
+    let mid_burn_prop = setup
+        .with(start_state, almanac.clone())
+        .until_epoch(mid_burn_epoch)
+        .unwrap();
+
+    let expected_kg =
+        start_state.fuel_mass_kg - mass_rate_kg_s * (mid_burn_epoch - burn_start).to_seconds();
+    println!(
+        "mid-burn fuel mass: {:.6} kg (propagated {:.6} kg, expected {expected_kg:.6} kg)",
+        mid_burn.fuel_mass_kg, mid_burn_prop.fuel_mass_kg
+    );
+    assert!((mid_burn.fuel_mass_kg - mid_burn_prop.fuel_mass_kg).abs() < 1e-6);
+    assert!((mid_burn.fuel_mass_kg - expected_kg).abs() < 1e-3);
+    assert!((mid_burn.mass_kg() - mid_burn.dry_mass_kg - mid_burn.fuel_mass_kg).abs() < 1e-12);
+
+    // And the mass decreases monotonically through the burn.
+    let mut prev_fuel_kg = start_state.fuel_mass_kg;
+    for state in traj.every(1.minutes()) {
+        assert!(state.fuel_mass_kg <= prev_fuel_kg);
+        prev_fuel_kg = state.fuel_mass_kg;
+    }
+
+    // After the burn, the fuel mass is constant again.
+    let post_burn = traj.at(burn_end + 9.1.minutes()).unwrap();
+    assert!((post_burn.fuel_mass_kg - end_state.fuel_mass_kg).abs() < 1e-9);
+}
+
 #[allow(clippy::identity_op)]
 #[rstest]
 fn traj_ephem_backward(almanac: Arc<Almanac>) {
@@ -544,3 +631,436 @@ fn traj_ephem_backward(almanac: Arc<Almanac>) {
         "Maximum state in interpolation is too high!"
     );
 }
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn traj_evaluate_many(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let start_state = Orbit::cartesian(
+        -2436.45, -2436.45, 6891.037, 5.088_611, -5.088_611, 0.0, start_dt, eme2k,
+    );
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (end_state, traj) = setup
+        .with(start_state.into(), almanac)
+        .for_duration_with_traj(1 * Unit::Day)
+        .unwrap();
+
+    // Irregular and shuffled epochs, including the bounds of the trajectory, an exact state of the trajectory, and a duplicate.
+    let epochs = vec![
+        start_dt + 17.3 * Unit::Hour,
+        end_state.epoch(),
+        start_dt + 2 * Unit::Minute + 13 * Unit::Second,
+        traj.states[traj.states.len() / 2].epoch(),
+        start_dt,
+        start_dt + 5.05 * Unit::Hour,
+        start_dt + 17.3 * Unit::Hour,
+        start_dt + 23 * Unit::Hour + 59 * Unit::Minute,
+    ];
+
+    let states = traj.evaluate_many(&epochs).unwrap();
+    assert_eq!(states.len(), epochs.len());
+    for (epoch, state) in epochs.iter().zip(&states) {
+        assert_eq!(state.epoch(), *epoch);
+        assert_eq!(*state, traj.at(*epoch).unwrap(), "mismatch @ {epoch}");
+    }
+
+    // Any epoch outside of the trajectory is an error.
+    assert!(traj
+        .evaluate_many(&[start_dt + 1 * Unit::Hour, start_dt - 1 * Unit::Second])
+        .is_err());
+    assert!(traj
+        .evaluate_many(&[end_state.epoch() + 1 * Unit::Nanosecond])
+        .is_err());
+    assert!(traj.evaluate_many(&[]).unwrap().is_empty());
+}
+
+#[rstest]
+fn traj_closest_approach(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // At the TCA, both objects are separated by 100 m radially while their relative velocity is purely along track and cross track,
+    // so the relative distance is at a minimum at that epoch.
+    let tca = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let miss_km = 0.1;
+    let speed_km_s = (eme2k.mu_km3_s2().unwrap() / 7000.0).sqrt();
+    let primary = Orbit::cartesian(7000.0, 0.0, 0.0, 0.0, speed_km_s, 0.0, tca, eme2k);
+    let secondary = Orbit::cartesian(
+        7000.0 + miss_km,
+        0.0,
+        0.0,
+        0.0,
+        speed_km_s * 45.0_f64.to_radians().cos(),
+        speed_km_s * 45.0_f64.to_radians().sin(),
+        tca,
+        eme2k,
+    );
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+
+    // Start both objects an hour before the TCA, and build two hours of trajectory.
+    let mut trajs = Vec::new();
+    for orbit in [primary, secondary] {
+        let start = setup
+            .with(orbit.into(), almanac.clone())
+            .for_duration(-1 * Unit::Hour)
+            .unwrap();
+        let (_, traj) = setup
+            .with(start, almanac.clone())
+            .for_duration_with_traj(2 * Unit::Hour)
+            .unwrap();
+        trajs.push(traj);
+    }
+
+    let conj = trajs[0].closest_approach(&trajs[1]).unwrap();
+    println!("{conj}");
+    let (tca_est, miss_est_km) = (conj.tca, conj.miss_distance_km);
+    println!(
+        "TCA error: {}\tmiss distance error: {:.3e} m",
+        tca_est - tca,
+        (miss_est_km - miss_km) * 1e3
+    );
+
+    assert!((tca_est - tca).abs() < 1 * Unit::Second);
+    assert!((miss_est_km - miss_km).abs() < 1e-3);
+    assert!((conj.rel_radius_km - Vector3::new(miss_km, 0.0, 0.0)).norm() < 1e-3);
+    // The relative velocity is that of the secondary with respect to the primary.
+    let rel_vel_err_km_s =
+        (conj.rel_velocity_km_s - (secondary.velocity_km_s - primary.velocity_km_s)).norm();
+    assert!(rel_vel_err_km_s < 1e-6, "{rel_vel_err_km_s:e} km/s");
+    assert!((conj.rel_speed_km_s() - 2.0 * speed_km_s * 22.5_f64.to_radians().sin()).abs() < 1e-6);
+
+    // The closest approach is symmetric.
+    let conj_rev = trajs[1].closest_approach(&trajs[0]).unwrap();
+    assert!((conj_rev.tca - tca_est).abs() < 1 * Unit::Millisecond);
+    assert!((conj_rev.miss_distance_km - miss_est_km).abs() < 1e-6);
+    assert!((conj_rev.rel_velocity_km_s + conj.rel_velocity_km_s).norm() < 1e-8);
+
+    // Trajectories must be in the same frame.
+    let secondary_moon = trajs[1].to_frame(MOON_J2000, almanac.clone()).unwrap();
+    assert!(trajs[0].closest_approach(&secondary_moon).is_err());
+
+    // Trajectories which do not overlap have no closest approach.
+    let later_start = setup
+        .with(*trajs[1].last(), almanac.clone())
+        .for_duration(1 * Unit::Minute)
+        .unwrap();
+    let (_, later) = setup
+        .with(later_start, almanac)
+        .for_duration_with_traj(1 * Unit::Hour)
+        .unwrap();
+    assert!(trajs[0].closest_approach(&later).is_err());
+}
+
+#[rstest]
+fn vnc_burn_raises_energy(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // On an eccentric orbit away from the apsides, the velocity is not orthogonal to the radius.
+    let epoch = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let orbit = Orbit::keplerian(20_000.0, 0.6, 30.0, 60.0, 45.0, 60.0, epoch, eme2k);
+    assert!(orbit.fpa_deg().unwrap().abs() > 10.0);
+
+    let dv_km_s = 10.0e-3;
+    let post_mnvr = orbit
+        .with_dv(Vector3::new(dv_km_s, 0.0, 0.0), LocalFrame::VNC)
+        .unwrap();
+
+    // A pure along-velocity burn only changes the speed, and raises the orbital energy.
+    assert!((post_mnvr.vmag_km_s() - orbit.vmag_km_s() - dv_km_s).abs() < 1e-12);
+    assert!(
+        post_mnvr
+            .velocity_km_s
+            .normalize()
+            .dot(&orbit.velocity_km_s.normalize())
+            > 1.0 - 1e-12
+    );
+    assert!(post_mnvr.energy_km2_s2().unwrap() > orbit.energy_km2_s2().unwrap());
+
+    // Whereas the same burn along the in-track direction of the RIC frame is not along the velocity.
+    let post_ric_mnvr = orbit
+        .with_dv(Vector3::new(0.0, dv_km_s, 0.0), LocalFrame::RIC)
+        .unwrap();
+    assert!(post_ric_mnvr.vmag_km_s() - orbit.vmag_km_s() < dv_km_s - 1e-6);
+}
+
+#[allow(clippy::identity_op)]
+#[rstest]
+fn traj_splice_impulsive_maneuver(almanac: Arc<Almanac>) {
+    let _ = pretty_env_logger::try_init();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let start_state = Orbit::keplerian(7_000.0, 0.01, 30.0, 60.0, 45.0, 45.0, start_dt, eme2k);
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (_, pre_mnvr_traj) = setup
+        .with(start_state.into(), almanac.clone())
+        .for_duration_with_traj(2 * Unit::Hour)
+        .unwrap();
+
+    // Apply a purely radial burn of 10 m/s
+    let mnvr_epoch = start_dt + 1 * Unit::Hour;
+    let pre_mnvr = pre_mnvr_traj.at(mnvr_epoch).unwrap();
+    let post_mnvr_orbit = pre_mnvr
+        .orbit
+        .with_dv(Vector3::new(10.0e-3, 0.0, 0.0), LocalFrame::RIC)
+        .unwrap();
+
+    println!("{}\n{}", pre_mnvr.orbit, post_mnvr_orbit);
+
+    // The instantaneous radius is unchanged, but the eccentricity is.
+    assert_eq!(post_mnvr_orbit.radius_km, pre_mnvr.orbit.radius_km);
+    assert!((post_mnvr_orbit.rmag_km() - pre_mnvr.orbit.rmag_km()).abs() < f64::EPSILON);
+    assert!(
+        (post_mnvr_orbit.ecc().unwrap() - pre_mnvr.orbit.ecc().unwrap()).abs() > 1e-4,
+        "radial burn did not change the eccentricity"
+    );
+
+    let (_, post_mnvr_traj) = setup
+        .with(pre_mnvr.with_orbit(post_mnvr_orbit), almanac)
+        .for_duration_with_traj(1 * Unit::Hour)
+        .unwrap();
+
+    let spliced = pre_mnvr_traj.splice(&post_mnvr_traj).unwrap();
+    println!("{spliced}");
+
+    assert_eq!(spliced.first().epoch(), start_dt);
+    assert_eq!(spliced.last().epoch(), post_mnvr_traj.last().epoch());
+
+    // Just before the maneuver, the spliced trajectory matches the pre-maneuver arc, and the post-maneuver arc just after.
+    for (epoch, expected_traj) in [
+        (mnvr_epoch - 1 * Unit::Millisecond, &pre_mnvr_traj),
+        (mnvr_epoch - 5 * Unit::Minute, &pre_mnvr_traj),
+        (mnvr_epoch + 1 * Unit::Millisecond, &post_mnvr_traj),
+        (mnvr_epoch + 5 * Unit::Minute, &post_mnvr_traj),
+    ] {
+        let spliced_state = spliced.at(epoch).unwrap();
+        let expected_state = expected_traj.at(epoch).unwrap();
+        let delta = (spliced_state.orbit - expected_state.orbit).unwrap();
+        assert!(
+            delta.rmag_km() < 1e-4 && delta.vmag_km_s() < 1e-7,
+            "spliced trajectory mismatch @ {epoch}: {:.3e} km\t{:.3e} km/s",
+            delta.rmag_km(),
+            delta.vmag_km_s()
+        );
+    }
+
+    // The velocity jump across the maneuver is the delta-v
+    let dv_km_s = spliced
+        .at(mnvr_epoch + 1 * Unit::Millisecond)
+        .unwrap()
+        .orbit
+        .velocity_km_s
+        - spliced
+            .at(mnvr_epoch - 1 * Unit::Millisecond)
+            .unwrap()
+            .orbit
+            .velocity_km_s;
+    assert!((dv_km_s.norm() - 10.0e-3).abs() < 1e-4);
+}
+
+#[rstest]
+fn traj_append_and_resample(almanac: Arc<Almanac>) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let start_state = Spacecraft::from(Orbit::keplerian(
+        7_000.0, 0.01, 28.5, 10.0, 20.0, 30.0, start_dt, eme2k,
+    ));
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (mid_state, first_arc) = setup
+        .with(start_state, almanac.clone())
+        .for_duration_with_traj(1 * Unit::Hour)
+        .unwrap();
+    let (end_state, second_arc) = setup
+        .with(mid_state, almanac.clone())
+        .for_duration_with_traj(1 * Unit::Hour)
+        .unwrap();
+
+    // Overlapping arcs cannot be appended
+    let mut overlapping = first_arc.clone();
+    assert!(overlapping.append(first_arc.clone()).is_err());
+
+    // Neither can arcs in different frames
+    let moon_j2000 = almanac.frame_from_uid(MOON_J2000).unwrap();
+    let mut other_frame = nyx::md::prelude::Traj::new();
+    other_frame.states.push(Spacecraft::from(Orbit::cartesian(
+        2_000.0,
+        0.0,
+        0.0,
+        0.0,
+        1.5,
+        0.0,
+        end_state.epoch(),
+        moon_j2000,
+    )));
+    let mut mismatched = first_arc.clone();
+    assert!(mismatched.append(other_frame).is_err());
+
+    // Clean append of the adjacent arc, sharing its first state with the end of the first arc
+    let mut traj = first_arc.clone();
+    traj.append(second_arc.clone()).unwrap();
+
+    assert_eq!(
+        traj.states.len(),
+        first_arc.states.len() + second_arc.states.len() - 1
+    );
+    assert_eq!(traj.first(), first_arc.first());
+    assert_eq!(traj.last(), &end_state);
+    let probe = mid_state.epoch() + 17 * Unit::Minute;
+    let probe_err_km = (traj.at(probe).unwrap().orbit.radius_km
+        - second_arc.at(probe).unwrap().orbit.radius_km)
+        .norm();
+    assert!(
+        probe_err_km < 1e-3,
+        "interpolation error of {probe_err_km:e} km"
+    );
+
+    // Resampling with a step which does not divide the duration preserves both endpoints
+    let step = 7 * Unit::Minute;
+    let resampled = traj.resample(step).unwrap();
+    println!("{resampled}");
+
+    assert_eq!(resampled.first(), traj.first());
+    assert_eq!(resampled.last(), traj.last());
+    for pair in resampled.states[..resampled.states.len() - 1].windows(2) {
+        assert_eq!(pair[1].epoch() - pair[0].epoch(), step);
+    }
+    assert!(resampled.last().epoch() - resampled.states[resampled.states.len() - 2].epoch() < step);
+}
+
+#[rstest]
+fn traj_to_earth_fixed_frame(almanac: Arc<Almanac>) {
+    use anise::constants::frames::IAU_EARTH_FRAME;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let start_state = Spacecraft::from(Orbit::keplerian(
+        7_000.0, 0.01, 51.6, 10.0, 20.0, 30.0, start_dt, eme2k,
+    ));
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (_, traj) = setup
+        .with(start_state, almanac.clone())
+        .for_duration_with_traj(6 * Unit::Hour)
+        .unwrap();
+
+    let traj_fixed = traj.to_frame(iau_earth, almanac.clone()).unwrap();
+    let traj_back = traj_fixed.to_frame(eme2k, almanac.clone()).unwrap();
+
+    assert_eq!(traj_fixed.states.len(), traj.states.len());
+    assert_eq!(traj_fixed.first().orbit.frame, iau_earth);
+
+    // The round trip conversion of the nodes is exact to numerical precision
+    for (orig, back) in traj.states.iter().zip(&traj_back.states) {
+        assert!((orig.orbit.radius_km - back.orbit.radius_km).norm() < 1e-6);
+        assert!((orig.orbit.velocity_km_s - back.orbit.velocity_km_s).norm() < 1e-9);
+    }
+
+    // Between the nodes, the interpolation in the rotating frame matches the conversion of the interpolated inertial state.
+    let mut max_pos_err_km = 0.0_f64;
+    let mut max_vel_err_km_s = 0.0_f64;
+    let mut max_round_trip_err_km = 0.0_f64;
+    for pair in traj.states.windows(2) {
+        let mid = pair[0].epoch() + 0.5 * (pair[1].epoch() - pair[0].epoch());
+
+        let inertial = traj.at(mid).unwrap().orbit;
+        let expected = almanac.transform_to(inertial, iau_earth, None).unwrap();
+        let interpolated = traj_fixed.at(mid).unwrap().orbit;
+
+        max_pos_err_km = max_pos_err_km.max((interpolated.radius_km - expected.radius_km).norm());
+        max_vel_err_km_s =
+            max_vel_err_km_s.max((interpolated.velocity_km_s - expected.velocity_km_s).norm());
+        max_round_trip_err_km = max_round_trip_err_km
+            .max((traj_back.at(mid).unwrap().orbit.radius_km - inertial.radius_km).norm());
+    }
+
+    println!(
+        "max interior errors: {:.3} m\t{:.3} mm/s\tround trip: {:.3} m",
+        max_pos_err_km * 1e3,
+        max_vel_err_km_s * 1e6,
+        max_round_trip_err_km * 1e3
+    );
+
+    assert!(max_pos_err_km < 1e-3);
+    assert!(max_vel_err_km_s < 1e-6);
+    assert!(max_round_trip_err_km < 1e-3);
+}
+
+#[rstest]
+fn traj_export_keplerian_fields(almanac: Arc<Almanac>) {
+    use polars::prelude::{ParquetReader, SerReader};
+    use std::fs::File;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    let start_dt = Epoch::from_gregorian_utc_at_noon(2021, 1, 1);
+    let start_state = Spacecraft::from(Orbit::keplerian(
+        7_000.0, 0.01, 51.6, 10.0, 20.0, 30.0, start_dt, eme2k,
+    ));
+
+    let setup = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()));
+    let (_, traj) = setup
+        .with(start_state, almanac.clone())
+        .for_duration_with_traj(2 * Unit::Hour)
+        .unwrap();
+
+    let fields = [
+        StateParameter::SMA,
+        StateParameter::Eccentricity,
+        StateParameter::Inclination,
+        StateParameter::RAAN,
+        StateParameter::AoP,
+        StateParameter::TrueAnomaly,
+    ];
+
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "output_data",
+        "traj_keplerian.parquet",
+    ]
+    .iter()
+    .collect();
+
+    let output_path = traj
+        .to_parquet(path, None, ExportCfg::from_fields(&fields), almanac)
+        .unwrap();
+
+    let df = ParquetReader::new(File::open(output_path).unwrap())
+        .finish()
+        .unwrap();
+
+    // Only the epoch and the requested fields are exported
+    assert_eq!(df.width(), fields.len() + 1);
+    assert_eq!(df.height(), traj.states.len());
+
+    // The SMA is constant in two body dynamics
+    let sma_km = df
+        .column(&format!("{}", StateParameter::SMA))
+        .unwrap()
+        .f64()
+        .unwrap();
+    let sma_spread_km = sma_km.max().unwrap() - sma_km.min().unwrap();
+    println!("SMA spread: {:.3e} m", sma_spread_km * 1e3);
+    assert!(sma_spread_km < 1e-5);
+    assert!((sma_km.get(0).unwrap() - 7_000.0).abs() < 1e-6);
+
+    // Angles are exported in degrees
+    let inc_deg = df
+        .column(&format!("{}", StateParameter::Inclination))
+        .unwrap()
+        .f64()
+        .unwrap();
+    assert!((inc_deg.mean().unwrap() - 51.6).abs() < 1e-6);
+}
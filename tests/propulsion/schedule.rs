@@ -371,3 +371,159 @@ fn val_transfer_single_maneuver_depl(almanac: Arc<Almanac>) {
         err_v
     );
 }
+
+#[rstest]
+fn leo_drag_finite_burn_mass_depl(almanac: Arc<Almanac>) {
+    /* Checks that the spacecraft properties are used both by the drag model and by the finite burn, and that the fuel mass decreases at the rate of the thruster. */
+    use nyx::cosmic::STD_GRAVITY;
+    use nyx::dynamics::Drag;
+
+    let eme2k = almanac
+        .frame_from_uid(EARTH_J2000)
+        .unwrap()
+        .with_mu_km3_s2(GMAT_EARTH_GM);
+
+    let start_time = Epoch::from_gregorian_tai_at_midnight(2002, 1, 1);
+    let orbit =
+        Orbit::try_keplerian_altitude(400.0, 1e-3, 51.6, 20.0, 30.0, 40.0, start_time, eme2k)
+            .unwrap();
+
+    let monoprop = Thruster {
+        thrust_N: 10.0,
+        isp_s: 300.0,
+    };
+    let dry_mass_kg = 500.0;
+    let fuel_mass_kg = 100.0;
+    let sc_state = Spacecraft::from_thruster(
+        orbit,
+        dry_mass_kg,
+        fuel_mass_kg,
+        monoprop,
+        GuidanceMode::Coast,
+    )
+    .with_drag(5.0, 2.2);
+
+    let prop_time = 30.0 * Unit::Minute;
+    let burn_time = 10.0 * Unit::Minute;
+
+    let mnvr0 = Mnvr::from_time_invariant(
+        start_time,
+        start_time + burn_time,
+        1.0, // Full thrust
+        Vector3::new(1.0, 0.0, 0.0),
+        LocalFrame::VNC,
+    );
+
+    let mut sc_dyn =
+        SpacecraftDynamics::from_guidance_law(OrbitalDynamics::two_body(), Arc::new(mnvr0));
+    sc_dyn
+        .force_models
+        .push(Drag::earth_exp(almanac.clone()).unwrap());
+
+    let final_state = Propagator::rk89(sc_dyn, PropOpts::with_fixed_step(10.0 * Unit::Second))
+        .with(sc_state, almanac)
+        .for_duration(prop_time)
+        .unwrap();
+
+    println!("{sc_state}\n{final_state}");
+
+    // The fuel should only be consumed during the burn, at a constant rate.
+    let mdot_kg_s = monoprop.thrust_N / (monoprop.isp_s * STD_GRAVITY);
+    let expected_fuel_mass_kg = fuel_mass_kg - mdot_kg_s * burn_time.to_seconds();
+
+    let delta_fuel_mass = (final_state.fuel_mass_kg - expected_fuel_mass_kg).abs();
+    println!("Absolute fuel mass error: {:.0e} kg", delta_fuel_mass);
+    assert!(delta_fuel_mass < 1e-9, "incorrect fuel mass");
+    assert!(
+        (final_state.dry_mass_kg - dry_mass_kg).abs() < f64::EPSILON,
+        "dry mass must not change"
+    );
+    // The drag and SRP properties are carried along unchanged.
+    assert_eq!(final_state.drag, sc_state.drag);
+    assert_eq!(final_state.srp, sc_state.srp);
+    // The velocity-aligned burn raised the orbit despite the drag.
+    assert!(final_state.orbit.sma_km().unwrap() > sc_state.orbit.sma_km().unwrap());
+}
+
+#[rstest]
+fn tangential_burn_flameout_rocket_equation(almanac: Arc<Almanac>) {
+    /* Checks that a tangential burn longer than the propellant allows cuts off when the tank is empty, and that the delta-v matches the rocket equation. */
+    use nyx::cosmic::STD_GRAVITY;
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+
+    // Far from the Earth, such that the gravity gradient barely affects the velocity difference with a coasting spacecraft.
+    let start_time = Epoch::from_gregorian_tai_at_midnight(2002, 1, 1);
+    let orbit = Orbit::keplerian(150_000.0, 0.3, 10.0, 20.0, 30.0, 0.0, start_time, eme2k);
+
+    let monoprop = Thruster {
+        thrust_N: 10.0,
+        isp_s: 300.0,
+    };
+    let dry_mass_kg = 100.0;
+    let fuel_mass_kg = 5.0;
+    let sc_state = Spacecraft::from_thruster(
+        orbit,
+        dry_mass_kg,
+        fuel_mass_kg,
+        monoprop,
+        GuidanceMode::Thrust,
+    );
+
+    // The maneuver is scheduled for much longer than the fuel lasts.
+    let mdot_kg_s = monoprop.thrust_N / (monoprop.isp_s * STD_GRAVITY);
+    let depletion_s = fuel_mass_kg / mdot_kg_s;
+    println!("fuel depleted after {depletion_s:.3} s");
+
+    let mnvr0 = Mnvr::from_time_invariant(
+        start_time,
+        start_time + 2.0 * depletion_s * Unit::Second,
+        1.0, // Full thrust
+        Vector3::new(1.0, 0.0, 0.0),
+        LocalFrame::VNC,
+    );
+
+    let sc_dyn =
+        SpacecraftDynamics::from_guidance_law(OrbitalDynamics::two_body(), Arc::new(mnvr0));
+
+    let prop_time = depletion_s * Unit::Second + 1 * Unit::Minute;
+
+    let coast_state = Propagator::default(SpacecraftDynamics::new(OrbitalDynamics::two_body()))
+        .with(sc_state, almanac.clone())
+        .for_duration(prop_time)
+        .unwrap();
+
+    let rocket_eq_km_s =
+        monoprop.isp_s * STD_GRAVITY * 1e-3 * ((dry_mass_kg + fuel_mass_kg) / dry_mass_kg).ln();
+
+    // The adaptive step lands close to the depletion, whereas a large fixed step overshoots it:
+    // the thrust past the flameout must be removed in both cases.
+    for opts in [
+        PropOpts::default(),
+        PropOpts::with_fixed_step(45 * Unit::Second),
+    ] {
+        let final_state = Propagator::rk89(sc_dyn.clone(), opts)
+            .with(sc_state, almanac.clone())
+            .for_duration(prop_time)
+            .unwrap();
+
+        println!("{sc_state}\n{final_state}");
+
+        assert_eq!(final_state.fuel_mass_kg, 0.0, "tank should be empty");
+        assert!(
+            final_state.orbit.apoapsis_km().unwrap()
+                > sc_state.orbit.apoapsis_km().unwrap() + 1_000.0,
+            "tangential burn should raise the apoapsis"
+        );
+
+        let delta_v_km_s =
+            (final_state.orbit.velocity_km_s - coast_state.orbit.velocity_km_s).norm();
+        let rel_err = (delta_v_km_s - rocket_eq_km_s).abs() / rocket_eq_km_s;
+        println!(
+            "Δv = {:.3} m/s\trocket equation = {:.3} m/s\trelative error = {rel_err:.1e}",
+            delta_v_km_s * 1e3,
+            rocket_eq_km_s * 1e3
+        );
+        assert!(rel_err < 1e-3, "delta-v does not match the rocket equation");
+    }
+}
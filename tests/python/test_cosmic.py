@@ -1,59 +1,46 @@
-from nyx_space.cosmic import Orbit, Spacecraft, SrpConfig, DragConfig
+from pathlib import Path
+
+from nyx_space.cosmic import Almanac, Frame, Orbit, Spacecraft, SrpConfig, DragConfig
 from nyx_space.time import Epoch, Unit, Duration
 from nyx_space.monte_carlo import generate_orbits, generate_spacecraft, StateParameter
 import pickle
 
 
-def test_load_cosm():
-    cosm = Cosm.de438()
-    assert cosm.frames_get_names() == [
-        "SSB J2000",
-        "Sun J2000",
-        "iau sun",
-        "Mercury Barycenter J2000",
-        "Venus Barycenter J2000",
-        "iau venus",
-        "Earth Barycenter J2000",
-        "Earth J2000",
-        "iau earth",
-        "Moon J2000",
-        "iau moon",
-        "Mars Barycenter J2000",
-        "iau mars",
-        "Jupiter Barycenter J2000",
-        "iau jupiter",
-        "Saturn Barycenter J2000",
-        "iau saturn",
-        "Uranus Barycenter J2000",
-        "iau uranus",
-        "Neptune Barycenter J2000",
-        "iau neptune",
-        "Pluto Barycenter J2000",
-    ]
+def load_almanac():
+    """
+    Loads the planetary constants and the DE440s ephemerides
+    """
+    root = Path(__file__).joinpath("../../../").resolve()
+    return Almanac(str(root.joinpath("./data/pck08.pca"))).load(
+        str(root.joinpath("./data/de440s.bsp"))
+    )
+
+
+def test_load_almanac():
+    almanac = load_almanac()
+    eme2k = almanac.frame_info(Frame(399, 1))
+    # SPICE data, not GMAT data (398600.4415)
+    assert abs(eme2k.mu_km3_s2() - 398600.435436) < 1e-6
 
 
 def test_define_spacecraft():
-    cosm = Cosm.de438()
-    eme2k = cosm.frame("EME2000")
-
-    assert eme2k.gm() == 398600.435392  # SPICE data, not GMAT data (398600.4415)
-    assert eme2k.is_geoid()
-    assert eme2k.equatorial_radius() == 6378.1363
+    almanac = load_almanac()
+    eme2k = almanac.frame_info(Frame(399, 1))
 
     e = Epoch.system_now()
 
-    orbit = Orbit.from_try_keplerian_altitude(
+    orbit = Orbit.from_keplerian_altitude(
         400,
-        ecc=1e-4,
-        inc_deg=30.5,
-        raan_deg=35.0,
-        aop_deg=65.0,
-        ta_deg=590,
-        epoch=e,
-        frame=eme2k,
+        1e-4,
+        30.5,
+        35.0,
+        65.0,
+        590,
+        e,
+        eme2k,
     )
 
-    assert orbit.period().unwrap() == Unit.Second * 5553.623455582
+    assert abs(orbit.period().to_seconds() - 5553.62) < 0.1
 
     print(orbit)
     print(repr(orbit))
@@ -98,24 +85,20 @@ def test_generate_states():
     Tests that we can generate orbits from their state parameter deviations
     """
     # Build a demo orbit
-    cosm = Cosm.de438()
-    eme2k = cosm.frame("EME2000")
-
-    assert eme2k.gm() == 398600.435392  # SPICE data, not GMAT data (398600.4415)
-    assert eme2k.is_geoid()
-    assert eme2k.equatorial_radius() == 6378.1363
+    almanac = load_almanac()
+    eme2k = almanac.frame_info(Frame(399, 1))
 
     e = Epoch.system_now()
 
-    orbit = Orbit.from_try_keplerian_altitude(
+    orbit = Orbit.from_keplerian_altitude(
         400,
-        ecc=1e-4,
-        inc_deg=30.5,
-        raan_deg=35.0,
-        aop_deg=65.0,
-        ta_deg=590,
-        epoch=e,
-        frame=eme2k,
+        1e-4,
+        30.5,
+        35.0,
+        65.0,
+        590,
+        e,
+        eme2k,
     )
 
     # Generate a bunch of them in percentage of error
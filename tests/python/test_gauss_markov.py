@@ -14,9 +14,9 @@ def test_fogm(plot=False):
     root = Path(__file__).joinpath("../../../").resolve()
     outpath = root.joinpath("output_data/")
 
-    gm = GaussMarkov(tau=Unit.Hour * 24, sigma=5.6, steady_state=0.5)
+    gm = GaussMarkov(tau=Unit.Hour * 24, process_noise=5.6)
     print(gm)
-    assert str(gm) == "First order Gauss-Markov process with τ = 1 days, σ_b = 5.6, σ_q = 0.5"
+    assert str(gm) == "First order Gauss-Markov process with τ = 1 days, σ = 5.6"
     gm.simulate(str(outpath.joinpath("fogm.parquet")))
 
     # Read in the file
@@ -27,15 +27,15 @@ def test_fogm(plot=False):
     assert df["Bias (unitless)"].mean() != 0.0
     assert df["Bias (unitless)"].max() != 0.0
     assert df["Bias (unitless)"].min() != 0.0
-    assert df["Bias (unitless)"].count() == 12525
+    assert df["Bias (unitless)"].count() == 27050
 
     if plot:
         plot_gauss_markov(df, title=f"{gm}", tau=gm.tau.to_seconds())
 
 
-def test_defaults(kinds=["Range", "RangeHP", "Doppler", "DopplerHP"], plot=False):
+def test_defaults(kinds=["Range", "Doppler"], plot=False):
     """
-    Tests the four default models
+    Tests the two default models
     """
 
     # Base path
@@ -5,8 +5,7 @@ from pathlib import Path
 from timeit import timeit
 
 import pandas as pd
-import yaml
-from nyx_space.cosmic import Orbit, Spacecraft, SrpConfig
+from nyx_space.cosmic import Almanac, Frame, Orbit, Spacecraft, SrpConfig
 from nyx_space.mission_design import (
     Event,
     SpacecraftDynamics,
@@ -15,10 +14,40 @@ from nyx_space.mission_design import (
     propagate,
     two_body,
 )
-from nyx_space.monte_carlo import StateParameter, generate_orbits
+from nyx_space.monte_carlo import generate_orbits
 from nyx_space.plots import plot_traj_errors
 from nyx_space.time import Duration, Epoch, TimeSeries, Unit
 
+# Base path
+root = Path(__file__).joinpath("../../../").resolve()
+
+
+def load_almanac():
+    """
+    Loads the planetary constants and the DE440s ephemerides
+    """
+    return Almanac(str(root.joinpath("./data/pck08.pca"))).load(
+        str(root.joinpath("./data/de440s.bsp"))
+    )
+
+
+def build_spacecraft(almanac):
+    """
+    Builds the spacecraft also used in the Rust tests, in the Earth J2000 frame.
+    """
+    eme2k = almanac.frame_info(Frame(399, 1))
+    orbit = Orbit.from_cartesian(
+        -9042.862234,
+        18536.333069,
+        6999.957069,
+        -3.288789,
+        -2.226285,
+        1.646738,
+        Epoch("2018-09-15T00:15:53.098 UTC"),
+        eme2k,
+    )
+    return Spacecraft(orbit, 50.0, 50.0, SrpConfig(2.0, 1.0))
+
 
 def test_propagate():
     # Initialize logging
@@ -26,70 +55,38 @@ def test_propagate():
     logging.basicConfig(format=FORMAT)
     logging.getLogger().setLevel(logging.INFO)
 
-    # Base path
-    root = Path(__file__).joinpath("../../../").resolve()
-
-    config_path = root.joinpath("./data/tests/config/")
-
-    sc = Spacecraft.load(str(config_path.joinpath("spacecraft.yaml")))
-    # Check that we have loaded this correctly by checking the values from the YAML file
+    almanac = load_almanac()
+    sc = build_spacecraft(almanac)
+    # Check that we have built this correctly
     assert sc.value_of(StateParameter.X) == -9042.862234
-    assert sc.value_of(StateParameter.Y) == 18536.333069
-    assert sc.value_of(StateParameter.Z) == 6999.957069
-    assert sc.value_of(StateParameter.VX) == -3.288789
-    assert sc.value_of(StateParameter.VY) == -2.226285
     assert sc.value_of(StateParameter.VZ) == 1.646738
     assert sc.value_of(StateParameter.Cr) == 1.0
-    assert sc.value_of(StateParameter.Cd) == 2.2
     assert sc.value_of(StateParameter.FuelMass) == 50.0
     assert sc.epoch.timedelta(Epoch("2018-09-15T00:15:53.098 UTC")) == Duration.zero()
-    # Check other stuff that is computed on request
-    assert sc.value_of(StateParameter.SMA) == 21999.99774705774
-
-    dynamics = SpacecraftDynamics.load_named(str(config_path.joinpath("dynamics.yaml")))
-    # So far, there are no accessors on the dynamics, so we will check what they print out =(
-    assert (
-        f"{dynamics['lofi']}"
-        == "Spacecraft dynamics (with guidance = false): No force models; Orbital dynamics: Point masses of Sun J2000, Earth J2000"
-    )
-    assert (
-        f"{dynamics['hifi']}"
-        == "Spacecraft dynamics (with guidance = false): SRP with φ = 1367 W/m^2 and eclipse light-source: Sun J2000, shadows casted by: Sun J2000, Moon J2000;  Orbital dynamics: Point masses of Sun J2000, Earth J2000, Moon J2000; IAU Earth gravity field 10x10 (order x degree)"
-    )
 
-    rslt, traj = propagate(sc, dynamics["lofi"], Unit.Day * 5.159)
+    # Point masses of the Sun and the Earth
+    dynamics = SpacecraftDynamics([10, 399])
+
+    rslt, traj = propagate(sc, dynamics, almanac, Unit.Day * 5.159)
     # Check that we propagated for the correct duration
     assert rslt.epoch.timedelta(sc.epoch) == Duration("5 days 3 h 48 min 57 s 600 ms")
-    # We aren't in two body dynamics so the SMA has changed among other things.
-    assert rslt.value_of(StateParameter.SMA) == 22000.024381883788
     # Grab a specific epoch from the trajectory
     sc_state = traj.at(Epoch("2018-09-16T00:16:53 TDB"))
     # Check that we got the right epoch
     assert sc_state.epoch.timedelta(Epoch("2018-09-16T00:16:53 TDB")) == Duration.zero()
-    # Save the file to parquet
-    traj.to_parquet("lofi.parquet")
 
     # We can also propagate with a different method
-    rslt, traj = propagate(sc, dynamics["lofi"], Unit.Day * 5.159, method="Dormand78")
+    rslt, traj = propagate(sc, dynamics, almanac, Unit.Day * 5.159, method="Dormand78")
     assert rslt.epoch.timedelta(sc.epoch) == Duration("5 days 3 h 48 min 57 s 600 ms")
 
-    # Let's now propagate the original spacecraft to its apoapsis, but let's search no more than 2 orbit periods
-    rslt_apo, _ = propagate(
-        sc,
-        dynamics["lofi"],
-        sc.orbit.period().unwrap() * 2,
-        event=Event(StateParameter.Apoapsis, 0.0),
-    )
-    # Let's make sure that worked to within the default precision
-    assert abs(rslt_apo.value_of(StateParameter.TrueAnomaly) - 180.0) < 0.001
-
     event = Event(StateParameter.Apoapsis, 0.0, value_precision=1e-6)
 
-    # But we can also be even more precise
+    # Let's now propagate the original spacecraft to its apoapsis, but let's search no more than 2 orbit periods
     rslt_apo, traj = propagate(
         sc,
-        dynamics["lofi"],
-        sc.orbit.period().unwrap() * 2,
+        dynamics,
+        almanac,
+        sc.orbit.period() * 2,
         event=event,
     )
     assert abs(rslt_apo.orbit.ta_deg() - 180.0) <= 1e-6
@@ -110,57 +107,46 @@ def test_propagate():
     assert rebuilt_traj.last().epoch == epochs[-2]
 
     # Export this trajectory with additional metadata and the events
-    # Base path
-    root = Path(__file__).joinpath("../../../").resolve()
     outpath = root.joinpath("output_data/")
 
     # Note: Python interface only supports strings for paths, not Path objects.
     traj.to_parquet(
         str(outpath.joinpath("./lofi_with_events.parquet")),
-        metadata={"dynamics": str(dynamics["lofi"])},
+        almanac,
+        metadata={"dynamics": str(dynamics)},
         events=[event],
     )
 
-    # Propagate until the lo-fi epoch to compare
-    _, traj_hifi = propagate(
+    # Propagate until the apoapsis epoch with the Moon as well to compare
+    _, traj_moon = propagate(
         sc,
-        dynamics["hifi"],
+        SpacecraftDynamics([10, 399, 301]),
+        almanac,
         epoch=rslt_apo.epoch,
     )
 
     # Plot both trajectories in RIC frame
     if sys.platform != "win32":
-        diff_path = str(outpath.joinpath("./lofi_hifi_ric_diff.parquet"))
-        traj.ric_diff_to_parquet(traj_hifi, diff_path)
+        diff_path = str(outpath.joinpath("./lofi_moon_ric_diff.parquet"))
+        traj.ric_diff_to_parquet(traj_moon, diff_path)
 
         traj_diff_ric = pd.read_parquet(diff_path)
 
         plot_traj_errors(
             traj_diff_ric,
-            "LoFi vs HiFi",
-            html_out=outpath.joinpath("./md_ric_lofi_hifi_diff.html"),
-            show=False,
-        )
-
-        plot_traj_errors(
-            traj_diff_ric,
-            "LoFi vs HiFi",
-            html_out=outpath.joinpath("./md_ric_lofi_hifi_diff_velocity.html"),
-            vertical=True,
-            velocity=True,
+            "Without vs with the Moon",
+            html_out=outpath.joinpath("./md_ric_lofi_moon_diff.html"),
             show=False,
         )
 
-    # Resample the trajectory at fixed step size of 25 seconds
-    traj = traj.resample(Unit.Second * 25.0)
-
-    # Also export this ground track
-    cosm = Cosm.de438()
-    iau_earth = cosm.frame("IAU Earth")
-    traj.to_parquet("iau_earth_lofi.parquet", groundtrack=iau_earth)
+    # Also export this ground track in the IAU Earth frame
+    iau_earth = almanac.frame_info(Frame(399, 3000))
+    traj.to_parquet(
+        str(outpath.joinpath("./iau_earth_lofi.parquet")), almanac, groundtrack=iau_earth
+    )
 
     # Let's also search for this event in the trajectory
-    for sc_at_event in traj.find(event):
+    for sc_at_event in traj.find(event, almanac):
         print(sc_at_event)
         assert abs(sc_at_event.value_of(StateParameter.TrueAnomaly) - 180.0) <= 1e-6
 
@@ -174,9 +160,9 @@ def test_build_spacecraft():
     logging.basicConfig(format=FORMAT)
     logging.getLogger().setLevel(logging.INFO)
 
-    cosm = Cosm.de438()
-    eme2k = cosm.frame("EME2000")  # Earth Mean Equator J2000
-    orbit = Orbit.from_try_keplerian_altitude(
+    almanac = load_almanac()
+    eme2k = almanac.frame_info(Frame(399, 1))  # Earth Mean Equator J2000
+    orbit = Orbit.from_keplerian_altitude(
         400.0, 0.01, 15.6, 45.0, 90.0, 75.0, Epoch.system_now(), eme2k
     )
     # Define the SRP
@@ -193,12 +179,10 @@ def test_build_spacecraft():
 
     # Using this spacecraft as a template, let's load an OEM file, convert it to Parquet, and ensure we can load it back in.
     # The orbit data will be overwritten with data from the OEM file.
-    root = Path(__file__).joinpath("../../../").resolve()
-
     config_path = root.joinpath("./data/tests/ccsds/oem/LEO_10s.oem")
     output_path = root.joinpath("./output_data/LEO_10s.parquet")
     # Convert from OEM to Parquet will happen on load
-    traj = TrajectoryLoader(str(config_path), "oem", str(output_path), sc)
+    traj = TrajectoryLoader(str(config_path), "oem", str(output_path), sc, almanac)
     print(traj)
     # Check that we can pickle the trajectory loader object
     traj_pkl = pickle.dumps(traj)
@@ -206,8 +190,6 @@ def test_build_spacecraft():
     assert traj_unpkl == traj
     # Check that we can convert this to a spacecraft trajectory
     traj_sc = traj.to_spacecraft_traj()
-    traj_orbit = traj.to_orbit_traj()
-    traj_orbit_dc = traj_sc.downcast()
     # Check that we can query it (will raise an exception if we can't, thereby failing the test)
     ts = TimeSeries(
         Epoch("2020-06-01T12:00:00.000000"),
@@ -216,39 +198,26 @@ def test_build_spacecraft():
         inclusive=True,
     )
     for epoch in ts:
-        orbit = traj_orbit.at(epoch)
-        dc_orbit = traj_orbit_dc.at(epoch)
         sc_orbit = traj_sc.at(epoch).orbit
-        # Check params individually
-        assert orbit.epoch == sc_orbit.epoch
-        assert orbit.x_km == sc_orbit.x_km
-        assert orbit.vx_km_s == sc_orbit.vx_km_s
-        # Check the downcasted version
-        assert dc_orbit.x_km == sc_orbit.x_km
-        assert dc_orbit.vx_km_s == sc_orbit.vx_km_s
-        assert dc_orbit == sc_orbit
+        assert sc_orbit.epoch == epoch
 
 
 def test_two_body():
     # Build a demo orbit
-    cosm = Cosm.de438()
-    eme2k = cosm.frame("EME2000")
-
-    assert eme2k.gm() == 398600.435392  # SPICE data, not GMAT data (398600.4415)
-    assert eme2k.is_geoid()
-    assert eme2k.equatorial_radius() == 6378.1363
+    almanac = load_almanac()
+    eme2k = almanac.frame_info(Frame(399, 1))
 
     e = Epoch.system_now()
 
-    orbit = Orbit.from_try_keplerian_altitude(
+    orbit = Orbit.from_keplerian_altitude(
         400,
-        ecc=1e-4,
-        inc_deg=30.5,
-        raan_deg=35.0,
-        aop_deg=65.0,
-        ta_deg=590,
-        epoch=e,
-        frame=eme2k,
+        1e-4,
+        30.5,
+        35.0,
+        65.0,
+        590,
+        e,
+        eme2k,
     )
 
     orbits = generate_orbits(
@@ -283,25 +252,14 @@ def test_merge_traj():
     logging.basicConfig(format=FORMAT)
     logging.getLogger().setLevel(logging.INFO)
 
-    # Base path
-    root = Path(__file__).joinpath("../../../").resolve()
+    almanac = load_almanac()
+    sc1 = build_spacecraft(almanac)
 
-    config_path = root.joinpath("./data/tests/config/")
+    dynamics = SpacecraftDynamics([10, 399])
 
-    sc1 = Spacecraft.load(str(config_path.joinpath("spacecraft.yaml")))
-
-    dynamics = SpacecraftDynamics.load_named(str(config_path.joinpath("dynamics.yaml")))["lofi"]
-
-    # Check loading from the YAML read from Python
-    with open(config_path.joinpath("dynamics.yaml")) as fh:
-        data = yaml.safe_load(fh)
-
-    loaded = SpacecraftDynamics.loads(data["lofi"])
-    assert loaded == dynamics
-
-    sc2, traj1 = propagate(sc1, dynamics, Unit.Day * 5)
+    sc2, traj1 = propagate(sc1, dynamics, almanac, Unit.Day * 5)
     # And propagate again
-    sc3, traj2 = propagate(sc2, dynamics, Unit.Day * 5)
+    sc3, traj2 = propagate(sc2, dynamics, almanac, Unit.Day * 5)
     # Add the trajectories
     traj = traj1 + traj2
 
@@ -309,8 +267,9 @@ def test_merge_traj():
 
     # Convert into another frame and try to add them too.
     # We only check the epoch this time.
-    traj1_moon = traj1.to_frame("Moon J2000")
-    traj2_moon = traj2.to_frame("Moon J2000")
+    moon_j2k = almanac.frame_info(Frame(301, 1))
+    traj1_moon = traj1.to_frame(moon_j2k, almanac)
+    traj2_moon = traj2.to_frame(moon_j2k, almanac)
 
     traj_moon = traj1_moon + traj2_moon
 
@@ -318,6 +277,63 @@ def test_merge_traj():
     print(traj_moon)
 
 
+def test_python_force_model():
+    almanac = load_almanac()
+    sc = build_spacecraft(almanac)
+    dynamics = SpacecraftDynamics([10, 399])
+
+    accel_km_s2 = 1e-9
+    calls = []
+
+    def constant_accel(spacecraft):
+        calls.append(spacecraft.epoch)
+        return [0.0, 0.0, accel_km_s2]
+
+    custom = dynamics.with_force_model(constant_accel)
+    assert "Python force model `constant_accel`" in f"{custom}"
+
+    duration = Unit.Hour * 1
+    rslt, _ = propagate(sc, dynamics, almanac, duration, fixed_step=Unit.Second * 10)
+    rslt_custom, _ = propagate(sc, custom, almanac, duration, fixed_step=Unit.Second * 10)
+
+    assert len(calls) > 0
+    # Over a short duration compared to the orbital period, the displacement is that of a constant acceleration
+    delta_z = rslt_custom.value_of(StateParameter.Z) - rslt.value_of(StateParameter.Z)
+    expected = 0.5 * accel_km_s2 * 3600.0**2
+    assert abs(delta_z - expected) / expected < 0.2, f"{delta_z} km != {expected} km"
+
+
+
+def test_python_force_model_stm():
+    almanac = load_almanac()
+    sc = build_spacecraft(almanac).with_stm()
+    dynamics = SpacecraftDynamics([10, 399])
+
+    def radial_accel(spacecraft):
+        # A radial acceleration inversely proportional to the distance, such that its position partials are not zero
+        pos_km = [
+            spacecraft.value_of(StateParameter.X),
+            spacecraft.value_of(StateParameter.Y),
+            spacecraft.value_of(StateParameter.Z),
+        ]
+        rmag_km2 = sum(x**2 for x in pos_km)
+        return [1e-3 * x / rmag_km2 for x in pos_km]
+
+    custom = dynamics.with_force_model(radial_accel)
+
+    duration = Unit.Hour * 1
+    rslt, _ = propagate(sc, dynamics, almanac, duration, fixed_step=Unit.Second * 10)
+    rslt_custom, _ = propagate(sc, custom, almanac, duration, fixed_step=Unit.Second * 10)
+
+    stm = rslt.stm()
+    stm_custom = rslt_custom.stm()
+    # The partials of the Python force model must contribute to the STM
+    max_diff = max(
+        abs(stm_custom[i][j] - stm[i][j]) for i in range(6) for j in range(6)
+    )
+    assert max_diff > 1e-9, "the Python force model does not contribute to the STM"
+
+
 if __name__ == "__main__":
     test_propagate()
     test_merge_traj()
@@ -1,4 +1,5 @@
-from nyx_space.orbit_determination import GroundStation, GaussMarkov, FltResid
+from nyx_space.cosmic import Frame
+from nyx_space.orbit_determination import GroundStation, GaussMarkov, ResidRejectCrit
 from nyx_space.time import Unit
 
 import pickle
@@ -6,17 +7,13 @@ import pickle
 gs_data = {
     "one": {
         "name": "Santiago, CL",
-        "frame": "IAU Earth",
+        "frame": {"ephemeris_id": 399, "orientation_id": 399},
         "elevation_mask_deg": 5.0,
         "range_noise_km": {
-            "tau": "24 h",
-            "bias_sigma": 0.005,
-            "steady_state_sigma": 0.0001,
+            "bias": {"tau": "24 h", "process_noise": 0.005},
         },
         "doppler_noise_km_s": {
-            "tau": "24h",
-            "bias_sigma": 5e-05,
-            "steady_state_sigma": 1.5e-06,
+            "bias": {"tau": "24 h", "process_noise": 5e-05},
         },
         "light_time_correction": False,
         "latitude_deg": -33.447487,
@@ -25,58 +22,46 @@ gs_data = {
     },
     "two": {
         "name": "South Point, Hawaii, US",
-        "frame": "IAU Earth",
+        "frame": {"ephemeris_id": 399, "orientation_id": 399},
         "latitude_deg": 18.911057,
         "longitude_deg": -155.681022,
         "height_km": 0.5,
         "elevation_mask_deg": 5.0,
         "range_noise_km": {
-            "tau": "24 h",
-            "bias_sigma": 0.005,
-            "steady_state_sigma": 0.0001,
+            "bias": {"tau": "24 h", "process_noise": 0.005},
         },
         "doppler_noise_km_s": {
-            "tau": "24 h",
-            "bias_sigma": 5e-05,
-            "steady_state_sigma": 1.5e-06,
+            "bias": {"tau": "24 h", "process_noise": 5e-05},
         },
         "light_time_correction": False,
     },
     "three": {
         "name": "Dongara, AU",
-        "frame": "IAU Earth",
+        "frame": {"ephemeris_id": 399, "orientation_id": 399},
         "latitude_deg": -29.251281,
         "longitude_deg": 114.934621,
         "height_km": 0.5,
         "elevation_mask_deg": 5.0,
         "range_noise_km": {
-            "tau": "24 h",
-            "bias_sigma": 0.005,
-            "steady_state_sigma": 0.0001,
+            "bias": {"tau": "24 h", "process_noise": 0.005},
         },
         "doppler_noise_km_s": {
-            "tau": "24 h",
-            "bias_sigma": 5e-05,
-            "steady_state_sigma": 1.5e-06,
+            "bias": {"tau": "24 h", "process_noise": 5e-05},
         },
         "light_time_correction": False,
     },
     "four": {
         "name": "Maspalomas, ES",
-        "frame": "IAU Earth",
+        "frame": {"ephemeris_id": 399, "orientation_id": 399},
         "latitude_deg": 27.760562,
         "longitude_deg": -15.586017,
         "height_km": 0.5,
         "elevation_mask_deg": 5.0,
         "range_noise_km": {
-            "tau": "24 h",
-            "bias_sigma": 0.005,
-            "steady_state_sigma": 0.0001,
+            "bias": {"tau": "24 h", "process_noise": 0.005},
         },
         "doppler_noise_km_s": {
-            "tau": "24 h",
-            "bias_sigma": 5e-05,
-            "steady_state_sigma": 1.5e-06,
+            "bias": {"tau": "24 h", "process_noise": 5e-05},
         },
         "light_time_correction": False,
     },
@@ -85,13 +70,11 @@ gs_data = {
 gm_data = {
     "range_noise_km": {
         "tau": "24 h",
-        "bias_sigma": 0.005,
-        "steady_state_sigma": 0.0001,
+        "process_noise": 0.005,
     },
     "doppler_noise_km_s": {
         "tau": "24h",
-        "bias_sigma": 5e-05,
-        "steady_state_sigma": 1.5e-06,
+        "process_noise": 5e-05,
     },
 }
 
@@ -109,6 +92,7 @@ def test_ground_station():
     fourth = gs_data["four"]
     fourth.pop("range_noise_km")
     fourth.pop("doppler_noise_km_s")
+    fourth["frame"] = Frame(399, 399)
     # Using the constructor
     unique = GroundStation(**fourth)
     assert unique.name == "Maspalomas, ES"
@@ -137,14 +121,14 @@ def test_gauss_markov():
     assert unpkl.tau == Unit.Day * 1.0
 
 
-def test_flt_resid():
+def test_resid_reject_crit():
     # Test pickle
-    flt = FltResid(min_accepted=10, num_sigmas=3.0)
-    unplkd = pickle.loads(pickle.dumps(flt))
-    assert unplkd == flt
+    crit = ResidRejectCrit(num_sigmas=3.0)
+    unplkd = pickle.loads(pickle.dumps(crit))
+    assert unplkd == crit
 
 
 if __name__ == "__main__":
     test_ground_station()
     test_gauss_markov()
-    test_flt_resid()
+    test_resid_reject_crit()